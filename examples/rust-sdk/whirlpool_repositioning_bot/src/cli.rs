@@ -1,60 +1,56 @@
-use clap::Parser;
-
-use crate::utils::PriorityFeeTier;
+use clap::{Parser, ValueEnum};
+use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 pub struct Args {
     #[arg(
-        short = 'p',
+        short = 'c',
         long,
-        help = "The position mint address to monitor and rebalance."
+        default_value = "bot.toml",
+        help = "Path to the TOML config file with the positions to manage.\n"
     )]
-    pub position_mint_address: String,
+    pub config: PathBuf,
 
     #[arg(
-        short = 't',
+        short = 'm',
         long,
-        default_value_t = 100,
-        help = "Threshold for repositioning in bps.\n"
+        value_enum,
+        default_value_t = Mode::Live,
+        help = "Mode of operation. Options:\n  \
+                - `live`: Send repositioning transactions\n  \
+                - `dry-run`: Simulate repositioning transactions without sending them\n  \
+                - `paper`: Replay a recorded price feed against simulated positions\n"
     )]
-    pub threshold: u16,
+    pub mode: Mode,
 
     #[arg(
-        short = 'i',
         long,
-        default_value_t = 60,
-        help = "Time interval for checking in seconds.\n"
+        required_if_eq("mode", "paper"),
+        help = "Recorded price feed to replay in paper mode.\n"
     )]
-    pub interval: u64,
+    pub price_feed: Option<PathBuf>,
 
     #[arg(
-        short = 'f',
         long,
-        value_enum,
-        default_value_t = PriorityFeeTier::Medium,
-        help = "Priority fee tier for transaction processing based on recently paid priority fees. Options:\n  \
-                - `none`: No priority fee\n  \
-                - `low`: Lower 25th quartile prioritization fee\n  \
-                - `medium`: Median prioritization fee\n  \
-                - `high`: Upper 80th quartile prioritization fee\n  \
-                - `turbo`: Upper 99th quartile prioritization fee\n"
+        help = "Append every observed pool price to this file in live and dry-run mode.\n"
     )]
-    pub priority_fee_tier: PriorityFeeTier,
+    pub record_price_feed: Option<PathBuf>,
+}
 
-    #[arg(
-        short = 'm',
-        long,
-        default_value_t = 10_000_000,
-        help = "Maximum total priority fee in lamports.\n"
-    )]
-    pub max_priority_fee_lamports: u64,
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum Mode {
+    Live,
+    DryRun,
+    Paper,
+}
 
-    #[arg(
-        short = 's',
-        long,
-        default_value_t = 100,
-        help = "Slippage tolerance in basis points (bps).\n"
-    )]
-    pub slippage_tolerance_bps: u16,
+impl Mode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Mode::Live => "live",
+            Mode::DryRun => "dry_run",
+            Mode::Paper => "paper",
+        }
+    }
 }
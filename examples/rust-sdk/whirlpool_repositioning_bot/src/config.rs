@@ -0,0 +1,195 @@
+use serde::{Deserialize, Deserializer};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fs;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::utils::PriorityFeeTier;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BotConfig {
+    /// Time interval between checks in seconds.
+    #[serde(default = "default_interval_secs")]
+    pub interval_secs: u64,
+
+    /// Path of the SQLite database that holds position state and repositioning history.
+    #[serde(default = "default_database_path")]
+    pub database_path: PathBuf,
+
+    /// Default slippage tolerance in bps. Can be overridden per position.
+    #[serde(default = "default_slippage_tolerance_bps")]
+    pub slippage_tolerance_bps: u16,
+
+    #[serde(default)]
+    pub priority_fee_tier: PriorityFeeTier,
+
+    /// Maximum total priority fee in lamports.
+    #[serde(default = "default_max_priority_fee_lamports")]
+    pub max_priority_fee_lamports: u64,
+
+    pub metrics: Option<MetricsConfig>,
+
+    pub positions: Vec<PositionConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MetricsConfig {
+    /// The address the Prometheus metrics endpoint listens on, e.g. `127.0.0.1:9100`.
+    pub listen_address: SocketAddr,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PositionConfig {
+    /// A unique name for the position. State and history are stored under this name, because the
+    /// position mint changes every time the position is repositioned.
+    pub name: String,
+
+    /// The mint of the position when the bot first starts managing it.
+    #[serde(deserialize_with = "deserialize_pubkey")]
+    pub position_mint: Pubkey,
+
+    pub strategy: StrategyConfig,
+
+    pub slippage_tolerance_bps: Option<u16>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StrategyConfig {
+    /// Repositions when the price deviates from the center of the range by `threshold_bps`.
+    /// The new range keeps the width of the current range unless `width_ticks` is set.
+    FixedWidth {
+        threshold_bps: u16,
+        width_ticks: Option<u32>,
+    },
+
+    /// Repositions when the price deviates from the center of the range by `threshold_bps`.
+    /// The width of the new range grows with the volatility accumulator of the pool's oracle.
+    VolatilityScaled {
+        threshold_bps: u16,
+        base_width_ticks: u32,
+        volatility_multiplier: f64,
+        min_width_ticks: u32,
+        max_width_ticks: u32,
+    },
+
+    /// Repositions only when the price leaves the range.
+    OutOfRange { width_ticks: Option<u32> },
+}
+
+impl BotConfig {
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let config_string = fs::read_to_string(path)
+            .map_err(|err| format!("Failed to read config file {}: {}", path.display(), err))?;
+        let config: BotConfig = toml::from_str(&config_string)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), Box<dyn Error>> {
+        if self.positions.is_empty() {
+            return Err("Config must contain at least one position.".into());
+        }
+
+        let mut names = HashSet::new();
+        let mut position_mints = HashSet::new();
+        for position in &self.positions {
+            if !names.insert(position.name.as_str()) {
+                return Err(format!("Duplicate position name: {}", position.name).into());
+            }
+            if !position_mints.insert(position.position_mint) {
+                return Err(format!("Duplicate position mint: {}", position.position_mint).into());
+            }
+            position
+                .strategy
+                .validate()
+                .map_err(|err| format!("Invalid strategy for {}: {}", position.name, err))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl PositionConfig {
+    pub fn slippage_tolerance_bps(&self, config: &BotConfig) -> u16 {
+        self.slippage_tolerance_bps
+            .unwrap_or(config.slippage_tolerance_bps)
+    }
+}
+
+impl StrategyConfig {
+    fn validate(&self) -> Result<(), String> {
+        match self {
+            StrategyConfig::FixedWidth {
+                threshold_bps,
+                width_ticks,
+            } => {
+                if *threshold_bps == 0 {
+                    return Err("threshold_bps must be greater than 0".to_string());
+                }
+                if *width_ticks == Some(0) {
+                    return Err("width_ticks must be greater than 0".to_string());
+                }
+            }
+            StrategyConfig::VolatilityScaled {
+                threshold_bps,
+                base_width_ticks,
+                volatility_multiplier,
+                min_width_ticks,
+                max_width_ticks,
+            } => {
+                if *threshold_bps == 0 {
+                    return Err("threshold_bps must be greater than 0".to_string());
+                }
+                if *min_width_ticks == 0 || min_width_ticks > max_width_ticks {
+                    return Err(
+                        "min_width_ticks must be greater than 0 and at most max_width_ticks"
+                            .to_string(),
+                    );
+                }
+                if base_width_ticks > max_width_ticks {
+                    return Err("base_width_ticks must be at most max_width_ticks".to_string());
+                }
+                if !volatility_multiplier.is_finite() || *volatility_multiplier < 0.0 {
+                    return Err("volatility_multiplier must be a non-negative number".to_string());
+                }
+            }
+            StrategyConfig::OutOfRange { width_ticks } => {
+                if *width_ticks == Some(0) {
+                    return Err("width_ticks must be greater than 0".to_string());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn deserialize_pubkey<'de, D>(deserializer: D) -> Result<Pubkey, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    Pubkey::from_str(&value).map_err(serde::de::Error::custom)
+}
+
+fn default_interval_secs() -> u64 {
+    60
+}
+
+fn default_database_path() -> PathBuf {
+    PathBuf::from("repositioning_bot.sqlite")
+}
+
+fn default_slippage_tolerance_bps() -> u16 {
+    100
+}
+
+fn default_max_priority_fee_lamports() -> u64 {
+    10_000_000
+}
@@ -1,37 +1,43 @@
 mod cli;
+mod config;
+mod metrics;
+mod paper;
 mod position_manager;
+mod price_feed;
+mod store;
+mod strategy;
 mod utils;
 mod wallet;
 
 use clap::Parser;
-use cli::Args;
+use cli::{Args, Mode};
 use colored::Colorize;
+use config::BotConfig;
 use dotenv::dotenv;
+use metrics::{serve_metrics, Metrics};
 use orca_whirlpools::{set_funder, set_whirlpools_config_address, WhirlpoolsConfigInput};
-use orca_whirlpools_client::get_position_address;
-use position_manager::run_position_manager;
+use paper::{run_paper_trading, PoolParameters};
+use position_manager::{unix_timestamp, ManagedPosition, PositionManager};
+use price_feed::{read_price_feed, PriceFeedRecorder};
 use solana_client::nonblocking::rpc_client::RpcClient;
-use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
 use std::env;
-use std::str::FromStr;
+use std::sync::Arc;
+use store::Store;
 use tokio::time::{sleep, Duration};
-use utils::{
-    display_position_balances, display_wallet_balances, fetch_mint, fetch_position, fetch_whirlpool,
-};
+use utils::{display_position_balances, display_wallet_balances, fetch_whirlpool_with_oracle};
 
 #[tokio::main]
 async fn main() {
     let args = Args::parse();
     dotenv().ok();
+    let config = BotConfig::load(&args.config).expect("Failed to load config.");
     let rpc_url = env::var("RPC_URL").unwrap();
     let rpc = RpcClient::new(rpc_url.to_string());
     set_whirlpools_config_address(WhirlpoolsConfigInput::SolanaMainnet)
         .expect("Failed to set Whirlpools config address for specified network.");
-    let wallet = wallet::load_wallet();
-    set_funder(wallet.pubkey()).expect("Failed to set funder address.");
-
-    let position_mint_address = Pubkey::from_str(&args.position_mint_address)
-        .expect("Invalid position mint address provided.");
+    let store = Store::open(&config.database_path).expect("Failed to open database.");
+    let metrics = Arc::new(Metrics::new().expect("Failed to create metrics."));
 
     println!(
         "\n\
@@ -41,61 +47,145 @@ async fn main() {
     );
     println!("Configuration:");
     println!(
-        "  Position Mint Address: {}\n  Threshold: {:.2}bps\n  Interval: {} seconds\n  Priority Fee Tier: {:?}\n  Slippage tolerance bps: {:?}\n",
-        args.position_mint_address, args.threshold, args.interval, args.priority_fee_tier, args.slippage_tolerance_bps
+        "  Mode: {:?}\n  Positions: {}\n  Interval: {} seconds\n  Priority Fee Tier: {:?}\n  Slippage tolerance bps: {:?}\n  Database: {}\n",
+        args.mode,
+        config.positions.len(),
+        config.interval_secs,
+        config.priority_fee_tier,
+        config.slippage_tolerance_bps,
+        config.database_path.display()
     );
 
+    if let Some(metrics_config) = &config.metrics {
+        println!(
+            "Serving metrics on http://{}/metrics\n",
+            metrics_config.listen_address
+        );
+        let metrics = metrics.clone();
+        let listen_address = metrics_config.listen_address;
+        tokio::spawn(async move {
+            if let Err(err) = serve_metrics(metrics, listen_address).await {
+                eprintln!("{}", format!("Metrics server error: {}", err).red());
+            }
+        });
+    }
+
     println!("-------------------------------------\n");
 
-    let (position_address, _) =
-        get_position_address(&position_mint_address).expect("Failed to derive position address.");
-    let mut position = fetch_position(&rpc, &position_address)
-        .await
-        .expect("Failed to fetch position data.");
-    let whirlpool = fetch_whirlpool(&rpc, &position.whirlpool)
-        .await
-        .expect("Failed to fetch Whirlpool data.");
-    let token_mint_a = fetch_mint(&rpc, &whirlpool.token_mint_a)
-        .await
-        .expect("Failed to fetch Token Mint A data.");
-    let token_mint_b = fetch_mint(&rpc, &whirlpool.token_mint_b)
-        .await
-        .expect("Failed to fetch Token Mint B data.");
+    let mut positions = Vec::with_capacity(config.positions.len());
+    for position_config in &config.positions {
+        let managed = ManagedPosition::load(&rpc, &store, position_config)
+            .await
+            .expect("Failed to load position.");
+        println!(
+            "  {} ({}): strategy {}, position mint {}",
+            position_config.name,
+            managed.position.whirlpool,
+            managed.strategy.name(),
+            managed.position.position_mint
+        );
+        positions.push(managed);
+    }
+    println!();
 
-    display_wallet_balances(
-        &rpc,
-        &wallet.pubkey(),
-        &whirlpool.token_mint_a,
-        &whirlpool.token_mint_b,
-    )
-    .await
-    .expect("Failed to display wallet balances.");
+    if args.mode == Mode::Paper {
+        let price_feed_path = args
+            .price_feed
+            .as_ref()
+            .expect("Paper mode requires a price feed.");
+        let observations = read_price_feed(price_feed_path).expect("Failed to read price feed.");
 
-    display_position_balances(
-        &rpc,
-        &position,
-        &whirlpool.token_mint_a,
-        &whirlpool.token_mint_b,
-        token_mint_a.decimals,
-        token_mint_b.decimals,
-        args.slippage_tolerance_bps,
-    )
-    .await
-    .expect("Failed to display position balances.");
+        let mut pool_parameters = HashMap::new();
+        for managed in &positions {
+            if pool_parameters.contains_key(&managed.position.whirlpool) {
+                continue;
+            }
+            let (whirlpool, oracle) =
+                fetch_whirlpool_with_oracle(&rpc, &managed.position.whirlpool)
+                    .await
+                    .expect("Failed to fetch Whirlpool data.");
+            pool_parameters.insert(
+                managed.position.whirlpool,
+                PoolParameters {
+                    tick_spacing: whirlpool.tick_spacing,
+                    tick_group_size: oracle.map(|x| x.adaptive_fee_constants.tick_group_size),
+                },
+            );
+        }
 
-    loop {
-        if let Err(err) = run_position_manager(
+        run_paper_trading(
+            &positions,
+            &pool_parameters,
+            &observations,
+            &store,
+            &metrics,
+        )
+        .expect("Paper trading failed.");
+        return;
+    }
+
+    let wallet = wallet::load_wallet();
+    set_funder(wallet.pubkey()).expect("Failed to set funder address.");
+
+    for managed in &positions {
+        if args.mode == Mode::Live {
+            store
+                .save_position(
+                    &managed.config.name,
+                    args.mode,
+                    &managed.state(),
+                    unix_timestamp(),
+                )
+                .expect("Failed to save position state.");
+        }
+
+        println!("{}:", managed.config.name.bold());
+        let whirlpool = fetch_whirlpool_with_oracle(&rpc, &managed.position.whirlpool)
+            .await
+            .expect("Failed to fetch Whirlpool data.")
+            .0;
+        display_wallet_balances(
+            &rpc,
+            &wallet.pubkey(),
+            &whirlpool.token_mint_a,
+            &whirlpool.token_mint_b,
+        )
+        .await
+        .expect("Failed to display wallet balances.");
+
+        display_position_balances(
             &rpc,
-            &args,
-            &wallet,
-            &mut position,
-            &token_mint_a,
-            &token_mint_b,
+            &managed.position,
+            &whirlpool.token_mint_a,
+            &whirlpool.token_mint_b,
+            managed.token_mint_a.decimals,
+            managed.token_mint_b.decimals,
+            managed.config.slippage_tolerance_bps(&config),
         )
         .await
-        {
-            eprintln!("{}", format!("Error: {}", err).to_string().red());
+        .expect("Failed to display position balances.");
+    }
+
+    let mut manager = PositionManager {
+        rpc: &rpc,
+        config: &config,
+        mode: args.mode,
+        wallet: wallet.as_ref(),
+        store: &store,
+        metrics: &metrics,
+        price_feed_recorder: args
+            .record_price_feed
+            .as_ref()
+            .map(|path| PriceFeedRecorder::open(path).expect("Failed to open price feed file.")),
+    };
+
+    loop {
+        for managed in positions.iter_mut() {
+            if let Err(err) = manager.run_position_manager(managed).await {
+                manager.metrics.observe_error(&managed.config.name);
+                eprintln!("{}", format!("Error: {}", err).to_string().red());
+            }
         }
-        sleep(Duration::from_secs(args.interval)).await;
+        sleep(Duration::from_secs(config.interval_secs)).await;
     }
 }
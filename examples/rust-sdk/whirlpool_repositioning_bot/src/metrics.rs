@@ -0,0 +1,165 @@
+use prometheus::{Encoder, GaugeVec, IntCounterVec, IntGaugeVec, Opts, Registry, TextEncoder};
+use std::error::Error;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+use crate::cli::Mode;
+use crate::strategy::StrategyInput;
+
+pub struct Metrics {
+    registry: Registry,
+    checks: IntCounterVec,
+    errors: IntCounterVec,
+    repositions: IntCounterVec,
+    price: GaugeVec,
+    deviation_bps: GaugeVec,
+    volatility_ticks: GaugeVec,
+    in_range: IntGaugeVec,
+    tick_lower_index: IntGaugeVec,
+    tick_upper_index: IntGaugeVec,
+}
+
+impl Metrics {
+    pub fn new() -> Result<Self, prometheus::Error> {
+        let registry = Registry::new_custom(Some("whirlpool_repositioning_bot".to_string()), None)?;
+
+        let checks = IntCounterVec::new(
+            Opts::new("checks_total", "Number of position checks"),
+            &["position"],
+        )?;
+        let errors = IntCounterVec::new(
+            Opts::new("errors_total", "Number of failed position checks"),
+            &["position"],
+        )?;
+        let repositions = IntCounterVec::new(
+            Opts::new("repositions_total", "Number of repositions"),
+            &["position", "mode"],
+        )?;
+        let price = GaugeVec::new(
+            Opts::new("price", "Current pool price adjusted for token decimals"),
+            &["position"],
+        )?;
+        let deviation_bps = GaugeVec::new(
+            Opts::new(
+                "deviation_bps",
+                "Distance between the pool price and the center of the position range in bps",
+            ),
+            &["position"],
+        )?;
+        let volatility_ticks = GaugeVec::new(
+            Opts::new(
+                "volatility_ticks",
+                "Volatility accumulator of the pool oracle converted to ticks",
+            ),
+            &["position"],
+        )?;
+        let in_range = IntGaugeVec::new(
+            Opts::new(
+                "in_range",
+                "Whether the pool price is inside the position range",
+            ),
+            &["position"],
+        )?;
+        let tick_lower_index = IntGaugeVec::new(
+            Opts::new("tick_lower_index", "Lower tick index of the position"),
+            &["position"],
+        )?;
+        let tick_upper_index = IntGaugeVec::new(
+            Opts::new("tick_upper_index", "Upper tick index of the position"),
+            &["position"],
+        )?;
+
+        registry.register(Box::new(checks.clone()))?;
+        registry.register(Box::new(errors.clone()))?;
+        registry.register(Box::new(repositions.clone()))?;
+        registry.register(Box::new(price.clone()))?;
+        registry.register(Box::new(deviation_bps.clone()))?;
+        registry.register(Box::new(volatility_ticks.clone()))?;
+        registry.register(Box::new(in_range.clone()))?;
+        registry.register(Box::new(tick_lower_index.clone()))?;
+        registry.register(Box::new(tick_upper_index.clone()))?;
+
+        Ok(Self {
+            registry,
+            checks,
+            errors,
+            repositions,
+            price,
+            deviation_bps,
+            volatility_ticks,
+            in_range,
+            tick_lower_index,
+            tick_upper_index,
+        })
+    }
+
+    pub fn observe_check(&self, name: &str, input: &StrategyInput, price: f64) {
+        self.checks.with_label_values(&[name]).inc();
+        self.price.with_label_values(&[name]).set(price);
+        self.deviation_bps
+            .with_label_values(&[name])
+            .set(input.deviation_bps());
+        self.volatility_ticks
+            .with_label_values(&[name])
+            .set(input.volatility_ticks());
+        self.in_range
+            .with_label_values(&[name])
+            .set(input.is_in_range() as i64);
+        self.tick_lower_index
+            .with_label_values(&[name])
+            .set(input.tick_lower_index as i64);
+        self.tick_upper_index
+            .with_label_values(&[name])
+            .set(input.tick_upper_index as i64);
+    }
+
+    pub fn observe_error(&self, name: &str) {
+        self.errors.with_label_values(&[name]).inc();
+    }
+
+    pub fn observe_reposition(&self, name: &str, mode: Mode) {
+        self.repositions
+            .with_label_values(&[name, mode.as_str()])
+            .inc();
+    }
+
+    fn encode(&self) -> Result<Vec<u8>, prometheus::Error> {
+        let mut buffer = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+        Ok(buffer)
+    }
+}
+
+/// Serves the metrics in the Prometheus text format on every path of `listen_address`.
+pub async fn serve_metrics(
+    metrics: Arc<Metrics>,
+    listen_address: SocketAddr,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let listener = TcpListener::bind(listen_address).await?;
+    loop {
+        let (mut stream, _) = listener.accept().await?;
+        let metrics = metrics.clone();
+        tokio::spawn(async move {
+            let mut request = [0u8; 1024];
+            if stream.read(&mut request).await.is_err() {
+                return;
+            }
+            let response = match metrics.encode() {
+                Ok(body) => {
+                    let mut response = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                        TextEncoder::new().format_type(),
+                        body.len()
+                    )
+                    .into_bytes();
+                    response.extend(body);
+                    response
+                }
+                Err(_) => b"HTTP/1.1 500 Internal Server Error\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_vec(),
+            };
+            let _ = stream.write_all(&response).await;
+        });
+    }
+}
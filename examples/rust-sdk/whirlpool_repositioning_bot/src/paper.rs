@@ -0,0 +1,171 @@
+use colored::Colorize;
+use orca_whirlpools_core::{sqrt_price_to_price, tick_index_to_price};
+use std::collections::HashMap;
+use std::error::Error;
+
+use crate::{
+    cli::Mode,
+    metrics::Metrics,
+    position_manager::ManagedPosition,
+    price_feed::PriceObservation,
+    store::{PositionState, RepositionRecord, Store},
+    strategy::{Decision, StrategyInput, Volatility},
+};
+
+struct PaperPosition<'a> {
+    managed: &'a ManagedPosition,
+    state: PositionState,
+    tick_spacing: u16,
+    tick_group_size: Option<u16>,
+    observations: u64,
+    observations_in_range: u64,
+    repositions: u64,
+}
+
+/// Pool parameters that are not part of a price observation.
+pub struct PoolParameters {
+    pub tick_spacing: u16,
+    pub tick_group_size: Option<u16>,
+}
+
+/// Replays a recorded price feed against simulated positions.
+///
+/// Simulated positions start from the current on-chain range of each managed position, or from the
+/// range left by a previous paper run, and never send transactions.
+pub fn run_paper_trading(
+    positions: &[ManagedPosition],
+    pool_parameters: &HashMap<solana_sdk::pubkey::Pubkey, PoolParameters>,
+    observations: &[PriceObservation],
+    store: &Store,
+    metrics: &Metrics,
+) -> Result<(), Box<dyn Error>> {
+    let mut paper_positions = positions
+        .iter()
+        .map(|managed| {
+            let parameters = pool_parameters
+                .get(&managed.position.whirlpool)
+                .ok_or(format!(
+                    "Missing pool parameters for {}",
+                    managed.config.name
+                ))?;
+            let state = store
+                .load_position(&managed.config.name, Mode::Paper)?
+                .unwrap_or(managed.state());
+            Ok(PaperPosition {
+                managed,
+                state,
+                tick_spacing: parameters.tick_spacing,
+                tick_group_size: parameters.tick_group_size,
+                observations: 0,
+                observations_in_range: 0,
+                repositions: 0,
+            })
+        })
+        .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+
+    for observation in observations {
+        for paper in paper_positions
+            .iter_mut()
+            .filter(|paper| paper.state.whirlpool == observation.whirlpool)
+        {
+            let managed = paper.managed;
+            let input = StrategyInput {
+                sqrt_price: observation.sqrt_price,
+                tick_current_index: observation.tick_current_index,
+                tick_spacing: paper.tick_spacing,
+                tick_lower_index: paper.state.tick_lower_index,
+                tick_upper_index: paper.state.tick_upper_index,
+                volatility: observation
+                    .volatility_accumulator
+                    .zip(paper.tick_group_size)
+                    .map(|(volatility_accumulator, tick_group_size)| Volatility {
+                        volatility_accumulator,
+                        tick_group_size,
+                    }),
+            };
+            let price = sqrt_price_to_price(
+                observation.sqrt_price.into(),
+                managed.token_mint_a.decimals,
+                managed.token_mint_b.decimals,
+            );
+            metrics.observe_check(&managed.config.name, &input, price);
+
+            paper.observations += 1;
+            if input.is_in_range() {
+                paper.observations_in_range += 1;
+            }
+
+            let Decision::Reposition {
+                tick_lower_index,
+                tick_upper_index,
+                reason,
+            } = managed.strategy.evaluate(&input)
+            else {
+                continue;
+            };
+
+            let new_state = PositionState {
+                tick_lower_index,
+                tick_upper_index,
+                ..paper.state.clone()
+            };
+            store.record_reposition(&RepositionRecord {
+                name: &managed.config.name,
+                mode: Mode::Paper,
+                unix_timestamp: observation.unix_timestamp,
+                strategy: managed.strategy.name(),
+                reason: &reason,
+                price,
+                old_position: &paper.state,
+                new_position: &new_state,
+                signature: None,
+            })?;
+            store.save_position(
+                &managed.config.name,
+                Mode::Paper,
+                &new_state,
+                observation.unix_timestamp,
+            )?;
+            metrics.observe_reposition(&managed.config.name, Mode::Paper);
+
+            println!(
+                "[{}] {}: {}. Repositioning to [{:.6}, {:.6}].",
+                observation.unix_timestamp,
+                managed.config.name.bold(),
+                reason,
+                tick_index_to_price(
+                    tick_lower_index,
+                    managed.token_mint_a.decimals,
+                    managed.token_mint_b.decimals
+                ),
+                tick_index_to_price(
+                    tick_upper_index,
+                    managed.token_mint_a.decimals,
+                    managed.token_mint_b.decimals
+                ),
+            );
+            paper.state = new_state;
+            paper.repositions += 1;
+        }
+    }
+
+    println!("\nPaper trading summary:");
+    for paper in &paper_positions {
+        let time_in_range = if paper.observations == 0 {
+            0.0
+        } else {
+            paper.observations_in_range as f64 * 100.0 / paper.observations as f64
+        };
+        println!(
+            "  {}: {} observations, {} repositions, {:.2}% of observations in range, final range [{}, {}]",
+            paper.managed.config.name,
+            paper.observations,
+            paper.repositions,
+            time_in_range,
+            paper.state.tick_lower_index,
+            paper.state.tick_upper_index,
+        );
+    }
+
+    Ok(())
+}
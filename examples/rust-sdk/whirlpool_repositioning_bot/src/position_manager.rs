@@ -1,96 +1,227 @@
 use crate::{
-    cli::Args,
+    cli::Mode,
+    config::{BotConfig, PositionConfig},
+    metrics::Metrics,
+    price_feed::{PriceFeedRecorder, PriceObservation},
+    store::{PositionState, RepositionRecord, Store},
+    strategy::{Decision, RepositionStrategy, StrategyInput, Volatility},
     utils::{
-        display_position_balances, display_wallet_balances, fetch_position, fetch_whirlpool,
-        send_transaction,
+        display_position_balances, display_wallet_balances, fetch_mint, fetch_position,
+        fetch_whirlpool_with_oracle, send_transaction, simulate_transaction,
     },
 };
 use colored::Colorize;
 use orca_whirlpools::{
     close_position_instructions, open_position_instructions, IncreaseLiquidityParam,
 };
-use orca_whirlpools_client::{get_position_address, Position};
-use orca_whirlpools_core::{sqrt_price_to_price, tick_index_to_price};
+use orca_whirlpools_client::{get_position_address, Oracle, Position, Whirlpool};
+use orca_whirlpools_core::{
+    increase_liquidity_quote_a, increase_liquidity_quote_b, sqrt_price_to_price,
+    tick_index_to_price,
+};
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::signer::Signer;
 use spl_token_2022::state::Mint;
+use std::error::Error;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-pub async fn run_position_manager(
-    rpc: &RpcClient,
-    args: &Args,
-    wallet: &Box<dyn Signer>,
-    position: &mut Position,
-    token_mint_a: &Mint,
-    token_mint_b: &Mint,
-) -> Result<(), Box<dyn std::error::Error>> {
-    println!("Checking position.");
-
-    let whirlpool_address = position.whirlpool;
-    let whirlpool = fetch_whirlpool(rpc, &whirlpool_address)
-        .await
-        .map_err(|_| "Failed to fetch Whirlpool data.")?;
-
-    let current_price = sqrt_price_to_price(
-        whirlpool.sqrt_price,
-        token_mint_a.decimals,
-        token_mint_b.decimals,
-    );
-    let position_lower_price = tick_index_to_price(
-        position.tick_lower_index,
-        token_mint_a.decimals,
-        token_mint_b.decimals,
-    );
-    let position_upper_price = tick_index_to_price(
-        position.tick_upper_index,
-        token_mint_a.decimals,
-        token_mint_b.decimals,
-    );
-    let position_center_price = (position_lower_price + position_upper_price) / 2.0;
-
-    let deviation_amount = if current_price > position_center_price {
-        current_price - position_center_price
-    } else {
-        position_center_price - current_price
-    };
-    let deviation_bps = (deviation_amount * 10000.0) / (position_center_price);
+/// A position managed by the bot together with the strategy that decides when to reposition it.
+pub struct ManagedPosition {
+    pub config: PositionConfig,
+    pub strategy: Box<dyn RepositionStrategy>,
+    pub position: Position,
+    pub token_mint_a: Mint,
+    pub token_mint_b: Mint,
+}
+
+impl ManagedPosition {
+    /// Loads the position the config entry currently points to. If the bot has repositioned the
+    /// position in live mode before, the latest position mint is read from the store instead of
+    /// the config. Dry-run and paper mode start from the same on-chain position.
+    pub async fn load(
+        rpc: &RpcClient,
+        store: &Store,
+        config: &PositionConfig,
+    ) -> Result<Self, Box<dyn Error>> {
+        let position_mint = store
+            .load_position(&config.name, Mode::Live)?
+            .map(|state| state.position_mint)
+            .unwrap_or(config.position_mint);
+        let (position_address, _) = get_position_address(&position_mint)?;
+        let position = fetch_position(rpc, &position_address)
+            .await
+            .map_err(|_| format!("Failed to fetch position {}.", position_mint))?;
+        let (whirlpool, _) = fetch_whirlpool_with_oracle(rpc, &position.whirlpool).await?;
+        let token_mint_a = fetch_mint(rpc, &whirlpool.token_mint_a).await?;
+        let token_mint_b = fetch_mint(rpc, &whirlpool.token_mint_b).await?;
+
+        Ok(Self {
+            config: config.clone(),
+            strategy: (&config.strategy).into(),
+            position,
+            token_mint_a,
+            token_mint_b,
+        })
+    }
+
+    pub fn state(&self) -> PositionState {
+        PositionState {
+            position_mint: self.position.position_mint,
+            whirlpool: self.position.whirlpool,
+            tick_lower_index: self.position.tick_lower_index,
+            tick_upper_index: self.position.tick_upper_index,
+            liquidity: self.position.liquidity,
+        }
+    }
+
+    fn price(&self, sqrt_price: u128) -> f64 {
+        sqrt_price_to_price(
+            sqrt_price.into(),
+            self.token_mint_a.decimals,
+            self.token_mint_b.decimals,
+        )
+    }
+}
+
+pub struct PositionManager<'a> {
+    pub rpc: &'a RpcClient,
+    pub config: &'a BotConfig,
+    pub mode: Mode,
+    pub wallet: &'a dyn Signer,
+    pub store: &'a Store,
+    pub metrics: &'a Metrics,
+    pub price_feed_recorder: Option<PriceFeedRecorder>,
+}
+
+pub fn strategy_input(
+    whirlpool: &Whirlpool,
+    oracle: Option<&Oracle>,
+    position: &Position,
+) -> StrategyInput {
+    StrategyInput {
+        sqrt_price: whirlpool.sqrt_price,
+        tick_current_index: whirlpool.tick_current_index,
+        tick_spacing: whirlpool.tick_spacing,
+        tick_lower_index: position.tick_lower_index,
+        tick_upper_index: position.tick_upper_index,
+        volatility: oracle.map(|oracle| Volatility {
+            volatility_accumulator: oracle.adaptive_fee_variables.volatility_accumulator,
+            tick_group_size: oracle.adaptive_fee_constants.tick_group_size,
+        }),
+    }
+}
+
+pub fn unix_timestamp() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|x| x.as_secs() as i64)
+        .unwrap_or_default()
+}
 
-    println!("Current pool price: {:.6}", current_price);
-    println!(
-        "Position price range: [{:.6}, {:.6}]",
-        position_lower_price, position_upper_price
-    );
-    println!("Position center price: {:.6}", position_center_price);
-    println!("Price deviation from center: {:.2} bps", deviation_bps);
+impl PositionManager<'_> {
+    pub async fn run_position_manager(
+        &mut self,
+        managed: &mut ManagedPosition,
+    ) -> Result<(), Box<dyn Error>> {
+        println!("Checking position {}.", managed.config.name.bold());
 
-    if deviation_bps as u16 >= args.threshold {
+        let whirlpool_address = managed.position.whirlpool;
+        let (whirlpool, oracle) = fetch_whirlpool_with_oracle(self.rpc, &whirlpool_address)
+            .await
+            .map_err(|_| "Failed to fetch Whirlpool data.")?;
+
+        let input = strategy_input(&whirlpool, oracle.as_ref(), &managed.position);
+        let current_price = managed.price(whirlpool.sqrt_price);
+        self.metrics
+            .observe_check(&managed.config.name, &input, current_price);
+        if let Some(recorder) = self.price_feed_recorder.as_mut() {
+            recorder.record(&PriceObservation {
+                unix_timestamp: unix_timestamp(),
+                whirlpool: whirlpool_address,
+                sqrt_price: whirlpool.sqrt_price,
+                tick_current_index: whirlpool.tick_current_index,
+                volatility_accumulator: input.volatility.map(|x| x.volatility_accumulator),
+            })?;
+        }
+
+        println!("Current pool price: {:.6}", current_price);
+        println!(
+            "Position price range: [{:.6}, {:.6}]",
+            tick_index_to_price(
+                managed.position.tick_lower_index,
+                managed.token_mint_a.decimals,
+                managed.token_mint_b.decimals,
+            ),
+            tick_index_to_price(
+                managed.position.tick_upper_index,
+                managed.token_mint_a.decimals,
+                managed.token_mint_b.decimals,
+            ),
+        );
+        println!(
+            "Price deviation from center: {:.2} bps",
+            input.deviation_bps()
+        );
+
+        let (tick_lower_index, tick_upper_index, reason) = match managed.strategy.evaluate(&input) {
+            Decision::Hold { reason } => {
+                println!(
+                    "{}",
+                    format!("{}. No repositioning needed.", reason).green()
+                );
+                return Ok(());
+            }
+            Decision::Reposition {
+                tick_lower_index,
+                tick_upper_index,
+                reason,
+            } => (tick_lower_index, tick_upper_index, reason),
+        };
         println!(
             "{}",
-            "Deviation exceeds threshold. Rebalancing position."
-                .to_string()
-                .yellow()
+            format!(
+                "{}. Repositioning to [{}, {}].",
+                reason, tick_lower_index, tick_upper_index
+            )
+            .yellow()
         );
 
+        let slippage_tolerance_bps = managed.config.slippage_tolerance_bps(self.config);
         let close_position_instructions = close_position_instructions(
-            rpc,
-            position.position_mint,
-            Some(args.slippage_tolerance_bps),
+            self.rpc,
+            managed.position.position_mint,
+            Some(slippage_tolerance_bps),
             None,
         )
         .await
         .map_err(|_| "Failed to generate close position instructions.")?;
 
-        let new_lower_price = current_price - (position_upper_price - position_lower_price) / 2.0;
-        let new_upper_price = current_price + (position_upper_price - position_lower_price) / 2.0;
+        // TODO: support transfer fees when sizing the new position
+        let liquidity = get_reposition_liquidity(
+            close_position_instructions.quote.token_min_a
+                + close_position_instructions.fees_quote.fee_owed_a,
+            close_position_instructions.quote.token_min_b
+                + close_position_instructions.fees_quote.fee_owed_b,
+            whirlpool.sqrt_price,
+            tick_lower_index,
+            tick_upper_index,
+        )?;
 
-        let increase_liquidity_param =
-            IncreaseLiquidityParam::Liquidity(close_position_instructions.quote.liquidity_delta);
         let open_position_instructions = open_position_instructions(
-            rpc,
+            self.rpc,
             whirlpool_address,
-            new_lower_price,
-            new_upper_price,
-            increase_liquidity_param,
-            Some(100),
+            tick_index_to_price(
+                tick_lower_index,
+                managed.token_mint_a.decimals,
+                managed.token_mint_b.decimals,
+            ),
+            tick_index_to_price(
+                tick_upper_index,
+                managed.token_mint_a.decimals,
+                managed.token_mint_b.decimals,
+            ),
+            IncreaseLiquidityParam::Liquidity(liquidity),
+            Some(slippage_tolerance_bps),
             None,
         )
         .await
@@ -100,7 +231,7 @@ pub async fn run_position_manager(
         all_instructions.extend(close_position_instructions.instructions);
         all_instructions.extend(open_position_instructions.instructions);
 
-        let mut signers: Vec<&dyn Signer> = vec![wallet.as_ref()];
+        let mut signers: Vec<&dyn Signer> = vec![];
         signers.extend(
             open_position_instructions
                 .additional_signers
@@ -114,30 +245,83 @@ pub async fn run_position_manager(
                 .map(|kp| kp as &dyn Signer),
         );
 
-        let signature = send_transaction(
-            rpc,
-            wallet.as_ref(),
-            &whirlpool_address,
-            all_instructions,
-            signers,
-            args.priority_fee_tier,
-            args.max_priority_fee_lamports,
-        )
-        .await
-        .map_err(|_| "Failed to send rebalancing transaction.")?;
-        println!("Rebalancing transaction signature: {}", signature);
+        let old_state = managed.state();
+        let signature = match self.mode {
+            Mode::DryRun => {
+                let (units_consumed, _) =
+                    simulate_transaction(self.rpc, self.wallet, all_instructions, signers).await?;
+                println!(
+                    "{}",
+                    format!(
+                        "Dry run: repositioning simulated successfully ({} compute units).",
+                        units_consumed.unwrap_or_default()
+                    )
+                    .cyan()
+                );
+                None
+            }
+            Mode::Live => {
+                let mut all_signers: Vec<&dyn Signer> = vec![self.wallet];
+                all_signers.extend(signers);
+                let signature = send_transaction(
+                    self.rpc,
+                    self.wallet,
+                    &whirlpool_address,
+                    all_instructions,
+                    all_signers,
+                    self.config.priority_fee_tier,
+                    self.config.max_priority_fee_lamports,
+                )
+                .await
+                .map_err(|_| "Failed to send rebalancing transaction.")?;
+                println!("Rebalancing transaction signature: {}", signature);
+                Some(signature.to_string())
+            }
+            Mode::Paper => return Err("Paper mode does not send transactions.".into()),
+        };
+
+        let new_state = PositionState {
+            position_mint: open_position_instructions.position_mint,
+            whirlpool: whirlpool_address,
+            tick_lower_index,
+            tick_upper_index,
+            liquidity,
+        };
+        self.store.record_reposition(&RepositionRecord {
+            name: &managed.config.name,
+            mode: self.mode,
+            unix_timestamp: unix_timestamp(),
+            strategy: managed.strategy.name(),
+            reason: &reason,
+            price: current_price,
+            old_position: &old_state,
+            new_position: &new_state,
+            signature,
+        })?;
+        self.metrics
+            .observe_reposition(&managed.config.name, self.mode);
+
+        if self.mode == Mode::DryRun {
+            return Ok(());
+        }
 
         let position_mint_address = open_position_instructions.position_mint;
         println!("New position mint address: {}", position_mint_address);
         let (position_address, _) = get_position_address(&position_mint_address)
             .map_err(|_| "Failed to derive new position address.")?;
-        *position = fetch_position(rpc, &position_address)
+        managed.position = fetch_position(self.rpc, &position_address)
             .await
             .map_err(|_| "Failed to fetch new position data.")?;
+        self.store.save_position(
+            &managed.config.name,
+            self.mode,
+            &managed.state(),
+            unix_timestamp(),
+        )?;
 
         display_wallet_balances(
-            rpc,
-            &wallet.pubkey(),
+            self.rpc,
+            &self.wallet.pubkey(),
             &whirlpool.token_mint_a,
             &whirlpool.token_mint_b,
         )
@@ -145,23 +329,58 @@ pub async fn run_position_manager(
         .map_err(|_| "Failed to display wallet balances.")?;
 
         display_position_balances(
-            rpc,
-            position,
+            self.rpc,
+            &managed.position,
             &whirlpool.token_mint_a,
             &whirlpool.token_mint_b,
-            token_mint_a.decimals,
-            token_mint_b.decimals,
-            args.slippage_tolerance_bps,
+            managed.token_mint_a.decimals,
+            managed.token_mint_b.decimals,
+            slippage_tolerance_bps,
         )
         .await
         .map_err(|_| "Failed to display position balances.")?;
-    } else {
-        println!(
-            "{}",
-            "Current price is within range. No repositioning needed."
-                .to_string()
-                .green()
-        );
+
+        Ok(())
+    }
+}
+
+/// The largest liquidity the new range can hold with the tokens withdrawn from the old position.
+fn get_reposition_liquidity(
+    token_amount_a: u64,
+    token_amount_b: u64,
+    sqrt_price: u128,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+) -> Result<u128, Box<dyn Error>> {
+    let liquidity_a = increase_liquidity_quote_a(
+        token_amount_a,
+        0,
+        sqrt_price.into(),
+        tick_lower_index,
+        tick_upper_index,
+        None,
+        None,
+    )?
+    .liquidity_delta;
+    let liquidity_b = increase_liquidity_quote_b(
+        token_amount_b,
+        0,
+        sqrt_price.into(),
+        tick_lower_index,
+        tick_upper_index,
+        None,
+        None,
+    )?
+    .liquidity_delta;
+
+    // A position that was out of range only returns one token. The wallet has to provide the
+    // other token in that case, as the bot does not swap.
+    let liquidity = match (liquidity_a, liquidity_b) {
+        (0, liquidity) | (liquidity, 0) => liquidity,
+        (liquidity_a, liquidity_b) => liquidity_a.min(liquidity_b),
+    };
+    if liquidity == 0 {
+        return Err("Withdrawn tokens are not enough to open the new position.".into());
     }
-    Ok(())
+    Ok(liquidity)
 }
@@ -0,0 +1,90 @@
+use solana_sdk::pubkey::Pubkey;
+use std::error::Error;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+const PRICE_FEED_HEADER: &str =
+    "unix_timestamp,whirlpool,sqrt_price,tick_current_index,volatility_accumulator";
+
+/// A single observation of a pool. A price feed is a CSV file of observations, which is written
+/// with `--record-price-feed` in live or dry-run mode and replayed in paper mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriceObservation {
+    pub unix_timestamp: i64,
+    pub whirlpool: Pubkey,
+    pub sqrt_price: u128,
+    pub tick_current_index: i32,
+    pub volatility_accumulator: Option<u32>,
+}
+
+/// Reads a recorded price feed ordered by timestamp.
+pub fn read_price_feed(path: &Path) -> Result<Vec<PriceObservation>, Box<dyn Error>> {
+    let content = fs::read_to_string(path)
+        .map_err(|err| format!("Failed to read price feed {}: {}", path.display(), err))?;
+
+    let mut observations = Vec::new();
+    for (line_number, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line == PRICE_FEED_HEADER {
+            continue;
+        }
+        let observation = parse_observation(line)
+            .map_err(|err| format!("Invalid price feed line {}: {}", line_number + 1, err))?;
+        observations.push(observation);
+    }
+    observations.sort_by_key(|observation| observation.unix_timestamp);
+
+    Ok(observations)
+}
+
+fn parse_observation(line: &str) -> Result<PriceObservation, Box<dyn Error>> {
+    let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+    if fields.len() != 5 {
+        return Err(format!("expected 5 fields, found {}", fields.len()).into());
+    }
+
+    Ok(PriceObservation {
+        unix_timestamp: fields[0].parse()?,
+        whirlpool: Pubkey::from_str(fields[1])?,
+        sqrt_price: fields[2].parse()?,
+        tick_current_index: fields[3].parse()?,
+        volatility_accumulator: if fields[4].is_empty() {
+            None
+        } else {
+            Some(fields[4].parse()?)
+        },
+    })
+}
+
+pub struct PriceFeedRecorder {
+    file: File,
+}
+
+impl PriceFeedRecorder {
+    pub fn open(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let is_new = fs::metadata(path).map(|x| x.len() == 0).unwrap_or(true);
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        if is_new {
+            writeln!(file, "{}", PRICE_FEED_HEADER)?;
+        }
+        Ok(Self { file })
+    }
+
+    pub fn record(&mut self, observation: &PriceObservation) -> Result<(), Box<dyn Error>> {
+        writeln!(
+            self.file,
+            "{},{},{},{},{}",
+            observation.unix_timestamp,
+            observation.whirlpool,
+            observation.sqrt_price,
+            observation.tick_current_index,
+            observation
+                .volatility_accumulator
+                .map(|x| x.to_string())
+                .unwrap_or_default()
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,172 @@
+use rusqlite::{params, Connection, OptionalExtension};
+use solana_sdk::pubkey::Pubkey;
+use std::error::Error;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::cli::Mode;
+
+/// The position a configured entry currently points to. Repositioning closes the position and opens
+/// a new one, so the mint in the config file is only used until the first reposition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PositionState {
+    pub position_mint: Pubkey,
+    pub whirlpool: Pubkey,
+    pub tick_lower_index: i32,
+    pub tick_upper_index: i32,
+    pub liquidity: u128,
+}
+
+#[derive(Debug, Clone)]
+pub struct RepositionRecord<'a> {
+    pub name: &'a str,
+    pub mode: Mode,
+    pub unix_timestamp: i64,
+    pub strategy: &'a str,
+    pub reason: &'a str,
+    pub price: f64,
+    pub old_position: &'a PositionState,
+    pub new_position: &'a PositionState,
+    pub signature: Option<String>,
+}
+
+pub struct Store {
+    connection: Connection,
+}
+
+impl Store {
+    pub fn open(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let connection = Connection::open(path)?;
+        connection.execute_batch(
+            "CREATE TABLE IF NOT EXISTS positions (
+                name TEXT NOT NULL,
+                mode TEXT NOT NULL,
+                position_mint TEXT NOT NULL,
+                whirlpool TEXT NOT NULL,
+                tick_lower_index INTEGER NOT NULL,
+                tick_upper_index INTEGER NOT NULL,
+                liquidity TEXT NOT NULL,
+                updated_at INTEGER NOT NULL,
+                PRIMARY KEY (name, mode)
+            );
+            CREATE TABLE IF NOT EXISTS history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                mode TEXT NOT NULL,
+                timestamp INTEGER NOT NULL,
+                strategy TEXT NOT NULL,
+                reason TEXT NOT NULL,
+                price REAL NOT NULL,
+                old_position_mint TEXT NOT NULL,
+                old_tick_lower_index INTEGER NOT NULL,
+                old_tick_upper_index INTEGER NOT NULL,
+                old_liquidity TEXT NOT NULL,
+                new_position_mint TEXT NOT NULL,
+                new_tick_lower_index INTEGER NOT NULL,
+                new_tick_upper_index INTEGER NOT NULL,
+                new_liquidity TEXT NOT NULL,
+                signature TEXT
+            );
+            CREATE INDEX IF NOT EXISTS history_name_mode ON history (name, mode);",
+        )?;
+        Ok(Self { connection })
+    }
+
+    pub fn load_position(
+        &self,
+        name: &str,
+        mode: Mode,
+    ) -> Result<Option<PositionState>, Box<dyn Error>> {
+        let row = self
+            .connection
+            .query_row(
+                "SELECT position_mint, whirlpool, tick_lower_index, tick_upper_index, liquidity
+                FROM positions WHERE name = ?1 AND mode = ?2",
+                params![name, mode.as_str()],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, i32>(2)?,
+                        row.get::<_, i32>(3)?,
+                        row.get::<_, String>(4)?,
+                    ))
+                },
+            )
+            .optional()?;
+
+        row.map(
+            |(position_mint, whirlpool, tick_lower_index, tick_upper_index, liquidity)| -> Result<PositionState, Box<dyn Error>> {
+                Ok(PositionState {
+                    position_mint: Pubkey::from_str(&position_mint)?,
+                    whirlpool: Pubkey::from_str(&whirlpool)?,
+                    tick_lower_index,
+                    tick_upper_index,
+                    liquidity: liquidity.parse()?,
+                })
+            },
+        )
+        .transpose()
+    }
+
+    pub fn save_position(
+        &self,
+        name: &str,
+        mode: Mode,
+        position: &PositionState,
+        unix_timestamp: i64,
+    ) -> Result<(), Box<dyn Error>> {
+        self.connection.execute(
+            "INSERT INTO positions
+                (name, mode, position_mint, whirlpool, tick_lower_index, tick_upper_index, liquidity, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            ON CONFLICT (name, mode) DO UPDATE SET
+                position_mint = excluded.position_mint,
+                whirlpool = excluded.whirlpool,
+                tick_lower_index = excluded.tick_lower_index,
+                tick_upper_index = excluded.tick_upper_index,
+                liquidity = excluded.liquidity,
+                updated_at = excluded.updated_at",
+            params![
+                name,
+                mode.as_str(),
+                position.position_mint.to_string(),
+                position.whirlpool.to_string(),
+                position.tick_lower_index,
+                position.tick_upper_index,
+                position.liquidity.to_string(),
+                unix_timestamp,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn record_reposition(&self, record: &RepositionRecord) -> Result<(), Box<dyn Error>> {
+        self.connection.execute(
+            "INSERT INTO history (
+                name, mode, timestamp, strategy, reason, price,
+                old_position_mint, old_tick_lower_index, old_tick_upper_index, old_liquidity,
+                new_position_mint, new_tick_lower_index, new_tick_upper_index, new_liquidity,
+                signature
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+            params![
+                record.name,
+                record.mode.as_str(),
+                record.unix_timestamp,
+                record.strategy,
+                record.reason,
+                record.price,
+                record.old_position.position_mint.to_string(),
+                record.old_position.tick_lower_index,
+                record.old_position.tick_upper_index,
+                record.old_position.liquidity.to_string(),
+                record.new_position.position_mint.to_string(),
+                record.new_position.tick_lower_index,
+                record.new_position.tick_upper_index,
+                record.new_position.liquidity.to_string(),
+                record.signature,
+            ],
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,353 @@
+use orca_whirlpools_core::{
+    get_full_range_tick_indexes, get_initializable_tick_index, sqrt_price_to_price,
+    tick_index_to_price, VOLATILITY_ACCUMULATOR_SCALE_FACTOR,
+};
+
+use crate::config::StrategyConfig;
+
+/// The state of a pool and a position that a strategy bases its decision on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StrategyInput {
+    pub sqrt_price: u128,
+    pub tick_current_index: i32,
+    pub tick_spacing: u16,
+    pub tick_lower_index: i32,
+    pub tick_upper_index: i32,
+    /// The volatility of the pool. Only pools with adaptive fees have an oracle that tracks it.
+    pub volatility: Option<Volatility>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Volatility {
+    pub volatility_accumulator: u32,
+    pub tick_group_size: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Decision {
+    Hold {
+        reason: String,
+    },
+    Reposition {
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+        reason: String,
+    },
+}
+
+pub trait RepositionStrategy: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn evaluate(&self, input: &StrategyInput) -> Decision;
+}
+
+impl StrategyInput {
+    pub fn is_in_range(&self) -> bool {
+        self.tick_current_index >= self.tick_lower_index
+            && self.tick_current_index < self.tick_upper_index
+    }
+
+    pub fn width_ticks(&self) -> u32 {
+        self.tick_upper_index.abs_diff(self.tick_lower_index)
+    }
+
+    /// The distance between the current price and the center of the position's price range in bps.
+    /// The decimals of the tokens cancel out, so raw prices are used.
+    pub fn deviation_bps(&self) -> f64 {
+        let current_price = sqrt_price_to_price(self.sqrt_price.into(), 0, 0);
+        let lower_price = tick_index_to_price(self.tick_lower_index, 0, 0);
+        let upper_price = tick_index_to_price(self.tick_upper_index, 0, 0);
+        let center_price = (lower_price + upper_price) / 2.0;
+        (current_price - center_price).abs() * 10000.0 / center_price
+    }
+
+    /// The volatility accumulator converted to ticks. Zero for pools without an oracle.
+    pub fn volatility_ticks(&self) -> f64 {
+        self.volatility
+            .map(|volatility| {
+                volatility.volatility_accumulator as f64
+                    / VOLATILITY_ACCUMULATOR_SCALE_FACTOR as f64
+                    * volatility.tick_group_size as f64
+            })
+            .unwrap_or(0.0)
+    }
+}
+
+/// Builds a range of roughly `width_ticks` centered on the current tick, rounded outward to
+/// initializable tick indexes and clamped to the full range of the pool.
+pub fn centered_range(tick_current_index: i32, tick_spacing: u16, width_ticks: u32) -> (i32, i32) {
+    let full_range = get_full_range_tick_indexes(tick_spacing);
+    let half_width = (width_ticks / 2) as i32;
+
+    let tick_lower_index = get_initializable_tick_index(
+        tick_current_index.saturating_sub(half_width),
+        tick_spacing,
+        Some(false),
+    )
+    .max(full_range.tick_lower_index);
+    let mut tick_upper_index = get_initializable_tick_index(
+        tick_current_index.saturating_add(half_width),
+        tick_spacing,
+        Some(true),
+    )
+    .min(full_range.tick_upper_index);
+
+    if tick_upper_index <= tick_lower_index {
+        tick_upper_index = tick_lower_index + tick_spacing as i32;
+    }
+
+    (tick_lower_index, tick_upper_index)
+}
+
+fn reposition(input: &StrategyInput, width_ticks: u32, reason: String) -> Decision {
+    let (tick_lower_index, tick_upper_index) =
+        centered_range(input.tick_current_index, input.tick_spacing, width_ticks);
+    if tick_lower_index == input.tick_lower_index && tick_upper_index == input.tick_upper_index {
+        return Decision::Hold {
+            reason: format!("{}, but the position is already centered", reason),
+        };
+    }
+    Decision::Reposition {
+        tick_lower_index,
+        tick_upper_index,
+        reason,
+    }
+}
+
+pub struct FixedWidthStrategy {
+    pub threshold_bps: u16,
+    pub width_ticks: Option<u32>,
+}
+
+impl RepositionStrategy for FixedWidthStrategy {
+    fn name(&self) -> &'static str {
+        "fixed_width"
+    }
+
+    fn evaluate(&self, input: &StrategyInput) -> Decision {
+        let deviation_bps = input.deviation_bps();
+        if deviation_bps < self.threshold_bps as f64 {
+            return Decision::Hold {
+                reason: format!(
+                    "Deviation of {:.2} bps is below the threshold of {} bps",
+                    deviation_bps, self.threshold_bps
+                ),
+            };
+        }
+
+        let width_ticks = self.width_ticks.unwrap_or(input.width_ticks());
+        reposition(
+            input,
+            width_ticks,
+            format!(
+                "Deviation of {:.2} bps exceeds the threshold of {} bps",
+                deviation_bps, self.threshold_bps
+            ),
+        )
+    }
+}
+
+pub struct VolatilityScaledStrategy {
+    pub threshold_bps: u16,
+    pub base_width_ticks: u32,
+    pub volatility_multiplier: f64,
+    pub min_width_ticks: u32,
+    pub max_width_ticks: u32,
+}
+
+impl VolatilityScaledStrategy {
+    pub fn target_width_ticks(&self, input: &StrategyInput) -> u32 {
+        let width =
+            self.base_width_ticks as f64 + input.volatility_ticks() * self.volatility_multiplier;
+        (width.round() as u32).clamp(self.min_width_ticks, self.max_width_ticks)
+    }
+}
+
+impl RepositionStrategy for VolatilityScaledStrategy {
+    fn name(&self) -> &'static str {
+        "volatility_scaled"
+    }
+
+    fn evaluate(&self, input: &StrategyInput) -> Decision {
+        let deviation_bps = input.deviation_bps();
+        if deviation_bps < self.threshold_bps as f64 && input.is_in_range() {
+            return Decision::Hold {
+                reason: format!(
+                    "Deviation of {:.2} bps is below the threshold of {} bps",
+                    deviation_bps, self.threshold_bps
+                ),
+            };
+        }
+
+        let width_ticks = self.target_width_ticks(input);
+        reposition(
+            input,
+            width_ticks,
+            format!(
+                "Deviation of {:.2} bps exceeds the threshold of {} bps (volatility {:.1} ticks, width {} ticks)",
+                deviation_bps,
+                self.threshold_bps,
+                input.volatility_ticks(),
+                width_ticks
+            ),
+        )
+    }
+}
+
+pub struct OutOfRangeStrategy {
+    pub width_ticks: Option<u32>,
+}
+
+impl RepositionStrategy for OutOfRangeStrategy {
+    fn name(&self) -> &'static str {
+        "out_of_range"
+    }
+
+    fn evaluate(&self, input: &StrategyInput) -> Decision {
+        if input.is_in_range() {
+            return Decision::Hold {
+                reason: "Current price is within range".to_string(),
+            };
+        }
+
+        let width_ticks = self.width_ticks.unwrap_or(input.width_ticks());
+        reposition(
+            input,
+            width_ticks,
+            format!(
+                "Current tick {} is outside of the range [{}, {})",
+                input.tick_current_index, input.tick_lower_index, input.tick_upper_index
+            ),
+        )
+    }
+}
+
+impl From<&StrategyConfig> for Box<dyn RepositionStrategy> {
+    fn from(config: &StrategyConfig) -> Self {
+        match config {
+            StrategyConfig::FixedWidth {
+                threshold_bps,
+                width_ticks,
+            } => Box::new(FixedWidthStrategy {
+                threshold_bps: *threshold_bps,
+                width_ticks: *width_ticks,
+            }),
+            StrategyConfig::VolatilityScaled {
+                threshold_bps,
+                base_width_ticks,
+                volatility_multiplier,
+                min_width_ticks,
+                max_width_ticks,
+            } => Box::new(VolatilityScaledStrategy {
+                threshold_bps: *threshold_bps,
+                base_width_ticks: *base_width_ticks,
+                volatility_multiplier: *volatility_multiplier,
+                min_width_ticks: *min_width_ticks,
+                max_width_ticks: *max_width_ticks,
+            }),
+            StrategyConfig::OutOfRange { width_ticks } => Box::new(OutOfRangeStrategy {
+                width_ticks: *width_ticks,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use orca_whirlpools_core::tick_index_to_sqrt_price;
+
+    fn input(tick_current_index: i32, volatility_accumulator: Option<u32>) -> StrategyInput {
+        StrategyInput {
+            sqrt_price: tick_index_to_sqrt_price(tick_current_index).into(),
+            tick_current_index,
+            tick_spacing: 64,
+            tick_lower_index: -640,
+            tick_upper_index: 640,
+            volatility: volatility_accumulator.map(|volatility_accumulator| Volatility {
+                volatility_accumulator,
+                tick_group_size: 64,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_centered_range() {
+        assert_eq!(centered_range(0, 64, 1280), (-640, 640));
+        assert_eq!(centered_range(100, 64, 1280), (-576, 768));
+        assert_eq!(centered_range(0, 64, 10), (-64, 64));
+        assert_eq!(centered_range(443600, 64, 1280), (442944, 443584));
+    }
+
+    #[test]
+    fn test_fixed_width_holds_below_threshold() {
+        let strategy = FixedWidthStrategy {
+            threshold_bps: 100,
+            width_ticks: None,
+        };
+        assert!(matches!(
+            strategy.evaluate(&input(50, None)),
+            Decision::Hold { .. }
+        ));
+    }
+
+    #[test]
+    fn test_fixed_width_keeps_width() {
+        let strategy = FixedWidthStrategy {
+            threshold_bps: 100,
+            width_ticks: None,
+        };
+        let decision = strategy.evaluate(&input(500, None));
+        assert!(matches!(
+            decision,
+            Decision::Reposition {
+                tick_lower_index: -192,
+                tick_upper_index: 1152,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_volatility_scaled_widens_with_volatility() {
+        let strategy = VolatilityScaledStrategy {
+            threshold_bps: 100,
+            base_width_ticks: 1280,
+            volatility_multiplier: 2.0,
+            min_width_ticks: 640,
+            max_width_ticks: 12800,
+        };
+        assert_eq!(strategy.target_width_ticks(&input(0, None)), 1280);
+        assert_eq!(strategy.target_width_ticks(&input(0, Some(50_000))), 1920);
+        assert_eq!(
+            strategy.target_width_ticks(&input(0, Some(10_000_000))),
+            12800
+        );
+        let decision = strategy.evaluate(&input(500, Some(50_000)));
+        assert!(matches!(
+            decision,
+            Decision::Reposition {
+                tick_lower_index: -512,
+                tick_upper_index: 1472,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_out_of_range_only_triggers_outside_range() {
+        let strategy = OutOfRangeStrategy { width_ticks: None };
+        assert!(matches!(
+            strategy.evaluate(&input(639, None)),
+            Decision::Hold { .. }
+        ));
+        assert!(matches!(
+            strategy.evaluate(&input(640, None)),
+            Decision::Reposition {
+                tick_lower_index: 0,
+                tick_upper_index: 1280,
+                ..
+            }
+        ));
+    }
+}
@@ -1,15 +1,17 @@
 use clap::ValueEnum;
 use orca_whirlpools::close_position_instructions;
-use orca_whirlpools_client::{Position, Whirlpool};
+use orca_whirlpools_client::{get_oracle_address, Oracle, Position, Whirlpool};
+use serde::Deserialize;
 use solana_client::nonblocking::rpc_client::RpcClient;
-use solana_client::rpc_config::RpcSendTransactionConfig;
+use solana_client::rpc_config::{RpcSendTransactionConfig, RpcSimulateTransactionConfig};
 use solana_sdk::commitment_config::CommitmentLevel;
 use solana_sdk::compute_budget::ComputeBudgetInstruction;
 use solana_sdk::{
-    message::Message, program_pack::Pack, pubkey::Pubkey, signature::Signature, signer::Signer,
+    message::Message, pubkey::Pubkey, signature::Signature, signer::Signer,
     transaction::Transaction,
 };
 use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_token_2022::extension::StateWithExtensions;
 use spl_token_2022::state::Mint;
 use std::error::Error;
 use tokio::time::{sleep, Duration, Instant};
@@ -112,16 +114,38 @@ pub async fn fetch_whirlpool(
     Ok(whirlpool)
 }
 
+/// Fetches a whirlpool and its oracle. Only pools with adaptive fees have an oracle.
+pub async fn fetch_whirlpool_with_oracle(
+    rpc: &RpcClient,
+    whirlpool_address: &Pubkey,
+) -> Result<(Whirlpool, Option<Oracle>), Box<dyn Error>> {
+    let oracle_address = get_oracle_address(whirlpool_address)?.0;
+    let accounts = rpc
+        .get_multiple_accounts(&[*whirlpool_address, oracle_address])
+        .await?;
+    let whirlpool_account = accounts[0]
+        .as_ref()
+        .ok_or(format!("Whirlpool {} not found", whirlpool_address))?;
+    let whirlpool = Whirlpool::from_bytes(&whirlpool_account.data)?;
+    let oracle = accounts[1]
+        .as_ref()
+        .map(|x| Oracle::from_bytes(&x.data))
+        .transpose()?;
+    Ok((whirlpool, oracle))
+}
+
 pub async fn fetch_mint(rpc: &RpcClient, mint_address: &Pubkey) -> Result<Mint, Box<dyn Error>> {
     let mint_account = rpc.get_account(mint_address).await?;
-    let mint = Mint::unpack(&mint_account.data)?;
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_account.data)?.base;
     Ok(mint)
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Default, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum PriorityFeeTier {
     None,
     Low,
+    #[default]
     Medium,
     High,
     Turbo,
@@ -191,6 +215,39 @@ pub async fn send_transaction(
     })
 }
 
+/// Simulates the instructions in a single transaction without sending it.
+///
+/// Returns the compute units consumed and the logs of the simulation.
+pub async fn simulate_transaction(
+    rpc: &RpcClient,
+    wallet: &dyn Signer,
+    instructions: Vec<solana_sdk::instruction::Instruction>,
+    additional_signers: Vec<&dyn Signer>,
+) -> Result<(Option<u64>, Vec<String>), Box<dyn Error>> {
+    let recent_blockhash = rpc.get_latest_blockhash().await?;
+    let message = Message::new(&instructions, Some(&wallet.pubkey()));
+    let mut all_signers = vec![wallet];
+    all_signers.extend(additional_signers);
+    let transaction = Transaction::new(&all_signers, message, recent_blockhash);
+
+    let simulation = rpc
+        .simulate_transaction_with_config(
+            &transaction,
+            RpcSimulateTransactionConfig {
+                sig_verify: false,
+                replace_recent_blockhash: true,
+                ..Default::default()
+            },
+        )
+        .await?
+        .value;
+    let logs = simulation.logs.unwrap_or_default();
+    if let Some(err) = simulation.err {
+        return Err(format!("Simulation failed: {}\n{}", err, logs.join("\n")).into());
+    }
+    Ok((simulation.units_consumed, logs))
+}
+
 async fn get_compute_unit_instructions(
     rpc: &RpcClient,
     instructions: &[solana_sdk::instruction::Instruction],
@@ -3,6 +3,8 @@ import type { Instruction } from "@orca-so/common-sdk";
 import type { PublicKey } from "@solana/web3.js";
 import type { Whirlpool } from "../artifacts/whirlpool";
 
+import { PDAUtil } from "../utils/public";
+
 /**
  * Parameters to set fee rate for a Whirlpool.
  *
@@ -21,6 +23,7 @@ export type SetFeeRateParams = {
 
 /**
  * Sets the fee rate for a Whirlpool.
+ * Any scheduled fee rate change of the Whirlpool is cleared.
  * Only the current fee authority has permission to invoke this instruction.
  *
  * #### Special Errors
@@ -37,11 +40,14 @@ export function setFeeRateIx(
 ): Instruction {
   const { whirlpoolsConfig, whirlpool, feeAuthority, feeRate } = params;
 
+  const oracle = PDAUtil.getOracle(program.programId, whirlpool).publicKey;
+
   const ix = program.instruction.setFeeRate(feeRate, {
     accounts: {
       whirlpoolsConfig,
       whirlpool,
       feeAuthority,
+      oracle,
     },
   });
 
@@ -0,0 +1,78 @@
+import type { Program } from "@coral-xyz/anchor";
+import type { Instruction } from "@orca-so/common-sdk";
+import type { PublicKey } from "@solana/web3.js";
+import type { Whirlpool } from "../../artifacts/whirlpool";
+import { PDAUtil } from "../../utils/public";
+
+/**
+ * Parameters to delete a TokenBadge account and record the deletion in its TokenBadgeHistory.
+ *
+ * @category Instruction Types
+ * @param whirlpoolsConfig - PublicKey for the whirlpools config account
+ * @param whirlpoolsConfigExtension - PublicKey for the whirlpools config extension account
+ * @param tokenBadgeAuthority - PublicKey for the token badge authority
+ * @param tokenMint - Publickey for the mint for which the TokenBadge have been initialized
+ * @param tokenBadge - PublicKey for the token badge account to be deleted
+ * @param receiver - PublicKey for the account that will receive the rent
+ * @param reason - The reason for the deletion, recorded in the TokenBadgeHistory (at most 64 bytes)
+ * @param tokenBadgeHistory - PublicKey for the TokenBadgeHistory account. Derived from the config and mint if omitted.
+ */
+export type DeleteTokenBadgeWithHistoryParams = {
+  whirlpoolsConfig: PublicKey;
+  whirlpoolsConfigExtension: PublicKey;
+  tokenBadgeAuthority: PublicKey;
+  tokenMint: PublicKey;
+  tokenBadge: PublicKey;
+  receiver: PublicKey;
+  reason: string;
+  tokenBadgeHistory?: PublicKey;
+};
+
+/**
+ * Deletes a TokenBadge account. Its TokenBadgeHistory is kept and records the deletion with the reason.
+ *
+ * @category Instructions
+ * @param program - program object containing services required to generate the instruction
+ * @param params - DeleteTokenBadgeWithHistoryParams object
+ * @returns - Instruction to perform the action.
+ */
+export function deleteTokenBadgeWithHistoryIx(
+  program: Program<Whirlpool>,
+  params: DeleteTokenBadgeWithHistoryParams,
+): Instruction {
+  const {
+    whirlpoolsConfig,
+    whirlpoolsConfigExtension,
+    tokenBadgeAuthority,
+    tokenMint,
+    tokenBadge,
+    receiver,
+    reason,
+  } = params;
+
+  const tokenBadgeHistory =
+    params.tokenBadgeHistory ??
+    PDAUtil.getTokenBadgeHistory(
+      program.programId,
+      whirlpoolsConfig,
+      tokenMint,
+    ).publicKey;
+
+  const ix = program.instruction.deleteTokenBadgeWithHistory(reason, {
+    accounts: {
+      whirlpoolsConfig,
+      whirlpoolsConfigExtension,
+      tokenBadgeAuthority,
+      tokenMint,
+      tokenBadge,
+      tokenBadgeHistory,
+      receiver,
+    },
+  });
+
+  return {
+    instructions: [ix],
+    cleanupInstructions: [],
+    signers: [],
+  };
+}
@@ -14,3 +14,7 @@ export * from "./set-config-extension-authority-ix";
 export * from "./set-token-badge-authority-ix";
 export * from "./initialize-token-badge-ix";
 export * from "./delete-token-badge-ix";
+export * from "./initialize-token-badge-history-ix";
+export * from "./initialize-token-badge-with-history-ix";
+export * from "./delete-token-badge-with-history-ix";
+export * from "./set-token-badge-attributes-ix";
@@ -0,0 +1,70 @@
+import type { Program } from "@coral-xyz/anchor";
+import type { Instruction, PDA } from "@orca-so/common-sdk";
+import type { PublicKey } from "@solana/web3.js";
+import { SystemProgram } from "@solana/web3.js";
+import type { Whirlpool } from "../../artifacts/whirlpool";
+
+/**
+ * Parameters to initialize a TokenBadgeHistory account.
+ *
+ * @category Instruction Types
+ * @param whirlpoolsConfig - The public key for the WhirlpoolsConfig
+ * @param whirlpoolsConfigExtension - The public key for the WhirlpoolsConfigExtension
+ * @param tokenBadgeAuthority - The public key for the tokenBadgeAuthority
+ * @param tokenMint - The public key for the mint for which the TokenBadgeHistory is being initialized
+ * @param tokenBadge - The public key for the TokenBadge account, which may not be initialized yet
+ * @param tokenBadgeHistoryPda - The PDA for the TokenBadgeHistory account
+ * @param funder - The account that would fund the creation of this account
+ */
+export type InitializeTokenBadgeHistoryParams = {
+  whirlpoolsConfig: PublicKey;
+  whirlpoolsConfigExtension: PublicKey;
+  tokenBadgeAuthority: PublicKey;
+  tokenMint: PublicKey;
+  tokenBadge: PublicKey;
+  tokenBadgeHistoryPda: PDA;
+  funder: PublicKey;
+};
+
+/**
+ * Initializes a TokenBadgeHistory account. The history is never closed and records the
+ * approvals, attribute versions and deletions of the TokenBadge of the mint.
+ *
+ * @category Instructions
+ * @param program - program object containing services required to generate the instruction
+ * @param params - InitializeTokenBadgeHistoryParams object
+ * @returns - Instruction to perform the action.
+ */
+export function initializeTokenBadgeHistoryIx(
+  program: Program<Whirlpool>,
+  params: InitializeTokenBadgeHistoryParams,
+): Instruction {
+  const {
+    whirlpoolsConfig,
+    whirlpoolsConfigExtension,
+    tokenBadgeAuthority,
+    tokenMint,
+    tokenBadge,
+    tokenBadgeHistoryPda,
+    funder,
+  } = params;
+
+  const ix = program.instruction.initializeTokenBadgeHistory({
+    accounts: {
+      whirlpoolsConfig,
+      whirlpoolsConfigExtension,
+      tokenBadgeAuthority,
+      tokenMint,
+      tokenBadge,
+      tokenBadgeHistory: tokenBadgeHistoryPda.publicKey,
+      funder,
+      systemProgram: SystemProgram.programId,
+    },
+  });
+
+  return {
+    instructions: [ix],
+    cleanupInstructions: [],
+    signers: [],
+  };
+}
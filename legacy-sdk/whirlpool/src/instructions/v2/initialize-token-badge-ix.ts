@@ -14,6 +14,7 @@ import type { Whirlpool } from "../../artifacts/whirlpool";
  * @param tokenMint - The public key for the mint for which the TokenBadge is being initialized
  * @param tokenBadgePda - The PDA for the TokenBadge account
  * @param funder - The account that would fund the creation of this account
+ * @param tokenBadgeHistory - The public key for the TokenBadgeHistory of the mint. Must be set if the mint has a history.
  */
 export type InitializeTokenBadgeParams = {
   whirlpoolsConfig: PublicKey;
@@ -22,6 +23,7 @@ export type InitializeTokenBadgeParams = {
   tokenMint: PublicKey;
   tokenBadgePda: PDA;
   funder: PublicKey;
+  tokenBadgeHistory?: PublicKey;
 };
 
 /**
@@ -43,6 +45,7 @@ export function initializeTokenBadgeIx(
     tokenMint,
     tokenBadgePda,
     funder,
+    tokenBadgeHistory,
   } = params;
 
   const ix = program.instruction.initializeTokenBadge({
@@ -54,6 +57,7 @@ export function initializeTokenBadgeIx(
       tokenBadge: tokenBadgePda.publicKey,
       funder,
       systemProgram: SystemProgram.programId,
+      tokenBadgeHistory: tokenBadgeHistory ?? null,
     },
   });
 
@@ -0,0 +1,97 @@
+import type { Program } from "@coral-xyz/anchor";
+import type { Instruction, PDA } from "@orca-so/common-sdk";
+import type { PublicKey } from "@solana/web3.js";
+import { SystemProgram } from "@solana/web3.js";
+import type { Whirlpool } from "../../artifacts/whirlpool";
+import { PDAUtil } from "../../utils/public";
+
+/**
+ * Attributes of a TokenBadge. Every update is versioned in the TokenBadgeHistory.
+ *
+ * @category Instruction Types
+ * @param requiresMemo - Whether transfers of the mint require a memo
+ * @param maxTransferFeeBps - The maximum transfer fee of the mint, in basis points
+ */
+export type TokenBadgeAttributes = {
+  requiresMemo: boolean;
+  maxTransferFeeBps: number;
+};
+
+/**
+ * Parameters to initialize a TokenBadge account and record the approval in its TokenBadgeHistory.
+ *
+ * @category Instruction Types
+ * @param whirlpoolsConfig - The public key for the WhirlpoolsConfig
+ * @param whirlpoolsConfigExtension - The public key for the WhirlpoolsConfigExtension
+ * @param tokenBadgeAuthority - The public key for the tokenBadgeAuthority
+ * @param tokenMint - The public key for the mint for which the TokenBadge is being initialized
+ * @param tokenBadgePda - The PDA for the TokenBadge account
+ * @param funder - The account that would fund the creation of this account
+ * @param attributes - The conditions the mint is approved under
+ * @param hookSubmission - The approved HookSubmission in the transfer hook registry, for mints with a transfer hook
+ * @param tokenBadgeHistory - PublicKey for the TokenBadgeHistory account. Derived from the config and mint if omitted.
+ */
+export type InitializeTokenBadgeWithHistoryParams = {
+  whirlpoolsConfig: PublicKey;
+  whirlpoolsConfigExtension: PublicKey;
+  tokenBadgeAuthority: PublicKey;
+  tokenMint: PublicKey;
+  tokenBadgePda: PDA;
+  funder: PublicKey;
+  attributes: TokenBadgeAttributes;
+  hookSubmission?: PublicKey;
+  tokenBadgeHistory?: PublicKey;
+};
+
+/**
+ * Initializes a TokenBadge account and records the approval in its TokenBadgeHistory.
+ * The TokenBadgeHistory has to be initialized first with {@link initializeTokenBadgeHistoryIx}.
+ *
+ * @category Instructions
+ * @param program - program object containing services required to generate the instruction
+ * @param params - InitializeTokenBadgeWithHistoryParams object
+ * @returns - Instruction to perform the action.
+ */
+export function initializeTokenBadgeWithHistoryIx(
+  program: Program<Whirlpool>,
+  params: InitializeTokenBadgeWithHistoryParams,
+): Instruction {
+  const {
+    whirlpoolsConfig,
+    whirlpoolsConfigExtension,
+    tokenBadgeAuthority,
+    tokenMint,
+    tokenBadgePda,
+    funder,
+    attributes,
+    hookSubmission,
+  } = params;
+
+  const tokenBadgeHistory =
+    params.tokenBadgeHistory ??
+    PDAUtil.getTokenBadgeHistory(
+      program.programId,
+      whirlpoolsConfig,
+      tokenMint,
+    ).publicKey;
+
+  const ix = program.instruction.initializeTokenBadgeWithHistory(attributes, {
+    accounts: {
+      whirlpoolsConfig,
+      whirlpoolsConfigExtension,
+      tokenBadgeAuthority,
+      tokenMint,
+      tokenBadge: tokenBadgePda.publicKey,
+      tokenBadgeHistory,
+      hookSubmission: hookSubmission ?? null,
+      funder,
+      systemProgram: SystemProgram.programId,
+    },
+  });
+
+  return {
+    instructions: [ix],
+    cleanupInstructions: [],
+    signers: [],
+  };
+}
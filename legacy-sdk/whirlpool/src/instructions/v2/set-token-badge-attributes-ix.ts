@@ -0,0 +1,78 @@
+import type { Program } from "@coral-xyz/anchor";
+import type { Instruction } from "@orca-so/common-sdk";
+import type { PublicKey } from "@solana/web3.js";
+import type { Whirlpool } from "../../artifacts/whirlpool";
+import { PDAUtil } from "../../utils/public";
+import type {
+  TokenBadgeAttributes,
+} from "./initialize-token-badge-with-history-ix";
+
+/**
+ * Parameters to set the attributes of a TokenBadge account.
+ *
+ * @category Instruction Types
+ * @param whirlpoolsConfig - PublicKey for the whirlpools config account
+ * @param whirlpoolsConfigExtension - PublicKey for the whirlpools config extension account
+ * @param tokenBadgeAuthority - PublicKey for the token badge authority
+ * @param tokenMint - Publickey for the mint for which the TokenBadge have been initialized
+ * @param tokenBadge - PublicKey for the token badge account to be updated
+ * @param attributes - The new attributes of the TokenBadge
+ * @param tokenBadgeHistory - PublicKey for the TokenBadgeHistory account. Derived from the config and mint if omitted.
+ */
+export type SetTokenBadgeAttributesParams = {
+  whirlpoolsConfig: PublicKey;
+  whirlpoolsConfigExtension: PublicKey;
+  tokenBadgeAuthority: PublicKey;
+  tokenMint: PublicKey;
+  tokenBadge: PublicKey;
+  attributes: TokenBadgeAttributes;
+  tokenBadgeHistory?: PublicKey;
+};
+
+/**
+ * Sets the attributes of a TokenBadge account and bumps its attributes version.
+ * The previous attributes stay in the TokenBadgeHistory, which has to be initialized first.
+ *
+ * @category Instructions
+ * @param program - program object containing services required to generate the instruction
+ * @param params - SetTokenBadgeAttributesParams object
+ * @returns - Instruction to perform the action.
+ */
+export function setTokenBadgeAttributesIx(
+  program: Program<Whirlpool>,
+  params: SetTokenBadgeAttributesParams,
+): Instruction {
+  const {
+    whirlpoolsConfig,
+    whirlpoolsConfigExtension,
+    tokenBadgeAuthority,
+    tokenMint,
+    tokenBadge,
+    attributes,
+  } = params;
+
+  const tokenBadgeHistory =
+    params.tokenBadgeHistory ??
+    PDAUtil.getTokenBadgeHistory(
+      program.programId,
+      whirlpoolsConfig,
+      tokenMint,
+    ).publicKey;
+
+  const ix = program.instruction.setTokenBadgeAttributes(attributes, {
+    accounts: {
+      whirlpoolsConfig,
+      whirlpoolsConfigExtension,
+      tokenBadgeAuthority,
+      tokenMint,
+      tokenBadge,
+      tokenBadgeHistory,
+    },
+  });
+
+  return {
+    instructions: [ix],
+    cleanupInstructions: [],
+    signers: [],
+  };
+}
@@ -876,4 +876,32 @@ export class WhirlpoolIx {
   ) {
     return ix.deleteTokenBadgeIx(program, params);
   }
+
+  public static initializeTokenBadgeHistoryIx(
+    program: Program<Whirlpool>,
+    params: ix.InitializeTokenBadgeHistoryParams,
+  ) {
+    return ix.initializeTokenBadgeHistoryIx(program, params);
+  }
+
+  public static initializeTokenBadgeWithHistoryIx(
+    program: Program<Whirlpool>,
+    params: ix.InitializeTokenBadgeWithHistoryParams,
+  ) {
+    return ix.initializeTokenBadgeWithHistoryIx(program, params);
+  }
+
+  public static deleteTokenBadgeWithHistoryIx(
+    program: Program<Whirlpool>,
+    params: ix.DeleteTokenBadgeWithHistoryParams,
+  ) {
+    return ix.deleteTokenBadgeWithHistoryIx(program, params);
+  }
+
+  public static setTokenBadgeAttributesIx(
+    program: Program<Whirlpool>,
+    params: ix.SetTokenBadgeAttributesParams,
+  ) {
+    return ix.setTokenBadgeAttributesIx(program, params);
+  }
 }
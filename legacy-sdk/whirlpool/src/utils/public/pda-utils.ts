@@ -15,6 +15,7 @@ const PDA_POSITION_BUNDLE_SEED = "position_bundle";
 const PDA_BUNDLED_POSITION_SEED = "bundled_position";
 const PDA_CONFIG_EXTENSION_SEED = "config_extension";
 const PDA_TOKEN_BADGE_SEED = "token_badge";
+const PDA_TOKEN_BADGE_HISTORY_SEED = "token_badge_history";
 const PDA_LOCK_CONFIG_SEED = "lock_config";
 
 /**
@@ -277,6 +278,28 @@ export class PDAUtil {
     );
   }
 
+  /**
+   * @category Program Derived Addresses
+   * @param programId
+   * @param whirlpoolsConfigAddress
+   * @param tokenMintKey
+   * @returns
+   */
+  public static getTokenBadgeHistory(
+    programId: PublicKey,
+    whirlpoolsConfigAddress: PublicKey,
+    tokenMintKey: PublicKey,
+  ) {
+    return AddressUtil.findProgramAddress(
+      [
+        Buffer.from(PDA_TOKEN_BADGE_HISTORY_SEED),
+        whirlpoolsConfigAddress.toBuffer(),
+        tokenMintKey.toBuffer(),
+      ],
+      programId,
+    );
+  }
+
   /**
    * @category Program Derived Addresses
    * @param programId
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+pub mod transfer_hook_registry {
+    use super::*;
+    declare_id!("A8UEmdwPDW5pqsU7iMEvwDn2C7fC6bsZGoRceukLzadE");
+}
+
+// Based on the HookSubmission account of the transfer hook registry program
+//
+// discriminator            : 8 bytes
+// program_id               : 32 bytes
+// submitter                : 32 bytes
+// status (ApprovalStatus)  : 1 byte, Approved = 2
+pub const HOOK_SUBMISSION_DISCRIMINATOR: [u8; 8] = [183, 126, 97, 190, 135, 203, 89, 62];
+pub const HOOK_SUBMISSION_PROGRAM_ID_OFFSET: usize = 8;
+pub const HOOK_SUBMISSION_STATUS_OFFSET: usize = 8 + 32 + 32;
+pub const HOOK_SUBMISSION_STATUS_APPROVED: u8 = 2;
@@ -1,5 +1,7 @@
+pub mod hook_registry;
 pub mod nft;
 pub mod test_constants;
 pub mod transfer_memo;
 
+pub use hook_registry::*;
 pub use nft::*;
@@ -3,3 +3,7 @@ pub const TRANSFER_MEMO_COLLECT_FEES: &str = "Orca CollectFees";
 pub const TRANSFER_MEMO_COLLECT_REWARD: &str = "Orca CollectReward";
 pub const TRANSFER_MEMO_DECREASE_LIQUIDITY: &str = "Orca Withdraw";
 pub const TRANSFER_MEMO_SWAP: &str = "Orca Trade";
+pub const TRANSFER_MEMO_FLASH_BORROW: &str = "Orca FlashBorrow";
+pub const TRANSFER_MEMO_COMPOUND_REWARD: &str = "Orca CompoundReward";
+pub const TRANSFER_MEMO_REFERRAL_FEE: &str = "Orca ReferralFee";
+pub const TRANSFER_MEMO_SWEEP_PROTOCOL_FEES: &str = "Orca SweepProtocolFees";
@@ -166,6 +166,72 @@ pub enum ErrorCode {
 
     #[msg("Rent calculation error")]
     RentCalculationError, // 0x17b1 (6065)
+
+    #[msg("Invalid observation cardinality")]
+    InvalidObservationCardinality, // 0x17b2 (6066)
+    #[msg("Oracle observations are not initialized")]
+    OracleObservationsNotInitialized, // 0x17b3 (6067)
+    #[msg("Requested observation is older than the oldest stored observation")]
+    OracleObservationTooOld, // 0x17b4 (6068)
+    #[msg("Invalid TWAP window")]
+    InvalidTwapWindow, // 0x17b5 (6069)
+
+    #[msg("Invalid unlock timestamp")]
+    InvalidUnlockTimestamp, // 0x17b6 (6070)
+    #[msg("Position cannot be unlocked yet")]
+    PositionNotUnlockable, // 0x17b7 (6071)
+
+    #[msg("Range order position must be exactly one tick spacing wide")]
+    InvalidRangeOrderWidth, // 0x17b8 (6072)
+    #[msg("Range order position must be entirely on one side of the current price")]
+    InvalidRangeOrderRange, // 0x17b9 (6073)
+    #[msg("Range order is not filled")]
+    RangeOrderNotFilled, // 0x17ba (6074)
+
+    #[msg("Flash loan amount must be greater than zero")]
+    ZeroFlashLoanAmount, // 0x17bb (6075)
+    #[msg("Flash loan instructions must be called from the top level of the transaction")]
+    FlashLoanCpiNotAllowed, // 0x17bc (6076)
+    #[msg("No matching flash repay instruction found")]
+    FlashRepayNotFound, // 0x17bd (6077)
+    #[msg("No matching flash borrow instruction found")]
+    FlashBorrowNotFound, // 0x17be (6078)
+
+    #[msg("Invalid position delegate permissions")]
+    InvalidPositionDelegatePermissions, // 0x17bf (6079)
+    #[msg("Position delegate does not have the required permission")]
+    PositionDelegatePermissionDenied, // 0x17c0 (6080)
+    #[msg("Position delegate can only withdraw to the token accounts of the position owner")]
+    InvalidPositionDelegateDestination, // 0x17c1 (6081)
+
+    #[msg("Reward vault is not a vault of a reward denominated in a token of the whirlpool")]
+    InvalidCompoundRewardVault, // 0x17c2 (6082)
+
+    #[msg("Referral fee rate exceeds the maximum allowed")]
+    InvalidReferralFeeRate, // 0x17c3 (6083)
+    #[msg("Invalid referral accounts")]
+    InvalidReferralAccounts, // 0x17c4 (6084)
+
+    #[msg("Whirlpool is paused")]
+    WhirlpoolPaused, // 0x17c5 (6085)
+
+    #[msg("Invalid fee rate schedule")]
+    InvalidFeeRateSchedule, // 0x17c6 (6086)
+
+    #[msg("Invalid protocol fee split")]
+    InvalidProtocolFeeSplit, // 0x17c7 (6087)
+
+    #[msg("Invalid protocol fee recipient accounts")]
+    InvalidProtocolFeeRecipientAccounts, // 0x17c8 (6088)
+
+    #[msg("Invalid token badge attributes")]
+    InvalidTokenBadgeAttributes, // 0x17c9 (6089)
+    #[msg("Token badge deletion reason must be between 1 and 64 bytes")]
+    InvalidTokenBadgeReason, // 0x17ca (6090)
+    #[msg("Hook submission is not an approved submission for the transfer hook of the mint")]
+    InvalidHookSubmission, // 0x17cb (6091)
+    #[msg("Token badge has a history, use the instruction that records it")]
+    TokenBadgeHistoryRequired, // 0x17cc (6092)
 }
 
 impl From<TryFromIntError> for ErrorCode {
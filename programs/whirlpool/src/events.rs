@@ -53,3 +53,72 @@ pub struct Traded {
     pub lp_fee: u64,
     pub protocol_fee: u64,
 }
+
+#[event]
+pub struct FlashLoaned {
+    pub whirlpool: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    pub fee_a: u64,
+    pub fee_b: u64,
+    pub protocol_fee_a: u64,
+    pub protocol_fee_b: u64,
+}
+
+#[event]
+pub struct FeesCompounded {
+    pub whirlpool: Pubkey,
+    pub position: Pubkey,
+    pub liquidity: u128,
+    pub fee_a_amount: u64,
+    pub fee_b_amount: u64,
+    pub reward_a_amount: u64,
+    pub reward_b_amount: u64,
+    pub reward_a_transfer_fee: u64,
+    pub reward_b_transfer_fee: u64,
+}
+
+#[event]
+pub struct ReferralFeePaid {
+    pub whirlpool: Pubkey,
+    pub referral: Pubkey,
+    pub token_mint: Pubkey,
+    pub referral_fee: u64,
+    pub referral_fee_transfer_fee: u64,
+}
+
+#[event]
+pub struct ProtocolFeesSwept {
+    pub whirlpool: Pubkey,
+    pub protocol_fee_a: u64,
+    pub protocol_fee_b: u64,
+}
+
+#[event]
+pub struct TokenBadgeInitialized {
+    pub whirlpools_config: Pubkey,
+    pub token_mint: Pubkey,
+    pub token_badge_authority: Pubkey,
+    pub hook_submission: Option<Pubkey>,
+    pub attributes_version: u32,
+    pub requires_memo: bool,
+    pub max_transfer_fee_bps: u16,
+}
+
+#[event]
+pub struct TokenBadgeAttributesUpdated {
+    pub whirlpools_config: Pubkey,
+    pub token_mint: Pubkey,
+    pub token_badge_authority: Pubkey,
+    pub attributes_version: u32,
+    pub requires_memo: bool,
+    pub max_transfer_fee_bps: u16,
+}
+
+#[event]
+pub struct TokenBadgeDeleted {
+    pub whirlpools_config: Pubkey,
+    pub token_mint: Pubkey,
+    pub token_badge_authority: Pubkey,
+    pub reason: String,
+}
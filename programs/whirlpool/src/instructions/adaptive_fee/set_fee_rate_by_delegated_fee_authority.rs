@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::state::{AdaptiveFeeTier, Whirlpool};
+use crate::state::{AdaptiveFeeTier, OracleAccessor, Whirlpool};
 
 #[derive(Accounts)]
 pub struct SetFeeRateByDelegatedFeeAuthority<'info> {
@@ -17,8 +17,18 @@ pub struct SetFeeRateByDelegatedFeeAuthority<'info> {
 
     #[account(address = adaptive_fee_tier.delegated_fee_authority)]
     pub delegated_fee_authority: Signer<'info>,
+
+    // Optional so that existing callers keep working, see SetFeeRate.
+    #[account(mut, seeds = [b"oracle", whirlpool.key().as_ref()], bump)]
+    /// CHECK: checked in the handler
+    pub oracle: Option<UncheckedAccount<'info>>,
 }
 
 pub fn handler(ctx: Context<SetFeeRateByDelegatedFeeAuthority>, fee_rate: u16) -> Result<()> {
+    if let Some(oracle) = &ctx.accounts.oracle {
+        OracleAccessor::new(&ctx.accounts.whirlpool, oracle.to_account_info())?
+            .clear_fee_rate_schedule()?;
+    }
+
     ctx.accounts.whirlpool.update_fee_rate(fee_rate)
 }
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Oracle, Whirlpool, WhirlpoolsConfig};
+use crate::util::to_timestamp_u64;
+
+#[derive(Accounts)]
+pub struct CancelFeeRateSchedule<'info> {
+    pub whirlpools_config: Account<'info, WhirlpoolsConfig>,
+
+    #[account(mut, has_one = whirlpools_config)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(address = whirlpools_config.fee_authority)]
+    pub fee_authority: Signer<'info>,
+
+    #[account(mut, seeds = [b"oracle", whirlpool.key().as_ref()], bump)]
+    pub oracle: AccountLoader<'info, Oracle>,
+}
+
+pub fn handler(ctx: Context<CancelFeeRateSchedule>) -> Result<()> {
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let mut oracle = ctx.accounts.oracle.load_mut()?;
+
+    // The fee rate reached so far is kept, so cancelling a ramp halfway freezes the fee rate.
+    let fee_rate_schedule = oracle.fee_rate_schedule;
+    if let Some(fee_rate) =
+        fee_rate_schedule.fee_rate_for(ctx.accounts.whirlpool.fee_rate, timestamp)
+    {
+        ctx.accounts.whirlpool.update_fee_rate(fee_rate)?;
+    }
+
+    oracle.clear_fee_rate_schedule();
+    Ok(())
+}
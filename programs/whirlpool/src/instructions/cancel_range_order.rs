@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::state::*;
+use crate::util::verify_position_authority_interface;
+
+#[derive(Accounts)]
+pub struct CancelRangeOrder<'info> {
+    pub position_authority: Signer<'info>,
+
+    /// CHECK: safe, for receiving rent only
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+
+    pub position: Account<'info, Position>,
+
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut,
+        seeds = [b"range_order".as_ref(), position.key().as_ref()],
+        bump,
+        has_one = position,
+        close = receiver,
+    )]
+    pub range_order: Box<Account<'info, RangeOrder>>,
+}
+
+/*
+  Closes a range order without touching the liquidity of its position.
+*/
+pub fn handler(ctx: Context<CancelRangeOrder>) -> Result<()> {
+    verify_position_authority_interface(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )
+}
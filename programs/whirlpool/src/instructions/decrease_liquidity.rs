@@ -9,7 +9,7 @@ use crate::manager::tick_array_manager::update_tick_array_accounts;
 use crate::math::convert_to_liquidity_delta;
 use crate::state::TickArraysMut;
 use crate::util::{
-    is_locked_position, to_timestamp_u64, transfer_from_vault_to_owner,
+    to_timestamp_u64, transfer_from_vault_to_owner, verify_liquidity_withdrawable,
     verify_position_authority_interface,
 };
 
@@ -18,8 +18,8 @@ use super::increase_liquidity::ModifyLiquidity;
 /*
   Removes liquidity from an existing Whirlpool Position.
 */
-pub fn handler(
-    ctx: Context<ModifyLiquidity>,
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ModifyLiquidity<'info>>,
     liquidity_amount: u128,
     token_min_a: u64,
     token_min_b: u64,
@@ -29,10 +29,6 @@ pub fn handler(
         &ctx.accounts.position_authority,
     )?;
 
-    if is_locked_position(&ctx.accounts.position_token_account) {
-        return Err(ErrorCode::OperationNotAllowedOnLockedPosition.into());
-    }
-
     let clock = Clock::get()?;
 
     if liquidity_amount == 0 {
@@ -41,6 +37,15 @@ pub fn handler(
     let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, false)?;
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
 
+    // Locked positions pass their LockConfig as the first remaining account
+    verify_liquidity_withdrawable(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position,
+        ctx.remaining_accounts.first(),
+        liquidity_amount,
+        timestamp,
+    )?;
+
     let mut tick_arrays = TickArraysMut::load(
         &ctx.accounts.tick_array_lower,
         &ctx.accounts.tick_array_upper,
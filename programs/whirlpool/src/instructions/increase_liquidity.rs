@@ -12,6 +12,7 @@ use crate::math::convert_to_liquidity_delta;
 use crate::state::*;
 use crate::util::{
     to_timestamp_u64, transfer_from_owner_to_vault, verify_position_authority_interface,
+    verify_whirlpool_not_paused,
 };
 
 #[derive(Accounts)]
@@ -61,6 +62,9 @@ pub fn handler(
         &ctx.accounts.position_authority,
     )?;
 
+    // The Oracle of the Whirlpool is passed as the first remaining account to enforce its pause flags
+    verify_whirlpool_not_paused(&ctx.accounts.whirlpool, ctx.remaining_accounts.first())?;
+
     let clock = Clock::get()?;
 
     if liquidity_amount == 0 {
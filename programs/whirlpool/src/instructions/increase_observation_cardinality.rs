@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use solana_program::{program::invoke, system_instruction};
+
+use crate::{
+    state::{Oracle, Whirlpool},
+    util::to_timestamp_u64,
+};
+
+#[derive(Accounts)]
+pub struct IncreaseObservationCardinality<'info> {
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(mut, seeds = [b"oracle", whirlpool.key().as_ref()], bump)]
+    pub oracle: AccountLoader<'info, Oracle>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<IncreaseObservationCardinality>,
+    observation_cardinality_next: u16,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let oracle_account_info = ctx.accounts.oracle.to_account_info();
+    let required_size = Oracle::space_with_observations(observation_cardinality_next);
+    if required_size > oracle_account_info.data_len() {
+        let required_lamports = Rent::get()?.minimum_balance(required_size);
+        let lamports_diff = required_lamports.saturating_sub(oracle_account_info.lamports());
+        if lamports_diff > 0 {
+            invoke(
+                &system_instruction::transfer(
+                    ctx.accounts.funder.key,
+                    oracle_account_info.key,
+                    lamports_diff,
+                ),
+                &[
+                    ctx.accounts.funder.to_account_info(),
+                    oracle_account_info.clone(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+            )?;
+        }
+        oracle_account_info.realloc(required_size, true)?;
+    }
+
+    let mut data = oracle_account_info.try_borrow_mut_data()?;
+    let (oracle, observations) = Oracle::split_observations_mut(&mut data)?;
+    oracle.increase_observation_cardinality(observations, observation_cardinality_next, timestamp)
+}
@@ -81,6 +81,7 @@ pub fn handler(ctx: Context<LockPosition>, lock_type: LockType) -> Result<()> {
         ctx.accounts.position_token_account.owner,
         ctx.accounts.position.whirlpool,
         Clock::get()?.unix_timestamp as u64,
+        ctx.accounts.position.liquidity,
         lock_type,
     )?;
 
@@ -1,5 +1,7 @@
 #![allow(ambiguous_glob_reexports)]
 
+pub mod cancel_fee_rate_schedule;
+pub mod cancel_range_order;
 pub mod close_bundled_position;
 pub mod close_position;
 pub mod close_position_with_token_extensions;
@@ -10,6 +12,7 @@ pub mod decrease_liquidity;
 pub mod delete_position_bundle;
 pub mod idl_include;
 pub mod increase_liquidity;
+pub mod increase_observation_cardinality;
 pub mod initialize_config;
 pub mod initialize_dynamic_tick_array;
 pub mod initialize_fee_tier;
@@ -19,26 +22,35 @@ pub mod initialize_position_bundle_with_metadata;
 pub mod initialize_reward;
 pub mod initialize_tick_array;
 pub mod lock_position;
+pub mod observe;
 pub mod open_bundled_position;
 pub mod open_position;
 pub mod open_position_with_metadata;
 pub mod open_position_with_token_extensions;
+pub mod open_range_order;
 pub mod reset_position_range;
+pub mod revoke_position_delegate;
 pub mod set_collect_protocol_fees_authority;
 pub mod set_default_fee_rate;
 pub mod set_default_protocol_fee_rate;
 pub mod set_fee_authority;
 pub mod set_fee_rate;
+pub mod set_fee_rate_schedule;
+pub mod set_position_delegate;
 pub mod set_protocol_fee_rate;
 pub mod set_reward_authority;
 pub mod set_reward_authority_by_super_authority;
 pub mod set_reward_emissions;
 pub mod set_reward_emissions_super_authority;
+pub mod settle_range_order;
 pub mod swap;
 pub mod transfer_locked_position;
 pub mod two_hop_swap;
+pub mod unlock_position;
 pub mod update_fees_and_rewards;
 
+pub use cancel_fee_rate_schedule::*;
+pub use cancel_range_order::*;
 pub use close_bundled_position::*;
 pub use close_position::*;
 pub use close_position_with_token_extensions::*;
@@ -49,6 +61,7 @@ pub use collect_reward::*;
 pub use delete_position_bundle::*;
 pub use idl_include::*;
 pub use increase_liquidity::*;
+pub use increase_observation_cardinality::*;
 pub use initialize_config::*;
 pub use initialize_dynamic_tick_array::*;
 pub use initialize_fee_tier::*;
@@ -58,24 +71,31 @@ pub use initialize_position_bundle_with_metadata::*;
 pub use initialize_reward::*;
 pub use initialize_tick_array::*;
 pub use lock_position::*;
+pub use observe::*;
 pub use open_bundled_position::*;
 pub use open_position::*;
 pub use open_position_with_metadata::*;
 pub use open_position_with_token_extensions::*;
+pub use open_range_order::*;
 pub use reset_position_range::*;
+pub use revoke_position_delegate::*;
 pub use set_collect_protocol_fees_authority::*;
 pub use set_default_fee_rate::*;
 pub use set_default_protocol_fee_rate::*;
 pub use set_fee_authority::*;
 pub use set_fee_rate::*;
+pub use set_fee_rate_schedule::*;
+pub use set_position_delegate::*;
 pub use set_protocol_fee_rate::*;
 pub use set_reward_authority::*;
 pub use set_reward_authority_by_super_authority::*;
 pub use set_reward_emissions::*;
 pub use set_reward_emissions_super_authority::*;
+pub use settle_range_order::*;
 pub use swap::*;
 pub use transfer_locked_position::*;
 pub use two_hop_swap::*;
+pub use unlock_position::*;
 pub use update_fees_and_rewards::*;
 pub mod v2;
 pub use v2::*;
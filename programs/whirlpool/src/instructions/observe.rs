@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    state::{Oracle, OracleTwap, Whirlpool},
+    util::to_timestamp_u64,
+};
+
+#[derive(Accounts)]
+pub struct Observe<'info> {
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(seeds = [b"oracle", whirlpool.key().as_ref()], bump)]
+    pub oracle: AccountLoader<'info, Oracle>,
+}
+
+pub fn handler(ctx: Context<Observe>, window: u32) -> Result<OracleTwap> {
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let whirlpool = &ctx.accounts.whirlpool;
+    let oracle_account_info = ctx.accounts.oracle.to_account_info();
+    let data = oracle_account_info.try_borrow_data()?;
+    let (oracle, observations) = Oracle::split_observations(&data)?;
+    oracle.get_twap(
+        observations,
+        timestamp,
+        whirlpool.tick_current_index,
+        whirlpool.sqrt_price,
+        window,
+    )
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::util::{to_timestamp_u64, verify_position_authority_interface};
+
+#[derive(Accounts)]
+pub struct OpenRangeOrder<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub position_authority: Signer<'info>,
+
+    #[account(has_one = whirlpool)]
+    pub position: Account<'info, Position>,
+
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(seeds = [b"oracle", whirlpool.key().as_ref()], bump)]
+    /// CHECK: may not be initialized, the pause flags are read in the handler
+    pub oracle: UncheckedAccount<'info>,
+
+    #[account(init,
+        payer = funder,
+        space = RangeOrder::LEN,
+        seeds = [b"range_order".as_ref(), position.key().as_ref()],
+        bump,
+    )]
+    pub range_order: Box<Account<'info, RangeOrder>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/*
+  Turns a single-tick-spacing-wide position into a range order that anyone can settle once filled.
+*/
+pub fn handler(ctx: Context<OpenRangeOrder>) -> Result<()> {
+    verify_position_authority_interface(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    let oracle_accessor = OracleAccessor::new(
+        &ctx.accounts.whirlpool,
+        ctx.accounts.oracle.to_account_info(),
+    )?;
+    if oracle_accessor.is_paused()? {
+        return Err(ErrorCode::WhirlpoolPaused.into());
+    }
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    ctx.accounts.range_order.initialize(
+        ctx.accounts.position.key(),
+        &ctx.accounts.position,
+        ctx.accounts.whirlpool.tick_spacing,
+        ctx.accounts.whirlpool.tick_current_index,
+        timestamp,
+    )
+}
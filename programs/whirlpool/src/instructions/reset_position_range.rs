@@ -5,7 +5,7 @@ use solana_program::system_instruction;
 
 use crate::manager::tick_array_manager::get_tick_rent_amount;
 use crate::state::*;
-use crate::util::verify_position_authority_interface;
+use crate::util::verify_position_authority_or_delegate;
 
 #[derive(Accounts)]
 pub struct ResetPositionRange<'info> {
@@ -28,16 +28,22 @@ pub struct ResetPositionRange<'info> {
 
     // Maybe used in the future
     pub system_program: Program<'info, System>,
+    // remaining accounts
+    // - position delegate (if the position authority is a delegate)
 }
 
-pub fn handler(
-    ctx: Context<ResetPositionRange>,
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ResetPositionRange<'info>>,
     new_tick_lower_index: i32,
     new_tick_upper_index: i32,
 ) -> Result<()> {
-    verify_position_authority_interface(
+    verify_position_authority_or_delegate(
         &ctx.accounts.position_token_account,
         &ctx.accounts.position_authority,
+        ctx.accounts.position.key(),
+        ctx.remaining_accounts.first(),
+        PositionDelegate::PERMISSION_RESET_POSITION_RANGE,
+        &[],
     )?;
 
     ensure_position_has_enough_rent_for_ticks(
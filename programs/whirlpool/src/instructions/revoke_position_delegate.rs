@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::state::*;
+use crate::util::validate_owner;
+
+#[derive(Accounts)]
+pub struct RevokePositionDelegate<'info> {
+    pub position_authority: Signer<'info>,
+
+    /// CHECK: safe, for receiving rent only
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+
+    pub position: Account<'info, Position>,
+
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut,
+        seeds = [b"position_delegate".as_ref(), position.key().as_ref()],
+        bump,
+        has_one = position,
+        close = receiver,
+    )]
+    pub position_delegate: Box<Account<'info, PositionDelegate>>,
+}
+
+pub fn handler(ctx: Context<RevokePositionDelegate>) -> Result<()> {
+    // The delegate can revoke itself, so that it can hand back control without the owner.
+    if ctx.accounts.position_authority.key() == ctx.accounts.position_delegate.delegate {
+        return Ok(());
+    }
+
+    validate_owner(
+        &ctx.accounts.position_token_account.owner,
+        &ctx.accounts.position_authority.to_account_info(),
+    )
+}
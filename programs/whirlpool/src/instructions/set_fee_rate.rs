@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::state::{Whirlpool, WhirlpoolsConfig};
+use crate::state::{OracleAccessor, Whirlpool, WhirlpoolsConfig};
 
 #[derive(Accounts)]
 pub struct SetFeeRate<'info> {
@@ -11,8 +11,19 @@ pub struct SetFeeRate<'info> {
 
     #[account(address = whirlpools_config.fee_authority)]
     pub fee_authority: Signer<'info>,
+
+    // Optional so that existing callers keep working. Swaps ignore a scheduled fee rate change once
+    // the fee rate has been set directly, passing the Oracle only clears the schedule as well.
+    #[account(mut, seeds = [b"oracle", whirlpool.key().as_ref()], bump)]
+    /// CHECK: checked in the handler, may not be initialized
+    pub oracle: Option<UncheckedAccount<'info>>,
 }
 
 pub fn handler(ctx: Context<SetFeeRate>, fee_rate: u16) -> Result<()> {
+    if let Some(oracle) = &ctx.accounts.oracle {
+        OracleAccessor::new(&ctx.accounts.whirlpool, oracle.to_account_info())?
+            .clear_fee_rate_schedule()?;
+    }
+
     ctx.accounts.whirlpool.update_fee_rate(fee_rate)
 }
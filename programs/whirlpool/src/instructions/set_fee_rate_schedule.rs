@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{FeeRateSchedule, Whirlpool, WhirlpoolsConfig};
+use crate::util::{to_timestamp_u64, update_oracle};
+
+#[derive(Accounts)]
+pub struct SetFeeRateSchedule<'info> {
+    pub whirlpools_config: Account<'info, WhirlpoolsConfig>,
+
+    #[account(mut, has_one = whirlpools_config)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(address = whirlpools_config.fee_authority)]
+    pub fee_authority: Signer<'info>,
+
+    #[account(mut, seeds = [b"oracle", whirlpool.key().as_ref()], bump)]
+    /// CHECK: initialized in the handler if needed
+    pub oracle: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<SetFeeRateSchedule>,
+    target_fee_rate: u16,
+    ramp_start_timestamp: u64,
+    effective_timestamp: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let whirlpool = &ctx.accounts.whirlpool;
+    let mut current_fee_rate = whirlpool.fee_rate;
+    update_oracle(
+        whirlpool,
+        &ctx.accounts.oracle,
+        ctx.bumps.oracle,
+        &ctx.accounts.funder,
+        &ctx.accounts.system_program,
+        |oracle| {
+            // A previous schedule may not have been applied by any swap yet, so it is applied here
+            // and the ramp starts from the fee rate of the Whirlpool, which swaps compare against.
+            let previous_schedule = oracle.fee_rate_schedule;
+            if let Some(fee_rate) = previous_schedule.fee_rate_for(current_fee_rate, timestamp) {
+                current_fee_rate = fee_rate;
+            }
+
+            oracle.update_fee_rate_schedule(FeeRateSchedule::new(
+                current_fee_rate,
+                target_fee_rate,
+                ramp_start_timestamp,
+                effective_timestamp,
+                timestamp,
+            )?);
+            Ok(())
+        },
+    )?;
+
+    ctx.accounts.whirlpool.update_fee_rate(current_fee_rate)
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::state::*;
+use crate::util::validate_owner;
+
+#[derive(Accounts)]
+pub struct SetPositionDelegate<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub position_authority: Signer<'info>,
+
+    pub position: Account<'info, Position>,
+
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(init,
+        payer = funder,
+        space = PositionDelegate::LEN,
+        seeds = [b"position_delegate".as_ref(), position.key().as_ref()],
+        bump,
+    )]
+    pub position_delegate: Box<Account<'info, PositionDelegate>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<SetPositionDelegate>,
+    delegate: Pubkey,
+    permissions: u16,
+) -> Result<()> {
+    // Only the owner of the position can delegate it, the SPL token delegate is not allowed.
+    validate_owner(
+        &ctx.accounts.position_token_account.owner,
+        &ctx.accounts.position_authority.to_account_info(),
+    )?;
+
+    ctx.accounts.position_delegate.initialize(
+        ctx.accounts.position.key(),
+        ctx.accounts.position_token_account.owner,
+        delegate,
+        permissions,
+    )
+}
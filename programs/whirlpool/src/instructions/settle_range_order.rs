@@ -0,0 +1,227 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::transfer_memo;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::manager::liquidity_manager::{
+    calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
+};
+use crate::manager::tick_array_manager::update_tick_array_accounts;
+use crate::manager::tick_manager::next_range_order_fill_state;
+use crate::math::convert_to_liquidity_delta;
+use crate::state::*;
+use crate::util::{
+    calculate_transfer_fee_excluded_amount, parse_remaining_accounts, AccountsType,
+    RemainingAccountsInfo,
+};
+use crate::util::{
+    to_timestamp_u64, v2::transfer_from_vault_to_owner_v2, verify_liquidity_withdrawable,
+};
+
+#[derive(Accounts)]
+pub struct SettleRangeOrder<'info> {
+    #[account(mut)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(address = *token_mint_a.to_account_info().owner)]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = *token_mint_b.to_account_info().owner)]
+    pub token_program_b: Interface<'info, TokenInterface>,
+
+    pub memo_program: Program<'info, Memo>,
+
+    #[account(mut, has_one = whirlpool)]
+    pub position: Account<'info, Position>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: safe, receives the rent of the range order account
+    #[account(mut, address = position_token_account.owner)]
+    pub position_owner: UncheckedAccount<'info>,
+
+    #[account(mut,
+        seeds = [b"range_order".as_ref(), position.key().as_ref()],
+        bump,
+        has_one = position,
+        close = position_owner,
+    )]
+    pub range_order: Box<Account<'info, RangeOrder>>,
+
+    #[account(address = whirlpool.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    #[account(address = whirlpool.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(mut,
+        constraint = token_owner_account_a.mint == whirlpool.token_mint_a,
+        constraint = token_owner_account_a.owner == position_token_account.owner,
+    )]
+    pub token_owner_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut,
+        constraint = token_owner_account_b.mint == whirlpool.token_mint_b,
+        constraint = token_owner_account_b.owner == position_token_account.owner,
+    )]
+    pub token_owner_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, constraint = token_vault_a.key() == whirlpool.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, constraint = token_vault_b.key() == whirlpool.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    /// CHECK: Checked by the tick array loader
+    pub tick_array_lower: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Checked by the tick array loader
+    pub tick_array_upper: UncheckedAccount<'info>,
+    // remaining accounts
+    // - accounts for transfer hook program of token_mint_a
+    // - accounts for transfer hook program of token_mint_b
+    // - lock config of the position (if the position is locked)
+}
+
+/*
+  Withdraws all liquidity of a filled range order to the position owner and closes the order.
+  Anyone can settle a range order, the tokens always go to the owner of the position token.
+*/
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, SettleRangeOrder<'info>>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let range_order = &ctx.accounts.range_order;
+    let fill_state = next_range_order_fill_state(
+        ctx.accounts.whirlpool.tick_current_index,
+        range_order.tick_lower_index,
+        range_order.tick_upper_index,
+        range_order.a_to_b,
+    );
+    if fill_state != RangeOrderFillState::Filled {
+        return Err(ErrorCode::RangeOrderNotFilled.into());
+    }
+
+    // Nothing to withdraw if the owner has already emptied the position, which may also have been
+    // reset to a range that is unrelated to the order.
+    let liquidity_amount = ctx.accounts.position.liquidity;
+    if liquidity_amount == 0
+        || ctx.accounts.position.tick_lower_index != range_order.tick_lower_index
+        || ctx.accounts.position.tick_upper_index != range_order.tick_upper_index
+    {
+        return Ok(());
+    }
+
+    let clock = Clock::get()?;
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::LockConfig,
+        ],
+    )?;
+
+    let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, false)?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    verify_liquidity_withdrawable(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position,
+        remaining_accounts.lock_config.as_ref(),
+        liquidity_amount,
+        timestamp,
+    )?;
+
+    let mut tick_arrays = TickArraysMut::load(
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        &ctx.accounts.whirlpool.key(),
+    )?;
+
+    let (lower_tick_array, upper_tick_array) = tick_arrays.deref();
+    let update = calculate_modify_liquidity(
+        &ctx.accounts.whirlpool,
+        &ctx.accounts.position,
+        lower_tick_array,
+        upper_tick_array,
+        liquidity_delta,
+        timestamp,
+    )?;
+
+    let (lower_tick_array_mut, upper_tick_array_mut) = tick_arrays.deref_mut();
+    sync_modify_liquidity_values(
+        &mut ctx.accounts.whirlpool,
+        &mut ctx.accounts.position,
+        lower_tick_array_mut,
+        upper_tick_array_mut,
+        &update,
+        timestamp,
+    )?;
+
+    // Need to drop the tick arrays so we can potentially resize them
+    drop(tick_arrays);
+
+    update_tick_array_accounts(
+        &ctx.accounts.position,
+        ctx.accounts.tick_array_lower.to_account_info(),
+        ctx.accounts.tick_array_upper.to_account_info(),
+        &update.tick_array_lower_update,
+        &update.tick_array_upper_update,
+    )?;
+
+    let (delta_a, delta_b) = calculate_liquidity_token_deltas(
+        ctx.accounts.whirlpool.tick_current_index,
+        ctx.accounts.whirlpool.sqrt_price,
+        &ctx.accounts.position,
+        liquidity_delta,
+    )?;
+
+    let transfer_fee_excluded_delta_a =
+        calculate_transfer_fee_excluded_amount(&ctx.accounts.token_mint_a, delta_a)?;
+    let transfer_fee_excluded_delta_b =
+        calculate_transfer_fee_excluded_amount(&ctx.accounts.token_mint_b, delta_b)?;
+
+    transfer_from_vault_to_owner_v2(
+        &ctx.accounts.whirlpool,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_owner_account_a,
+        &ctx.accounts.token_program_a,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_a,
+        delta_a,
+        transfer_memo::TRANSFER_MEMO_DECREASE_LIQUIDITY.as_bytes(),
+    )?;
+
+    transfer_from_vault_to_owner_v2(
+        &ctx.accounts.whirlpool,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.token_owner_account_b,
+        &ctx.accounts.token_program_b,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_b,
+        delta_b,
+        transfer_memo::TRANSFER_MEMO_DECREASE_LIQUIDITY.as_bytes(),
+    )?;
+
+    emit!(LiquidityDecreased {
+        whirlpool: ctx.accounts.whirlpool.key(),
+        position: ctx.accounts.position.key(),
+        tick_lower_index: ctx.accounts.position.tick_lower_index,
+        tick_upper_index: ctx.accounts.position.tick_upper_index,
+        liquidity: liquidity_amount,
+        token_a_amount: delta_a,
+        token_b_amount: delta_b,
+        token_a_transfer_fee: transfer_fee_excluded_delta_a.transfer_fee,
+        token_b_transfer_fee: transfer_fee_excluded_delta_b.transfer_fee,
+    });
+
+    Ok(())
+}
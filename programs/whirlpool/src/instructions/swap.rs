@@ -76,9 +76,13 @@ pub fn handler(
     let mut swap_tick_sequence = swap_tick_sequence_builder.try_build(whirlpool, a_to_b)?;
 
     let oracle_accessor = OracleAccessor::new(whirlpool, ctx.accounts.oracle.to_account_info())?;
+    if oracle_accessor.is_paused()? {
+        return Err(ErrorCode::WhirlpoolPaused.into());
+    }
     if !oracle_accessor.is_trade_enabled(timestamp)? {
         return Err(ErrorCode::TradeIsNotEnabled.into());
     }
+    oracle_accessor.apply_fee_rate_schedule(whirlpool, timestamp)?;
     let adaptive_fee_info = oracle_accessor.get_adaptive_fee_info()?;
 
     let swap_update = swap(
@@ -105,6 +109,11 @@ pub fn handler(
     }
 
     oracle_accessor.update_adaptive_fee_variables(&swap_update.next_adaptive_fee_info)?;
+    oracle_accessor.write_observation(
+        timestamp,
+        whirlpool.tick_current_index,
+        whirlpool.sqrt_price,
+    )?;
 
     let pre_sqrt_price = whirlpool.sqrt_price;
     let (input_amount, output_amount) = if a_to_b {
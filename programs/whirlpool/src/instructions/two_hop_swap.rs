@@ -144,16 +144,24 @@ pub fn handler(
 
     let oracle_accessor_one =
         OracleAccessor::new(whirlpool_one, ctx.accounts.oracle_one.to_account_info())?;
+    if oracle_accessor_one.is_paused()? {
+        return Err(ErrorCode::WhirlpoolPaused.into());
+    }
     if !oracle_accessor_one.is_trade_enabled(timestamp)? {
         return Err(ErrorCode::TradeIsNotEnabled.into());
     }
+    oracle_accessor_one.apply_fee_rate_schedule(whirlpool_one, timestamp)?;
     let adaptive_fee_info_one = oracle_accessor_one.get_adaptive_fee_info()?;
 
     let oracle_accessor_two =
         OracleAccessor::new(whirlpool_two, ctx.accounts.oracle_two.to_account_info())?;
+    if oracle_accessor_two.is_paused()? {
+        return Err(ErrorCode::WhirlpoolPaused.into());
+    }
     if !oracle_accessor_two.is_trade_enabled(timestamp)? {
         return Err(ErrorCode::TradeIsNotEnabled.into());
     }
+    oracle_accessor_two.apply_fee_rate_schedule(whirlpool_two, timestamp)?;
     let adaptive_fee_info_two = oracle_accessor_two.get_adaptive_fee_info()?;
 
     // TODO: WLOG, we could extend this to N-swaps, but the account inputs to the instruction would
@@ -268,8 +276,18 @@ pub fn handler(
     }
 
     oracle_accessor_one.update_adaptive_fee_variables(&swap_update_one.next_adaptive_fee_info)?;
+    oracle_accessor_one.write_observation(
+        timestamp,
+        whirlpool_one.tick_current_index,
+        whirlpool_one.sqrt_price,
+    )?;
 
     oracle_accessor_two.update_adaptive_fee_variables(&swap_update_two.next_adaptive_fee_info)?;
+    oracle_accessor_two.write_observation(
+        timestamp,
+        whirlpool_two.tick_current_index,
+        whirlpool_two.sqrt_price,
+    )?;
 
     let pre_sqrt_price_one = whirlpool_one.sqrt_price;
     let (input_amount_one, output_amount_one) = if a_to_b_one {
@@ -6,7 +6,7 @@ use crate::util::{parse_remaining_accounts, AccountsType, RemainingAccountsInfo}
 use crate::{
     constants::transfer_memo,
     state::*,
-    util::{v2::transfer_from_vault_to_owner_v2, verify_position_authority_interface},
+    util::{v2::transfer_from_vault_to_owner_v2, verify_position_authority_or_delegate},
 };
 
 #[derive(Accounts)]
@@ -52,16 +52,27 @@ pub fn handler<'info>(
     ctx: Context<'_, '_, '_, 'info, CollectFeesV2<'info>>,
     remaining_accounts_info: Option<RemainingAccountsInfo>,
 ) -> Result<()> {
-    verify_position_authority_interface(
-        &ctx.accounts.position_token_account,
-        &ctx.accounts.position_authority,
-    )?;
-
     // Process remaining accounts
     let remaining_accounts = parse_remaining_accounts(
         ctx.remaining_accounts,
         &remaining_accounts_info,
-        &[AccountsType::TransferHookA, AccountsType::TransferHookB],
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::PositionDelegate,
+        ],
+    )?;
+
+    verify_position_authority_or_delegate(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+        ctx.accounts.position.key(),
+        remaining_accounts.position_delegate.as_ref(),
+        PositionDelegate::PERMISSION_COLLECT_FEES,
+        &[
+            ctx.accounts.token_owner_account_a.owner,
+            ctx.accounts.token_owner_account_b.owner,
+        ],
     )?;
 
     let position = &mut ctx.accounts.position;
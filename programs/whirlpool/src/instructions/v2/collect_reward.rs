@@ -6,7 +6,7 @@ use crate::util::{parse_remaining_accounts, AccountsType, RemainingAccountsInfo}
 use crate::{
     constants::transfer_memo,
     state::*,
-    util::{v2::transfer_from_vault_to_owner_v2, verify_position_authority_interface},
+    util::{v2::transfer_from_vault_to_owner_v2, verify_position_authority_or_delegate},
 };
 
 #[derive(Accounts)]
@@ -60,16 +60,23 @@ pub fn handler<'info>(
     reward_index: u8,
     remaining_accounts_info: Option<RemainingAccountsInfo>,
 ) -> Result<()> {
-    verify_position_authority_interface(
-        &ctx.accounts.position_token_account,
-        &ctx.accounts.position_authority,
-    )?;
-
     // Process remaining accounts
     let remaining_accounts = parse_remaining_accounts(
         ctx.remaining_accounts,
         &remaining_accounts_info,
-        &[AccountsType::TransferHookReward],
+        &[
+            AccountsType::TransferHookReward,
+            AccountsType::PositionDelegate,
+        ],
+    )?;
+
+    verify_position_authority_or_delegate(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+        ctx.accounts.position.key(),
+        remaining_accounts.position_delegate.as_ref(),
+        PositionDelegate::PERMISSION_COLLECT_REWARD,
+        &[ctx.accounts.reward_owner_account.owner],
     )?;
 
     let index = reward_index as usize;
@@ -0,0 +1,387 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::transfer_memo;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::manager::liquidity_manager::{
+    calculate_fee_and_reward_growths, calculate_liquidity_from_token_amounts,
+    calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
+};
+use crate::manager::tick_array_manager::update_tick_array_accounts;
+use crate::math::convert_to_liquidity_delta;
+use crate::state::*;
+use crate::util::{
+    calculate_transfer_fee_excluded_amount, calculate_transfer_fee_included_amount,
+    get_remaining_accounts_slice, parse_remaining_accounts, AccountsType, RemainingAccountsInfo,
+};
+use crate::util::{
+    to_timestamp_u64, v2::transfer_from_vault_to_owner_v2, verify_position_authority_or_delegate,
+};
+
+#[derive(Accounts)]
+pub struct CompoundFeesAndRewards<'info> {
+    #[account(mut)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(address = *token_mint_a.to_account_info().owner)]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = *token_mint_b.to_account_info().owner)]
+    pub token_program_b: Interface<'info, TokenInterface>,
+
+    pub memo_program: Program<'info, Memo>,
+
+    pub position_authority: Signer<'info>,
+
+    #[account(mut, has_one = whirlpool)]
+    pub position: Account<'info, Position>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = whirlpool.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    #[account(address = whirlpool.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, constraint = token_vault_a.key() == whirlpool.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, constraint = token_vault_b.key() == whirlpool.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    /// CHECK: Checked by the tick array loader
+    pub tick_array_lower: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Checked by the tick array loader
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"oracle", whirlpool.key().as_ref()], bump)]
+    /// CHECK: may not be initialized, the pause flags are read in the handler
+    pub oracle: UncheckedAccount<'info>,
+    // remaining accounts
+    // - accounts for transfer hook program of token_mint_a
+    // - accounts for transfer hook program of token_mint_b
+    // - position delegate
+    // - reward vaults of rewards denominated in token_mint_a or token_mint_b
+}
+
+struct CompoundRewardSource<'info> {
+    reward_index: usize,
+    reward_vault: InterfaceAccount<'info, TokenAccount>,
+    is_token_a: bool,
+    // Amount that arrives in the token vault if all of the owed reward is moved
+    transfer_fee_excluded_amount: u64,
+}
+
+/// Adds the fees owed to a position, and the rewards owed that are denominated in token A or B,
+/// to the liquidity of the position at the current price.
+///
+/// Fees owed are already held by the token vaults, so they are compounded without any transfer.
+/// Rewards are moved from the reward vault to the token vault, and only the amount that arrives
+/// after the transfer fee is added to the liquidity. Whatever cannot be matched at the current
+/// price remains owed to the position.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CompoundFeesAndRewards<'info>>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::PositionDelegate,
+            AccountsType::RewardVaults,
+        ],
+    )?;
+
+    verify_position_authority_or_delegate(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+        ctx.accounts.position.key(),
+        remaining_accounts.position_delegate.as_ref(),
+        PositionDelegate::PERMISSION_COMPOUND_FEES,
+        &[],
+    )?;
+
+    let oracle_accessor = OracleAccessor::new(
+        &ctx.accounts.whirlpool,
+        ctx.accounts.oracle.to_account_info(),
+    )?;
+    if oracle_accessor.is_paused()? {
+        return Err(ErrorCode::WhirlpoolPaused.into());
+    }
+
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    // Bring the fees and rewards owed up to date before compounding them
+    if ctx.accounts.position.liquidity > 0 {
+        let tick_arrays = TickArraysMut::load(
+            &ctx.accounts.tick_array_lower,
+            &ctx.accounts.tick_array_upper,
+            &ctx.accounts.whirlpool.key(),
+        )?;
+
+        let (lower_tick_array, upper_tick_array) = tick_arrays.deref();
+        let (position_update, reward_infos) = calculate_fee_and_reward_growths(
+            &ctx.accounts.whirlpool,
+            &ctx.accounts.position,
+            lower_tick_array,
+            upper_tick_array,
+            timestamp,
+        )?;
+        drop(tick_arrays);
+
+        ctx.accounts
+            .whirlpool
+            .update_rewards(reward_infos, timestamp);
+        ctx.accounts.position.update(&position_update);
+    }
+
+    let reward_sources = load_compound_reward_sources(
+        &ctx.accounts.whirlpool,
+        &ctx.accounts.position,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_mint_b,
+        get_remaining_accounts_slice(
+            ctx.remaining_accounts,
+            &remaining_accounts_info,
+            AccountsType::RewardVaults,
+        ),
+    )?;
+
+    let mut amount_a = ctx.accounts.position.fee_owed_a;
+    let mut amount_b = ctx.accounts.position.fee_owed_b;
+    for source in reward_sources.iter() {
+        let amount = if source.is_token_a {
+            &mut amount_a
+        } else {
+            &mut amount_b
+        };
+        *amount = amount
+            .checked_add(source.transfer_fee_excluded_amount)
+            .ok_or(ErrorCode::AmountCalcOverflow)?;
+    }
+
+    let liquidity_amount = calculate_liquidity_from_token_amounts(
+        ctx.accounts.whirlpool.tick_current_index,
+        ctx.accounts.whirlpool.sqrt_price,
+        &ctx.accounts.position,
+        amount_a,
+        amount_b,
+    )?;
+    if liquidity_amount == 0 {
+        return Err(ErrorCode::LiquidityZero.into());
+    }
+
+    let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, true)?;
+
+    let (delta_a, delta_b) = calculate_liquidity_token_deltas(
+        ctx.accounts.whirlpool.tick_current_index,
+        ctx.accounts.whirlpool.sqrt_price,
+        &ctx.accounts.position,
+        liquidity_delta,
+    )?;
+    if delta_a > amount_a || delta_b > amount_b {
+        return Err(ErrorCode::TokenMaxExceeded.into());
+    }
+
+    let tick_arrays = TickArraysMut::load(
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        &ctx.accounts.whirlpool.key(),
+    )?;
+
+    let (lower_tick_array, upper_tick_array) = tick_arrays.deref();
+    let update = calculate_modify_liquidity(
+        &ctx.accounts.whirlpool,
+        &ctx.accounts.position,
+        lower_tick_array,
+        upper_tick_array,
+        liquidity_delta,
+        timestamp,
+    )?;
+
+    // Need to drop the tick arrays so we can potentially resize them
+    drop(tick_arrays);
+
+    update_tick_array_accounts(
+        &ctx.accounts.position,
+        ctx.accounts.tick_array_lower.to_account_info(),
+        ctx.accounts.tick_array_upper.to_account_info(),
+        &update.tick_array_lower_update,
+        &update.tick_array_upper_update,
+    )?;
+
+    let mut tick_arrays = TickArraysMut::load(
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        &ctx.accounts.whirlpool.key(),
+    )?;
+
+    let (lower_tick_array_mut, upper_tick_array_mut) = tick_arrays.deref_mut();
+    sync_modify_liquidity_values(
+        &mut ctx.accounts.whirlpool,
+        &mut ctx.accounts.position,
+        lower_tick_array_mut,
+        upper_tick_array_mut,
+        &update,
+        timestamp,
+    )?;
+    drop(tick_arrays);
+
+    // Fees are used first because they are already in the token vaults
+    let fee_a_amount = delta_a.min(ctx.accounts.position.fee_owed_a);
+    let fee_b_amount = delta_b.min(ctx.accounts.position.fee_owed_b);
+    let fee_owed_a = ctx.accounts.position.fee_owed_a;
+    let fee_owed_b = ctx.accounts.position.fee_owed_b;
+    ctx.accounts
+        .position
+        .update_fees_owed(fee_owed_a - fee_a_amount, fee_owed_b - fee_b_amount);
+
+    let mut remaining_a = delta_a - fee_a_amount;
+    let mut remaining_b = delta_b - fee_b_amount;
+    let mut reward_a_amount = 0u64;
+    let mut reward_b_amount = 0u64;
+    let mut reward_a_transfer_fee = 0u64;
+    let mut reward_b_transfer_fee = 0u64;
+
+    for source in reward_sources.iter() {
+        let (remaining, reward_amount, reward_transfer_fee) = if source.is_token_a {
+            (
+                &mut remaining_a,
+                &mut reward_a_amount,
+                &mut reward_a_transfer_fee,
+            )
+        } else {
+            (
+                &mut remaining_b,
+                &mut reward_b_amount,
+                &mut reward_b_transfer_fee,
+            )
+        };
+
+        let transfer_fee_excluded_amount = (*remaining).min(source.transfer_fee_excluded_amount);
+        if transfer_fee_excluded_amount == 0 {
+            continue;
+        }
+        *remaining -= transfer_fee_excluded_amount;
+
+        let (token_mint, token_vault, token_program, transfer_hook_accounts) = if source.is_token_a
+        {
+            (
+                &ctx.accounts.token_mint_a,
+                &ctx.accounts.token_vault_a,
+                &ctx.accounts.token_program_a,
+                &remaining_accounts.transfer_hook_a,
+            )
+        } else {
+            (
+                &ctx.accounts.token_mint_b,
+                &ctx.accounts.token_vault_b,
+                &ctx.accounts.token_program_b,
+                &remaining_accounts.transfer_hook_b,
+            )
+        };
+
+        let transfer_fee_included_amount =
+            calculate_transfer_fee_included_amount(token_mint, transfer_fee_excluded_amount)?;
+
+        let amount_owed = ctx.accounts.position.reward_infos[source.reward_index].amount_owed;
+        ctx.accounts.position.update_reward_owed(
+            source.reward_index,
+            amount_owed
+                .checked_sub(transfer_fee_included_amount.amount)
+                .ok_or(ErrorCode::TransferFeeCalculationError)?,
+        );
+
+        transfer_from_vault_to_owner_v2(
+            &ctx.accounts.whirlpool,
+            token_mint,
+            &source.reward_vault,
+            token_vault,
+            token_program,
+            &ctx.accounts.memo_program,
+            transfer_hook_accounts,
+            transfer_fee_included_amount.amount,
+            transfer_memo::TRANSFER_MEMO_COMPOUND_REWARD.as_bytes(),
+        )?;
+
+        *reward_amount += transfer_fee_excluded_amount;
+        *reward_transfer_fee += transfer_fee_included_amount.transfer_fee;
+    }
+
+    emit!(FeesCompounded {
+        whirlpool: ctx.accounts.whirlpool.key(),
+        position: ctx.accounts.position.key(),
+        liquidity: liquidity_amount,
+        fee_a_amount,
+        fee_b_amount,
+        reward_a_amount,
+        reward_b_amount,
+        reward_a_transfer_fee,
+        reward_b_transfer_fee,
+    });
+
+    Ok(())
+}
+
+fn load_compound_reward_sources<'info>(
+    whirlpool: &Whirlpool,
+    position: &Position,
+    token_mint_a: &InterfaceAccount<'info, Mint>,
+    token_mint_b: &InterfaceAccount<'info, Mint>,
+    reward_vaults: Option<&'info [AccountInfo<'info>]>,
+) -> Result<Vec<CompoundRewardSource<'info>>> {
+    let mut reward_sources = Vec::new();
+    let reward_vaults = match reward_vaults {
+        Some(reward_vaults) => reward_vaults,
+        None => return Ok(reward_sources),
+    };
+
+    let mut used = [false; NUM_REWARDS];
+    for reward_vault_info in reward_vaults.iter() {
+        let reward_index = whirlpool
+            .reward_infos
+            .iter()
+            .position(|reward_info| {
+                reward_info.initialized() && reward_info.vault == reward_vault_info.key()
+            })
+            .ok_or(ErrorCode::InvalidCompoundRewardVault)?;
+        if used[reward_index] {
+            return Err(ErrorCode::RemainingAccountsDuplicatedAccountsType.into());
+        }
+        used[reward_index] = true;
+
+        let reward_mint = whirlpool.reward_infos[reward_index].mint;
+        let (is_token_a, token_mint) = if reward_mint == whirlpool.token_mint_a {
+            (true, token_mint_a)
+        } else if reward_mint == whirlpool.token_mint_b {
+            (false, token_mint_b)
+        } else {
+            return Err(ErrorCode::InvalidCompoundRewardVault.into());
+        };
+
+        let reward_vault = InterfaceAccount::<TokenAccount>::try_from(reward_vault_info)?;
+        // Only the reward that the vault can actually pay out is compounded
+        let available_amount = position.reward_infos[reward_index]
+            .amount_owed
+            .min(reward_vault.amount);
+        let transfer_fee_excluded_amount =
+            calculate_transfer_fee_excluded_amount(token_mint, available_amount)?.amount;
+
+        reward_sources.push(CompoundRewardSource {
+            reward_index,
+            reward_vault,
+            is_token_a,
+            transfer_fee_excluded_amount,
+        });
+    }
+
+    Ok(reward_sources)
+}
@@ -8,13 +8,14 @@ use crate::manager::liquidity_manager::{
 };
 use crate::manager::tick_array_manager::update_tick_array_accounts;
 use crate::math::convert_to_liquidity_delta;
-use crate::state::TickArraysMut;
+use crate::state::{PositionDelegate, TickArraysMut};
 use crate::util::{
-    calculate_transfer_fee_excluded_amount, is_locked_position, parse_remaining_accounts,
-    AccountsType, RemainingAccountsInfo,
+    calculate_transfer_fee_excluded_amount, parse_remaining_accounts, AccountsType,
+    RemainingAccountsInfo,
 };
 use crate::util::{
-    to_timestamp_u64, v2::transfer_from_vault_to_owner_v2, verify_position_authority_interface,
+    to_timestamp_u64, v2::transfer_from_vault_to_owner_v2, verify_liquidity_withdrawable,
+    verify_position_authority_or_delegate,
 };
 
 use super::increase_liquidity::ModifyLiquidityV2;
@@ -29,15 +30,6 @@ pub fn handler<'info>(
     token_min_b: u64,
     remaining_accounts_info: Option<RemainingAccountsInfo>,
 ) -> Result<()> {
-    verify_position_authority_interface(
-        &ctx.accounts.position_token_account,
-        &ctx.accounts.position_authority,
-    )?;
-
-    if is_locked_position(&ctx.accounts.position_token_account) {
-        return Err(ErrorCode::OperationNotAllowedOnLockedPosition.into());
-    }
-
     let clock = Clock::get()?;
 
     if liquidity_amount == 0 {
@@ -48,12 +40,37 @@ pub fn handler<'info>(
     let remaining_accounts = parse_remaining_accounts(
         ctx.remaining_accounts,
         &remaining_accounts_info,
-        &[AccountsType::TransferHookA, AccountsType::TransferHookB],
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::LockConfig,
+            AccountsType::PositionDelegate,
+        ],
+    )?;
+
+    verify_position_authority_or_delegate(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+        ctx.accounts.position.key(),
+        remaining_accounts.position_delegate.as_ref(),
+        PositionDelegate::PERMISSION_DECREASE_LIQUIDITY,
+        &[
+            ctx.accounts.token_owner_account_a.owner,
+            ctx.accounts.token_owner_account_b.owner,
+        ],
     )?;
 
     let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, false)?;
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
 
+    verify_liquidity_withdrawable(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position,
+        remaining_accounts.lock_config.as_ref(),
+        liquidity_amount,
+        timestamp,
+    )?;
+
     let mut tick_arrays = TickArraysMut::load(
         &ctx.accounts.tick_array_lower,
         &ctx.accounts.tick_array_upper,
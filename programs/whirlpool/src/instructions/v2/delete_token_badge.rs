@@ -1,3 +1,4 @@
+use crate::errors::ErrorCode;
 use crate::state::*;
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::Mint;
@@ -32,6 +33,11 @@ pub struct DeleteTokenBadge<'info> {
     pub receiver: UncheckedAccount<'info>,
 }
 
-pub fn handler(_ctx: Context<DeleteTokenBadge>) -> Result<()> {
+pub fn handler(ctx: Context<DeleteTokenBadge>) -> Result<()> {
+    // A badge with a history must leave a tombstone, see delete_token_badge_with_history.
+    if ctx.accounts.token_badge.has_history {
+        return Err(ErrorCode::TokenBadgeHistoryRequired.into());
+    }
+
     Ok(())
 }
@@ -0,0 +1,71 @@
+use crate::events::TokenBadgeDeleted;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+#[derive(Accounts)]
+pub struct DeleteTokenBadgeWithHistory<'info> {
+    pub whirlpools_config: Box<Account<'info, WhirlpoolsConfig>>,
+
+    #[account(has_one = whirlpools_config)]
+    pub whirlpools_config_extension: Box<Account<'info, WhirlpoolsConfigExtension>>,
+
+    #[account(address = whirlpools_config_extension.token_badge_authority)]
+    pub token_badge_authority: Signer<'info>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+      mut,
+      seeds = [
+        b"token_badge",
+        whirlpools_config.key().as_ref(),
+        token_mint.key().as_ref(),
+      ],
+      bump,
+      has_one = whirlpools_config,
+      close = receiver
+    )]
+    pub token_badge: Account<'info, TokenBadge>,
+
+    #[account(
+      mut,
+      seeds = [
+        b"token_badge_history",
+        whirlpools_config.key().as_ref(),
+        token_mint.key().as_ref(),
+      ],
+      bump,
+    )]
+    pub token_badge_history: Box<Account<'info, TokenBadgeHistory>>,
+
+    /// CHECK: safe, for receiving rent only
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+}
+
+/// Delete the token badge and leave a tombstone with the reason in the token badge history.
+pub fn handler(ctx: Context<DeleteTokenBadgeWithHistory>, reason: String) -> Result<()> {
+    let whirlpools_config = ctx.accounts.whirlpools_config.key();
+    let token_mint = ctx.accounts.token_mint.key();
+    let token_badge_authority = ctx.accounts.token_badge_authority.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    let token_badge = &ctx.accounts.token_badge;
+
+    ctx.accounts.token_badge_history.record_deleted(
+        token_badge_authority,
+        timestamp,
+        token_badge.attributes_version,
+        token_badge.attributes,
+        &reason,
+    )?;
+
+    emit!(TokenBadgeDeleted {
+        whirlpools_config,
+        token_mint,
+        token_badge_authority,
+        reason,
+    });
+
+    Ok(())
+}
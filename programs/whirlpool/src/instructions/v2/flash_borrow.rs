@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar;
+
+use crate::constants::transfer_memo;
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::util::{
+    parse_remaining_accounts, v2::transfer_from_vault_to_owner_v2, verify_flash_repay_follows,
+    AccountsType, RemainingAccountsInfo,
+};
+
+// Accounts of flash_repay, which is never blocked by a pause so that an open flash loan can always be repaid.
+#[derive(Accounts)]
+pub struct FlashLoan<'info> {
+    // The whirlpool must stay the first account, flash loan instructions are matched by it.
+    #[account(mut)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(address = *token_mint_a.to_account_info().owner)]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = *token_mint_b.to_account_info().owner)]
+    pub token_program_b: Interface<'info, TokenInterface>,
+
+    pub memo_program: Program<'info, Memo>,
+
+    pub borrower: Signer<'info>,
+
+    #[account(address = whirlpool.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    #[account(address = whirlpool.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == whirlpool.token_mint_a)]
+    pub token_owner_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, constraint = token_owner_account_b.mint == whirlpool.token_mint_b)]
+    pub token_owner_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, address = whirlpool.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = whirlpool.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: checked by the address constraint, used for instruction introspection
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    // remaining accounts
+    // - accounts for transfer hook program of token_mint_a
+    // - accounts for transfer hook program of token_mint_b
+}
+
+#[derive(Accounts)]
+pub struct FlashBorrow<'info> {
+    // The whirlpool must stay the first account, flash loan instructions are matched by it.
+    #[account(mut)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(address = *token_mint_a.to_account_info().owner)]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = *token_mint_b.to_account_info().owner)]
+    pub token_program_b: Interface<'info, TokenInterface>,
+
+    pub memo_program: Program<'info, Memo>,
+
+    pub borrower: Signer<'info>,
+
+    #[account(address = whirlpool.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    #[account(address = whirlpool.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == whirlpool.token_mint_a)]
+    pub token_owner_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, constraint = token_owner_account_b.mint == whirlpool.token_mint_b)]
+    pub token_owner_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, address = whirlpool.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = whirlpool.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: checked by the address constraint, used for instruction introspection
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"oracle", whirlpool.key().as_ref()], bump)]
+    /// CHECK: may not be initialized, the pause flags are read in the handler
+    pub oracle: UncheckedAccount<'info>,
+    // remaining accounts
+    // - accounts for transfer hook program of token_mint_a
+    // - accounts for transfer hook program of token_mint_b
+}
+
+/*
+  Lends tokens from the whirlpool vaults. A flash_repay instruction for the same whirlpool and
+  amounts must follow later in the same transaction.
+*/
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, FlashBorrow<'info>>,
+    amount_a: u64,
+    amount_b: u64,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    if amount_a == 0 && amount_b == 0 {
+        return Err(ErrorCode::ZeroFlashLoanAmount.into());
+    }
+
+    let oracle_accessor = OracleAccessor::new(
+        &ctx.accounts.whirlpool,
+        ctx.accounts.oracle.to_account_info(),
+    )?;
+    if oracle_accessor.is_paused()? {
+        return Err(ErrorCode::WhirlpoolPaused.into());
+    }
+
+    verify_flash_repay_follows(
+        &ctx.accounts.instructions_sysvar,
+        &ctx.accounts.whirlpool.key(),
+        amount_a,
+        amount_b,
+    )?;
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[AccountsType::TransferHookA, AccountsType::TransferHookB],
+    )?;
+
+    if amount_a > 0 {
+        transfer_from_vault_to_owner_v2(
+            &ctx.accounts.whirlpool,
+            &ctx.accounts.token_mint_a,
+            &ctx.accounts.token_vault_a,
+            &ctx.accounts.token_owner_account_a,
+            &ctx.accounts.token_program_a,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_a,
+            amount_a,
+            transfer_memo::TRANSFER_MEMO_FLASH_BORROW.as_bytes(),
+        )?;
+    }
+
+    if amount_b > 0 {
+        transfer_from_vault_to_owner_v2(
+            &ctx.accounts.whirlpool,
+            &ctx.accounts.token_mint_b,
+            &ctx.accounts.token_vault_b,
+            &ctx.accounts.token_owner_account_b,
+            &ctx.accounts.token_program_b,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_b,
+            amount_b,
+            transfer_memo::TRANSFER_MEMO_FLASH_BORROW.as_bytes(),
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::manager::flash_loan_manager::calculate_flash_loan_update;
+use crate::util::{
+    calculate_transfer_fee_included_amount, parse_remaining_accounts,
+    v2::transfer_from_owner_to_vault_v2, verify_flash_borrow_precedes, AccountsType,
+    RemainingAccountsInfo,
+};
+
+use super::flash_borrow::FlashLoan;
+
+/*
+  Repays a flash loan plus its fee to the whirlpool vaults and distributes the fee to liquidity
+  providers and the protocol.
+*/
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, FlashLoan<'info>>,
+    amount_a: u64,
+    amount_b: u64,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    if amount_a == 0 && amount_b == 0 {
+        return Err(ErrorCode::ZeroFlashLoanAmount.into());
+    }
+
+    verify_flash_borrow_precedes(
+        &ctx.accounts.instructions_sysvar,
+        &ctx.accounts.whirlpool.key(),
+        amount_a,
+        amount_b,
+    )?;
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[AccountsType::TransferHookA, AccountsType::TransferHookB],
+    )?;
+
+    let update = calculate_flash_loan_update(&ctx.accounts.whirlpool, amount_a, amount_b)?;
+
+    // The vaults must receive the full amount and fee, so the transfer fee is paid on top of it
+    let repay_amount_a = amount_a
+        .checked_add(update.fee_a)
+        .ok_or(ErrorCode::AmountCalcOverflow)?;
+    let repay_amount_b = amount_b
+        .checked_add(update.fee_b)
+        .ok_or(ErrorCode::AmountCalcOverflow)?;
+    let transfer_fee_included_repay_a =
+        calculate_transfer_fee_included_amount(&ctx.accounts.token_mint_a, repay_amount_a)?;
+    let transfer_fee_included_repay_b =
+        calculate_transfer_fee_included_amount(&ctx.accounts.token_mint_b, repay_amount_b)?;
+
+    if repay_amount_a > 0 {
+        transfer_from_owner_to_vault_v2(
+            &ctx.accounts.borrower,
+            &ctx.accounts.token_mint_a,
+            &ctx.accounts.token_owner_account_a,
+            &ctx.accounts.token_vault_a,
+            &ctx.accounts.token_program_a,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_a,
+            transfer_fee_included_repay_a.amount,
+        )?;
+    }
+
+    if repay_amount_b > 0 {
+        transfer_from_owner_to_vault_v2(
+            &ctx.accounts.borrower,
+            &ctx.accounts.token_mint_b,
+            &ctx.accounts.token_owner_account_b,
+            &ctx.accounts.token_vault_b,
+            &ctx.accounts.token_program_b,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_b,
+            transfer_fee_included_repay_b.amount,
+        )?;
+    }
+
+    ctx.accounts.whirlpool.update_after_flash_loan(
+        update.next_fee_growth_global_a,
+        update.next_fee_growth_global_b,
+        update.protocol_fee_a,
+        update.protocol_fee_b,
+    )?;
+
+    emit!(FlashLoaned {
+        whirlpool: ctx.accounts.whirlpool.key(),
+        amount_a,
+        amount_b,
+        fee_a: update.fee_a,
+        fee_b: update.fee_b,
+        protocol_fee_a: update.protocol_fee_a,
+        protocol_fee_b: update.protocol_fee_b,
+    });
+
+    Ok(())
+}
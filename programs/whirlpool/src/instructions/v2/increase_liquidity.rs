@@ -15,7 +15,8 @@ use crate::util::{
     RemainingAccountsInfo,
 };
 use crate::util::{
-    to_timestamp_u64, v2::transfer_from_owner_to_vault_v2, verify_position_authority_interface,
+    to_timestamp_u64, v2::transfer_from_owner_to_vault_v2, verify_position_authority_or_delegate,
+    verify_whirlpool_not_paused,
 };
 
 #[derive(Accounts)]
@@ -73,11 +74,6 @@ pub fn handler<'info>(
     token_max_b: u64,
     remaining_accounts_info: Option<RemainingAccountsInfo>,
 ) -> Result<()> {
-    verify_position_authority_interface(
-        &ctx.accounts.position_token_account,
-        &ctx.accounts.position_authority,
-    )?;
-
     let clock = Clock::get()?;
 
     if liquidity_amount == 0 {
@@ -88,7 +84,23 @@ pub fn handler<'info>(
     let remaining_accounts = parse_remaining_accounts(
         ctx.remaining_accounts,
         &remaining_accounts_info,
-        &[AccountsType::TransferHookA, AccountsType::TransferHookB],
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::PositionDelegate,
+            AccountsType::Oracle,
+        ],
+    )?;
+
+    verify_whirlpool_not_paused(&ctx.accounts.whirlpool, remaining_accounts.oracle.as_ref())?;
+
+    verify_position_authority_or_delegate(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+        ctx.accounts.position.key(),
+        remaining_accounts.position_delegate.as_ref(),
+        PositionDelegate::PERMISSION_INCREASE_LIQUIDITY,
+        &[],
     )?;
 
     let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, true)?;
@@ -0,0 +1,39 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct InitializeReferral<'info> {
+    pub whirlpools_config: Box<Account<'info, WhirlpoolsConfig>>,
+
+    #[account(has_one = whirlpools_config)]
+    pub whirlpools_config_extension: Box<Account<'info, WhirlpoolsConfigExtension>>,
+
+    pub referrer: Signer<'info>,
+
+    #[account(init,
+      payer = funder,
+      seeds = [
+        b"referral",
+        whirlpools_config.key().as_ref(),
+        referrer.key().as_ref(),
+      ],
+      bump,
+      space = Referral::LEN)]
+    pub referral: Account<'info, Referral>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeReferral>, referral_fee_rate: u16) -> Result<()> {
+    ctx.accounts.referral.initialize(
+        ctx.accounts.whirlpools_config.key(),
+        ctx.accounts.referrer.key(),
+        referral_fee_rate,
+        ctx.accounts
+            .whirlpools_config_extension
+            .max_referral_fee_rate,
+    )
+}
@@ -1,3 +1,4 @@
+use crate::events::TokenBadgeInitialized;
 use crate::state::*;
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::Mint;
@@ -29,11 +30,58 @@ pub struct InitializeTokenBadge<'info> {
     pub funder: Signer<'info>,
 
     pub system_program: Program<'info, System>,
+
+    // Optional so that existing callers keep working. It must be passed if the mint has a history,
+    // otherwise the history keeps the status of the previous badge.
+    #[account(
+      mut,
+      seeds = [
+        b"token_badge_history",
+        whirlpools_config.key().as_ref(),
+        token_mint.key().as_ref(),
+      ],
+      bump,
+    )]
+    pub token_badge_history: Option<Box<Account<'info, TokenBadgeHistory>>>,
 }
 
 pub fn handler(ctx: Context<InitializeTokenBadge>) -> Result<()> {
-    ctx.accounts.token_badge.initialize(
-        ctx.accounts.whirlpools_config.key(),
-        ctx.accounts.token_mint.key(),
-    )
+    let whirlpools_config = ctx.accounts.whirlpools_config.key();
+    let token_mint = ctx.accounts.token_mint.key();
+
+    let token_badge = &mut ctx.accounts.token_badge;
+    token_badge.initialize(whirlpools_config, token_mint)?;
+
+    // Recorded like initialize_token_badge_with_history with the default attributes.
+    if let Some(token_badge_history) = &mut ctx.accounts.token_badge_history {
+        let token_badge_authority = ctx.accounts.token_badge_authority.key();
+        let timestamp = Clock::get()?.unix_timestamp;
+        token_badge.approve(
+            token_badge_authority,
+            timestamp,
+            None,
+            TokenBadgeAttributes::default(),
+        )?;
+        token_badge.enable_history();
+
+        token_badge_history.record_initialized(
+            token_badge_authority,
+            timestamp,
+            None,
+            token_badge.attributes_version,
+            token_badge.attributes,
+        );
+
+        emit!(TokenBadgeInitialized {
+            whirlpools_config,
+            token_mint,
+            token_badge_authority,
+            hook_submission: None,
+            attributes_version: token_badge.attributes_version,
+            requires_memo: token_badge.attributes.requires_memo,
+            max_transfer_fee_bps: token_badge.attributes.max_transfer_fee_bps,
+        });
+    }
+
+    Ok(())
 }
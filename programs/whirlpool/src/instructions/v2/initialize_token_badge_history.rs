@@ -0,0 +1,65 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+#[derive(Accounts)]
+pub struct InitializeTokenBadgeHistory<'info> {
+    pub whirlpools_config: Box<Account<'info, WhirlpoolsConfig>>,
+
+    #[account(has_one = whirlpools_config)]
+    pub whirlpools_config_extension: Box<Account<'info, WhirlpoolsConfigExtension>>,
+
+    #[account(address = whirlpools_config_extension.token_badge_authority)]
+    pub token_badge_authority: Signer<'info>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: checked in the handler, may not be initialized
+    #[account(
+      mut,
+      seeds = [
+        b"token_badge",
+        whirlpools_config.key().as_ref(),
+        token_mint.key().as_ref(),
+      ],
+      bump,
+    )]
+    pub token_badge: UncheckedAccount<'info>,
+
+    #[account(init,
+      payer = funder,
+      seeds = [
+        b"token_badge_history",
+        whirlpools_config.key().as_ref(),
+        token_mint.key().as_ref(),
+      ],
+      bump,
+      space = TokenBadgeHistory::LEN)]
+    pub token_badge_history: Box<Account<'info, TokenBadgeHistory>>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeTokenBadgeHistory>) -> Result<()> {
+    let token_badge = &ctx.accounts.token_badge;
+    let token_badge_exists = token_badge.owner == &crate::ID && !token_badge.data_is_empty();
+
+    // An existing badge is marked so that it can only be deleted with a tombstone from now on.
+    if token_badge_exists {
+        let mut data = token_badge.try_borrow_mut_data()?;
+        let mut token_badge = TokenBadge::try_deserialize(&mut &data[..])?;
+        token_badge.enable_history();
+        token_badge.try_serialize(&mut &mut data[..])?;
+    }
+
+    ctx.accounts.token_badge_history.initialize(
+        ctx.accounts.whirlpools_config.key(),
+        ctx.accounts.token_mint.key(),
+        token_badge_exists,
+    );
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use crate::events::TokenBadgeInitialized;
+use crate::state::*;
+use crate::util::verify_hook_submission;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+#[derive(Accounts)]
+pub struct InitializeTokenBadgeWithHistory<'info> {
+    pub whirlpools_config: Box<Account<'info, WhirlpoolsConfig>>,
+
+    #[account(has_one = whirlpools_config)]
+    pub whirlpools_config_extension: Box<Account<'info, WhirlpoolsConfigExtension>>,
+
+    #[account(address = whirlpools_config_extension.token_badge_authority)]
+    pub token_badge_authority: Signer<'info>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(init,
+      payer = funder,
+      seeds = [
+        b"token_badge",
+        whirlpools_config.key().as_ref(),
+        token_mint.key().as_ref(),
+      ],
+      bump,
+      space = TokenBadge::LEN)]
+    pub token_badge: Account<'info, TokenBadge>,
+
+    #[account(
+      mut,
+      seeds = [
+        b"token_badge_history",
+        whirlpools_config.key().as_ref(),
+        token_mint.key().as_ref(),
+      ],
+      bump,
+    )]
+    pub token_badge_history: Box<Account<'info, TokenBadgeHistory>>,
+
+    /// CHECK: checked in the handler, must be an approved submission of the transfer hook registry
+    pub hook_submission: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<InitializeTokenBadgeWithHistory>,
+    attributes: TokenBadgeAttributes,
+) -> Result<()> {
+    let whirlpools_config = ctx.accounts.whirlpools_config.key();
+    let token_mint = ctx.accounts.token_mint.key();
+    let token_badge_authority = ctx.accounts.token_badge_authority.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    let hook_submission = match &ctx.accounts.hook_submission {
+        Some(hook_submission) => {
+            verify_hook_submission(&ctx.accounts.token_mint, hook_submission)?;
+            Some(hook_submission.key())
+        }
+        None => None,
+    };
+
+    let token_badge = &mut ctx.accounts.token_badge;
+    token_badge.initialize(whirlpools_config, token_mint)?;
+    token_badge.approve(
+        token_badge_authority,
+        timestamp,
+        hook_submission,
+        attributes,
+    )?;
+    token_badge.enable_history();
+
+    ctx.accounts.token_badge_history.record_initialized(
+        token_badge_authority,
+        timestamp,
+        hook_submission,
+        token_badge.attributes_version,
+        token_badge.attributes,
+    );
+
+    emit!(TokenBadgeInitialized {
+        whirlpools_config,
+        token_mint,
+        token_badge_authority,
+        hook_submission,
+        attributes_version: token_badge.attributes_version,
+        requires_memo: token_badge.attributes.requires_memo,
+        max_transfer_fee_bps: token_badge.attributes.max_transfer_fee_bps,
+    });
+
+    Ok(())
+}
@@ -3,7 +3,10 @@
 pub mod collect_fees;
 pub mod collect_protocol_fees;
 pub mod collect_reward;
+pub mod compound_fees_and_rewards;
 pub mod decrease_liquidity;
+pub mod flash_borrow;
+pub mod flash_repay;
 pub mod increase_liquidity;
 pub mod initialize_pool;
 pub mod initialize_reward;
@@ -12,14 +15,30 @@ pub mod swap;
 pub mod two_hop_swap;
 
 pub mod delete_token_badge;
+pub mod delete_token_badge_with_history;
 pub mod initialize_config_extension;
+pub mod initialize_referral;
 pub mod initialize_token_badge;
+pub mod initialize_token_badge_history;
+pub mod initialize_token_badge_with_history;
 pub mod set_config_extension_authority;
+pub mod set_config_pause;
+pub mod set_emergency_authority;
+pub mod set_max_referral_fee_rate;
+pub mod set_protocol_fee_split;
+pub mod set_referral_fee_rate;
+pub mod set_token_badge_attributes;
 pub mod set_token_badge_authority;
+pub mod set_whirlpool_pause;
+pub mod sweep_protocol_fees;
+pub mod sync_whirlpool_pause;
 
 pub use collect_fees::*;
 pub use collect_protocol_fees::*;
 pub use collect_reward::*;
+pub use compound_fees_and_rewards::*;
+pub use flash_borrow::*;
+pub use flash_repay::*;
 pub use increase_liquidity::*;
 pub use initialize_pool::*;
 pub use initialize_reward::*;
@@ -28,7 +47,20 @@ pub use swap::*;
 pub use two_hop_swap::*;
 
 pub use delete_token_badge::*;
+pub use delete_token_badge_with_history::*;
 pub use initialize_config_extension::*;
+pub use initialize_referral::*;
 pub use initialize_token_badge::*;
+pub use initialize_token_badge_history::*;
+pub use initialize_token_badge_with_history::*;
 pub use set_config_extension_authority::*;
+pub use set_config_pause::*;
+pub use set_emergency_authority::*;
+pub use set_max_referral_fee_rate::*;
+pub use set_protocol_fee_split::*;
+pub use set_referral_fee_rate::*;
+pub use set_token_badge_attributes::*;
 pub use set_token_badge_authority::*;
+pub use set_whirlpool_pause::*;
+pub use sweep_protocol_fees::*;
+pub use sync_whirlpool_pause::*;
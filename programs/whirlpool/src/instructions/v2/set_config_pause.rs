@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{WhirlpoolsConfig, WhirlpoolsConfigExtension};
+
+#[derive(Accounts)]
+pub struct SetConfigPause<'info> {
+    pub whirlpools_config: Box<Account<'info, WhirlpoolsConfig>>,
+
+    #[account(mut, has_one = whirlpools_config)]
+    pub whirlpools_config_extension: Account<'info, WhirlpoolsConfigExtension>,
+
+    #[account(address = whirlpools_config_extension.emergency_authority)]
+    pub emergency_authority: Signer<'info>,
+}
+
+/// Pause or unpause all Whirlpools of the config. Only the emergency authority has permission to invoke this instruction.
+/// The pause state takes effect on each Whirlpool once it is synced with `sync_whirlpool_pause`.
+pub fn handler(ctx: Context<SetConfigPause>, paused: bool) -> Result<()> {
+    ctx.accounts
+        .whirlpools_config_extension
+        .update_paused(paused);
+    Ok(())
+}
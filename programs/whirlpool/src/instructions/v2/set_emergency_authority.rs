@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{WhirlpoolsConfig, WhirlpoolsConfigExtension};
+
+#[derive(Accounts)]
+pub struct SetEmergencyAuthority<'info> {
+    pub whirlpools_config: Box<Account<'info, WhirlpoolsConfig>>,
+
+    #[account(mut, has_one = whirlpools_config)]
+    pub whirlpools_config_extension: Account<'info, WhirlpoolsConfigExtension>,
+
+    #[account(address = whirlpools_config_extension.config_extension_authority)]
+    pub config_extension_authority: Signer<'info>,
+
+    /// CHECK: safe, the account that will be new authority can be arbitrary
+    pub new_emergency_authority: UncheckedAccount<'info>,
+}
+
+/// Set the emergency authority. Only the config extension authority has permission to invoke this instruction.
+pub fn handler(ctx: Context<SetEmergencyAuthority>) -> Result<()> {
+    ctx.accounts
+        .whirlpools_config_extension
+        .update_emergency_authority(ctx.accounts.new_emergency_authority.key());
+    Ok(())
+}
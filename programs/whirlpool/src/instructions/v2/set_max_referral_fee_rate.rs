@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{WhirlpoolsConfig, WhirlpoolsConfigExtension};
+
+#[derive(Accounts)]
+pub struct SetMaxReferralFeeRate<'info> {
+    pub whirlpools_config: Box<Account<'info, WhirlpoolsConfig>>,
+
+    #[account(mut, has_one = whirlpools_config)]
+    pub whirlpools_config_extension: Account<'info, WhirlpoolsConfigExtension>,
+
+    #[account(address = whirlpools_config_extension.config_extension_authority)]
+    pub config_extension_authority: Signer<'info>,
+}
+
+/// Set the maximum referral fee rate. Only the config extension authority has permission to invoke this instruction.
+pub fn handler(ctx: Context<SetMaxReferralFeeRate>, max_referral_fee_rate: u16) -> Result<()> {
+    ctx.accounts
+        .whirlpools_config_extension
+        .update_max_referral_fee_rate(max_referral_fee_rate)
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{ProtocolFeeRecipient, WhirlpoolsConfig, WhirlpoolsConfigExtension};
+
+#[derive(Accounts)]
+pub struct SetProtocolFeeSplit<'info> {
+    pub whirlpools_config: Box<Account<'info, WhirlpoolsConfig>>,
+
+    #[account(mut, has_one = whirlpools_config)]
+    pub whirlpools_config_extension: Account<'info, WhirlpoolsConfigExtension>,
+
+    #[account(address = whirlpools_config.collect_protocol_fees_authority)]
+    pub collect_protocol_fees_authority: Signer<'info>,
+}
+
+/// Set the recipients of swept protocol fees. Only the collect protocol fees authority has permission to invoke this instruction.
+pub fn handler(
+    ctx: Context<SetProtocolFeeSplit>,
+    protocol_fee_recipients: Vec<ProtocolFeeRecipient>,
+) -> Result<()> {
+    ctx.accounts
+        .whirlpools_config_extension
+        .update_protocol_fee_recipients(&protocol_fee_recipients)
+}
@@ -0,0 +1,25 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetReferralFeeRate<'info> {
+    pub whirlpools_config: Box<Account<'info, WhirlpoolsConfig>>,
+
+    #[account(has_one = whirlpools_config)]
+    pub whirlpools_config_extension: Box<Account<'info, WhirlpoolsConfigExtension>>,
+
+    pub referrer: Signer<'info>,
+
+    #[account(mut, has_one = whirlpools_config, has_one = referrer)]
+    pub referral: Account<'info, Referral>,
+}
+
+/// Set the referral fee rate. Only the referrer has permission to invoke this instruction.
+pub fn handler(ctx: Context<SetReferralFeeRate>, referral_fee_rate: u16) -> Result<()> {
+    ctx.accounts.referral.update_referral_fee_rate(
+        referral_fee_rate,
+        ctx.accounts
+            .whirlpools_config_extension
+            .max_referral_fee_rate,
+    )
+}
@@ -0,0 +1,72 @@
+use crate::events::TokenBadgeAttributesUpdated;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+#[derive(Accounts)]
+pub struct SetTokenBadgeAttributes<'info> {
+    pub whirlpools_config: Box<Account<'info, WhirlpoolsConfig>>,
+
+    #[account(has_one = whirlpools_config)]
+    pub whirlpools_config_extension: Box<Account<'info, WhirlpoolsConfigExtension>>,
+
+    #[account(address = whirlpools_config_extension.token_badge_authority)]
+    pub token_badge_authority: Signer<'info>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+      mut,
+      seeds = [
+        b"token_badge",
+        whirlpools_config.key().as_ref(),
+        token_mint.key().as_ref(),
+      ],
+      bump,
+      has_one = whirlpools_config,
+    )]
+    pub token_badge: Account<'info, TokenBadge>,
+
+    #[account(
+      mut,
+      seeds = [
+        b"token_badge_history",
+        whirlpools_config.key().as_ref(),
+        token_mint.key().as_ref(),
+      ],
+      bump,
+    )]
+    pub token_badge_history: Box<Account<'info, TokenBadgeHistory>>,
+}
+
+/// Set the attributes of a token badge. Every change creates a new version in the token badge history.
+pub fn handler(
+    ctx: Context<SetTokenBadgeAttributes>,
+    attributes: TokenBadgeAttributes,
+) -> Result<()> {
+    let whirlpools_config = ctx.accounts.whirlpools_config.key();
+    let token_mint = ctx.accounts.token_mint.key();
+    let token_badge_authority = ctx.accounts.token_badge_authority.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    let token_badge = &mut ctx.accounts.token_badge;
+    token_badge.update_attributes(attributes)?;
+
+    ctx.accounts.token_badge_history.record_attributes_updated(
+        token_badge_authority,
+        timestamp,
+        token_badge.attributes_version,
+        token_badge.attributes,
+    );
+
+    emit!(TokenBadgeAttributesUpdated {
+        whirlpools_config,
+        token_mint,
+        token_badge_authority,
+        attributes_version: token_badge.attributes_version,
+        requires_memo: token_badge.attributes.requires_memo,
+        max_transfer_fee_bps: token_badge.attributes.max_transfer_fee_bps,
+    });
+
+    Ok(())
+}
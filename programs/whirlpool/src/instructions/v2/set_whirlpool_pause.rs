@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Whirlpool, WhirlpoolsConfig, WhirlpoolsConfigExtension, PAUSE_FLAG_WHIRLPOOL};
+use crate::util::update_oracle_pause_flag;
+
+#[derive(Accounts)]
+pub struct SetWhirlpoolPause<'info> {
+    pub whirlpools_config: Box<Account<'info, WhirlpoolsConfig>>,
+
+    #[account(has_one = whirlpools_config)]
+    pub whirlpools_config_extension: Box<Account<'info, WhirlpoolsConfigExtension>>,
+
+    #[account(address = whirlpools_config_extension.emergency_authority)]
+    pub emergency_authority: Signer<'info>,
+
+    #[account(has_one = whirlpools_config)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(mut, seeds = [b"oracle", whirlpool.key().as_ref()], bump)]
+    /// CHECK: initialized in the handler if needed
+    pub oracle: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Pause or unpause a Whirlpool. Only the emergency authority has permission to invoke this instruction.
+pub fn handler(ctx: Context<SetWhirlpoolPause>, paused: bool) -> Result<()> {
+    update_oracle_pause_flag(
+        &ctx.accounts.whirlpool,
+        &ctx.accounts.oracle,
+        ctx.bumps.oracle,
+        &ctx.accounts.funder,
+        &ctx.accounts.system_program,
+        PAUSE_FLAG_WHIRLPOOL,
+        paused,
+    )
+}
@@ -10,7 +10,8 @@ use crate::{
     state::*,
     util::{
         calculate_transfer_fee_excluded_amount, calculate_transfer_fee_included_amount,
-        parse_remaining_accounts, to_timestamp_u64, v2::update_and_swap_whirlpool_v2, AccountsType,
+        get_remaining_accounts_slice, load_referral_accounts, parse_remaining_accounts,
+        pay_referral_fee_v2, to_timestamp_u64, v2::update_and_swap_whirlpool_v2, AccountsType,
         RemainingAccountsInfo, SparseSwapTickSequenceBuilder, SwapTickSequence,
     },
 };
@@ -63,10 +64,14 @@ pub struct SwapV2<'info> {
     // - accounts for transfer hook program of token_mint_a
     // - accounts for transfer hook program of token_mint_b
     // - supplemental TickArray accounts
+    // - referral accounts (optional)
+    //   - WhirlpoolsConfigExtension
+    //   - Referral
+    //   - token account of the referrer for the input token
 }
 
 pub fn handler<'info>(
-    ctx: Context<'_, '_, '_, 'info, SwapV2<'info>>,
+    ctx: Context<'_, '_, 'info, 'info, SwapV2<'info>>,
     amount: u64,
     other_amount_threshold: u64,
     sqrt_price_limit: u128,
@@ -87,9 +92,25 @@ pub fn handler<'info>(
             AccountsType::TransferHookA,
             AccountsType::TransferHookB,
             AccountsType::SupplementalTickArrays,
+            AccountsType::Referral,
         ],
     )?;
 
+    let input_token_mint = if a_to_b {
+        whirlpool.token_mint_a
+    } else {
+        whirlpool.token_mint_b
+    };
+    let referral_accounts = load_referral_accounts(
+        get_remaining_accounts_slice(
+            ctx.remaining_accounts,
+            &remaining_accounts_info,
+            AccountsType::Referral,
+        ),
+        whirlpool,
+        input_token_mint,
+    )?;
+
     let swap_tick_sequence_builder = SparseSwapTickSequenceBuilder::new(
         vec![
             ctx.accounts.tick_array_0.to_account_info(),
@@ -101,9 +122,13 @@ pub fn handler<'info>(
     let mut swap_tick_sequence = swap_tick_sequence_builder.try_build(whirlpool, a_to_b)?;
 
     let oracle_accessor = OracleAccessor::new(whirlpool, ctx.accounts.oracle.to_account_info())?;
+    if oracle_accessor.is_paused()? {
+        return Err(ErrorCode::WhirlpoolPaused.into());
+    }
     if !oracle_accessor.is_trade_enabled(timestamp)? {
         return Err(ErrorCode::TradeIsNotEnabled.into());
     }
+    oracle_accessor.apply_fee_rate_schedule(whirlpool, timestamp)?;
     let adaptive_fee_info = oracle_accessor.get_adaptive_fee_info()?;
 
     let swap_update = swap_with_transfer_fee_extension(
@@ -148,6 +173,11 @@ pub fn handler<'info>(
     }
 
     oracle_accessor.update_adaptive_fee_variables(&swap_update.next_adaptive_fee_info)?;
+    oracle_accessor.write_observation(
+        timestamp,
+        whirlpool.tick_current_index,
+        whirlpool.sqrt_price,
+    )?;
 
     let pre_sqrt_price = whirlpool.sqrt_price;
     let (input_amount, output_amount) = if a_to_b {
@@ -186,6 +216,33 @@ pub fn handler<'info>(
         transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
     )?;
 
+    if let Some(referral_accounts) = &referral_accounts {
+        let (token_vault_input, token_program_input, transfer_hook_input) = if a_to_b {
+            (
+                &ctx.accounts.token_vault_a,
+                &ctx.accounts.token_program_a,
+                &remaining_accounts.transfer_hook_a,
+            )
+        } else {
+            (
+                &ctx.accounts.token_vault_b,
+                &ctx.accounts.token_program_b,
+                &remaining_accounts.transfer_hook_b,
+            )
+        };
+        pay_referral_fee_v2(
+            whirlpool,
+            referral_accounts,
+            token_mint_input,
+            token_vault_input,
+            token_program_input,
+            &ctx.accounts.memo_program,
+            transfer_hook_input,
+            protocol_fee,
+            a_to_b,
+        )?;
+    }
+
     emit!(Traded {
         whirlpool: whirlpool.key(),
         a_to_b,
@@ -0,0 +1,134 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::transfer_memo;
+use crate::errors::ErrorCode;
+use crate::events::ProtocolFeesSwept;
+use crate::state::{Whirlpool, WhirlpoolsConfigExtension};
+use crate::util::{
+    get_remaining_accounts_slice, parse_remaining_accounts, transfer_from_vault_to_owner_v2,
+    AccountsType, RemainingAccountsInfo,
+};
+
+#[derive(Accounts)]
+pub struct SweepProtocolFees<'info> {
+    pub whirlpools_config_extension: Box<Account<'info, WhirlpoolsConfigExtension>>,
+
+    #[account(mut, constraint = whirlpool.whirlpools_config == whirlpools_config_extension.whirlpools_config)]
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(address = whirlpool.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    #[account(address = whirlpool.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = whirlpool.token_vault_a)]
+    pub token_vault_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = whirlpool.token_vault_b)]
+    pub token_vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = *token_mint_a.to_account_info().owner)]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = *token_mint_b.to_account_info().owner)]
+    pub token_program_b: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+    // remaining accounts
+    // - accounts for transfer hook program of token_mint_a
+    // - accounts for transfer hook program of token_mint_b
+    // - token accounts for token_mint_a and token_mint_b of each protocol fee recipient, in order
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SweepProtocolFees<'info>>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let whirlpools_config_extension = &ctx.accounts.whirlpools_config_extension;
+    let recipient_count = whirlpools_config_extension.protocol_fee_recipient_count();
+    if recipient_count == 0 {
+        return Err(ErrorCode::InvalidProtocolFeeSplit.into());
+    }
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::ProtocolFeeRecipients,
+        ],
+    )?;
+    // The recipient token accounts are loaded into InterfaceAccounts, so they are borrowed from the
+    // instruction remaining accounts rather than from the parsed copies.
+    let recipient_account_infos = get_remaining_accounts_slice(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        AccountsType::ProtocolFeeRecipients,
+    )
+    .unwrap_or(&[]);
+    if recipient_account_infos.len() != recipient_count * 2 {
+        return Err(ErrorCode::InvalidProtocolFeeRecipientAccounts.into());
+    }
+
+    let whirlpool = &ctx.accounts.whirlpool;
+    let protocol_fee_a = whirlpool.protocol_fee_owed_a;
+    let protocol_fee_b = whirlpool.protocol_fee_owed_b;
+    let shares_a = whirlpools_config_extension.split_protocol_fee(protocol_fee_a);
+    let shares_b = whirlpools_config_extension.split_protocol_fee(protocol_fee_b);
+
+    for (i, recipient) in whirlpools_config_extension.protocol_fee_recipients[..recipient_count]
+        .iter()
+        .enumerate()
+    {
+        let token_account_a =
+            InterfaceAccount::<TokenAccount>::try_from(&recipient_account_infos[i * 2])?;
+        let token_account_b =
+            InterfaceAccount::<TokenAccount>::try_from(&recipient_account_infos[i * 2 + 1])?;
+        if token_account_a.owner != recipient.owner
+            || token_account_a.mint != whirlpool.token_mint_a
+            || token_account_b.owner != recipient.owner
+            || token_account_b.mint != whirlpool.token_mint_b
+        {
+            return Err(ErrorCode::InvalidProtocolFeeRecipientAccounts.into());
+        }
+
+        if shares_a[i] > 0 {
+            transfer_from_vault_to_owner_v2(
+                whirlpool,
+                &ctx.accounts.token_mint_a,
+                &ctx.accounts.token_vault_a,
+                &token_account_a,
+                &ctx.accounts.token_program_a,
+                &ctx.accounts.memo_program,
+                &remaining_accounts.transfer_hook_a,
+                shares_a[i],
+                transfer_memo::TRANSFER_MEMO_SWEEP_PROTOCOL_FEES.as_bytes(),
+            )?;
+        }
+
+        if shares_b[i] > 0 {
+            transfer_from_vault_to_owner_v2(
+                whirlpool,
+                &ctx.accounts.token_mint_b,
+                &ctx.accounts.token_vault_b,
+                &token_account_b,
+                &ctx.accounts.token_program_b,
+                &ctx.accounts.memo_program,
+                &remaining_accounts.transfer_hook_b,
+                shares_b[i],
+                transfer_memo::TRANSFER_MEMO_SWEEP_PROTOCOL_FEES.as_bytes(),
+            )?;
+        }
+    }
+
+    emit!(ProtocolFeesSwept {
+        whirlpool: whirlpool.key(),
+        protocol_fee_a,
+        protocol_fee_b,
+    });
+
+    ctx.accounts.whirlpool.reset_protocol_fees_owed();
+    Ok(())
+}
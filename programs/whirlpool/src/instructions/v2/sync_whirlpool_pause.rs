@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Whirlpool, WhirlpoolsConfigExtension, PAUSE_FLAG_CONFIG};
+use crate::util::update_oracle_pause_flag;
+
+#[derive(Accounts)]
+pub struct SyncWhirlpoolPause<'info> {
+    pub whirlpools_config_extension: Box<Account<'info, WhirlpoolsConfigExtension>>,
+
+    #[account(constraint = whirlpool.whirlpools_config == whirlpools_config_extension.whirlpools_config)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(mut, seeds = [b"oracle", whirlpool.key().as_ref()], bump)]
+    /// CHECK: initialized in the handler if needed
+    pub oracle: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Apply the pause state of the config to a Whirlpool. Anyone can invoke this instruction.
+pub fn handler(ctx: Context<SyncWhirlpoolPause>) -> Result<()> {
+    update_oracle_pause_flag(
+        &ctx.accounts.whirlpool,
+        &ctx.accounts.oracle,
+        ctx.bumps.oracle,
+        &ctx.accounts.funder,
+        &ctx.accounts.system_program,
+        PAUSE_FLAG_CONFIG,
+        ctx.accounts.whirlpools_config_extension.paused,
+    )
+}
@@ -9,7 +9,8 @@ use crate::{
     events::*,
     state::{OracleAccessor, Whirlpool},
     util::{
-        calculate_transfer_fee_excluded_amount, parse_remaining_accounts, to_timestamp_u64,
+        calculate_transfer_fee_excluded_amount, get_remaining_accounts_slice,
+        load_referral_accounts, parse_remaining_accounts, pay_referral_fee_v2, to_timestamp_u64,
         update_and_two_hop_swap_whirlpool_v2, AccountsType, RemainingAccountsInfo,
         SparseSwapTickSequenceBuilder,
     },
@@ -98,11 +99,15 @@ pub struct TwoHopSwapV2<'info> {
     // - accounts for transfer hook program of token_mint_output
     // - supplemental TickArray accounts for whirlpool_one
     // - supplemental TickArray accounts for whirlpool_two
+    // - referral accounts (optional, applied to the swap on whirlpool_one)
+    //   - WhirlpoolsConfigExtension
+    //   - Referral
+    //   - token account of the referrer for token_mint_input
 }
 
 #[allow(clippy::too_many_arguments)]
 pub fn handler<'info>(
-    ctx: Context<'_, '_, '_, 'info, TwoHopSwapV2<'info>>,
+    ctx: Context<'_, '_, 'info, 'info, TwoHopSwapV2<'info>>,
     amount: u64,
     other_amount_threshold: u64,
     amount_specified_is_input: bool,
@@ -149,9 +154,20 @@ pub fn handler<'info>(
             AccountsType::TransferHookOutput,
             AccountsType::SupplementalTickArraysOne,
             AccountsType::SupplementalTickArraysTwo,
+            AccountsType::Referral,
         ],
     )?;
 
+    let referral_accounts = load_referral_accounts(
+        get_remaining_accounts_slice(
+            ctx.remaining_accounts,
+            &remaining_accounts_info,
+            AccountsType::Referral,
+        ),
+        whirlpool_one,
+        whirlpool_one.input_token_mint(a_to_b_one),
+    )?;
+
     let swap_tick_sequence_one = SparseSwapTickSequenceBuilder::new(
         vec![
             ctx.accounts.tick_array_one_0.to_account_info(),
@@ -174,16 +190,24 @@ pub fn handler<'info>(
 
     let oracle_accessor_one =
         OracleAccessor::new(whirlpool_one, ctx.accounts.oracle_one.to_account_info())?;
+    if oracle_accessor_one.is_paused()? {
+        return Err(ErrorCode::WhirlpoolPaused.into());
+    }
     if !oracle_accessor_one.is_trade_enabled(timestamp)? {
         return Err(ErrorCode::TradeIsNotEnabled.into());
     }
+    oracle_accessor_one.apply_fee_rate_schedule(whirlpool_one, timestamp)?;
     let adaptive_fee_info_one = oracle_accessor_one.get_adaptive_fee_info()?;
 
     let oracle_accessor_two =
         OracleAccessor::new(whirlpool_two, ctx.accounts.oracle_two.to_account_info())?;
+    if oracle_accessor_two.is_paused()? {
+        return Err(ErrorCode::WhirlpoolPaused.into());
+    }
     if !oracle_accessor_two.is_trade_enabled(timestamp)? {
         return Err(ErrorCode::TradeIsNotEnabled.into());
     }
+    oracle_accessor_two.apply_fee_rate_schedule(whirlpool_two, timestamp)?;
     let adaptive_fee_info_two = oracle_accessor_two.get_adaptive_fee_info()?;
 
     // TODO: WLOG, we could extend this to N-swaps, but the account inputs to the instruction would
@@ -355,8 +379,18 @@ pub fn handler<'info>(
     }
 
     oracle_accessor_one.update_adaptive_fee_variables(&swap_update_one.next_adaptive_fee_info)?;
+    oracle_accessor_one.write_observation(
+        timestamp,
+        whirlpool_one.tick_current_index,
+        whirlpool_one.sqrt_price,
+    )?;
 
     oracle_accessor_two.update_adaptive_fee_variables(&swap_update_two.next_adaptive_fee_info)?;
+    oracle_accessor_two.write_observation(
+        timestamp,
+        whirlpool_two.tick_current_index,
+        whirlpool_two.sqrt_price,
+    )?;
 
     let pre_sqrt_price_one = whirlpool_one.sqrt_price;
     let (input_amount_one, output_amount_one) = if a_to_b_one {
@@ -420,6 +454,20 @@ pub fn handler<'info>(
         transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
     )?;
 
+    if let Some(referral_accounts) = &referral_accounts {
+        pay_referral_fee_v2(
+            whirlpool_one,
+            referral_accounts,
+            &ctx.accounts.token_mint_input,
+            &ctx.accounts.token_vault_one_input,
+            &ctx.accounts.token_program_input,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_input,
+            protocol_fee_one,
+            a_to_b_one,
+        )?;
+    }
+
     emit!(Traded {
         whirlpool: whirlpool_one.key(),
         a_to_b: a_to_b_one,
@@ -22,7 +22,10 @@ pub mod tests;
 #[doc(hidden)]
 pub mod util;
 
-use crate::state::{LockType, OpenPositionBumps, OpenPositionWithMetadataBumps, WhirlpoolBumps};
+use crate::state::{
+    LockType, OpenPositionBumps, OpenPositionWithMetadataBumps, OracleTwap, ProtocolFeeRecipient,
+    TokenBadgeAttributes, WhirlpoolBumps,
+};
 use crate::util::RemainingAccountsInfo;
 use instructions::*;
 
@@ -230,6 +233,7 @@ pub mod whirlpool {
     /// - `LiquidityZero` - Provided liquidity amount is zero.
     /// - `LiquidityTooHigh` - Provided liquidity exceeds u128::max.
     /// - `TokenMaxExceeded` - The required token to perform this operation exceeds the user defined amount.
+    /// - `WhirlpoolPaused` - The Oracle passed as the first remaining account has a pause flag set.
     pub fn increase_liquidity(
         ctx: Context<ModifyLiquidity>,
         liquidity_amount: u128,
@@ -253,8 +257,10 @@ pub mod whirlpool {
     /// - `LiquidityZero` - Provided liquidity amount is zero.
     /// - `LiquidityTooHigh` - Provided liquidity exceeds u128::max.
     /// - `TokenMinSubceeded` - The required token to perform this operation subceeds the user defined amount.
-    pub fn decrease_liquidity(
-        ctx: Context<ModifyLiquidity>,
+    /// - `OperationNotAllowedOnLockedPosition` - The position is locked and the LockConfig is missing
+    ///                                           or does not allow withdrawing the liquidity amount.
+    pub fn decrease_liquidity<'info>(
+        ctx: Context<'_, '_, '_, 'info, ModifyLiquidity<'info>>,
         liquidity_amount: u128,
         token_min_a: u64,
         token_min_b: u64,
@@ -316,6 +322,7 @@ pub mod whirlpool {
     /// - `TickArrayIndexOutofBounds` - The swap loop attempted to access an invalid array index during tick crossing.
     /// - `LiquidityOverflow` - Liquidity value overflowed 128bits during tick crossing.
     /// - `InvalidTickSpacing` - The swap pool was initialized with tick-spacing of 0.
+    /// - `WhirlpoolPaused` - The Whirlpool or its WhirlpoolsConfig has been paused by the emergency authority.
     pub fn swap(
         ctx: Context<Swap>,
         amount: u64,
@@ -385,6 +392,8 @@ pub mod whirlpool {
 
     /// Sets the fee rate for a Whirlpool.
     /// Fee rate is represented as hundredths of a basis point.
+    /// Swaps stop applying a scheduled fee rate change that the new fee rate does not match,
+    /// and the change is cleared if the Oracle account is provided.
     /// Only the current fee authority has permission to invoke this instruction.
     ///
     /// ### Authority
@@ -399,6 +408,47 @@ pub mod whirlpool {
         instructions::set_fee_rate::handler(ctx, fee_rate)
     }
 
+    /// Schedules a fee rate change for a Whirlpool.
+    /// The fee rate moves linearly from the fee rate in effect to the target fee rate between
+    /// the ramp start and the effective timestamps, and is applied by swaps. Scheduling again
+    /// replaces the pending change. Only the current fee authority has permission to invoke this instruction.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority that can modify pool fees in the WhirlpoolConfig
+    ///
+    /// ### Parameters
+    /// - `target_fee_rate` - The fee rate that the pool will use from the effective timestamp onwards.
+    /// - `ramp_start_timestamp` - The timestamp at which the fee rate starts moving to the target fee rate.
+    /// - `effective_timestamp` - The timestamp at which the target fee rate is reached.
+    ///   Equal to the ramp start timestamp for a step change.
+    ///
+    /// #### Special Errors
+    /// - `InvalidFeeRateSchedule` - If the target fee rate exceeds MAX_FEE_RATE, the ramp starts in the past,
+    ///                              or the effective timestamp is before the ramp start timestamp.
+    pub fn set_fee_rate_schedule(
+        ctx: Context<SetFeeRateSchedule>,
+        target_fee_rate: u16,
+        ramp_start_timestamp: u64,
+        effective_timestamp: u64,
+    ) -> Result<()> {
+        instructions::set_fee_rate_schedule::handler(
+            ctx,
+            target_fee_rate,
+            ramp_start_timestamp,
+            effective_timestamp,
+        )
+    }
+
+    /// Cancels the scheduled fee rate change of a Whirlpool.
+    /// The fee rate reached so far by a ramp in progress is kept.
+    /// Only the current fee authority has permission to invoke this instruction.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority that can modify pool fees in the WhirlpoolConfig
+    pub fn cancel_fee_rate_schedule(ctx: Context<CancelFeeRateSchedule>) -> Result<()> {
+        instructions::cancel_fee_rate_schedule::handler(ctx)
+    }
+
     /// Sets the protocol fee rate for a Whirlpool.
     /// Protocol fee rate is represented as a basis point.
     /// Only the current fee authority has permission to invoke this instruction.
@@ -508,6 +558,7 @@ pub mod whirlpool {
     /// - `InvalidTickSpacing` - The swap pool was initialized with tick-spacing of 0.
     /// - `InvalidIntermediaryMint` - Error if the intermediary mint between hop one and two do not equal.
     /// - `DuplicateTwoHopPool` - Error if whirlpool one & two are the same pool.
+    /// - `WhirlpoolPaused` - The Whirlpool or its WhirlpoolsConfig has been paused by the emergency authority.
     #[allow(clippy::too_many_arguments)]
     pub fn two_hop_swap(
         ctx: Context<TwoHopSwap>,
@@ -646,14 +697,20 @@ pub mod whirlpool {
         instructions::close_position_with_token_extensions::handler(ctx)
     }
 
-    /// Lock the position to prevent any liquidity changes.
+    /// Lock the position to prevent liquidity from being withdrawn.
+    /// Fees and rewards can still be collected while the position is locked.
     ///
     /// ### Authority
     /// - `position_authority` - The authority that owns the position token.
     ///
+    /// ### Parameters
+    /// - `lock_type` - `Permanent`, `TimeLocked` until an unlock timestamp, or `Vesting` where the
+    ///                 locked liquidity becomes withdrawable linearly until the unlock timestamp.
+    ///
     /// #### Special Errors
     /// - `PositionAlreadyLocked` - The provided position is already locked.
     /// - `PositionNotLockable` - The provided position is not lockable (e.g. An empty position).
+    /// - `InvalidUnlockTimestamp` - The provided unlock timestamp is not in the future.
     pub fn lock_position(ctx: Context<LockPosition>, lock_type: LockType) -> Result<()> {
         instructions::lock_position::handler(ctx, lock_type)
     }
@@ -662,6 +719,7 @@ pub mod whirlpool {
     ///
     /// ### Authority
     /// - `position_authority` - The authority that owns the position token.
+    ///                         A PositionDelegate with the reset position range permission can be passed as a remaining account.
     ///
     /// ### Parameters
     /// - `new_tick_lower_index` - The new tick specifying the lower end of the position range.
@@ -672,8 +730,8 @@ pub mod whirlpool {
     ///                        the tick-spacing in this pool.
     /// - `ClosePositionNotEmpty` - The provided position account is not empty.
     /// - `SameTickRangeNotAllowed` - The provided tick range is the same as the current tick range.
-    pub fn reset_position_range(
-        ctx: Context<ResetPositionRange>,
+    pub fn reset_position_range<'info>(
+        ctx: Context<'_, '_, '_, 'info, ResetPositionRange<'info>>,
         new_tick_lower_index: i32,
         new_tick_upper_index: i32,
     ) -> Result<()> {
@@ -688,6 +746,88 @@ pub mod whirlpool {
         instructions::transfer_locked_position::handler(ctx)
     }
 
+    /// Unlock a time-locked or vesting position after its unlock timestamp and close the LockConfig.
+    ///
+    /// ### Authority
+    /// - `position_authority` - The authority that owns the position token.
+    ///
+    /// #### Special Errors
+    /// - `PositionNotUnlockable` - The position is permanently locked or the unlock timestamp has not been reached.
+    pub fn unlock_position(ctx: Context<UnlockPosition>) -> Result<()> {
+        instructions::unlock_position::handler(ctx)
+    }
+
+    /// Delegate scoped rights over a position to another authority, such as an automated
+    /// position manager. The delegate can never withdraw tokens to accounts that the position
+    /// owner does not own, and the delegation stops applying once the position token changes owner.
+    ///
+    /// ### Authority
+    /// - `position_authority` - The owner of the position token. The SPL token delegate is not allowed.
+    ///
+    /// ### Parameters
+    /// - `delegate` - The authority receiving the rights.
+    /// - `permissions` - Bit flags of the granted rights: collect fees (1), collect rewards (2),
+    ///                   decrease liquidity (4), increase liquidity (8) and reset position range (16).
+    ///
+    /// #### Special Errors
+    /// - `InvalidPositionDelegatePermissions` - No permission or an unknown permission is set.
+    pub fn set_position_delegate(
+        ctx: Context<SetPositionDelegate>,
+        delegate: Pubkey,
+        permissions: u16,
+    ) -> Result<()> {
+        instructions::set_position_delegate::handler(ctx, delegate, permissions)
+    }
+
+    /// Revoke the delegate of a position and close the PositionDelegate account.
+    ///
+    /// ### Authority
+    /// - `position_authority` - The owner of the position token or the delegate itself.
+    pub fn revoke_position_delegate(ctx: Context<RevokePositionDelegate>) -> Result<()> {
+        instructions::revoke_position_delegate::handler(ctx)
+    }
+
+    /// Open a range order on a position that is exactly one tick-spacing wide and entirely on one
+    /// side of the current price. A range above the price sells token A, a range below sells token B.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///
+    /// #### Special Errors
+    /// - `InvalidRangeOrderWidth` - The position is not exactly one tick-spacing wide.
+    /// - `InvalidRangeOrderRange` - The current price is inside the position range.
+    /// - `LiquidityZero` - The position is empty.
+    /// - `WhirlpoolPaused` - The Whirlpool or its WhirlpoolsConfig has been paused by the emergency authority.
+    pub fn open_range_order(ctx: Context<OpenRangeOrder>) -> Result<()> {
+        instructions::open_range_order::handler(ctx)
+    }
+
+    /// Withdraw all liquidity of a filled range order to the owner of the position token and
+    /// close the RangeOrder account. Fees and rewards stay in the position.
+    ///
+    /// ### Authority
+    /// - None. Anyone can settle a filled range order.
+    ///
+    /// #### Special Errors
+    /// - `RangeOrderNotFilled` - The current price is not beyond the far end of the order range,
+    ///                          including when it has moved back after crossing it.
+    /// - `OperationNotAllowedOnLockedPosition` - The position is locked and the LockConfig is missing
+    ///                                           or does not allow withdrawing the liquidity.
+    pub fn settle_range_order<'info>(
+        ctx: Context<'_, '_, '_, 'info, SettleRangeOrder<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::settle_range_order::handler(ctx, remaining_accounts_info)
+    }
+
+    /// Close a range order without withdrawing the liquidity of its position.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    pub fn cancel_range_order(ctx: Context<CancelRangeOrder>) -> Result<()> {
+        instructions::cancel_range_order::handler(ctx)
+    }
+
     /// Initializes an adaptive_fee_tier account usable by Whirlpools in a WhirlpoolConfig space.
     ///
     /// ### Authority
@@ -848,6 +988,8 @@ pub mod whirlpool {
 
     /// Sets the fee rate for a Whirlpool by the delegated fee authority in AdaptiveFeeTier.
     /// Fee rate is represented as hundredths of a basis point.
+    /// Swaps stop applying a scheduled fee rate change that the new fee rate does not match,
+    /// and the change is cleared if the Oracle account is provided.
     ///
     /// ### Authority
     /// - "delegated_fee_authority" - Set authority that can modify pool fees in the AdaptiveFeeTier
@@ -864,6 +1006,37 @@ pub mod whirlpool {
         instructions::set_fee_rate_by_delegated_fee_authority::handler(ctx, fee_rate)
     }
 
+    /// Increases the number of price observations stored in the Oracle account.
+    /// The account is reallocated and the funder pays for the additional rent.
+    /// The first call enables observations, which are then written on every swap that passes
+    /// the Oracle account as writable. Swaps with a read-only Oracle account skip the observation.
+    ///
+    /// ### Parameters
+    /// - `observation_cardinality_next` - The number of observations to store (up to 1024).
+    ///
+    /// #### Special Errors
+    /// - `InvalidObservationCardinality` - If the provided cardinality is not larger than the current one or exceeds the maximum.
+    pub fn increase_observation_cardinality(
+        ctx: Context<IncreaseObservationCardinality>,
+        observation_cardinality_next: u16,
+    ) -> Result<()> {
+        instructions::increase_observation_cardinality::handler(ctx, observation_cardinality_next)
+    }
+
+    /// Returns the time-weighted average tick index and sqrt-price over a window ending now.
+    /// The result is set as return data, so it can be read via CPI or transaction simulation.
+    ///
+    /// ### Parameters
+    /// - `window` - The length of the averaging window in seconds.
+    ///
+    /// #### Special Errors
+    /// - `OracleObservationsNotInitialized` - If observations have not been enabled for the Oracle.
+    /// - `OracleObservationTooOld` - If the window reaches further back than the oldest stored observation.
+    /// - `InvalidTwapWindow` - If the window is zero.
+    pub fn observe(ctx: Context<Observe>, window: u32) -> Result<OracleTwap> {
+        instructions::observe::handler(ctx, window)
+    }
+
     ////////////////////////////////////////////////////////////////////////////////
     // V2 instructions (TokenExtensions)
     ////////////////////////////////////////////////////////////////////////////////
@@ -874,6 +1047,7 @@ pub mod whirlpool {
     ///
     /// ### Authority
     /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///                         A PositionDelegate with the collect fees permission can be passed as a remaining account.
     pub fn collect_fees_v2<'info>(
         ctx: Context<'_, '_, '_, 'info, CollectFeesV2<'info>>,
         remaining_accounts_info: Option<RemainingAccountsInfo>,
@@ -896,6 +1070,7 @@ pub mod whirlpool {
     ///
     /// ### Authority
     /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///                         A PositionDelegate with the collect rewards permission can be passed as a remaining account.
     pub fn collect_reward_v2<'info>(
         ctx: Context<'_, '_, '_, 'info, CollectRewardV2<'info>>,
         reward_index: u8,
@@ -904,10 +1079,31 @@ pub mod whirlpool {
         instructions::v2::collect_reward::handler(ctx, reward_index, remaining_accounts_info)
     }
 
+    /// Add the fees owed to a position to its liquidity at the current price, without transferring
+    /// them out of the token vaults. Rewards denominated in token A or B are also compounded when
+    /// their reward vaults are passed as remaining accounts. Amounts that cannot be matched at the
+    /// current price remain owed to the position.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///                         A PositionDelegate with the compound fees permission can be passed as a remaining account.
+    ///
+    /// #### Special Errors
+    /// - `LiquidityZero` - The fees and rewards owed are not enough to add any liquidity.
+    /// - `InvalidCompoundRewardVault` - A reward vault does not belong to a reward denominated in token A or B.
+    /// - `WhirlpoolPaused` - The Whirlpool or its WhirlpoolsConfig has been paused by the emergency authority.
+    pub fn compound_fees_and_rewards<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CompoundFeesAndRewards<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::v2::compound_fees_and_rewards::handler(ctx, remaining_accounts_info)
+    }
+
     /// Withdraw liquidity from a position in the Whirlpool. This call also updates the position's accrued fees and rewards.
     ///
     /// ### Authority
     /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///                         A PositionDelegate with the decrease liquidity permission can be passed as a remaining account.
     ///
     /// ### Parameters
     /// - `liquidity_amount` - The total amount of Liquidity the user desires to withdraw.
@@ -918,6 +1114,8 @@ pub mod whirlpool {
     /// - `LiquidityZero` - Provided liquidity amount is zero.
     /// - `LiquidityTooHigh` - Provided liquidity exceeds u128::max.
     /// - `TokenMinSubceeded` - The required token to perform this operation subceeds the user defined amount.
+    /// - `OperationNotAllowedOnLockedPosition` - The position is locked and the LockConfig is missing
+    ///                                           or does not allow withdrawing the liquidity amount.
     pub fn decrease_liquidity_v2<'info>(
         ctx: Context<'_, '_, '_, 'info, ModifyLiquidityV2<'info>>,
         liquidity_amount: u128,
@@ -938,6 +1136,7 @@ pub mod whirlpool {
     ///
     /// ### Authority
     /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///                         A PositionDelegate with the increase liquidity permission can be passed as a remaining account.
     ///
     /// ### Parameters
     /// - `liquidity_amount` - The total amount of Liquidity the user is willing to deposit.
@@ -948,6 +1147,7 @@ pub mod whirlpool {
     /// - `LiquidityZero` - Provided liquidity amount is zero.
     /// - `LiquidityTooHigh` - Provided liquidity exceeds u128::max.
     /// - `TokenMaxExceeded` - The required token to perform this operation exceeds the user defined amount.
+    /// - `WhirlpoolPaused` - The Oracle passed in the remaining accounts has a pause flag set.
     pub fn increase_liquidity_v2<'info>(
         ctx: Context<'_, '_, '_, 'info, ModifyLiquidityV2<'info>>,
         liquidity_amount: u128,
@@ -1047,8 +1247,11 @@ pub mod whirlpool {
     /// - `TickArrayIndexOutofBounds` - The swap loop attempted to access an invalid array index during tick crossing.
     /// - `LiquidityOverflow` - Liquidity value overflowed 128bits during tick crossing.
     /// - `InvalidTickSpacing` - The swap pool was initialized with tick-spacing of 0.
+    /// - `WhirlpoolPaused` - The Whirlpool or its WhirlpoolsConfig has been paused by the emergency authority.
+    /// - `InvalidReferralAccounts` - The optional referral accounts do not belong to the WhirlpoolsConfig
+    ///                               or the referral token account is not the referrer's input token account.
     pub fn swap_v2<'info>(
-        ctx: Context<'_, '_, '_, 'info, SwapV2<'info>>,
+        ctx: Context<'_, '_, 'info, 'info, SwapV2<'info>>,
         amount: u64,
         other_amount_threshold: u64,
         sqrt_price_limit: u128,
@@ -1092,9 +1295,12 @@ pub mod whirlpool {
     /// - `InvalidTickSpacing` - The swap pool was initialized with tick-spacing of 0.
     /// - `InvalidIntermediaryMint` - Error if the intermediary mint between hop one and two do not equal.
     /// - `DuplicateTwoHopPool` - Error if whirlpool one & two are the same pool.
+    /// - `WhirlpoolPaused` - The Whirlpool or its WhirlpoolsConfig has been paused by the emergency authority.
+    /// - `InvalidReferralAccounts` - The optional referral accounts do not belong to the WhirlpoolsConfig
+    ///                               or the referral token account is not the referrer's input token account.
     #[allow(clippy::too_many_arguments)]
     pub fn two_hop_swap_v2<'info>(
-        ctx: Context<'_, '_, '_, 'info, TwoHopSwapV2<'info>>,
+        ctx: Context<'_, '_, 'info, 'info, TwoHopSwapV2<'info>>,
         amount: u64,
         other_amount_threshold: u64,
         amount_specified_is_input: bool,
@@ -1117,6 +1323,55 @@ pub mod whirlpool {
         )
     }
 
+    /// Borrow tokens from the vaults of this Whirlpool. The loan must be repaid with `flash_repay`
+    /// for the same Whirlpool and amounts later in the same transaction.
+    ///
+    /// ### Authority
+    /// - `borrower` - The authority receiving the borrowed tokens.
+    ///
+    /// ### Parameters
+    /// - `amount_a` - The amount of token A to borrow.
+    /// - `amount_b` - The amount of token B to borrow.
+    ///
+    /// #### Special Errors
+    /// - `ZeroFlashLoanAmount` - Both amounts are zero.
+    /// - `FlashLoanCpiNotAllowed` - The instruction is not invoked from the top level of the transaction.
+    /// - `FlashRepayNotFound` - The next flash loan instruction for this Whirlpool is not a `flash_repay`
+    ///                          of the same amounts.
+    /// - `WhirlpoolPaused` - The Whirlpool or its WhirlpoolsConfig has been paused by the emergency authority.
+    pub fn flash_borrow<'info>(
+        ctx: Context<'_, '_, '_, 'info, FlashBorrow<'info>>,
+        amount_a: u64,
+        amount_b: u64,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::v2::flash_borrow::handler(ctx, amount_a, amount_b, remaining_accounts_info)
+    }
+
+    /// Repay a flash loan plus a fee at the pool's fee rate. The fee is distributed to in-range
+    /// liquidity after the protocol fee is taken, the same way as swap fees.
+    ///
+    /// ### Authority
+    /// - `borrower` - The authority to withdraw the repaid tokens from the token owner accounts.
+    ///
+    /// ### Parameters
+    /// - `amount_a` - The amount of token A that was borrowed.
+    /// - `amount_b` - The amount of token B that was borrowed.
+    ///
+    /// #### Special Errors
+    /// - `ZeroFlashLoanAmount` - Both amounts are zero.
+    /// - `FlashLoanCpiNotAllowed` - The instruction is not invoked from the top level of the transaction.
+    /// - `FlashBorrowNotFound` - The previous flash loan instruction for this Whirlpool is not a
+    ///                           `flash_borrow` of the same amounts.
+    pub fn flash_repay<'info>(
+        ctx: Context<'_, '_, '_, 'info, FlashLoan<'info>>,
+        amount_a: u64,
+        amount_b: u64,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::v2::flash_repay::handler(ctx, amount_a, amount_b, remaining_accounts_info)
+    }
+
     pub fn initialize_config_extension(ctx: Context<InitializeConfigExtension>) -> Result<()> {
         instructions::v2::initialize_config_extension::handler(ctx)
     }
@@ -1129,14 +1384,174 @@ pub mod whirlpool {
         instructions::v2::set_token_badge_authority::handler(ctx)
     }
 
+    /// Initialize a token badge for a mint. If the token badge history of the mint is passed,
+    /// the badge is approved with the default attributes and recorded in the history.
+    ///
+    /// ### Authority
+    /// - "token_badge_authority" - Set authority in the WhirlpoolsConfigExtension
     pub fn initialize_token_badge(ctx: Context<InitializeTokenBadge>) -> Result<()> {
         instructions::v2::initialize_token_badge::handler(ctx)
     }
 
+    /// Delete a token badge that is not recorded in a token badge history.
+    ///
+    /// ### Authority
+    /// - "token_badge_authority" - Set authority in the WhirlpoolsConfigExtension
+    ///
+    /// #### Special Errors
+    /// - `TokenBadgeHistoryRequired` - If the badge is recorded in a token badge history,
+    ///                                 use `delete_token_badge_with_history` instead.
     pub fn delete_token_badge(ctx: Context<DeleteTokenBadge>) -> Result<()> {
         instructions::v2::delete_token_badge::handler(ctx)
     }
 
+    /// Initialize the token badge history of a mint. The history is never closed, so it keeps
+    /// the approvals, attribute versions and deletions of every badge of the mint in the config.
+    /// An existing badge can only be deleted with `delete_token_badge_with_history` afterwards.
+    ///
+    /// ### Authority
+    /// - "token_badge_authority" - Set authority in the WhirlpoolsConfigExtension
+    pub fn initialize_token_badge_history(ctx: Context<InitializeTokenBadgeHistory>) -> Result<()> {
+        instructions::v2::initialize_token_badge_history::handler(ctx)
+    }
+
+    /// Initialize a token badge and record the approval in the token badge history.
+    ///
+    /// ### Authority
+    /// - "token_badge_authority" - Set authority in the WhirlpoolsConfigExtension
+    ///
+    /// ### Parameters
+    /// - `attributes` - The conditions the mint is approved under, recorded as version 1.
+    ///
+    /// #### Special Errors
+    /// - `InvalidTokenBadgeAttributes` - If the maximum transfer fee exceeds 10_000 bps.
+    /// - `InvalidHookSubmission` - If the hook submission is not an approved submission
+    ///                             of the transfer hook registry for the transfer hook of the mint.
+    pub fn initialize_token_badge_with_history(
+        ctx: Context<InitializeTokenBadgeWithHistory>,
+        attributes: TokenBadgeAttributes,
+    ) -> Result<()> {
+        instructions::v2::initialize_token_badge_with_history::handler(ctx, attributes)
+    }
+
+    /// Delete a token badge and leave a tombstone with the reason in the token badge history.
+    ///
+    /// ### Authority
+    /// - "token_badge_authority" - Set authority in the WhirlpoolsConfigExtension
+    ///
+    /// ### Parameters
+    /// - `reason` - Why the badge is deleted.
+    ///
+    /// #### Special Errors
+    /// - `InvalidTokenBadgeReason` - If the reason is empty or longer than 64 bytes.
+    pub fn delete_token_badge_with_history(
+        ctx: Context<DeleteTokenBadgeWithHistory>,
+        reason: String,
+    ) -> Result<()> {
+        instructions::v2::delete_token_badge_with_history::handler(ctx, reason)
+    }
+
+    /// Set the attributes of a token badge as a new version in the token badge history.
+    ///
+    /// ### Authority
+    /// - "token_badge_authority" - Set authority in the WhirlpoolsConfigExtension
+    ///
+    /// ### Parameters
+    /// - `attributes` - The new conditions the mint is approved under.
+    ///
+    /// #### Special Errors
+    /// - `InvalidTokenBadgeAttributes` - If the maximum transfer fee exceeds 10_000 bps.
+    pub fn set_token_badge_attributes(
+        ctx: Context<SetTokenBadgeAttributes>,
+        attributes: TokenBadgeAttributes,
+    ) -> Result<()> {
+        instructions::v2::set_token_badge_attributes::handler(ctx, attributes)
+    }
+
+    pub fn set_max_referral_fee_rate(
+        ctx: Context<SetMaxReferralFeeRate>,
+        max_referral_fee_rate: u16,
+    ) -> Result<()> {
+        instructions::v2::set_max_referral_fee_rate::handler(ctx, max_referral_fee_rate)
+    }
+
+    pub fn initialize_referral(
+        ctx: Context<InitializeReferral>,
+        referral_fee_rate: u16,
+    ) -> Result<()> {
+        instructions::v2::initialize_referral::handler(ctx, referral_fee_rate)
+    }
+
+    pub fn set_referral_fee_rate(
+        ctx: Context<SetReferralFeeRate>,
+        referral_fee_rate: u16,
+    ) -> Result<()> {
+        instructions::v2::set_referral_fee_rate::handler(ctx, referral_fee_rate)
+    }
+
+    /// Sets the emergency authority for a WhirlpoolsConfigExtension.
+    /// The emergency authority can pause and unpause individual Whirlpools or all Whirlpools of the config.
+    /// Only the current config extension authority has permission to invoke this instruction.
+    ///
+    /// ### Authority
+    /// - "config_extension_authority" - Set authority of the WhirlpoolsConfigExtension
+    pub fn set_emergency_authority(ctx: Context<SetEmergencyAuthority>) -> Result<()> {
+        instructions::v2::set_emergency_authority::handler(ctx)
+    }
+
+    /// Pauses or unpauses all Whirlpools of a WhirlpoolsConfig.
+    /// While paused, swaps and liquidity increases are rejected. Decreasing liquidity, collecting
+    /// fees and rewards and closing positions are never blocked.
+    /// The pause takes effect on each Whirlpool once it is synced with `sync_whirlpool_pause`.
+    ///
+    /// ### Authority
+    /// - "emergency_authority" - Set authority in the WhirlpoolsConfigExtension
+    ///
+    /// ### Parameters
+    /// - `paused` - Whether the Whirlpools of the config are paused.
+    pub fn set_config_pause(ctx: Context<SetConfigPause>, paused: bool) -> Result<()> {
+        instructions::v2::set_config_pause::handler(ctx, paused)
+    }
+
+    /// Pauses or unpauses a single Whirlpool by setting its pause flag in the Oracle account.
+    /// While paused, swaps and liquidity increases are rejected. Decreasing liquidity, collecting
+    /// fees and rewards and closing positions are never blocked.
+    /// If the Whirlpool has no Oracle account, one without adaptive fee is created and paid by the funder.
+    ///
+    /// ### Authority
+    /// - "emergency_authority" - Set authority in the WhirlpoolsConfigExtension
+    ///
+    /// ### Parameters
+    /// - `paused` - Whether the Whirlpool is paused.
+    pub fn set_whirlpool_pause(ctx: Context<SetWhirlpoolPause>, paused: bool) -> Result<()> {
+        instructions::v2::set_whirlpool_pause::handler(ctx, paused)
+    }
+
+    /// Applies the pause state of the WhirlpoolsConfig to a Whirlpool by recording it in its Oracle account,
+    /// where it is enforced together with the pause flag of the Whirlpool.
+    /// If the Whirlpool has no Oracle account and the config is paused, one without adaptive fee
+    /// is created and paid by the funder.
+    ///
+    /// ### Authority
+    /// - Anyone can invoke this instruction.
+    pub fn sync_whirlpool_pause(ctx: Context<SyncWhirlpoolPause>) -> Result<()> {
+        instructions::v2::sync_whirlpool_pause::handler(ctx)
+    }
+
+    pub fn set_protocol_fee_split(
+        ctx: Context<SetProtocolFeeSplit>,
+        protocol_fee_recipients: Vec<ProtocolFeeRecipient>,
+    ) -> Result<()> {
+        instructions::v2::set_protocol_fee_split::handler(ctx, protocol_fee_recipients)
+    }
+
+    pub fn sweep_protocol_fees<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SweepProtocolFees<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::v2::sweep_protocol_fees::handler(ctx, remaining_accounts_info)
+    }
+
     // Only for inclusion in the IDL
     pub fn idl_include(ctx: Context<IdlInclude>) -> Result<()> {
         // So compiler doesn't strip out the ctx
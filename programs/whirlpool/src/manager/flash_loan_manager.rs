@@ -0,0 +1,117 @@
+use crate::errors::ErrorCode;
+use crate::math::{
+    checked_mul_div_round_up, FEE_RATE_MUL_VALUE, PROTOCOL_FEE_RATE_MUL_VALUE, Q64_RESOLUTION,
+};
+use crate::state::*;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct FlashLoanUpdate {
+    pub fee_a: u64,
+    pub fee_b: u64,
+    pub protocol_fee_a: u64,
+    pub protocol_fee_b: u64,
+    pub next_fee_growth_global_a: u128,
+    pub next_fee_growth_global_b: u128,
+}
+
+// Calculates the fees of a flash loan using the static fee rate of the pool. The protocol takes its
+// cut first and the rest is distributed to in-range liquidity, as it is for swaps. If there is no
+// in-range liquidity, the whole fee goes to the protocol so that it is not left unaccounted in the vault.
+pub fn calculate_flash_loan_update(
+    whirlpool: &Whirlpool,
+    amount_a: u64,
+    amount_b: u64,
+) -> Result<FlashLoanUpdate, ErrorCode> {
+    let (fee_a, protocol_fee_a, next_fee_growth_global_a) =
+        calculate_flash_loan_fee(whirlpool, amount_a, whirlpool.fee_growth_global_a)?;
+    let (fee_b, protocol_fee_b, next_fee_growth_global_b) =
+        calculate_flash_loan_fee(whirlpool, amount_b, whirlpool.fee_growth_global_b)?;
+
+    Ok(FlashLoanUpdate {
+        fee_a,
+        fee_b,
+        protocol_fee_a,
+        protocol_fee_b,
+        next_fee_growth_global_a,
+        next_fee_growth_global_b,
+    })
+}
+
+fn calculate_flash_loan_fee(
+    whirlpool: &Whirlpool,
+    amount: u64,
+    fee_growth_global: u128,
+) -> Result<(u64, u64, u128), ErrorCode> {
+    let fee: u64 = checked_mul_div_round_up(
+        amount as u128,
+        whirlpool.fee_rate as u128,
+        FEE_RATE_MUL_VALUE,
+    )?
+    .try_into()?;
+
+    if whirlpool.liquidity == 0 {
+        return Ok((fee, fee, fee_growth_global));
+    }
+
+    let protocol_fee: u64 = ((fee as u128) * (whirlpool.protocol_fee_rate as u128)
+        / PROTOCOL_FEE_RATE_MUL_VALUE)
+        .try_into()?;
+    let lp_fee = fee - protocol_fee;
+    let next_fee_growth_global =
+        fee_growth_global.wrapping_add(((lp_fee as u128) << Q64_RESOLUTION) / whirlpool.liquidity);
+
+    Ok((fee, protocol_fee, next_fee_growth_global))
+}
+
+#[cfg(test)]
+mod flash_loan_manager_tests {
+    use super::*;
+
+    fn test_whirlpool(fee_rate: u16, protocol_fee_rate: u16, liquidity: u128) -> Whirlpool {
+        Whirlpool {
+            fee_rate,
+            protocol_fee_rate,
+            liquidity,
+            fee_growth_global_a: 100,
+            fee_growth_global_b: 200,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_calculate_flash_loan_update() {
+        // 0.3% fee rate, 1/4 protocol fee rate
+        let whirlpool = test_whirlpool(3000, 2500, 1 << 64);
+        let update = calculate_flash_loan_update(&whirlpool, 1_000_000, 2_000_000).unwrap();
+
+        assert_eq!(update.fee_a, 3_000);
+        assert_eq!(update.fee_b, 6_000);
+        assert_eq!(update.protocol_fee_a, 750);
+        assert_eq!(update.protocol_fee_b, 1_500);
+        assert_eq!(update.next_fee_growth_global_a, 100 + 2_250);
+        assert_eq!(update.next_fee_growth_global_b, 200 + 4_500);
+    }
+
+    #[test]
+    fn test_calculate_flash_loan_update_rounds_fee_up() {
+        let whirlpool = test_whirlpool(3000, 0, 1 << 64);
+        let update = calculate_flash_loan_update(&whirlpool, 1, 0).unwrap();
+
+        assert_eq!(update.fee_a, 1);
+        assert_eq!(update.fee_b, 0);
+        assert_eq!(update.protocol_fee_a, 0);
+        assert_eq!(update.next_fee_growth_global_a, 100 + 1);
+        assert_eq!(update.next_fee_growth_global_b, 200);
+    }
+
+    #[test]
+    fn test_calculate_flash_loan_update_without_liquidity() {
+        let whirlpool = test_whirlpool(3000, 2500, 0);
+        let update = calculate_flash_loan_update(&whirlpool, 1_000_000, 0).unwrap();
+
+        assert_eq!(update.fee_a, 3_000);
+        assert_eq!(update.protocol_fee_a, 3_000);
+        assert_eq!(update.next_fee_growth_global_a, 100);
+        assert_eq!(update.next_fee_growth_global_b, 200);
+    }
+}
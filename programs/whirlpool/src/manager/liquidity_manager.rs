@@ -8,7 +8,10 @@ use super::{
 };
 use crate::{
     errors::ErrorCode,
-    math::{get_amount_delta_a, get_amount_delta_b, sqrt_price_from_tick_index},
+    math::{
+        get_amount_delta_a, get_amount_delta_b, get_liquidity_from_amount_a,
+        get_liquidity_from_amount_b, sqrt_price_from_tick_index,
+    },
     state::*,
 };
 use anchor_lang::prelude::*;
@@ -223,6 +226,34 @@ pub fn calculate_liquidity_token_deltas(
     Ok((delta_a, delta_b))
 }
 
+// Calculates the largest liquidity that can be added to the position at the current price
+// without requiring more than amount_a of token_a and amount_b of token_b.
+pub fn calculate_liquidity_from_token_amounts(
+    current_tick_index: i32,
+    sqrt_price: u128,
+    position: &Position,
+    amount_a: u64,
+    amount_b: u64,
+) -> Result<u128> {
+    let lower_price = sqrt_price_from_tick_index(position.tick_lower_index);
+    let upper_price = sqrt_price_from_tick_index(position.tick_upper_index);
+
+    let liquidity = if current_tick_index < position.tick_lower_index {
+        // current tick below position
+        get_liquidity_from_amount_a(lower_price, upper_price, amount_a)?
+    } else if current_tick_index < position.tick_upper_index {
+        // current tick inside position
+        let liquidity_a = get_liquidity_from_amount_a(sqrt_price, upper_price, amount_a)?;
+        let liquidity_b = get_liquidity_from_amount_b(lower_price, sqrt_price, amount_b)?;
+        liquidity_a.min(liquidity_b)
+    } else {
+        // current tick above position
+        get_liquidity_from_amount_b(lower_price, upper_price, amount_b)?
+    };
+
+    Ok(liquidity)
+}
+
 pub fn sync_modify_liquidity_values<'info>(
     whirlpool: &mut Whirlpool,
     position: &mut Position,
@@ -3747,3 +3778,50 @@ mod calculate_modify_liquidity_unit_tests {
         }
     }
 }
+
+#[cfg(test)]
+mod calculate_liquidity_from_token_amounts_unit_tests {
+    use super::*;
+    use crate::state::position_builder::PositionBuilder;
+
+    #[test]
+    fn test_in_range_uses_limiting_token() {
+        let position = PositionBuilder::new(-100, 100).build();
+        let sqrt_price = sqrt_price_from_tick_index(0);
+
+        let liquidity =
+            calculate_liquidity_from_token_amounts(0, sqrt_price, &position, 1_000, 1_000_000)
+                .unwrap();
+        let (delta_a, delta_b) =
+            calculate_liquidity_token_deltas(0, sqrt_price, &position, liquidity as i128).unwrap();
+        assert!(delta_a <= 1_000);
+        assert!(delta_b <= 1_000_000);
+        // token_a is the limiting token, so almost all of it is used
+        assert!(delta_a >= 999);
+    }
+
+    #[test]
+    fn test_out_of_range_ignores_other_token() {
+        let position = PositionBuilder::new(-100, 100).build();
+
+        let below = calculate_liquidity_from_token_amounts(
+            -200,
+            sqrt_price_from_tick_index(-200),
+            &position,
+            1_000,
+            0,
+        )
+        .unwrap();
+        assert!(below > 0);
+
+        let above = calculate_liquidity_from_token_amounts(
+            200,
+            sqrt_price_from_tick_index(200),
+            &position,
+            1_000,
+            0,
+        )
+        .unwrap();
+        assert_eq!(above, 0);
+    }
+}
@@ -1,4 +1,5 @@
 pub mod fee_rate_manager;
+pub mod flash_loan_manager;
 pub mod liquidity_manager;
 pub mod position_manager;
 pub mod swap_manager;
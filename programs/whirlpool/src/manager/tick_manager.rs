@@ -1,7 +1,7 @@
 use crate::{
     errors::ErrorCode,
     math::add_liquidity_delta,
-    state::{Tick, TickUpdate, WhirlpoolRewardInfo, NUM_REWARDS},
+    state::{RangeOrderFillState, Tick, TickUpdate, WhirlpoolRewardInfo, NUM_REWARDS},
 };
 
 pub fn next_tick_cross_update(
@@ -189,6 +189,32 @@ pub fn next_reward_growths_inside(
     reward_growths_inside
 }
 
+// Determines the fill state of a range order from tick_current_index. Crossing tick_lower_index
+// upwards (a_to_b) or tick_upper_index downwards (b_to_a) starts filling the order, and it is
+// filled once the tick at the far end of the range has been crossed as well.
+pub fn next_range_order_fill_state(
+    tick_current_index: i32,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    a_to_b: bool,
+) -> RangeOrderFillState {
+    if tick_current_index < tick_lower_index {
+        if a_to_b {
+            RangeOrderFillState::Unfilled
+        } else {
+            RangeOrderFillState::Filled
+        }
+    } else if tick_current_index >= tick_upper_index {
+        if a_to_b {
+            RangeOrderFillState::Filled
+        } else {
+            RangeOrderFillState::Unfilled
+        }
+    } else {
+        RangeOrderFillState::PartiallyFilled
+    }
+}
+
 #[cfg(test)]
 mod tick_manager_tests {
     use anchor_lang::prelude::Pubkey;
@@ -196,11 +222,13 @@ mod tick_manager_tests {
     use crate::{
         errors::ErrorCode,
         manager::tick_manager::{
-            next_fee_growths_inside, next_tick_cross_update, next_tick_modify_liquidity_update,
-            TickUpdate,
+            next_fee_growths_inside, next_range_order_fill_state, next_tick_cross_update,
+            next_tick_modify_liquidity_update, TickUpdate,
         },
         math::Q64_RESOLUTION,
-        state::{tick_builder::TickBuilder, Tick, WhirlpoolRewardInfo, NUM_REWARDS},
+        state::{
+            tick_builder::TickBuilder, RangeOrderFillState, Tick, WhirlpoolRewardInfo, NUM_REWARDS,
+        },
     };
 
     use super::next_reward_growths_inside;
@@ -861,4 +889,57 @@ mod tick_manager_tests {
             }
         }
     }
+
+    #[test]
+    fn test_next_range_order_fill_state() {
+        struct Test<'a> {
+            name: &'a str,
+            tick_current_index: i32,
+            a_to_b: bool,
+            expected_fill_state: RangeOrderFillState,
+        }
+
+        for test in [
+            Test {
+                name: "a_to_b, price below range",
+                tick_current_index: 63,
+                a_to_b: true,
+                expected_fill_state: RangeOrderFillState::Unfilled,
+            },
+            Test {
+                name: "a_to_b, price at lower tick",
+                tick_current_index: 64,
+                a_to_b: true,
+                expected_fill_state: RangeOrderFillState::PartiallyFilled,
+            },
+            Test {
+                name: "a_to_b, price at upper tick",
+                tick_current_index: 128,
+                a_to_b: true,
+                expected_fill_state: RangeOrderFillState::Filled,
+            },
+            Test {
+                name: "b_to_a, price above range",
+                tick_current_index: 200,
+                a_to_b: false,
+                expected_fill_state: RangeOrderFillState::Unfilled,
+            },
+            Test {
+                name: "b_to_a, price inside range",
+                tick_current_index: 127,
+                a_to_b: false,
+                expected_fill_state: RangeOrderFillState::PartiallyFilled,
+            },
+            Test {
+                name: "b_to_a, price below lower tick",
+                tick_current_index: 63,
+                a_to_b: false,
+                expected_fill_state: RangeOrderFillState::Filled,
+            },
+        ] {
+            let fill_state =
+                next_range_order_fill_state(test.tick_current_index, 64, 128, test.a_to_b);
+            assert_eq!(fill_state, test.expected_fill_state, "{}", test.name);
+        }
+    }
 }
@@ -1,5 +1,7 @@
 use crate::errors::ErrorCode;
 
+use super::{increasing_price_order, mul_u256, U256Muldiv, Q64_RESOLUTION};
+
 // Adds a signed liquidity delta to a given integer liquidity amount.
 // Errors on overflow or underflow.
 pub fn add_liquidity_delta(liquidity: u128, delta: i128) -> Result<u128, ErrorCode> {
@@ -35,10 +37,55 @@ pub fn convert_to_liquidity_delta(
     })
 }
 
+// Calculates the liquidity that can be provided with amount of token_a over a price range.
+// Inverse of get_amount_delta_a, rounded down so that the required amount never exceeds amount.
+// liquidity = amount * sqrt_price_lower * sqrt_price_upper / (sqrt_price_upper - sqrt_price_lower)
+pub fn get_liquidity_from_amount_a(
+    sqrt_price_0: u128,
+    sqrt_price_1: u128,
+    amount: u64,
+) -> Result<u128, ErrorCode> {
+    let (sqrt_price_lower, sqrt_price_upper) = increasing_price_order(sqrt_price_0, sqrt_price_1);
+
+    let sqrt_price_diff = sqrt_price_upper - sqrt_price_lower;
+    if sqrt_price_diff == 0 {
+        return Err(ErrorCode::DivideByZero);
+    }
+
+    // amount * sqrt_price_lower / sqrt_price_diff fits in u128 for any valid tick range
+    let (quotient, _) =
+        mul_u256(amount as u128, sqrt_price_lower).div(U256Muldiv::new(0, sqrt_price_diff), false);
+    let quotient = quotient.try_into_u128()?;
+
+    mul_u256(quotient, sqrt_price_upper)
+        .shift_right(Q64_RESOLUTION as u32)
+        .try_into_u128()
+}
+
+// Calculates the liquidity that can be provided with amount of token_b over a price range.
+// Inverse of get_amount_delta_b, rounded down so that the required amount never exceeds amount.
+// liquidity = amount / (sqrt_price_upper - sqrt_price_lower)
+pub fn get_liquidity_from_amount_b(
+    sqrt_price_0: u128,
+    sqrt_price_1: u128,
+    amount: u64,
+) -> Result<u128, ErrorCode> {
+    let (sqrt_price_lower, sqrt_price_upper) = increasing_price_order(sqrt_price_0, sqrt_price_1);
+
+    let sqrt_price_diff = sqrt_price_upper - sqrt_price_lower;
+    if sqrt_price_diff == 0 {
+        return Err(ErrorCode::DivideByZero);
+    }
+
+    Ok(((amount as u128) << Q64_RESOLUTION) / sqrt_price_diff)
+}
+
 #[cfg(test)]
 mod liquidity_math_tests {
     use super::add_liquidity_delta;
     use super::ErrorCode;
+    use super::{get_liquidity_from_amount_a, get_liquidity_from_amount_b};
+    use crate::math::{get_amount_delta_a, get_amount_delta_b, sqrt_price_from_tick_index};
 
     #[test]
     fn test_valid_add_liquidity_delta() {
@@ -58,4 +105,43 @@ mod liquidity_math_tests {
         let result = add_liquidity_delta(u128::MIN, -1);
         assert_eq!(result.unwrap_err(), ErrorCode::LiquidityUnderflow);
     }
+
+    #[test]
+    fn test_get_liquidity_from_amount_a_round_trip() {
+        let sqrt_price_lower = sqrt_price_from_tick_index(-128);
+        let sqrt_price_upper = sqrt_price_from_tick_index(256);
+        for amount in [1u64, 1_000, 1_000_000_007, u32::MAX as u64] {
+            let liquidity =
+                get_liquidity_from_amount_a(sqrt_price_lower, sqrt_price_upper, amount).unwrap();
+            let required =
+                get_amount_delta_a(sqrt_price_lower, sqrt_price_upper, liquidity, true).unwrap();
+            assert!(required <= amount);
+        }
+    }
+
+    #[test]
+    fn test_get_liquidity_from_amount_b_round_trip() {
+        let sqrt_price_lower = sqrt_price_from_tick_index(-128);
+        let sqrt_price_upper = sqrt_price_from_tick_index(256);
+        for amount in [1u64, 1_000, 1_000_000_007, u32::MAX as u64] {
+            let liquidity =
+                get_liquidity_from_amount_b(sqrt_price_lower, sqrt_price_upper, amount).unwrap();
+            let required =
+                get_amount_delta_b(sqrt_price_lower, sqrt_price_upper, liquidity, true).unwrap();
+            assert!(required <= amount);
+        }
+    }
+
+    #[test]
+    fn test_get_liquidity_from_amount_zero_width() {
+        let sqrt_price = sqrt_price_from_tick_index(0);
+        assert_eq!(
+            get_liquidity_from_amount_a(sqrt_price, sqrt_price, 1).unwrap_err(),
+            ErrorCode::DivideByZero
+        );
+        assert_eq!(
+            get_liquidity_from_amount_b(sqrt_price, sqrt_price, 1).unwrap_err(),
+            ErrorCode::DivideByZero
+        );
+    }
 }
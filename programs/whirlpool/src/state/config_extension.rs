@@ -1,15 +1,48 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::ErrorCode;
+
+// Referral fee rate is represented as a basis point of the protocol fee of a swap.
+pub const MAX_REFERRAL_FEE_RATE: u16 = 10_000;
+
+// Protocol fees can be swept to at most this many recipients.
+// The weights of the recipients are basis points of the protocol fees and must sum to 10_000.
+pub const MAX_PROTOCOL_FEE_RECIPIENTS: usize = 4;
+pub const PROTOCOL_FEE_SPLIT_WEIGHT_DENOMINATOR: u16 = 10_000;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct ProtocolFeeRecipient {
+    pub owner: Pubkey,   // 32
+    pub weight_bps: u16, // 2
+}
+
+impl ProtocolFeeRecipient {
+    pub const LEN: usize = 32 + 2;
+}
+
 #[account]
 pub struct WhirlpoolsConfigExtension {
     pub whirlpools_config: Pubkey,          // 32
     pub config_extension_authority: Pubkey, // 32
     pub token_badge_authority: Pubkey,      // 32
-                                            // 512 RESERVE
+    pub max_referral_fee_rate: u16,         // 2
+    pub emergency_authority: Pubkey,        // 32
+    pub paused: bool,                       // 1
+    // 34 * MAX_PROTOCOL_FEE_RECIPIENTS
+    pub protocol_fee_recipients: [ProtocolFeeRecipient; MAX_PROTOCOL_FEE_RECIPIENTS],
+    // 341 RESERVE
 }
 
 impl WhirlpoolsConfigExtension {
-    pub const LEN: usize = 8 + 32 + 32 + 32 + 512;
+    pub const LEN: usize = 8
+        + 32
+        + 32
+        + 32
+        + 2
+        + 32
+        + 1
+        + ProtocolFeeRecipient::LEN * MAX_PROTOCOL_FEE_RECIPIENTS
+        + 341;
 
     pub fn initialize(
         &mut self,
@@ -19,6 +52,11 @@ impl WhirlpoolsConfigExtension {
         self.whirlpools_config = whirlpools_config;
         self.config_extension_authority = default_authority;
         self.token_badge_authority = default_authority;
+        self.max_referral_fee_rate = 0;
+        self.emergency_authority = default_authority;
+        self.paused = false;
+        self.protocol_fee_recipients =
+            [ProtocolFeeRecipient::default(); MAX_PROTOCOL_FEE_RECIPIENTS];
         Ok(())
     }
 
@@ -29,6 +67,76 @@ impl WhirlpoolsConfigExtension {
     pub fn update_token_badge_authority(&mut self, token_badge_authority: Pubkey) {
         self.token_badge_authority = token_badge_authority;
     }
+
+    pub fn update_max_referral_fee_rate(&mut self, max_referral_fee_rate: u16) -> Result<()> {
+        if max_referral_fee_rate > MAX_REFERRAL_FEE_RATE {
+            return Err(ErrorCode::InvalidReferralFeeRate.into());
+        }
+        self.max_referral_fee_rate = max_referral_fee_rate;
+        Ok(())
+    }
+
+    pub fn update_emergency_authority(&mut self, emergency_authority: Pubkey) {
+        self.emergency_authority = emergency_authority;
+    }
+
+    pub fn update_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    // An empty list disables sweeping, otherwise the weights must sum to 10_000 basis points.
+    pub fn update_protocol_fee_recipients(
+        &mut self,
+        protocol_fee_recipients: &[ProtocolFeeRecipient],
+    ) -> Result<()> {
+        if protocol_fee_recipients.len() > MAX_PROTOCOL_FEE_RECIPIENTS {
+            return Err(ErrorCode::InvalidProtocolFeeSplit.into());
+        }
+
+        let mut weight_sum = 0u32;
+        for recipient in protocol_fee_recipients.iter() {
+            if recipient.owner == Pubkey::default() || recipient.weight_bps == 0 {
+                return Err(ErrorCode::InvalidProtocolFeeSplit.into());
+            }
+            weight_sum += recipient.weight_bps as u32;
+        }
+        if !protocol_fee_recipients.is_empty()
+            && weight_sum != PROTOCOL_FEE_SPLIT_WEIGHT_DENOMINATOR as u32
+        {
+            return Err(ErrorCode::InvalidProtocolFeeSplit.into());
+        }
+
+        let mut recipients = [ProtocolFeeRecipient::default(); MAX_PROTOCOL_FEE_RECIPIENTS];
+        recipients[..protocol_fee_recipients.len()].copy_from_slice(protocol_fee_recipients);
+        self.protocol_fee_recipients = recipients;
+        Ok(())
+    }
+
+    pub fn protocol_fee_recipient_count(&self) -> usize {
+        self.protocol_fee_recipients
+            .iter()
+            .take_while(|recipient| recipient.weight_bps != 0)
+            .count()
+    }
+
+    // Splits the protocol fee according to the weights of the recipients.
+    // The rounding remainder goes to the first recipient, so the whole fee is always distributed.
+    pub fn split_protocol_fee(&self, protocol_fee: u64) -> Vec<u64> {
+        let recipient_count = self.protocol_fee_recipient_count();
+        let mut shares: Vec<u64> = self.protocol_fee_recipients[..recipient_count]
+            .iter()
+            .map(|recipient| {
+                ((protocol_fee as u128) * (recipient.weight_bps as u128)
+                    / (PROTOCOL_FEE_SPLIT_WEIGHT_DENOMINATOR as u128)) as u64
+            })
+            .collect();
+
+        let distributed: u64 = shares.iter().sum();
+        if let Some(first_share) = shares.first_mut() {
+            *first_share += protocol_fee - distributed;
+        }
+        shares
+    }
 }
 
 #[cfg(test)]
@@ -42,6 +150,10 @@ mod whirlpools_config_extension_initialize_tests {
             whirlpools_config: Pubkey::default(),
             config_extension_authority: Pubkey::default(),
             token_badge_authority: Pubkey::default(),
+            max_referral_fee_rate: 0,
+            emergency_authority: Pubkey::default(),
+            paused: false,
+            protocol_fee_recipients: [ProtocolFeeRecipient::default(); MAX_PROTOCOL_FEE_RECIPIENTS],
         };
 
         let whirlpools_config =
@@ -58,6 +170,9 @@ mod whirlpools_config_extension_initialize_tests {
             config_extension.config_extension_authority
         );
         assert_eq!(default_authority, config_extension.token_badge_authority);
+        assert_eq!(0, config_extension.max_referral_fee_rate);
+        assert_eq!(default_authority, config_extension.emergency_authority);
+        assert!(!config_extension.paused);
     }
 }
 
@@ -72,6 +187,10 @@ mod whirlpools_config_extension_update_tests {
             whirlpools_config: Pubkey::default(),
             config_extension_authority: Pubkey::default(),
             token_badge_authority: Pubkey::default(),
+            max_referral_fee_rate: 0,
+            emergency_authority: Pubkey::default(),
+            paused: false,
+            protocol_fee_recipients: [ProtocolFeeRecipient::default(); MAX_PROTOCOL_FEE_RECIPIENTS],
         };
 
         let config_extension_authority =
@@ -86,12 +205,58 @@ mod whirlpools_config_extension_update_tests {
         assert_eq!(Pubkey::default(), config_extension.token_badge_authority);
     }
 
+    #[test]
+    fn test_update_emergency_authority() {
+        let mut config_extension = WhirlpoolsConfigExtension {
+            whirlpools_config: Pubkey::default(),
+            config_extension_authority: Pubkey::default(),
+            token_badge_authority: Pubkey::default(),
+            max_referral_fee_rate: 0,
+            emergency_authority: Pubkey::default(),
+            paused: false,
+            protocol_fee_recipients: [ProtocolFeeRecipient::default(); MAX_PROTOCOL_FEE_RECIPIENTS],
+        };
+
+        let emergency_authority =
+            Pubkey::from_str("orcaEKTdK7LKz57vaAYr9QeNsVEPfiu6QeMU1kektZE").unwrap();
+
+        config_extension.update_emergency_authority(emergency_authority);
+
+        assert_eq!(emergency_authority, config_extension.emergency_authority);
+        assert_eq!(
+            Pubkey::default(),
+            config_extension.config_extension_authority
+        );
+    }
+
+    #[test]
+    fn test_update_paused() {
+        let mut config_extension = WhirlpoolsConfigExtension {
+            whirlpools_config: Pubkey::default(),
+            config_extension_authority: Pubkey::default(),
+            token_badge_authority: Pubkey::default(),
+            max_referral_fee_rate: 0,
+            emergency_authority: Pubkey::default(),
+            paused: false,
+            protocol_fee_recipients: [ProtocolFeeRecipient::default(); MAX_PROTOCOL_FEE_RECIPIENTS],
+        };
+
+        config_extension.update_paused(true);
+        assert!(config_extension.paused);
+        config_extension.update_paused(false);
+        assert!(!config_extension.paused);
+    }
+
     #[test]
     fn test_update_token_badge_authority() {
         let mut config_extension = WhirlpoolsConfigExtension {
             whirlpools_config: Pubkey::default(),
             config_extension_authority: Pubkey::default(),
             token_badge_authority: Pubkey::default(),
+            max_referral_fee_rate: 0,
+            emergency_authority: Pubkey::default(),
+            paused: false,
+            protocol_fee_recipients: [ProtocolFeeRecipient::default(); MAX_PROTOCOL_FEE_RECIPIENTS],
         };
 
         let token_badge_authority =
@@ -110,6 +275,150 @@ mod whirlpools_config_extension_update_tests {
     }
 }
 
+#[cfg(test)]
+mod whirlpools_config_extension_referral_tests {
+    use super::*;
+
+    #[test]
+    fn test_update_max_referral_fee_rate() {
+        let mut config_extension = WhirlpoolsConfigExtension {
+            whirlpools_config: Pubkey::default(),
+            config_extension_authority: Pubkey::default(),
+            token_badge_authority: Pubkey::default(),
+            max_referral_fee_rate: 0,
+            emergency_authority: Pubkey::default(),
+            paused: false,
+            protocol_fee_recipients: [ProtocolFeeRecipient::default(); MAX_PROTOCOL_FEE_RECIPIENTS],
+        };
+
+        config_extension
+            .update_max_referral_fee_rate(MAX_REFERRAL_FEE_RATE)
+            .unwrap();
+        assert_eq!(
+            MAX_REFERRAL_FEE_RATE,
+            config_extension.max_referral_fee_rate
+        );
+
+        let result = config_extension.update_max_referral_fee_rate(MAX_REFERRAL_FEE_RATE + 1);
+        assert!(result.is_err());
+        assert_eq!(
+            MAX_REFERRAL_FEE_RATE,
+            config_extension.max_referral_fee_rate
+        );
+    }
+}
+
+#[cfg(test)]
+mod whirlpools_config_extension_protocol_fee_split_tests {
+    use super::*;
+
+    fn test_config_extension() -> WhirlpoolsConfigExtension {
+        WhirlpoolsConfigExtension {
+            whirlpools_config: Pubkey::default(),
+            config_extension_authority: Pubkey::default(),
+            token_badge_authority: Pubkey::default(),
+            max_referral_fee_rate: 0,
+            emergency_authority: Pubkey::default(),
+            paused: false,
+            protocol_fee_recipients: [ProtocolFeeRecipient::default(); MAX_PROTOCOL_FEE_RECIPIENTS],
+        }
+    }
+
+    fn recipient(weight_bps: u16) -> ProtocolFeeRecipient {
+        ProtocolFeeRecipient {
+            owner: Pubkey::new_unique(),
+            weight_bps,
+        }
+    }
+
+    #[test]
+    fn test_update_protocol_fee_recipients() {
+        let mut config_extension = test_config_extension();
+        assert_eq!(config_extension.protocol_fee_recipient_count(), 0);
+
+        let recipients = [recipient(6000), recipient(3000), recipient(1000)];
+        config_extension
+            .update_protocol_fee_recipients(&recipients)
+            .unwrap();
+        assert_eq!(config_extension.protocol_fee_recipient_count(), 3);
+        assert_eq!(config_extension.protocol_fee_recipients[..3], recipients);
+        assert_eq!(
+            config_extension.protocol_fee_recipients[3],
+            ProtocolFeeRecipient::default()
+        );
+
+        // shrinking clears the remaining recipients
+        let recipients = [recipient(10_000)];
+        config_extension
+            .update_protocol_fee_recipients(&recipients)
+            .unwrap();
+        assert_eq!(config_extension.protocol_fee_recipient_count(), 1);
+        assert_eq!(
+            config_extension.protocol_fee_recipients[1],
+            ProtocolFeeRecipient::default()
+        );
+
+        // empty disables sweeping
+        config_extension
+            .update_protocol_fee_recipients(&[])
+            .unwrap();
+        assert_eq!(config_extension.protocol_fee_recipient_count(), 0);
+    }
+
+    #[test]
+    fn test_update_protocol_fee_recipients_invalid() {
+        let mut config_extension = test_config_extension();
+
+        let invalid_splits = [
+            // weights do not sum to 10_000
+            vec![recipient(6000), recipient(3000)],
+            vec![recipient(6000), recipient(5000)],
+            // zero weight
+            vec![recipient(10_000), recipient(0)],
+            // default owner
+            vec![ProtocolFeeRecipient {
+                owner: Pubkey::default(),
+                weight_bps: 10_000,
+            }],
+            // too many recipients
+            vec![recipient(2000); MAX_PROTOCOL_FEE_RECIPIENTS + 1],
+        ];
+        for split in invalid_splits.iter() {
+            assert_eq!(
+                config_extension
+                    .update_protocol_fee_recipients(split)
+                    .unwrap_err(),
+                ErrorCode::InvalidProtocolFeeSplit.into()
+            );
+        }
+        assert_eq!(config_extension.protocol_fee_recipient_count(), 0);
+    }
+
+    #[test]
+    fn test_split_protocol_fee() {
+        let mut config_extension = test_config_extension();
+        assert!(config_extension.split_protocol_fee(1000).is_empty());
+
+        config_extension
+            .update_protocol_fee_recipients(&[recipient(6000), recipient(3000), recipient(1000)])
+            .unwrap();
+        assert_eq!(
+            config_extension.split_protocol_fee(1000),
+            vec![600, 300, 100]
+        );
+        assert_eq!(config_extension.split_protocol_fee(0), vec![0, 0, 0]);
+        // the rounding remainder goes to the first recipient
+        assert_eq!(config_extension.split_protocol_fee(9), vec![7, 2, 0]);
+        assert_eq!(
+            config_extension
+                .split_protocol_fee(u64::MAX)
+                .iter()
+                .sum::<u64>(),
+            u64::MAX
+        );
+    }
+}
+
 #[cfg(test)]
 mod data_layout_tests {
     use anchor_lang::Discriminator;
@@ -121,7 +430,12 @@ mod data_layout_tests {
         let config_extension_whirlpools_config = Pubkey::new_unique();
         let config_extension_config_extension_authority = Pubkey::new_unique();
         let config_extension_token_badge_authority = Pubkey::new_unique();
-        let config_extension_reserved = [0u8; 512];
+        let config_extension_max_referral_fee_rate = 0x1234u16;
+        let config_extension_emergency_authority = Pubkey::new_unique();
+        let config_extension_paused = true;
+        let config_extension_protocol_fee_recipient_owner = Pubkey::new_unique();
+        let config_extension_protocol_fee_recipient_weight_bps = 0x1122u16;
+        let config_extension_reserved = [0u8; 341];
 
         let mut config_extension_data = [0u8; WhirlpoolsConfigExtension::LEN];
         let mut offset = 0;
@@ -137,6 +451,22 @@ mod data_layout_tests {
         config_extension_data[offset..offset + 32]
             .copy_from_slice(&config_extension_token_badge_authority.to_bytes());
         offset += 32;
+        config_extension_data[offset..offset + 2]
+            .copy_from_slice(&config_extension_max_referral_fee_rate.to_le_bytes());
+        offset += 2;
+        config_extension_data[offset..offset + 32]
+            .copy_from_slice(&config_extension_emergency_authority.to_bytes());
+        offset += 32;
+        config_extension_data[offset] = config_extension_paused as u8;
+        offset += 1;
+        for _ in 0..MAX_PROTOCOL_FEE_RECIPIENTS {
+            config_extension_data[offset..offset + 32]
+                .copy_from_slice(&config_extension_protocol_fee_recipient_owner.to_bytes());
+            offset += 32;
+            config_extension_data[offset..offset + 2]
+                .copy_from_slice(&config_extension_protocol_fee_recipient_weight_bps.to_le_bytes());
+            offset += 2;
+        }
         config_extension_data[offset..offset + config_extension_reserved.len()]
             .copy_from_slice(&config_extension_reserved);
         offset += config_extension_reserved.len();
@@ -159,6 +489,25 @@ mod data_layout_tests {
             config_extension_token_badge_authority,
             deserialized.token_badge_authority
         );
+        assert_eq!(
+            config_extension_max_referral_fee_rate,
+            deserialized.max_referral_fee_rate
+        );
+        assert_eq!(
+            config_extension_emergency_authority,
+            deserialized.emergency_authority
+        );
+        assert_eq!(config_extension_paused, deserialized.paused);
+        for recipient in deserialized.protocol_fee_recipients.iter() {
+            assert_eq!(
+                config_extension_protocol_fee_recipient_owner,
+                recipient.owner
+            );
+            assert_eq!(
+                config_extension_protocol_fee_recipient_weight_bps,
+                recipient.weight_bps
+            );
+        }
 
         // serialize
         let mut serialized = Vec::new();
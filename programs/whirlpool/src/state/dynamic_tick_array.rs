@@ -119,6 +119,16 @@ impl Default for DynamicTickArrayLoader {
     }
 }
 
+#[cfg(test)]
+impl DynamicTickArrayLoader {
+    pub fn new_with_start_tick_index(start_tick_index: i32) -> Self {
+        let mut array = Self::default();
+        array.0[Self::START_TICK_INDEX_OFFSET..Self::START_TICK_INDEX_OFFSET + 4]
+            .copy_from_slice(&start_tick_index.to_le_bytes());
+        array
+    }
+}
+
 impl DynamicTickArrayLoader {
     // Reimplement these functions from bytemuck::from_bytes_mut without
     // the size and alignment checks. If reading beyond the end of the underlying
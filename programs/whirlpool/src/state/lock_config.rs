@@ -1,19 +1,28 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::ErrorCode;
+use crate::math::U256Muldiv;
+
 #[account]
 pub struct LockConfig {
-    pub position: Pubkey,       // 32
-    pub position_owner: Pubkey, // 32
-    pub whirlpool: Pubkey,      // 32
-    pub locked_timestamp: u64,  // 8
+    pub position: Pubkey,         // 32
+    pub position_owner: Pubkey,   // 32
+    pub whirlpool: Pubkey,        // 32
+    pub locked_timestamp: u64,    // 8
     pub lock_type: LockTypeLabel, // 1
-                                // 128 RESERVE
+    pub unlock_timestamp: u64,    // 8
+    pub locked_liquidity: u128,   // 16
+                                  // 104 RESERVE
 }
 
 #[non_exhaustive]
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
 pub enum LockType {
     Permanent,
+    // Liquidity cannot be withdrawn until unlock_timestamp.
+    TimeLocked { unlock_timestamp: u64 },
+    // Liquidity becomes withdrawable linearly from the lock until unlock_timestamp.
+    Vesting { unlock_timestamp: u64 },
 }
 
 // To avoid storing an enum that may be extended in the future to the account, separate the variant label and value. The value is added flatly to the account.
@@ -21,10 +30,12 @@ pub enum LockType {
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
 pub enum LockTypeLabel {
     Permanent,
+    TimeLocked,
+    Vesting,
 }
 
 impl LockConfig {
-    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 1 + 128;
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 1 + 8 + 16 + 104;
 
     pub fn initialize(
         &mut self,
@@ -32,14 +43,33 @@ impl LockConfig {
         position_owner: Pubkey,
         whirlpool: Pubkey,
         locked_timestamp: u64,
+        locked_liquidity: u128,
         lock_type: LockType,
     ) -> Result<()> {
         self.position = position;
         self.position_owner = position_owner;
         self.whirlpool = whirlpool;
         self.locked_timestamp = locked_timestamp;
+        self.locked_liquidity = locked_liquidity;
         match lock_type {
-            LockType::Permanent => self.lock_type = LockTypeLabel::Permanent,
+            LockType::Permanent => {
+                self.lock_type = LockTypeLabel::Permanent;
+                self.unlock_timestamp = 0;
+            }
+            LockType::TimeLocked { unlock_timestamp } => {
+                if unlock_timestamp <= locked_timestamp {
+                    return Err(ErrorCode::InvalidUnlockTimestamp.into());
+                }
+                self.lock_type = LockTypeLabel::TimeLocked;
+                self.unlock_timestamp = unlock_timestamp;
+            }
+            LockType::Vesting { unlock_timestamp } => {
+                if unlock_timestamp <= locked_timestamp {
+                    return Err(ErrorCode::InvalidUnlockTimestamp.into());
+                }
+                self.lock_type = LockTypeLabel::Vesting;
+                self.unlock_timestamp = unlock_timestamp;
+            }
         }
         Ok(())
     }
@@ -47,6 +77,48 @@ impl LockConfig {
     pub fn update_position_owner(&mut self, position_owner: Pubkey) {
         self.position_owner = position_owner;
     }
+
+    pub fn is_unlockable(&self, timestamp: u64) -> bool {
+        match self.lock_type {
+            LockTypeLabel::Permanent => false,
+            LockTypeLabel::TimeLocked | LockTypeLabel::Vesting => {
+                timestamp >= self.unlock_timestamp
+            }
+        }
+    }
+
+    // Liquidity added after locking the position is not locked and can always be withdrawn.
+    pub fn get_withdrawable_liquidity(
+        &self,
+        position_liquidity: u128,
+        timestamp: u64,
+    ) -> Result<u128> {
+        let still_locked_liquidity = match self.lock_type {
+            LockTypeLabel::Permanent => return Ok(0),
+            LockTypeLabel::TimeLocked => {
+                if timestamp >= self.unlock_timestamp {
+                    0
+                } else {
+                    self.locked_liquidity
+                }
+            }
+            LockTypeLabel::Vesting => {
+                if timestamp >= self.unlock_timestamp {
+                    0
+                } else {
+                    let elapsed = timestamp.saturating_sub(self.locked_timestamp);
+                    let duration = self.unlock_timestamp - self.locked_timestamp;
+                    let vested_liquidity = U256Muldiv::new(0, self.locked_liquidity)
+                        .mul(U256Muldiv::new(0, elapsed as u128))
+                        .div(U256Muldiv::new(0, duration as u128), false)
+                        .0
+                        .try_into_u128()?;
+                    self.locked_liquidity - vested_liquidity
+                }
+            }
+        };
+        Ok(position_liquidity.saturating_sub(still_locked_liquidity))
+    }
 }
 
 #[cfg(test)]
@@ -61,6 +133,8 @@ mod lock_config_initialize_tests {
             whirlpool: Pubkey::default(),
             lock_type: LockTypeLabel::Permanent,
             locked_timestamp: 0,
+            unlock_timestamp: 0,
+            locked_liquidity: 0,
         };
 
         let position = Pubkey::new_unique();
@@ -73,6 +147,7 @@ mod lock_config_initialize_tests {
             position_owner,
             whirlpool,
             locked_timestamp,
+            1_000,
             LockType::Permanent,
         );
         assert!(result.is_ok());
@@ -82,6 +157,187 @@ mod lock_config_initialize_tests {
         assert_eq!(whirlpool, lock_config.whirlpool);
         assert_eq!(LockTypeLabel::Permanent, lock_config.lock_type);
         assert_eq!(locked_timestamp, lock_config.locked_timestamp);
+        assert_eq!(1_000, lock_config.locked_liquidity);
+        assert_eq!(0, lock_config.unlock_timestamp);
+    }
+
+    #[test]
+    fn test_initialize_time_locked() {
+        let mut lock_config = test_lock_config(LockType::TimeLocked {
+            unlock_timestamp: 2_000,
+        });
+        assert_eq!(LockTypeLabel::TimeLocked, lock_config.lock_type);
+        assert_eq!(2_000, lock_config.unlock_timestamp);
+
+        let result = lock_config.initialize(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            1_000,
+            1_000,
+            LockType::TimeLocked {
+                unlock_timestamp: 1_000,
+            },
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_initialize_vesting() {
+        let mut lock_config = test_lock_config(LockType::Vesting {
+            unlock_timestamp: 2_000,
+        });
+        assert_eq!(LockTypeLabel::Vesting, lock_config.lock_type);
+        assert_eq!(2_000, lock_config.unlock_timestamp);
+
+        let result = lock_config.initialize(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            1_000,
+            1_000,
+            LockType::Vesting {
+                unlock_timestamp: 999,
+            },
+        );
+        assert!(result.is_err());
+    }
+
+    // locked at 1_000 with 1_000 liquidity
+    pub(super) fn test_lock_config(lock_type: LockType) -> LockConfig {
+        let mut lock_config = LockConfig {
+            position: Pubkey::default(),
+            position_owner: Pubkey::default(),
+            whirlpool: Pubkey::default(),
+            lock_type: LockTypeLabel::Permanent,
+            locked_timestamp: 0,
+            unlock_timestamp: 0,
+            locked_liquidity: 0,
+        };
+        lock_config
+            .initialize(
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                1_000,
+                1_000,
+                lock_type,
+            )
+            .unwrap();
+        lock_config
+    }
+}
+
+#[cfg(test)]
+mod lock_config_withdrawable_liquidity_tests {
+    use super::lock_config_initialize_tests::test_lock_config;
+    use super::*;
+
+    #[test]
+    fn test_permanent() {
+        let lock_config = test_lock_config(LockType::Permanent);
+        assert_eq!(
+            lock_config
+                .get_withdrawable_liquidity(1_500, 1_000)
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            lock_config
+                .get_withdrawable_liquidity(1_500, u64::MAX)
+                .unwrap(),
+            0
+        );
+        assert!(!lock_config.is_unlockable(u64::MAX));
+    }
+
+    #[test]
+    fn test_time_locked() {
+        let lock_config = test_lock_config(LockType::TimeLocked {
+            unlock_timestamp: 2_000,
+        });
+        assert_eq!(
+            lock_config
+                .get_withdrawable_liquidity(1_000, 1_999)
+                .unwrap(),
+            0
+        );
+        // liquidity added after locking can be withdrawn
+        assert_eq!(
+            lock_config
+                .get_withdrawable_liquidity(1_500, 1_999)
+                .unwrap(),
+            500
+        );
+        assert_eq!(
+            lock_config
+                .get_withdrawable_liquidity(1_000, 2_000)
+                .unwrap(),
+            1_000
+        );
+        assert!(!lock_config.is_unlockable(1_999));
+        assert!(lock_config.is_unlockable(2_000));
+    }
+
+    #[test]
+    fn test_vesting() {
+        let lock_config = test_lock_config(LockType::Vesting {
+            unlock_timestamp: 2_000,
+        });
+        assert_eq!(
+            lock_config
+                .get_withdrawable_liquidity(1_000, 1_000)
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            lock_config
+                .get_withdrawable_liquidity(1_000, 1_250)
+                .unwrap(),
+            250
+        );
+        assert_eq!(
+            lock_config
+                .get_withdrawable_liquidity(1_000, 1_999)
+                .unwrap(),
+            999
+        );
+        assert_eq!(
+            lock_config
+                .get_withdrawable_liquidity(1_000, 2_000)
+                .unwrap(),
+            1_000
+        );
+        // part of the vested liquidity has already been withdrawn
+        assert_eq!(
+            lock_config.get_withdrawable_liquidity(800, 1_250).unwrap(),
+            50
+        );
+        assert_eq!(
+            lock_config.get_withdrawable_liquidity(700, 1_250).unwrap(),
+            0
+        );
+        // liquidity added after locking can be withdrawn
+        assert_eq!(
+            lock_config
+                .get_withdrawable_liquidity(1_200, 1_250)
+                .unwrap(),
+            450
+        );
+        assert!(!lock_config.is_unlockable(1_999));
+        assert!(lock_config.is_unlockable(2_000));
+    }
+
+    #[test]
+    fn test_vesting_large_liquidity() {
+        let mut lock_config = test_lock_config(LockType::Vesting {
+            unlock_timestamp: u64::MAX,
+        });
+        lock_config.locked_liquidity = u128::MAX;
+        let withdrawable = lock_config
+            .get_withdrawable_liquidity(u128::MAX, u64::MAX / 2)
+            .unwrap();
+        assert!(withdrawable > 0 && withdrawable < u128::MAX);
     }
 }
 
@@ -97,8 +353,10 @@ mod data_layout_tests {
         let lock_config_position_owner = Pubkey::new_unique();
         let lock_config_whirlpool = Pubkey::new_unique();
         let lock_config_locked_timestamp = 1711385715u64;
-        let lock_config_lock_type = LockTypeLabel::Permanent;
-        let lock_config_reserved = [0u8; 128];
+        let lock_config_lock_type = LockTypeLabel::Vesting;
+        let lock_config_unlock_timestamp = 1727385715u64;
+        let lock_config_locked_liquidity = 0x11223344556677889900aabbccddeeffu128;
+        let lock_config_reserved = [0u8; 104];
 
         let mut lock_config_data = [0u8; LockConfig::LEN];
         let mut offset = 0;
@@ -116,6 +374,12 @@ mod data_layout_tests {
         offset += 8;
         lock_config_data[offset] = lock_config_lock_type as u8;
         offset += 1;
+        lock_config_data[offset..offset + 8]
+            .copy_from_slice(&lock_config_unlock_timestamp.to_le_bytes());
+        offset += 8;
+        lock_config_data[offset..offset + 16]
+            .copy_from_slice(&lock_config_locked_liquidity.to_le_bytes());
+        offset += 16;
         lock_config_data[offset..offset + lock_config_reserved.len()]
             .copy_from_slice(&lock_config_reserved);
         offset += lock_config_reserved.len();
@@ -129,6 +393,8 @@ mod data_layout_tests {
         assert_eq!(lock_config_whirlpool, deserialized.whirlpool);
         assert_eq!(lock_config_locked_timestamp, deserialized.locked_timestamp);
         assert_eq!(lock_config_lock_type, deserialized.lock_type);
+        assert_eq!(lock_config_unlock_timestamp, deserialized.unlock_timestamp);
+        assert_eq!(lock_config_locked_liquidity, deserialized.locked_liquidity);
 
         // serialize
         let mut serialized = Vec::new();
@@ -8,9 +8,13 @@ pub mod lock_config;
 pub mod oracle;
 pub mod position;
 pub mod position_bundle;
+pub mod position_delegate;
+pub mod range_order;
+pub mod referral;
 pub mod tick;
 pub mod tick_array;
 pub mod token_badge;
+pub mod token_badge_history;
 pub mod whirlpool;
 pub mod zeroed_tick_array;
 
@@ -25,7 +29,11 @@ pub use lock_config::*;
 pub use oracle::*;
 pub use position::*;
 pub use position_bundle::*;
+pub use position_delegate::*;
+pub use range_order::*;
+pub use referral::*;
 pub use tick::*;
 pub use tick_array::*;
 pub use token_badge::*;
+pub use token_badge_history::*;
 pub use zeroed_tick_array::*;
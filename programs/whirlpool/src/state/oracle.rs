@@ -1,5 +1,7 @@
 use crate::errors::ErrorCode;
-use crate::math::{increasing_price_order, sqrt_price_from_tick_index, U256Muldiv, Q64_RESOLUTION};
+use crate::math::{
+    increasing_price_order, sqrt_price_from_tick_index, U256Muldiv, MAX_FEE_RATE, Q64_RESOLUTION,
+};
 use crate::state::Whirlpool;
 use anchor_lang::prelude::*;
 use std::cell::{Ref, RefMut};
@@ -27,6 +29,16 @@ pub const ADAPTIVE_FEE_CONTROL_FACTOR_DENOMINATOR: u32 = 100_000;
 // A recovery measure against the act of intentionally repeating major swaps to keep the Adaptive Fee high (DoS).
 pub const MAX_REFERENCE_AGE: u64 = 3_600; // 1 hour
 
+// The maximum number of price observations that can be stored in an Oracle account.
+// An account can grow by at most 10KiB per instruction, so growing to the maximum takes several instructions.
+pub const MAX_OBSERVATION_CARDINALITY: u16 = 1_024;
+
+// Pause flags of a Whirlpool, trading and liquidity increases are blocked while any flag is set.
+// The Whirlpool flag is set by the emergency authority, the config flag mirrors the pause state
+// of the WhirlpoolsConfig and can be synced by anyone.
+pub const PAUSE_FLAG_WHIRLPOOL: u8 = 1 << 0;
+pub const PAUSE_FLAG_CONFIG: u8 = 1 << 1;
+
 #[zero_copy(unsafe)]
 #[repr(C, packed)]
 #[derive(Default, Debug, PartialEq, Eq)]
@@ -244,6 +256,90 @@ impl AdaptiveFeeVariables {
     }
 }
 
+#[zero_copy(unsafe)]
+#[repr(C, packed)]
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct FeeRateSchedule {
+    // Fee rate when the ramp starts
+    pub start_fee_rate: u16,
+    // Fee rate from effective_timestamp onwards
+    pub target_fee_rate: u16,
+    // The fee rate moves linearly from start_fee_rate to target_fee_rate between
+    // ramp_start_timestamp and effective_timestamp (equal timestamps mean a step change)
+    pub ramp_start_timestamp: u64,
+    // 0 if no fee rate change is scheduled
+    pub effective_timestamp: u64,
+}
+
+impl FeeRateSchedule {
+    pub const LEN: usize = 2 + 2 + 8 + 8;
+
+    pub fn new(
+        start_fee_rate: u16,
+        target_fee_rate: u16,
+        ramp_start_timestamp: u64,
+        effective_timestamp: u64,
+        current_timestamp: u64,
+    ) -> Result<Self> {
+        if target_fee_rate > MAX_FEE_RATE
+            || ramp_start_timestamp < current_timestamp
+            || effective_timestamp < ramp_start_timestamp
+            || effective_timestamp == 0
+        {
+            return Err(ErrorCode::InvalidFeeRateSchedule.into());
+        }
+
+        Ok(Self {
+            start_fee_rate,
+            target_fee_rate,
+            ramp_start_timestamp,
+            effective_timestamp,
+        })
+    }
+
+    pub fn is_scheduled(&self) -> bool {
+        self.effective_timestamp != 0
+    }
+
+    pub fn is_completed(&self, timestamp: u64) -> bool {
+        self.is_scheduled() && timestamp >= self.effective_timestamp
+    }
+
+    // Returns the fee rate to be applied at the given timestamp,
+    // or None if the scheduled change has not started yet.
+    pub fn fee_rate_at(&self, timestamp: u64) -> Option<u16> {
+        if !self.is_scheduled() || timestamp < self.ramp_start_timestamp {
+            return None;
+        }
+        if self.is_completed(timestamp) {
+            return Some(self.target_fee_rate);
+        }
+
+        // ramp_start_timestamp <= timestamp < effective_timestamp
+        let elapsed = (timestamp - self.ramp_start_timestamp) as i64;
+        let duration = (self.effective_timestamp - self.ramp_start_timestamp) as i64;
+        let start_fee_rate = self.start_fee_rate as i64;
+        let target_fee_rate = self.target_fee_rate as i64;
+        let fee_rate = start_fee_rate + (target_fee_rate - start_fee_rate) * elapsed / duration;
+        Some(fee_rate as u16)
+    }
+
+    // Returns the fee rate to be applied at the given timestamp to a Whirlpool with the given fee rate,
+    // or None if the scheduled change has not started yet or no longer applies.
+    // start_fee_rate is the fee rate of the Whirlpool when the change was scheduled, and swaps only
+    // move it towards the fee rate at the given timestamp. Any other fee rate has been set directly,
+    // which takes precedence over the schedule even if the Oracle was not cleared at that time.
+    pub fn fee_rate_for(&self, whirlpool_fee_rate: u16, timestamp: u64) -> Option<u16> {
+        let fee_rate = self.fee_rate_at(timestamp)?;
+        let lower_fee_rate = self.start_fee_rate.min(fee_rate);
+        let upper_fee_rate = self.start_fee_rate.max(fee_rate);
+        if whirlpool_fee_rate < lower_fee_rate || whirlpool_fee_rate > upper_fee_rate {
+            return None;
+        }
+        Some(fee_rate)
+    }
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct AdaptiveFeeInfo {
     pub constants: AdaptiveFeeConstants,
@@ -258,8 +354,18 @@ pub struct Oracle {
     pub trade_enable_timestamp: u64,
     pub adaptive_fee_constants: AdaptiveFeeConstants,
     pub adaptive_fee_variables: AdaptiveFeeVariables,
+    // Index of the most recently written observation
+    pub observation_index: u16,
+    // Number of observations currently used in the ring buffer (0 if observations are disabled)
+    pub observation_cardinality: u16,
+    // Number of observations allocated in the account, the ring buffer grows to it on the next wrap
+    pub observation_cardinality_next: u16,
+    // Combination of PAUSE_FLAG_* (0 if the Whirlpool is not paused)
+    pub pause_flags: u8,
+    // Fee rate change scheduled by the fee authority, applied by swaps
+    pub fee_rate_schedule: FeeRateSchedule,
     // Reserved for future use
-    pub reserved: [u8; 128],
+    pub reserved: [u8; 101],
 }
 
 impl Default for Oracle {
@@ -269,13 +375,28 @@ impl Default for Oracle {
             trade_enable_timestamp: 0,
             adaptive_fee_constants: AdaptiveFeeConstants::default(),
             adaptive_fee_variables: AdaptiveFeeVariables::default(),
-            reserved: [0u8; 128],
+            observation_index: 0,
+            observation_cardinality: 0,
+            observation_cardinality_next: 0,
+            pause_flags: 0,
+            fee_rate_schedule: FeeRateSchedule::default(),
+            reserved: [0u8; 101],
         }
     }
 }
 
 impl Oracle {
-    pub const LEN: usize = 8 + 32 + 8 + AdaptiveFeeConstants::LEN + AdaptiveFeeVariables::LEN + 128;
+    pub const LEN: usize = 8
+        + 32
+        + 8
+        + AdaptiveFeeConstants::LEN
+        + AdaptiveFeeVariables::LEN
+        + 2
+        + 2
+        + 2
+        + 1
+        + FeeRateSchedule::LEN
+        + 101;
 
     #[allow(clippy::too_many_arguments)]
     pub fn initialize(
@@ -334,13 +455,314 @@ impl Oracle {
         Ok(())
     }
 
+    // Initializes an Oracle without adaptive fee for a Whirlpool that did not have one, so that it can hold pause flags.
+    pub fn initialize_without_adaptive_fee(&mut self, whirlpool: Pubkey) {
+        self.whirlpool = whirlpool;
+        self.trade_enable_timestamp = 0;
+        self.adaptive_fee_constants = AdaptiveFeeConstants::default();
+        self.reset_adaptive_fee_variables();
+    }
+
+    // Valid adaptive fee constants are never all zero, so zeroed constants mean the Oracle has no adaptive fee.
+    pub fn is_adaptive_fee_enabled(&self) -> bool {
+        let constants = self.adaptive_fee_constants;
+        constants != AdaptiveFeeConstants::default()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.pause_flags != 0
+    }
+
+    pub fn update_pause_flag(&mut self, flag: u8, paused: bool) {
+        if paused {
+            self.pause_flags |= flag;
+        } else {
+            self.pause_flags &= !flag;
+        }
+    }
+
     pub fn update_adaptive_fee_variables(&mut self, variables: AdaptiveFeeVariables) {
         self.adaptive_fee_variables = variables;
     }
 
+    pub fn update_fee_rate_schedule(&mut self, fee_rate_schedule: FeeRateSchedule) {
+        self.fee_rate_schedule = fee_rate_schedule;
+    }
+
+    pub fn clear_fee_rate_schedule(&mut self) {
+        self.fee_rate_schedule = FeeRateSchedule::default();
+    }
+
     fn reset_adaptive_fee_variables(&mut self) {
         self.adaptive_fee_variables = AdaptiveFeeVariables::default();
     }
+
+    pub fn space_with_observations(observation_cardinality: u16) -> usize {
+        Oracle::LEN + observation_cardinality as usize * OracleObservation::LEN
+    }
+
+    // Observations are stored right after the Oracle struct, the account is reallocated to grow them.
+    pub fn split_observations(data: &[u8]) -> Result<(&Oracle, &[OracleObservation])> {
+        if data.len() < Oracle::LEN {
+            return Err(anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into());
+        }
+        let (oracle_data, observations_data) = data[8..].split_at(Oracle::LEN - 8);
+        let observation_count = observations_data.len() / OracleObservation::LEN;
+        Ok((
+            bytemuck::from_bytes(oracle_data),
+            bytemuck::cast_slice(&observations_data[..observation_count * OracleObservation::LEN]),
+        ))
+    }
+
+    pub fn split_observations_mut(
+        data: &mut [u8],
+    ) -> Result<(&mut Oracle, &mut [OracleObservation])> {
+        if data.len() < Oracle::LEN {
+            return Err(anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into());
+        }
+        let (oracle_data, observations_data) = data[8..].split_at_mut(Oracle::LEN - 8);
+        let observation_count = observations_data.len() / OracleObservation::LEN;
+        Ok((
+            bytemuck::from_bytes_mut(oracle_data),
+            bytemuck::cast_slice_mut(
+                &mut observations_data[..observation_count * OracleObservation::LEN],
+            ),
+        ))
+    }
+
+    pub fn increase_observation_cardinality(
+        &mut self,
+        observations: &mut [OracleObservation],
+        observation_cardinality_next: u16,
+        timestamp: u64,
+    ) -> Result<()> {
+        if observation_cardinality_next <= self.observation_cardinality_next
+            || observation_cardinality_next > MAX_OBSERVATION_CARDINALITY
+        {
+            return Err(ErrorCode::InvalidObservationCardinality.into());
+        }
+        if observations.len() < observation_cardinality_next as usize {
+            return Err(anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into());
+        }
+
+        // The first observation starts the cumulative values from zero.
+        if self.observation_cardinality == 0 {
+            observations[0] = OracleObservation {
+                timestamp,
+                tick_cumulative: 0,
+                sqrt_price_cumulative: 0,
+            };
+            self.observation_index = 0;
+            self.observation_cardinality = 1;
+        }
+
+        self.observation_cardinality_next = observation_cardinality_next;
+        Ok(())
+    }
+
+    // Records the tick index and sqrt price that were active since the last observation.
+    // Must be called with the state before the swap is applied to the whirlpool.
+    pub fn write_observation(
+        &mut self,
+        observations: &mut [OracleObservation],
+        timestamp: u64,
+        tick_index: i32,
+        sqrt_price: u128,
+    ) -> Result<()> {
+        if self.observation_cardinality == 0 {
+            return Ok(());
+        }
+
+        let last = observations[self.observation_index as usize];
+        if timestamp == last.timestamp {
+            // only one observation per block time
+            return Ok(());
+        }
+        if timestamp < last.timestamp {
+            return Err(ErrorCode::InvalidTimestamp.into());
+        }
+
+        // grow the ring buffer once the write position reaches its end
+        if self.observation_cardinality_next > self.observation_cardinality
+            && self.observation_index == self.observation_cardinality - 1
+        {
+            self.observation_cardinality = self.observation_cardinality_next;
+        }
+
+        self.observation_index = (self.observation_index + 1) % self.observation_cardinality;
+        observations[self.observation_index as usize] =
+            last.transform(timestamp, tick_index, sqrt_price);
+        Ok(())
+    }
+
+    // Returns the cumulative values as of `seconds_ago` seconds before `timestamp`.
+    // `tick_index` and `sqrt_price` must be the current state of the whirlpool.
+    pub fn observe(
+        &self,
+        observations: &[OracleObservation],
+        timestamp: u64,
+        tick_index: i32,
+        sqrt_price: u128,
+        seconds_ago: u32,
+    ) -> Result<OracleObservation> {
+        if self.observation_cardinality == 0 {
+            return Err(ErrorCode::OracleObservationsNotInitialized.into());
+        }
+
+        let cardinality = self.observation_cardinality as usize;
+        let index = self.observation_index as usize;
+        let target = timestamp
+            .checked_sub(seconds_ago as u64)
+            .ok_or(ErrorCode::OracleObservationTooOld)?;
+
+        let last = observations[index];
+        if target >= last.timestamp {
+            if target == last.timestamp {
+                return Ok(last);
+            }
+            return Ok(last.transform(target, tick_index, sqrt_price));
+        }
+
+        let mut oldest = observations[(index + 1) % cardinality];
+        if !oldest.is_initialized() {
+            oldest = observations[0];
+        }
+        if target < oldest.timestamp {
+            return Err(ErrorCode::OracleObservationTooOld.into());
+        }
+
+        let (before, after) =
+            find_surrounding_observations(observations, cardinality, index, target);
+        if target == before.timestamp {
+            return Ok(before);
+        }
+        if target == after.timestamp {
+            return Ok(after);
+        }
+
+        // The tick index and sqrt price are constant between two observations.
+        let elapsed = target - before.timestamp;
+        let duration = after.timestamp - before.timestamp;
+        let tick_index =
+            after.tick_cumulative.wrapping_sub(before.tick_cumulative) / duration as i64;
+        let sqrt_price = after
+            .sqrt_price_cumulative
+            .wrapping_sub(before.sqrt_price_cumulative)
+            / duration as u128;
+        Ok(OracleObservation {
+            timestamp: target,
+            tick_cumulative: before
+                .tick_cumulative
+                .wrapping_add(tick_index.wrapping_mul(elapsed as i64)),
+            sqrt_price_cumulative: before
+                .sqrt_price_cumulative
+                .wrapping_add(sqrt_price.wrapping_mul(elapsed as u128)),
+        })
+    }
+
+    // Returns the time-weighted average tick index and sqrt price over the last `window` seconds.
+    pub fn get_twap(
+        &self,
+        observations: &[OracleObservation],
+        timestamp: u64,
+        tick_index: i32,
+        sqrt_price: u128,
+        window: u32,
+    ) -> Result<OracleTwap> {
+        if window == 0 {
+            return Err(ErrorCode::InvalidTwapWindow.into());
+        }
+
+        let current = self.observe(observations, timestamp, tick_index, sqrt_price, 0)?;
+        let past = self.observe(observations, timestamp, tick_index, sqrt_price, window)?;
+
+        let tick_delta = current.tick_cumulative.wrapping_sub(past.tick_cumulative);
+        let mut twap_tick_index = tick_delta / window as i64;
+        // round towards negative infinity
+        if tick_delta < 0 && tick_delta % window as i64 != 0 {
+            twap_tick_index -= 1;
+        }
+
+        let twap_sqrt_price = current
+            .sqrt_price_cumulative
+            .wrapping_sub(past.sqrt_price_cumulative)
+            / window as u128;
+
+        Ok(OracleTwap {
+            tick_index: twap_tick_index as i32,
+            sqrt_price: twap_sqrt_price,
+        })
+    }
+}
+
+fn find_surrounding_observations(
+    observations: &[OracleObservation],
+    cardinality: usize,
+    index: usize,
+    target: u64,
+) -> (OracleObservation, OracleObservation) {
+    // binary search over the ring buffer, starting from the oldest observation
+    let mut left = index + 1;
+    let mut right = index + cardinality;
+    loop {
+        let i = (left + right) / 2;
+        let before = observations[i % cardinality];
+        if !before.is_initialized() {
+            left = i + 1;
+            continue;
+        }
+        let after = observations[(i + 1) % cardinality];
+        let target_at_or_after = before.timestamp <= target;
+        if target_at_or_after && target <= after.timestamp {
+            return (before, after);
+        }
+        if target_at_or_after {
+            left = i + 1;
+        } else {
+            right = i - 1;
+        }
+    }
+}
+
+// Observations are cast from the account data behind the Oracle struct, so they must be Pod.
+// The packed layout has no padding, which makes the derive valid.
+#[zero_copy(unsafe)]
+#[repr(C, packed)]
+#[derive(Default, Debug, PartialEq, Eq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct OracleObservation {
+    // Block time of the observation, zero if the observation has not been written yet
+    pub timestamp: u64,
+    // Tick index accumulated over time (tick index * seconds)
+    pub tick_cumulative: i64,
+    // Sqrt price (Q64.64) accumulated over time (sqrt price * seconds), wraps around on overflow
+    pub sqrt_price_cumulative: u128,
+}
+
+impl OracleObservation {
+    pub const LEN: usize = 8 + 8 + 16;
+
+    pub fn is_initialized(&self) -> bool {
+        self.timestamp != 0
+    }
+
+    pub fn transform(&self, timestamp: u64, tick_index: i32, sqrt_price: u128) -> Self {
+        let elapsed = timestamp - self.timestamp;
+        Self {
+            timestamp,
+            tick_cumulative: self
+                .tick_cumulative
+                .wrapping_add((tick_index as i64).wrapping_mul(elapsed as i64)),
+            sqrt_price_cumulative: self
+                .sqrt_price_cumulative
+                .wrapping_add(sqrt_price.wrapping_mul(elapsed as u128)),
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OracleTwap {
+    pub tick_index: i32,
+    pub sqrt_price: u128,
 }
 
 pub struct OracleAccessor<'info> {
@@ -370,18 +792,73 @@ impl<'info> OracleAccessor<'info> {
         Ok(oracle.trade_enable_timestamp <= current_timestamp)
     }
 
+    pub fn is_paused(&self) -> Result<bool> {
+        if !self.oracle_account_initialized {
+            return Ok(false);
+        }
+
+        let oracle = self.load()?;
+        Ok(oracle.is_paused())
+    }
+
     pub fn get_adaptive_fee_info(&self) -> Result<Option<AdaptiveFeeInfo>> {
         if !self.oracle_account_initialized {
             return Ok(None);
         }
 
         let oracle = self.load()?;
+        if !oracle.is_adaptive_fee_enabled() {
+            return Ok(None);
+        }
+
         Ok(Some(AdaptiveFeeInfo {
             constants: oracle.adaptive_fee_constants,
             variables: oracle.adaptive_fee_variables,
         }))
     }
 
+    // Applies the scheduled fee rate change to the Whirlpool before a swap.
+    // A completed schedule is cleared if the Oracle account is writable, otherwise it is left in place.
+    pub fn apply_fee_rate_schedule(
+        &self,
+        whirlpool: &mut Whirlpool,
+        current_timestamp: u64,
+    ) -> Result<()> {
+        if !self.oracle_account_initialized {
+            return Ok(());
+        }
+
+        let fee_rate_schedule = self.load()?.fee_rate_schedule;
+        let fee_rate = match fee_rate_schedule.fee_rate_for(whirlpool.fee_rate, current_timestamp) {
+            Some(fee_rate) => fee_rate,
+            None => return Ok(()),
+        };
+
+        // Applying a completed schedule again only sets the target fee rate that is already in effect,
+        // so it is fine to leave it for the next swap with a writable Oracle account to clear.
+        if fee_rate_schedule.is_completed(current_timestamp) && self.oracle_account_info.is_writable
+        {
+            self.load_mut()?.clear_fee_rate_schedule();
+        }
+
+        whirlpool.update_fee_rate(fee_rate)
+    }
+
+    // Clears the scheduled fee rate change, active or completed, so swaps no longer apply it.
+    // Used when the fee rate is set directly, which takes precedence over the schedule.
+    pub fn clear_fee_rate_schedule(&self) -> Result<()> {
+        if !self.oracle_account_initialized {
+            return Ok(());
+        }
+
+        let fee_rate_schedule = self.load()?.fee_rate_schedule;
+        if fee_rate_schedule.is_scheduled() {
+            self.load_mut()?.clear_fee_rate_schedule();
+        }
+
+        Ok(())
+    }
+
     pub fn update_adaptive_fee_variables(
         &self,
         adaptive_fee_info: &Option<AdaptiveFeeInfo>,
@@ -398,10 +875,34 @@ impl<'info> OracleAccessor<'info> {
             }
             // Oracle account has not been initialized and adaptive fee info is not provided
             (false, None) => Ok(()),
+            // Oracle account has been initialized only to hold pause flags and adaptive fee info is not provided
+            (true, None) if !self.load()?.is_adaptive_fee_enabled() => Ok(()),
             _ => unreachable!(),
         }
     }
 
+    pub fn write_observation(
+        &self,
+        timestamp: u64,
+        tick_index: i32,
+        sqrt_price: u128,
+    ) -> Result<()> {
+        // Observations are opt-in, so the Oracle account is only written if they have been enabled.
+        if !self.oracle_account_initialized || self.load()?.observation_cardinality == 0 {
+            return Ok(());
+        }
+
+        // Anyone can enable observations, so they must not break swaps that pass the Oracle account
+        // as read-only (e.g. swap and two_hop_swap). The observation is skipped for those swaps.
+        if !self.oracle_account_info.is_writable {
+            return Ok(());
+        }
+
+        let mut data = self.oracle_account_info.try_borrow_mut_data()?;
+        let (oracle, observations) = Oracle::split_observations_mut(&mut data)?;
+        oracle.write_observation(observations, timestamp, tick_index, sqrt_price)
+    }
+
     fn is_oracle_account_initialized(
         oracle_account_info: &AccountInfo<'info>,
         whirlpool: Pubkey,
@@ -486,7 +987,16 @@ mod data_layout_tests {
 
     #[test]
     fn test_oracle_data_layout() {
-        let oracle_reserved = [0u8; 128];
+        let oracle_observation_index = 0x1122u16;
+        let oracle_observation_cardinality = 0x3344u16;
+        let oracle_observation_cardinality_next = 0x5566u16;
+        let oracle_pause_flags = 0x03u8;
+        let oracle_reserved = [0u8; 101];
+
+        let schedule_start_fee_rate = 0x1122u16;
+        let schedule_target_fee_rate = 0x3344u16;
+        let schedule_ramp_start_timestamp = 0x1122334455667788u64;
+        let schedule_effective_timestamp = 0x99aabbccddeeff00u64;
 
         let oracle_whirlpool = Pubkey::new_unique();
         let oracle_trade_enable_timestamp = 0x1122334455667788u64;
@@ -565,6 +1075,26 @@ mod data_layout_tests {
         offset += AdaptiveFeeConstants::LEN;
         oracle_data[offset..offset + AdaptiveFeeVariables::LEN].copy_from_slice(&af_var_data);
         offset += AdaptiveFeeVariables::LEN;
+        oracle_data[offset..offset + 2].copy_from_slice(&oracle_observation_index.to_le_bytes());
+        offset += 2;
+        oracle_data[offset..offset + 2]
+            .copy_from_slice(&oracle_observation_cardinality.to_le_bytes());
+        offset += 2;
+        oracle_data[offset..offset + 2]
+            .copy_from_slice(&oracle_observation_cardinality_next.to_le_bytes());
+        offset += 2;
+        oracle_data[offset] = oracle_pause_flags;
+        offset += 1;
+        oracle_data[offset..offset + 2].copy_from_slice(&schedule_start_fee_rate.to_le_bytes());
+        offset += 2;
+        oracle_data[offset..offset + 2].copy_from_slice(&schedule_target_fee_rate.to_le_bytes());
+        offset += 2;
+        oracle_data[offset..offset + 8]
+            .copy_from_slice(&schedule_ramp_start_timestamp.to_le_bytes());
+        offset += 8;
+        oracle_data[offset..offset + 8]
+            .copy_from_slice(&schedule_effective_timestamp.to_le_bytes());
+        offset += 8;
         oracle_data[offset..offset + oracle_reserved.len()].copy_from_slice(&oracle_reserved);
         offset += oracle_reserved.len();
 
@@ -635,55 +1165,285 @@ mod data_layout_tests {
             read_af_var_volatility_accumulator,
             af_var_volatility_accumulator
         );
+
+        let read_observation_index = oracle.observation_index;
+        assert_eq!(read_observation_index, oracle_observation_index);
+        let read_observation_cardinality = oracle.observation_cardinality;
+        assert_eq!(read_observation_cardinality, oracle_observation_cardinality);
+        let read_observation_cardinality_next = oracle.observation_cardinality_next;
+        assert_eq!(
+            read_observation_cardinality_next,
+            oracle_observation_cardinality_next
+        );
+        let read_pause_flags = oracle.pause_flags;
+        assert_eq!(read_pause_flags, oracle_pause_flags);
+
+        let read_schedule_start_fee_rate = oracle.fee_rate_schedule.start_fee_rate;
+        assert_eq!(read_schedule_start_fee_rate, schedule_start_fee_rate);
+        let read_schedule_target_fee_rate = oracle.fee_rate_schedule.target_fee_rate;
+        assert_eq!(read_schedule_target_fee_rate, schedule_target_fee_rate);
+        let read_schedule_ramp_start_timestamp = oracle.fee_rate_schedule.ramp_start_timestamp;
+        assert_eq!(
+            read_schedule_ramp_start_timestamp,
+            schedule_ramp_start_timestamp
+        );
+        let read_schedule_effective_timestamp = oracle.fee_rate_schedule.effective_timestamp;
+        assert_eq!(
+            read_schedule_effective_timestamp,
+            schedule_effective_timestamp
+        );
+    }
+
+    #[test]
+    fn test_oracle_observation_data_layout() {
+        let timestamp = 0x1122334455667788u64;
+        let tick_cumulative = -0x0011223344556677i64;
+        let sqrt_price_cumulative = 0x11223344556677889900aabbccddeeffu128;
+
+        let mut observation_data = [0u8; OracleObservation::LEN];
+        observation_data[0..8].copy_from_slice(&timestamp.to_le_bytes());
+        observation_data[8..16].copy_from_slice(&tick_cumulative.to_le_bytes());
+        observation_data[16..32].copy_from_slice(&sqrt_price_cumulative.to_le_bytes());
+
+        let observation: &OracleObservation = bytemuck::from_bytes(&observation_data);
+
+        let read_timestamp = observation.timestamp;
+        assert_eq!(read_timestamp, timestamp);
+        let read_tick_cumulative = observation.tick_cumulative;
+        assert_eq!(read_tick_cumulative, tick_cumulative);
+        let read_sqrt_price_cumulative = observation.sqrt_price_cumulative;
+        assert_eq!(read_sqrt_price_cumulative, sqrt_price_cumulative);
     }
 }
 
 #[cfg(test)]
-mod oracle_accessor_test {
+mod oracle_observation_tests {
     use super::*;
-    use crate::util::test_utils::account_info_mock::AccountInfoMock;
+
+    fn oracle_with_observations(cardinality_next: u16) -> (Oracle, Vec<OracleObservation>) {
+        let mut oracle = Oracle::default();
+        let mut observations = vec![OracleObservation::default(); cardinality_next as usize];
+        oracle
+            .increase_observation_cardinality(&mut observations, cardinality_next, 1_000)
+            .unwrap();
+        (oracle, observations)
+    }
 
     #[test]
-    fn new_with_uninitialized_oracle_account_not_writable() {
-        let is_writable = false;
+    fn test_split_observations_account_data() {
+        let mut data = vec![0u8; Oracle::space_with_observations(3)];
 
-        let whirlpool_address = Pubkey::new_unique();
-        let mut account_info_mock =
-            AccountInfoMock::new_whirlpool(whirlpool_address, 64, 5650, None);
-        let account_info = account_info_mock.to_account_info(false);
-        let whirlpool = Account::<Whirlpool>::try_from(&account_info).unwrap();
+        {
+            let (oracle, observations) = Oracle::split_observations_mut(&mut data).unwrap();
+            assert_eq!(observations.len(), 3);
+            oracle
+                .increase_observation_cardinality(observations, 3, 1_000)
+                .unwrap();
+            observations[2] = OracleObservation {
+                timestamp: 3_000,
+                tick_cumulative: -256,
+                sqrt_price_cumulative: 1 << 80,
+            };
+        }
 
-        let account_address = Pubkey::new_unique();
-        let mut account_info_mock = AccountInfoMock::new(account_address, vec![], System::id());
-        let account_info = account_info_mock.to_account_info(is_writable);
+        // Observations are stored right after the Oracle struct
+        let observation_data = &data[Oracle::LEN + 2 * OracleObservation::LEN..];
+        assert_eq!(observation_data[0..8], 3_000u64.to_le_bytes());
+        assert_eq!(observation_data[8..16], (-256i64).to_le_bytes());
+        assert_eq!(observation_data[16..32], (1u128 << 80).to_le_bytes());
+
+        let (oracle, observations) = Oracle::split_observations(&data).unwrap();
+        assert_eq!({ oracle.observation_cardinality }, 1);
+        assert_eq!({ oracle.observation_cardinality_next }, 3);
+        assert_eq!({ observations[0].timestamp }, 1_000);
+        assert_eq!(observations[1], OracleObservation::default());
+        assert_eq!({ observations[2].timestamp }, 3_000);
+        assert_eq!({ observations[2].tick_cumulative }, -256);
+        assert_eq!({ observations[2].sqrt_price_cumulative }, 1 << 80);
+
+        // A trailing partial observation is ignored
+        data.extend_from_slice(&[0u8; OracleObservation::LEN - 1]);
+        let (_, observations) = Oracle::split_observations(&data).unwrap();
+        assert_eq!(observations.len(), 3);
+    }
 
-        let result = OracleAccessor::new(&whirlpool, account_info);
-        assert!(result.is_ok());
-        assert!(!result.unwrap().oracle_account_initialized);
+    #[test]
+    fn test_increase_observation_cardinality() {
+        let (mut oracle, mut observations) = oracle_with_observations(2);
+        assert_eq!({ oracle.observation_index }, 0);
+        assert_eq!({ oracle.observation_cardinality }, 1);
+        assert_eq!({ oracle.observation_cardinality_next }, 2);
+        assert_eq!({ observations[0].timestamp }, 1_000);
+
+        // cardinality can only grow
+        assert!(oracle
+            .increase_observation_cardinality(&mut observations, 2, 2_000)
+            .is_err());
+        // the account must hold the new observations
+        assert!(oracle
+            .increase_observation_cardinality(&mut observations, 3, 2_000)
+            .is_err());
+        assert!(oracle
+            .increase_observation_cardinality(
+                &mut vec![OracleObservation::default(); 2_000],
+                MAX_OBSERVATION_CARDINALITY + 1,
+                2_000
+            )
+            .is_err());
+
+        observations.push(OracleObservation::default());
+        oracle
+            .increase_observation_cardinality(&mut observations, 3, 2_000)
+            .unwrap();
+        // the first observation is kept
+        assert_eq!({ observations[0].timestamp }, 1_000);
+        assert_eq!({ oracle.observation_cardinality }, 1);
+        assert_eq!({ oracle.observation_cardinality_next }, 3);
     }
 
     #[test]
-    fn new_with_uninitialized_oracle_account_writable() {
-        let is_writable = true;
+    fn test_write_observation_without_cardinality() {
+        let mut oracle = Oracle::default();
+        oracle
+            .write_observation(&mut [], 1_000, 100, 1 << 64)
+            .unwrap();
+        assert_eq!({ oracle.observation_cardinality }, 0);
+    }
 
-        let whirlpool_address = Pubkey::new_unique();
-        let mut account_info_mock =
-            AccountInfoMock::new_whirlpool(whirlpool_address, 64, 5650, None);
-        let account_info = account_info_mock.to_account_info(false);
-        let whirlpool = Account::<Whirlpool>::try_from(&account_info).unwrap();
+    #[test]
+    fn test_write_observation_grows_and_wraps() {
+        let (mut oracle, mut observations) = oracle_with_observations(3);
 
-        let account_address = Pubkey::new_unique();
-        let mut account_info_mock = AccountInfoMock::new(account_address, vec![], System::id());
-        let account_info = account_info_mock.to_account_info(is_writable);
+        oracle
+            .write_observation(&mut observations, 1_010, 100, 1 << 64)
+            .unwrap();
+        assert_eq!({ oracle.observation_index }, 1);
+        assert_eq!({ oracle.observation_cardinality }, 3);
+        assert_eq!({ observations[1].tick_cumulative }, 1_000);
+        assert_eq!({ observations[1].sqrt_price_cumulative }, 10 << 64);
 
-        let result = OracleAccessor::new(&whirlpool, account_info);
-        assert!(result.is_ok());
-        assert!(!result.unwrap().oracle_account_initialized);
+        // same block time is ignored
+        oracle
+            .write_observation(&mut observations, 1_010, 200, 2 << 64)
+            .unwrap();
+        assert_eq!({ oracle.observation_index }, 1);
+
+        oracle
+            .write_observation(&mut observations, 1_020, -50, 2 << 64)
+            .unwrap();
+        oracle
+            .write_observation(&mut observations, 1_030, 10, 1 << 64)
+            .unwrap();
+        assert_eq!({ oracle.observation_index }, 0);
+        assert_eq!({ observations[0].timestamp }, 1_030);
+        assert_eq!({ observations[0].tick_cumulative }, 1_000 - 500 + 100);
+
+        assert!(oracle
+            .write_observation(&mut observations, 1_000, 10, 1 << 64)
+            .is_err());
     }
 
     #[test]
-    fn new_with_initialized_oracle_account_not_writable() {
-        let is_writable = false;
+    fn test_get_twap() {
+        let (mut oracle, mut observations) = oracle_with_observations(4);
+        // tick 100 during [1_000, 1_100), tick -200 during [1_100, 1_200), tick 300 afterwards
+        oracle
+            .write_observation(&mut observations, 1_100, 100, 4 << 64)
+            .unwrap();
+        oracle
+            .write_observation(&mut observations, 1_200, -200, 2 << 64)
+            .unwrap();
+
+        let twap = oracle
+            .get_twap(&observations, 1_300, 300, 6 << 64, 100)
+            .unwrap();
+        assert_eq!(twap.tick_index, 300);
+        assert_eq!(twap.sqrt_price, 6 << 64);
+
+        let twap = oracle
+            .get_twap(&observations, 1_300, 300, 6 << 64, 200)
+            .unwrap();
+        assert_eq!(twap.tick_index, 50);
+        assert_eq!(twap.sqrt_price, 4 << 64);
+
+        // interpolated between observations
+        let twap = oracle
+            .get_twap(&observations, 1_200, 300, 6 << 64, 150)
+            .unwrap();
+        assert_eq!(twap.tick_index, -100);
+        assert_eq!(twap.sqrt_price, (8 << 64) / 3);
+
+        // rounds towards negative infinity
+        let twap = oracle
+            .get_twap(&observations, 1_200, 300, 6 << 64, 70)
+            .unwrap();
+        assert_eq!(twap.tick_index, -200);
+        let twap = oracle
+            .get_twap(&observations, 1_130, 300, 6 << 64, 70)
+            .unwrap();
+        assert_eq!(twap.tick_index, -29);
+
+        assert!(oracle
+            .get_twap(&observations, 1_300, 300, 6 << 64, 301)
+            .is_err());
+        assert!(oracle
+            .get_twap(&observations, 1_300, 300, 6 << 64, 0)
+            .is_err());
+    }
+
+    #[test]
+    fn test_get_twap_without_observations() {
+        let oracle = Oracle::default();
+        assert!(oracle.get_twap(&[], 1_300, 300, 6 << 64, 100).is_err());
+    }
+}
+
+#[cfg(test)]
+mod oracle_accessor_test {
+    use super::*;
+    use crate::util::test_utils::account_info_mock::AccountInfoMock;
+
+    #[test]
+    fn new_with_uninitialized_oracle_account_not_writable() {
+        let is_writable = false;
+
+        let whirlpool_address = Pubkey::new_unique();
+        let mut account_info_mock =
+            AccountInfoMock::new_whirlpool(whirlpool_address, 64, 5650, None);
+        let account_info = account_info_mock.to_account_info(false);
+        let whirlpool = Account::<Whirlpool>::try_from(&account_info).unwrap();
+
+        let account_address = Pubkey::new_unique();
+        let mut account_info_mock = AccountInfoMock::new(account_address, vec![], System::id());
+        let account_info = account_info_mock.to_account_info(is_writable);
+
+        let result = OracleAccessor::new(&whirlpool, account_info);
+        assert!(result.is_ok());
+        assert!(!result.unwrap().oracle_account_initialized);
+    }
+
+    #[test]
+    fn new_with_uninitialized_oracle_account_writable() {
+        let is_writable = true;
+
+        let whirlpool_address = Pubkey::new_unique();
+        let mut account_info_mock =
+            AccountInfoMock::new_whirlpool(whirlpool_address, 64, 5650, None);
+        let account_info = account_info_mock.to_account_info(false);
+        let whirlpool = Account::<Whirlpool>::try_from(&account_info).unwrap();
+
+        let account_address = Pubkey::new_unique();
+        let mut account_info_mock = AccountInfoMock::new(account_address, vec![], System::id());
+        let account_info = account_info_mock.to_account_info(is_writable);
+
+        let result = OracleAccessor::new(&whirlpool, account_info);
+        assert!(result.is_ok());
+        assert!(!result.unwrap().oracle_account_initialized);
+    }
+
+    #[test]
+    fn new_with_initialized_oracle_account_not_writable() {
+        let is_writable = false;
 
         let whirlpool_address = Pubkey::new_unique();
         let mut account_info_mock =
@@ -1011,18 +1771,6 @@ mod oracle_accessor_test {
         let account_info = account_info_mock.to_account_info(false);
         let whirlpool = Account::<Whirlpool>::try_from(&account_info).unwrap();
 
-        let account_address = Pubkey::new_unique();
-        let mut account_info_mock = AccountInfoMock::new_oracle(
-            account_address,
-            whirlpool_address,
-            100,
-            AdaptiveFeeConstants::default(),
-            None,
-        );
-        let account_info = account_info_mock.to_account_info(true);
-        let result = OracleAccessor::new(&whirlpool, account_info);
-        let accessor = result.unwrap();
-
         let af_consts = AdaptiveFeeConstants {
             filter_period: 0x7777,
             decay_period: 0xffff,
@@ -1034,6 +1782,13 @@ mod oracle_accessor_test {
             ..Default::default()
         };
 
+        let account_address = Pubkey::new_unique();
+        let mut account_info_mock =
+            AccountInfoMock::new_oracle(account_address, whirlpool_address, 100, af_consts, None);
+        let account_info = account_info_mock.to_account_info(true);
+        let result = OracleAccessor::new(&whirlpool, account_info);
+        let accessor = result.unwrap();
+
         let af_vars = AdaptiveFeeVariables {
             last_reference_update_timestamp: 0x1122334455667788u64,
             last_major_swap_timestamp: 0x2233445566778899u64,
@@ -1044,7 +1799,7 @@ mod oracle_accessor_test {
         };
 
         let adaptive_fee_info = AdaptiveFeeInfo {
-            constants: af_consts,
+            constants: AdaptiveFeeConstants::default(),
             variables: af_vars,
         };
 
@@ -1054,7 +1809,7 @@ mod oracle_accessor_test {
 
         let adaptive_fee_info = accessor.get_adaptive_fee_info().unwrap().unwrap();
         // constants should not be updated
-        assert_eq!(adaptive_fee_info.constants, AdaptiveFeeConstants::default());
+        assert_eq!(adaptive_fee_info.constants, af_consts);
         // variables should be updated
         assert_eq!(adaptive_fee_info.variables, af_vars);
     }
@@ -1068,6 +1823,35 @@ mod oracle_accessor_test {
         let account_info = account_info_mock.to_account_info(false);
         let whirlpool = Account::<Whirlpool>::try_from(&account_info).unwrap();
 
+        let af_consts = AdaptiveFeeConstants {
+            filter_period: 0x7777,
+            decay_period: 0xffff,
+            reduction_factor: 0x9999,
+            adaptive_fee_control_factor: 0x33333333,
+            max_volatility_accumulator: 0x55555555,
+            tick_group_size: 256,
+            major_swap_threshold_ticks: 128,
+            ..Default::default()
+        };
+
+        let account_address = Pubkey::new_unique();
+        let mut account_info_mock =
+            AccountInfoMock::new_oracle(account_address, whirlpool_address, 100, af_consts, None);
+        let account_info = account_info_mock.to_account_info(true);
+        let result = OracleAccessor::new(&whirlpool, account_info);
+        let accessor = result.unwrap();
+
+        let _result = accessor.update_adaptive_fee_variables(&None);
+    }
+
+    #[test]
+    fn update_adaptive_fee_variables_none_initialized_without_adaptive_fee() {
+        let whirlpool_address = Pubkey::new_unique();
+        let mut account_info_mock =
+            AccountInfoMock::new_whirlpool(whirlpool_address, 64, 5650, None);
+        let account_info = account_info_mock.to_account_info(false);
+        let whirlpool = Account::<Whirlpool>::try_from(&account_info).unwrap();
+
         let account_address = Pubkey::new_unique();
         let mut account_info_mock = AccountInfoMock::new_oracle(
             account_address,
@@ -1080,7 +1864,8 @@ mod oracle_accessor_test {
         let result = OracleAccessor::new(&whirlpool, account_info);
         let accessor = result.unwrap();
 
-        let _result = accessor.update_adaptive_fee_variables(&None);
+        assert!(accessor.get_adaptive_fee_info().unwrap().is_none());
+        accessor.update_adaptive_fee_variables(&None).unwrap();
     }
 
     #[test]
@@ -1178,12 +1963,441 @@ mod oracle_accessor_test {
         // should work even if the account is not writable
         accessor.update_adaptive_fee_variables(&None).unwrap();
     }
+
+    fn oracle_account_with_observations(
+        account_address: Pubkey,
+        whirlpool_address: Pubkey,
+        observation_cardinality_next: u16,
+    ) -> AccountInfoMock {
+        let mut account_info_mock = AccountInfoMock::new_oracle(
+            account_address,
+            whirlpool_address,
+            100,
+            AdaptiveFeeConstants::default(),
+            None,
+        );
+        account_info_mock.data.resize(
+            Oracle::space_with_observations(observation_cardinality_next),
+            0,
+        );
+        let (oracle, observations) =
+            Oracle::split_observations_mut(&mut account_info_mock.data).unwrap();
+        oracle
+            .increase_observation_cardinality(observations, observation_cardinality_next, 100)
+            .unwrap();
+        account_info_mock
+    }
+
+    fn oracle_account_with_fee_rate_schedule(
+        account_address: Pubkey,
+        whirlpool_address: Pubkey,
+    ) -> AccountInfoMock {
+        let mut account_info_mock = AccountInfoMock::new_oracle(
+            account_address,
+            whirlpool_address,
+            100,
+            AdaptiveFeeConstants::default(),
+            None,
+        );
+        let (oracle, _) = Oracle::split_observations_mut(&mut account_info_mock.data).unwrap();
+        oracle.update_fee_rate_schedule(
+            FeeRateSchedule::new(3000, 1000, 2_000, 3_000, 1_000).unwrap(),
+        );
+        account_info_mock
+    }
+
+    #[test]
+    fn apply_fee_rate_schedule_ramp_not_writable() {
+        let whirlpool_address = Pubkey::new_unique();
+        let mut account_info_mock =
+            AccountInfoMock::new_whirlpool(whirlpool_address, 64, 5650, None);
+        let account_info = account_info_mock.to_account_info(false);
+        let whirlpool = Account::<Whirlpool>::try_from(&account_info).unwrap();
+
+        let mut account_info_mock =
+            oracle_account_with_fee_rate_schedule(Pubkey::new_unique(), whirlpool_address);
+        let account_info = account_info_mock.to_account_info(false);
+        let accessor = OracleAccessor::new(&whirlpool, account_info).unwrap();
+
+        let mut whirlpool = Whirlpool {
+            fee_rate: 3000,
+            ..Default::default()
+        };
+        accessor
+            .apply_fee_rate_schedule(&mut whirlpool, 2_500)
+            .unwrap();
+        assert_eq!(whirlpool.fee_rate, 2000);
+    }
+
+    #[test]
+    fn apply_fee_rate_schedule_completed_and_writable() {
+        let whirlpool_address = Pubkey::new_unique();
+        let mut account_info_mock =
+            AccountInfoMock::new_whirlpool(whirlpool_address, 64, 5650, None);
+        let account_info = account_info_mock.to_account_info(false);
+        let whirlpool = Account::<Whirlpool>::try_from(&account_info).unwrap();
+
+        let mut account_info_mock =
+            oracle_account_with_fee_rate_schedule(Pubkey::new_unique(), whirlpool_address);
+        let account_info = account_info_mock.to_account_info(true);
+        let accessor = OracleAccessor::new(&whirlpool, account_info).unwrap();
+
+        let mut whirlpool = Whirlpool {
+            fee_rate: 2000,
+            ..Default::default()
+        };
+        accessor
+            .apply_fee_rate_schedule(&mut whirlpool, 3_000)
+            .unwrap();
+        assert_eq!(whirlpool.fee_rate, 1000);
+        drop(accessor);
+
+        let (oracle, _) = Oracle::split_observations(&account_info_mock.data).unwrap();
+        assert_eq!({ oracle.fee_rate_schedule }, FeeRateSchedule::default());
+    }
+
+    #[test]
+    fn apply_fee_rate_schedule_completed_but_not_writable() {
+        let whirlpool_address = Pubkey::new_unique();
+        let mut account_info_mock =
+            AccountInfoMock::new_whirlpool(whirlpool_address, 64, 5650, None);
+        let account_info = account_info_mock.to_account_info(false);
+        let whirlpool = Account::<Whirlpool>::try_from(&account_info).unwrap();
+
+        let mut account_info_mock =
+            oracle_account_with_fee_rate_schedule(Pubkey::new_unique(), whirlpool_address);
+        let account_info = account_info_mock.to_account_info(false);
+        let accessor = OracleAccessor::new(&whirlpool, account_info).unwrap();
+
+        // the completed schedule is applied, but left for a swap with a writable Oracle to clear
+        let mut whirlpool = Whirlpool {
+            fee_rate: 2000,
+            ..Default::default()
+        };
+        accessor
+            .apply_fee_rate_schedule(&mut whirlpool, 3_000)
+            .unwrap();
+        assert_eq!(whirlpool.fee_rate, 1000);
+        accessor
+            .apply_fee_rate_schedule(&mut whirlpool, 3_500)
+            .unwrap();
+        assert_eq!(whirlpool.fee_rate, 1000);
+        drop(accessor);
+
+        let (oracle, _) = Oracle::split_observations(&account_info_mock.data).unwrap();
+        assert!({ oracle.fee_rate_schedule }.is_scheduled());
+    }
+
+    #[test]
+    fn apply_fee_rate_schedule_after_fee_rate_set_directly() {
+        let whirlpool_address = Pubkey::new_unique();
+        let mut account_info_mock =
+            AccountInfoMock::new_whirlpool(whirlpool_address, 64, 5650, None);
+        let account_info = account_info_mock.to_account_info(false);
+        let whirlpool = Account::<Whirlpool>::try_from(&account_info).unwrap();
+
+        let mut account_info_mock =
+            oracle_account_with_fee_rate_schedule(Pubkey::new_unique(), whirlpool_address);
+        let account_info = account_info_mock.to_account_info(false);
+        let accessor = OracleAccessor::new(&whirlpool, account_info).unwrap();
+
+        // the fee rate was set directly without clearing the schedule
+        let mut whirlpool = Whirlpool {
+            fee_rate: 5000,
+            ..Default::default()
+        };
+        accessor
+            .apply_fee_rate_schedule(&mut whirlpool, 2_500)
+            .unwrap();
+        assert_eq!(whirlpool.fee_rate, 5000);
+        accessor
+            .apply_fee_rate_schedule(&mut whirlpool, 3_000)
+            .unwrap();
+        assert_eq!(whirlpool.fee_rate, 5000);
+    }
+
+    #[test]
+    fn clear_fee_rate_schedule_initialized_and_writable() {
+        let whirlpool_address = Pubkey::new_unique();
+        let mut account_info_mock =
+            AccountInfoMock::new_whirlpool(whirlpool_address, 64, 5650, None);
+        let account_info = account_info_mock.to_account_info(false);
+        let whirlpool = Account::<Whirlpool>::try_from(&account_info).unwrap();
+
+        let mut account_info_mock =
+            oracle_account_with_fee_rate_schedule(Pubkey::new_unique(), whirlpool_address);
+        let account_info = account_info_mock.to_account_info(true);
+        let accessor = OracleAccessor::new(&whirlpool, account_info).unwrap();
+
+        accessor.clear_fee_rate_schedule().unwrap();
+        drop(accessor);
+
+        let (oracle, _) = Oracle::split_observations(&account_info_mock.data).unwrap();
+        assert_eq!({ oracle.fee_rate_schedule }, FeeRateSchedule::default());
+    }
+
+    #[test]
+    fn clear_fee_rate_schedule_without_schedule_not_writable() {
+        let whirlpool_address = Pubkey::new_unique();
+        let mut account_info_mock =
+            AccountInfoMock::new_whirlpool(whirlpool_address, 64, 5650, None);
+        let account_info = account_info_mock.to_account_info(false);
+        let whirlpool = Account::<Whirlpool>::try_from(&account_info).unwrap();
+
+        let mut account_info_mock = AccountInfoMock::new_oracle(
+            Pubkey::new_unique(),
+            whirlpool_address,
+            100,
+            AdaptiveFeeConstants::default(),
+            None,
+        );
+        let account_info = account_info_mock.to_account_info(false);
+        let accessor = OracleAccessor::new(&whirlpool, account_info).unwrap();
+
+        // nothing to clear, so the Oracle account is not written
+        accessor.clear_fee_rate_schedule().unwrap();
+    }
+
+    #[test]
+    fn clear_fee_rate_schedule_uninitialized() {
+        let whirlpool_address = Pubkey::new_unique();
+        let mut account_info_mock =
+            AccountInfoMock::new_whirlpool(whirlpool_address, 64, 5650, None);
+        let account_info = account_info_mock.to_account_info(false);
+        let whirlpool = Account::<Whirlpool>::try_from(&account_info).unwrap();
+
+        let mut account_info_mock =
+            AccountInfoMock::new(Pubkey::new_unique(), vec![], System::id());
+        let account_info = account_info_mock.to_account_info(true);
+        let accessor = OracleAccessor::new(&whirlpool, account_info).unwrap();
+
+        accessor.clear_fee_rate_schedule().unwrap();
+    }
+
+    #[test]
+    fn write_observation_initialized_and_writable() {
+        let whirlpool_address = Pubkey::new_unique();
+        let mut account_info_mock =
+            AccountInfoMock::new_whirlpool(whirlpool_address, 64, 5650, None);
+        let account_info = account_info_mock.to_account_info(false);
+        let whirlpool = Account::<Whirlpool>::try_from(&account_info).unwrap();
+
+        let mut account_info_mock =
+            oracle_account_with_observations(Pubkey::new_unique(), whirlpool_address, 2);
+        let account_info = account_info_mock.to_account_info(true);
+        let accessor = OracleAccessor::new(&whirlpool, account_info).unwrap();
+
+        accessor.write_observation(200, 5650, 1 << 64).unwrap();
+        drop(accessor);
+
+        let (oracle, observations) = Oracle::split_observations(&account_info_mock.data).unwrap();
+        assert_eq!({ oracle.observation_cardinality }, 2);
+        assert_eq!({ oracle.observation_index }, 1);
+        assert_eq!({ observations[1].timestamp }, 200);
+    }
+
+    #[test]
+    fn write_observation_initialized_but_not_writable() {
+        let is_writable = false;
+
+        let whirlpool_address = Pubkey::new_unique();
+        let mut account_info_mock =
+            AccountInfoMock::new_whirlpool(whirlpool_address, 64, 5650, None);
+        let account_info = account_info_mock.to_account_info(false);
+        let whirlpool = Account::<Whirlpool>::try_from(&account_info).unwrap();
+
+        let mut account_info_mock =
+            oracle_account_with_observations(Pubkey::new_unique(), whirlpool_address, 2);
+        let data_before = account_info_mock.data.clone();
+        let account_info = account_info_mock.to_account_info(is_writable);
+        let accessor = OracleAccessor::new(&whirlpool, account_info).unwrap();
+
+        // a swap passing the Oracle account as read-only must not fail, the observation is skipped
+        accessor.write_observation(200, 5650, 1 << 64).unwrap();
+        drop(accessor);
+
+        assert_eq!(account_info_mock.data, data_before);
+    }
+
+    fn paused_oracle_account(
+        account_address: Pubkey,
+        whirlpool_address: Pubkey,
+    ) -> AccountInfoMock {
+        let mut account_info_mock = AccountInfoMock::new_oracle(
+            account_address,
+            whirlpool_address,
+            100,
+            AdaptiveFeeConstants::default(),
+            None,
+        );
+        let (oracle, _) = Oracle::split_observations_mut(&mut account_info_mock.data).unwrap();
+        oracle.update_pause_flag(PAUSE_FLAG_WHIRLPOOL, true);
+        account_info_mock
+    }
+
+    #[test]
+    fn is_paused() {
+        let whirlpool_address = Pubkey::new_unique();
+        let mut account_info_mock =
+            AccountInfoMock::new_whirlpool(whirlpool_address, 64, 5650, None);
+        let account_info = account_info_mock.to_account_info(false);
+        let whirlpool = Account::<Whirlpool>::try_from(&account_info).unwrap();
+
+        // uninitialized oracle
+        let mut account_info_mock =
+            AccountInfoMock::new(Pubkey::new_unique(), vec![], System::id());
+        let account_info = account_info_mock.to_account_info(false);
+        let accessor = OracleAccessor::new(&whirlpool, account_info).unwrap();
+        assert!(!accessor.is_paused().unwrap());
+
+        // initialized oracle without pause flags
+        let mut account_info_mock = AccountInfoMock::new_oracle(
+            Pubkey::new_unique(),
+            whirlpool_address,
+            100,
+            AdaptiveFeeConstants::default(),
+            None,
+        );
+        let account_info = account_info_mock.to_account_info(false);
+        let accessor = OracleAccessor::new(&whirlpool, account_info).unwrap();
+        assert!(!accessor.is_paused().unwrap());
+
+        // paused oracle
+        let mut account_info_mock = paused_oracle_account(Pubkey::new_unique(), whirlpool_address);
+        let account_info = account_info_mock.to_account_info(false);
+        let accessor = OracleAccessor::new(&whirlpool, account_info).unwrap();
+        assert!(accessor.is_paused().unwrap());
+    }
 }
 
 #[cfg(test)]
 mod oracle_tests {
     use super::*;
 
+    #[test]
+    fn test_update_pause_flag() {
+        let mut oracle = Oracle::default();
+        assert!(!oracle.is_paused());
+
+        oracle.update_pause_flag(PAUSE_FLAG_WHIRLPOOL, true);
+        assert!(oracle.is_paused());
+        oracle.update_pause_flag(PAUSE_FLAG_CONFIG, true);
+        assert!(oracle.is_paused());
+        let pause_flags = oracle.pause_flags;
+        assert_eq!(pause_flags, PAUSE_FLAG_WHIRLPOOL | PAUSE_FLAG_CONFIG);
+
+        // both flags must be cleared to unpause
+        oracle.update_pause_flag(PAUSE_FLAG_WHIRLPOOL, false);
+        assert!(oracle.is_paused());
+        oracle.update_pause_flag(PAUSE_FLAG_CONFIG, false);
+        assert!(!oracle.is_paused());
+
+        // clearing a flag that is not set is a no-op
+        oracle.update_pause_flag(PAUSE_FLAG_CONFIG, false);
+        assert!(!oracle.is_paused());
+    }
+
+    #[test]
+    fn test_fee_rate_schedule_new() {
+        let schedule = FeeRateSchedule::new(3000, 1000, 1_000, 2_000, 1_000).unwrap();
+        assert!(schedule.is_scheduled());
+
+        // step change
+        assert!(FeeRateSchedule::new(3000, 1000, 2_000, 2_000, 1_000).is_ok());
+        // target above the maximum
+        assert_eq!(
+            FeeRateSchedule::new(3000, MAX_FEE_RATE + 1, 1_000, 2_000, 1_000).unwrap_err(),
+            ErrorCode::InvalidFeeRateSchedule.into()
+        );
+        // ramp starts in the past
+        assert_eq!(
+            FeeRateSchedule::new(3000, 1000, 999, 2_000, 1_000).unwrap_err(),
+            ErrorCode::InvalidFeeRateSchedule.into()
+        );
+        // ramp ends before it starts
+        assert_eq!(
+            FeeRateSchedule::new(3000, 1000, 2_000, 1_999, 1_000).unwrap_err(),
+            ErrorCode::InvalidFeeRateSchedule.into()
+        );
+        // effective timestamp 0 is reserved for no schedule
+        assert_eq!(
+            FeeRateSchedule::new(3000, 1000, 0, 0, 0).unwrap_err(),
+            ErrorCode::InvalidFeeRateSchedule.into()
+        );
+    }
+
+    #[test]
+    fn test_fee_rate_schedule_fee_rate_at() {
+        assert_eq!(FeeRateSchedule::default().fee_rate_at(1_000), None);
+
+        // step change
+        let schedule = FeeRateSchedule::new(3000, 1000, 2_000, 2_000, 1_000).unwrap();
+        assert_eq!(schedule.fee_rate_at(1_999), None);
+        assert_eq!(schedule.fee_rate_at(2_000), Some(1000));
+        assert_eq!(schedule.fee_rate_at(u64::MAX), Some(1000));
+        assert!(!schedule.is_completed(1_999));
+        assert!(schedule.is_completed(2_000));
+
+        // decreasing ramp
+        let schedule = FeeRateSchedule::new(3000, 1000, 2_000, 3_000, 1_000).unwrap();
+        assert_eq!(schedule.fee_rate_at(1_999), None);
+        assert_eq!(schedule.fee_rate_at(2_000), Some(3000));
+        assert_eq!(schedule.fee_rate_at(2_250), Some(2500));
+        assert_eq!(schedule.fee_rate_at(2_999), Some(1002));
+        assert_eq!(schedule.fee_rate_at(3_000), Some(1000));
+
+        // increasing ramp
+        let schedule = FeeRateSchedule::new(1000, 3000, 2_000, 3_000, 1_000).unwrap();
+        assert_eq!(schedule.fee_rate_at(2_250), Some(1500));
+        assert_eq!(schedule.fee_rate_at(2_999), Some(2998));
+        assert_eq!(schedule.fee_rate_at(3_000), Some(3000));
+    }
+
+    #[test]
+    fn test_fee_rate_schedule_fee_rate_for() {
+        let schedule = FeeRateSchedule::new(3000, 1000, 2_000, 3_000, 1_000).unwrap();
+        assert_eq!(schedule.fee_rate_for(3000, 1_999), None);
+
+        // fee rates reached by the ramp so far
+        assert_eq!(schedule.fee_rate_for(3000, 2_250), Some(2500));
+        assert_eq!(schedule.fee_rate_for(2700, 2_250), Some(2500));
+        assert_eq!(schedule.fee_rate_for(2500, 2_250), Some(2500));
+        assert_eq!(schedule.fee_rate_for(2000, 3_000), Some(1000));
+        assert_eq!(schedule.fee_rate_for(1000, 3_000), Some(1000));
+
+        // fee rates set directly
+        assert_eq!(schedule.fee_rate_for(3001, 2_250), None);
+        assert_eq!(schedule.fee_rate_for(2499, 2_250), None);
+        assert_eq!(schedule.fee_rate_for(999, 3_000), None);
+        assert_eq!(schedule.fee_rate_for(5000, u64::MAX), None);
+    }
+
+    #[test]
+    fn test_initialize_without_adaptive_fee() {
+        let mut oracle = Oracle::default();
+        let whirlpool = Pubkey::new_unique();
+
+        oracle.initialize_without_adaptive_fee(whirlpool);
+        assert_eq!(oracle.whirlpool, whirlpool);
+        assert!(!oracle.is_adaptive_fee_enabled());
+        assert!(!oracle.is_paused());
+
+        let constants = AdaptiveFeeConstants {
+            filter_period: 30,
+            decay_period: 600,
+            reduction_factor: 500,
+            adaptive_fee_control_factor: 4_000,
+            max_volatility_accumulator: 350_000,
+            tick_group_size: 64,
+            major_swap_threshold_ticks: 64,
+            ..Default::default()
+        };
+        oracle
+            .initialize_adaptive_fee_constants(constants, 64)
+            .unwrap();
+        assert!(oracle.is_adaptive_fee_enabled());
+    }
+
     #[test]
     fn test_update_adaptive_fee_constants() {
         let mut oracle = Oracle::default();
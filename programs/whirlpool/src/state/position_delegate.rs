@@ -0,0 +1,241 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+#[account]
+pub struct PositionDelegate {
+    pub position: Pubkey,       // 32
+    pub position_owner: Pubkey, // 32
+    pub delegate: Pubkey,       // 32
+    pub permissions: u16,       // 2
+                                // 64 RESERVE
+}
+
+impl PositionDelegate {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 2 + 64;
+
+    pub const PERMISSION_COLLECT_FEES: u16 = 1 << 0;
+    pub const PERMISSION_COLLECT_REWARD: u16 = 1 << 1;
+    pub const PERMISSION_DECREASE_LIQUIDITY: u16 = 1 << 2;
+    pub const PERMISSION_INCREASE_LIQUIDITY: u16 = 1 << 3;
+    pub const PERMISSION_RESET_POSITION_RANGE: u16 = 1 << 4;
+    pub const PERMISSION_COMPOUND_FEES: u16 = 1 << 5;
+
+    pub const ALL_PERMISSIONS: u16 = Self::PERMISSION_COLLECT_FEES
+        | Self::PERMISSION_COLLECT_REWARD
+        | Self::PERMISSION_DECREASE_LIQUIDITY
+        | Self::PERMISSION_INCREASE_LIQUIDITY
+        | Self::PERMISSION_RESET_POSITION_RANGE
+        | Self::PERMISSION_COMPOUND_FEES;
+
+    pub fn initialize(
+        &mut self,
+        position: Pubkey,
+        position_owner: Pubkey,
+        delegate: Pubkey,
+        permissions: u16,
+    ) -> Result<()> {
+        if permissions == 0 || permissions & !Self::ALL_PERMISSIONS != 0 {
+            return Err(ErrorCode::InvalidPositionDelegatePermissions.into());
+        }
+
+        self.position = position;
+        self.position_owner = position_owner;
+        self.delegate = delegate;
+        self.permissions = permissions;
+
+        Ok(())
+    }
+
+    // The delegation is only valid while the position token is held by the owner who granted it.
+    pub fn verify_permission(
+        &self,
+        position: Pubkey,
+        position_owner: Pubkey,
+        delegate: Pubkey,
+        permission: u16,
+    ) -> Result<()> {
+        if self.position != position
+            || self.position_owner != position_owner
+            || self.delegate != delegate
+            || self.permissions & permission != permission
+        {
+            return Err(ErrorCode::PositionDelegatePermissionDenied.into());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod position_delegate_tests {
+    use super::*;
+
+    fn test_position_delegate(permissions: u16) -> (PositionDelegate, Pubkey, Pubkey, Pubkey) {
+        let mut position_delegate = PositionDelegate {
+            position: Pubkey::default(),
+            position_owner: Pubkey::default(),
+            delegate: Pubkey::default(),
+            permissions: 0,
+        };
+
+        let position = Pubkey::new_unique();
+        let position_owner = Pubkey::new_unique();
+        let delegate = Pubkey::new_unique();
+        position_delegate
+            .initialize(position, position_owner, delegate, permissions)
+            .unwrap();
+
+        (position_delegate, position, position_owner, delegate)
+    }
+
+    #[test]
+    fn test_initialize() {
+        let (position_delegate, position, position_owner, delegate) = test_position_delegate(
+            PositionDelegate::PERMISSION_COLLECT_FEES
+                | PositionDelegate::PERMISSION_DECREASE_LIQUIDITY,
+        );
+
+        assert_eq!(position, position_delegate.position);
+        assert_eq!(position_owner, position_delegate.position_owner);
+        assert_eq!(delegate, position_delegate.delegate);
+        assert_eq!(0b101, position_delegate.permissions);
+    }
+
+    #[test]
+    fn test_initialize_invalid_permissions() {
+        let (mut position_delegate, position, position_owner, delegate) =
+            test_position_delegate(PositionDelegate::ALL_PERMISSIONS);
+
+        for permissions in [0, 1 << 6, PositionDelegate::ALL_PERMISSIONS | 1 << 15] {
+            let result =
+                position_delegate.initialize(position, position_owner, delegate, permissions);
+            assert_eq!(
+                result.unwrap_err(),
+                ErrorCode::InvalidPositionDelegatePermissions.into()
+            );
+        }
+    }
+
+    #[test]
+    fn test_verify_permission() {
+        let (position_delegate, position, position_owner, delegate) = test_position_delegate(
+            PositionDelegate::PERMISSION_COLLECT_FEES
+                | PositionDelegate::PERMISSION_RESET_POSITION_RANGE,
+        );
+
+        assert!(position_delegate
+            .verify_permission(
+                position,
+                position_owner,
+                delegate,
+                PositionDelegate::PERMISSION_COLLECT_FEES
+            )
+            .is_ok());
+        assert!(position_delegate
+            .verify_permission(
+                position,
+                position_owner,
+                delegate,
+                PositionDelegate::PERMISSION_RESET_POSITION_RANGE
+            )
+            .is_ok());
+
+        // permission not granted
+        assert!(position_delegate
+            .verify_permission(
+                position,
+                position_owner,
+                delegate,
+                PositionDelegate::PERMISSION_DECREASE_LIQUIDITY
+            )
+            .is_err());
+
+        // other delegate
+        assert!(position_delegate
+            .verify_permission(
+                position,
+                position_owner,
+                Pubkey::new_unique(),
+                PositionDelegate::PERMISSION_COLLECT_FEES
+            )
+            .is_err());
+
+        // position token was transferred to another owner
+        assert!(position_delegate
+            .verify_permission(
+                position,
+                Pubkey::new_unique(),
+                delegate,
+                PositionDelegate::PERMISSION_COLLECT_FEES
+            )
+            .is_err());
+
+        // other position
+        assert!(position_delegate
+            .verify_permission(
+                Pubkey::new_unique(),
+                position_owner,
+                delegate,
+                PositionDelegate::PERMISSION_COLLECT_FEES
+            )
+            .is_err());
+    }
+}
+
+#[cfg(test)]
+mod data_layout_tests {
+    use anchor_lang::Discriminator;
+
+    use super::*;
+
+    #[test]
+    fn test_position_delegate_data_layout() {
+        let position_delegate_position = Pubkey::new_unique();
+        let position_delegate_position_owner = Pubkey::new_unique();
+        let position_delegate_delegate = Pubkey::new_unique();
+        let position_delegate_permissions = 0x1122u16;
+        let position_delegate_reserved = [0u8; 64];
+
+        let mut position_delegate_data = [0u8; PositionDelegate::LEN];
+        let mut offset = 0;
+        position_delegate_data[offset..offset + 8]
+            .copy_from_slice(&PositionDelegate::discriminator());
+        offset += 8;
+        position_delegate_data[offset..offset + 32]
+            .copy_from_slice(&position_delegate_position.to_bytes());
+        offset += 32;
+        position_delegate_data[offset..offset + 32]
+            .copy_from_slice(&position_delegate_position_owner.to_bytes());
+        offset += 32;
+        position_delegate_data[offset..offset + 32]
+            .copy_from_slice(&position_delegate_delegate.to_bytes());
+        offset += 32;
+        position_delegate_data[offset..offset + 2]
+            .copy_from_slice(&position_delegate_permissions.to_le_bytes());
+        offset += 2;
+        position_delegate_data[offset..offset + position_delegate_reserved.len()]
+            .copy_from_slice(&position_delegate_reserved);
+        offset += position_delegate_reserved.len();
+        assert_eq!(offset, PositionDelegate::LEN);
+
+        // deserialize
+        let deserialized =
+            PositionDelegate::try_deserialize(&mut position_delegate_data.as_ref()).unwrap();
+
+        assert_eq!(position_delegate_position, deserialized.position);
+        assert_eq!(
+            position_delegate_position_owner,
+            deserialized.position_owner
+        );
+        assert_eq!(position_delegate_delegate, deserialized.delegate);
+        assert_eq!(position_delegate_permissions, deserialized.permissions);
+
+        // serialize
+        let mut serialized = Vec::new();
+        deserialized.try_serialize(&mut serialized).unwrap();
+        serialized.extend_from_slice(&position_delegate_reserved);
+
+        assert_eq!(serialized.as_slice(), position_delegate_data.as_ref());
+    }
+}
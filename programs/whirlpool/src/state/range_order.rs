@@ -0,0 +1,223 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+use super::Position;
+
+#[account]
+pub struct RangeOrder {
+    pub whirlpool: Pubkey,     // 32
+    pub position: Pubkey,      // 32
+    pub a_to_b: bool,          // 1
+    pub tick_lower_index: i32, // 4
+    pub tick_upper_index: i32, // 4
+    pub liquidity: u128,       // 16
+    pub opened_timestamp: u64, // 8
+                               // 64 RESERVE
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RangeOrderFillState {
+    // The price has not reached the order range yet.
+    Unfilled,
+    // The price is inside the order range, so the position holds both tokens.
+    PartiallyFilled,
+    // The price has crossed the far end of the order range, so the position holds only the output token.
+    Filled,
+}
+
+impl RangeOrder {
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 4 + 4 + 16 + 8 + 64;
+
+    // A range order is a position exactly one tick spacing wide that sits entirely on one side of
+    // the current price. The side determines the direction: a range above the price only holds
+    // token A and is filled into token B as the price rises through it, and vice versa.
+    pub fn initialize(
+        &mut self,
+        position_key: Pubkey,
+        position: &Position,
+        tick_spacing: u16,
+        tick_current_index: i32,
+        opened_timestamp: u64,
+    ) -> Result<()> {
+        if position.tick_upper_index - position.tick_lower_index != tick_spacing as i32 {
+            return Err(ErrorCode::InvalidRangeOrderWidth.into());
+        }
+
+        if position.liquidity == 0 {
+            return Err(ErrorCode::LiquidityZero.into());
+        }
+
+        let a_to_b = if tick_current_index < position.tick_lower_index {
+            true
+        } else if tick_current_index >= position.tick_upper_index {
+            false
+        } else {
+            return Err(ErrorCode::InvalidRangeOrderRange.into());
+        };
+
+        self.whirlpool = position.whirlpool;
+        self.position = position_key;
+        self.a_to_b = a_to_b;
+        self.tick_lower_index = position.tick_lower_index;
+        self.tick_upper_index = position.tick_upper_index;
+        self.liquidity = position.liquidity;
+        self.opened_timestamp = opened_timestamp;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod range_order_initialize_tests {
+    use super::*;
+
+    fn test_position(tick_lower_index: i32, tick_upper_index: i32, liquidity: u128) -> Position {
+        Position {
+            whirlpool: Pubkey::new_unique(),
+            tick_lower_index,
+            tick_upper_index,
+            liquidity,
+            ..Default::default()
+        }
+    }
+
+    fn test_range_order() -> RangeOrder {
+        RangeOrder {
+            whirlpool: Pubkey::default(),
+            position: Pubkey::default(),
+            a_to_b: false,
+            tick_lower_index: 0,
+            tick_upper_index: 0,
+            liquidity: 0,
+            opened_timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn test_initialize_a_to_b() {
+        let position_key = Pubkey::new_unique();
+        let position = test_position(64, 128, 1_000);
+        let mut range_order = test_range_order();
+
+        let result = range_order.initialize(position_key, &position, 64, 0, 1711385715);
+        assert!(result.is_ok());
+
+        assert_eq!(position.whirlpool, range_order.whirlpool);
+        assert_eq!(position_key, range_order.position);
+        assert!(range_order.a_to_b);
+        assert_eq!(64, range_order.tick_lower_index);
+        assert_eq!(128, range_order.tick_upper_index);
+        assert_eq!(1_000, range_order.liquidity);
+        assert_eq!(1711385715, range_order.opened_timestamp);
+    }
+
+    #[test]
+    fn test_initialize_b_to_a() {
+        let position = test_position(-128, -64, 1_000);
+        let mut range_order = test_range_order();
+
+        let result = range_order.initialize(Pubkey::new_unique(), &position, 64, -64, 0);
+        assert!(result.is_ok());
+        assert!(!range_order.a_to_b);
+    }
+
+    #[test]
+    fn test_initialize_invalid_width() {
+        let position = test_position(64, 256, 1_000);
+        let mut range_order = test_range_order();
+
+        let result = range_order.initialize(Pubkey::new_unique(), &position, 64, 0, 0);
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::InvalidRangeOrderWidth.into()
+        );
+    }
+
+    #[test]
+    fn test_initialize_empty_position() {
+        let position = test_position(64, 128, 0);
+        let mut range_order = test_range_order();
+
+        let result = range_order.initialize(Pubkey::new_unique(), &position, 64, 0, 0);
+        assert_eq!(result.unwrap_err(), ErrorCode::LiquidityZero.into());
+    }
+
+    #[test]
+    fn test_initialize_price_in_range() {
+        let position = test_position(64, 128, 1_000);
+        let mut range_order = test_range_order();
+
+        for tick_current_index in [64, 100, 127] {
+            let result =
+                range_order.initialize(Pubkey::new_unique(), &position, 64, tick_current_index, 0);
+            assert_eq!(
+                result.unwrap_err(),
+                ErrorCode::InvalidRangeOrderRange.into()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod data_layout_tests {
+    use anchor_lang::Discriminator;
+
+    use super::*;
+
+    #[test]
+    fn test_range_order_data_layout() {
+        let range_order_whirlpool = Pubkey::new_unique();
+        let range_order_position = Pubkey::new_unique();
+        let range_order_a_to_b = true;
+        let range_order_tick_lower_index = -128i32;
+        let range_order_tick_upper_index = -64i32;
+        let range_order_liquidity = 0x11223344556677889900aabbccddeeffu128;
+        let range_order_opened_timestamp = 1711385715u64;
+        let range_order_reserved = [0u8; 64];
+
+        let mut range_order_data = [0u8; RangeOrder::LEN];
+        let mut offset = 0;
+        range_order_data[offset..offset + 8].copy_from_slice(&RangeOrder::discriminator());
+        offset += 8;
+        range_order_data[offset..offset + 32].copy_from_slice(&range_order_whirlpool.to_bytes());
+        offset += 32;
+        range_order_data[offset..offset + 32].copy_from_slice(&range_order_position.to_bytes());
+        offset += 32;
+        range_order_data[offset] = range_order_a_to_b as u8;
+        offset += 1;
+        range_order_data[offset..offset + 4]
+            .copy_from_slice(&range_order_tick_lower_index.to_le_bytes());
+        offset += 4;
+        range_order_data[offset..offset + 4]
+            .copy_from_slice(&range_order_tick_upper_index.to_le_bytes());
+        offset += 4;
+        range_order_data[offset..offset + 16].copy_from_slice(&range_order_liquidity.to_le_bytes());
+        offset += 16;
+        range_order_data[offset..offset + 8]
+            .copy_from_slice(&range_order_opened_timestamp.to_le_bytes());
+        offset += 8;
+        range_order_data[offset..offset + range_order_reserved.len()]
+            .copy_from_slice(&range_order_reserved);
+        offset += range_order_reserved.len();
+        assert_eq!(offset, RangeOrder::LEN);
+
+        // deserialize
+        let deserialized = RangeOrder::try_deserialize(&mut range_order_data.as_ref()).unwrap();
+
+        assert_eq!(range_order_whirlpool, deserialized.whirlpool);
+        assert_eq!(range_order_position, deserialized.position);
+        assert_eq!(range_order_a_to_b, deserialized.a_to_b);
+        assert_eq!(range_order_tick_lower_index, deserialized.tick_lower_index);
+        assert_eq!(range_order_tick_upper_index, deserialized.tick_upper_index);
+        assert_eq!(range_order_liquidity, deserialized.liquidity);
+        assert_eq!(range_order_opened_timestamp, deserialized.opened_timestamp);
+
+        // serialize
+        let mut serialized = Vec::new();
+        deserialized.try_serialize(&mut serialized).unwrap();
+        serialized.extend_from_slice(&range_order_reserved);
+
+        assert_eq!(serialized.as_slice(), range_order_data.as_ref());
+    }
+}
@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::math::PROTOCOL_FEE_RATE_MUL_VALUE;
+
+use super::MAX_REFERRAL_FEE_RATE;
+
+#[account]
+pub struct Referral {
+    pub whirlpools_config: Pubkey, // 32
+    pub referrer: Pubkey,          // 32
+    pub referral_fee_rate: u16,    // 2
+                                   // 64 RESERVE
+}
+
+impl Referral {
+    pub const LEN: usize = 8 + 32 + 32 + 2 + 64;
+
+    pub fn initialize(
+        &mut self,
+        whirlpools_config: Pubkey,
+        referrer: Pubkey,
+        referral_fee_rate: u16,
+        max_referral_fee_rate: u16,
+    ) -> Result<()> {
+        self.whirlpools_config = whirlpools_config;
+        self.referrer = referrer;
+        self.update_referral_fee_rate(referral_fee_rate, max_referral_fee_rate)
+    }
+
+    pub fn update_referral_fee_rate(
+        &mut self,
+        referral_fee_rate: u16,
+        max_referral_fee_rate: u16,
+    ) -> Result<()> {
+        if referral_fee_rate > max_referral_fee_rate || referral_fee_rate > MAX_REFERRAL_FEE_RATE {
+            return Err(ErrorCode::InvalidReferralFeeRate.into());
+        }
+        self.referral_fee_rate = referral_fee_rate;
+        Ok(())
+    }
+
+    // The referral fee is a share of the protocol fee, so liquidity providers are not affected.
+    // The maximum is checked again because it may have been lowered after the referral was set.
+    pub fn calculate_referral_fee(&self, protocol_fee: u64, max_referral_fee_rate: u16) -> u64 {
+        let referral_fee_rate = self.referral_fee_rate.min(max_referral_fee_rate);
+        ((protocol_fee as u128) * (referral_fee_rate as u128) / PROTOCOL_FEE_RATE_MUL_VALUE) as u64
+    }
+}
+
+#[cfg(test)]
+mod referral_tests {
+    use super::*;
+
+    fn test_referral(referral_fee_rate: u16) -> Referral {
+        let mut referral = Referral {
+            whirlpools_config: Pubkey::default(),
+            referrer: Pubkey::default(),
+            referral_fee_rate: 0,
+        };
+        referral
+            .initialize(
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                referral_fee_rate,
+                MAX_REFERRAL_FEE_RATE,
+            )
+            .unwrap();
+        referral
+    }
+
+    #[test]
+    fn test_update_referral_fee_rate() {
+        let mut referral = test_referral(1000);
+        assert_eq!(referral.referral_fee_rate, 1000);
+
+        assert!(referral.update_referral_fee_rate(2000, 2000).is_ok());
+        assert_eq!(referral.referral_fee_rate, 2000);
+
+        // above the config maximum
+        assert_eq!(
+            referral.update_referral_fee_rate(2001, 2000).unwrap_err(),
+            ErrorCode::InvalidReferralFeeRate.into()
+        );
+        // above the absolute maximum
+        assert_eq!(
+            referral
+                .update_referral_fee_rate(MAX_REFERRAL_FEE_RATE + 1, u16::MAX)
+                .unwrap_err(),
+            ErrorCode::InvalidReferralFeeRate.into()
+        );
+        assert_eq!(referral.referral_fee_rate, 2000);
+    }
+
+    #[test]
+    fn test_calculate_referral_fee() {
+        let referral = test_referral(2500);
+
+        assert_eq!(
+            referral.calculate_referral_fee(1000, MAX_REFERRAL_FEE_RATE),
+            250
+        );
+        // rounded down
+        assert_eq!(referral.calculate_referral_fee(3, MAX_REFERRAL_FEE_RATE), 0);
+        // capped by a lowered maximum
+        assert_eq!(referral.calculate_referral_fee(1000, 1000), 100);
+        assert_eq!(referral.calculate_referral_fee(1000, 0), 0);
+        // never more than the protocol fee
+        let referral = test_referral(MAX_REFERRAL_FEE_RATE);
+        assert_eq!(
+            referral.calculate_referral_fee(u64::MAX, MAX_REFERRAL_FEE_RATE),
+            u64::MAX
+        );
+    }
+}
+
+#[cfg(test)]
+mod data_layout_tests {
+    use anchor_lang::Discriminator;
+
+    use super::*;
+
+    #[test]
+    fn test_referral_data_layout() {
+        let referral_whirlpools_config = Pubkey::new_unique();
+        let referral_referrer = Pubkey::new_unique();
+        let referral_referral_fee_rate = 0x1122u16;
+        let referral_reserved = [0u8; 64];
+
+        let mut referral_data = [0u8; Referral::LEN];
+        let mut offset = 0;
+        referral_data[offset..offset + 8].copy_from_slice(&Referral::discriminator());
+        offset += 8;
+        referral_data[offset..offset + 32].copy_from_slice(&referral_whirlpools_config.to_bytes());
+        offset += 32;
+        referral_data[offset..offset + 32].copy_from_slice(&referral_referrer.to_bytes());
+        offset += 32;
+        referral_data[offset..offset + 2]
+            .copy_from_slice(&referral_referral_fee_rate.to_le_bytes());
+        offset += 2;
+        referral_data[offset..offset + referral_reserved.len()].copy_from_slice(&referral_reserved);
+        offset += referral_reserved.len();
+        assert_eq!(offset, Referral::LEN);
+
+        // deserialize
+        let deserialized = Referral::try_deserialize(&mut referral_data.as_ref()).unwrap();
+
+        assert_eq!(referral_whirlpools_config, deserialized.whirlpools_config);
+        assert_eq!(referral_referrer, deserialized.referrer);
+        assert_eq!(referral_referral_fee_rate, deserialized.referral_fee_rate);
+
+        // serialize
+        let mut serialized = Vec::new();
+        deserialized.try_serialize(&mut serialized).unwrap();
+        serialized.extend_from_slice(&referral_reserved);
+
+        assert_eq!(serialized.as_slice(), referral_data.as_ref());
+    }
+}
@@ -1,14 +1,52 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::ErrorCode;
+
+// Transfer fees are represented as basis points of the transferred amount.
+pub const MAX_TOKEN_BADGE_TRANSFER_FEE_BPS: u16 = 10_000;
+
+// The conditions the token badge authority approved the mint under.
+// They are recorded for auditing and are not enforced by the program.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct TokenBadgeAttributes {
+    pub requires_memo: bool,       // 1
+    pub max_transfer_fee_bps: u16, // 2
+}
+
+impl TokenBadgeAttributes {
+    pub const LEN: usize = 1 + 2;
+
+    pub fn validate(&self) -> Result<()> {
+        if self.max_transfer_fee_bps > MAX_TOKEN_BADGE_TRANSFER_FEE_BPS {
+            return Err(ErrorCode::InvalidTokenBadgeAttributes.into());
+        }
+        Ok(())
+    }
+}
+
 #[account]
 #[derive(Default)]
 pub struct TokenBadge {
     pub whirlpools_config: Pubkey, // 32
     pub token_mint: Pubkey,        // 32
-                                   // 128 RESERVE
+
+    pub approved_by: Pubkey,     // 32
+    pub approved_at: i64,        // 8
+    pub hook_submission: Pubkey, // 32
+
+    // Incremented on every change of the attributes, starting at 1 on approval
+    pub attributes_version: u32, // 4
+
+    pub attributes: TokenBadgeAttributes, // 3
+
+    // Set once a TokenBadgeHistory records the badge, so that it cannot be deleted without a tombstone
+    pub has_history: bool, // 1
+                           // 48 RESERVE
 }
 
 impl TokenBadge {
+    // The approval fields were carved out of the reserved space. They stay zeroed for badges
+    // initialized without a history.
     pub const LEN: usize = 8 + 32 + 32 + 128;
 
     pub fn initialize(&mut self, whirlpools_config: Pubkey, token_mint: Pubkey) -> Result<()> {
@@ -16,6 +54,44 @@ impl TokenBadge {
         self.token_mint = token_mint;
         Ok(())
     }
+
+    pub fn approve(
+        &mut self,
+        approved_by: Pubkey,
+        approved_at: i64,
+        hook_submission: Option<Pubkey>,
+        attributes: TokenBadgeAttributes,
+    ) -> Result<()> {
+        attributes.validate()?;
+        self.approved_by = approved_by;
+        self.approved_at = approved_at;
+        self.hook_submission = hook_submission.unwrap_or_default();
+        self.attributes_version = 1;
+        self.attributes = attributes;
+        Ok(())
+    }
+
+    pub fn update_attributes(&mut self, attributes: TokenBadgeAttributes) -> Result<()> {
+        attributes.validate()?;
+        self.attributes_version = self
+            .attributes_version
+            .checked_add(1)
+            .ok_or(ErrorCode::NumberCastError)?;
+        self.attributes = attributes;
+        Ok(())
+    }
+
+    pub fn enable_history(&mut self) {
+        self.has_history = true;
+    }
+
+    pub fn hook_submission(&self) -> Option<Pubkey> {
+        if self.hook_submission == Pubkey::default() {
+            None
+        } else {
+            Some(self.hook_submission)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -30,6 +106,9 @@ mod token_badge_initialize_tests {
         };
         assert_eq!(token_badge.whirlpools_config, Pubkey::default());
         assert_eq!(token_badge.token_mint, Pubkey::default());
+        assert_eq!(token_badge.attributes_version, 0);
+        assert_eq!(token_badge.hook_submission(), None);
+        assert!(!token_badge.has_history);
     }
 
     #[test]
@@ -46,6 +125,92 @@ mod token_badge_initialize_tests {
 
         assert_eq!(whirlpools_config, token_badge.whirlpools_config);
         assert_eq!(token_mint, token_badge.token_mint);
+        assert_eq!(0, token_badge.attributes_version);
+    }
+
+    #[test]
+    fn test_approve() {
+        let mut token_badge = TokenBadge {
+            ..Default::default()
+        };
+        let approved_by = Pubkey::new_unique();
+        let hook_submission = Pubkey::new_unique();
+        let attributes = TokenBadgeAttributes {
+            requires_memo: true,
+            max_transfer_fee_bps: 100,
+        };
+
+        let result = token_badge.approve(
+            approved_by,
+            1_700_000_000,
+            Some(hook_submission),
+            attributes,
+        );
+        assert!(result.is_ok());
+
+        assert_eq!(approved_by, token_badge.approved_by);
+        assert_eq!(1_700_000_000, token_badge.approved_at);
+        assert_eq!(Some(hook_submission), token_badge.hook_submission());
+        assert_eq!(1, token_badge.attributes_version);
+        assert_eq!(attributes, token_badge.attributes);
+    }
+
+    #[test]
+    fn test_enable_history() {
+        let mut token_badge = TokenBadge {
+            ..Default::default()
+        };
+        token_badge.enable_history();
+        assert!(token_badge.has_history);
+        assert_eq!(0, token_badge.attributes_version);
+    }
+
+    #[test]
+    fn test_approve_invalid_attributes() {
+        let mut token_badge = TokenBadge {
+            ..Default::default()
+        };
+        let result = token_badge.approve(
+            Pubkey::new_unique(),
+            0,
+            None,
+            TokenBadgeAttributes {
+                requires_memo: false,
+                max_transfer_fee_bps: MAX_TOKEN_BADGE_TRANSFER_FEE_BPS + 1,
+            },
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_update_attributes() {
+        let mut token_badge = TokenBadge {
+            ..Default::default()
+        };
+        token_badge
+            .approve(
+                Pubkey::new_unique(),
+                0,
+                None,
+                TokenBadgeAttributes::default(),
+            )
+            .unwrap();
+
+        let attributes = TokenBadgeAttributes {
+            requires_memo: true,
+            max_transfer_fee_bps: MAX_TOKEN_BADGE_TRANSFER_FEE_BPS,
+        };
+        assert!(token_badge.update_attributes(attributes).is_ok());
+        assert_eq!(2, token_badge.attributes_version);
+        assert_eq!(attributes, token_badge.attributes);
+
+        let invalid_attributes = TokenBadgeAttributes {
+            requires_memo: false,
+            max_transfer_fee_bps: MAX_TOKEN_BADGE_TRANSFER_FEE_BPS + 1,
+        };
+        assert!(token_badge.update_attributes(invalid_attributes).is_err());
+        assert_eq!(2, token_badge.attributes_version);
+        assert_eq!(attributes, token_badge.attributes);
     }
 }
 
@@ -59,7 +224,14 @@ mod data_layout_tests {
     fn test_token_badge_data_layout() {
         let token_badge_whirlpools_config = Pubkey::new_unique();
         let token_badge_token_mint = Pubkey::new_unique();
-        let token_badge_reserved = [0u8; 128];
+        let token_badge_approved_by = Pubkey::new_unique();
+        let token_badge_approved_at = 1_700_000_000i64;
+        let token_badge_hook_submission = Pubkey::new_unique();
+        let token_badge_attributes_version = 3u32;
+        let token_badge_requires_memo = true;
+        let token_badge_max_transfer_fee_bps = 250u16;
+        let token_badge_has_history = true;
+        let token_badge_reserved = [0u8; 48];
 
         // manually build the expected data layout
         let mut token_badge_data = [0u8; TokenBadge::LEN];
@@ -71,6 +243,24 @@ mod data_layout_tests {
         offset += 32;
         token_badge_data[offset..offset + 32].copy_from_slice(&token_badge_token_mint.to_bytes());
         offset += 32;
+        token_badge_data[offset..offset + 32].copy_from_slice(&token_badge_approved_by.to_bytes());
+        offset += 32;
+        token_badge_data[offset..offset + 8]
+            .copy_from_slice(&token_badge_approved_at.to_le_bytes());
+        offset += 8;
+        token_badge_data[offset..offset + 32]
+            .copy_from_slice(&token_badge_hook_submission.to_bytes());
+        offset += 32;
+        token_badge_data[offset..offset + 4]
+            .copy_from_slice(&token_badge_attributes_version.to_le_bytes());
+        offset += 4;
+        token_badge_data[offset] = token_badge_requires_memo as u8;
+        offset += 1;
+        token_badge_data[offset..offset + 2]
+            .copy_from_slice(&token_badge_max_transfer_fee_bps.to_le_bytes());
+        offset += 2;
+        token_badge_data[offset] = token_badge_has_history as u8;
+        offset += 1;
         token_badge_data[offset..offset + token_badge_reserved.len()]
             .copy_from_slice(&token_badge_reserved);
         offset += token_badge_reserved.len();
@@ -84,6 +274,22 @@ mod data_layout_tests {
             deserialized.whirlpools_config
         );
         assert_eq!(token_badge_token_mint, deserialized.token_mint);
+        assert_eq!(token_badge_approved_by, deserialized.approved_by);
+        assert_eq!(token_badge_approved_at, deserialized.approved_at);
+        assert_eq!(token_badge_hook_submission, deserialized.hook_submission);
+        assert_eq!(
+            token_badge_attributes_version,
+            deserialized.attributes_version
+        );
+        assert_eq!(
+            token_badge_requires_memo,
+            deserialized.attributes.requires_memo
+        );
+        assert_eq!(
+            token_badge_max_transfer_fee_bps,
+            deserialized.attributes.max_transfer_fee_bps
+        );
+        assert_eq!(token_badge_has_history, deserialized.has_history);
 
         // serialize
         let mut serialized = Vec::new();
@@ -92,4 +298,28 @@ mod data_layout_tests {
 
         assert_eq!(serialized.as_slice(), token_badge_data.as_ref());
     }
+
+    #[test]
+    fn test_token_badge_legacy_data_layout() {
+        let token_badge_whirlpools_config = Pubkey::new_unique();
+        let token_badge_token_mint = Pubkey::new_unique();
+
+        // badges initialized without a history have zeros after the mint
+        let mut token_badge_data = [0u8; TokenBadge::LEN];
+        token_badge_data[0..8].copy_from_slice(&TokenBadge::discriminator());
+        token_badge_data[8..40].copy_from_slice(&token_badge_whirlpools_config.to_bytes());
+        token_badge_data[40..72].copy_from_slice(&token_badge_token_mint.to_bytes());
+
+        let deserialized = TokenBadge::try_deserialize(&mut token_badge_data.as_ref()).unwrap();
+
+        assert_eq!(
+            token_badge_whirlpools_config,
+            deserialized.whirlpools_config
+        );
+        assert_eq!(token_badge_token_mint, deserialized.token_mint);
+        assert_eq!(Pubkey::default(), deserialized.approved_by);
+        assert_eq!(0, deserialized.attributes_version);
+        assert_eq!(None, deserialized.hook_submission());
+        assert!(!deserialized.has_history);
+    }
 }
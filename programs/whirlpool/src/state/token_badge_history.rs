@@ -0,0 +1,392 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+use super::TokenBadgeAttributes;
+
+// The history keeps the most recent entries of a token badge in a ring buffer.
+// Every change is emitted as an event as well, so older entries can be recovered from the logs.
+pub const TOKEN_BADGE_HISTORY_SIZE: usize = 16;
+
+// Reasons for deleting a token badge are stored as UTF-8 bytes, padded with zeros.
+pub const MAX_TOKEN_BADGE_REASON_LEN: usize = 64;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum TokenBadgeStatus {
+    #[default]
+    Active,
+    Deleted,
+    // The history was created before the badge was initialized.
+    Pending,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum TokenBadgeAction {
+    #[default]
+    Initialized,
+    AttributesUpdated,
+    Deleted,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TokenBadgeHistoryEntry {
+    pub action: TokenBadgeAction,                 // 1
+    pub authority: Pubkey,                        // 32
+    pub timestamp: i64,                           // 8
+    pub hook_submission: Pubkey,                  // 32
+    pub attributes_version: u32,                  // 4
+    pub attributes: TokenBadgeAttributes,         // 3
+    pub reason: [u8; MAX_TOKEN_BADGE_REASON_LEN], // 64
+}
+
+impl Default for TokenBadgeHistoryEntry {
+    fn default() -> Self {
+        Self {
+            action: TokenBadgeAction::default(),
+            authority: Pubkey::default(),
+            timestamp: 0,
+            hook_submission: Pubkey::default(),
+            attributes_version: 0,
+            attributes: TokenBadgeAttributes::default(),
+            reason: [0u8; MAX_TOKEN_BADGE_REASON_LEN],
+        }
+    }
+}
+
+impl TokenBadgeHistoryEntry {
+    pub const LEN: usize =
+        1 + 32 + 8 + 32 + 4 + TokenBadgeAttributes::LEN + MAX_TOKEN_BADGE_REASON_LEN;
+
+    pub fn reason(&self) -> &str {
+        let len = self
+            .reason
+            .iter()
+            .position(|&x| x == 0)
+            .unwrap_or(MAX_TOKEN_BADGE_REASON_LEN);
+        std::str::from_utf8(&self.reason[..len]).unwrap_or_default()
+    }
+}
+
+#[account]
+pub struct TokenBadgeHistory {
+    pub whirlpools_config: Pubkey, // 32
+    pub token_mint: Pubkey,        // 32
+    pub status: TokenBadgeStatus,  // 1
+    pub entry_count: u32,          // 4
+    // 144 * TOKEN_BADGE_HISTORY_SIZE
+    pub entries: [TokenBadgeHistoryEntry; TOKEN_BADGE_HISTORY_SIZE],
+    // 64 RESERVE
+}
+
+impl Default for TokenBadgeHistory {
+    fn default() -> Self {
+        Self {
+            whirlpools_config: Pubkey::default(),
+            token_mint: Pubkey::default(),
+            status: TokenBadgeStatus::default(),
+            entry_count: 0,
+            entries: [TokenBadgeHistoryEntry::default(); TOKEN_BADGE_HISTORY_SIZE],
+        }
+    }
+}
+
+impl TokenBadgeHistory {
+    pub const LEN: usize =
+        8 + 32 + 32 + 1 + 4 + TokenBadgeHistoryEntry::LEN * TOKEN_BADGE_HISTORY_SIZE + 64;
+
+    // The history is never closed, so it outlives the deletion of its badge. It may be created
+    // for a badge that was initialized before histories were recorded.
+    pub fn initialize(
+        &mut self,
+        whirlpools_config: Pubkey,
+        token_mint: Pubkey,
+        token_badge_exists: bool,
+    ) {
+        self.whirlpools_config = whirlpools_config;
+        self.token_mint = token_mint;
+        self.status = if token_badge_exists {
+            TokenBadgeStatus::Active
+        } else {
+            TokenBadgeStatus::Pending
+        };
+    }
+
+    pub fn record_initialized(
+        &mut self,
+        authority: Pubkey,
+        timestamp: i64,
+        hook_submission: Option<Pubkey>,
+        attributes_version: u32,
+        attributes: TokenBadgeAttributes,
+    ) {
+        self.status = TokenBadgeStatus::Active;
+        self.push(TokenBadgeHistoryEntry {
+            action: TokenBadgeAction::Initialized,
+            authority,
+            timestamp,
+            hook_submission: hook_submission.unwrap_or_default(),
+            attributes_version,
+            attributes,
+            ..Default::default()
+        });
+    }
+
+    pub fn record_attributes_updated(
+        &mut self,
+        authority: Pubkey,
+        timestamp: i64,
+        attributes_version: u32,
+        attributes: TokenBadgeAttributes,
+    ) {
+        self.push(TokenBadgeHistoryEntry {
+            action: TokenBadgeAction::AttributesUpdated,
+            authority,
+            timestamp,
+            attributes_version,
+            attributes,
+            ..Default::default()
+        });
+    }
+
+    // The deletion entry keeps the last attributes of the badge, so the tombstone shows what was revoked.
+    pub fn record_deleted(
+        &mut self,
+        authority: Pubkey,
+        timestamp: i64,
+        attributes_version: u32,
+        attributes: TokenBadgeAttributes,
+        reason: &str,
+    ) -> Result<()> {
+        if reason.is_empty() || reason.len() > MAX_TOKEN_BADGE_REASON_LEN || reason.contains('\0') {
+            return Err(ErrorCode::InvalidTokenBadgeReason.into());
+        }
+        let mut reason_bytes = [0u8; MAX_TOKEN_BADGE_REASON_LEN];
+        reason_bytes[..reason.len()].copy_from_slice(reason.as_bytes());
+
+        self.status = TokenBadgeStatus::Deleted;
+        self.push(TokenBadgeHistoryEntry {
+            action: TokenBadgeAction::Deleted,
+            authority,
+            timestamp,
+            attributes_version,
+            attributes,
+            reason: reason_bytes,
+            ..Default::default()
+        });
+        Ok(())
+    }
+
+    // Returns the recorded entries that are still kept, from the oldest to the newest.
+    pub fn entries(&self) -> impl Iterator<Item = &TokenBadgeHistoryEntry> {
+        let count = self.entry_count as usize;
+        let kept = count.min(TOKEN_BADGE_HISTORY_SIZE);
+        (count - kept..count).map(|i| &self.entries[i % TOKEN_BADGE_HISTORY_SIZE])
+    }
+
+    fn push(&mut self, entry: TokenBadgeHistoryEntry) {
+        self.entries[self.entry_count as usize % TOKEN_BADGE_HISTORY_SIZE] = entry;
+        self.entry_count = self.entry_count.wrapping_add(1);
+    }
+}
+
+#[cfg(test)]
+mod token_badge_history_tests {
+    use super::*;
+
+    fn initialized_history() -> TokenBadgeHistory {
+        let mut history = TokenBadgeHistory::default();
+        history.initialize(Pubkey::new_unique(), Pubkey::new_unique(), false);
+        history
+    }
+
+    #[test]
+    fn test_initialize() {
+        let mut history = TokenBadgeHistory::default();
+        let whirlpools_config = Pubkey::new_unique();
+        let token_mint = Pubkey::new_unique();
+        history.initialize(whirlpools_config, token_mint, false);
+        assert_eq!(history.whirlpools_config, whirlpools_config);
+        assert_eq!(history.token_mint, token_mint);
+        assert_eq!(history.status, TokenBadgeStatus::Pending);
+        assert_eq!(history.entry_count, 0);
+        assert_eq!(history.entries().count(), 0);
+
+        // a badge initialized before histories were recorded is active
+        let mut history = TokenBadgeHistory::default();
+        history.initialize(whirlpools_config, token_mint, true);
+        assert_eq!(history.status, TokenBadgeStatus::Active);
+    }
+
+    #[test]
+    fn test_lifecycle() {
+        let mut history = initialized_history();
+        let authority = Pubkey::new_unique();
+        let hook_submission = Pubkey::new_unique();
+        let attributes = TokenBadgeAttributes {
+            requires_memo: true,
+            max_transfer_fee_bps: 100,
+        };
+
+        history.record_initialized(
+            authority,
+            100,
+            Some(hook_submission),
+            1,
+            TokenBadgeAttributes::default(),
+        );
+        assert_eq!(history.status, TokenBadgeStatus::Active);
+
+        history.record_attributes_updated(authority, 200, 2, attributes);
+        assert_eq!(history.status, TokenBadgeStatus::Active);
+
+        history
+            .record_deleted(authority, 300, 2, attributes, "hook program was upgraded")
+            .unwrap();
+        assert_eq!(history.status, TokenBadgeStatus::Deleted);
+        assert_eq!(history.entry_count, 3);
+
+        let entries: Vec<&TokenBadgeHistoryEntry> = history.entries().collect();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].action, TokenBadgeAction::Initialized);
+        assert_eq!(entries[0].hook_submission, hook_submission);
+        assert_eq!(entries[0].timestamp, 100);
+        assert_eq!(entries[1].action, TokenBadgeAction::AttributesUpdated);
+        assert_eq!(entries[1].attributes_version, 2);
+        assert_eq!(entries[1].attributes, attributes);
+        assert_eq!(entries[2].action, TokenBadgeAction::Deleted);
+        assert_eq!(entries[2].attributes, attributes);
+        assert_eq!(entries[2].reason(), "hook program was upgraded");
+
+        // the badge can be created again after it was deleted
+        history.record_initialized(authority, 400, None, 1, attributes);
+        assert_eq!(history.status, TokenBadgeStatus::Active);
+        assert_eq!(history.entries().count(), 4);
+    }
+
+    #[test]
+    fn test_record_deleted_invalid_reason() {
+        let mut history = initialized_history();
+        let authority = Pubkey::new_unique();
+        let attributes = TokenBadgeAttributes::default();
+
+        assert!(history
+            .record_deleted(authority, 0, 1, attributes, "")
+            .is_err());
+        assert!(history
+            .record_deleted(authority, 0, 1, attributes, &"a".repeat(65))
+            .is_err());
+        assert!(history
+            .record_deleted(authority, 0, 1, attributes, "a\0b")
+            .is_err());
+        assert_eq!(history.status, TokenBadgeStatus::Pending);
+        assert_eq!(history.entry_count, 0);
+
+        let reason = "a".repeat(MAX_TOKEN_BADGE_REASON_LEN);
+        assert!(history
+            .record_deleted(authority, 0, 1, attributes, &reason)
+            .is_ok());
+        assert_eq!(history.entries().last().unwrap().reason(), reason);
+    }
+
+    #[test]
+    fn test_ring_buffer_keeps_newest_entries() {
+        let mut history = initialized_history();
+        let authority = Pubkey::new_unique();
+        let total = TOKEN_BADGE_HISTORY_SIZE as u32 + 5;
+        for version in 1..=total {
+            history.record_attributes_updated(
+                authority,
+                version as i64,
+                version,
+                TokenBadgeAttributes::default(),
+            );
+        }
+
+        assert_eq!(history.entry_count, total);
+        let versions: Vec<u32> = history.entries().map(|x| x.attributes_version).collect();
+        let expected: Vec<u32> = (6..=total).collect();
+        assert_eq!(versions, expected);
+    }
+}
+
+#[cfg(test)]
+mod data_layout_tests {
+    use anchor_lang::Discriminator;
+
+    use super::*;
+
+    #[test]
+    fn test_token_badge_history_data_layout() {
+        let whirlpools_config = Pubkey::new_unique();
+        let token_mint = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let hook_submission = Pubkey::new_unique();
+        let reserved = [0u8; 64];
+
+        let mut history = TokenBadgeHistory::default();
+        history.initialize(whirlpools_config, token_mint, false);
+        history.record_initialized(
+            authority,
+            1_700_000_000,
+            Some(hook_submission),
+            1,
+            TokenBadgeAttributes {
+                requires_memo: true,
+                max_transfer_fee_bps: 250,
+            },
+        );
+
+        let mut serialized = Vec::new();
+        history.try_serialize(&mut serialized).unwrap();
+        serialized.extend_from_slice(&reserved);
+        assert_eq!(serialized.len(), TokenBadgeHistory::LEN);
+
+        // the status is at a fixed offset so badges can be listed by status
+        let mut offset = 0;
+        assert_eq!(
+            serialized[offset..offset + 8],
+            TokenBadgeHistory::discriminator()
+        );
+        offset += 8;
+        assert_eq!(
+            serialized[offset..offset + 32],
+            whirlpools_config.to_bytes()
+        );
+        offset += 32;
+        assert_eq!(serialized[offset..offset + 32], token_mint.to_bytes());
+        offset += 32;
+        assert_eq!(serialized[offset], 0); // Active
+        offset += 1;
+        assert_eq!(serialized[offset..offset + 4], 1u32.to_le_bytes());
+        offset += 4;
+
+        // first entry
+        assert_eq!(serialized[offset], 0); // Initialized
+        offset += 1;
+        assert_eq!(serialized[offset..offset + 32], authority.to_bytes());
+        offset += 32;
+        assert_eq!(
+            serialized[offset..offset + 8],
+            1_700_000_000i64.to_le_bytes()
+        );
+        offset += 8;
+        assert_eq!(serialized[offset..offset + 32], hook_submission.to_bytes());
+        offset += 32;
+        assert_eq!(serialized[offset..offset + 4], 1u32.to_le_bytes());
+        offset += 4;
+        assert_eq!(serialized[offset], 1);
+        offset += 1;
+        assert_eq!(serialized[offset..offset + 2], 250u16.to_le_bytes());
+        offset += 2;
+        assert_eq!(
+            serialized[offset..offset + MAX_TOKEN_BADGE_REASON_LEN],
+            [0u8; MAX_TOKEN_BADGE_REASON_LEN]
+        );
+
+        let deserialized = TokenBadgeHistory::try_deserialize(&mut serialized.as_ref()).unwrap();
+        assert_eq!(deserialized.whirlpools_config, whirlpools_config);
+        assert_eq!(deserialized.token_mint, token_mint);
+        assert_eq!(deserialized.status, TokenBadgeStatus::Active);
+        assert_eq!(deserialized.entries[0], history.entries[0]);
+    }
+}
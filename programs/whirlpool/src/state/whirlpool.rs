@@ -257,6 +257,45 @@ impl Whirlpool {
         }
     }
 
+    pub fn deduct_referral_fee(
+        &mut self,
+        referral_fee: u64,
+        is_token_fee_in_a: bool,
+    ) -> Result<()> {
+        let protocol_fee_owed = if is_token_fee_in_a {
+            &mut self.protocol_fee_owed_a
+        } else {
+            &mut self.protocol_fee_owed_b
+        };
+        *protocol_fee_owed = protocol_fee_owed
+            .checked_sub(referral_fee)
+            .ok_or(ErrorCode::AmountCalcOverflow)?;
+        Ok(())
+    }
+
+    pub fn update_after_flash_loan(
+        &mut self,
+        fee_growth_global_a: u128,
+        fee_growth_global_b: u128,
+        protocol_fee_a: u64,
+        protocol_fee_b: u64,
+    ) -> Result<()> {
+        let protocol_fee_owed_a = self
+            .protocol_fee_owed_a
+            .checked_add(protocol_fee_a)
+            .ok_or(ErrorCode::AmountCalcOverflow)?;
+        let protocol_fee_owed_b = self
+            .protocol_fee_owed_b
+            .checked_add(protocol_fee_b)
+            .ok_or(ErrorCode::AmountCalcOverflow)?;
+
+        self.fee_growth_global_a = fee_growth_global_a;
+        self.fee_growth_global_b = fee_growth_global_b;
+        self.protocol_fee_owed_a = protocol_fee_owed_a;
+        self.protocol_fee_owed_b = protocol_fee_owed_b;
+        Ok(())
+    }
+
     pub fn update_fee_rate(&mut self, fee_rate: u16) -> Result<()> {
         if fee_rate > MAX_FEE_RATE {
             return Err(ErrorCode::FeeRateMaxExceeded.into());
@@ -353,6 +392,32 @@ fn test_whirlpool_reward_info_initialized() {
     assert!(reward_info.initialized());
 }
 
+#[test]
+fn test_update_after_flash_loan() {
+    let whirlpool = &mut Whirlpool {
+        protocol_fee_owed_a: 100,
+        protocol_fee_owed_b: 200,
+        ..Default::default()
+    };
+    whirlpool.update_after_flash_loan(10, 20, 1, 2).unwrap();
+    assert_eq!(whirlpool.fee_growth_global_a, 10);
+    assert_eq!(whirlpool.fee_growth_global_b, 20);
+    assert_eq!(whirlpool.protocol_fee_owed_a, 101);
+    assert_eq!(whirlpool.protocol_fee_owed_b, 202);
+}
+
+#[test]
+fn test_update_after_flash_loan_protocol_fee_overflow() {
+    let whirlpool = &mut Whirlpool {
+        protocol_fee_owed_b: u64::MAX,
+        ..Default::default()
+    };
+    let result = whirlpool.update_after_flash_loan(10, 20, 1, 1);
+    assert_eq!(result.unwrap_err(), ErrorCode::AmountCalcOverflow.into());
+    assert_eq!(whirlpool.protocol_fee_owed_a, 0);
+    assert_eq!(whirlpool.fee_growth_global_a, 0);
+}
+
 #[cfg(test)]
 pub mod whirlpool_builder {
     use super::{Whirlpool, WhirlpoolRewardInfo, NUM_REWARDS};
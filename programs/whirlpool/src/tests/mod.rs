@@ -1,2 +1,4 @@
 #[cfg(test)]
+mod swap_differential_tests;
+#[cfg(test)]
 mod swap_integration_tests;
@@ -0,0 +1,542 @@
+use crate::manager::swap_manager::*;
+use crate::math::*;
+use crate::state::tick_array_builder::TickArrayBuilder;
+use crate::state::whirlpool_builder::WhirlpoolBuilder;
+use crate::state::{
+    AdaptiveFeeConstants, AdaptiveFeeInfo, AdaptiveFeeVariables, DynamicTickArrayLoader,
+    TickArrayType, TickUpdate, Whirlpool, MAX_TICK_INDEX, MIN_TICK_INDEX, TICK_ARRAY_SIZE,
+};
+use crate::util::SwapTickSequence;
+use orca_whirlpools_core::{
+    compute_swap, get_swap_sqrt_price_limit, get_swap_tick_array_start_tick_indexes,
+    AdaptiveFeeConstantsFacade, AdaptiveFeeInfo as CoreAdaptiveFeeInfo, AdaptiveFeeVariablesFacade,
+    TickArrayFacade, TickArraySequence, TickFacade, WhirlpoolFacade,
+};
+use proptest::prelude::*;
+use proptest::sample::select;
+use proptest::test_runner::{Config, TestCaseError, TestError, TestRunner};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+
+// Shrunk failing cases are written to this directory and replayed by `replay_differential_cases`.
+const DIFFERENTIAL_CASES_DIR: &str = "src/tests/swap_differential_cases";
+
+const TICK_SPACINGS: [u16; 5] = [1, 8, 64, 128, 256];
+const TICK_ARRAY_COUNT: u32 = 3;
+const MAX_POSITIONS: usize = 4;
+const TIMESTAMP: u64 = 1_700_000_000;
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TestPosition {
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    #[serde_as(as = "DisplayFromStr")]
+    liquidity: u128,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TestAdaptiveFee {
+    filter_period: u16,
+    decay_period: u16,
+    reduction_factor: u16,
+    adaptive_fee_control_factor: u32,
+    max_volatility_accumulator: u32,
+    tick_group_size: u16,
+    major_swap_threshold_ticks: u16,
+    last_reference_update_timestamp: u64,
+    last_major_swap_timestamp: u64,
+    volatility_reference: u32,
+    tick_group_index_reference: i32,
+    volatility_accumulator: u32,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DifferentialTestCase {
+    tick_spacing: u16,
+    fee_rate: u16,
+    protocol_fee_rate: u16,
+    #[serde_as(as = "DisplayFromStr")]
+    sqrt_price: u128,
+    dynamic_tick_arrays: bool,
+    positions: Vec<TestPosition>,
+    adaptive_fee: Option<TestAdaptiveFee>,
+    #[serde_as(as = "DisplayFromStr")]
+    trade_amount: u64,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+    timestamp: u64,
+}
+
+impl DifferentialTestCase {
+    fn tick_current_index(&self) -> i32 {
+        tick_index_from_sqrt_price(&self.sqrt_price)
+    }
+
+    fn tick_array_start_indexes(&self) -> Vec<i32> {
+        get_swap_tick_array_start_tick_indexes(
+            self.tick_current_index(),
+            self.tick_spacing,
+            self.a_to_b,
+            TICK_ARRAY_COUNT,
+        )
+    }
+
+    fn sqrt_price_limit(&self, tick_array_count: usize) -> u128 {
+        get_swap_sqrt_price_limit(
+            self.tick_current_index(),
+            self.tick_spacing,
+            self.a_to_b,
+            tick_array_count as u32,
+        )
+        .into()
+    }
+
+    // Positions are the source of truth for the tick state, so that the liquidity never underflows while crossing ticks.
+    fn ticks(&self) -> BTreeMap<i32, TickUpdate> {
+        let mut ticks: BTreeMap<i32, TickUpdate> = BTreeMap::new();
+        for position in self.positions.iter() {
+            for (tick_index, liquidity_net) in [
+                (position.tick_lower_index, position.liquidity as i128),
+                (position.tick_upper_index, -(position.liquidity as i128)),
+            ] {
+                let tick = ticks.entry(tick_index).or_default();
+                tick.initialized = true;
+                tick.liquidity_net += liquidity_net;
+                tick.liquidity_gross += position.liquidity;
+            }
+        }
+        ticks
+    }
+
+    fn liquidity(&self) -> u128 {
+        let tick_current_index = self.tick_current_index();
+        self.positions
+            .iter()
+            .filter(|position| {
+                position.tick_lower_index <= tick_current_index
+                    && tick_current_index < position.tick_upper_index
+            })
+            .map(|position| position.liquidity)
+            .sum()
+    }
+
+    fn whirlpool(&self) -> Whirlpool {
+        WhirlpoolBuilder::new()
+            .liquidity(self.liquidity())
+            .sqrt_price(self.sqrt_price)
+            .tick_spacing(self.tick_spacing)
+            .tick_current_index(self.tick_current_index())
+            .fee_rate(self.fee_rate)
+            .protocol_fee_rate(self.protocol_fee_rate)
+            .build()
+    }
+
+    fn adaptive_fee_info(&self) -> Option<AdaptiveFeeInfo> {
+        self.adaptive_fee.as_ref().map(|x| AdaptiveFeeInfo {
+            constants: AdaptiveFeeConstants {
+                filter_period: x.filter_period,
+                decay_period: x.decay_period,
+                reduction_factor: x.reduction_factor,
+                adaptive_fee_control_factor: x.adaptive_fee_control_factor,
+                max_volatility_accumulator: x.max_volatility_accumulator,
+                tick_group_size: x.tick_group_size,
+                major_swap_threshold_ticks: x.major_swap_threshold_ticks,
+                ..Default::default()
+            },
+            variables: AdaptiveFeeVariables {
+                last_reference_update_timestamp: x.last_reference_update_timestamp,
+                last_major_swap_timestamp: x.last_major_swap_timestamp,
+                volatility_reference: x.volatility_reference,
+                tick_group_index_reference: x.tick_group_index_reference,
+                volatility_accumulator: x.volatility_accumulator,
+                ..Default::default()
+            },
+        })
+    }
+
+    fn core_adaptive_fee_info(&self) -> Option<CoreAdaptiveFeeInfo> {
+        self.adaptive_fee.as_ref().map(|x| CoreAdaptiveFeeInfo {
+            constants: AdaptiveFeeConstantsFacade {
+                filter_period: x.filter_period,
+                decay_period: x.decay_period,
+                reduction_factor: x.reduction_factor,
+                adaptive_fee_control_factor: x.adaptive_fee_control_factor,
+                max_volatility_accumulator: x.max_volatility_accumulator,
+                tick_group_size: x.tick_group_size,
+                major_swap_threshold_ticks: x.major_swap_threshold_ticks,
+            },
+            variables: AdaptiveFeeVariablesFacade {
+                last_reference_update_timestamp: x.last_reference_update_timestamp,
+                last_major_swap_timestamp: x.last_major_swap_timestamp,
+                volatility_reference: x.volatility_reference,
+                tick_group_index_reference: x.tick_group_index_reference,
+                volatility_accumulator: x.volatility_accumulator,
+            },
+        })
+    }
+
+    fn core_whirlpool(&self, whirlpool: &Whirlpool) -> WhirlpoolFacade {
+        // The core crate detects adaptive fee pools by a fee tier index that differs from the tick spacing.
+        let fee_tier_index = if self.adaptive_fee.is_some() {
+            self.tick_spacing.wrapping_add(1024)
+        } else {
+            self.tick_spacing
+        };
+        WhirlpoolFacade {
+            fee_tier_index_seed: fee_tier_index.to_le_bytes(),
+            tick_spacing: whirlpool.tick_spacing,
+            fee_rate: whirlpool.fee_rate,
+            protocol_fee_rate: whirlpool.protocol_fee_rate,
+            liquidity: whirlpool.liquidity,
+            sqrt_price: whirlpool.sqrt_price,
+            tick_current_index: whirlpool.tick_current_index,
+            ..Default::default()
+        }
+    }
+}
+
+fn build_tick_arrays(
+    test_case: &DifferentialTestCase,
+    start_tick_indexes: &[i32],
+) -> Vec<Box<RefCell<dyn TickArrayType>>> {
+    let mut tick_arrays: Vec<Box<RefCell<dyn TickArrayType>>> =
+        Vec::with_capacity(start_tick_indexes.len());
+    for start_tick_index in start_tick_indexes.iter() {
+        if test_case.dynamic_tick_arrays {
+            tick_arrays.push(Box::new(RefCell::new(
+                DynamicTickArrayLoader::new_with_start_tick_index(*start_tick_index),
+            )));
+        } else {
+            tick_arrays.push(Box::new(RefCell::new(
+                TickArrayBuilder::default()
+                    .start_tick_index(*start_tick_index)
+                    .build(),
+            )));
+        }
+    }
+
+    let ticks_in_array = TICK_ARRAY_SIZE * test_case.tick_spacing as i32;
+    for (tick_index, update) in test_case.ticks() {
+        let tick_array = tick_arrays
+            .iter()
+            .find(|x| {
+                let start_tick_index = x.borrow().start_tick_index();
+                (start_tick_index..start_tick_index + ticks_in_array).contains(&tick_index)
+            })
+            .expect("position ticks are generated inside the swap tick arrays");
+        tick_array
+            .borrow_mut()
+            .update_tick(tick_index, test_case.tick_spacing, &update)
+            .unwrap();
+    }
+
+    tick_arrays
+}
+
+fn to_tick_array_facade(tick_array: &dyn TickArrayType, tick_spacing: u16) -> TickArrayFacade {
+    let start_tick_index = tick_array.start_tick_index();
+    let mut ticks = [TickFacade::default(); orca_whirlpools_core::TICK_ARRAY_SIZE];
+    for (i, tick_facade) in ticks.iter_mut().enumerate() {
+        let tick_index = start_tick_index + i as i32 * tick_spacing as i32;
+        if let Ok(tick) = tick_array.get_tick(tick_index, tick_spacing) {
+            *tick_facade = TickFacade {
+                initialized: tick.initialized,
+                liquidity_net: tick.liquidity_net,
+                liquidity_gross: tick.liquidity_gross,
+                fee_growth_outside_a: tick.fee_growth_outside_a,
+                fee_growth_outside_b: tick.fee_growth_outside_b,
+                reward_growths_outside: tick.reward_growths_outside,
+            };
+        }
+    }
+    TickArrayFacade {
+        start_tick_index,
+        ticks,
+    }
+}
+
+/// Runs the program's swap and the core crate's swap on the same state and compares the results.
+fn check_differential_case(test_case: &DifferentialTestCase) -> std::result::Result<(), String> {
+    let start_tick_indexes = test_case.tick_array_start_indexes();
+    let tick_arrays = build_tick_arrays(test_case, &start_tick_indexes);
+    let sqrt_price_limit = test_case.sqrt_price_limit(tick_arrays.len());
+    let whirlpool = test_case.whirlpool();
+
+    let core_tick_arrays: Vec<TickArrayFacade> = tick_arrays
+        .iter()
+        .map(|x| to_tick_array_facade(&*x.borrow(), test_case.tick_spacing))
+        .collect();
+    let core_tick_sequence = TickArraySequence::new(core_tick_arrays, test_case.tick_spacing)
+        .map_err(|e| format!("core tick sequence: {}", e))?;
+    let core_result = compute_swap(
+        test_case.trade_amount,
+        sqrt_price_limit,
+        test_case.core_whirlpool(&whirlpool),
+        &core_tick_sequence,
+        test_case.a_to_b,
+        test_case.amount_specified_is_input,
+        test_case.timestamp,
+        test_case.core_adaptive_fee_info(),
+    );
+
+    let mut borrowed = tick_arrays.iter().map(|x| x.borrow_mut());
+    let mut tick_sequence =
+        SwapTickSequence::new(borrowed.next().unwrap(), borrowed.next(), borrowed.next());
+    let program_result = swap(
+        &whirlpool,
+        &mut tick_sequence,
+        test_case.trade_amount,
+        sqrt_price_limit,
+        test_case.amount_specified_is_input,
+        test_case.a_to_b,
+        test_case.timestamp,
+        &test_case.adaptive_fee_info(),
+    );
+
+    let (program_result, core_result) = match (program_result, core_result) {
+        (Ok(program_result), Ok(core_result)) => (program_result, core_result),
+        // Both sides reject the swap, error codes are not comparable across crates.
+        (Err(_), Err(_)) => return Ok(()),
+        (Ok(_), Err(e)) => return Err(format!("only core failed: {}", e)),
+        (Err(e), Ok(_)) => return Err(format!("only program failed: {:?}", e)),
+    };
+
+    let program_fee = program_result.lp_fee + program_result.next_protocol_fee;
+    let program_volatility_accumulator = program_result
+        .next_adaptive_fee_info
+        .as_ref()
+        .map(|x| x.variables.volatility_accumulator);
+    let core_volatility_accumulator = core_result
+        .next_adaptive_fee_info
+        .map(|x| x.variables.volatility_accumulator);
+
+    let comparisons: [(&str, u128, u128); 5] = [
+        (
+            "amount_a",
+            program_result.amount_a.into(),
+            core_result.token_a.into(),
+        ),
+        (
+            "amount_b",
+            program_result.amount_b.into(),
+            core_result.token_b.into(),
+        ),
+        ("fee", program_fee.into(), core_result.trade_fee.into()),
+        (
+            "next_sqrt_price",
+            program_result.next_sqrt_price,
+            core_result.next_sqrt_price,
+        ),
+        (
+            "next_liquidity",
+            program_result.next_liquidity,
+            core_result.next_liquidity,
+        ),
+    ];
+    for (name, program_value, core_value) in comparisons {
+        if program_value != core_value {
+            return Err(format!(
+                "{} mismatch: program {}, core {}",
+                name, program_value, core_value
+            ));
+        }
+    }
+    if program_result.next_tick_index != core_result.next_tick_index {
+        return Err(format!(
+            "next_tick_index mismatch: program {}, core {}",
+            program_result.next_tick_index, core_result.next_tick_index
+        ));
+    }
+    if program_volatility_accumulator != core_volatility_accumulator {
+        return Err(format!(
+            "volatility_accumulator mismatch: program {:?}, core {:?}",
+            program_volatility_accumulator, core_volatility_accumulator
+        ));
+    }
+
+    Ok(())
+}
+
+prop_compose! {
+    fn adaptive_fee_strategy(tick_spacing: u16, tick_current_index: i32)(
+        tick_group_size in select(vec![1, tick_spacing]),
+        filter_period in 1u16..=60,
+        decay_period_delta in 1u16..=600,
+        reduction_factor in 0u16..10_000,
+        adaptive_fee_control_factor in 0u32..100_000,
+        max_volatility_accumulator in 0u32..=350_000,
+        major_swap_threshold_ticks in
+            1u16..=(tick_spacing as u32 * TICK_ARRAY_SIZE as u32).min(u16::MAX as u32) as u16,
+        reference_age in 0u64..=4_000,
+        major_swap_age in 0u64..=4_000,
+        volatility_reference in 0u32..=350_000,
+        tick_group_index_delta in -10i32..=10,
+        volatility_accumulator in 0u32..=350_000,
+    ) -> TestAdaptiveFee {
+        TestAdaptiveFee {
+            filter_period,
+            decay_period: filter_period + decay_period_delta,
+            reduction_factor,
+            adaptive_fee_control_factor,
+            max_volatility_accumulator,
+            tick_group_size,
+            major_swap_threshold_ticks,
+            last_reference_update_timestamp: TIMESTAMP - reference_age,
+            last_major_swap_timestamp: TIMESTAMP - major_swap_age,
+            volatility_reference: volatility_reference.min(max_volatility_accumulator),
+            tick_group_index_reference: tick_current_index.div_euclid(tick_group_size as i32)
+                + tick_group_index_delta,
+            volatility_accumulator: volatility_accumulator.min(max_volatility_accumulator),
+        }
+    }
+}
+
+prop_compose! {
+    fn differential_test_case()(
+        tick_spacing in select(TICK_SPACINGS.to_vec()),
+        tick_current_index in prop_oneof![
+            -5_000i32..5_000,
+            MIN_TICK_INDEX..MAX_TICK_INDEX,
+        ],
+        sqrt_price_offset in 0u128..u16::MAX as u128,
+        a_to_b in any::<bool>(),
+    )(
+        fee_rate in 0u16..=MAX_FEE_RATE,
+        protocol_fee_rate in 0u16..=MAX_PROTOCOL_FEE_RATE,
+        dynamic_tick_arrays in any::<bool>(),
+        positions in prop::collection::vec(
+            (
+                0i32..TICK_ARRAY_COUNT as i32 * TICK_ARRAY_SIZE,
+                0i32..TICK_ARRAY_COUNT as i32 * TICK_ARRAY_SIZE,
+                1u128..=1_000_000_000_000_000_000,
+            ),
+            0..=MAX_POSITIONS,
+        ),
+        adaptive_fee in prop::option::of(adaptive_fee_strategy(tick_spacing, tick_current_index)),
+        trade_amount in prop_oneof![1u64..1_000_000_000, 1u64..=u64::MAX],
+        amount_specified_is_input in any::<bool>(),
+        tick_spacing in Just(tick_spacing),
+        tick_current_index in Just(tick_current_index),
+        sqrt_price_offset in Just(sqrt_price_offset),
+        a_to_b in Just(a_to_b),
+    ) -> DifferentialTestCase {
+        let lower_sqrt_price = sqrt_price_from_tick_index(tick_current_index);
+        let upper_sqrt_price = sqrt_price_from_tick_index(tick_current_index + 1);
+        let sqrt_price = lower_sqrt_price
+            + (upper_sqrt_price - lower_sqrt_price) * sqrt_price_offset / u16::MAX as u128;
+
+        // Position ticks are offsets into the window of initializable ticks covered by the swap tick arrays.
+        let start_tick_indexes = get_swap_tick_array_start_tick_indexes(
+            tick_current_index,
+            tick_spacing,
+            a_to_b,
+            TICK_ARRAY_COUNT,
+        );
+        let window_start = *start_tick_indexes.iter().min().unwrap();
+        let window_len = start_tick_indexes.len() as i32 * TICK_ARRAY_SIZE;
+        let positions = positions
+            .into_iter()
+            .map(|(a, b, liquidity)| (a % window_len, b % window_len, liquidity))
+            .filter(|(a, b, _)| a != b)
+            .map(|(a, b, liquidity)| TestPosition {
+                tick_lower_index: window_start + a.min(b) * tick_spacing as i32,
+                tick_upper_index: window_start + a.max(b) * tick_spacing as i32,
+                liquidity,
+            })
+            .filter(|position| {
+                position.tick_lower_index >= MIN_TICK_INDEX
+                    && position.tick_upper_index <= MAX_TICK_INDEX
+            })
+            .collect();
+
+        DifferentialTestCase {
+            tick_spacing,
+            fee_rate,
+            protocol_fee_rate,
+            sqrt_price,
+            dynamic_tick_arrays,
+            positions,
+            adaptive_fee,
+            trade_amount,
+            amount_specified_is_input,
+            a_to_b,
+            timestamp: TIMESTAMP,
+        }
+    }
+}
+
+fn differential_cases_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(DIFFERENTIAL_CASES_DIR)
+}
+
+fn dump_differential_case(test_case: &DifferentialTestCase) -> PathBuf {
+    let json = serde_json::to_string_pretty(test_case).unwrap();
+    let mut hasher = DefaultHasher::new();
+    json.hash(&mut hasher);
+
+    let dir = differential_cases_dir();
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(format!("case_{:016x}.json", hasher.finish()));
+    fs::write(&path, json + "\n").unwrap();
+    path
+}
+
+#[test]
+/// Compares the program's swap with the core crate's compute_swap on generated pools.
+/// Set PROPTEST_CASES to change the number of generated cases.
+fn differential_swap_program_vs_core() {
+    let mut runner = TestRunner::new(Config {
+        failure_persistence: None,
+        ..Config::default()
+    });
+    let result = runner.run(&differential_test_case(), |test_case| {
+        check_differential_case(&test_case).map_err(TestCaseError::fail)
+    });
+
+    match result {
+        Ok(()) => {}
+        Err(TestError::Fail(reason, test_case)) => {
+            let path = dump_differential_case(&test_case);
+            panic!(
+                "program and core swap diverged: {}\nminimal case written to {}",
+                reason,
+                path.display()
+            );
+        }
+        Err(e) => panic!("{}", e),
+    }
+}
+
+#[test]
+/// Replays the dumped cases so that divergences stay fixed once resolved.
+fn replay_differential_cases() {
+    let entries = match fs::read_dir(differential_cases_dir()) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension() == Some(OsStr::new("json")))
+        .collect();
+    paths.sort();
+
+    for path in paths {
+        let test_case: DifferentialTestCase =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        if let Err(reason) = check_differential_case(&test_case) {
+            panic!("{}: {}", path.display(), reason);
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use solana_program::instruction::{get_stack_height, Instruction, TRANSACTION_LEVEL_STACK_HEIGHT};
+use solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+
+use crate::errors::ErrorCode;
+
+#[derive(Debug, PartialEq)]
+enum FlashLoanInstruction {
+    Borrow { amount_a: u64, amount_b: u64 },
+    Repay { amount_a: u64, amount_b: u64 },
+}
+
+// Both flash loan instructions share the FlashLoan accounts, so the whirlpool is always the first
+// account and the amounts are always the first two arguments.
+fn parse_flash_loan_instruction(
+    instruction: &Instruction,
+    whirlpool: &Pubkey,
+) -> Option<FlashLoanInstruction> {
+    if instruction.program_id != crate::ID
+        || instruction.accounts.first().map(|a| a.pubkey) != Some(*whirlpool)
+        || instruction.data.len() < 24
+    {
+        return None;
+    }
+
+    let discriminator = &instruction.data[0..8];
+    let amount_a = u64::from_le_bytes(instruction.data[8..16].try_into().unwrap());
+    let amount_b = u64::from_le_bytes(instruction.data[16..24].try_into().unwrap());
+
+    if discriminator == crate::instruction::FlashBorrow::DISCRIMINATOR {
+        Some(FlashLoanInstruction::Borrow { amount_a, amount_b })
+    } else if discriminator == crate::instruction::FlashRepay::DISCRIMINATOR {
+        Some(FlashLoanInstruction::Repay { amount_a, amount_b })
+    } else {
+        None
+    }
+}
+
+// Instruction introspection only sees top-level instructions, so a flash loan invoked through CPI
+// could not be matched reliably.
+fn verify_top_level_instruction() -> Result<()> {
+    if get_stack_height() != TRANSACTION_LEVEL_STACK_HEIGHT {
+        return Err(ErrorCode::FlashLoanCpiNotAllowed.into());
+    }
+    Ok(())
+}
+
+// The next flash loan instruction for this whirlpool must repay exactly the borrowed amounts.
+pub fn verify_flash_repay_follows(
+    instructions_sysvar: &AccountInfo,
+    whirlpool: &Pubkey,
+    amount_a: u64,
+    amount_b: u64,
+) -> Result<()> {
+    verify_top_level_instruction()?;
+
+    let current_index = load_current_index_checked(instructions_sysvar)? as usize;
+    let mut index = current_index + 1;
+    while let Ok(instruction) = load_instruction_at_checked(index, instructions_sysvar) {
+        if let Some(flash_loan) = parse_flash_loan_instruction(&instruction, whirlpool) {
+            if flash_loan == (FlashLoanInstruction::Repay { amount_a, amount_b }) {
+                return Ok(());
+            }
+            break;
+        }
+        index += 1;
+    }
+
+    Err(ErrorCode::FlashRepayNotFound.into())
+}
+
+// The previous flash loan instruction for this whirlpool must have borrowed exactly the repaid amounts.
+pub fn verify_flash_borrow_precedes(
+    instructions_sysvar: &AccountInfo,
+    whirlpool: &Pubkey,
+    amount_a: u64,
+    amount_b: u64,
+) -> Result<()> {
+    verify_top_level_instruction()?;
+
+    let current_index = load_current_index_checked(instructions_sysvar)? as usize;
+    for index in (0..current_index).rev() {
+        let instruction = load_instruction_at_checked(index, instructions_sysvar)?;
+        if let Some(flash_loan) = parse_flash_loan_instruction(&instruction, whirlpool) {
+            if flash_loan == (FlashLoanInstruction::Borrow { amount_a, amount_b }) {
+                return Ok(());
+            }
+            break;
+        }
+    }
+
+    Err(ErrorCode::FlashBorrowNotFound.into())
+}
+
+#[cfg(test)]
+mod flash_loan_tests {
+    use super::*;
+    use solana_program::instruction::AccountMeta;
+
+    fn flash_loan_instruction(
+        program_id: Pubkey,
+        whirlpool: Pubkey,
+        discriminator: [u8; 8],
+        amount_a: u64,
+        amount_b: u64,
+    ) -> Instruction {
+        let mut data = discriminator.to_vec();
+        data.extend_from_slice(&amount_a.to_le_bytes());
+        data.extend_from_slice(&amount_b.to_le_bytes());
+        // remaining_accounts_info: None
+        data.push(0);
+        Instruction {
+            program_id,
+            accounts: vec![AccountMeta::new(whirlpool, false)],
+            data,
+        }
+    }
+
+    #[test]
+    fn test_parse_flash_loan_instruction() {
+        let whirlpool = Pubkey::new_unique();
+
+        let borrow = flash_loan_instruction(
+            crate::ID,
+            whirlpool,
+            crate::instruction::FlashBorrow::DISCRIMINATOR,
+            1_000,
+            2_000,
+        );
+        assert_eq!(
+            parse_flash_loan_instruction(&borrow, &whirlpool),
+            Some(FlashLoanInstruction::Borrow {
+                amount_a: 1_000,
+                amount_b: 2_000
+            })
+        );
+
+        let repay = flash_loan_instruction(
+            crate::ID,
+            whirlpool,
+            crate::instruction::FlashRepay::DISCRIMINATOR,
+            1_000,
+            0,
+        );
+        assert_eq!(
+            parse_flash_loan_instruction(&repay, &whirlpool),
+            Some(FlashLoanInstruction::Repay {
+                amount_a: 1_000,
+                amount_b: 0
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_flash_loan_instruction_ignores_unrelated() {
+        let whirlpool = Pubkey::new_unique();
+
+        // other whirlpool
+        let other_whirlpool = flash_loan_instruction(
+            crate::ID,
+            Pubkey::new_unique(),
+            crate::instruction::FlashBorrow::DISCRIMINATOR,
+            1_000,
+            0,
+        );
+        assert_eq!(
+            parse_flash_loan_instruction(&other_whirlpool, &whirlpool),
+            None
+        );
+
+        // other program
+        let other_program = flash_loan_instruction(
+            Pubkey::new_unique(),
+            whirlpool,
+            crate::instruction::FlashBorrow::DISCRIMINATOR,
+            1_000,
+            0,
+        );
+        assert_eq!(
+            parse_flash_loan_instruction(&other_program, &whirlpool),
+            None
+        );
+
+        // other instruction
+        let other_instruction = flash_loan_instruction(
+            crate::ID,
+            whirlpool,
+            crate::instruction::Swap::DISCRIMINATOR,
+            1_000,
+            0,
+        );
+        assert_eq!(
+            parse_flash_loan_instruction(&other_instruction, &whirlpool),
+            None
+        );
+    }
+}
@@ -1,3 +1,5 @@
+pub mod flash_loan;
+pub mod oracle;
 pub mod shared;
 pub mod sparse_swap;
 pub mod swap_tick_sequence;
@@ -6,6 +8,8 @@ pub mod token;
 pub mod token_2022;
 pub mod v2;
 
+pub use flash_loan::*;
+pub use oracle::*;
 pub use shared::*;
 pub use sparse_swap::*;
 pub use swap_tick_sequence::*;
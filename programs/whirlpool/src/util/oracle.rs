@@ -0,0 +1,150 @@
+use std::cmp::max;
+
+use anchor_lang::{prelude::*, system_program, Discriminator};
+use solana_program::{program::invoke_signed, system_instruction};
+
+use crate::{
+    errors::ErrorCode,
+    state::{Oracle, OracleAccessor, Whirlpool},
+    ID,
+};
+
+// Liquidity increase instructions do not have the Oracle in their accounts, so they receive it as an
+// optional remaining account to enforce the pause flags of the Whirlpool. The SDKs always pass it.
+pub fn verify_whirlpool_not_paused<'info>(
+    whirlpool: &Account<'info, Whirlpool>,
+    oracle_account_info: Option<&AccountInfo<'info>>,
+) -> Result<()> {
+    let oracle_account_info = match oracle_account_info {
+        Some(oracle_account_info) => oracle_account_info,
+        None => return Ok(()),
+    };
+
+    let (oracle_address, _) =
+        Pubkey::find_program_address(&[b"oracle", whirlpool.key().as_ref()], &ID);
+    if oracle_account_info.key() != oracle_address {
+        return Err(Error::from(anchor_lang::error::ErrorCode::ConstraintSeeds)
+            .with_pubkeys((oracle_account_info.key(), oracle_address)));
+    }
+
+    let oracle_accessor = OracleAccessor::new(whirlpool, oracle_account_info.clone())?;
+    if oracle_accessor.is_paused()? {
+        return Err(ErrorCode::WhirlpoolPaused.into());
+    }
+
+    Ok(())
+}
+
+// Sets or clears a pause flag on the Oracle of a Whirlpool.
+pub fn update_oracle_pause_flag<'info>(
+    whirlpool: &Account<'info, Whirlpool>,
+    oracle: &UncheckedAccount<'info>,
+    oracle_bump: u8,
+    funder: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    flag: u8,
+    paused: bool,
+) -> Result<()> {
+    if oracle.owner == &system_program::ID && !paused {
+        // nothing to clear
+        return Ok(());
+    }
+
+    update_oracle(
+        whirlpool,
+        oracle,
+        oracle_bump,
+        funder,
+        system_program,
+        |oracle| {
+            oracle.update_pause_flag(flag, paused);
+            Ok(())
+        },
+    )
+}
+
+// Updates the Oracle of a Whirlpool with the given function.
+// Whirlpools without adaptive fee have no Oracle, so one without adaptive fee is created to hold the update.
+pub fn update_oracle<'info>(
+    whirlpool: &Account<'info, Whirlpool>,
+    oracle: &UncheckedAccount<'info>,
+    oracle_bump: u8,
+    funder: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    update: impl FnOnce(&mut Oracle) -> Result<()>,
+) -> Result<()> {
+    if oracle.owner == &system_program::ID {
+        create_oracle_account(whirlpool, oracle, oracle_bump, funder, system_program)?;
+    }
+
+    if oracle.owner != &ID {
+        return Err(anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram.into());
+    }
+
+    let mut data = oracle.try_borrow_mut_data()?;
+    let is_initialized = data[0..8] != [0; 8];
+    if is_initialized && data[0..8] != Oracle::discriminator() {
+        return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into());
+    }
+    if !is_initialized {
+        data[0..8].copy_from_slice(&Oracle::discriminator());
+    }
+
+    let oracle: &mut Oracle = bytemuck::from_bytes_mut(&mut data[8..Oracle::LEN]);
+    if !is_initialized {
+        oracle.initialize_without_adaptive_fee(whirlpool.key());
+    }
+    if oracle.whirlpool != whirlpool.key() {
+        // Just for safety: Oracle address is derived from Whirlpool address, so this should not happen.
+        unreachable!();
+    }
+
+    update(oracle)
+}
+
+fn create_oracle_account<'info>(
+    whirlpool: &Account<'info, Whirlpool>,
+    oracle: &UncheckedAccount<'info>,
+    oracle_bump: u8,
+    funder: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let whirlpool_key = whirlpool.key();
+    let signer_seeds: &[&[u8]] = &[b"oracle", whirlpool_key.as_ref(), &[oracle_bump]];
+    let current_balance = oracle.lamports();
+
+    if current_balance > 0 {
+        // If there is already a balance, `create_account` fails.
+        // Move the balance to the funder and send it back with the rent below.
+        invoke_signed(
+            &system_instruction::transfer(&oracle.key(), &funder.key(), current_balance),
+            &[
+                oracle.to_account_info(),
+                funder.to_account_info(),
+                system_program.to_account_info(),
+            ],
+            &[signer_seeds],
+        )?;
+    }
+
+    let rent_exempt = Rent::get()?.minimum_balance(Oracle::LEN);
+    let lamports = max(rent_exempt, current_balance);
+
+    invoke_signed(
+        &system_instruction::create_account(
+            &funder.key(),
+            &oracle.key(),
+            lamports,
+            Oracle::LEN as u64,
+            &ID,
+        ),
+        &[
+            funder.to_account_info(),
+            oracle.to_account_info(),
+            system_program.to_account_info(),
+        ],
+        &[signer_seeds],
+    )?;
+
+    Ok(())
+}
@@ -8,6 +8,7 @@ use solana_program::program_option::COption;
 use std::convert::TryFrom;
 
 use crate::errors::ErrorCode;
+use crate::state::{LockConfig, Position, PositionDelegate};
 
 pub fn verify_position_bundle_authority(
     position_bundle_token_account: &TokenAccount,
@@ -58,6 +59,47 @@ pub fn verify_position_authority_interface(
     Ok(())
 }
 
+// The position authority is either the holder of the position token (or its SPL token delegate),
+// or the delegate of a PositionDelegate granting the permission. A PositionDelegate never allows
+// tokens to leave the position towards accounts that the position owner does not own.
+pub fn verify_position_authority_or_delegate<'info>(
+    position_token_account: &InterfaceAccount<'info, TokenAccountInterface>,
+    position_authority: &Signer<'info>,
+    position: Pubkey,
+    position_delegate_account_info: Option<&AccountInfo<'info>>,
+    permission: u16,
+    destination_owners: &[Pubkey],
+) -> Result<()> {
+    let position_delegate_account_info = match position_delegate_account_info {
+        Some(position_delegate_account_info) => position_delegate_account_info,
+        None => {
+            return verify_position_authority_interface(position_token_account, position_authority)
+        }
+    };
+
+    if verify_position_authority_interface(position_token_account, position_authority).is_ok() {
+        return Ok(());
+    }
+
+    let position_delegate =
+        load_remaining_account::<PositionDelegate>(position_delegate_account_info)?;
+    position_delegate.verify_permission(
+        position,
+        position_token_account.owner,
+        position_authority.key(),
+        permission,
+    )?;
+
+    if destination_owners
+        .iter()
+        .any(|owner| *owner != position_token_account.owner)
+    {
+        return Err(ErrorCode::InvalidPositionDelegateDestination.into());
+    }
+
+    Ok(())
+}
+
 pub fn validate_owner(expected_owner: &Pubkey, owner_account_info: &AccountInfo) -> Result<()> {
     if expected_owner != owner_account_info.key || !owner_account_info.is_signer {
         return Err(ErrorCode::MissingOrInvalidDelegate.into());
@@ -75,3 +117,45 @@ pub fn is_locked_position(
 ) -> bool {
     position_token_account.is_frozen()
 }
+
+// Locked positions can only withdraw the liquidity that their LockConfig allows.
+pub fn verify_liquidity_withdrawable<'info>(
+    position_token_account: &InterfaceAccount<'info, TokenAccountInterface>,
+    position: &Account<'info, Position>,
+    lock_config_account_info: Option<&AccountInfo<'info>>,
+    liquidity_amount: u128,
+    timestamp: u64,
+) -> Result<()> {
+    if !is_locked_position(position_token_account) {
+        return Ok(());
+    }
+
+    let lock_config_account_info =
+        lock_config_account_info.ok_or(ErrorCode::OperationNotAllowedOnLockedPosition)?;
+    let lock_config = load_remaining_account::<LockConfig>(lock_config_account_info)?;
+    if lock_config.position != position.key() {
+        return Err(ErrorCode::OperationNotAllowedOnLockedPosition.into());
+    }
+
+    let withdrawable_liquidity =
+        lock_config.get_withdrawable_liquidity(position.liquidity, timestamp)?;
+    if liquidity_amount > withdrawable_liquidity {
+        return Err(ErrorCode::OperationNotAllowedOnLockedPosition.into());
+    }
+
+    Ok(())
+}
+
+// Accounts passed as remaining accounts are only read here, so they are deserialized directly
+// instead of being wrapped into an Account that would need to borrow them for the whole instruction.
+fn load_remaining_account<T: AccountDeserialize + Owner>(account_info: &AccountInfo) -> Result<T> {
+    if account_info.owner != &T::owner() {
+        return Err(
+            Error::from(anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram)
+                .with_pubkeys((*account_info.owner, T::owner())),
+        );
+    }
+
+    let data = account_info.try_borrow_data()?;
+    T::try_deserialize(&mut data.as_ref())
+}
@@ -1,5 +1,4 @@
 use anchor_lang::{prelude::*, system_program};
-use std::collections::VecDeque;
 
 use crate::{
     math::floor_division,
@@ -7,11 +6,14 @@ use crate::{
         FixedTickArray, Tick, TickArrayType, TickUpdate, Whirlpool, ZeroedTickArray,
         TICK_ARRAY_SIZE,
     },
-    util::SwapTickSequence,
+    util::{SwapTickSequence, MAX_SUPPLEMENTAL_TICK_ARRAYS_LEN},
 };
 
 use crate::state::{load_tick_array_mut, LoadedTickArrayMut};
 
+// The static tick arrays of a swap instruction and its supplemental tick arrays can all be traversed.
+pub const MAX_SWAP_TICK_ARRAYS: usize = 3 + MAX_SUPPLEMENTAL_TICK_ARRAYS_LEN;
+
 pub(crate) enum ProxiedTickArray<'a> {
     Initialized(LoadedTickArrayMut<'a>),
     Uninitialized(ZeroedTickArray),
@@ -96,8 +98,8 @@ impl<'info> SparseSwapTickSequenceBuilder<'info> {
     /// static_tick_array_account_infos and supplemental_tick_array_account_infos will be merged,
     /// and deduplicated by key. TickArray accounts can be provided in any order.
     ///
-    /// Even if over three tick arrays are provided, only three tick arrays are used in the single swap.
-    /// The extra TickArray acts as a fallback in case the current price moves.
+    /// Up to MAX_SWAP_TICK_ARRAYS tick arrays in the swap direction are used in the single swap.
+    /// The TickArrays in the opposite direction act as a fallback in case the current price moves.
     pub fn new(
         static_tick_array_account_infos: Vec<AccountInfo<'info>>,
         supplemental_tick_array_account_infos: Option<Vec<AccountInfo<'info>>>,
@@ -132,7 +134,8 @@ impl<'info> SparseSwapTickSequenceBuilder<'info> {
         whirlpool: &Account<Whirlpool>,
         a_to_b: bool,
     ) -> Result<SwapTickSequence<'a>> {
-        let mut loaded_tick_arrays: Vec<LoadedTickArrayMut> = Vec::with_capacity(3);
+        let mut loaded_tick_arrays: Vec<LoadedTickArrayMut> =
+            Vec::with_capacity(MAX_SWAP_TICK_ARRAYS);
         for account_info in &self.tick_array_accounts {
             let tick_array = maybe_load_tick_array(account_info, whirlpool)?;
             if let Some(tick_array) = tick_array {
@@ -140,15 +143,16 @@ impl<'info> SparseSwapTickSequenceBuilder<'info> {
             }
         }
 
-        let start_tick_indexes = get_start_tick_indexes(whirlpool, a_to_b);
-        let mut required_tick_arrays: VecDeque<ProxiedTickArray> = VecDeque::with_capacity(3);
+        let start_tick_indexes = get_start_tick_indexes(whirlpool, a_to_b, MAX_SWAP_TICK_ARRAYS);
+        let mut required_tick_arrays: Vec<ProxiedTickArray> =
+            Vec::with_capacity(MAX_SWAP_TICK_ARRAYS);
         for start_tick_index in start_tick_indexes.iter() {
             let pos = loaded_tick_arrays
                 .iter()
                 .position(|tick_array| tick_array.start_tick_index() == *start_tick_index);
             if let Some(pos) = pos {
                 let tick_array = loaded_tick_arrays.remove(pos);
-                required_tick_arrays.push_back(ProxiedTickArray::new_initialized(tick_array));
+                required_tick_arrays.push(ProxiedTickArray::new_initialized(tick_array));
                 continue;
             }
 
@@ -158,8 +162,7 @@ impl<'info> SparseSwapTickSequenceBuilder<'info> {
                 .iter()
                 .any(|account_info| account_info.key() == tick_array_pda);
             if has_account_info {
-                required_tick_arrays
-                    .push_back(ProxiedTickArray::new_uninitialized(*start_tick_index));
+                required_tick_arrays.push(ProxiedTickArray::new_uninitialized(*start_tick_index));
                 continue;
             }
             break;
@@ -169,11 +172,7 @@ impl<'info> SparseSwapTickSequenceBuilder<'info> {
             return Err(crate::errors::ErrorCode::InvalidTickArraySequence.into());
         }
 
-        Ok(SwapTickSequence::new_with_proxy(
-            required_tick_arrays.pop_front().unwrap(),
-            required_tick_arrays.pop_front(),
-            required_tick_arrays.pop_front(),
-        ))
+        Ok(SwapTickSequence::new_with_proxies(required_tick_arrays))
     }
 }
 
@@ -201,28 +200,38 @@ fn derive_tick_array_pda(whirlpool: &Account<Whirlpool>, start_tick_index: i32)
     .0
 }
 
-fn get_start_tick_indexes(whirlpool: &Account<Whirlpool>, a_to_b: bool) -> Vec<i32> {
+fn get_start_tick_indexes(
+    whirlpool: &Account<Whirlpool>,
+    a_to_b: bool,
+    tick_array_count: usize,
+) -> Vec<i32> {
     let tick_current_index = whirlpool.tick_current_index;
     let tick_spacing_u16 = whirlpool.tick_spacing;
     let tick_spacing_i32 = whirlpool.tick_spacing as i32;
     let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing_i32;
 
     let start_tick_index_base = floor_division(tick_current_index, ticks_in_array) * ticks_in_array;
-    let offset = if a_to_b {
-        [0, -1, -2]
+    let first_offset = if a_to_b {
+        0
     } else {
         let shifted =
             tick_current_index + tick_spacing_i32 >= start_tick_index_base + ticks_in_array;
         if shifted {
-            [1, 2, 3]
+            1
         } else {
-            [0, 1, 2]
+            0
         }
     };
 
-    let start_tick_indexes = offset
-        .iter()
-        .filter_map(|&o| {
+    let start_tick_indexes = (0..tick_array_count as i32)
+        .map(|i| {
+            if a_to_b {
+                first_offset - i
+            } else {
+                first_offset + i
+            }
+        })
+        .filter_map(|o| {
             let start_tick_index = start_tick_index_base + o * ticks_in_array;
             if Tick::check_is_valid_start_tick(start_tick_index, tick_spacing_u16) {
                 Some(start_tick_index)
@@ -304,10 +313,26 @@ mod sparse_swap_tick_sequence_tests {
             );
             let account_info = account_info_mock.to_account_info(true);
             let whirlpool_account = Account::<Whirlpool>::try_from(&account_info).unwrap();
-            let start_tick_indexes = get_start_tick_indexes(&whirlpool_account, a_to_b);
+            let start_tick_indexes = get_start_tick_indexes(&whirlpool_account, a_to_b, 3);
             assert_eq!(start_tick_indexes, expected);
         }
 
+        #[test]
+        fn with_supplemental_tick_arrays() {
+            let mut account_info_mock =
+                AccountInfoMock::new_whirlpool(Pubkey::new_unique(), 1, 0, None);
+            let account_info = account_info_mock.to_account_info(true);
+            let whirlpool_account = Account::<Whirlpool>::try_from(&account_info).unwrap();
+            assert_eq!(
+                get_start_tick_indexes(&whirlpool_account, true, MAX_SWAP_TICK_ARRAYS),
+                vec![0, -88, -176, -264, -352, -440]
+            );
+            assert_eq!(
+                get_start_tick_indexes(&whirlpool_account, false, MAX_SWAP_TICK_ARRAYS),
+                vec![0, 88, 176, 264, 352, 440]
+            );
+        }
+
         mod tick_spacing_1 {
             use super::*;
 
@@ -639,10 +664,11 @@ mod sparse_swap_tick_sequence_tests {
             );
             let swap_tick_sequence = builder.try_build(&whirlpool, false).unwrap();
 
-            assert_eq!(swap_tick_sequence.arrays.len(), 3);
+            assert_eq!(swap_tick_sequence.arrays.len(), 4);
             assert_eq!(swap_tick_sequence.arrays[0].start_tick_index(), -5632);
             assert_eq!(swap_tick_sequence.arrays[1].start_tick_index(), 0);
             assert_eq!(swap_tick_sequence.arrays[2].start_tick_index(), 5632);
+            assert_eq!(swap_tick_sequence.arrays[3].start_tick_index(), 11264);
         }
 
         #[test]
@@ -38,6 +38,10 @@ impl<'a> SwapTickSequence<'a> {
         Self { arrays: vec }
     }
 
+    pub(crate) fn new_with_proxies(arrays: Vec<ProxiedTickArray<'a>>) -> Self {
+        Self { arrays }
+    }
+
     /// Get the Tick object at the given tick-index & tick-spacing
     ///
     /// # Parameters
@@ -1,7 +1,9 @@
+pub mod referral;
 pub mod remaining_accounts_utils;
 pub mod swap_utils;
 pub mod token;
 
+pub use referral::*;
 pub use remaining_accounts_utils::*;
 pub use swap_utils::*;
 pub use token::*;
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::transfer_memo;
+use crate::errors::ErrorCode;
+use crate::events::ReferralFeePaid;
+use crate::state::{Referral, Whirlpool, WhirlpoolsConfigExtension};
+
+use super::{calculate_transfer_fee_excluded_amount, transfer_from_vault_to_owner_v2};
+
+pub struct ReferralAccounts<'info> {
+    pub whirlpools_config_extension: Account<'info, WhirlpoolsConfigExtension>,
+    pub referral: Account<'info, Referral>,
+    pub referral_token_account: InterfaceAccount<'info, TokenAccount>,
+}
+
+// Loads the optional referral accounts of a swap.
+// The referral token account must be owned by the referrer and hold the input token of the swap.
+// The accounts are borrowed from the instruction remaining accounts (see get_remaining_accounts_slice).
+pub fn load_referral_accounts<'info>(
+    referral_account_infos: Option<&'info [AccountInfo<'info>]>,
+    whirlpool: &Whirlpool,
+    input_token_mint: Pubkey,
+) -> Result<Option<ReferralAccounts<'info>>> {
+    let referral_account_infos = match referral_account_infos {
+        Some(referral_account_infos) => referral_account_infos,
+        None => return Ok(None),
+    };
+
+    let whirlpools_config_extension =
+        Account::<WhirlpoolsConfigExtension>::try_from(&referral_account_infos[0])?;
+    let referral = Account::<Referral>::try_from(&referral_account_infos[1])?;
+    let referral_token_account =
+        InterfaceAccount::<TokenAccount>::try_from(&referral_account_infos[2])?;
+
+    if whirlpools_config_extension.whirlpools_config != whirlpool.whirlpools_config
+        || referral.whirlpools_config != whirlpool.whirlpools_config
+        || referral_token_account.owner != referral.referrer
+        || referral_token_account.mint != input_token_mint
+    {
+        return Err(ErrorCode::InvalidReferralAccounts.into());
+    }
+
+    Ok(Some(ReferralAccounts {
+        whirlpools_config_extension,
+        referral,
+        referral_token_account,
+    }))
+}
+
+// Pays the referral share of the protocol fee of a swap from the input token vault.
+// Returns the referral fee deducted from the protocol fee.
+#[allow(clippy::too_many_arguments)]
+pub fn pay_referral_fee_v2<'info>(
+    whirlpool: &mut Account<'info, Whirlpool>,
+    referral_accounts: &ReferralAccounts<'info>,
+    token_mint: &InterfaceAccount<'info, Mint>,
+    token_vault: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    memo_program: &Program<'info, Memo>,
+    transfer_hook_accounts: &Option<Vec<AccountInfo<'info>>>,
+    protocol_fee: u64,
+    is_token_fee_in_a: bool,
+) -> Result<u64> {
+    let referral_fee = referral_accounts.referral.calculate_referral_fee(
+        protocol_fee,
+        referral_accounts
+            .whirlpools_config_extension
+            .max_referral_fee_rate,
+    );
+    if referral_fee == 0 {
+        return Ok(0);
+    }
+
+    whirlpool.deduct_referral_fee(referral_fee, is_token_fee_in_a)?;
+
+    transfer_from_vault_to_owner_v2(
+        whirlpool,
+        token_mint,
+        token_vault,
+        &referral_accounts.referral_token_account,
+        token_program,
+        memo_program,
+        transfer_hook_accounts,
+        referral_fee,
+        transfer_memo::TRANSFER_MEMO_REFERRAL_FEE.as_bytes(),
+    )?;
+
+    emit!(ReferralFeePaid {
+        whirlpool: whirlpool.key(),
+        referral: referral_accounts.referral.key(),
+        token_mint: token_mint.key(),
+        referral_fee,
+        referral_fee_transfer_fee: calculate_transfer_fee_excluded_amount(
+            token_mint,
+            referral_fee
+        )?
+        .transfer_fee,
+    });
+
+    Ok(referral_fee)
+}
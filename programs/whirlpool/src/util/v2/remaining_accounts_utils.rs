@@ -1,4 +1,5 @@
 use crate::errors::ErrorCode;
+use crate::state::{MAX_PROTOCOL_FEE_RECIPIENTS, NUM_REWARDS};
 use anchor_lang::prelude::*;
 
 pub const MAX_SUPPLEMENTAL_TICK_ARRAYS_LEN: usize = 3;
@@ -14,6 +15,12 @@ pub enum AccountsType {
     SupplementalTickArrays,
     SupplementalTickArraysOne,
     SupplementalTickArraysTwo,
+    LockConfig,
+    PositionDelegate,
+    RewardVaults,
+    Referral,
+    ProtocolFeeRecipients,
+    Oracle,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -38,6 +45,12 @@ pub struct ParsedRemainingAccounts<'info> {
     pub supplemental_tick_arrays: Option<Vec<AccountInfo<'info>>>,
     pub supplemental_tick_arrays_one: Option<Vec<AccountInfo<'info>>>,
     pub supplemental_tick_arrays_two: Option<Vec<AccountInfo<'info>>>,
+    pub lock_config: Option<AccountInfo<'info>>,
+    pub position_delegate: Option<AccountInfo<'info>>,
+    pub reward_vaults: Option<Vec<AccountInfo<'info>>>,
+    pub referral: Option<Vec<AccountInfo<'info>>>,
+    pub protocol_fee_recipients: Option<Vec<AccountInfo<'info>>>,
+    pub oracle: Option<AccountInfo<'info>>,
 }
 
 pub fn parse_remaining_accounts<'info>(
@@ -147,8 +160,158 @@ pub fn parse_remaining_accounts<'info>(
                 }
                 parsed_remaining_accounts.supplemental_tick_arrays_two = Some(accounts);
             }
+            AccountsType::LockConfig => {
+                if accounts.len() != 1 {
+                    return Err(ErrorCode::RemainingAccountsInvalidSlice.into());
+                }
+
+                if parsed_remaining_accounts.lock_config.is_some() {
+                    return Err(ErrorCode::RemainingAccountsDuplicatedAccountsType.into());
+                }
+                parsed_remaining_accounts.lock_config = accounts.into_iter().next();
+            }
+            AccountsType::PositionDelegate => {
+                if accounts.len() != 1 {
+                    return Err(ErrorCode::RemainingAccountsInvalidSlice.into());
+                }
+
+                if parsed_remaining_accounts.position_delegate.is_some() {
+                    return Err(ErrorCode::RemainingAccountsDuplicatedAccountsType.into());
+                }
+                parsed_remaining_accounts.position_delegate = accounts.into_iter().next();
+            }
+            AccountsType::RewardVaults => {
+                if accounts.len() > NUM_REWARDS {
+                    return Err(ErrorCode::RemainingAccountsInvalidSlice.into());
+                }
+
+                if parsed_remaining_accounts.reward_vaults.is_some() {
+                    return Err(ErrorCode::RemainingAccountsDuplicatedAccountsType.into());
+                }
+                parsed_remaining_accounts.reward_vaults = Some(accounts);
+            }
+            AccountsType::Referral => {
+                // whirlpools_config_extension, referral, referral_token_account
+                if accounts.len() != 3 {
+                    return Err(ErrorCode::RemainingAccountsInvalidSlice.into());
+                }
+
+                if parsed_remaining_accounts.referral.is_some() {
+                    return Err(ErrorCode::RemainingAccountsDuplicatedAccountsType.into());
+                }
+                parsed_remaining_accounts.referral = Some(accounts);
+            }
+            AccountsType::ProtocolFeeRecipients => {
+                // token account A and token account B of each recipient
+                if accounts.len() % 2 != 0 || accounts.len() > MAX_PROTOCOL_FEE_RECIPIENTS * 2 {
+                    return Err(ErrorCode::RemainingAccountsInvalidSlice.into());
+                }
+
+                if parsed_remaining_accounts.protocol_fee_recipients.is_some() {
+                    return Err(ErrorCode::RemainingAccountsDuplicatedAccountsType.into());
+                }
+                parsed_remaining_accounts.protocol_fee_recipients = Some(accounts);
+            }
+            AccountsType::Oracle => {
+                if accounts.len() != 1 {
+                    return Err(ErrorCode::RemainingAccountsInvalidSlice.into());
+                }
+
+                if parsed_remaining_accounts.oracle.is_some() {
+                    return Err(ErrorCode::RemainingAccountsDuplicatedAccountsType.into());
+                }
+                parsed_remaining_accounts.oracle = accounts.into_iter().next();
+            }
         }
     }
 
     Ok(parsed_remaining_accounts)
 }
+
+// Account and InterfaceAccount borrow their AccountInfo for the whole instruction, so the accounts
+// that are loaded into them are taken from the remaining accounts instead of the copies held by
+// ParsedRemainingAccounts. The slices must have been validated by parse_remaining_accounts.
+pub fn get_remaining_accounts_slice<'info>(
+    remaining_accounts: &'info [AccountInfo<'info>],
+    remaining_accounts_info: &Option<RemainingAccountsInfo>,
+    accounts_type: AccountsType,
+) -> Option<&'info [AccountInfo<'info>]> {
+    let remaining_accounts_info = remaining_accounts_info.as_ref()?;
+
+    let mut offset = 0;
+    for slice in remaining_accounts_info.slices.iter() {
+        let length = slice.length as usize;
+        if slice.accounts_type == accounts_type && length > 0 {
+            return remaining_accounts.get(offset..offset + length);
+        }
+        offset += length;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod get_remaining_accounts_slice_tests {
+    use super::*;
+    use crate::util::test_utils::account_info_mock::AccountInfoMock;
+
+    fn slice(accounts_type: AccountsType, length: u8) -> RemainingAccountsSlice {
+        RemainingAccountsSlice {
+            accounts_type,
+            length,
+        }
+    }
+
+    #[test]
+    fn test_get_remaining_accounts_slice() {
+        let mut account_info_mocks: Vec<AccountInfoMock> = (0..4)
+            .map(|_| AccountInfoMock::new(Pubkey::new_unique(), vec![], System::id()))
+            .collect();
+        let remaining_accounts: Vec<AccountInfo> = account_info_mocks
+            .iter_mut()
+            .map(|account_info_mock| account_info_mock.to_account_info(false))
+            .collect();
+        let remaining_accounts_info = Some(RemainingAccountsInfo {
+            slices: vec![
+                slice(AccountsType::TransferHookA, 1),
+                slice(AccountsType::PositionDelegate, 0),
+                slice(AccountsType::RewardVaults, 2),
+                slice(AccountsType::TransferHookB, 1),
+            ],
+        });
+
+        let keys = |accounts_type: AccountsType| {
+            get_remaining_accounts_slice(
+                &remaining_accounts,
+                &remaining_accounts_info,
+                accounts_type,
+            )
+            .map(|accounts| accounts.iter().map(|x| x.key()).collect::<Vec<Pubkey>>())
+        };
+
+        assert_eq!(
+            keys(AccountsType::TransferHookA),
+            Some(vec![remaining_accounts[0].key()])
+        );
+        assert_eq!(
+            keys(AccountsType::RewardVaults),
+            Some(vec![
+                remaining_accounts[1].key(),
+                remaining_accounts[2].key()
+            ])
+        );
+        assert_eq!(
+            keys(AccountsType::TransferHookB),
+            Some(vec![remaining_accounts[3].key()])
+        );
+        // empty and missing slices
+        assert_eq!(keys(AccountsType::PositionDelegate), None);
+        assert_eq!(keys(AccountsType::Referral), None);
+        assert!(get_remaining_accounts_slice(
+            &remaining_accounts,
+            &None,
+            AccountsType::TransferHookA
+        )
+        .is_none());
+    }
+}
@@ -1,3 +1,7 @@
+use crate::constants::{
+    transfer_hook_registry, HOOK_SUBMISSION_DISCRIMINATOR, HOOK_SUBMISSION_PROGRAM_ID_OFFSET,
+    HOOK_SUBMISSION_STATUS_APPROVED, HOOK_SUBMISSION_STATUS_OFFSET,
+};
 use crate::errors::ErrorCode;
 use crate::state::{TokenBadge, Whirlpool};
 use anchor_lang::prelude::*;
@@ -174,7 +178,9 @@ pub fn transfer_from_vault_to_owner_v2<'info>(
     Ok(())
 }
 
-fn get_transfer_hook_program_id(token_mint: &InterfaceAccount<'_, Mint>) -> Result<Option<Pubkey>> {
+pub fn get_transfer_hook_program_id(
+    token_mint: &InterfaceAccount<'_, Mint>,
+) -> Result<Option<Pubkey>> {
     let token_mint_info = token_mint.to_account_info();
     if *token_mint_info.owner == Token::id() {
         return Ok(None);
@@ -314,6 +320,35 @@ pub fn is_token_badge_initialized(
         && token_badge.token_mint == token_mint_key)
 }
 
+// The hook submission must be an approved submission of the transfer hook registry
+// for the transfer hook program of the mint.
+pub fn verify_hook_submission(
+    token_mint: &InterfaceAccount<'_, Mint>,
+    hook_submission: &UncheckedAccount<'_>,
+) -> Result<()> {
+    if *hook_submission.owner != transfer_hook_registry::ID {
+        return Err(ErrorCode::InvalidHookSubmission.into());
+    }
+
+    let hook_submission_data = hook_submission.try_borrow_data()?;
+    if hook_submission_data.len() <= HOOK_SUBMISSION_STATUS_OFFSET
+        || hook_submission_data[..8] != HOOK_SUBMISSION_DISCRIMINATOR
+        || hook_submission_data[HOOK_SUBMISSION_STATUS_OFFSET] != HOOK_SUBMISSION_STATUS_APPROVED
+    {
+        return Err(ErrorCode::InvalidHookSubmission.into());
+    }
+
+    let hook_program_id =
+        get_transfer_hook_program_id(token_mint)?.ok_or(ErrorCode::InvalidHookSubmission)?;
+    let submitted_program_id = &hook_submission_data
+        [HOOK_SUBMISSION_PROGRAM_ID_OFFSET..HOOK_SUBMISSION_PROGRAM_ID_OFFSET + 32];
+    if submitted_program_id != hook_program_id.as_ref() {
+        return Err(ErrorCode::InvalidHookSubmission.into());
+    }
+
+    Ok(())
+}
+
 pub fn verify_supported_token_mint(
     token_mint: &InterfaceAccount<'_, Mint>,
     whirlpools_config_key: Pubkey,
@@ -0,0 +1,289 @@
+use clap::Subcommand;
+use orca_whirlpools::set_reward_emissions_instructions;
+use orca_whirlpools_client::{
+    get_fee_tier_address, get_oracle_address, get_token_badge_address,
+    get_token_badge_history_address, get_whirlpools_config_extension_address,
+    DeleteTokenBadgeWithHistory, DeleteTokenBadgeWithHistoryInstructionArgs,
+    InitializeTokenBadgeHistory, InitializeTokenBadgeWithHistory,
+    InitializeTokenBadgeWithHistoryInstructionArgs, SetConfigExtensionAuthority, SetDefaultFeeRate,
+    SetDefaultFeeRateInstructionArgs, SetFeeRate, SetFeeRateInstructionArgs, SetProtocolFeeRate,
+    SetProtocolFeeRateInstructionArgs, SetTokenBadgeAttributes,
+    SetTokenBadgeAttributesInstructionArgs, SetTokenBadgeAuthority, TokenBadgeAttributes,
+};
+use serde_json::{json, Value};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, system_program};
+use std::error::Error;
+
+use crate::context::Context;
+
+/// Administrative commands of the whirlpools config. The keypair has to be the authority the
+/// instruction requires, e.g. the fee authority for fee rate changes.
+#[derive(Debug, Subcommand)]
+pub enum AdminCommand {
+    /// Set the fee rate of a pool, in hundredths of a basis point.
+    SetFeeRate {
+        #[arg(long)]
+        pool: Pubkey,
+        #[arg(long)]
+        fee_rate: u16,
+    },
+    /// Set the default fee rate of a fee tier, in hundredths of a basis point.
+    SetDefaultFeeRate {
+        /// The index of the fee tier, which is the tick spacing for regular fee tiers.
+        #[arg(long)]
+        fee_tier_index: u16,
+        #[arg(long)]
+        default_fee_rate: u16,
+    },
+    /// Set the protocol fee rate of a pool, in basis points of the trade fee.
+    SetProtocolFeeRate {
+        #[arg(long)]
+        pool: Pubkey,
+        #[arg(long)]
+        protocol_fee_rate: u16,
+    },
+    /// Set the emissions of a pool reward.
+    SetRewardEmissions {
+        #[arg(long)]
+        pool: Pubkey,
+        #[arg(long)]
+        reward_index: u8,
+        /// The emissions per second in native token units.
+        #[arg(long)]
+        emissions_per_second: f64,
+    },
+    /// Create the token badge of a mint.
+    InitializeTokenBadge {
+        #[arg(long)]
+        token_mint: Pubkey,
+        /// Whether transfers of the mint require a memo.
+        #[arg(long)]
+        requires_memo: bool,
+        /// The maximum transfer fee of the mint, in basis points.
+        #[arg(long, default_value_t = 0)]
+        max_transfer_fee_bps: u16,
+        /// The approved hook submission in the transfer hook registry, for mints with a hook.
+        #[arg(long)]
+        hook_submission: Option<Pubkey>,
+    },
+    /// Update the attributes of the token badge of a mint.
+    SetTokenBadgeAttributes {
+        #[arg(long)]
+        token_mint: Pubkey,
+        /// Whether transfers of the mint require a memo.
+        #[arg(long)]
+        requires_memo: bool,
+        /// The maximum transfer fee of the mint, in basis points.
+        #[arg(long, default_value_t = 0)]
+        max_transfer_fee_bps: u16,
+    },
+    /// Delete the token badge of a mint and reclaim its rent. The history of the badge is kept.
+    DeleteTokenBadge {
+        #[arg(long)]
+        token_mint: Pubkey,
+        /// The reason for the deletion, recorded in the token badge history.
+        #[arg(long)]
+        reason: String,
+        /// The account receiving the rent. Defaults to the keypair.
+        #[arg(long)]
+        receiver: Option<Pubkey>,
+    },
+    /// Transfer the config extension authority.
+    SetConfigExtensionAuthority {
+        #[arg(long)]
+        new_authority: Pubkey,
+    },
+    /// Set the authority that manages token badges.
+    SetTokenBadgeAuthority {
+        #[arg(long)]
+        new_authority: Pubkey,
+    },
+}
+
+pub async fn run_admin_command(
+    ctx: &Context,
+    command: AdminCommand,
+) -> Result<Value, Box<dyn Error>> {
+    let wallet = ctx.wallet()?;
+    let whirlpools_config = ctx.whirlpools_config;
+    let whirlpools_config_extension =
+        get_whirlpools_config_extension_address(&whirlpools_config)?.0;
+
+    let instruction: Instruction = match command {
+        AdminCommand::SetFeeRate { pool, fee_rate } => SetFeeRate {
+            whirlpools_config,
+            whirlpool: pool,
+            fee_authority: wallet,
+            oracle: Some(get_oracle_address(&pool)?.0),
+        }
+        .instruction(SetFeeRateInstructionArgs { fee_rate }),
+        AdminCommand::SetDefaultFeeRate {
+            fee_tier_index,
+            default_fee_rate,
+        } => SetDefaultFeeRate {
+            whirlpools_config,
+            fee_tier: get_fee_tier_address(&whirlpools_config, fee_tier_index)?.0,
+            fee_authority: wallet,
+        }
+        .instruction(SetDefaultFeeRateInstructionArgs { default_fee_rate }),
+        AdminCommand::SetProtocolFeeRate {
+            pool,
+            protocol_fee_rate,
+        } => SetProtocolFeeRate {
+            whirlpools_config,
+            whirlpool: pool,
+            fee_authority: wallet,
+        }
+        .instruction(SetProtocolFeeRateInstructionArgs { protocol_fee_rate }),
+        AdminCommand::SetRewardEmissions {
+            pool,
+            reward_index,
+            emissions_per_second,
+        } => {
+            if emissions_per_second < 0.0 {
+                return Err("Emissions per second must not be negative.".into());
+            }
+            let emissions_per_second_x64 = (emissions_per_second * 2f64.powi(64)) as u128;
+            let result = set_reward_emissions_instructions(
+                &ctx.rpc,
+                pool,
+                reward_index,
+                emissions_per_second_x64,
+                Some(wallet),
+            )
+            .await?;
+            let mut value = ctx
+                .execute(result.instructions, &result.additional_signers)
+                .await?;
+            value["emissionsPerSecondX64"] = json!(emissions_per_second_x64);
+            return Ok(value);
+        }
+        AdminCommand::InitializeTokenBadge {
+            token_mint,
+            requires_memo,
+            max_transfer_fee_bps,
+            hook_submission,
+        } => {
+            let (token_badge_history, mut instructions) =
+                token_badge_history_instructions(ctx, wallet, token_mint).await?;
+            instructions.push(
+                InitializeTokenBadgeWithHistory {
+                    whirlpools_config,
+                    whirlpools_config_extension,
+                    token_badge_authority: wallet,
+                    token_mint,
+                    token_badge: get_token_badge_address(&whirlpools_config, &token_mint)?.0,
+                    token_badge_history,
+                    hook_submission,
+                    funder: wallet,
+                    system_program: system_program::id(),
+                }
+                .instruction(InitializeTokenBadgeWithHistoryInstructionArgs {
+                    attributes: TokenBadgeAttributes {
+                        requires_memo,
+                        max_transfer_fee_bps,
+                    },
+                }),
+            );
+            return ctx.execute(instructions, &[]).await;
+        }
+        AdminCommand::SetTokenBadgeAttributes {
+            token_mint,
+            requires_memo,
+            max_transfer_fee_bps,
+        } => {
+            let (token_badge_history, mut instructions) =
+                token_badge_history_instructions(ctx, wallet, token_mint).await?;
+            instructions.push(
+                SetTokenBadgeAttributes {
+                    whirlpools_config,
+                    whirlpools_config_extension,
+                    token_badge_authority: wallet,
+                    token_mint,
+                    token_badge: get_token_badge_address(&whirlpools_config, &token_mint)?.0,
+                    token_badge_history,
+                }
+                .instruction(SetTokenBadgeAttributesInstructionArgs {
+                    attributes: TokenBadgeAttributes {
+                        requires_memo,
+                        max_transfer_fee_bps,
+                    },
+                }),
+            );
+            return ctx.execute(instructions, &[]).await;
+        }
+        AdminCommand::DeleteTokenBadge {
+            token_mint,
+            reason,
+            receiver,
+        } => {
+            let (token_badge_history, mut instructions) =
+                token_badge_history_instructions(ctx, wallet, token_mint).await?;
+            instructions.push(
+                DeleteTokenBadgeWithHistory {
+                    whirlpools_config,
+                    whirlpools_config_extension,
+                    token_badge_authority: wallet,
+                    token_mint,
+                    token_badge: get_token_badge_address(&whirlpools_config, &token_mint)?.0,
+                    token_badge_history,
+                    receiver: receiver.unwrap_or(wallet),
+                }
+                .instruction(DeleteTokenBadgeWithHistoryInstructionArgs { reason }),
+            );
+            return ctx.execute(instructions, &[]).await;
+        }
+        AdminCommand::SetConfigExtensionAuthority { new_authority } => {
+            SetConfigExtensionAuthority {
+                whirlpools_config,
+                whirlpools_config_extension,
+                config_extension_authority: wallet,
+                new_config_extension_authority: new_authority,
+            }
+            .instruction()
+        }
+        AdminCommand::SetTokenBadgeAuthority { new_authority } => SetTokenBadgeAuthority {
+            whirlpools_config,
+            whirlpools_config_extension,
+            config_extension_authority: wallet,
+            new_token_badge_authority: new_authority,
+        }
+        .instruction(),
+    };
+
+    ctx.execute(vec![instruction], &[]).await
+}
+
+/// Returns the token badge history of a mint, with the instruction to create it if it does not
+/// exist yet. Badges initialized before histories were recorded get one on their next change.
+async fn token_badge_history_instructions(
+    ctx: &Context,
+    wallet: Pubkey,
+    token_mint: Pubkey,
+) -> Result<(Pubkey, Vec<Instruction>), Box<dyn Error>> {
+    let whirlpools_config = ctx.whirlpools_config;
+    let token_badge_history = get_token_badge_history_address(&whirlpools_config, &token_mint)?.0;
+    let account = ctx
+        .rpc
+        .get_multiple_accounts(&[token_badge_history])
+        .await?
+        .into_iter()
+        .next()
+        .flatten();
+    if account.is_some() {
+        return Ok((token_badge_history, vec![]));
+    }
+
+    let instruction = InitializeTokenBadgeHistory {
+        whirlpools_config,
+        whirlpools_config_extension: get_whirlpools_config_extension_address(&whirlpools_config)?.0,
+        token_badge_authority: wallet,
+        token_mint,
+        token_badge: get_token_badge_address(&whirlpools_config, &token_mint)?.0,
+        token_badge_history,
+        funder: wallet,
+        system_program: system_program::id(),
+    }
+    .instruction();
+    Ok((token_badge_history, vec![instruction]))
+}
@@ -0,0 +1,33 @@
+pub mod admin;
+pub mod oracle;
+pub mod pool;
+pub mod position;
+pub mod quote;
+pub mod token_badge;
+
+use serde::Serialize;
+use serde_json::Value;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use spl_token_2022::{extension::StateWithExtensions, state::Mint};
+use std::error::Error;
+
+/// Converts a decoded account to a JSON value with its address.
+pub(crate) fn account_to_json<T: Serialize>(
+    address: Pubkey,
+    data: &T,
+) -> Result<Value, Box<dyn Error>> {
+    let mut value = serde_json::json!({ "address": address.to_string() });
+    value["data"] = serde_json::to_value(data)?;
+    Ok(value)
+}
+
+/// Fetches a mint and its token program.
+pub(crate) async fn fetch_mint(
+    rpc: &RpcClient,
+    mint_address: &Pubkey,
+) -> Result<(Mint, Pubkey), Box<dyn Error>> {
+    let account = rpc.get_account(mint_address).await?;
+    let mint = StateWithExtensions::<Mint>::unpack(&account.data)?.base;
+    Ok((mint, account.owner))
+}
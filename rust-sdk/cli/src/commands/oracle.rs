@@ -0,0 +1,46 @@
+use clap::Subcommand;
+use orca_whirlpools_client::{get_oracle_address, Oracle};
+use orca_whirlpools_core::VOLATILITY_ACCUMULATOR_SCALE_FACTOR;
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+use std::error::Error;
+
+use super::account_to_json;
+use crate::context::Context;
+
+#[derive(Debug, Subcommand)]
+pub enum OracleCommand {
+    /// Show the oracle of an adaptive fee pool.
+    Show {
+        /// The address of the pool.
+        whirlpool: Pubkey,
+    },
+}
+
+pub async fn run_oracle_command(
+    ctx: &Context,
+    command: OracleCommand,
+) -> Result<Value, Box<dyn Error>> {
+    match command {
+        OracleCommand::Show { whirlpool } => {
+            let oracle_address = get_oracle_address(&whirlpool)?.0;
+            let account = ctx
+                .rpc
+                .get_multiple_accounts(&[oracle_address])
+                .await?
+                .into_iter()
+                .next()
+                .flatten()
+                .ok_or_else(|| format!("Pool {} does not have an oracle.", whirlpool))?;
+            let oracle = Oracle::from_bytes(&account.data)?;
+
+            let mut value = account_to_json(oracle_address, &oracle)?;
+            value["volatilityTicks"] = json!(
+                oracle.adaptive_fee_variables.volatility_accumulator as f64
+                    / VOLATILITY_ACCUMULATOR_SCALE_FACTOR as f64
+                    * oracle.adaptive_fee_constants.tick_group_size as f64
+            );
+            Ok(value)
+        }
+    }
+}
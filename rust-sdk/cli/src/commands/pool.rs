@@ -0,0 +1,238 @@
+use clap::{Args, Subcommand};
+use orca_whirlpools::{
+    create_adaptive_fee_pool_instructions, create_concentrated_liquidity_pool_instructions,
+    create_splash_pool_instructions, fetch_whirlpools_by_token_pair, PoolInfo,
+};
+use orca_whirlpools_client::{
+    fetch_pool_snapshot, get_oracle_address, PoolSnapshotOptions, SnapshotFormat, Whirlpool,
+};
+use orca_whirlpools_core::sqrt_price_to_price;
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+use std::error::Error;
+use std::path::PathBuf;
+
+use super::{account_to_json, fetch_mint};
+use crate::context::Context;
+
+#[derive(Debug, Subcommand)]
+pub enum PoolCommand {
+    /// Show the state of a pool.
+    Show {
+        /// The address of the pool.
+        address: Pubkey,
+    },
+    /// List all pools of a token pair in the whirlpools config.
+    List {
+        #[arg(long)]
+        token_a: Pubkey,
+        #[arg(long)]
+        token_b: Pubkey,
+    },
+    /// Save the state of a pool to a file to reproduce issues locally.
+    Snapshot {
+        /// The address of the pool.
+        address: Pubkey,
+        /// The file to write the snapshot to.
+        #[arg(long)]
+        out: PathBuf,
+        /// Write the compact binary format instead of JSON.
+        #[arg(long)]
+        binary: bool,
+        /// Also include every position of the pool.
+        #[arg(long)]
+        include_positions: bool,
+    },
+    /// Create a new pool.
+    #[command(subcommand)]
+    Create(CreatePoolCommand),
+}
+
+#[derive(Debug, Subcommand)]
+pub enum CreatePoolCommand {
+    /// Create a splash pool.
+    Splash(CreatePoolArgs),
+    /// Create a concentrated liquidity pool.
+    Concentrated {
+        #[command(flatten)]
+        pool: CreatePoolArgs,
+        /// The tick spacing of the fee tier.
+        #[arg(long)]
+        tick_spacing: u16,
+    },
+    /// Create a pool with an adaptive fee tier.
+    AdaptiveFee {
+        #[command(flatten)]
+        pool: CreatePoolArgs,
+        /// The index of the adaptive fee tier.
+        #[arg(long)]
+        fee_tier_index: u16,
+        /// The timestamp from which trading is enabled, if the fee tier allows delaying it.
+        #[arg(long)]
+        trade_enable_timestamp: Option<u64>,
+    },
+}
+
+#[derive(Debug, Args)]
+pub struct CreatePoolArgs {
+    #[arg(long)]
+    pub token_a: Pubkey,
+    #[arg(long)]
+    pub token_b: Pubkey,
+    /// The initial price of token A in terms of token B. Defaults to 1.0.
+    #[arg(long)]
+    pub price: Option<f64>,
+}
+
+pub async fn run_pool_command(
+    ctx: &Context,
+    command: PoolCommand,
+) -> Result<Value, Box<dyn Error>> {
+    match command {
+        PoolCommand::Show { address } => show_pool(ctx, address).await,
+        PoolCommand::List { token_a, token_b } => {
+            let pools = fetch_whirlpools_by_token_pair(&ctx.rpc, token_a, token_b).await?;
+            Ok(Value::Array(pools.iter().map(pool_info_to_json).collect()))
+        }
+        PoolCommand::Snapshot {
+            address,
+            out,
+            binary,
+            include_positions,
+        } => {
+            let options = PoolSnapshotOptions {
+                include_positions,
+                ..Default::default()
+            };
+            let snapshot = fetch_pool_snapshot(&ctx.rpc, address, options).await?;
+            let format = if binary {
+                SnapshotFormat::Binary
+            } else {
+                SnapshotFormat::Json
+            };
+            snapshot.write_to_file(&out, format)?;
+            Ok(json!({
+                "file": out.display().to_string(),
+                "slot": snapshot.slot,
+                "accounts": snapshot.accounts.len(),
+            }))
+        }
+        PoolCommand::Create(command) => create_pool(ctx, command).await,
+    }
+}
+
+async fn show_pool(ctx: &Context, address: Pubkey) -> Result<Value, Box<dyn Error>> {
+    let account = ctx.rpc.get_account(&address).await?;
+    let whirlpool = Whirlpool::from_bytes(&account.data)?;
+    let (mint_a, _) = fetch_mint(&ctx.rpc, &whirlpool.token_mint_a).await?;
+    let (mint_b, _) = fetch_mint(&ctx.rpc, &whirlpool.token_mint_b).await?;
+    let oracle_address = get_oracle_address(&address)?.0;
+    let has_oracle = ctx
+        .rpc
+        .get_multiple_accounts(&[oracle_address])
+        .await?
+        .first()
+        .is_some_and(|x| x.is_some());
+
+    let mut value = account_to_json(address, &whirlpool)?;
+    value["price"] = json!(sqrt_price_to_price(
+        whirlpool.sqrt_price.into(),
+        mint_a.decimals,
+        mint_b.decimals
+    ));
+    value["oracle"] = if has_oracle {
+        json!(oracle_address.to_string())
+    } else {
+        Value::Null
+    };
+    Ok(value)
+}
+
+fn pool_info_to_json(pool: &PoolInfo) -> Value {
+    match pool {
+        PoolInfo::Initialized(pool) => json!({
+            "address": pool.address.to_string(),
+            "initialized": true,
+            "tickSpacing": pool.data.tick_spacing,
+            "feeRate": pool.data.fee_rate,
+            "protocolFeeRate": pool.data.protocol_fee_rate,
+            "liquidity": pool.data.liquidity,
+            "price": pool.price,
+            "paused": pool.paused,
+        }),
+        PoolInfo::Uninitialized(pool) => json!({
+            "address": pool.address.to_string(),
+            "initialized": false,
+            "tickSpacing": pool.tick_spacing,
+            "feeRate": pool.fee_rate,
+            "protocolFeeRate": pool.protocol_fee_rate,
+        }),
+    }
+}
+
+async fn create_pool(ctx: &Context, command: CreatePoolCommand) -> Result<Value, Box<dyn Error>> {
+    let funder = Some(ctx.wallet()?);
+    let (pool_address, initialization_cost, instructions, additional_signers) = match command {
+        CreatePoolCommand::Splash(pool) => {
+            let result = create_splash_pool_instructions(
+                &ctx.rpc,
+                pool.token_a,
+                pool.token_b,
+                pool.price,
+                funder,
+            )
+            .await?;
+            (
+                result.pool_address,
+                result.initialization_cost,
+                result.instructions,
+                result.additional_signers,
+            )
+        }
+        CreatePoolCommand::Concentrated { pool, tick_spacing } => {
+            let result = create_concentrated_liquidity_pool_instructions(
+                &ctx.rpc,
+                pool.token_a,
+                pool.token_b,
+                tick_spacing,
+                pool.price,
+                funder,
+            )
+            .await?;
+            (
+                result.pool_address,
+                result.initialization_cost,
+                result.instructions,
+                result.additional_signers,
+            )
+        }
+        CreatePoolCommand::AdaptiveFee {
+            pool,
+            fee_tier_index,
+            trade_enable_timestamp,
+        } => {
+            let result = create_adaptive_fee_pool_instructions(
+                &ctx.rpc,
+                pool.token_a,
+                pool.token_b,
+                fee_tier_index,
+                pool.price,
+                trade_enable_timestamp,
+                None,
+                funder,
+            )
+            .await?;
+            (
+                result.pool_address,
+                result.initialization_cost,
+                result.instructions,
+                result.additional_signers,
+            )
+        }
+    };
+
+    let mut value = ctx.execute(instructions, &additional_signers).await?;
+    value["poolAddress"] = json!(pool_address.to_string());
+    value["initializationCost"] = json!(initialization_cost);
+    Ok(value)
+}
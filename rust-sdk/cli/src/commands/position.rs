@@ -0,0 +1,386 @@
+use clap::{ArgGroup, Args, Subcommand};
+use orca_whirlpools::{
+    close_position_instructions, fetch_positions_for_owner, harvest_position_instructions,
+    open_full_range_position_instructions, open_position_instructions, IncreaseLiquidityParam,
+    PositionOrBundle,
+};
+use orca_whirlpools_client::{
+    get_lock_config_address, get_position_address, LockConfig, LockPosition,
+    LockPositionInstructionArgs, LockType, Position, ResetPositionRange,
+    ResetPositionRangeInstructionArgs, Whirlpool,
+};
+use orca_whirlpools_core::{
+    get_initializable_tick_index, price_to_tick_index, sqrt_price_to_price, tick_index_to_price,
+};
+use serde_json::{json, Value};
+use solana_sdk::{pubkey::Pubkey, system_program};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use std::error::Error;
+
+use super::quote::{
+    collect_fees_quote_to_json, decrease_liquidity_quote_to_json, increase_liquidity_quote_to_json,
+};
+use super::{account_to_json, fetch_mint};
+use crate::context::Context;
+
+#[derive(Debug, Subcommand)]
+pub enum PositionCommand {
+    /// Show the state of a position and its lock.
+    Show {
+        /// The mint address of the position NFT.
+        position_mint: Pubkey,
+    },
+    /// List all positions and position bundles of an owner.
+    List {
+        /// The owner of the positions. Defaults to the keypair.
+        #[arg(long)]
+        owner: Option<Pubkey>,
+    },
+    /// Open a position and deposit liquidity.
+    Open(OpenPositionArgs),
+    /// Withdraw all liquidity, collect fees and rewards, and close a position.
+    Close { position_mint: Pubkey },
+    /// Collect the fees and rewards of a position.
+    Harvest { position_mint: Pubkey },
+    /// Lock the liquidity of a Token-2022 position.
+    #[command(group(ArgGroup::new("lock").required(true).args(["permanent", "until"])))]
+    Lock {
+        position_mint: Pubkey,
+        /// Lock the position forever.
+        #[arg(long)]
+        permanent: bool,
+        /// Lock the position until this unix timestamp.
+        #[arg(long)]
+        until: Option<u64>,
+        /// Unlock the liquidity linearly until `--until` instead of all at once.
+        #[arg(long, requires = "until")]
+        vesting: bool,
+    },
+    /// Move an empty position to a new price range.
+    Reset {
+        position_mint: Pubkey,
+        #[arg(long)]
+        lower_price: f64,
+        #[arg(long)]
+        upper_price: f64,
+    },
+}
+
+#[derive(Debug, Args)]
+#[command(group(ArgGroup::new("range").required(true).args(["lower_price", "full_range"])))]
+pub struct OpenPositionArgs {
+    /// The address of the pool.
+    #[arg(long)]
+    pub pool: Pubkey,
+    #[arg(long, requires = "upper_price")]
+    pub lower_price: Option<f64>,
+    #[arg(long, requires = "lower_price")]
+    pub upper_price: Option<f64>,
+    /// Open a position over the full price range of the pool.
+    #[arg(long)]
+    pub full_range: bool,
+    #[command(flatten)]
+    pub liquidity: LiquidityArgs,
+}
+
+/// The amount of liquidity to deposit, specified by exactly one of the token amounts or the
+/// liquidity itself.
+#[derive(Debug, Args)]
+#[group(required = true, multiple = false)]
+pub struct LiquidityArgs {
+    /// The amount of token A to deposit.
+    #[arg(long)]
+    pub token_a: Option<u64>,
+    /// The amount of token B to deposit.
+    #[arg(long)]
+    pub token_b: Option<u64>,
+    /// The amount of liquidity to deposit.
+    #[arg(long)]
+    pub liquidity: Option<u128>,
+}
+
+impl From<&LiquidityArgs> for IncreaseLiquidityParam {
+    fn from(args: &LiquidityArgs) -> Self {
+        match (args.token_a, args.token_b, args.liquidity) {
+            (Some(amount), _, _) => IncreaseLiquidityParam::TokenA(amount),
+            (_, Some(amount), _) => IncreaseLiquidityParam::TokenB(amount),
+            (_, _, liquidity) => IncreaseLiquidityParam::Liquidity(liquidity.unwrap_or_default()),
+        }
+    }
+}
+
+pub async fn run_position_command(
+    ctx: &Context,
+    command: PositionCommand,
+) -> Result<Value, Box<dyn Error>> {
+    match command {
+        PositionCommand::Show { position_mint } => show_position(ctx, position_mint).await,
+        PositionCommand::List { owner } => {
+            let owner = match owner {
+                Some(owner) => owner,
+                None => ctx.wallet()?,
+            };
+            let positions = fetch_positions_for_owner(&ctx.rpc, owner).await?;
+            Ok(Value::Array(
+                positions
+                    .iter()
+                    .flat_map(position_or_bundle_to_json)
+                    .collect(),
+            ))
+        }
+        PositionCommand::Open(args) => open_position(ctx, args).await,
+        PositionCommand::Close { position_mint } => {
+            let result =
+                close_position_instructions(&ctx.rpc, position_mint, None, Some(ctx.wallet()?))
+                    .await?;
+            let mut value = ctx
+                .execute(result.instructions, &result.additional_signers)
+                .await?;
+            value["quote"] = decrease_liquidity_quote_to_json(&result.quote);
+            value["fees"] = collect_fees_quote_to_json(&result.fees_quote);
+            value["rewards"] = json!(result
+                .rewards_quote
+                .rewards
+                .iter()
+                .map(|x| x.rewards_owed)
+                .collect::<Vec<_>>());
+            Ok(value)
+        }
+        PositionCommand::Harvest { position_mint } => {
+            let result =
+                harvest_position_instructions(&ctx.rpc, position_mint, Some(ctx.wallet()?)).await?;
+            let mut value = ctx
+                .execute(result.instructions, &result.additional_signers)
+                .await?;
+            value["fees"] = collect_fees_quote_to_json(&result.fees_quote);
+            value["rewards"] = json!(result
+                .rewards_quote
+                .rewards
+                .iter()
+                .map(|x| x.rewards_owed)
+                .collect::<Vec<_>>());
+            Ok(value)
+        }
+        PositionCommand::Lock {
+            position_mint,
+            permanent,
+            until,
+            vesting,
+        } => {
+            let lock_type = match (permanent, until) {
+                (true, _) => LockType::Permanent,
+                (false, Some(unlock_timestamp)) if vesting => {
+                    LockType::Vesting { unlock_timestamp }
+                }
+                (false, Some(unlock_timestamp)) => LockType::TimeLocked { unlock_timestamp },
+                (false, None) => return Err("Either --permanent or --until is required.".into()),
+            };
+            lock_position(ctx, position_mint, lock_type).await
+        }
+        PositionCommand::Reset {
+            position_mint,
+            lower_price,
+            upper_price,
+        } => reset_position(ctx, position_mint, lower_price, upper_price).await,
+    }
+}
+
+async fn fetch_position(
+    ctx: &Context,
+    position_mint: Pubkey,
+) -> Result<(Pubkey, Position, Whirlpool), Box<dyn Error>> {
+    let position_address = get_position_address(&position_mint)?.0;
+    let position_account = ctx.rpc.get_account(&position_address).await?;
+    let position = Position::from_bytes(&position_account.data)?;
+    let whirlpool_account = ctx.rpc.get_account(&position.whirlpool).await?;
+    let whirlpool = Whirlpool::from_bytes(&whirlpool_account.data)?;
+    Ok((position_address, position, whirlpool))
+}
+
+async fn show_position(ctx: &Context, position_mint: Pubkey) -> Result<Value, Box<dyn Error>> {
+    let (position_address, position, whirlpool) = fetch_position(ctx, position_mint).await?;
+    let (mint_a, _) = fetch_mint(&ctx.rpc, &whirlpool.token_mint_a).await?;
+    let (mint_b, _) = fetch_mint(&ctx.rpc, &whirlpool.token_mint_b).await?;
+    let lock_config_address = get_lock_config_address(&position_address)?.0;
+    let lock_config = ctx
+        .rpc
+        .get_multiple_accounts(&[lock_config_address])
+        .await?
+        .into_iter()
+        .next()
+        .flatten()
+        .map(|account| LockConfig::from_bytes(&account.data))
+        .transpose()?;
+
+    let mut value = account_to_json(position_address, &position)?;
+    value["price"] = json!(sqrt_price_to_price(
+        whirlpool.sqrt_price.into(),
+        mint_a.decimals,
+        mint_b.decimals
+    ));
+    value["lowerPrice"] = json!(tick_index_to_price(
+        position.tick_lower_index,
+        mint_a.decimals,
+        mint_b.decimals
+    ));
+    value["upperPrice"] = json!(tick_index_to_price(
+        position.tick_upper_index,
+        mint_a.decimals,
+        mint_b.decimals
+    ));
+    value["inRange"] = json!(
+        whirlpool.tick_current_index >= position.tick_lower_index
+            && whirlpool.tick_current_index < position.tick_upper_index
+    );
+    value["lockConfig"] = match lock_config {
+        Some(lock_config) => serde_json::to_value(lock_config)?,
+        None => Value::Null,
+    };
+    Ok(value)
+}
+
+fn position_or_bundle_to_json(position: &PositionOrBundle) -> Vec<Value> {
+    let to_json = |address: &Pubkey, data: &Position, bundle: Option<&Pubkey>| {
+        json!({
+            "address": address.to_string(),
+            "positionMint": data.position_mint.to_string(),
+            "whirlpool": data.whirlpool.to_string(),
+            "tickLowerIndex": data.tick_lower_index,
+            "tickUpperIndex": data.tick_upper_index,
+            "liquidity": data.liquidity,
+            "positionBundle": bundle.map(|x| x.to_string()),
+        })
+    };
+    match position {
+        PositionOrBundle::Position(position) => {
+            vec![to_json(&position.address, &position.data, None)]
+        }
+        PositionOrBundle::PositionBundle(bundle) => bundle
+            .positions
+            .iter()
+            .map(|position| to_json(&position.address, &position.data, Some(&bundle.address)))
+            .collect(),
+    }
+}
+
+async fn open_position(ctx: &Context, args: OpenPositionArgs) -> Result<Value, Box<dyn Error>> {
+    let param = IncreaseLiquidityParam::from(&args.liquidity);
+    let funder = Some(ctx.wallet()?);
+    let result = match (args.full_range, args.lower_price, args.upper_price) {
+        (true, _, _) => {
+            open_full_range_position_instructions(&ctx.rpc, args.pool, param, None, funder).await?
+        }
+        (false, Some(lower_price), Some(upper_price)) => {
+            open_position_instructions(
+                &ctx.rpc,
+                args.pool,
+                lower_price,
+                upper_price,
+                param,
+                None,
+                funder,
+            )
+            .await?
+        }
+        _ => {
+            return Err(
+                "Either --full-range or --lower-price and --upper-price are required.".into(),
+            )
+        }
+    };
+
+    let mut value = ctx
+        .execute(result.instructions, &result.additional_signers)
+        .await?;
+    value["positionMint"] = json!(result.position_mint.to_string());
+    value["initializationCost"] = json!(result.initialization_cost);
+    value["quote"] = increase_liquidity_quote_to_json(&result.quote);
+    Ok(value)
+}
+
+/// The token account holding the position NFT of the keypair.
+async fn position_token_account(
+    ctx: &Context,
+    position_mint: Pubkey,
+) -> Result<Pubkey, Box<dyn Error>> {
+    let (_, token_program) = fetch_mint(&ctx.rpc, &position_mint).await?;
+    Ok(get_associated_token_address_with_program_id(
+        &ctx.wallet()?,
+        &position_mint,
+        &token_program,
+    ))
+}
+
+async fn lock_position(
+    ctx: &Context,
+    position_mint: Pubkey,
+    lock_type: LockType,
+) -> Result<Value, Box<dyn Error>> {
+    let (position_address, position, _) = fetch_position(ctx, position_mint).await?;
+    let wallet = ctx.wallet()?;
+    let lock_config = get_lock_config_address(&position_address)?.0;
+
+    let instruction = LockPosition {
+        funder: wallet,
+        position_authority: wallet,
+        position: position_address,
+        position_mint,
+        position_token_account: position_token_account(ctx, position_mint).await?,
+        lock_config,
+        whirlpool: position.whirlpool,
+        token2022_program: spl_token_2022::ID,
+        system_program: system_program::id(),
+    }
+    .instruction(LockPositionInstructionArgs { lock_type });
+
+    let mut value = ctx.execute(vec![instruction], &[]).await?;
+    value["lockConfig"] = json!(lock_config.to_string());
+    Ok(value)
+}
+
+async fn reset_position(
+    ctx: &Context,
+    position_mint: Pubkey,
+    lower_price: f64,
+    upper_price: f64,
+) -> Result<Value, Box<dyn Error>> {
+    if lower_price <= 0.0 || upper_price <= 0.0 {
+        return Err("Prices must be greater than 0.".into());
+    }
+    let (position_address, position, whirlpool) = fetch_position(ctx, position_mint).await?;
+    let (mint_a, _) = fetch_mint(&ctx.rpc, &whirlpool.token_mint_a).await?;
+    let (mint_b, _) = fetch_mint(&ctx.rpc, &whirlpool.token_mint_b).await?;
+    let wallet = ctx.wallet()?;
+
+    let tick_lower_index = get_initializable_tick_index(
+        price_to_tick_index(lower_price, mint_a.decimals, mint_b.decimals),
+        whirlpool.tick_spacing,
+        Some(false),
+    );
+    let tick_upper_index = get_initializable_tick_index(
+        price_to_tick_index(upper_price, mint_a.decimals, mint_b.decimals),
+        whirlpool.tick_spacing,
+        Some(true),
+    );
+    if tick_lower_index >= tick_upper_index {
+        return Err("Lower price must be less than upper price.".into());
+    }
+
+    let instruction = ResetPositionRange {
+        funder: wallet,
+        position_authority: wallet,
+        whirlpool: position.whirlpool,
+        position: position_address,
+        position_token_account: position_token_account(ctx, position_mint).await?,
+        system_program: system_program::id(),
+    }
+    .instruction(ResetPositionRangeInstructionArgs {
+        new_tick_lower_index: tick_lower_index,
+        new_tick_upper_index: tick_upper_index,
+    });
+
+    let mut value = ctx.execute(vec![instruction], &[]).await?;
+    value["tickLowerIndex"] = json!(tick_lower_index);
+    value["tickUpperIndex"] = json!(tick_upper_index);
+    Ok(value)
+}
@@ -0,0 +1,187 @@
+use clap::{ArgGroup, Args, Subcommand};
+use orca_whirlpools::{
+    decrease_liquidity_instructions, open_full_range_position_instructions,
+    open_position_instructions, swap_instructions, DecreaseLiquidityParam, IncreaseLiquidityParam,
+    SwapQuote, SwapType,
+};
+use orca_whirlpools_core::{CollectFeesQuote, DecreaseLiquidityQuote, IncreaseLiquidityQuote};
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+use std::error::Error;
+
+use super::position::LiquidityArgs;
+use crate::context::Context;
+
+#[derive(Debug, Subcommand)]
+pub enum QuoteCommand {
+    /// Quote a swap.
+    Swap {
+        /// The address of the pool.
+        #[arg(long)]
+        pool: Pubkey,
+        /// The amount of the specified mint to swap.
+        #[arg(long)]
+        amount: u64,
+        /// The mint of the specified amount.
+        #[arg(long)]
+        mint: Pubkey,
+        /// Quote the input for an exact output amount instead of the output for an exact input.
+        #[arg(long)]
+        exact_out: bool,
+    },
+    /// Quote the tokens needed to open a position.
+    IncreaseLiquidity(IncreaseLiquidityQuoteArgs),
+    /// Quote the tokens received when withdrawing liquidity from a position.
+    #[command(group(ArgGroup::new("amount").required(true).args(["token_a", "token_b", "liquidity"])))]
+    DecreaseLiquidity {
+        /// The mint address of the position NFT.
+        position_mint: Pubkey,
+        #[arg(long)]
+        token_a: Option<u64>,
+        #[arg(long)]
+        token_b: Option<u64>,
+        #[arg(long)]
+        liquidity: Option<u128>,
+    },
+}
+
+#[derive(Debug, Args)]
+#[command(group(ArgGroup::new("range").required(true).args(["lower_price", "full_range"])))]
+pub struct IncreaseLiquidityQuoteArgs {
+    /// The address of the pool.
+    #[arg(long)]
+    pub pool: Pubkey,
+    #[arg(long, requires = "upper_price")]
+    pub lower_price: Option<f64>,
+    #[arg(long, requires = "lower_price")]
+    pub upper_price: Option<f64>,
+    /// Quote a position over the full price range of the pool.
+    #[arg(long)]
+    pub full_range: bool,
+    #[command(flatten)]
+    pub liquidity: LiquidityArgs,
+}
+
+pub async fn run_quote_command(
+    ctx: &Context,
+    command: QuoteCommand,
+) -> Result<Value, Box<dyn Error>> {
+    let authority = Some(ctx.quote_authority());
+    match command {
+        QuoteCommand::Swap {
+            pool,
+            amount,
+            mint,
+            exact_out,
+        } => {
+            let swap_type = if exact_out {
+                SwapType::ExactOut
+            } else {
+                SwapType::ExactIn
+            };
+            let result = swap_instructions(
+                &ctx.rpc, pool, amount, mint, swap_type, None, None, authority,
+            )
+            .await?;
+            Ok(swap_quote_to_json(&result.quote))
+        }
+        QuoteCommand::IncreaseLiquidity(args) => {
+            let param = IncreaseLiquidityParam::from(&args.liquidity);
+            let result = match (args.full_range, args.lower_price, args.upper_price) {
+                (true, _, _) => {
+                    open_full_range_position_instructions(
+                        &ctx.rpc, args.pool, param, None, authority,
+                    )
+                    .await?
+                }
+                (false, Some(lower_price), Some(upper_price)) => {
+                    open_position_instructions(
+                        &ctx.rpc,
+                        args.pool,
+                        lower_price,
+                        upper_price,
+                        param,
+                        None,
+                        authority,
+                    )
+                    .await?
+                }
+                _ => {
+                    return Err(
+                        "Either --full-range or --lower-price and --upper-price are required."
+                            .into(),
+                    )
+                }
+            };
+            let mut value = increase_liquidity_quote_to_json(&result.quote);
+            value["initializationCost"] = json!(result.initialization_cost);
+            Ok(value)
+        }
+        QuoteCommand::DecreaseLiquidity {
+            position_mint,
+            token_a,
+            token_b,
+            liquidity,
+        } => {
+            let param = match (token_a, token_b, liquidity) {
+                (Some(amount), _, _) => DecreaseLiquidityParam::TokenA(amount),
+                (_, Some(amount), _) => DecreaseLiquidityParam::TokenB(amount),
+                (_, _, liquidity) => {
+                    DecreaseLiquidityParam::Liquidity(liquidity.unwrap_or_default())
+                }
+            };
+            let result =
+                decrease_liquidity_instructions(&ctx.rpc, position_mint, param, None, authority)
+                    .await?;
+            Ok(decrease_liquidity_quote_to_json(&result.quote))
+        }
+    }
+}
+
+fn swap_quote_to_json(quote: &SwapQuote) -> Value {
+    match quote {
+        SwapQuote::ExactIn(quote) => json!({
+            "tokenIn": quote.token_in,
+            "tokenEstOut": quote.token_est_out,
+            "tokenMinOut": quote.token_min_out,
+            "tradeFee": quote.trade_fee,
+            "tradeFeeRateMin": quote.trade_fee_rate_min,
+            "tradeFeeRateMax": quote.trade_fee_rate_max,
+        }),
+        SwapQuote::ExactOut(quote) => json!({
+            "tokenOut": quote.token_out,
+            "tokenEstIn": quote.token_est_in,
+            "tokenMaxIn": quote.token_max_in,
+            "tradeFee": quote.trade_fee,
+            "tradeFeeRateMin": quote.trade_fee_rate_min,
+            "tradeFeeRateMax": quote.trade_fee_rate_max,
+        }),
+    }
+}
+
+pub(crate) fn increase_liquidity_quote_to_json(quote: &IncreaseLiquidityQuote) -> Value {
+    json!({
+        "liquidityDelta": quote.liquidity_delta,
+        "tokenEstA": quote.token_est_a,
+        "tokenEstB": quote.token_est_b,
+        "tokenMaxA": quote.token_max_a,
+        "tokenMaxB": quote.token_max_b,
+    })
+}
+
+pub(crate) fn decrease_liquidity_quote_to_json(quote: &DecreaseLiquidityQuote) -> Value {
+    json!({
+        "liquidityDelta": quote.liquidity_delta,
+        "tokenEstA": quote.token_est_a,
+        "tokenEstB": quote.token_est_b,
+        "tokenMinA": quote.token_min_a,
+        "tokenMinB": quote.token_min_b,
+    })
+}
+
+pub(crate) fn collect_fees_quote_to_json(quote: &CollectFeesQuote) -> Value {
+    json!({
+        "feeOwedA": quote.fee_owed_a,
+        "feeOwedB": quote.fee_owed_b,
+    })
+}
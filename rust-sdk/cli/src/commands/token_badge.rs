@@ -0,0 +1,101 @@
+use clap::Subcommand;
+use orca_whirlpools_client::{
+    check_token_mint_compatibility, get_token_badge_address, is_token_badge_initialized,
+    TokenBadge, TokenMintProperty,
+};
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+use std::error::Error;
+
+use super::account_to_json;
+use crate::context::Context;
+
+#[derive(Debug, Subcommand)]
+pub enum TokenBadgeCommand {
+    /// Show the token badge of a mint in the whirlpools config.
+    Show {
+        /// The address of the token mint.
+        token_mint: Pubkey,
+    },
+    /// Check whether pools can be created with a mint, and whether it needs a token badge.
+    Check {
+        /// The address of the token mint.
+        token_mint: Pubkey,
+    },
+}
+
+pub async fn run_token_badge_command(
+    ctx: &Context,
+    command: TokenBadgeCommand,
+) -> Result<Value, Box<dyn Error>> {
+    match command {
+        TokenBadgeCommand::Show { token_mint } => {
+            let token_badge_address =
+                get_token_badge_address(&ctx.whirlpools_config, &token_mint)?.0;
+            let account = ctx
+                .rpc
+                .get_multiple_accounts(&[token_badge_address])
+                .await?
+                .into_iter()
+                .next()
+                .flatten();
+            match account {
+                Some(account) => {
+                    let token_badge = TokenBadge::from_bytes(&account.data)?;
+                    let mut value = account_to_json(token_badge_address, &token_badge)?;
+                    value["initialized"] = json!(true);
+                    Ok(value)
+                }
+                None => Ok(json!({
+                    "address": token_badge_address.to_string(),
+                    "initialized": false,
+                })),
+            }
+        }
+        TokenBadgeCommand::Check { token_mint } => {
+            let token_badge_address =
+                get_token_badge_address(&ctx.whirlpools_config, &token_mint)?.0;
+            let accounts = ctx
+                .rpc
+                .get_multiple_accounts(&[token_mint, token_badge_address])
+                .await?;
+            let mint_account = accounts[0]
+                .as_ref()
+                .ok_or(format!("Mint {} not found", token_mint))?;
+            let token_badge_initialized = accounts[1].as_ref().is_some_and(|x| {
+                is_token_badge_initialized(&ctx.whirlpools_config, &token_mint, &x.owner, &x.data)
+            });
+            let compatibility = check_token_mint_compatibility(
+                &token_mint,
+                &mint_account.owner,
+                &mint_account.data,
+            )?;
+            let checks: Vec<Value> = compatibility
+                .checks
+                .iter()
+                .map(|x| {
+                    json!({
+                        "property": property_name(&x.property),
+                        "support": format!("{:?}", x.support),
+                        "reason": x.reason,
+                    })
+                })
+                .collect();
+            Ok(json!({
+                "tokenMint": token_mint.to_string(),
+                "tokenProgram": mint_account.owner.to_string(),
+                "tokenBadgeInitialized": token_badge_initialized,
+                "support": format!("{:?}", compatibility.support()),
+                "supported": compatibility.is_supported(token_badge_initialized),
+                "checks": checks,
+            }))
+        }
+    }
+}
+
+fn property_name(property: &TokenMintProperty) -> String {
+    match property {
+        TokenMintProperty::Extension(extension) => format!("{:?}", extension),
+        property => format!("{:?}", property),
+    }
+}
@@ -0,0 +1,201 @@
+use orca_tx_sender::{
+    build_transaction, send_transaction, set_priority_fee_strategy, set_rpc, Percentile,
+    PriorityFeeStrategy,
+};
+use serde_json::{json, Value};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcSimulateTransactionConfig;
+use solana_sdk::{
+    commitment_config::CommitmentLevel,
+    instruction::Instruction,
+    message::{v0, VersionedMessage},
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair},
+    signer::Signer,
+    transaction::VersionedTransaction,
+};
+use std::error::Error;
+use std::path::PathBuf;
+
+/// How transactions built by a command are handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionMode {
+    /// Sign, send and confirm the transaction.
+    Send,
+    /// Simulate the transaction and print the logs without sending it.
+    Simulate,
+    /// Print the base64 encoded transaction without sending it.
+    DumpTransaction,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum PriorityFee {
+    None,
+    P25,
+    P50,
+    P75,
+    P95,
+    P99,
+}
+
+pub struct Context {
+    pub rpc: RpcClient,
+    pub whirlpools_config: Pubkey,
+    pub execution_mode: ExecutionMode,
+    keypair_path: PathBuf,
+    keypair: Option<Keypair>,
+}
+
+impl Context {
+    pub async fn new(
+        url: &str,
+        keypair_path: PathBuf,
+        whirlpools_config: Pubkey,
+        execution_mode: ExecutionMode,
+        priority_fee: PriorityFee,
+        max_priority_fee_lamports: u64,
+    ) -> Result<Self, Box<dyn Error>> {
+        set_rpc(url).await?;
+        let percentile = match priority_fee {
+            PriorityFee::None => None,
+            PriorityFee::P25 => Some(Percentile::P25),
+            PriorityFee::P50 => Some(Percentile::P50),
+            PriorityFee::P75 => Some(Percentile::P75),
+            PriorityFee::P95 => Some(Percentile::P95),
+            PriorityFee::P99 => Some(Percentile::P99),
+        };
+        set_priority_fee_strategy(match percentile {
+            Some(percentile) => PriorityFeeStrategy::Dynamic {
+                percentile,
+                max_lamports: max_priority_fee_lamports,
+            },
+            None => PriorityFeeStrategy::Disabled,
+        })?;
+
+        // Read-only commands do not need a keypair, so a missing file is only an error once a
+        // command needs to sign.
+        let keypair = read_keypair_file(&keypair_path).ok();
+        if let Some(keypair) = &keypair {
+            orca_whirlpools::set_funder(keypair.pubkey())?;
+        }
+
+        Ok(Self {
+            rpc: RpcClient::new(url.to_string()),
+            whirlpools_config,
+            execution_mode,
+            keypair_path,
+            keypair,
+        })
+    }
+
+    pub fn keypair(&self) -> Result<&Keypair, Box<dyn Error>> {
+        self.keypair.as_ref().ok_or_else(|| {
+            format!(
+                "Failed to read keypair from {}. Pass a keypair with --keypair.",
+                self.keypair_path.display()
+            )
+            .into()
+        })
+    }
+
+    pub fn wallet(&self) -> Result<Pubkey, Box<dyn Error>> {
+        Ok(self.keypair()?.pubkey())
+    }
+
+    /// The address quotes are built for. Quotes do not need a signature, so any address works
+    /// when no keypair is available.
+    pub fn quote_authority(&self) -> Pubkey {
+        self.keypair
+            .as_ref()
+            .map(|x| x.pubkey())
+            .unwrap_or_else(Pubkey::new_unique)
+    }
+
+    /// Sends, simulates or dumps a transaction with the instructions of a command.
+    ///
+    /// The keypair pays for the transaction and signs it together with `additional_signers`.
+    pub async fn execute(
+        &self,
+        instructions: Vec<Instruction>,
+        additional_signers: &[Keypair],
+    ) -> Result<Value, Box<dyn Error>> {
+        let payer = self.keypair()?;
+        let mut signers: Vec<&Keypair> = vec![payer];
+        signers.extend(additional_signers);
+
+        match self.execution_mode {
+            ExecutionMode::Send => {
+                let mut transaction =
+                    build_transaction(instructions, &payer.pubkey(), None).await?;
+                sign_transaction(&mut transaction, &signers)?;
+                let signature =
+                    send_transaction(transaction, Some(CommitmentLevel::Confirmed)).await?;
+                Ok(json!({ "signature": signature.to_string() }))
+            }
+            ExecutionMode::Simulate => {
+                // The transaction is compiled without compute budget instructions, because
+                // estimating them fails for transactions that fail the simulation.
+                let recent_blockhash = self.rpc.get_latest_blockhash().await?;
+                let message = v0::Message::try_compile(
+                    &payer.pubkey(),
+                    &instructions,
+                    &[],
+                    recent_blockhash,
+                )?;
+                let mut transaction = VersionedTransaction {
+                    signatures: vec![],
+                    message: VersionedMessage::V0(message),
+                };
+                sign_transaction(&mut transaction, &signers)?;
+                let simulation = self
+                    .rpc
+                    .simulate_transaction_with_config(
+                        &transaction,
+                        RpcSimulateTransactionConfig {
+                            sig_verify: false,
+                            replace_recent_blockhash: true,
+                            ..Default::default()
+                        },
+                    )
+                    .await?
+                    .value;
+                Ok(json!({
+                    "success": simulation.err.is_none(),
+                    "error": simulation.err.map(|x| x.to_string()),
+                    "unitsConsumed": simulation.units_consumed,
+                    "logs": simulation.logs.unwrap_or_default(),
+                }))
+            }
+            ExecutionMode::DumpTransaction => {
+                let mut transaction =
+                    build_transaction(instructions, &payer.pubkey(), None).await?;
+                sign_transaction(&mut transaction, &signers)?;
+                Ok(json!({
+                    "transaction": base64::encode(bincode::serialize(&transaction)?),
+                    "message": base64::encode(transaction.message.serialize()),
+                }))
+            }
+        }
+    }
+}
+
+fn sign_transaction(
+    transaction: &mut VersionedTransaction,
+    signers: &[&Keypair],
+) -> Result<(), Box<dyn Error>> {
+    let message = transaction.message.serialize();
+    let signer_keys = transaction.message.static_account_keys()
+        [..transaction.message.header().num_required_signatures as usize]
+        .to_vec();
+    transaction.signatures = signer_keys
+        .iter()
+        .map(|key| {
+            signers
+                .iter()
+                .find(|signer| signer.pubkey() == *key)
+                .map(|signer| signer.sign_message(&message))
+                .ok_or_else(|| format!("Missing signature for {}", key))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(())
+}
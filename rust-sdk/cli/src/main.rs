@@ -0,0 +1,178 @@
+mod commands;
+mod context;
+mod output;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use commands::{
+    admin::{run_admin_command, AdminCommand},
+    oracle::{run_oracle_command, OracleCommand},
+    pool::{run_pool_command, PoolCommand},
+    position::{run_position_command, PositionCommand},
+    quote::{run_quote_command, QuoteCommand},
+    token_badge::{run_token_badge_command, TokenBadgeCommand},
+};
+use context::{Context, ExecutionMode, PriorityFee};
+use orca_whirlpools::{
+    set_slippage_tolerance_bps, set_whirlpools_config_address, WhirlpoolsConfigInput,
+};
+use output::{print_output, OutputFormat};
+use solana_sdk::pubkey::Pubkey;
+use std::error::Error;
+use std::path::PathBuf;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "whirlpools",
+    version,
+    about = "Inspect and operate Orca Whirlpools"
+)]
+struct Cli {
+    /// The RPC endpoint.
+    #[arg(
+        short,
+        long,
+        global = true,
+        env = "RPC_URL",
+        default_value = "https://api.mainnet-beta.solana.com"
+    )]
+    url: String,
+
+    /// The keypair that signs and pays for transactions.
+    #[arg(short, long, global = true, env = "KEYPAIR_PATH")]
+    keypair: Option<PathBuf>,
+
+    /// The network whose whirlpools config is used.
+    #[arg(long, global = true, value_enum, default_value_t = Network::SolanaMainnet)]
+    network: Network,
+
+    /// A whirlpools config address that overrides the config of `--network`.
+    #[arg(long, global = true)]
+    whirlpools_config: Option<Pubkey>,
+
+    #[arg(short, long, global = true, value_enum, default_value_t = OutputFormat::Table)]
+    output: OutputFormat,
+
+    /// Simulate transactions and print the logs instead of sending them.
+    #[arg(long, global = true, conflicts_with = "dump_transaction")]
+    simulate: bool,
+
+    /// Print signed transactions as base64 instead of sending them.
+    #[arg(long, global = true)]
+    dump_transaction: bool,
+
+    /// The slippage tolerance for quotes and transactions, in basis points.
+    #[arg(long, global = true, default_value_t = 100)]
+    slippage_bps: u16,
+
+    /// The percentile of recent priority fees to pay.
+    #[arg(long, global = true, value_enum, default_value_t = PriorityFee::None)]
+    priority_fee: PriorityFee,
+
+    /// The maximum priority fee to pay, in lamports.
+    #[arg(long, global = true, default_value_t = 4_000_000)]
+    max_priority_fee_lamports: u64,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Network {
+    SolanaMainnet,
+    SolanaDevnet,
+    EclipseMainnet,
+    EclipseTestnet,
+}
+
+impl From<Network> for WhirlpoolsConfigInput {
+    fn from(network: Network) -> Self {
+        match network {
+            Network::SolanaMainnet => WhirlpoolsConfigInput::SolanaMainnet,
+            Network::SolanaDevnet => WhirlpoolsConfigInput::SolanaDevnet,
+            Network::EclipseMainnet => WhirlpoolsConfigInput::EclipseMainnet,
+            Network::EclipseTestnet => WhirlpoolsConfigInput::EclipseTestnet,
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Inspect and create pools.
+    #[command(subcommand)]
+    Pool(PoolCommand),
+    /// Inspect and manage positions.
+    #[command(subcommand)]
+    Position(PositionCommand),
+    /// Inspect the oracles of adaptive fee pools.
+    #[command(subcommand)]
+    Oracle(OracleCommand),
+    /// Inspect token badges.
+    #[command(subcommand)]
+    TokenBadge(TokenBadgeCommand),
+    /// Quote swaps and liquidity changes without sending a transaction.
+    #[command(subcommand)]
+    Quote(QuoteCommand),
+    /// Run administrative actions of the whirlpools config.
+    #[command(subcommand)]
+    Admin(AdminCommand),
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+    let output = cli.output;
+    if let Err(error) = run(cli).await {
+        match output {
+            OutputFormat::Json => print_output(
+                &serde_json::json!({ "error": error.to_string() }),
+                OutputFormat::Json,
+            ),
+            OutputFormat::Table => eprintln!("Error: {}", error),
+        }
+        std::process::exit(1);
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), Box<dyn Error>> {
+    let whirlpools_config: Pubkey = match cli.whirlpools_config {
+        Some(address) => WhirlpoolsConfigInput::Address(address).into(),
+        None => WhirlpoolsConfigInput::from(cli.network).into(),
+    };
+    set_whirlpools_config_address(WhirlpoolsConfigInput::Address(whirlpools_config))?;
+    set_slippage_tolerance_bps(cli.slippage_bps)?;
+
+    let execution_mode = if cli.simulate {
+        ExecutionMode::Simulate
+    } else if cli.dump_transaction {
+        ExecutionMode::DumpTransaction
+    } else {
+        ExecutionMode::Send
+    };
+    let keypair_path = cli.keypair.unwrap_or_else(default_keypair_path);
+    let ctx = Context::new(
+        &cli.url,
+        keypair_path,
+        whirlpools_config,
+        execution_mode,
+        cli.priority_fee,
+        cli.max_priority_fee_lamports,
+    )
+    .await?;
+
+    let value = match cli.command {
+        Command::Pool(command) => run_pool_command(&ctx, command).await?,
+        Command::Position(command) => run_position_command(&ctx, command).await?,
+        Command::Oracle(command) => run_oracle_command(&ctx, command).await?,
+        Command::TokenBadge(command) => run_token_badge_command(&ctx, command).await?,
+        Command::Quote(command) => run_quote_command(&ctx, command).await?,
+        Command::Admin(command) => run_admin_command(&ctx, command).await?,
+    };
+    print_output(&value, cli.output);
+    Ok(())
+}
+
+/// The keypair of the Solana CLI.
+fn default_keypair_path() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    PathBuf::from(home).join(".config/solana/id.json")
+}
@@ -0,0 +1,172 @@
+use serde_json::{Map, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    Json,
+    Table,
+}
+
+/// Prints the result of a command.
+///
+/// Objects are printed as a key-value table and arrays of objects as a table with a column per
+/// key. Nested objects are flattened into dotted keys.
+pub fn print_output(value: &Value, format: OutputFormat) {
+    match format {
+        OutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(value).unwrap_or_default()
+        ),
+        OutputFormat::Table => println!("{}", render_table(value)),
+    }
+}
+
+fn render_table(value: &Value) -> String {
+    match value {
+        Value::Object(_) => {
+            let mut rows = Vec::new();
+            flatten("", value, &mut rows);
+            render_rows(
+                &["KEY".to_string(), "VALUE".to_string()],
+                &rows_to_cells(rows),
+            )
+        }
+        Value::Array(items) if items.iter().all(Value::is_object) && !items.is_empty() => {
+            let flattened: Vec<Vec<(String, String)>> = items
+                .iter()
+                .map(|item| {
+                    let mut rows = Vec::new();
+                    flatten("", item, &mut rows);
+                    rows
+                })
+                .collect();
+            let mut columns: Vec<String> = Vec::new();
+            for rows in &flattened {
+                for (key, _) in rows {
+                    if !columns.contains(key) {
+                        columns.push(key.clone());
+                    }
+                }
+            }
+            let cells: Vec<Vec<String>> = flattened
+                .iter()
+                .map(|rows| {
+                    columns
+                        .iter()
+                        .map(|column| {
+                            rows.iter()
+                                .find(|(key, _)| key == column)
+                                .map(|(_, value)| value.clone())
+                                .unwrap_or_default()
+                        })
+                        .collect()
+                })
+                .collect();
+            let header: Vec<String> = columns.iter().map(|x| x.to_uppercase()).collect();
+            render_rows(&header, &cells)
+        }
+        Value::Array(items) if items.is_empty() => "No results".to_string(),
+        _ => scalar_to_string(value),
+    }
+}
+
+fn flatten(prefix: &str, value: &Value, rows: &mut Vec<(String, String)>) {
+    let key = |name: &str| {
+        if prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", prefix, name)
+        }
+    };
+    match value {
+        Value::Object(map) => flatten_object(prefix, map, rows),
+        Value::Array(items) if items.iter().any(|x| x.is_object() || x.is_array()) => {
+            for (index, item) in items.iter().enumerate() {
+                flatten(&key(&index.to_string()), item, rows);
+            }
+        }
+        _ => rows.push((prefix.to_string(), scalar_to_string(value))),
+    }
+}
+
+fn flatten_object(prefix: &str, map: &Map<String, Value>, rows: &mut Vec<(String, String)>) {
+    for (name, value) in map {
+        let key = if prefix.is_empty() {
+            name.clone()
+        } else {
+            format!("{}.{}", prefix, name)
+        };
+        flatten(&key, value, rows);
+    }
+}
+
+fn scalar_to_string(value: &Value) -> String {
+    match value {
+        Value::Null => "-".to_string(),
+        Value::String(string) => string.clone(),
+        Value::Array(items) => items
+            .iter()
+            .map(scalar_to_string)
+            .collect::<Vec<_>>()
+            .join(", "),
+        _ => value.to_string(),
+    }
+}
+
+fn rows_to_cells(rows: Vec<(String, String)>) -> Vec<Vec<String>> {
+    rows.into_iter()
+        .map(|(key, value)| vec![key, value])
+        .collect()
+}
+
+fn render_rows(header: &[String], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = header.iter().map(|x| x.len()).collect();
+    for row in rows {
+        for (index, cell) in row.iter().enumerate() {
+            widths[index] = widths[index].max(cell.len());
+        }
+    }
+
+    let render_row = |cells: &[String]| {
+        cells
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut lines = vec![render_row(header)];
+    lines.extend(rows.iter().map(|row| render_row(row)));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_render_object() {
+        let value = json!({ "address": "abc", "data": { "liquidity": 10, "rewards": [1, 2] } });
+        assert_eq!(
+            render_table(&value),
+            "KEY             VALUE\naddress         abc\ndata.liquidity  10\ndata.rewards    1, 2"
+        );
+    }
+
+    #[test]
+    fn test_render_array() {
+        let value = json!([{ "address": "a", "price": 1.5 }, { "address": "bb", "paused": true }]);
+        assert_eq!(
+            render_table(&value),
+            "ADDRESS  PRICE  PAUSED\na        1.5\nbb              true"
+        );
+    }
+
+    #[test]
+    fn test_render_empty_array() {
+        assert_eq!(render_table(&json!([])), "No results");
+    }
+}
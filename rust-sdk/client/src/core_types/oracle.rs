@@ -1,6 +1,12 @@
-use orca_whirlpools_core::{AdaptiveFeeConstantsFacade, AdaptiveFeeVariablesFacade, OracleFacade};
+use orca_whirlpools_core::{
+    AdaptiveFeeConstantsFacade, AdaptiveFeeVariablesFacade, FeeRateScheduleFacade, OracleFacade,
+    OracleObservationFacade, OracleObservationsFacade,
+};
 
-use crate::{AdaptiveFeeConstants, AdaptiveFeeVariables, Oracle};
+use crate::{
+    AdaptiveFeeConstants, AdaptiveFeeVariables, FeeRateSchedule, Oracle, OracleObservation,
+    OracleWithObservations,
+};
 
 impl From<Oracle> for OracleFacade {
     fn from(val: Oracle) -> Self {
@@ -8,6 +14,7 @@ impl From<Oracle> for OracleFacade {
             trade_enable_timestamp: val.trade_enable_timestamp,
             adaptive_fee_constants: val.adaptive_fee_constants.into(),
             adaptive_fee_variables: val.adaptive_fee_variables.into(),
+            fee_rate_schedule: val.fee_rate_schedule.into(),
         }
     }
 }
@@ -37,3 +44,34 @@ impl From<AdaptiveFeeVariables> for AdaptiveFeeVariablesFacade {
         }
     }
 }
+
+impl From<FeeRateSchedule> for FeeRateScheduleFacade {
+    fn from(val: FeeRateSchedule) -> Self {
+        FeeRateScheduleFacade {
+            start_fee_rate: val.start_fee_rate,
+            target_fee_rate: val.target_fee_rate,
+            ramp_start_timestamp: val.ramp_start_timestamp,
+            effective_timestamp: val.effective_timestamp,
+        }
+    }
+}
+
+impl From<OracleObservation> for OracleObservationFacade {
+    fn from(val: OracleObservation) -> Self {
+        OracleObservationFacade {
+            timestamp: val.timestamp,
+            tick_cumulative: val.tick_cumulative,
+            sqrt_price_cumulative: val.sqrt_price_cumulative,
+        }
+    }
+}
+
+impl From<OracleWithObservations> for OracleObservationsFacade {
+    fn from(val: OracleWithObservations) -> Self {
+        OracleObservationsFacade {
+            observation_index: val.oracle.observation_index,
+            observation_cardinality: val.oracle.observation_cardinality,
+            observations: val.observations.into_iter().map(|x| x.into()).collect(),
+        }
+    }
+}
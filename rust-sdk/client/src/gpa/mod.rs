@@ -8,6 +8,7 @@ mod position;
 mod position_bundle;
 mod tick_array;
 mod token_badge;
+mod token_badge_history;
 mod utils;
 mod whirlpool;
 mod whirlpools_config;
@@ -27,6 +28,7 @@ pub use position::*;
 pub use position_bundle::*;
 pub use tick_array::*;
 pub use token_badge::*;
+pub use token_badge_history::*;
 pub use utils::*;
 pub use whirlpool::*;
 pub use whirlpools_config::*;
@@ -0,0 +1,44 @@
+use std::error::Error;
+
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_program::pubkey::Pubkey;
+
+use super::fetch_decoded_program_accounts;
+use crate::TOKEN_BADGE_HISTORY_DISCRIMINATOR;
+use crate::{generated::shared::DecodedAccount, TokenBadgeHistory, TokenBadgeStatus};
+
+#[derive(Debug, Clone)]
+pub enum TokenBadgeHistoryFilter {
+    WhirlpoolsConfig(Pubkey),
+    TokenMint(Pubkey),
+    Status(TokenBadgeStatus),
+}
+
+impl From<TokenBadgeHistoryFilter> for RpcFilterType {
+    fn from(val: TokenBadgeHistoryFilter) -> Self {
+        match val {
+            TokenBadgeHistoryFilter::WhirlpoolsConfig(address) => {
+                RpcFilterType::Memcmp(Memcmp::new_raw_bytes(8, address.to_bytes().to_vec()))
+            }
+            TokenBadgeHistoryFilter::TokenMint(address) => {
+                RpcFilterType::Memcmp(Memcmp::new_raw_bytes(40, address.to_bytes().to_vec()))
+            }
+            TokenBadgeHistoryFilter::Status(status) => {
+                RpcFilterType::Memcmp(Memcmp::new_raw_bytes(72, vec![status as u8]))
+            }
+        }
+    }
+}
+
+pub async fn fetch_all_token_badge_history_with_filter(
+    rpc: &RpcClient,
+    filters: Vec<TokenBadgeHistoryFilter>,
+) -> Result<Vec<DecodedAccount<TokenBadgeHistory>>, Box<dyn Error>> {
+    let mut filters: Vec<RpcFilterType> = filters.into_iter().map(|filter| filter.into()).collect();
+    filters.push(RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+        0,
+        TOKEN_BADGE_HISTORY_DISCRIMINATOR.to_vec(),
+    )));
+    fetch_decoded_program_accounts(rpc, filters).await
+}
@@ -5,12 +5,17 @@ mod pda;
 
 mod state;
 
+mod token;
+
 #[cfg(feature = "fetch")]
 mod gpa;
 
 #[cfg(feature = "core-types")]
 mod core_types;
 
+#[cfg(feature = "snapshot")]
+mod snapshot;
+
 pub use generated::accounts::*;
 pub use generated::errors::*;
 pub use generated::instructions::*;
@@ -26,6 +31,10 @@ pub(crate) use generated::*;
 
 pub use pda::*;
 pub use state::*;
+pub use token::*;
 
 #[cfg(feature = "fetch")]
 pub use gpa::*;
+
+#[cfg(feature = "snapshot")]
+pub use snapshot::*;
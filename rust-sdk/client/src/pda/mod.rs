@@ -3,6 +3,9 @@ mod lock_config;
 mod oracle;
 mod position;
 mod position_bundle;
+mod position_delegate;
+mod range_order;
+mod referral;
 mod tick_array;
 mod token_badge;
 mod whirlpool;
@@ -13,6 +16,9 @@ pub use lock_config::*;
 pub use oracle::*;
 pub use position::*;
 pub use position_bundle::*;
+pub use position_delegate::*;
+pub use range_order::*;
+pub use referral::*;
 pub use tick_array::*;
 pub use token_badge::*;
 pub use whirlpool::*;
@@ -0,0 +1,24 @@
+use crate::generated::programs::WHIRLPOOL_ID;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+
+pub fn get_position_delegate_address(position: &Pubkey) -> Result<(Pubkey, u8), ProgramError> {
+    let seeds = &[b"position_delegate", position.as_ref()];
+
+    Pubkey::try_find_program_address(seeds, &WHIRLPOOL_ID).ok_or(ProgramError::InvalidSeeds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_get_position_delegate_address() {
+        let position_delegate =
+            Pubkey::from_str("2GqkrWESy8MKq5bQPWLuEbmTrj8kB2ekiTRFLyUWB5sc").unwrap();
+        let position = Pubkey::from_str("2EtH4ZZStW8Ffh2CbbW4baekdtWgPLcBXfYQ6FRmMVsq").unwrap();
+        let (address, _) = get_position_delegate_address(&position).unwrap();
+        assert_eq!(address, position_delegate);
+    }
+}
@@ -0,0 +1,23 @@
+use crate::generated::programs::WHIRLPOOL_ID;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+
+pub fn get_range_order_address(position: &Pubkey) -> Result<(Pubkey, u8), ProgramError> {
+    let seeds = &[b"range_order", position.as_ref()];
+
+    Pubkey::try_find_program_address(seeds, &WHIRLPOOL_ID).ok_or(ProgramError::InvalidSeeds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_get_range_order_address() {
+        let range_order = Pubkey::from_str("F23DMB3s8eoHEkNL7mvMjECrLGuRd6hTFRE1evjg1W4b").unwrap();
+        let position = Pubkey::from_str("2EtH4ZZStW8Ffh2CbbW4baekdtWgPLcBXfYQ6FRmMVsq").unwrap();
+        let (address, _) = get_range_order_address(&position).unwrap();
+        assert_eq!(address, range_order);
+    }
+}
@@ -0,0 +1,28 @@
+use crate::generated::programs::WHIRLPOOL_ID;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+
+pub fn get_referral_address(
+    whirlpools_config: &Pubkey,
+    referrer: &Pubkey,
+) -> Result<(Pubkey, u8), ProgramError> {
+    let seeds = &[b"referral", whirlpools_config.as_ref(), referrer.as_ref()];
+
+    Pubkey::try_find_program_address(seeds, &WHIRLPOOL_ID).ok_or(ProgramError::InvalidSeeds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_get_referral_address() {
+        let whirlpools_config =
+            Pubkey::from_str("2LecshUwdy9xi7meFgHtFJQNSKk4KdTrcpvaB56dP2NQ").unwrap();
+        let referrer = Pubkey::from_str("2b1kV6DkPAnxd5ixfnxCpjxmKwqjjaYmCZfHsFu24GXo").unwrap();
+        let referral = Pubkey::from_str("8dic4bNBQUuXvYK5GfnA8FMMhDqYVPLGYi39xfkNUetY").unwrap();
+        let (address, _) = get_referral_address(&whirlpools_config, &referrer).unwrap();
+        assert_eq!(address, referral);
+    }
+}
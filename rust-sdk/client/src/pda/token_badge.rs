@@ -15,6 +15,19 @@ pub fn get_token_badge_address(
     Pubkey::try_find_program_address(seeds, &WHIRLPOOL_ID).ok_or(ProgramError::InvalidSeeds)
 }
 
+pub fn get_token_badge_history_address(
+    whirlpools_config: &Pubkey,
+    token_mint: &Pubkey,
+) -> Result<(Pubkey, u8), ProgramError> {
+    let seeds = &[
+        b"token_badge_history".as_ref(),
+        whirlpools_config.as_ref(),
+        token_mint.as_ref(),
+    ];
+
+    Pubkey::try_find_program_address(seeds, &WHIRLPOOL_ID).ok_or(ProgramError::InvalidSeeds)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -29,4 +42,16 @@ mod tests {
         let (address, _) = get_token_badge_address(&whirlpools_config, &token_mint).unwrap();
         assert_eq!(address, token_badge);
     }
+
+    #[test]
+    fn test_get_token_badge_history_address() {
+        let whirlpools_config =
+            Pubkey::from_str("2LecshUwdy9xi7meFgHtFJQNSKk4KdTrcpvaB56dP2NQ").unwrap();
+        let token_mint = Pubkey::from_str("2b1kV6DkPAnxd5ixfnxCpjxmKwqjjaYmCZfHsFu24GXo").unwrap();
+        let token_badge_history =
+            Pubkey::from_str("Fz3wqH7XtF2DTuiV8qd6hawE6gvhQXSWVqEi6mLp2U4c").unwrap();
+        let (address, _) =
+            get_token_badge_history_address(&whirlpools_config, &token_mint).unwrap();
+        assert_eq!(address, token_badge_history);
+    }
 }
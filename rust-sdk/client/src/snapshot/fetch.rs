@@ -0,0 +1,208 @@
+use std::error::Error;
+
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{nonblocking::rpc_client::RpcClient, rpc_config::RpcAccountInfoConfig};
+use solana_program::pubkey::Pubkey;
+use solana_sdk::{account::Account, sysvar::clock};
+
+use crate::{
+    fetch_all_position_with_filter, fetch_all_tick_array_with_filter, get_oracle_address,
+    get_token_badge_address, get_whirlpools_config_extension_address, PositionFilter, TickArray,
+    TickArrayFilter, Whirlpool,
+};
+
+use super::{PoolSnapshot, SnapshotAccount, SnapshotAccountKind, POOL_SNAPSHOT_VERSION};
+
+const MAX_ACCOUNTS_PER_REQUEST: usize = 100;
+
+/// Options for `fetch_pool_snapshot`.
+#[derive(Debug, Clone, Default)]
+pub struct PoolSnapshotOptions {
+    /// Also snapshot every position of the pool. Pools can have many positions, so they are
+    /// left out by default.
+    pub include_positions: bool,
+
+    /// The slot the snapshot is taken at or after. Rpc nodes only serve the latest state of an
+    /// account, so an older slot can not be requested.
+    pub min_context_slot: Option<u64>,
+}
+
+/// Takes a snapshot of a pool.
+///
+/// The accounts a swap reads, i.e. the whirlpool, oracle, config, mints, vaults, token badges and
+/// the tick arrays closest to the current price, are fetched in the first request so they are
+/// read at the same slot. Pools with more accounts than fit in one request are read over several
+/// slots; the slot of each account is recorded in the snapshot.
+///
+/// # Arguments
+///
+/// * `rpc` - A reference to a Solana RPC client.
+/// * `whirlpool_address` - The address of the pool.
+/// * `options` - Which accounts to include and the slot to take the snapshot at or after.
+///
+/// # Returns
+///
+/// A `PoolSnapshot` with the raw data of every account that exists.
+pub async fn fetch_pool_snapshot(
+    rpc: &RpcClient,
+    whirlpool_address: Pubkey,
+    options: PoolSnapshotOptions,
+) -> Result<PoolSnapshot, Box<dyn Error>> {
+    let whirlpool_account = rpc.get_account(&whirlpool_address).await?;
+    let whirlpool = Whirlpool::from_bytes(&whirlpool_account.data)?;
+    let config = whirlpool.whirlpools_config;
+
+    let mut addresses: Vec<(Pubkey, SnapshotAccountKind)> = vec![
+        (whirlpool_address, SnapshotAccountKind::Whirlpool),
+        (
+            get_oracle_address(&whirlpool_address)?.0,
+            SnapshotAccountKind::Oracle,
+        ),
+        (config, SnapshotAccountKind::WhirlpoolsConfig),
+        (
+            get_whirlpools_config_extension_address(&config)?.0,
+            SnapshotAccountKind::WhirlpoolsConfigExtension,
+        ),
+        (whirlpool.token_mint_a, SnapshotAccountKind::Mint),
+        (whirlpool.token_mint_b, SnapshotAccountKind::Mint),
+        (whirlpool.token_vault_a, SnapshotAccountKind::TokenVault),
+        (whirlpool.token_vault_b, SnapshotAccountKind::TokenVault),
+        (
+            get_token_badge_address(&config, &whirlpool.token_mint_a)?.0,
+            SnapshotAccountKind::TokenBadge,
+        ),
+        (
+            get_token_badge_address(&config, &whirlpool.token_mint_b)?.0,
+            SnapshotAccountKind::TokenBadge,
+        ),
+    ];
+    for reward_info in whirlpool.reward_infos.iter() {
+        if reward_info.mint != Pubkey::default() {
+            addresses.push((reward_info.mint, SnapshotAccountKind::Mint));
+            addresses.push((reward_info.vault, SnapshotAccountKind::TokenVault));
+        }
+    }
+
+    // Only the addresses are used, the data is fetched again below so all accounts are read at
+    // a known slot.
+    let mut tick_arrays =
+        fetch_all_tick_array_with_filter(rpc, vec![TickArrayFilter::Whirlpool(whirlpool_address)])
+            .await?;
+    tick_arrays.sort_by_key(|x| {
+        (tick_array_start_tick_index(&x.data) - whirlpool.tick_current_index).abs()
+    });
+    addresses.extend(
+        tick_arrays
+            .iter()
+            .map(|x| (x.address, SnapshotAccountKind::TickArray)),
+    );
+
+    if options.include_positions {
+        let positions =
+            fetch_all_position_with_filter(rpc, vec![PositionFilter::Whirlpool(whirlpool_address)])
+                .await?;
+        addresses.extend(
+            positions
+                .iter()
+                .map(|x| (x.address, SnapshotAccountKind::Position)),
+        );
+    }
+
+    // The clock is read in the first request, so its timestamp matches the slot of the pool.
+    let mut chunks = addresses.chunks(MAX_ACCOUNTS_PER_REQUEST - 1);
+    let mut min_context_slot = options.min_context_slot;
+    let mut accounts: Vec<SnapshotAccount> = Vec::new();
+    let mut clock_data: Option<Vec<u8>> = None;
+    let mut snapshot_slot = 0;
+
+    if let Some(first_chunk) = chunks.next() {
+        let mut chunk_addresses: Vec<Pubkey> = vec![clock::ID];
+        chunk_addresses.extend(first_chunk.iter().map(|x| x.0));
+        let (slot, mut chunk_accounts) =
+            fetch_accounts(rpc, &chunk_addresses, min_context_slot).await?;
+        clock_data = chunk_accounts.remove(0).map(|x| x.data);
+        push_accounts(&mut accounts, first_chunk, chunk_accounts, slot);
+        snapshot_slot = slot;
+        min_context_slot = Some(slot);
+    }
+
+    for chunk in chunks {
+        let chunk_addresses: Vec<Pubkey> = chunk.iter().map(|x| x.0).collect();
+        let (slot, chunk_accounts) =
+            fetch_accounts(rpc, &chunk_addresses, min_context_slot).await?;
+        push_accounts(&mut accounts, chunk, chunk_accounts, slot);
+    }
+
+    let clock_data = clock_data.ok_or("Clock sysvar not found")?;
+    let (epoch, unix_timestamp) = decode_clock(&clock_data)?;
+
+    Ok(PoolSnapshot {
+        version: POOL_SNAPSHOT_VERSION,
+        whirlpool: whirlpool_address,
+        slot: snapshot_slot,
+        unix_timestamp,
+        epoch,
+        accounts,
+    })
+}
+
+async fn fetch_accounts(
+    rpc: &RpcClient,
+    addresses: &[Pubkey],
+    min_context_slot: Option<u64>,
+) -> Result<(u64, Vec<Option<Account>>), Box<dyn Error>> {
+    let response = rpc
+        .get_multiple_accounts_with_config(
+            addresses,
+            RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                data_slice: None,
+                commitment: None,
+                min_context_slot,
+            },
+        )
+        .await?;
+    Ok((response.context.slot, response.value))
+}
+
+fn push_accounts(
+    accounts: &mut Vec<SnapshotAccount>,
+    addresses: &[(Pubkey, SnapshotAccountKind)],
+    fetched: Vec<Option<Account>>,
+    slot: u64,
+) {
+    for ((address, kind), account) in addresses.iter().zip(fetched) {
+        // Accounts that do not exist, e.g. the oracle of a pool without adaptive fee or a token
+        // badge, are left out.
+        if let Some(account) = account {
+            accounts.push(SnapshotAccount {
+                address: *address,
+                kind: *kind,
+                slot,
+                lamports: account.lamports,
+                owner: account.owner,
+                executable: account.executable,
+                rent_epoch: account.rent_epoch,
+                data: account.data,
+            });
+        }
+    }
+}
+
+fn tick_array_start_tick_index(tick_array: &TickArray) -> i32 {
+    match tick_array {
+        TickArray::FixedTickArray(x) => x.start_tick_index,
+        TickArray::DynamicTickArray(x) => x.start_tick_index,
+    }
+}
+
+/// Reads the epoch and unix timestamp from the data of the clock sysvar, which is laid out as
+/// slot, epoch start timestamp, epoch, leader schedule epoch and unix timestamp.
+fn decode_clock(data: &[u8]) -> Result<(u64, i64), Box<dyn Error>> {
+    if data.len() < 40 {
+        return Err("Invalid clock sysvar data length".into());
+    }
+    let epoch = u64::from_le_bytes(data[16..24].try_into()?);
+    let unix_timestamp = i64::from_le_bytes(data[32..40].try_into()?);
+    Ok((epoch, unix_timestamp))
+}
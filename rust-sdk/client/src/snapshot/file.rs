@@ -0,0 +1,169 @@
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use super::{PoolSnapshot, POOL_SNAPSHOT_VERSION};
+
+/// The prefix of snapshots in the binary format.
+const BINARY_SNAPSHOT_MAGIC: &[u8; 8] = b"WPSNAPSH";
+
+/// The file format of a pool snapshot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SnapshotFormat {
+    /// Human-readable JSON with the account data encoded as base64.
+    Json,
+    /// Compact borsh encoding prefixed with a magic number.
+    Binary,
+}
+
+impl PoolSnapshot {
+    pub fn to_json(&self) -> Result<String, Box<dyn Error>> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, Box<dyn Error>> {
+        let snapshot: Self = serde_json::from_str(json)?;
+        snapshot.check_version()?;
+        Ok(snapshot)
+    }
+
+    pub fn to_binary(&self) -> Result<Vec<u8>, Box<dyn Error>> {
+        let mut bytes = BINARY_SNAPSHOT_MAGIC.to_vec();
+        self.serialize(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    pub fn from_binary(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let data = bytes
+            .strip_prefix(BINARY_SNAPSHOT_MAGIC.as_slice())
+            .ok_or("Invalid snapshot magic number")?;
+        let snapshot = Self::try_from_slice(data)?;
+        snapshot.check_version()?;
+        Ok(snapshot)
+    }
+
+    /// Writes the snapshot to a file in the given format.
+    pub fn write_to_file(
+        &self,
+        path: impl AsRef<Path>,
+        format: SnapshotFormat,
+    ) -> Result<(), Box<dyn Error>> {
+        let bytes = match format {
+            SnapshotFormat::Json => self.to_json()?.into_bytes(),
+            SnapshotFormat::Binary => self.to_binary()?,
+        };
+        fs::write(path, bytes)?;
+        Ok(())
+    }
+
+    /// Reads a snapshot from a file. The format is detected from the content of the file.
+    pub fn read_from_file(path: impl AsRef<Path>) -> Result<Self, Box<dyn Error>> {
+        let bytes = fs::read(path)?;
+        if bytes.starts_with(BINARY_SNAPSHOT_MAGIC) {
+            Self::from_binary(&bytes)
+        } else {
+            Self::from_json(std::str::from_utf8(&bytes)?)
+        }
+    }
+
+    /// Writes every account to a directory in the format of `solana account --output json`, so
+    /// a test validator can load them with `solana-test-validator --account-dir <dir>`.
+    ///
+    /// Returns the paths of the written files.
+    pub fn write_test_validator_accounts(
+        &self,
+        dir: impl AsRef<Path>,
+    ) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+        fs::create_dir_all(&dir)?;
+        let mut paths = Vec::with_capacity(self.accounts.len());
+        for account in &self.accounts {
+            let value = serde_json::json!({
+                "pubkey": account.address.to_string(),
+                "account": {
+                    "lamports": account.lamports,
+                    "data": [base64::encode(&account.data), "base64"],
+                    "owner": account.owner.to_string(),
+                    "executable": account.executable,
+                    "rentEpoch": account.rent_epoch,
+                    "space": account.data.len(),
+                },
+            });
+            let path = dir.as_ref().join(format!("{}.json", account.address));
+            fs::write(&path, serde_json::to_string_pretty(&value)?)?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+
+    fn check_version(&self) -> Result<(), Box<dyn Error>> {
+        if self.version != POOL_SNAPSHOT_VERSION {
+            return Err(format!(
+                "Unsupported snapshot version {}, expected {}",
+                self.version, POOL_SNAPSHOT_VERSION
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::{SnapshotAccount, SnapshotAccountKind};
+    use solana_program::pubkey::Pubkey;
+
+    fn test_snapshot() -> PoolSnapshot {
+        let whirlpool = Pubkey::new_unique();
+        PoolSnapshot {
+            version: POOL_SNAPSHOT_VERSION,
+            whirlpool,
+            slot: 300_000_000,
+            unix_timestamp: 1_700_000_000,
+            epoch: 700,
+            accounts: vec![SnapshotAccount {
+                address: whirlpool,
+                kind: SnapshotAccountKind::Whirlpool,
+                slot: 300_000_000,
+                lamports: 5_435_760,
+                owner: crate::WHIRLPOOL_ID,
+                executable: false,
+                rent_epoch: u64::MAX,
+                data: vec![0, 1, 2, 255],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let snapshot = test_snapshot();
+        let json = snapshot.to_json().unwrap();
+        assert!(json.contains("\"data\": \"AAEC/w==\""));
+        assert_eq!(PoolSnapshot::from_json(&json).unwrap(), snapshot);
+    }
+
+    #[test]
+    fn test_binary_roundtrip() {
+        let snapshot = test_snapshot();
+        let bytes = snapshot.to_binary().unwrap();
+        assert!(bytes.starts_with(BINARY_SNAPSHOT_MAGIC));
+        assert_eq!(PoolSnapshot::from_binary(&bytes).unwrap(), snapshot);
+    }
+
+    #[test]
+    fn test_unsupported_version() {
+        let mut snapshot = test_snapshot();
+        snapshot.version = POOL_SNAPSHOT_VERSION + 1;
+        let json = snapshot.to_json().unwrap();
+        assert!(PoolSnapshot::from_json(&json).is_err());
+        let bytes = snapshot.to_binary().unwrap();
+        assert!(PoolSnapshot::from_binary(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_invalid_magic() {
+        assert!(PoolSnapshot::from_binary(b"NOTASNAPSHOT").is_err());
+    }
+}
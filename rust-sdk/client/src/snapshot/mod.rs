@@ -0,0 +1,133 @@
+mod fetch;
+mod file;
+
+#[cfg(feature = "core-types")]
+mod simulation;
+
+pub use fetch::*;
+pub use file::*;
+
+#[cfg(feature = "core-types")]
+pub use simulation::*;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use solana_program::pubkey::Pubkey;
+use solana_sdk::account::Account;
+
+use crate::{Oracle, Position, TickArray, Whirlpool};
+
+/// The version of the snapshot format. Snapshots of other versions are rejected when loaded.
+pub const POOL_SNAPSHOT_VERSION: u32 = 1;
+
+/// The role of an account in a pool snapshot.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
+)]
+pub enum SnapshotAccountKind {
+    WhirlpoolsConfig,
+    WhirlpoolsConfigExtension,
+    Whirlpool,
+    Oracle,
+    TickArray,
+    Position,
+    Mint,
+    TokenVault,
+    TokenBadge,
+}
+
+/// An account of a pool snapshot with its raw data, so it can be restored byte-for-byte.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct SnapshotAccount {
+    #[serde(with = "serde_with::As::<serde_with::DisplayFromStr>")]
+    pub address: Pubkey,
+    pub kind: SnapshotAccountKind,
+    /// The slot the rpc node read the account at.
+    pub slot: u64,
+    pub lamports: u64,
+    #[serde(with = "serde_with::As::<serde_with::DisplayFromStr>")]
+    pub owner: Pubkey,
+    pub executable: bool,
+    pub rent_epoch: u64,
+    #[serde(with = "serde_with::As::<serde_with::base64::Base64>")]
+    pub data: Vec<u8>,
+}
+
+impl SnapshotAccount {
+    pub fn to_account(&self) -> Account {
+        Account {
+            lamports: self.lamports,
+            data: self.data.clone(),
+            owner: self.owner,
+            executable: self.executable,
+            rent_epoch: self.rent_epoch,
+        }
+    }
+}
+
+/// The full state of a pool at a slot: the `Whirlpool`, its tick arrays, oracle and optionally
+/// positions, together with the mints, token vaults, token badges and config the program reads
+/// when trading against the pool.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct PoolSnapshot {
+    pub version: u32,
+    #[serde(with = "serde_with::As::<serde_with::DisplayFromStr>")]
+    pub whirlpool: Pubkey,
+    /// The slot the whirlpool was read at.
+    pub slot: u64,
+    /// The unix timestamp of the clock sysvar at `slot`.
+    pub unix_timestamp: i64,
+    /// The epoch of the clock sysvar at `slot`, which selects the active transfer fee of mints.
+    pub epoch: u64,
+    pub accounts: Vec<SnapshotAccount>,
+}
+
+impl PoolSnapshot {
+    pub fn account(&self, address: &Pubkey) -> Option<&SnapshotAccount> {
+        self.accounts.iter().find(|x| x.address == *address)
+    }
+
+    pub fn accounts_of_kind(
+        &self,
+        kind: SnapshotAccountKind,
+    ) -> impl Iterator<Item = &SnapshotAccount> {
+        self.accounts.iter().filter(move |x| x.kind == kind)
+    }
+
+    /// The accounts of the snapshot, e.g. to write them to an in-memory account store.
+    pub fn to_accounts(&self) -> Vec<(Pubkey, Account)> {
+        self.accounts
+            .iter()
+            .map(|x| (x.address, x.to_account()))
+            .collect()
+    }
+
+    pub fn decode_whirlpool(&self) -> Result<Whirlpool, std::io::Error> {
+        let account = self.account(&self.whirlpool).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "Whirlpool not found in snapshot",
+            )
+        })?;
+        Whirlpool::from_bytes(&account.data)
+    }
+
+    pub fn decode_oracle(&self) -> Result<Option<Oracle>, std::io::Error> {
+        self.accounts_of_kind(SnapshotAccountKind::Oracle)
+            .next()
+            .map(|x| Oracle::from_bytes(&x.data))
+            .transpose()
+    }
+
+    pub fn decode_tick_arrays(&self) -> Result<Vec<(Pubkey, TickArray)>, std::io::Error> {
+        self.accounts_of_kind(SnapshotAccountKind::TickArray)
+            .map(|x| Ok((x.address, TickArray::from_bytes(&x.data)?)))
+            .collect()
+    }
+
+    pub fn decode_positions(&self) -> Result<Vec<(Pubkey, Position)>, std::io::Error> {
+        self.accounts_of_kind(SnapshotAccountKind::Position)
+            .map(|x| Ok((x.address, Position::from_bytes(&x.data)?)))
+            .collect()
+    }
+}
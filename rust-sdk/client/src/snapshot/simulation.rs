@@ -0,0 +1,72 @@
+use orca_whirlpools_core::{
+    get_swap_tick_array_start_tick_indexes, OracleFacade, TickArrayFacade, TickArrays, TickFacade,
+    WhirlpoolFacade, MAX_SWAP_TICK_ARRAYS, TICK_ARRAY_SIZE,
+};
+
+use solana_program::pubkey::Pubkey;
+
+use super::PoolSnapshot;
+use crate::TickArray;
+
+/// The state of a pool snapshot as the swap quote functions of `orca_whirlpools_core` take it.
+#[derive(Debug, Clone)]
+pub struct SwapSimulationInput {
+    pub whirlpool: WhirlpoolFacade,
+    pub oracle: Option<OracleFacade>,
+    pub tick_arrays: TickArrays,
+    /// The timestamp of the snapshot, which the adaptive fee and fee rate schedule depend on.
+    pub timestamp: u64,
+}
+
+impl PoolSnapshot {
+    /// Builds the input of a swap quote in the direction `a_to_b` from the snapshot.
+    ///
+    /// Tick arrays missing from the snapshot are treated as uninitialized, like the sdk does
+    /// for tick arrays that do not exist on chain.
+    pub fn swap_simulation_input(
+        &self,
+        a_to_b: bool,
+    ) -> Result<SwapSimulationInput, std::io::Error> {
+        let whirlpool = self.decode_whirlpool()?;
+        let oracle = self.decode_oracle()?;
+        let snapshot_tick_arrays: Vec<TickArrayFacade> = self
+            .decode_tick_arrays()?
+            .into_iter()
+            .filter(|(_, tick_array)| tick_array_whirlpool(tick_array) == self.whirlpool)
+            .map(|(_, tick_array)| tick_array.into())
+            .collect();
+
+        let tick_arrays: TickArrays = get_swap_tick_array_start_tick_indexes(
+            whirlpool.tick_current_index,
+            whirlpool.tick_spacing,
+            a_to_b,
+            MAX_SWAP_TICK_ARRAYS,
+        )
+        .into_iter()
+        .map(|start_tick_index| {
+            snapshot_tick_arrays
+                .iter()
+                .find(|x| x.start_tick_index == start_tick_index)
+                .copied()
+                .unwrap_or(TickArrayFacade {
+                    start_tick_index,
+                    ticks: [TickFacade::default(); TICK_ARRAY_SIZE],
+                })
+        })
+        .collect();
+
+        Ok(SwapSimulationInput {
+            whirlpool: whirlpool.into(),
+            oracle: oracle.map(|x| x.into()),
+            tick_arrays,
+            timestamp: self.unix_timestamp.max(0) as u64,
+        })
+    }
+}
+
+fn tick_array_whirlpool(tick_array: &TickArray) -> Pubkey {
+    match tick_array {
+        TickArray::FixedTickArray(x) => x.whirlpool,
+        TickArray::DynamicTickArray(x) => x.whirlpool,
+    }
+}
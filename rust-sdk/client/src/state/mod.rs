@@ -8,6 +8,7 @@ mod position;
 mod position_bundle;
 mod tick_array;
 mod token_badge;
+mod token_badge_history;
 mod whirlpool;
 mod whirlpools_config;
 mod whirlpools_config_extension;
@@ -26,6 +27,7 @@ pub use position::*;
 pub use position_bundle::*;
 pub use tick_array::*;
 pub use token_badge::*;
+pub use token_badge_history::*;
 pub use whirlpool::*;
 pub use whirlpools_config::*;
 pub use whirlpools_config_extension::*;
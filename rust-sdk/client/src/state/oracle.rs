@@ -1 +1,89 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::Oracle;
+
 pub const ORACLE_DISCRIMINATOR: &[u8] = &[139, 194, 131, 179, 140, 179, 229, 244];
+
+/// A cumulative price observation, stored in the Oracle account after the Oracle data.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OracleObservation {
+    pub timestamp: u64,
+    pub tick_cumulative: i64,
+    pub sqrt_price_cumulative: u128,
+}
+
+impl OracleObservation {
+    pub const LEN: usize = 32;
+}
+
+/// An Oracle account together with the price observations that follow it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OracleWithObservations {
+    pub oracle: Oracle,
+    pub observations: Vec<OracleObservation>,
+}
+
+impl OracleWithObservations {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, std::io::Error> {
+        if bytes.len() < Oracle::LEN {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Invalid account data length",
+            ));
+        }
+        if &bytes[0..8] != ORACLE_DISCRIMINATOR {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Invalid account discriminator",
+            ));
+        }
+        let oracle = Oracle::from_bytes(&bytes[..Oracle::LEN])?;
+        let observations = bytes[Oracle::LEN..]
+            .chunks_exact(OracleObservation::LEN)
+            .map(OracleObservation::try_from_slice)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            oracle,
+            observations,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_oracle_without_observations() {
+        let mut data = vec![0u8; Oracle::LEN];
+        data[0..8].copy_from_slice(ORACLE_DISCRIMINATOR);
+        let result = OracleWithObservations::from_bytes(&data).unwrap();
+        assert!(result.observations.is_empty());
+    }
+
+    #[test]
+    fn test_decode_oracle_with_observations() {
+        let observation = OracleObservation {
+            timestamp: 1_000,
+            tick_cumulative: -10_000,
+            sqrt_price_cumulative: 600 << 64,
+        };
+        let mut data = vec![0u8; Oracle::LEN];
+        data[0..8].copy_from_slice(ORACLE_DISCRIMINATOR);
+        data.extend(observation.try_to_vec().unwrap());
+        data.extend([0u8; OracleObservation::LEN]);
+
+        let result = OracleWithObservations::from_bytes(&data).unwrap();
+        assert_eq!(
+            result.observations,
+            vec![observation, OracleObservation::default()]
+        );
+    }
+
+    #[test]
+    fn test_decode_invalid_oracle() {
+        assert!(OracleWithObservations::from_bytes(&[0u8; 8]).is_err());
+        assert!(OracleWithObservations::from_bytes(&vec![0u8; Oracle::LEN]).is_err());
+    }
+}
@@ -0,0 +1 @@
+pub const TOKEN_BADGE_HISTORY_DISCRIMINATOR: &[u8] = &[57, 76, 87, 55, 199, 100, 190, 216];
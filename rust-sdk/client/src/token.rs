@@ -0,0 +1,469 @@
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+
+use crate::generated::accounts::TokenBadge;
+use crate::generated::programs::WHIRLPOOL_ID;
+
+const TOKEN_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+const TOKEN_2022_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+const TOKEN_2022_NATIVE_MINT: Pubkey =
+    solana_program::pubkey!("9pan9bMn5HatX4EJdBwg9VgCa7Uz5HL8N1m5D3NdXejP");
+
+// Layout of a Token-2022 mint: the 82 byte base mint, padding up to the size of a token
+// account, the account type and the extensions as type-length-value entries.
+const MINT_LEN: usize = 82;
+const ACCOUNT_LEN: usize = 165;
+const ACCOUNT_TYPE_MINT: u8 = 1;
+const FREEZE_AUTHORITY_OPTION_OFFSET: usize = 46;
+
+/// The extensions of the Token-2022 program, numbered as in `spl_token_2022::extension::ExtensionType`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenExtension {
+    TransferFeeConfig,
+    TransferFeeAmount,
+    MintCloseAuthority,
+    ConfidentialTransferMint,
+    ConfidentialTransferAccount,
+    DefaultAccountState,
+    ImmutableOwner,
+    MemoTransfer,
+    NonTransferable,
+    InterestBearingConfig,
+    CpiGuard,
+    PermanentDelegate,
+    NonTransferableAccount,
+    TransferHook,
+    TransferHookAccount,
+    ConfidentialTransferFeeConfig,
+    ConfidentialTransferFeeAmount,
+    MetadataPointer,
+    TokenMetadata,
+    GroupPointer,
+    TokenGroup,
+    GroupMemberPointer,
+    TokenGroupMember,
+    ConfidentialMintBurn,
+    ScaledUiAmount,
+    Pausable,
+    PausableAccount,
+    /// An extension type this client does not know about.
+    Unknown(u16),
+}
+
+impl TokenExtension {
+    fn from_type(extension_type: u16) -> Self {
+        match extension_type {
+            1 => Self::TransferFeeConfig,
+            2 => Self::TransferFeeAmount,
+            3 => Self::MintCloseAuthority,
+            4 => Self::ConfidentialTransferMint,
+            5 => Self::ConfidentialTransferAccount,
+            6 => Self::DefaultAccountState,
+            7 => Self::ImmutableOwner,
+            8 => Self::MemoTransfer,
+            9 => Self::NonTransferable,
+            10 => Self::InterestBearingConfig,
+            11 => Self::CpiGuard,
+            12 => Self::PermanentDelegate,
+            13 => Self::NonTransferableAccount,
+            14 => Self::TransferHook,
+            15 => Self::TransferHookAccount,
+            16 => Self::ConfidentialTransferFeeConfig,
+            17 => Self::ConfidentialTransferFeeAmount,
+            18 => Self::MetadataPointer,
+            19 => Self::TokenMetadata,
+            20 => Self::GroupPointer,
+            21 => Self::TokenGroup,
+            22 => Self::GroupMemberPointer,
+            23 => Self::TokenGroupMember,
+            24 => Self::ConfidentialMintBurn,
+            25 => Self::ScaledUiAmount,
+            26 => Self::Pausable,
+            27 => Self::PausableAccount,
+            _ => Self::Unknown(extension_type),
+        }
+    }
+
+    /// Whether the Whirlpool program accepts a mint with this extension, mirroring
+    /// `is_supported_token_mint` of the program.
+    fn support(&self) -> (TokenMintSupport, &'static str) {
+        match self {
+            Self::TransferFeeConfig => (
+                TokenMintSupport::Supported,
+                "Transfer fees are deducted from transfers in and out of the pool",
+            ),
+            Self::InterestBearingConfig => (
+                TokenMintSupport::Supported,
+                "Interest only changes the UI amount",
+            ),
+            Self::ScaledUiAmount => (
+                TokenMintSupport::Supported,
+                "The multiplier only changes the UI amount",
+            ),
+            Self::TokenMetadata | Self::MetadataPointer => (
+                TokenMintSupport::Supported,
+                "Metadata does not affect transfers",
+            ),
+            Self::ConfidentialTransferMint | Self::ConfidentialTransferFeeConfig => (
+                TokenMintSupport::Supported,
+                "Only non-confidential transfers can be used with pools",
+            ),
+            Self::PermanentDelegate => (
+                TokenMintSupport::RequiresTokenBadge,
+                "The permanent delegate can move tokens out of the pool vaults",
+            ),
+            Self::TransferHook => (
+                TokenMintSupport::RequiresTokenBadge,
+                "The transfer hook program runs on every transfer of the pool",
+            ),
+            Self::MintCloseAuthority => (
+                TokenMintSupport::RequiresTokenBadge,
+                "The mint can be closed and reinitialized with other extensions",
+            ),
+            Self::DefaultAccountState => (
+                TokenMintSupport::RequiresTokenBadge,
+                "New token accounts, including the pool vaults, may start frozen",
+            ),
+            Self::Pausable => (
+                TokenMintSupport::RequiresTokenBadge,
+                "Transfers, including swaps, can be paused",
+            ),
+            Self::NonTransferable => (
+                TokenMintSupport::Unsupported,
+                "Tokens of the mint cannot be transferred",
+            ),
+            Self::Unknown(_) => (
+                TokenMintSupport::Unsupported,
+                "The extension is unknown to the Whirlpool program",
+            ),
+            _ => (
+                TokenMintSupport::Unsupported,
+                "The extension is not supported by the Whirlpool program",
+            ),
+        }
+    }
+}
+
+/// Whether the Whirlpool program accepts a mint, or one of its properties, in a pool.
+///
+/// The variants are ordered from least to most restrictive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TokenMintSupport {
+    Supported,
+    /// Supported once the config's token badge authority has initialized a token badge for
+    /// the mint.
+    RequiresTokenBadge,
+    Unsupported,
+}
+
+/// A property of a mint that decides whether it is supported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenMintProperty {
+    TokenProgram,
+    NativeMint,
+    FreezeAuthority,
+    Extension(TokenExtension),
+}
+
+/// The verdict for a single property of a mint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenMintCheck {
+    pub property: TokenMintProperty,
+    pub support: TokenMintSupport,
+    pub reason: &'static str,
+}
+
+/// The compatibility of a mint with the Whirlpool program, with a verdict for every
+/// property and extension of the mint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenMintCompatibility {
+    pub checks: Vec<TokenMintCheck>,
+}
+
+impl TokenMintCompatibility {
+    /// The most restrictive verdict of all checks.
+    pub fn support(&self) -> TokenMintSupport {
+        self.checks
+            .iter()
+            .map(|x| x.support)
+            .max()
+            .unwrap_or(TokenMintSupport::Supported)
+    }
+
+    /// Whether pools and rewards can be initialized with the mint, the same way the program
+    /// decides it.
+    pub fn is_supported(&self, is_token_badge_initialized: bool) -> bool {
+        self.blocking_checks(is_token_badge_initialized)
+            .next()
+            .is_none()
+    }
+
+    /// The checks that prevent the mint from being supported.
+    pub fn blocking_checks(
+        &self,
+        is_token_badge_initialized: bool,
+    ) -> impl Iterator<Item = &TokenMintCheck> {
+        self.checks.iter().filter(move |x| match x.support {
+            TokenMintSupport::Supported => false,
+            TokenMintSupport::RequiresTokenBadge => !is_token_badge_initialized,
+            TokenMintSupport::Unsupported => true,
+        })
+    }
+}
+
+/// Checks whether the Whirlpool program supports a mint in pools and rewards, and why.
+///
+/// This mirrors `is_supported_token_mint` of the program, so that a mint can be checked
+/// before an instruction fails with `UnsupportedTokenMint`.
+///
+/// # Arguments
+///
+/// * `mint_address` - The address of the mint.
+/// * `mint_owner` - The program that owns the mint account.
+/// * `mint_data` - The data of the mint account.
+///
+/// # Returns
+///
+/// A `Result` containing the `TokenMintCompatibility` of the mint, or an error if the mint
+/// data is malformed.
+pub fn check_token_mint_compatibility(
+    mint_address: &Pubkey,
+    mint_owner: &Pubkey,
+    mint_data: &[u8],
+) -> Result<TokenMintCompatibility, ProgramError> {
+    if mint_data.len() < MINT_LEN {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let mut checks = Vec::new();
+
+    if *mint_owner == TOKEN_PROGRAM_ID {
+        checks.push(TokenMintCheck {
+            property: TokenMintProperty::TokenProgram,
+            support: TokenMintSupport::Supported,
+            reason: "Mints of the Token program are always supported",
+        });
+        return Ok(TokenMintCompatibility { checks });
+    }
+
+    if *mint_owner != TOKEN_2022_PROGRAM_ID {
+        checks.push(TokenMintCheck {
+            property: TokenMintProperty::TokenProgram,
+            support: TokenMintSupport::Unsupported,
+            reason: "The mint is not owned by a token program",
+        });
+        return Ok(TokenMintCompatibility { checks });
+    }
+
+    if *mint_address == TOKEN_2022_NATIVE_MINT {
+        checks.push(TokenMintCheck {
+            property: TokenMintProperty::NativeMint,
+            support: TokenMintSupport::Unsupported,
+            reason: "The Token-2022 native mint is rejected to avoid fragmenting SOL liquidity",
+        });
+    }
+
+    if read_u32(mint_data, FREEZE_AUTHORITY_OPTION_OFFSET)? != 0 {
+        checks.push(TokenMintCheck {
+            property: TokenMintProperty::FreezeAuthority,
+            support: TokenMintSupport::RequiresTokenBadge,
+            reason: "The freeze authority can freeze the pool vaults",
+        });
+    }
+
+    for extension in get_token_extensions(mint_data)? {
+        let (support, reason) = extension.support();
+        checks.push(TokenMintCheck {
+            property: TokenMintProperty::Extension(extension),
+            support,
+            reason,
+        });
+    }
+
+    Ok(TokenMintCompatibility { checks })
+}
+
+/// Checks whether a token badge account is initialized for a mint in a whirlpools config,
+/// the same way the program does.
+pub fn is_token_badge_initialized(
+    whirlpools_config: &Pubkey,
+    token_mint: &Pubkey,
+    token_badge_owner: &Pubkey,
+    token_badge_data: &[u8],
+) -> bool {
+    if *token_badge_owner != WHIRLPOOL_ID {
+        return false;
+    }
+    TokenBadge::from_bytes(token_badge_data)
+        .is_ok_and(|x| x.whirlpools_config == *whirlpools_config && x.token_mint == *token_mint)
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16, ProgramError> {
+    data.get(offset..offset + 2)
+        .map(|x| u16::from_le_bytes([x[0], x[1]]))
+        .ok_or(ProgramError::InvalidAccountData)
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32, ProgramError> {
+    data.get(offset..offset + 4)
+        .map(|x| u32::from_le_bytes([x[0], x[1], x[2], x[3]]))
+        .ok_or(ProgramError::InvalidAccountData)
+}
+
+// reference implementation: get_token_extension_types of the program
+fn get_token_extensions(mint_data: &[u8]) -> Result<Vec<TokenExtension>, ProgramError> {
+    if mint_data.len() == MINT_LEN {
+        return Ok(Vec::new());
+    }
+    if mint_data.len() <= ACCOUNT_LEN || mint_data[ACCOUNT_LEN] != ACCOUNT_TYPE_MINT {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let tlv_data = &mint_data[ACCOUNT_LEN + 1..];
+    let mut extensions = Vec::new();
+    let mut cursor = 0;
+    while cursor + 2 <= tlv_data.len() {
+        let extension_type = read_u16(tlv_data, cursor)?;
+        if extension_type == 0 {
+            break;
+        }
+        let length = read_u16(tlv_data, cursor + 2)? as usize;
+        let value_end = cursor + 4 + length;
+        if value_end > tlv_data.len() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        extensions.push(TokenExtension::from_type(extension_type));
+        cursor = value_end;
+    }
+    Ok(extensions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mint_data(freeze_authority: bool, extensions: &[(u16, usize)]) -> Vec<u8> {
+        let mut data = vec![0u8; MINT_LEN];
+        data[45] = 1;
+        if freeze_authority {
+            data[FREEZE_AUTHORITY_OPTION_OFFSET] = 1;
+        }
+        if extensions.is_empty() {
+            return data;
+        }
+        data.resize(ACCOUNT_LEN, 0);
+        data.push(ACCOUNT_TYPE_MINT);
+        for (extension_type, length) in extensions {
+            data.extend_from_slice(&extension_type.to_le_bytes());
+            data.extend_from_slice(&(*length as u16).to_le_bytes());
+            data.extend(std::iter::repeat(0).take(*length));
+        }
+        data
+    }
+
+    #[test]
+    fn test_token_program_mint_is_supported() {
+        let data = mint_data(true, &[]);
+        let result =
+            check_token_mint_compatibility(&Pubkey::new_unique(), &TOKEN_PROGRAM_ID, &data)
+                .unwrap();
+        assert_eq!(result.support(), TokenMintSupport::Supported);
+        assert!(result.is_supported(false));
+    }
+
+    #[test]
+    fn test_token_2022_mint_without_extensions() {
+        let data = mint_data(false, &[]);
+        let result =
+            check_token_mint_compatibility(&Pubkey::new_unique(), &TOKEN_2022_PROGRAM_ID, &data)
+                .unwrap();
+        assert!(result.checks.is_empty());
+        assert!(result.is_supported(false));
+    }
+
+    #[test]
+    fn test_supported_extensions() {
+        let data = mint_data(false, &[(1, 108), (10, 52), (18, 64), (25, 56)]);
+        let result =
+            check_token_mint_compatibility(&Pubkey::new_unique(), &TOKEN_2022_PROGRAM_ID, &data)
+                .unwrap();
+        let extensions: Vec<TokenMintProperty> = result.checks.iter().map(|x| x.property).collect();
+        assert_eq!(
+            extensions,
+            vec![
+                TokenMintProperty::Extension(TokenExtension::TransferFeeConfig),
+                TokenMintProperty::Extension(TokenExtension::InterestBearingConfig),
+                TokenMintProperty::Extension(TokenExtension::MetadataPointer),
+                TokenMintProperty::Extension(TokenExtension::ScaledUiAmount),
+            ]
+        );
+        assert_eq!(result.support(), TokenMintSupport::Supported);
+    }
+
+    #[test]
+    fn test_extensions_requiring_token_badge() {
+        let data = mint_data(true, &[(12, 32), (14, 64)]);
+        let result =
+            check_token_mint_compatibility(&Pubkey::new_unique(), &TOKEN_2022_PROGRAM_ID, &data)
+                .unwrap();
+        assert_eq!(result.support(), TokenMintSupport::RequiresTokenBadge);
+        assert_eq!(result.blocking_checks(false).count(), 3);
+        assert!(!result.is_supported(false));
+        assert!(result.is_supported(true));
+    }
+
+    #[test]
+    fn test_unsupported_extensions() {
+        let data = mint_data(false, &[(9, 0), (12, 32)]);
+        let result =
+            check_token_mint_compatibility(&Pubkey::new_unique(), &TOKEN_2022_PROGRAM_ID, &data)
+                .unwrap();
+        assert_eq!(result.support(), TokenMintSupport::Unsupported);
+        assert!(!result.is_supported(true));
+        let blocking: Vec<&TokenMintCheck> = result.blocking_checks(true).collect();
+        assert_eq!(
+            blocking[0].property,
+            TokenMintProperty::Extension(TokenExtension::NonTransferable)
+        );
+
+        let data = mint_data(false, &[(99, 0)]);
+        let result =
+            check_token_mint_compatibility(&Pubkey::new_unique(), &TOKEN_2022_PROGRAM_ID, &data)
+                .unwrap();
+        assert_eq!(
+            result.checks[0].property,
+            TokenMintProperty::Extension(TokenExtension::Unknown(99))
+        );
+        assert!(!result.is_supported(true));
+    }
+
+    #[test]
+    fn test_native_mint_is_unsupported() {
+        let data = mint_data(false, &[]);
+        let result =
+            check_token_mint_compatibility(&TOKEN_2022_NATIVE_MINT, &TOKEN_2022_PROGRAM_ID, &data)
+                .unwrap();
+        assert!(!result.is_supported(true));
+    }
+
+    #[test]
+    fn test_malformed_mint() {
+        let mut data = mint_data(false, &[(1, 108)]);
+        data.truncate(data.len() - 1);
+        assert!(check_token_mint_compatibility(
+            &Pubkey::new_unique(),
+            &TOKEN_2022_PROGRAM_ID,
+            &data
+        )
+        .is_err());
+        assert!(check_token_mint_compatibility(
+            &Pubkey::new_unique(),
+            &TOKEN_2022_PROGRAM_ID,
+            &[0; 10]
+        )
+        .is_err());
+    }
+}
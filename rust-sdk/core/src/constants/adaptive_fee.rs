@@ -23,3 +23,7 @@ pub const MAX_REFERENCE_AGE: u64 = 3_600;
 /// Fee rate is represented as hundredths of a basis point.
 #[cfg_attr(feature = "wasm", wasm_expose)]
 pub const FEE_RATE_HARD_LIMIT: u32 = 100_000; // 10%
+
+/// The maximum time (in seconds) between the creation of a pool and its trade enable timestamp.
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub const MAX_TRADE_ENABLE_TIMESTAMP_DELTA: u64 = 259_200; // 72 hours
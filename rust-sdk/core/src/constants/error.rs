@@ -52,3 +52,21 @@ pub const INVALID_TICK_ARRAY_SEQUENCE: CoreError = "Invalid tick array sequence"
 
 #[cfg_attr(feature = "wasm", wasm_expose)]
 pub const INVALID_ADAPTIVE_FEE_INFO: CoreError = "Invalid adaptive fee info";
+
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub const INVALID_REWARD_INDEX: CoreError = "Invalid reward index";
+
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub const INVALID_EMISSIONS_DURATION: CoreError = "Invalid emissions duration";
+
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub const ORACLE_OBSERVATIONS_NOT_INITIALIZED: CoreError = "Oracle observations not initialized";
+
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub const ORACLE_OBSERVATION_TOO_OLD: CoreError = "Oracle observation too old";
+
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub const INVALID_TWAP_WINDOW: CoreError = "Invalid TWAP window";
+
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub const INVALID_RANGE_ORDER_RANGE: CoreError = "Invalid range order range";
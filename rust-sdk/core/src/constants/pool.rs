@@ -6,3 +6,7 @@ use orca_whirlpools_macros::wasm_expose;
 /// The number of reward tokens in a pool.
 #[cfg_attr(feature = "wasm", wasm_expose)]
 pub const NUM_REWARDS: usize = 3;
+
+/// The number of seconds of emissions a reward vault must hold when the emissions rate is set.
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub const REWARD_VAULT_MIN_EMISSIONS_DURATION: u64 = 86_400;
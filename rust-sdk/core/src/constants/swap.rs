@@ -7,6 +7,11 @@ use orca_whirlpools_macros::wasm_expose;
 #[cfg_attr(feature = "wasm", wasm_expose)]
 pub const FEE_RATE_DENOMINATOR: u32 = 1_000_000;
 
+/// The maximum number of tick arrays a single swap instruction can traverse.
+/// The three tick arrays of the instruction and up to three supplemental tick arrays.
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub const MAX_SWAP_TICK_ARRAYS: u32 = 6;
+
 // TODO: WASM export (which doesn't work with u128 yet)
 
 /// The minimum sqrt price for a whirlpool.
@@ -1 +1,4 @@
 pub const BPS_DENOMINATOR: u16 = 10000;
+
+/// The number of seconds in a year, as used by the interest-bearing extension of Token-2022.
+pub const SECONDS_PER_YEAR: f64 = 31_556_736.0;
@@ -1,9 +1,9 @@
 use super::{sqrt_price_to_tick_index, tick_index_to_sqrt_price};
 use crate::{
     AdaptiveFeeConstantsFacade, AdaptiveFeeInfo, AdaptiveFeeVariablesFacade, CoreError,
-    WhirlpoolFacade, ADAPTIVE_FEE_CONTROL_FACTOR_DENOMINATOR, FEE_RATE_HARD_LIMIT,
-    INVALID_TIMESTAMP, MAX_REFERENCE_AGE, MAX_TICK_INDEX, MIN_TICK_INDEX,
-    REDUCTION_FACTOR_DENOMINATOR, VOLATILITY_ACCUMULATOR_SCALE_FACTOR,
+    OracleFacade, WhirlpoolFacade, ADAPTIVE_FEE_CONTROL_FACTOR_DENOMINATOR, FEE_RATE_HARD_LIMIT,
+    INVALID_ADAPTIVE_FEE_INFO, INVALID_TIMESTAMP, MAX_REFERENCE_AGE, MAX_TICK_INDEX,
+    MIN_TICK_INDEX, REDUCTION_FACTOR_DENOMINATOR, VOLATILITY_ACCUMULATOR_SCALE_FACTOR,
 };
 use ethnum::U256;
 #[cfg(feature = "wasm")]
@@ -21,6 +21,81 @@ pub fn is_initialized_with_adaptive_fee(whirlpool: WhirlpoolFacade) -> bool {
     whirlpool.is_initialized_with_adaptive_fee()
 }
 
+/// Get the total fee rate a swap pays in each tick group it crosses
+///
+/// The first entry is the fee rate in the tick group of the current tick index, each following
+/// entry is the fee rate one tick group further in the direction of the swap.
+///
+/// # Paramters
+/// - `whirlpool`: The whirlpool state
+/// - `oracle`: The oracle data for the whirlpool
+/// - `timestamp`: The timestamp at which the swap is executed
+/// - `a_to_b`: The direction of the swap
+/// - `tick_group_count`: The number of tick groups to return the fee rate for
+///
+/// # Returns
+/// - The total fee rate (static + adaptive) for each tick group crossed
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn get_adaptive_fee_rates_by_tick_group(
+    whirlpool: WhirlpoolFacade,
+    oracle: OracleFacade,
+    timestamp: u64,
+    a_to_b: bool,
+    tick_group_count: u32,
+) -> Result<Vec<u32>, CoreError> {
+    if !whirlpool.is_initialized_with_adaptive_fee() {
+        return Err(INVALID_ADAPTIVE_FEE_INFO);
+    }
+
+    let mut fee_rate_manager = FeeRateManager::new(
+        a_to_b,
+        whirlpool.tick_current_index,
+        timestamp,
+        whirlpool.fee_rate,
+        &Some(oracle.into()),
+    )?;
+
+    let mut fee_rates = Vec::with_capacity(tick_group_count as usize);
+    for _ in 0..tick_group_count {
+        fee_rate_manager.update_volatility_accumulator();
+        fee_rates.push(fee_rate_manager.get_total_fee_rate());
+        fee_rate_manager.advance_tick_group();
+    }
+    Ok(fee_rates)
+}
+
+/// Get the timestamps after `timestamp` at which the volatility reference of the oracle decays
+///
+/// Without further major swaps, the volatility reference (and with it the adaptive fee) only changes
+/// once the filter period or the decay period has passed since the last update, or once the
+/// reference is older than `MAX_REFERENCE_AGE`. A swap executed at any time between two consecutive
+/// timestamps pays the same fee rate.
+///
+/// # Paramters
+/// - `oracle`: The oracle data for the whirlpool
+/// - `timestamp`: The current timestamp
+///
+/// # Returns
+/// - The timestamps in ascending order
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn get_adaptive_fee_decay_timestamps(oracle: OracleFacade, timestamp: u64) -> Vec<u64> {
+    let constants = oracle.adaptive_fee_constants;
+    let variables = oracle.adaptive_fee_variables;
+    let max_timestamp = variables
+        .last_reference_update_timestamp
+        .max(variables.last_major_swap_timestamp);
+
+    let mut timestamps = vec![
+        max_timestamp + constants.filter_period as u64,
+        max_timestamp + constants.decay_period as u64,
+        variables.last_reference_update_timestamp + MAX_REFERENCE_AGE + 1,
+    ];
+    timestamps.retain(|&x| x > timestamp);
+    timestamps.sort_unstable();
+    timestamps.dedup();
+    timestamps
+}
+
 #[derive(Debug)]
 pub(crate) enum FeeRateManager {
     Adaptive {
@@ -0,0 +1,161 @@
+#[cfg(feature = "wasm")]
+use orca_whirlpools_macros::wasm_expose;
+
+use crate::{FeeRateScheduleFacade, OracleFacade, WhirlpoolFacade};
+
+/// Get the fee rate of a whirlpool at a given timestamp
+///
+/// Scheduled fee rate changes are applied by swaps, so the fee rate stored in the whirlpool
+/// is outdated while a change is ramping or once it has become effective without any swap.
+///
+/// # Parameters
+/// - `whirlpool`: The current state of the whirlpool
+/// - `oracle`: The oracle data for the whirlpool, if it has one
+/// - `timestamp`: The timestamp to get the fee rate for
+///
+/// # Returns
+/// - `u16`: The fee rate used by a swap executed at `timestamp`
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn get_fee_rate_at_timestamp(
+    whirlpool: WhirlpoolFacade,
+    oracle: Option<OracleFacade>,
+    timestamp: u64,
+) -> u16 {
+    oracle
+        .and_then(|oracle| {
+            oracle
+                .fee_rate_schedule
+                .fee_rate_for(whirlpool.fee_rate, timestamp)
+        })
+        .unwrap_or(whirlpool.fee_rate)
+}
+
+impl FeeRateScheduleFacade {
+    pub fn is_scheduled(&self) -> bool {
+        self.effective_timestamp != 0
+    }
+
+    pub fn fee_rate_at(&self, timestamp: u64) -> Option<u16> {
+        if !self.is_scheduled() || timestamp < self.ramp_start_timestamp {
+            return None;
+        }
+        if timestamp >= self.effective_timestamp {
+            return Some(self.target_fee_rate);
+        }
+
+        let elapsed = (timestamp - self.ramp_start_timestamp) as i64;
+        let duration = (self.effective_timestamp - self.ramp_start_timestamp) as i64;
+        let start_fee_rate = self.start_fee_rate as i64;
+        let target_fee_rate = self.target_fee_rate as i64;
+        let fee_rate = start_fee_rate + (target_fee_rate - start_fee_rate) * elapsed / duration;
+        Some(fee_rate as u16)
+    }
+
+    // A schedule no longer applies once the fee rate of the whirlpool has been set directly,
+    // that is to a fee rate that the schedule has not moved through by the given timestamp.
+    pub fn fee_rate_for(&self, whirlpool_fee_rate: u16, timestamp: u64) -> Option<u16> {
+        let fee_rate = self.fee_rate_at(timestamp)?;
+        let lower_fee_rate = self.start_fee_rate.min(fee_rate);
+        let upper_fee_rate = self.start_fee_rate.max(fee_rate);
+        if whirlpool_fee_rate < lower_fee_rate || whirlpool_fee_rate > upper_fee_rate {
+            return None;
+        }
+        Some(fee_rate)
+    }
+}
+
+#[cfg(all(test, not(feature = "wasm")))]
+mod tests {
+    use super::*;
+
+    fn test_whirlpool() -> WhirlpoolFacade {
+        WhirlpoolFacade {
+            fee_rate: 3000,
+            ..WhirlpoolFacade::default()
+        }
+    }
+
+    fn test_oracle(ramp_start_timestamp: u64, effective_timestamp: u64) -> OracleFacade {
+        OracleFacade {
+            fee_rate_schedule: FeeRateScheduleFacade {
+                start_fee_rate: 3000,
+                target_fee_rate: 1000,
+                ramp_start_timestamp,
+                effective_timestamp,
+            },
+            ..OracleFacade::default()
+        }
+    }
+
+    #[test]
+    fn test_no_schedule() {
+        assert_eq!(
+            get_fee_rate_at_timestamp(test_whirlpool(), None, 1_000),
+            3000
+        );
+        assert_eq!(
+            get_fee_rate_at_timestamp(test_whirlpool(), Some(OracleFacade::default()), 1_000),
+            3000
+        );
+    }
+
+    #[test]
+    fn test_step_change() {
+        let oracle = Some(test_oracle(2_000, 2_000));
+        assert_eq!(
+            get_fee_rate_at_timestamp(test_whirlpool(), oracle, 1_999),
+            3000
+        );
+        assert_eq!(
+            get_fee_rate_at_timestamp(test_whirlpool(), oracle, 2_000),
+            1000
+        );
+        assert_eq!(
+            get_fee_rate_at_timestamp(test_whirlpool(), oracle, u64::MAX),
+            1000
+        );
+    }
+
+    #[test]
+    fn test_ramp() {
+        let oracle = Some(test_oracle(2_000, 3_000));
+        assert_eq!(
+            get_fee_rate_at_timestamp(test_whirlpool(), oracle, 1_999),
+            3000
+        );
+        assert_eq!(
+            get_fee_rate_at_timestamp(test_whirlpool(), oracle, 2_000),
+            3000
+        );
+        assert_eq!(
+            get_fee_rate_at_timestamp(test_whirlpool(), oracle, 2_250),
+            2500
+        );
+        assert_eq!(
+            get_fee_rate_at_timestamp(test_whirlpool(), oracle, 2_999),
+            1002
+        );
+        assert_eq!(
+            get_fee_rate_at_timestamp(test_whirlpool(), oracle, 3_000),
+            1000
+        );
+    }
+
+    #[test]
+    fn test_fee_rate_set_directly() {
+        let oracle = Some(test_oracle(2_000, 3_000));
+        let whirlpool = WhirlpoolFacade {
+            fee_rate: 5000,
+            ..WhirlpoolFacade::default()
+        };
+        assert_eq!(get_fee_rate_at_timestamp(whirlpool, oracle, 2_250), 5000);
+        assert_eq!(get_fee_rate_at_timestamp(whirlpool, oracle, 3_000), 5000);
+
+        let whirlpool = WhirlpoolFacade {
+            fee_rate: 2000,
+            ..WhirlpoolFacade::default()
+        };
+        assert_eq!(get_fee_rate_at_timestamp(whirlpool, oracle, 2_250), 2000);
+        assert_eq!(get_fee_rate_at_timestamp(whirlpool, oracle, 2_750), 1500);
+    }
+}
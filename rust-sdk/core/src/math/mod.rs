@@ -1,6 +1,9 @@
 mod adaptive_fee;
 mod bundle;
+mod fee_rate_schedule;
+mod oracle;
 mod position;
+mod reward;
 mod tick;
 mod token;
 
@@ -10,9 +13,15 @@ mod tick_array;
 #[cfg(feature = "floats")]
 mod price;
 
+#[cfg(feature = "floats")]
+mod ui_amount;
+
 pub use adaptive_fee::*;
 pub use bundle::*;
+pub use fee_rate_schedule::*;
+pub use oracle::*;
 pub use position::*;
+pub use reward::*;
 pub use tick::*;
 
 pub use token::*;
@@ -20,5 +29,8 @@ pub use token::*;
 #[cfg(feature = "floats")]
 pub use price::*;
 
+#[cfg(feature = "floats")]
+pub use ui_amount::*;
+
 #[cfg(feature = "swap")]
 pub use tick_array::*;
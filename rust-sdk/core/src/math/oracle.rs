@@ -0,0 +1,330 @@
+#[cfg(feature = "wasm")]
+use orca_whirlpools_macros::wasm_expose;
+
+use crate::{
+    CoreError, OracleObservationFacade, OracleObservationsFacade, OracleTwap, WhirlpoolFacade,
+    INVALID_TWAP_WINDOW, ORACLE_OBSERVATIONS_NOT_INITIALIZED, ORACLE_OBSERVATION_TOO_OLD,
+};
+
+/// Get the cumulative tick index and sqrt price of a whirlpool at a point in the past
+///
+/// # Parameters
+/// - `observations`: The observations stored in the oracle account of the whirlpool
+/// - `whirlpool`: The current state of the whirlpool
+/// - `timestamp`: The current timestamp
+/// - `seconds_ago`: How many seconds before `timestamp` to get the observation for
+///
+/// # Returns
+/// - `OracleObservationFacade`: The (interpolated) observation at `timestamp - seconds_ago`
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn try_get_oracle_observation(
+    observations: OracleObservationsFacade,
+    whirlpool: WhirlpoolFacade,
+    timestamp: u64,
+    seconds_ago: u32,
+) -> Result<OracleObservationFacade, CoreError> {
+    observe(&observations, &whirlpool, timestamp, seconds_ago)
+}
+
+/// Get the time-weighted average tick index and sqrt price of a whirlpool
+///
+/// The tick index is rounded towards negative infinity, the sqrt price is rounded down.
+///
+/// # Parameters
+/// - `observations`: The observations stored in the oracle account of the whirlpool
+/// - `whirlpool`: The current state of the whirlpool
+/// - `timestamp`: The current timestamp
+/// - `window`: The length of the averaging window in seconds, ending at `timestamp`
+///
+/// # Returns
+/// - `OracleTwap`: The time-weighted average tick index and sqrt price
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn try_get_oracle_twap(
+    observations: OracleObservationsFacade,
+    whirlpool: WhirlpoolFacade,
+    timestamp: u64,
+    window: u32,
+) -> Result<OracleTwap, CoreError> {
+    if window == 0 {
+        return Err(INVALID_TWAP_WINDOW);
+    }
+
+    let current = observe(&observations, &whirlpool, timestamp, 0)?;
+    let past = observe(&observations, &whirlpool, timestamp, window)?;
+
+    let tick_delta = current.tick_cumulative.wrapping_sub(past.tick_cumulative);
+    let tick_index = tick_delta.div_euclid(window.into());
+    let sqrt_price = current
+        .sqrt_price_cumulative
+        .wrapping_sub(past.sqrt_price_cumulative)
+        / u128::from(window);
+
+    Ok(OracleTwap {
+        tick_index: tick_index as i32,
+        sqrt_price,
+    })
+}
+
+fn observe(
+    observations: &OracleObservationsFacade,
+    whirlpool: &WhirlpoolFacade,
+    timestamp: u64,
+    seconds_ago: u32,
+) -> Result<OracleObservationFacade, CoreError> {
+    let cardinality = observations.observation_cardinality as usize;
+    let index = observations.observation_index as usize;
+    if cardinality == 0 || index >= cardinality || observations.observations.len() < cardinality {
+        return Err(ORACLE_OBSERVATIONS_NOT_INITIALIZED);
+    }
+    let observations = &observations.observations[..cardinality];
+
+    let target = timestamp
+        .checked_sub(seconds_ago.into())
+        .ok_or(ORACLE_OBSERVATION_TOO_OLD)?;
+
+    let last = observations[index];
+    if target >= last.timestamp {
+        return Ok(transform(
+            last,
+            target,
+            whirlpool.tick_current_index,
+            whirlpool.sqrt_price,
+        ));
+    }
+
+    let mut oldest = observations[(index + 1) % cardinality];
+    if !oldest.is_initialized() {
+        oldest = observations[0];
+    }
+    if target < oldest.timestamp {
+        return Err(ORACLE_OBSERVATION_TOO_OLD);
+    }
+
+    let (before, after) = find_surrounding_observations(observations, index, target);
+    if target == before.timestamp {
+        return Ok(before);
+    }
+    if target == after.timestamp {
+        return Ok(after);
+    }
+
+    // The tick index and sqrt price are constant between two observations
+    let duration = after.timestamp - before.timestamp;
+    let tick_index = after.tick_cumulative.wrapping_sub(before.tick_cumulative) / duration as i64;
+    let sqrt_price = after
+        .sqrt_price_cumulative
+        .wrapping_sub(before.sqrt_price_cumulative)
+        / u128::from(duration);
+    Ok(transform(before, target, tick_index as i32, sqrt_price))
+}
+
+fn transform(
+    observation: OracleObservationFacade,
+    timestamp: u64,
+    tick_index: i32,
+    sqrt_price: u128,
+) -> OracleObservationFacade {
+    let elapsed = timestamp - observation.timestamp;
+    OracleObservationFacade {
+        timestamp,
+        tick_cumulative: observation
+            .tick_cumulative
+            .wrapping_add(i64::from(tick_index).wrapping_mul(elapsed as i64)),
+        sqrt_price_cumulative: observation
+            .sqrt_price_cumulative
+            .wrapping_add(sqrt_price.wrapping_mul(u128::from(elapsed))),
+    }
+}
+
+fn find_surrounding_observations(
+    observations: &[OracleObservationFacade],
+    index: usize,
+    target: u64,
+) -> (OracleObservationFacade, OracleObservationFacade) {
+    let cardinality = observations.len();
+    let mut left = index + 1;
+    let mut right = index + cardinality;
+    loop {
+        let i = (left + right) / 2;
+        let before = observations[i % cardinality];
+        if !before.is_initialized() {
+            left = i + 1;
+            continue;
+        }
+        let after = observations[(i + 1) % cardinality];
+        let target_at_or_after = before.timestamp <= target;
+        if target_at_or_after && target <= after.timestamp {
+            return (before, after);
+        }
+        if target_at_or_after {
+            left = i + 1;
+        } else {
+            right = i - 1;
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "wasm")))]
+mod tests {
+    use super::*;
+
+    // tick 100 during [1_000, 1_100), tick -200 during [1_100, 1_200), current tick 300
+    fn test_observations() -> OracleObservationsFacade {
+        OracleObservationsFacade {
+            observation_index: 2,
+            observation_cardinality: 4,
+            observations: vec![
+                OracleObservationFacade {
+                    timestamp: 1_000,
+                    tick_cumulative: 0,
+                    sqrt_price_cumulative: 0,
+                },
+                OracleObservationFacade {
+                    timestamp: 1_100,
+                    tick_cumulative: 10_000,
+                    sqrt_price_cumulative: 400 << 64,
+                },
+                OracleObservationFacade {
+                    timestamp: 1_200,
+                    tick_cumulative: -10_000,
+                    sqrt_price_cumulative: 600 << 64,
+                },
+                OracleObservationFacade::default(),
+            ],
+        }
+    }
+
+    fn test_whirlpool() -> WhirlpoolFacade {
+        WhirlpoolFacade {
+            tick_current_index: 300,
+            sqrt_price: 6 << 64,
+            ..WhirlpoolFacade::default()
+        }
+    }
+
+    #[test]
+    fn test_get_oracle_observation() {
+        let result =
+            try_get_oracle_observation(test_observations(), test_whirlpool(), 1_300, 0).unwrap();
+        assert_eq!(
+            result,
+            OracleObservationFacade {
+                timestamp: 1_300,
+                tick_cumulative: 20_000,
+                sqrt_price_cumulative: 1_200 << 64,
+            }
+        );
+
+        let result =
+            try_get_oracle_observation(test_observations(), test_whirlpool(), 1_300, 250).unwrap();
+        assert_eq!(
+            result,
+            OracleObservationFacade {
+                timestamp: 1_050,
+                tick_cumulative: 5_000,
+                sqrt_price_cumulative: 200 << 64,
+            }
+        );
+
+        let result =
+            try_get_oracle_observation(test_observations(), test_whirlpool(), 1_300, 200).unwrap();
+        assert_eq!(result, test_observations().observations[1]);
+    }
+
+    #[test]
+    fn test_get_oracle_twap() {
+        let result = try_get_oracle_twap(test_observations(), test_whirlpool(), 1_300, 100);
+        assert_eq!(
+            result,
+            Ok(OracleTwap {
+                tick_index: 300,
+                sqrt_price: 6 << 64,
+            })
+        );
+
+        let result = try_get_oracle_twap(test_observations(), test_whirlpool(), 1_300, 200);
+        assert_eq!(
+            result,
+            Ok(OracleTwap {
+                tick_index: 50,
+                sqrt_price: 4 << 64,
+            })
+        );
+
+        let result = try_get_oracle_twap(test_observations(), test_whirlpool(), 1_200, 150);
+        assert_eq!(
+            result,
+            Ok(OracleTwap {
+                tick_index: -100,
+                sqrt_price: (8 << 64) / 3,
+            })
+        );
+    }
+
+    #[test]
+    fn test_get_oracle_twap_rounds_tick_index_down() {
+        let result = try_get_oracle_twap(test_observations(), test_whirlpool(), 1_130, 70).unwrap();
+        assert_eq!(result.tick_index, -29);
+    }
+
+    #[test]
+    fn test_get_oracle_twap_errors() {
+        assert_eq!(
+            try_get_oracle_twap(test_observations(), test_whirlpool(), 1_300, 0),
+            Err(INVALID_TWAP_WINDOW)
+        );
+        assert_eq!(
+            try_get_oracle_twap(test_observations(), test_whirlpool(), 1_300, 301),
+            Err(ORACLE_OBSERVATION_TOO_OLD)
+        );
+        assert_eq!(
+            try_get_oracle_twap(test_observations(), test_whirlpool(), 100, 101),
+            Err(ORACLE_OBSERVATION_TOO_OLD)
+        );
+        assert_eq!(
+            try_get_oracle_twap(
+                OracleObservationsFacade::default(),
+                test_whirlpool(),
+                1_300,
+                100
+            ),
+            Err(ORACLE_OBSERVATIONS_NOT_INITIALIZED)
+        );
+    }
+
+    #[test]
+    fn test_get_oracle_twap_wrapped_ring_buffer() {
+        // the ring buffer wrapped around: the oldest observation is at index 1
+        let observations = OracleObservationsFacade {
+            observation_index: 0,
+            observation_cardinality: 3,
+            observations: vec![
+                OracleObservationFacade {
+                    timestamp: 1_300,
+                    tick_cumulative: 20_000,
+                    sqrt_price_cumulative: 1_200 << 64,
+                },
+                OracleObservationFacade {
+                    timestamp: 1_100,
+                    tick_cumulative: 10_000,
+                    sqrt_price_cumulative: 400 << 64,
+                },
+                OracleObservationFacade {
+                    timestamp: 1_200,
+                    tick_cumulative: -10_000,
+                    sqrt_price_cumulative: 600 << 64,
+                },
+            ],
+        };
+        let result = try_get_oracle_twap(observations.clone(), test_whirlpool(), 1_300, 200);
+        assert_eq!(
+            result,
+            Ok(OracleTwap {
+                tick_index: 50,
+                sqrt_price: 4 << 64,
+            })
+        );
+        let result = try_get_oracle_twap(observations, test_whirlpool(), 1_300, 250);
+        assert_eq!(result, Err(ORACLE_OBSERVATION_TOO_OLD));
+    }
+}
@@ -0,0 +1,86 @@
+#[cfg(feature = "wasm")]
+use orca_whirlpools_macros::wasm_expose;
+
+use ethnum::U256;
+
+use crate::{CoreError, AMOUNT_EXCEEDS_MAX_U64, INVALID_EMISSIONS_DURATION, U128};
+
+/// Get the emissions rate that distributes an amount of reward tokens over a duration
+///
+/// The rate is rounded down so that the amount emitted over the duration never exceeds `amount`.
+///
+/// # Parameters
+/// - `amount`: The amount of reward tokens to distribute
+/// - `duration`: The duration in seconds over which the tokens are distributed
+///
+/// # Returns
+/// - `u128`: The emissions per second as a x64 fixed-point number
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn try_get_emissions_per_second_x64(amount: u64, duration: u64) -> Result<U128, CoreError> {
+    if duration == 0 {
+        return Err(INVALID_EMISSIONS_DURATION);
+    }
+    let emissions_per_second_x64 = (<u128>::from(amount) << 64) / <u128>::from(duration);
+    Ok(emissions_per_second_x64.into())
+}
+
+/// Get the amount of reward tokens emitted over a duration
+///
+/// # Parameters
+/// - `emissions_per_second_x64`: The emissions per second as a x64 fixed-point number
+/// - `duration`: The duration in seconds
+///
+/// # Returns
+/// - `u64`: The amount of reward tokens emitted, rounded down
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn try_get_emissions_amount(
+    emissions_per_second_x64: U128,
+    duration: u64,
+) -> Result<u64, CoreError> {
+    let emissions_per_second_x64: u128 = emissions_per_second_x64.into();
+    let amount: U256 = (<U256>::from(emissions_per_second_x64) * <U256>::from(duration)) >> 64;
+    amount.try_into().map_err(|_| AMOUNT_EXCEEDS_MAX_U64)
+}
+
+#[cfg(all(test, not(feature = "wasm")))]
+mod tests {
+    use crate::{INVALID_EMISSIONS_DURATION, REWARD_VAULT_MIN_EMISSIONS_DURATION};
+
+    use super::*;
+
+    #[test]
+    fn test_get_emissions_per_second_x64() {
+        assert_eq!(try_get_emissions_per_second_x64(100, 1), Ok(100 << 64));
+        assert_eq!(try_get_emissions_per_second_x64(1, 2), Ok(1 << 63));
+        assert_eq!(
+            try_get_emissions_per_second_x64(u64::MAX, 1),
+            Ok((u64::MAX as u128) << 64)
+        );
+        assert_eq!(
+            try_get_emissions_per_second_x64(100, 0),
+            Err(INVALID_EMISSIONS_DURATION)
+        );
+    }
+
+    #[test]
+    fn test_get_emissions_amount() {
+        assert_eq!(try_get_emissions_amount(100 << 64, 10), Ok(1000));
+        assert_eq!(try_get_emissions_amount(1 << 63, 3), Ok(1));
+        assert_eq!(try_get_emissions_amount(0, 10), Ok(0));
+        assert_eq!(
+            try_get_emissions_amount(u128::MAX, 2),
+            Err(AMOUNT_EXCEEDS_MAX_U64)
+        );
+    }
+
+    #[test]
+    fn test_emissions_round_trip_never_exceeds_amount() {
+        let duration = 30 * REWARD_VAULT_MIN_EMISSIONS_DURATION;
+        for amount in [1, 7, 1_000_000, 123_456_789_012, u64::MAX / 3] {
+            let emissions = try_get_emissions_per_second_x64(amount, duration).unwrap();
+            let emitted = try_get_emissions_amount(emissions, duration).unwrap();
+            assert!(emitted <= amount);
+            assert!(amount - emitted <= 1);
+        }
+    }
+}
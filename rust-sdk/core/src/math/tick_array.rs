@@ -1,39 +1,43 @@
 use crate::{
     CoreError, TickArrayFacade, TickFacade, INVALID_TICK_ARRAY_SEQUENCE, INVALID_TICK_INDEX,
     MAX_TICK_INDEX, MIN_TICK_INDEX, TICK_ARRAY_NOT_EVENLY_SPACED, TICK_ARRAY_SIZE,
-    TICK_INDEX_OUT_OF_BOUNDS, TICK_SEQUENCE_EMPTY,
+    TICK_INDEX_OUT_OF_BOUNDS, TICK_SEQUENCE_EMPTY, U128,
 };
 
+#[cfg(feature = "wasm")]
+use orca_whirlpools_macros::wasm_expose;
+
 use super::{
     get_initializable_tick_index, get_next_initializable_tick_index,
-    get_prev_initializable_tick_index,
+    get_prev_initializable_tick_index, get_tick_array_start_tick_index, sqrt_price_to_tick_index,
+    tick_index_to_sqrt_price,
 };
 
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct TickArraySequence<const SIZE: usize> {
-    pub tick_arrays: [Option<TickArrayFacade>; SIZE],
+pub struct TickArraySequence {
+    pub tick_arrays: Vec<TickArrayFacade>,
     pub tick_spacing: u16,
 }
 
-impl<const SIZE: usize> TickArraySequence<SIZE> {
-    pub fn new(
-        tick_arrays: [Option<TickArrayFacade>; SIZE],
+impl TickArraySequence {
+    pub fn new<T: Into<Option<TickArrayFacade>>>(
+        tick_arrays: impl IntoIterator<Item = T>,
         tick_spacing: u16,
     ) -> Result<Self, CoreError> {
-        let mut tick_arrays = tick_arrays;
-        tick_arrays.sort_by_key(start_tick_index);
+        let mut tick_arrays: Vec<TickArrayFacade> =
+            tick_arrays.into_iter().filter_map(Into::into).collect();
+        tick_arrays.sort_by_key(|tick_array| tick_array.start_tick_index);
+        tick_arrays.dedup_by_key(|tick_array| tick_array.start_tick_index);
 
-        if tick_arrays.is_empty() || tick_arrays[0].is_none() {
+        if tick_arrays.is_empty() {
             return Err(TICK_SEQUENCE_EMPTY);
         }
 
         let required_tick_array_spacing = TICK_ARRAY_SIZE as i32 * tick_spacing as i32;
         for i in 0..tick_arrays.len() - 1 {
-            let current_start_tick_index = start_tick_index(&tick_arrays[i]);
-            let next_start_tick_index = start_tick_index(&tick_arrays[i + 1]);
-            if next_start_tick_index != <i32>::MAX
-                && next_start_tick_index - current_start_tick_index != required_tick_array_spacing
-            {
+            let current_start_tick_index = tick_arrays[i].start_tick_index;
+            let next_start_tick_index = tick_arrays[i + 1].start_tick_index;
+            if next_start_tick_index - current_start_tick_index != required_tick_array_spacing {
                 return Err(TICK_ARRAY_NOT_EVENLY_SPACED);
             }
         }
@@ -44,19 +48,24 @@ impl<const SIZE: usize> TickArraySequence<SIZE> {
         })
     }
 
+    /// Returns the number of tick arrays in the sequence.
+    pub fn len(&self) -> usize {
+        self.tick_arrays.len()
+    }
+
+    /// Returns `true` if the sequence holds no tick arrays.
+    pub fn is_empty(&self) -> bool {
+        self.tick_arrays.is_empty()
+    }
+
     /// Returns the first valid tick index in the sequence.
     pub fn start_index(&self) -> i32 {
-        start_tick_index(&self.tick_arrays[0]).max(MIN_TICK_INDEX)
+        self.tick_arrays[0].start_tick_index.max(MIN_TICK_INDEX)
     }
 
     /// Returns the last valid tick index in the sequence.
     pub fn end_index(&self) -> i32 {
-        let mut last_valid_start_index = self.start_index();
-        for i in 0..self.tick_arrays.len() {
-            if start_tick_index(&self.tick_arrays[i]) != <i32>::MAX {
-                last_valid_start_index = start_tick_index(&self.tick_arrays[i]);
-            }
-        }
+        let last_valid_start_index = self.tick_arrays[self.tick_arrays.len() - 1].start_tick_index;
         let end_index =
             last_valid_start_index + TICK_ARRAY_SIZE as i32 * self.tick_spacing as i32 - 1;
         end_index.min(MAX_TICK_INDEX)
@@ -69,14 +78,13 @@ impl<const SIZE: usize> TickArraySequence<SIZE> {
         if (tick_index % self.tick_spacing as i32) != 0 {
             return Err(INVALID_TICK_INDEX);
         }
-        let first_index = start_tick_index(&self.tick_arrays[0]);
+        let first_index = self.tick_arrays[0].start_tick_index;
         let tick_array_index = ((tick_index - first_index)
             / (TICK_ARRAY_SIZE as i32 * self.tick_spacing as i32))
             as usize;
-        let tick_array_start_index = start_tick_index(&self.tick_arrays[tick_array_index]);
-        let tick_array_ticks = ticks(&self.tick_arrays[tick_array_index]);
-        let index_in_array = (tick_index - tick_array_start_index) / self.tick_spacing as i32;
-        Ok(&tick_array_ticks[index_in_array as usize])
+        let tick_array = &self.tick_arrays[tick_array_index];
+        let index_in_array = (tick_index - tick_array.start_tick_index) / self.tick_spacing as i32;
+        Ok(&tick_array.ticks[index_in_array as usize])
     }
 
     pub fn next_initialized_tick(
@@ -125,21 +133,126 @@ impl<const SIZE: usize> TickArraySequence<SIZE> {
     }
 }
 
-// internal functions
+/// Get the start tick indexes of the tick arrays a swap traverses, in the order the swap visits them.
+///
+/// This mirrors how the program resolves tick arrays: an a to b swap starts at the tick array that
+/// contains the current tick index, a b to a swap starts at the tick array that contains the next
+/// initializable tick index. Tick arrays outside of the valid tick range are omitted.
+///
+/// # Parameters
+/// - `tick_current_index` - A i32 integer representing the current tick index of the whirlpool
+/// - `tick_spacing` - A u16 integer representing the tick spacing
+/// - `a_to_b` - A boolean indicating the direction of the swap
+/// - `tick_array_count` - A u32 integer representing the number of tick arrays to return
+///
+/// # Returns
+/// - A vector of i32 integers representing the start tick indexes of the tick arrays
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn get_swap_tick_array_start_tick_indexes(
+    tick_current_index: i32,
+    tick_spacing: u16,
+    a_to_b: bool,
+    tick_array_count: u32,
+) -> Vec<i32> {
+    let ticks_in_array = TICK_ARRAY_SIZE as i32 * tick_spacing as i32;
+    let first_start_tick_index =
+        get_swap_first_tick_array_start_tick_index(tick_current_index, tick_spacing, a_to_b);
+    let min_start_tick_index = get_tick_array_start_tick_index(MIN_TICK_INDEX, tick_spacing);
+    let max_start_tick_index = get_tick_array_start_tick_index(MAX_TICK_INDEX, tick_spacing);
+
+    (0..tick_array_count as i32)
+        .map(|offset| {
+            if a_to_b {
+                first_start_tick_index - offset * ticks_in_array
+            } else {
+                first_start_tick_index + offset * ticks_in_array
+            }
+        })
+        .take_while(|start_tick_index| {
+            (min_start_tick_index..=max_start_tick_index).contains(start_tick_index)
+        })
+        .collect()
+}
 
-fn start_tick_index(tick_array: &Option<TickArrayFacade>) -> i32 {
-    if let Some(tick_array) = tick_array {
-        tick_array.start_tick_index
+/// Get the furthest sqrt price a swap can reach without traversing more than the given number of tick arrays.
+///
+/// # Parameters
+/// - `tick_current_index` - A i32 integer representing the current tick index of the whirlpool
+/// - `tick_spacing` - A u16 integer representing the tick spacing
+/// - `a_to_b` - A boolean indicating the direction of the swap
+/// - `tick_array_count` - A u32 integer representing the number of tick arrays the swap may traverse
+///
+/// # Returns
+/// - A u128 Q32.64 representing the sqrt price limit for the swap
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn get_swap_sqrt_price_limit(
+    tick_current_index: i32,
+    tick_spacing: u16,
+    a_to_b: bool,
+    tick_array_count: u32,
+) -> U128 {
+    let ticks_in_array = TICK_ARRAY_SIZE as i32 * tick_spacing as i32;
+    let first_start_tick_index =
+        get_swap_first_tick_array_start_tick_index(tick_current_index, tick_spacing, a_to_b);
+    let tick_array_count = tick_array_count.max(1) as i64;
+
+    // The program stops at the first tick of the last tick array (a to b)
+    // or at the last tick of the last tick array (b to a).
+    let limit_tick_index = if a_to_b {
+        first_start_tick_index as i64 - (tick_array_count - 1) * ticks_in_array as i64
     } else {
-        <i32>::MAX
-    }
+        first_start_tick_index as i64 + tick_array_count * ticks_in_array as i64 - 1
+    };
+    let limit_tick_index = limit_tick_index.clamp(MIN_TICK_INDEX as i64, MAX_TICK_INDEX as i64);
+    tick_index_to_sqrt_price(limit_tick_index as i32)
 }
 
-fn ticks(tick_array: &Option<TickArrayFacade>) -> &[TickFacade] {
-    if let Some(tick_array) = tick_array {
-        &tick_array.ticks
+/// Get the number of tick arrays a swap traverses when it moves the price from the current tick index to the given sqrt price.
+///
+/// # Parameters
+/// - `tick_current_index` - A i32 integer representing the tick index before the swap
+/// - `next_sqrt_price` - A u128 Q32.64 representing the sqrt price after the swap
+/// - `tick_spacing` - A u16 integer representing the tick spacing
+/// - `a_to_b` - A boolean indicating the direction of the swap
+///
+/// # Returns
+/// - A u32 integer representing the number of tick arrays traversed
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn get_swap_tick_array_count(
+    tick_current_index: i32,
+    next_sqrt_price: U128,
+    tick_spacing: u16,
+    a_to_b: bool,
+) -> u32 {
+    let ticks_in_array = TICK_ARRAY_SIZE as i32 * tick_spacing as i32;
+    let first_start_tick_index =
+        get_swap_first_tick_array_start_tick_index(tick_current_index, tick_spacing, a_to_b);
+    let next_tick_index = sqrt_price_to_tick_index(next_sqrt_price);
+    let last_start_tick_index = get_tick_array_start_tick_index(next_tick_index, tick_spacing);
+
+    let tick_array_delta = if a_to_b {
+        first_start_tick_index - last_start_tick_index
     } else {
-        &[]
+        last_start_tick_index - first_start_tick_index
+    };
+    (tick_array_delta / ticks_in_array).max(0) as u32 + 1
+}
+
+// Private functions
+
+fn get_swap_first_tick_array_start_tick_index(
+    tick_current_index: i32,
+    tick_spacing: u16,
+    a_to_b: bool,
+) -> i32 {
+    let ticks_in_array = TICK_ARRAY_SIZE as i32 * tick_spacing as i32;
+    let start_tick_index = get_tick_array_start_tick_index(tick_current_index, tick_spacing);
+    let shifted =
+        !a_to_b && tick_current_index + tick_spacing as i32 >= start_tick_index + ticks_in_array;
+    if shifted {
+        start_tick_index + ticks_in_array
+    } else {
+        start_tick_index
     }
 }
 
@@ -187,7 +300,7 @@ mod tests {
         tick_spacing: u16,
         ticks: [TickFacade; TICK_ARRAY_SIZE],
         start_tick_index: i32,
-    ) -> TickArraySequence<5> {
+    ) -> TickArraySequence {
         let one = TickArrayFacade {
             start_tick_index,
             ticks,
@@ -195,10 +308,7 @@ mod tests {
         TickArraySequence::new([Some(one), None, None, None, None], tick_spacing).unwrap()
     }
 
-    fn test_sequence(
-        tick_spacing: u16,
-        ticks: [TickFacade; TICK_ARRAY_SIZE],
-    ) -> TickArraySequence<5> {
+    fn test_sequence(tick_spacing: u16, ticks: [TickFacade; TICK_ARRAY_SIZE]) -> TickArraySequence {
         let one = TickArrayFacade {
             start_tick_index: -(TICK_ARRAY_SIZE as i32 * tick_spacing as i32),
             ticks,
@@ -243,7 +353,7 @@ mod tests {
 
     #[test]
     fn test_get_tick_large_tick_spacing() {
-        let sequence: TickArraySequence<5> =
+        let sequence: TickArraySequence =
             test_sequence(32896, test_ticks_alternating_initialized());
         assert_eq!(sequence.tick(-427648).map(|x| x.liquidity_net), Ok(75));
         assert_eq!(sequence.tick(0).map(|x| x.liquidity_net), Ok(0));
@@ -470,4 +580,94 @@ mod tests {
         let pair = sequence.prev_initialized_tick(MIN_TICK_INDEX);
         assert_eq!(pair, Ok((Some(&test_tick(true, 60)), MIN_TICK_INDEX)));
     }
+
+    #[test]
+    fn test_sequence_with_many_tick_arrays() {
+        let tick_arrays = (-5..5).map(|i| TickArrayFacade {
+            start_tick_index: i * TICK_ARRAY_SIZE as i32 * 16,
+            ticks: test_ticks_alternating_initialized(),
+        });
+        let sequence = TickArraySequence::new(tick_arrays, 16).unwrap();
+        assert_eq!(sequence.len(), 10);
+        assert_eq!(sequence.start_index(), -7040);
+        assert_eq!(sequence.end_index(), 7039);
+        assert_eq!(sequence.tick(-7024).map(|x| x.liquidity_net), Ok(1));
+        assert_eq!(sequence.tick(7024).map(|x| x.liquidity_net), Ok(87));
+    }
+
+    #[test]
+    fn test_sequence_dedups_tick_arrays() {
+        let one = TickArrayFacade {
+            start_tick_index: 0,
+            ticks: test_ticks_alternating_initialized(),
+        };
+        let sequence = TickArraySequence::new([one, one], 16).unwrap();
+        assert_eq!(sequence.len(), 1);
+    }
+
+    #[test]
+    fn test_sequence_empty() {
+        let result = TickArraySequence::new([None, None], 16);
+        assert_eq!(result, Err(TICK_SEQUENCE_EMPTY));
+    }
+
+    #[test]
+    fn test_swap_tick_array_start_tick_indexes() {
+        assert_eq!(
+            get_swap_tick_array_start_tick_indexes(0, 2, true, 3),
+            vec![0, -176, -352]
+        );
+        assert_eq!(
+            get_swap_tick_array_start_tick_indexes(0, 2, false, 3),
+            vec![0, 176, 352]
+        );
+        assert_eq!(
+            get_swap_tick_array_start_tick_indexes(174, 2, false, 3),
+            vec![176, 352, 528]
+        );
+        assert_eq!(
+            get_swap_tick_array_start_tick_indexes(443600, 64, false, 3),
+            vec![439296]
+        );
+    }
+
+    #[test]
+    fn test_swap_sqrt_price_limit() {
+        assert_eq!(
+            get_swap_sqrt_price_limit(0, 2, true, 3),
+            tick_index_to_sqrt_price(-352)
+        );
+        assert_eq!(
+            get_swap_sqrt_price_limit(0, 2, false, 3),
+            tick_index_to_sqrt_price(527)
+        );
+        assert_eq!(
+            get_swap_sqrt_price_limit(-443600, 64, true, 3),
+            tick_index_to_sqrt_price(MIN_TICK_INDEX)
+        );
+    }
+
+    #[test]
+    fn test_swap_tick_array_count() {
+        assert_eq!(
+            get_swap_tick_array_count(0, tick_index_to_sqrt_price(0), 2, true),
+            1
+        );
+        assert_eq!(
+            get_swap_tick_array_count(0, tick_index_to_sqrt_price(-352), 2, true),
+            3
+        );
+        assert_eq!(
+            get_swap_tick_array_count(0, tick_index_to_sqrt_price(-353), 2, true),
+            4
+        );
+        assert_eq!(
+            get_swap_tick_array_count(0, tick_index_to_sqrt_price(527), 2, false),
+            3
+        );
+        assert_eq!(
+            get_swap_tick_array_count(0, tick_index_to_sqrt_price(528), 2, false),
+            4
+        );
+    }
 }
@@ -0,0 +1,295 @@
+#[cfg(feature = "wasm")]
+use orca_whirlpools_macros::wasm_expose;
+
+use libm::{exp, floor, pow};
+
+use super::{
+    price_to_sqrt_price, sqrt_price_to_price, sqrt_price_to_tick_index, tick_index_to_sqrt_price,
+};
+use crate::{
+    InterestBearingConfigFacade, ScaledUiAmountConfigFacade, BPS_DENOMINATOR, SECONDS_PER_YEAR,
+    U128,
+};
+
+/// Calculate the UI amount multiplier of an interest-bearing mint at a timestamp
+/// IMPORTANT: floating point operations can reduce the precision of the result.
+/// Make sure to do these operations last and not to use the result for further calculations.
+///
+/// Interest compounds continuously, at the average rate before the last rate update and at the
+/// current rate after it, the same way Token-2022 calculates UI amounts.
+///
+/// # Parameters
+/// * `config` - The interest rate state of the mint
+/// * `timestamp` - The timestamp to calculate the multiplier at
+///
+/// # Returns
+/// * `f64` - The multiplier from raw amounts to UI amounts, before applying decimals
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn interest_bearing_multiplier(config: InterestBearingConfigFacade, timestamp: u64) -> f64 {
+    let pre_update_timespan = config
+        .last_update_timestamp
+        .saturating_sub(config.initialization_timestamp);
+    let post_update_timespan = timestamp.saturating_sub(config.last_update_timestamp);
+    continuous_compounding(config.pre_update_average_rate, pre_update_timespan)
+        * continuous_compounding(config.current_rate, post_update_timespan)
+}
+
+/// Calculate the UI amount multiplier of a mint with scaled UI amounts at a timestamp
+///
+/// # Parameters
+/// * `config` - The multiplier state of the mint
+/// * `timestamp` - The timestamp to calculate the multiplier at
+///
+/// # Returns
+/// * `f64` - The multiplier from raw amounts to UI amounts, before applying decimals
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn scaled_ui_amount_multiplier(config: ScaledUiAmountConfigFacade, timestamp: u64) -> f64 {
+    if timestamp >= config.new_multiplier_effective_timestamp {
+        config.new_multiplier
+    } else {
+        config.multiplier
+    }
+}
+
+/// Convert a raw token amount into a UI amount
+/// IMPORTANT: floating point operations can reduce the precision of the result.
+/// Make sure to do these operations last and not to use the result for further calculations.
+///
+/// # Parameters
+/// * `amount` - The raw token amount
+/// * `decimals` - The number of decimals of the token
+/// * `multiplier` - The UI amount multiplier of the mint, 1.0 for mints without one
+///
+/// # Returns
+/// * `f64` - The UI amount
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn amount_to_ui_amount(amount: u64, decimals: u8, multiplier: f64) -> f64 {
+    amount as f64 * multiplier / pow(10f64, decimals as f64)
+}
+
+/// Convert a UI amount into a raw token amount, rounded down
+/// IMPORTANT: floating point operations can reduce the precision of the result.
+/// Make sure to do these operations last and not to use the result for further calculations.
+///
+/// # Parameters
+/// * `ui_amount` - The UI amount
+/// * `decimals` - The number of decimals of the token
+/// * `multiplier` - The UI amount multiplier of the mint, 1.0 for mints without one
+///
+/// # Returns
+/// * `u64` - The raw token amount
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn ui_amount_to_amount(ui_amount: f64, decimals: u8, multiplier: f64) -> u64 {
+    floor(ui_amount * pow(10f64, decimals as f64) / multiplier) as u64
+}
+
+/// Convert a sqrt priceX64 into a price in UI amounts
+/// IMPORTANT: floating point operations can reduce the precision of the result.
+/// Make sure to do these operations last and not to use the result for further calculations.
+///
+/// # Parameters
+/// * `sqrt_price` - The sqrt priceX64 to convert
+/// * `decimals_a` - The number of decimals of the base token
+/// * `decimals_b` - The number of decimals of the quote token
+/// * `multiplier_a` - The UI amount multiplier of the base token
+/// * `multiplier_b` - The UI amount multiplier of the quote token
+///
+/// # Returns
+/// * `f64` - The UI price
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn sqrt_price_to_ui_price(
+    sqrt_price: U128,
+    decimals_a: u8,
+    decimals_b: u8,
+    multiplier_a: f64,
+    multiplier_b: f64,
+) -> f64 {
+    sqrt_price_to_price(sqrt_price, decimals_a, decimals_b) * multiplier_b / multiplier_a
+}
+
+/// Convert a price in UI amounts into a sqrt priceX64
+/// IMPORTANT: floating point operations can reduce the precision of the result.
+/// Make sure to do these operations last and not to use the result for further calculations.
+///
+/// # Parameters
+/// * `price` - The UI price to convert
+/// * `decimals_a` - The number of decimals of the base token
+/// * `decimals_b` - The number of decimals of the quote token
+/// * `multiplier_a` - The UI amount multiplier of the base token
+/// * `multiplier_b` - The UI amount multiplier of the quote token
+///
+/// # Returns
+/// * `u128` - The sqrt priceX64
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn ui_price_to_sqrt_price(
+    price: f64,
+    decimals_a: u8,
+    decimals_b: u8,
+    multiplier_a: f64,
+    multiplier_b: f64,
+) -> U128 {
+    price_to_sqrt_price(price * multiplier_a / multiplier_b, decimals_a, decimals_b)
+}
+
+/// Convert a tick index into a price in UI amounts
+/// IMPORTANT: floating point operations can reduce the precision of the result.
+/// Make sure to do these operations last and not to use the result for further calculations.
+///
+/// # Parameters
+/// * `tick_index` - The tick index to convert
+/// * `decimals_a` - The number of decimals of the base token
+/// * `decimals_b` - The number of decimals of the quote token
+/// * `multiplier_a` - The UI amount multiplier of the base token
+/// * `multiplier_b` - The UI amount multiplier of the quote token
+///
+/// # Returns
+/// * `f64` - The UI price
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn tick_index_to_ui_price(
+    tick_index: i32,
+    decimals_a: u8,
+    decimals_b: u8,
+    multiplier_a: f64,
+    multiplier_b: f64,
+) -> f64 {
+    let sqrt_price = tick_index_to_sqrt_price(tick_index);
+    sqrt_price_to_ui_price(
+        sqrt_price,
+        decimals_a,
+        decimals_b,
+        multiplier_a,
+        multiplier_b,
+    )
+}
+
+/// Convert a price in UI amounts into a tick index, e.g. for the price range of a new position
+/// IMPORTANT: floating point operations can reduce the precision of the result.
+/// Make sure to do these operations last and not to use the result for further calculations.
+///
+/// # Parameters
+/// * `price` - The UI price to convert
+/// * `decimals_a` - The number of decimals of the base token
+/// * `decimals_b` - The number of decimals of the quote token
+/// * `multiplier_a` - The UI amount multiplier of the base token
+/// * `multiplier_b` - The UI amount multiplier of the quote token
+///
+/// # Returns
+/// * `i32` - The tick index
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn ui_price_to_tick_index(
+    price: f64,
+    decimals_a: u8,
+    decimals_b: u8,
+    multiplier_a: f64,
+    multiplier_b: f64,
+) -> i32 {
+    let sqrt_price =
+        ui_price_to_sqrt_price(price, decimals_a, decimals_b, multiplier_a, multiplier_b);
+    sqrt_price_to_tick_index(sqrt_price)
+}
+
+fn continuous_compounding(rate: i16, timespan: u64) -> f64 {
+    exp(rate as f64 * timespan as f64 / SECONDS_PER_YEAR / BPS_DENOMINATOR as f64)
+}
+
+#[cfg(all(test, not(feature = "wasm")))]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::{price_to_tick_index, tick_index_to_price};
+
+    const ONE_YEAR: u64 = 31_556_736;
+
+    #[test]
+    fn test_interest_bearing_multiplier() {
+        let config = InterestBearingConfigFacade {
+            initialization_timestamp: 1_000,
+            pre_update_average_rate: 500,
+            last_update_timestamp: 1_000 + ONE_YEAR,
+            current_rate: -500,
+        };
+        assert_relative_eq!(
+            interest_bearing_multiplier(config, 1_000),
+            1.0512710963760241
+        );
+        assert_relative_eq!(
+            interest_bearing_multiplier(config, 1_000 + ONE_YEAR),
+            1.0512710963760241
+        );
+        assert_relative_eq!(
+            interest_bearing_multiplier(config, 1_000 + 2 * ONE_YEAR),
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_interest_bearing_multiplier_without_interest() {
+        let config = InterestBearingConfigFacade::default();
+        assert_eq!(interest_bearing_multiplier(config, 1_700_000_000), 1.0);
+    }
+
+    #[test]
+    fn test_scaled_ui_amount_multiplier() {
+        let config = ScaledUiAmountConfigFacade {
+            multiplier: 1.5,
+            new_multiplier_effective_timestamp: 100,
+            new_multiplier: 2.0,
+        };
+        assert_eq!(scaled_ui_amount_multiplier(config, 99), 1.5);
+        assert_eq!(scaled_ui_amount_multiplier(config, 100), 2.0);
+    }
+
+    #[test]
+    fn test_amount_to_ui_amount() {
+        assert_eq!(amount_to_ui_amount(1_500_000, 6, 1.0), 1.5);
+        assert_eq!(amount_to_ui_amount(1_500_000, 6, 2.0), 3.0);
+        assert_eq!(ui_amount_to_amount(3.0, 6, 2.0), 1_500_000);
+        assert_eq!(ui_amount_to_amount(1.5, 6, 1.0), 1_500_000);
+    }
+
+    #[test]
+    fn test_ui_price_without_multipliers() {
+        let sqrt_price = 6918418495991757039u128;
+        assert_eq!(
+            sqrt_price_to_ui_price(sqrt_price, 9, 6, 1.0, 1.0),
+            sqrt_price_to_price(sqrt_price, 9, 6)
+        );
+        assert_eq!(
+            ui_price_to_tick_index(140.0, 9, 6, 1.0, 1.0),
+            price_to_tick_index(140.0, 9, 6)
+        );
+        assert_eq!(
+            tick_index_to_ui_price(-20000, 9, 6, 1.0, 1.0),
+            tick_index_to_price(-20000, 9, 6)
+        );
+    }
+
+    #[test]
+    fn test_ui_price_with_multipliers() {
+        // One raw token A is worth 1.05 UI tokens, one raw token B is worth 1 UI token.
+        let sqrt_price = price_to_sqrt_price(1.05, 6, 6);
+        assert_relative_eq!(
+            sqrt_price_to_ui_price(sqrt_price, 6, 6, 1.05, 1.0),
+            1.0,
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            sqrt_price_to_ui_price(sqrt_price, 6, 6, 1.0, 2.0),
+            2.1,
+            epsilon = 1e-9
+        );
+
+        let ui_sqrt_price = ui_price_to_sqrt_price(1.0, 6, 6, 1.05, 1.0);
+        let diff = (ui_sqrt_price as i128) - (sqrt_price as i128);
+        assert!(diff.abs() <= 1);
+
+        let tick_index = ui_price_to_tick_index(1.0, 6, 6, 1.05, 1.0);
+        assert_eq!(tick_index, price_to_tick_index(1.05, 6, 6));
+        assert_relative_eq!(
+            tick_index_to_ui_price(tick_index, 6, 6, 1.05, 1.0),
+            1.0,
+            epsilon = 1e-4
+        );
+    }
+}
@@ -0,0 +1,208 @@
+#[cfg(feature = "wasm")]
+use orca_whirlpools_macros::wasm_expose;
+
+use crate::{
+    compute_swap, get_fee_rate_at_timestamp, AdaptiveFeeForecast, CoreError, OracleFacade,
+    TickArrayFacade, TickArraySequence, TickArrays, WhirlpoolFacade, INVALID_ADAPTIVE_FEE_INFO,
+};
+
+/// Forecast the fee a swap on an adaptive fee whirlpool pays when executed at a given timestamp.
+///
+/// The volatility reference of the oracle decays over time, so the same swap gets cheaper the longer
+/// it waits after a major swap. Use `get_adaptive_fee_decay_timestamps` to find the timestamps at
+/// which the forecast changes.
+///
+/// # Arguments
+/// - `token_amount`: The token amount specified for the swap.
+/// - `specified_token_a`: If `true`, the specified token is token A. Otherwise, it is token B.
+/// - `specified_input`: If `true`, `token_amount` is the input amount. Otherwise, it is the output amount.
+/// - `whirlpool`: The whirlpool state.
+/// - `oracle`: The oracle data for the whirlpool.
+/// - `tick_arrays`: The tick arrays needed for the swap.
+/// - `timestamp`: The timestamp at which the swap is executed.
+///
+/// # Returns
+/// The volatility reference, trade fee and applied fee rates of the swap at `timestamp`.
+/// # Notes
+/// - This function doesn't take into account transfer fee extension.
+/// - This function assumes no other swap is executed before `timestamp`.
+#[allow(clippy::too_many_arguments)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn adaptive_fee_forecast(
+    token_amount: u64,
+    specified_token_a: bool,
+    specified_input: bool,
+    whirlpool: WhirlpoolFacade,
+    oracle: OracleFacade,
+    tick_arrays: TickArrays,
+    timestamp: u64,
+) -> Result<AdaptiveFeeForecast, CoreError> {
+    if !whirlpool.is_initialized_with_adaptive_fee() {
+        return Err(INVALID_ADAPTIVE_FEE_INFO);
+    }
+
+    let a_to_b = specified_token_a == specified_input;
+    let tick_arrays: Vec<TickArrayFacade> = tick_arrays.into();
+    let tick_sequence = TickArraySequence::new(tick_arrays, whirlpool.tick_spacing)?;
+
+    let whirlpool = WhirlpoolFacade {
+        fee_rate: get_fee_rate_at_timestamp(whirlpool, Some(oracle), timestamp),
+        ..whirlpool
+    };
+    let swap_result = compute_swap(
+        token_amount,
+        0,
+        whirlpool,
+        &tick_sequence,
+        a_to_b,
+        specified_input,
+        timestamp,
+        Some(oracle.into()),
+    )?;
+
+    let constants = oracle.adaptive_fee_constants;
+    let mut variables = oracle.adaptive_fee_variables;
+    let tick_group_index = whirlpool
+        .tick_current_index
+        .div_euclid(constants.tick_group_size as i32);
+    variables.update_reference(tick_group_index, timestamp, &constants)?;
+
+    Ok(AdaptiveFeeForecast {
+        timestamp,
+        volatility_reference: variables.volatility_reference,
+        trade_fee: swap_result.trade_fee,
+        trade_fee_rate_min: swap_result.applied_fee_rate_min,
+        trade_fee_rate_max: swap_result.applied_fee_rate_max,
+    })
+}
+
+#[cfg(all(test, not(feature = "wasm")))]
+mod tests {
+    use crate::{
+        get_adaptive_fee_decay_timestamps, get_adaptive_fee_rates_by_tick_group,
+        tick_index_to_sqrt_price, AdaptiveFeeConstantsFacade, AdaptiveFeeVariablesFacade,
+        TickFacade, MAX_REFERENCE_AGE, TICK_ARRAY_SIZE,
+    };
+
+    use super::*;
+
+    const NOW: u64 = 1_700_000_000;
+
+    fn test_whirlpool() -> WhirlpoolFacade {
+        WhirlpoolFacade {
+            fee_rate: 1000,
+            liquidity: 1_000_000,
+            sqrt_price: tick_index_to_sqrt_price(0),
+            fee_tier_index_seed: [128 + 64, 0],
+            tick_spacing: 64,
+            ..WhirlpoolFacade::default()
+        }
+    }
+
+    fn test_oracle(last_update_timestamp: u64, volatility_reference: u32) -> OracleFacade {
+        OracleFacade {
+            trade_enable_timestamp: 0,
+            adaptive_fee_constants: AdaptiveFeeConstantsFacade {
+                filter_period: 30,
+                decay_period: 600,
+                adaptive_fee_control_factor: 5_000,
+                reduction_factor: 500,
+                max_volatility_accumulator: 88 * 3 * 10_000,
+                tick_group_size: 64,
+                major_swap_threshold_ticks: 64,
+            },
+            adaptive_fee_variables: AdaptiveFeeVariablesFacade {
+                last_reference_update_timestamp: last_update_timestamp,
+                last_major_swap_timestamp: last_update_timestamp,
+                volatility_reference,
+                tick_group_index_reference: 0,
+                volatility_accumulator: volatility_reference,
+            },
+            ..OracleFacade::default()
+        }
+    }
+
+    fn test_tick_arrays() -> TickArrays {
+        [-11264, -5632, 0, 5632, 11264]
+            .map(|start_tick_index| TickArrayFacade {
+                start_tick_index,
+                ticks: [TickFacade::default(); TICK_ARRAY_SIZE],
+            })
+            .into()
+    }
+
+    fn forecast(oracle: OracleFacade, timestamp: u64) -> AdaptiveFeeForecast {
+        adaptive_fee_forecast(
+            150_000,
+            true,
+            true,
+            test_whirlpool(),
+            oracle,
+            test_tick_arrays(),
+            timestamp,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_forecast_decays_over_time() {
+        let oracle = test_oracle(NOW, 100_000);
+        let within_filter_period = forecast(oracle, NOW + 10);
+        let within_decay_period = forecast(oracle, NOW + 100);
+        let after_decay_period = forecast(oracle, NOW + 600);
+
+        assert_eq!(within_filter_period.volatility_reference, 100_000);
+        assert_eq!(within_decay_period.volatility_reference, 5_000);
+        assert_eq!(after_decay_period.volatility_reference, 0);
+        assert!(within_filter_period.trade_fee >= within_decay_period.trade_fee);
+        assert!(within_decay_period.trade_fee > after_decay_period.trade_fee);
+        assert_eq!(after_decay_period.timestamp, NOW + 600);
+    }
+
+    #[test]
+    fn test_forecast_static_fee_pool() {
+        let whirlpool = WhirlpoolFacade {
+            fee_tier_index_seed: [64, 0],
+            ..test_whirlpool()
+        };
+        let result = adaptive_fee_forecast(
+            150_000,
+            true,
+            true,
+            whirlpool,
+            test_oracle(NOW, 0),
+            test_tick_arrays(),
+            NOW,
+        );
+        assert_eq!(result, Err(INVALID_ADAPTIVE_FEE_INFO));
+    }
+
+    #[test]
+    fn test_fee_rates_by_tick_group() {
+        let rates = get_adaptive_fee_rates_by_tick_group(
+            test_whirlpool(),
+            test_oracle(NOW, 0),
+            NOW,
+            true,
+            4,
+        )
+        .unwrap();
+        assert_eq!(rates.len(), 4);
+        assert!(rates.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(rates[3] > rates[0]);
+    }
+
+    #[test]
+    fn test_decay_timestamps() {
+        let oracle = test_oracle(NOW, 100_000);
+        assert_eq!(
+            get_adaptive_fee_decay_timestamps(oracle, NOW),
+            vec![NOW + 30, NOW + 600, NOW + MAX_REFERENCE_AGE + 1]
+        );
+        assert_eq!(
+            get_adaptive_fee_decay_timestamps(oracle, NOW + 30),
+            vec![NOW + 600, NOW + MAX_REFERENCE_AGE + 1]
+        );
+        assert!(get_adaptive_fee_decay_timestamps(oracle, NOW + MAX_REFERENCE_AGE + 1).is_empty());
+    }
+}
@@ -0,0 +1,91 @@
+#[cfg(feature = "wasm")]
+use orca_whirlpools_macros::wasm_expose;
+
+use crate::{
+    try_apply_transfer_fee, try_reverse_apply_transfer_fee, CoreError, FlashLoanQuote, TransferFee,
+    ARITHMETIC_OVERFLOW, FEE_RATE_DENOMINATOR,
+};
+
+/// Calculate the quote for a flash loan from the vaults of a pool.
+///
+/// # Parameters
+/// - `amount_a` - The amount of token A to borrow
+/// - `amount_b` - The amount of token B to borrow
+/// - `fee_rate` - The fee rate of the pool
+/// - `transfer_fee_a` - The transfer fee for token A in bps
+/// - `transfer_fee_b` - The transfer fee for token B in bps
+///
+/// # Returns
+/// - A FlashLoanQuote struct containing the fees, the received amounts and the amounts to repay
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn flash_loan_quote(
+    amount_a: u64,
+    amount_b: u64,
+    fee_rate: u16,
+    transfer_fee_a: Option<TransferFee>,
+    transfer_fee_b: Option<TransferFee>,
+) -> Result<FlashLoanQuote, CoreError> {
+    let fee_a = try_get_flash_loan_fee(amount_a, fee_rate)?;
+    let fee_b = try_get_flash_loan_fee(amount_b, fee_rate)?;
+
+    let token_received_a = try_apply_transfer_fee(amount_a, transfer_fee_a.unwrap_or_default())?;
+    let token_received_b = try_apply_transfer_fee(amount_b, transfer_fee_b.unwrap_or_default())?;
+
+    let repay_a = amount_a.checked_add(fee_a).ok_or(ARITHMETIC_OVERFLOW)?;
+    let repay_b = amount_b.checked_add(fee_b).ok_or(ARITHMETIC_OVERFLOW)?;
+    let token_repay_a =
+        try_reverse_apply_transfer_fee(repay_a, transfer_fee_a.unwrap_or_default())?;
+    let token_repay_b =
+        try_reverse_apply_transfer_fee(repay_b, transfer_fee_b.unwrap_or_default())?;
+
+    Ok(FlashLoanQuote {
+        fee_a,
+        fee_b,
+        token_received_a,
+        token_received_b,
+        token_repay_a,
+        token_repay_b,
+    })
+}
+
+fn try_get_flash_loan_fee(amount: u64, fee_rate: u16) -> Result<u64, CoreError> {
+    let numerator = u128::from(amount) * u128::from(fee_rate);
+    let fee = numerator.div_ceil(u128::from(FEE_RATE_DENOMINATOR));
+    fee.try_into().map_err(|_| ARITHMETIC_OVERFLOW)
+}
+
+#[cfg(all(test, not(feature = "wasm")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flash_loan_quote() {
+        let result = flash_loan_quote(1_000_000, 2_000_000, 3000, None, None).unwrap();
+        assert_eq!(result.fee_a, 3_000);
+        assert_eq!(result.fee_b, 6_000);
+        assert_eq!(result.token_received_a, 1_000_000);
+        assert_eq!(result.token_received_b, 2_000_000);
+        assert_eq!(result.token_repay_a, 1_003_000);
+        assert_eq!(result.token_repay_b, 2_006_000);
+    }
+
+    #[test]
+    fn test_flash_loan_quote_rounds_fee_up() {
+        let result = flash_loan_quote(1, 0, 3000, None, None).unwrap();
+        assert_eq!(result.fee_a, 1);
+        assert_eq!(result.fee_b, 0);
+        assert_eq!(result.token_repay_a, 2);
+        assert_eq!(result.token_repay_b, 0);
+    }
+
+    #[test]
+    fn test_flash_loan_quote_with_transfer_fee() {
+        let transfer_fee = TransferFee::new(100);
+        let result =
+            flash_loan_quote(1_000_000, 0, 3000, Some(transfer_fee), Some(transfer_fee)).unwrap();
+        assert_eq!(result.fee_a, 3_000);
+        assert_eq!(result.token_received_a, 990_000);
+        assert_eq!(result.token_repay_a, 1_013_132);
+        assert_eq!(result.token_repay_b, 0);
+    }
+}
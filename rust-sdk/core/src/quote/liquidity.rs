@@ -6,8 +6,9 @@ use ethnum::U256;
 use crate::{
     order_tick_indexes, position_status, tick_index_to_sqrt_price, try_apply_transfer_fee,
     try_get_max_amount_with_slippage_tolerance, try_get_min_amount_with_slippage_tolerance,
-    try_reverse_apply_transfer_fee, CoreError, DecreaseLiquidityQuote, IncreaseLiquidityQuote,
-    PositionStatus, TransferFee, AMOUNT_EXCEEDS_MAX_U64, ARITHMETIC_OVERFLOW, U128,
+    try_reverse_apply_transfer_fee, CompoundFeesQuote, CoreError, DecreaseLiquidityQuote,
+    IncreaseLiquidityQuote, PositionStatus, TransferFee, AMOUNT_EXCEEDS_MAX_U64,
+    ARITHMETIC_OVERFLOW, U128,
 };
 
 /// Calculate the quote for decreasing liquidity
@@ -370,6 +371,98 @@ pub fn increase_liquidity_quote_b(
     )
 }
 
+/// Calculate the quote for compounding the fees and rewards owed to a position into its liquidity
+///
+/// Fees are already held by the pool, so they are used first and no transfer fee applies to them.
+/// Rewards denominated in token A or B are moved from the reward vault, so the transfer fee is
+/// deducted from them before they are added to the liquidity.
+///
+/// # Parameters
+/// - `fee_owed_a` - The amount of token A fees owed to the position
+/// - `fee_owed_b` - The amount of token B fees owed to the position
+/// - `reward_owed_a` - The amount of rewards owed to the position that are denominated in token A
+/// - `reward_owed_b` - The amount of rewards owed to the position that are denominated in token B
+/// - `current_sqrt_price` - The current sqrt price of the pool
+/// - `tick_index_1` - The first tick index of the position
+/// - `tick_index_2` - The second tick index of the position
+/// - `transfer_fee_a` - The transfer fee for token A in bps
+/// - `transfer_fee_b` - The transfer fee for token B in bps
+///
+/// # Returns
+/// - A CompoundFeesQuote struct containing the liquidity added and the amounts that remain owed
+#[allow(clippy::too_many_arguments)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn compound_fees_quote(
+    fee_owed_a: u64,
+    fee_owed_b: u64,
+    reward_owed_a: u64,
+    reward_owed_b: u64,
+    current_sqrt_price: U128,
+    tick_index_1: i32,
+    tick_index_2: i32,
+    transfer_fee_a: Option<TransferFee>,
+    transfer_fee_b: Option<TransferFee>,
+) -> Result<CompoundFeesQuote, CoreError> {
+    let tick_range = order_tick_indexes(tick_index_1, tick_index_2);
+    let transfer_fee_a = transfer_fee_a.unwrap_or_default();
+    let transfer_fee_b = transfer_fee_b.unwrap_or_default();
+
+    let reward_delta_a = try_apply_transfer_fee(reward_owed_a, transfer_fee_a)?;
+    let reward_delta_b = try_apply_transfer_fee(reward_owed_b, transfer_fee_b)?;
+    let token_delta_a = fee_owed_a
+        .checked_add(reward_delta_a)
+        .ok_or(ARITHMETIC_OVERFLOW)?;
+    let token_delta_b = fee_owed_b
+        .checked_add(reward_delta_b)
+        .ok_or(ARITHMETIC_OVERFLOW)?;
+
+    let current_sqrt_price: u128 = current_sqrt_price.into();
+    let sqrt_price_lower: u128 = tick_index_to_sqrt_price(tick_range.tick_lower_index).into();
+    let sqrt_price_upper: u128 = tick_index_to_sqrt_price(tick_range.tick_upper_index).into();
+
+    let position_status = position_status(current_sqrt_price.into(), tick_index_1, tick_index_2);
+
+    let liquidity_delta: u128 = match position_status {
+        PositionStatus::Invalid => 0,
+        PositionStatus::PriceBelowRange => {
+            try_get_liquidity_from_a(token_delta_a, sqrt_price_lower, sqrt_price_upper)?
+        }
+        PositionStatus::PriceInRange => {
+            let liquidity_a =
+                try_get_liquidity_from_a(token_delta_a, current_sqrt_price, sqrt_price_upper)?;
+            let liquidity_b =
+                try_get_liquidity_from_b(token_delta_b, sqrt_price_lower, current_sqrt_price)?;
+            liquidity_a.min(liquidity_b)
+        }
+        PositionStatus::PriceAboveRange => {
+            try_get_liquidity_from_b(token_delta_b, sqrt_price_lower, sqrt_price_upper)?
+        }
+    };
+
+    let (token_est_a, token_est_b) = try_get_token_estimates_from_liquidity(
+        liquidity_delta,
+        current_sqrt_price,
+        tick_range.tick_lower_index,
+        tick_range.tick_upper_index,
+        true,
+    )?;
+
+    let fee_used_a = token_est_a.min(fee_owed_a);
+    let fee_used_b = token_est_b.min(fee_owed_b);
+    let reward_used_a = try_reverse_apply_transfer_fee(token_est_a - fee_used_a, transfer_fee_a)?;
+    let reward_used_b = try_reverse_apply_transfer_fee(token_est_b - fee_used_b, transfer_fee_b)?;
+
+    Ok(CompoundFeesQuote {
+        liquidity_delta,
+        token_est_a,
+        token_est_b,
+        fee_owed_a: fee_owed_a - fee_used_a,
+        fee_owed_b: fee_owed_b - fee_used_b,
+        reward_owed_a: reward_owed_a.saturating_sub(reward_used_a),
+        reward_owed_b: reward_owed_b.saturating_sub(reward_used_b),
+    })
+}
+
 /// Calculate the estimated token amounts for a given liquidity delta and price range
 ///
 /// # Parameters
@@ -1194,4 +1287,67 @@ mod tests {
         assert_eq!(result.token_max_a, 0);
         assert_eq!(result.token_max_b, 0);
     }
+
+    #[test]
+    fn test_compound_fees_quote() {
+        // Below range
+        let result =
+            compound_fees_quote(1000, 1000, 0, 0, 18354745142194483561, -10, 10, None, None)
+                .unwrap();
+        assert!(result.liquidity_delta > 0);
+        assert!(result.token_est_a <= 1000);
+        assert_eq!(result.token_est_b, 0);
+        assert_eq!(result.fee_owed_a, 1000 - result.token_est_a);
+        assert_eq!(result.fee_owed_b, 1000);
+
+        // in range
+        let result =
+            compound_fees_quote(1000, 1000, 0, 0, 18446744073709551616, -10, 10, None, None)
+                .unwrap();
+        assert!(result.liquidity_delta > 0);
+        assert!(result.token_est_a <= 1000);
+        assert!(result.token_est_b <= 1000);
+        assert_eq!(result.fee_owed_a, 1000 - result.token_est_a);
+        assert_eq!(result.fee_owed_b, 1000 - result.token_est_b);
+
+        // Above range
+        let result =
+            compound_fees_quote(1000, 1000, 0, 0, 18539204128674405812, -10, 10, None, None)
+                .unwrap();
+        assert!(result.liquidity_delta > 0);
+        assert_eq!(result.token_est_a, 0);
+        assert!(result.token_est_b <= 1000);
+        assert_eq!(result.fee_owed_a, 1000);
+
+        // Nothing owed
+        let result =
+            compound_fees_quote(0, 0, 0, 0, 18446744073709551616, -10, 10, None, None).unwrap();
+        assert_eq!(result, CompoundFeesQuote::default());
+    }
+
+    #[test]
+    fn test_compound_fees_quote_with_reward() {
+        // Below range, token A fees are used before rewards
+        let result = compound_fees_quote(
+            1000,
+            0,
+            2000,
+            0,
+            18354745142194483561,
+            -10,
+            10,
+            Some(TransferFee::new(1000)),
+            None,
+        )
+        .unwrap();
+        assert!(result.token_est_a > 1000);
+        assert!(result.token_est_a <= 2800);
+        assert_eq!(result.fee_owed_a, 0);
+        assert!(result.reward_owed_a < 2000);
+
+        let without_fee =
+            compound_fees_quote(1000, 0, 2000, 0, 18354745142194483561, -10, 10, None, None)
+                .unwrap();
+        assert!(without_fee.liquidity_delta > result.liquidity_delta);
+    }
 }
@@ -1,13 +1,23 @@
 mod fees;
+mod flash_loan;
 mod liquidity;
+mod range_order;
 mod rewards;
 
+#[cfg(feature = "swap")]
+mod adaptive_fee;
+
 #[cfg(feature = "swap")]
 mod swap;
 
 pub use fees::*;
+pub use flash_loan::*;
 pub use liquidity::*;
+pub use range_order::*;
 pub use rewards::*;
 
+#[cfg(feature = "swap")]
+pub use adaptive_fee::*;
+
 #[cfg(feature = "swap")]
 pub use swap::*;
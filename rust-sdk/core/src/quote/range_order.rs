@@ -0,0 +1,141 @@
+#[cfg(feature = "wasm")]
+use orca_whirlpools_macros::wasm_expose;
+
+use crate::{
+    decrease_liquidity_quote, get_initializable_tick_index, increase_liquidity_quote_a,
+    increase_liquidity_quote_b, tick_index_to_sqrt_price, CoreError, RangeOrderQuote, TransferFee,
+    INVALID_RANGE_ORDER_RANGE, U128,
+};
+
+/// Calculate the quote for opening a range order.
+///
+/// A range order is a position exactly one tick spacing wide that sits entirely on one side
+/// of the current price. Once the price has moved through the whole range, the deposited
+/// token has been fully converted into the other token and the order can be settled.
+///
+/// # Parameters
+/// - `token_amount_in` - The amount of the input token to deposit
+/// - `a_to_b` - Whether the order sells token A for token B
+/// - `tick_index` - The tick index at which the order should start filling
+/// - `tick_spacing` - The tick spacing of the pool
+/// - `current_sqrt_price` - The current sqrt price of the pool
+/// - `transfer_fee_a` - The transfer fee for token A in bps
+/// - `transfer_fee_b` - The transfer fee for token B in bps
+///
+/// # Returns
+/// - A RangeOrderQuote struct containing the order range and the estimated token amounts
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn range_order_quote(
+    token_amount_in: u64,
+    a_to_b: bool,
+    tick_index: i32,
+    tick_spacing: u16,
+    current_sqrt_price: U128,
+    transfer_fee_a: Option<TransferFee>,
+    transfer_fee_b: Option<TransferFee>,
+) -> Result<RangeOrderQuote, CoreError> {
+    let (tick_lower_index, tick_upper_index) = if a_to_b {
+        let tick_lower_index = get_initializable_tick_index(tick_index, tick_spacing, Some(false));
+        (tick_lower_index, tick_lower_index + tick_spacing as i32)
+    } else {
+        let tick_upper_index = get_initializable_tick_index(tick_index, tick_spacing, Some(true));
+        (tick_upper_index - tick_spacing as i32, tick_upper_index)
+    };
+
+    let current_sqrt_price: u128 = current_sqrt_price.into();
+    let sqrt_price_lower: u128 = tick_index_to_sqrt_price(tick_lower_index).into();
+    let sqrt_price_upper: u128 = tick_index_to_sqrt_price(tick_upper_index).into();
+
+    // The whole range must be on the unfilled side of the current price
+    let is_valid_range = if a_to_b {
+        current_sqrt_price < sqrt_price_lower
+    } else {
+        current_sqrt_price >= sqrt_price_upper
+    };
+    if !is_valid_range {
+        return Err(INVALID_RANGE_ORDER_RANGE);
+    }
+
+    let (liquidity_delta, token_max_in, filled_sqrt_price) = if a_to_b {
+        let quote = increase_liquidity_quote_a(
+            token_amount_in,
+            0,
+            current_sqrt_price.into(),
+            tick_lower_index,
+            tick_upper_index,
+            transfer_fee_a,
+            transfer_fee_b,
+        )?;
+        (quote.liquidity_delta, quote.token_max_a, sqrt_price_upper)
+    } else {
+        let quote = increase_liquidity_quote_b(
+            token_amount_in,
+            0,
+            current_sqrt_price.into(),
+            tick_lower_index,
+            tick_upper_index,
+            transfer_fee_a,
+            transfer_fee_b,
+        )?;
+        (quote.liquidity_delta, quote.token_max_b, sqrt_price_lower)
+    };
+
+    let filled_quote = decrease_liquidity_quote(
+        liquidity_delta.into(),
+        0,
+        filled_sqrt_price.into(),
+        tick_lower_index,
+        tick_upper_index,
+        transfer_fee_a,
+        transfer_fee_b,
+    )?;
+    let token_est_out = if a_to_b {
+        filled_quote.token_est_b
+    } else {
+        filled_quote.token_est_a
+    };
+
+    Ok(RangeOrderQuote {
+        liquidity_delta,
+        tick_lower_index,
+        tick_upper_index,
+        token_max_in,
+        token_est_out,
+    })
+}
+
+#[cfg(all(test, not(feature = "wasm")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range_order_quote_a_to_b() {
+        let result = range_order_quote(1000, true, 15, 10, 1 << 64, None, None).unwrap();
+        assert_eq!(result.tick_lower_index, 10);
+        assert_eq!(result.tick_upper_index, 20);
+        assert!(result.liquidity_delta > 0);
+        assert!(result.token_max_in <= 1000);
+        assert!(result.token_est_out > 0);
+    }
+
+    #[test]
+    fn test_range_order_quote_b_to_a() {
+        let result = range_order_quote(1000, false, -5, 10, 1 << 64, None, None).unwrap();
+        assert_eq!(result.tick_lower_index, -10);
+        assert_eq!(result.tick_upper_index, 0);
+        assert!(result.liquidity_delta > 0);
+        assert!(result.token_max_in <= 1000);
+        assert!(result.token_est_out > 0);
+    }
+
+    #[test]
+    fn test_range_order_quote_invalid_range() {
+        // Selling token a requires the range to be above the current price
+        let result = range_order_quote(1000, true, -5, 10, 1 << 64, None, None);
+        assert_eq!(result, Err(INVALID_RANGE_ORDER_RANGE));
+
+        // Selling token b requires the range to be below the current price
+        let result = range_order_quote(1000, false, 5, 10, 1 << 64, None, None);
+        assert_eq!(result, Err(INVALID_RANGE_ORDER_RANGE));
+    }
+}
@@ -3,7 +3,8 @@ use orca_whirlpools_macros::wasm_expose;
 
 use crate::{
     try_apply_transfer_fee, CollectRewardQuote, CollectRewardsQuote, CoreError, PositionFacade,
-    TickFacade, TransferFee, WhirlpoolFacade, ARITHMETIC_OVERFLOW, NUM_REWARDS,
+    RewardVaultRunwayQuote, TickFacade, TransferFee, WhirlpoolFacade, AMOUNT_EXCEEDS_MAX_U64,
+    ARITHMETIC_OVERFLOW, INVALID_REWARD_INDEX, INVALID_TIMESTAMP, NUM_REWARDS,
 };
 
 /// Calculate rewards owed for a position
@@ -88,6 +89,77 @@ pub fn collect_rewards_quote(
     })
 }
 
+/// Calculate how long the vault of a reward can keep up with its emissions
+///
+/// The vault has to hold the rewards positions are still owed plus the rewards emitted since the
+/// whirlpool was last updated. Whatever is left covers future emissions.
+///
+/// # Paramters
+/// - `whirlpool`: The whirlpool state
+/// - `reward_index`: The index of the reward
+/// - `vault_balance`: The amount of tokens in the reward vault
+/// - `rewards_owed`: The rewards owed to all positions at `whirlpool.reward_last_updated_timestamp`
+/// - `current_timestamp`: The current timestamp
+///
+/// # Returns
+/// - `RewardVaultRunwayQuote`: The global reward growth and outstanding rewards at `current_timestamp`,
+///   the vault balance left for future emissions and the timestamp at which the vault runs dry.
+///   The depletion timestamp is `u64::MAX` if the reward has no emissions.
+/// # Notes
+/// - Rewards are only emitted while the whirlpool has in-range liquidity. The depletion timestamp
+///   assumes there is in-range liquidity at all times, so it is the earliest the vault can run dry.
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn reward_vault_runway_quote(
+    whirlpool: WhirlpoolFacade,
+    reward_index: u8,
+    vault_balance: u64,
+    rewards_owed: u64,
+    current_timestamp: u64,
+) -> Result<RewardVaultRunwayQuote, CoreError> {
+    let reward_info = whirlpool
+        .reward_infos
+        .get(reward_index as usize)
+        .ok_or(INVALID_REWARD_INDEX)?;
+    let timestamp_delta = current_timestamp
+        .checked_sub(whirlpool.reward_last_updated_timestamp)
+        .ok_or(INVALID_TIMESTAMP)?;
+
+    let reward_growth_delta = reward_info
+        .emissions_per_second_x64
+        .checked_mul(timestamp_delta as u128)
+        .ok_or(ARITHMETIC_OVERFLOW)?
+        .checked_div(whirlpool.liquidity)
+        .unwrap_or(0);
+    let growth_global_x64 = reward_info
+        .growth_global_x64
+        .wrapping_add(reward_growth_delta);
+    let rewards_emitted: u64 = (whirlpool
+        .liquidity
+        .checked_mul(reward_growth_delta)
+        .ok_or(ARITHMETIC_OVERFLOW)?
+        >> 64)
+        .try_into()
+        .map_err(|_| AMOUNT_EXCEEDS_MAX_U64)?;
+
+    let rewards_outstanding = rewards_owed
+        .checked_add(rewards_emitted)
+        .ok_or(AMOUNT_EXCEEDS_MAX_U64)?;
+    let vault_surplus = vault_balance.saturating_sub(rewards_outstanding);
+
+    let depletion_timestamp = (<u128>::from(vault_surplus) << 64)
+        .checked_div(reward_info.emissions_per_second_x64)
+        .and_then(|runway| u64::try_from(runway).ok())
+        .and_then(|runway| current_timestamp.checked_add(runway))
+        .unwrap_or(u64::MAX);
+
+    Ok(RewardVaultRunwayQuote {
+        growth_global_x64,
+        rewards_outstanding,
+        vault_surplus,
+        depletion_timestamp,
+    })
+}
+
 #[cfg(all(test, not(feature = "wasm")))]
 mod tests {
     use crate::{PositionRewardInfoFacade, WhirlpoolRewardInfoFacade};
@@ -325,4 +397,51 @@ mod tests {
         assert_eq!(result.rewards[1].rewards_owed, 0);
         assert_eq!(result.rewards[2].rewards_owed, 0);
     }
+
+    #[test]
+    fn test_reward_vault_runway() {
+        let whirlpool = test_whirlpool(0, 1000, [0, 0, 0], [10 << 64, 0, 0], 1 << 64);
+        let result = reward_vault_runway_quote(whirlpool, 0, 10_000, 500, 1100).unwrap();
+        assert_eq!(result.growth_global_x64, 1000);
+        assert_eq!(result.rewards_outstanding, 1500);
+        assert_eq!(result.vault_surplus, 8500);
+        assert_eq!(result.depletion_timestamp, 1950);
+    }
+
+    #[test]
+    fn test_reward_vault_runway_underfunded() {
+        let whirlpool = test_whirlpool(0, 1000, [0, 0, 0], [10 << 64, 0, 0], 1 << 64);
+        let result = reward_vault_runway_quote(whirlpool, 0, 1000, 500, 1100).unwrap();
+        assert_eq!(result.vault_surplus, 0);
+        assert_eq!(result.depletion_timestamp, 1100);
+    }
+
+    #[test]
+    fn test_reward_vault_runway_no_emissions() {
+        let whirlpool = test_whirlpool(0, 1000, [0, 0, 0], [10 << 64, 0, 0], 1 << 64);
+        let result = reward_vault_runway_quote(whirlpool, 1, 1000, 0, 1100).unwrap();
+        assert_eq!(result.vault_surplus, 1000);
+        assert_eq!(result.depletion_timestamp, u64::MAX);
+    }
+
+    #[test]
+    fn test_reward_vault_runway_no_liquidity() {
+        let whirlpool = test_whirlpool(0, 1000, [0, 0, 0], [10 << 64, 0, 0], 0);
+        let result = reward_vault_runway_quote(whirlpool, 0, 1000, 0, 1100).unwrap();
+        assert_eq!(result.rewards_outstanding, 0);
+        assert_eq!(result.depletion_timestamp, 1200);
+    }
+
+    #[test]
+    fn test_reward_vault_runway_invalid_input() {
+        let whirlpool = test_whirlpool(0, 1000, [0, 0, 0], [10 << 64, 0, 0], 1 << 64);
+        assert_eq!(
+            reward_vault_runway_quote(whirlpool, 3, 1000, 0, 1100),
+            Err(INVALID_REWARD_INDEX)
+        );
+        assert_eq!(
+            reward_vault_runway_quote(whirlpool, 0, 1000, 0, 999),
+            Err(INVALID_TIMESTAMP)
+        );
+    }
 }
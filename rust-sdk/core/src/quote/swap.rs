@@ -1,10 +1,11 @@
 use crate::{
+    get_fee_rate_at_timestamp, get_swap_sqrt_price_limit, get_swap_tick_array_count,
     sqrt_price_to_tick_index, tick_index_to_sqrt_price, try_apply_swap_fee, try_apply_transfer_fee,
     try_get_amount_delta_a, try_get_amount_delta_b, try_get_max_amount_with_slippage_tolerance,
     try_get_min_amount_with_slippage_tolerance, try_get_next_sqrt_price_from_a,
     try_get_next_sqrt_price_from_b, try_reverse_apply_swap_fee, try_reverse_apply_transfer_fee,
     AdaptiveFeeInfo, CoreError, ExactInSwapQuote, ExactOutSwapQuote, FeeRateManager, OracleFacade,
-    TickArraySequence, TickArrays, TickFacade, TransferFee, WhirlpoolFacade,
+    TickArrayFacade, TickArraySequence, TickArrays, TickFacade, TransferFee, WhirlpoolFacade,
     AMOUNT_EXCEEDS_MAX_U64, ARITHMETIC_OVERFLOW, INVALID_ADAPTIVE_FEE_INFO,
     INVALID_SQRT_PRICE_LIMIT_DIRECTION, MAX_SQRT_PRICE, MIN_SQRT_PRICE,
     SQRT_PRICE_LIMIT_OUT_OF_BOUNDS, ZERO_TRADABLE_AMOUNT,
@@ -21,7 +22,7 @@ use orca_whirlpools_macros::wasm_expose;
 /// - `slippage_tolerance`: The slippage tolerance in basis points.
 /// - `whirlpool`: The whirlpool state.
 /// - `oracle`: The oracle data for the whirlpool.
-/// - `tick_arrays`: The tick arrays needed for the swap.
+/// - `tick_arrays`: The tick arrays needed for the swap. Any number of tick arrays can be provided.
 /// - `timestamp`: The timestamp for the swap.
 /// - `transfer_fee_a`: The transfer fee for token A.
 /// - `transfer_fee_b`: The transfer fee for token B.
@@ -49,17 +50,19 @@ pub fn swap_quote_by_input_token(
     let token_in_after_fee =
         try_apply_transfer_fee(token_in.into(), transfer_fee_in.unwrap_or_default())?;
 
-    let tick_sequence = TickArraySequence::new(tick_arrays.into(), whirlpool.tick_spacing)?;
+    let tick_arrays: Vec<TickArrayFacade> = tick_arrays.into();
+    let tick_sequence = TickArraySequence::new(tick_arrays, whirlpool.tick_spacing)?;
 
+    let (whirlpool, adaptive_fee_info) = apply_oracle(whirlpool, oracle, timestamp);
     let swap_result = compute_swap(
         token_in_after_fee.into(),
         0,
         whirlpool,
-        tick_sequence,
+        &tick_sequence,
         specified_token_a,
         true,
         timestamp,
-        oracle.map(|oracle| oracle.into()),
+        adaptive_fee_info,
     )?;
 
     let (token_in_after_fees, token_est_out_before_fee) = if specified_token_a {
@@ -86,6 +89,7 @@ pub fn swap_quote_by_input_token(
         trade_fee: swap_result.trade_fee,
         trade_fee_rate_min: swap_result.applied_fee_rate_min,
         trade_fee_rate_max: swap_result.applied_fee_rate_max,
+        tick_array_count: swap_result.tick_array_count,
     })
 }
 
@@ -97,7 +101,7 @@ pub fn swap_quote_by_input_token(
 /// - `slippage_tolerance`: The slippage tolerance in basis points.
 /// - `whirlpool`: The whirlpool state.
 /// - `oracle`: The oracle data for the whirlpool.
-/// - `tick_arrays`: The tick arrays needed for the swap.
+/// - `tick_arrays`: The tick arrays needed for the swap. Any number of tick arrays can be provided.
 /// - `timestamp`: The timestamp for the swap.
 /// - `transfer_fee_a`: The transfer fee for token A.
 /// - `transfer_fee_b`: The transfer fee for token B.
@@ -125,17 +129,19 @@ pub fn swap_quote_by_output_token(
     let token_out_before_fee =
         try_reverse_apply_transfer_fee(token_out, transfer_fee_out.unwrap_or_default())?;
 
-    let tick_sequence = TickArraySequence::new(tick_arrays.into(), whirlpool.tick_spacing)?;
+    let tick_arrays: Vec<TickArrayFacade> = tick_arrays.into();
+    let tick_sequence = TickArraySequence::new(tick_arrays, whirlpool.tick_spacing)?;
 
+    let (whirlpool, adaptive_fee_info) = apply_oracle(whirlpool, oracle, timestamp);
     let swap_result = compute_swap(
         token_out_before_fee.into(),
         0,
         whirlpool,
-        tick_sequence,
+        &tick_sequence,
         !specified_token_a,
         false,
         timestamp,
-        oracle.map(|oracle| oracle.into()),
+        adaptive_fee_info,
     )?;
 
     let (token_out_before_fee, token_est_in_after_fee) = if specified_token_a {
@@ -162,15 +168,141 @@ pub fn swap_quote_by_output_token(
         trade_fee: swap_result.trade_fee,
         trade_fee_rate_min: swap_result.applied_fee_rate_min,
         trade_fee_rate_max: swap_result.applied_fee_rate_max,
+        tick_array_count: swap_result.tick_array_count,
     })
 }
 
+/// Splits a swap into consecutive swaps that each traverse at most `max_tick_arrays` tick arrays.
+///
+/// A single swap instruction can only traverse a limited number of tick arrays (see `MAX_SWAP_TICK_ARRAYS`).
+/// Swaps that move the price further have to be split over multiple transactions. This function
+/// simulates the swaps back to back and returns the amount to specify in each of them.
+///
+/// # Arguments
+/// - `token_amount`: The token amount specified for the whole swap.
+/// - `specified_token_a`: If `true`, the specified token is token A. Otherwise, it is token B.
+/// - `specified_input`: If `true`, `token_amount` is the input amount. Otherwise, it is the output amount.
+/// - `whirlpool`: The whirlpool state.
+/// - `oracle`: The oracle data for the whirlpool.
+/// - `tick_arrays`: The tick arrays needed for the whole swap. Any number of tick arrays can be provided.
+/// - `timestamp`: The timestamp for the swaps.
+/// - `max_tick_arrays`: The maximum number of tick arrays a single swap may traverse.
+///
+/// # Returns
+/// The amount of the specified token for each consecutive swap.
+/// # Notes
+/// - This function doesn't take into account transfer fee extension.
+/// - All swaps are assumed to execute at `timestamp`.
+#[allow(clippy::too_many_arguments)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub fn split_swap_by_tick_arrays(
+    token_amount: u64,
+    specified_token_a: bool,
+    specified_input: bool,
+    whirlpool: WhirlpoolFacade,
+    oracle: Option<OracleFacade>,
+    tick_arrays: TickArrays,
+    timestamp: u64,
+    max_tick_arrays: u32,
+) -> Result<Vec<u64>, CoreError> {
+    let a_to_b = specified_token_a == specified_input;
+    let tick_arrays: Vec<TickArrayFacade> = tick_arrays.into();
+    let tick_sequence = TickArraySequence::new(tick_arrays, whirlpool.tick_spacing)?;
+
+    let (mut whirlpool, mut adaptive_fee_info) = apply_oracle(whirlpool, oracle, timestamp);
+    let mut amount_remaining = token_amount;
+    let mut amounts: Vec<u64> = Vec::new();
+
+    while amount_remaining > 0 {
+        let sqrt_price_limit: u128 = get_swap_sqrt_price_limit(
+            whirlpool.tick_current_index,
+            whirlpool.tick_spacing,
+            a_to_b,
+            max_tick_arrays,
+        )
+        .into();
+
+        // The price cannot move any further in the swap direction
+        if a_to_b && sqrt_price_limit >= whirlpool.sqrt_price
+            || !a_to_b && sqrt_price_limit <= whirlpool.sqrt_price
+        {
+            break;
+        }
+
+        let mut swap_result = compute_swap(
+            amount_remaining,
+            sqrt_price_limit,
+            whirlpool,
+            &tick_sequence,
+            a_to_b,
+            specified_input,
+            timestamp,
+            adaptive_fee_info,
+        )?;
+        let mut amount = get_specified_amount(&swap_result, specified_token_a);
+
+        // Each swap is executed without an explicit sqrt price limit. Rounding of the fee can push
+        // the price just past the limit so search for the largest amount that fits in
+        // `max_tick_arrays`. The number of tick arrays a swap touches only grows with its amount.
+        if amount > 0 && amount < amount_remaining {
+            let swap_without_limit = |amount: u64| {
+                compute_swap(
+                    amount,
+                    0,
+                    whirlpool,
+                    &tick_sequence,
+                    a_to_b,
+                    specified_input,
+                    timestamp,
+                    adaptive_fee_info,
+                )
+            };
+
+            swap_result = swap_without_limit(amount)?;
+            if swap_result.tick_array_count > max_tick_arrays {
+                let mut fitting_amount = 0;
+                let mut exceeding_amount = amount;
+                while exceeding_amount - fitting_amount > 1 {
+                    let candidate = fitting_amount + (exceeding_amount - fitting_amount) / 2;
+                    let candidate_result = swap_without_limit(candidate)?;
+                    if candidate_result.tick_array_count <= max_tick_arrays {
+                        fitting_amount = candidate;
+                        swap_result = candidate_result;
+                    } else {
+                        exceeding_amount = candidate;
+                    }
+                }
+                amount = fitting_amount;
+            }
+        }
+
+        if amount == 0 {
+            break;
+        }
+
+        amounts.push(amount);
+        amount_remaining -= amount;
+
+        whirlpool.sqrt_price = swap_result.next_sqrt_price;
+        whirlpool.tick_current_index = swap_result.next_tick_index;
+        whirlpool.liquidity = swap_result.next_liquidity;
+        adaptive_fee_info = swap_result.next_adaptive_fee_info;
+    }
+
+    Ok(amounts)
+}
+
 pub struct SwapResult {
     pub token_a: u64,
     pub token_b: u64,
     pub trade_fee: u64,
     pub applied_fee_rate_min: u32,
     pub applied_fee_rate_max: u32,
+    pub next_sqrt_price: u128,
+    pub next_tick_index: i32,
+    pub next_liquidity: u128,
+    pub next_adaptive_fee_info: Option<AdaptiveFeeInfo>,
+    pub tick_array_count: u32,
 }
 
 /// Computes the amounts of tokens A and B based on the current Whirlpool state and tick sequence.
@@ -180,7 +312,7 @@ pub struct SwapResult {
 /// - `sqrt_price_limit`: The price limit for the swap represented as a square root.
 ///    If set to `0`, it defaults to the minimum or maximum sqrt price based on the direction of the swap.
 /// - `whirlpool`: The current state of the Whirlpool AMM, including liquidity, price, and tick information.
-/// - `tick_sequence`: A sequence of ticks used to determine price levels during the swap process.
+/// - `tick_sequence`: A sequence of tick arrays of any length used to determine price levels during the swap process.
 /// - `a_to_b`: Indicates the direction of the swap:
 ///    - `true`: Swap from token A to token B.
 ///    - `false`: Swap from token B to token A.
@@ -195,11 +327,11 @@ pub struct SwapResult {
 /// - This function doesn't take into account slippage tolerance.
 /// - This function doesn't take into account transfer fee extension.
 #[allow(clippy::too_many_arguments)]
-pub fn compute_swap<const SIZE: usize>(
+pub fn compute_swap(
     token_amount: u64,
     sqrt_price_limit: u128,
     whirlpool: WhirlpoolFacade,
-    tick_sequence: TickArraySequence<SIZE>,
+    tick_sequence: &TickArraySequence,
     a_to_b: bool,
     specified_input: bool,
     timestamp: u64,
@@ -371,11 +503,47 @@ pub fn compute_swap<const SIZE: usize>(
         trade_fee,
         applied_fee_rate_min: applied_fee_rate_min.unwrap_or(base_fee_rate as u32),
         applied_fee_rate_max: applied_fee_rate_max.unwrap_or(base_fee_rate as u32),
+        next_sqrt_price: current_sqrt_price,
+        next_tick_index: current_tick_index,
+        next_liquidity: current_liquidity,
+        next_adaptive_fee_info: fee_rate_manager.get_next_adaptive_fee_info(),
+        tick_array_count: get_swap_tick_array_count(
+            whirlpool.tick_current_index,
+            current_sqrt_price.into(),
+            whirlpool.tick_spacing,
+            a_to_b,
+        ),
     })
 }
 
 // Private functions
 
+// Applies the oracle of a whirlpool to a swap: the fee rate in effect at `timestamp` according to
+// the fee rate schedule, and the adaptive fee info for whirlpools initialized with adaptive fee.
+// Whirlpools without adaptive fee can have an oracle to hold a fee rate schedule.
+fn apply_oracle(
+    whirlpool: WhirlpoolFacade,
+    oracle: Option<OracleFacade>,
+    timestamp: u64,
+) -> (WhirlpoolFacade, Option<AdaptiveFeeInfo>) {
+    let whirlpool = WhirlpoolFacade {
+        fee_rate: get_fee_rate_at_timestamp(whirlpool, oracle, timestamp),
+        ..whirlpool
+    };
+    let adaptive_fee_info = oracle
+        .filter(|_| whirlpool.is_initialized_with_adaptive_fee())
+        .map(|oracle| oracle.into());
+    (whirlpool, adaptive_fee_info)
+}
+
+fn get_specified_amount(swap_result: &SwapResult, specified_token_a: bool) -> u64 {
+    if specified_token_a {
+        swap_result.token_a
+    } else {
+        swap_result.token_b
+    }
+}
+
 fn get_next_liquidity(
     current_liquidity: u128,
     next_tick: Option<&TickFacade>,
@@ -567,7 +735,10 @@ fn try_get_next_sqrt_price(
 
 #[cfg(all(test, not(feature = "wasm")))]
 mod tests {
-    use crate::{TickArrayFacade, TICK_ARRAY_SIZE};
+    use crate::{
+        FeeRateScheduleFacade, TickArrayFacade, INVALID_TICK_ARRAY_SEQUENCE, MAX_SWAP_TICK_ARRAYS,
+        TICK_ARRAY_SIZE,
+    };
 
     use super::*;
 
@@ -809,6 +980,130 @@ mod tests {
         assert!(matches!(result_3429, Err(INVALID_TICK_ARRAY_SEQUENCE)));
     }
 
+    fn test_empty_tick_arrays(count: i32) -> Vec<TickArrayFacade> {
+        (0..count)
+            .map(|i| TickArrayFacade {
+                start_tick_index: -(i * TICK_ARRAY_SIZE as i32 * 2),
+                ticks: [TickFacade::default(); TICK_ARRAY_SIZE],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_exact_in_many_tick_arrays() {
+        let whirlpool = WhirlpoolFacade {
+            liquidity: 1_000_000,
+            ..test_whirlpool(1 << 64, true)
+        };
+        let result = swap_quote_by_input_token(
+            50_000,
+            true,
+            1000,
+            whirlpool,
+            None,
+            test_empty_tick_arrays(10).into(),
+            now(),
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(result.token_in, 50_000);
+        assert_eq!(result.tick_array_count, 7);
+
+        let result = swap_quote_by_input_token(
+            50_000,
+            true,
+            1000,
+            whirlpool,
+            None,
+            test_empty_tick_arrays(3).into(),
+            now(),
+            None,
+            None,
+        );
+        assert_eq!(result.err(), Some(INVALID_TICK_ARRAY_SEQUENCE));
+    }
+
+    #[test]
+    fn test_split_swap_by_tick_arrays() {
+        let whirlpool = WhirlpoolFacade {
+            liquidity: 1_000_000,
+            ..test_whirlpool(1 << 64, true)
+        };
+        let amounts = split_swap_by_tick_arrays(
+            50_000,
+            true,
+            true,
+            whirlpool,
+            None,
+            test_empty_tick_arrays(10).into(),
+            now(),
+            MAX_SWAP_TICK_ARRAYS,
+        )
+        .unwrap();
+        assert_eq!(amounts.len(), 2);
+        assert_eq!(amounts.iter().sum::<u64>(), 50_000);
+
+        let first_swap = swap_quote_by_input_token(
+            amounts[0],
+            true,
+            1000,
+            whirlpool,
+            None,
+            test_empty_tick_arrays(10).into(),
+            now(),
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(first_swap.token_in, amounts[0]);
+        assert_eq!(first_swap.tick_array_count, MAX_SWAP_TICK_ARRAYS);
+    }
+
+    #[test]
+    fn test_exact_in_with_fee_rate_schedule() {
+        let oracle = OracleFacade {
+            fee_rate_schedule: FeeRateScheduleFacade {
+                start_fee_rate: 3000,
+                target_fee_rate: 1000,
+                ramp_start_timestamp: 2_000,
+                effective_timestamp: 3_000,
+            },
+            ..OracleFacade::default()
+        };
+        let quote_at = |timestamp: u64| {
+            swap_quote_by_input_token(
+                1_000_000,
+                true,
+                1000,
+                test_whirlpool(1 << 64, true),
+                Some(oracle),
+                test_tick_arrays(),
+                timestamp,
+                None,
+                None,
+            )
+            .unwrap()
+        };
+
+        // before the ramp, the fee rate of the whirlpool is used
+        let before = quote_at(1_000);
+        assert_eq!(before.trade_fee_rate_min, 3000);
+        assert_eq!(before.trade_fee_rate_max, 3000);
+        // halfway through the ramp
+        let halfway = quote_at(2_500);
+        assert_eq!(halfway.trade_fee_rate_min, 2000);
+        assert_eq!(halfway.trade_fee_rate_max, 2000);
+        // once effective
+        let after = quote_at(3_000);
+        assert_eq!(after.trade_fee_rate_min, 1000);
+        assert_eq!(after.trade_fee_rate_max, 1000);
+        assert!(after.trade_fee < halfway.trade_fee);
+        assert!(halfway.trade_fee < before.trade_fee);
+        assert!(after.token_est_out > halfway.token_est_out);
+        assert!(halfway.token_est_out > before.token_est_out);
+    }
+
     mod adaptive_fee {
         use crate::{AdaptiveFeeConstantsFacade, AdaptiveFeeVariablesFacade};
 
@@ -855,6 +1150,7 @@ mod tests {
                 trade_enable_timestamp,
                 adaptive_fee_constants,
                 adaptive_fee_variables,
+                ..OracleFacade::default()
             }
         }
 
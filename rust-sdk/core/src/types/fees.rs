@@ -9,3 +9,14 @@ pub struct CollectFeesQuote {
     pub fee_owed_a: u64,
     pub fee_owed_b: u64,
 }
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub struct FlashLoanQuote {
+    pub fee_a: u64,
+    pub fee_b: u64,
+    pub token_received_a: u64,
+    pub token_received_b: u64,
+    pub token_repay_a: u64,
+    pub token_repay_b: u64,
+}
@@ -24,3 +24,25 @@ pub struct IncreaseLiquidityQuote {
     pub token_max_a: u64,
     pub token_max_b: u64,
 }
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub struct RangeOrderQuote {
+    pub liquidity_delta: u128,
+    pub tick_lower_index: i32,
+    pub tick_upper_index: i32,
+    pub token_max_in: u64,
+    pub token_est_out: u64,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub struct CompoundFeesQuote {
+    pub liquidity_delta: u128,
+    pub token_est_a: u64,
+    pub token_est_b: u64,
+    pub fee_owed_a: u64,
+    pub fee_owed_b: u64,
+    pub reward_owed_a: u64,
+    pub reward_owed_b: u64,
+}
@@ -9,6 +9,16 @@ pub struct OracleFacade {
     pub trade_enable_timestamp: u64,
     pub adaptive_fee_constants: AdaptiveFeeConstantsFacade,
     pub adaptive_fee_variables: AdaptiveFeeVariablesFacade,
+    pub fee_rate_schedule: FeeRateScheduleFacade,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub struct FeeRateScheduleFacade {
+    pub start_fee_rate: u16,
+    pub target_fee_rate: u16,
+    pub ramp_start_timestamp: u64,
+    pub effective_timestamp: u64,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
@@ -48,3 +58,42 @@ impl From<OracleFacade> for AdaptiveFeeInfo {
         }
     }
 }
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub struct AdaptiveFeeForecast {
+    pub timestamp: u64,
+    pub volatility_reference: u32,
+    pub trade_fee: u64,
+    pub trade_fee_rate_min: u32,
+    pub trade_fee_rate_max: u32,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub struct OracleObservationFacade {
+    pub timestamp: u64,
+    pub tick_cumulative: i64,
+    pub sqrt_price_cumulative: u128,
+}
+
+impl OracleObservationFacade {
+    pub fn is_initialized(&self) -> bool {
+        self.timestamp != 0
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub struct OracleObservationsFacade {
+    pub observation_index: u16,
+    pub observation_cardinality: u16,
+    pub observations: Vec<OracleObservationFacade>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub struct OracleTwap {
+    pub tick_index: i32,
+    pub sqrt_price: u128,
+}
@@ -16,3 +16,12 @@ pub struct CollectRewardsQuote {
 pub struct CollectRewardQuote {
     pub rewards_owed: u64,
 }
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub struct RewardVaultRunwayQuote {
+    pub growth_global_x64: u128,
+    pub rewards_outstanding: u64,
+    pub vault_surplus: u64,
+    pub depletion_timestamp: u64,
+}
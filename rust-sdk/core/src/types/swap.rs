@@ -13,6 +13,7 @@ pub struct ExactInSwapQuote {
     pub trade_fee: u64,
     pub trade_fee_rate_min: u32,
     pub trade_fee_rate_max: u32,
+    pub tick_array_count: u32,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
@@ -25,4 +26,5 @@ pub struct ExactOutSwapQuote {
     pub trade_fee: u64,
     pub trade_fee_rate_min: u32,
     pub trade_fee_rate_max: u32,
+    pub tick_array_count: u32,
 }
@@ -1,6 +1,9 @@
 use crate::types::TickArrayFacade;
 
+// The fixed-arity variants are kept inline so existing callers can keep constructing them by
+// value. `Many` is only a heap pointer, which is what makes the size difference large.
 #[cfg(not(feature = "wasm"))]
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TickArrays {
     One(TickArrayFacade),
@@ -27,6 +30,7 @@ pub enum TickArrays {
         TickArrayFacade,
         TickArrayFacade,
     ),
+    Many(Vec<TickArrayFacade>),
 }
 
 #[cfg(feature = "wasm")]
@@ -53,65 +57,44 @@ impl Debug for TickArrays {
 }
 
 #[cfg(feature = "wasm")]
-impl From<TickArrays> for [Option<TickArrayFacade>; 6] {
+impl From<TickArrays> for Vec<TickArrayFacade> {
     fn from(val: TickArrays) -> Self {
         let val = JsValue::from(val);
         if !val.is_array() {
-            return [None, None, None, None, None, None];
+            return Vec::new();
         }
         let array: Array = val.unchecked_into();
-        let mut result = [None, None, None, None, None, None];
-        for (i, item) in array.iter().enumerate() {
-            if let Ok(item) = serde_wasm_bindgen::from_value(item) {
-                result[i] = Some(item);
-            }
-        }
-        result
+        array
+            .iter()
+            .filter_map(|item| serde_wasm_bindgen::from_value(item).ok())
+            .collect()
     }
 }
 
 #[cfg(not(feature = "wasm"))]
-impl From<TickArrays> for [Option<TickArrayFacade>; 6] {
+impl From<TickArrays> for Vec<TickArrayFacade> {
     fn from(val: TickArrays) -> Self {
         match val {
-            TickArrays::One(tick_array) => [Some(tick_array), None, None, None, None, None],
-            TickArrays::Two(tick_array_1, tick_array_2) => [
-                Some(tick_array_1),
-                Some(tick_array_2),
-                None,
-                None,
-                None,
-                None,
-            ],
-            TickArrays::Three(tick_array_1, tick_array_2, tick_array_3) => [
-                Some(tick_array_1),
-                Some(tick_array_2),
-                Some(tick_array_3),
-                None,
-                None,
-                None,
-            ],
-            TickArrays::Four(tick_array_1, tick_array_2, tick_array_3, tick_array_4) => [
-                Some(tick_array_1),
-                Some(tick_array_2),
-                Some(tick_array_3),
-                Some(tick_array_4),
-                None,
-                None,
-            ],
+            TickArrays::One(tick_array) => vec![tick_array],
+            TickArrays::Two(tick_array_1, tick_array_2) => vec![tick_array_1, tick_array_2],
+            TickArrays::Three(tick_array_1, tick_array_2, tick_array_3) => {
+                vec![tick_array_1, tick_array_2, tick_array_3]
+            }
+            TickArrays::Four(tick_array_1, tick_array_2, tick_array_3, tick_array_4) => {
+                vec![tick_array_1, tick_array_2, tick_array_3, tick_array_4]
+            }
             TickArrays::Five(
                 tick_array_1,
                 tick_array_2,
                 tick_array_3,
                 tick_array_4,
                 tick_array_5,
-            ) => [
-                Some(tick_array_1),
-                Some(tick_array_2),
-                Some(tick_array_3),
-                Some(tick_array_4),
-                Some(tick_array_5),
-                None,
+            ) => vec![
+                tick_array_1,
+                tick_array_2,
+                tick_array_3,
+                tick_array_4,
+                tick_array_5,
             ],
             TickArrays::Six(
                 tick_array_1,
@@ -120,14 +103,15 @@ impl From<TickArrays> for [Option<TickArrayFacade>; 6] {
                 tick_array_4,
                 tick_array_5,
                 tick_array_6,
-            ) => [
-                Some(tick_array_1),
-                Some(tick_array_2),
-                Some(tick_array_3),
-                Some(tick_array_4),
-                Some(tick_array_5),
-                Some(tick_array_6),
+            ) => vec![
+                tick_array_1,
+                tick_array_2,
+                tick_array_3,
+                tick_array_4,
+                tick_array_5,
+                tick_array_6,
             ],
+            TickArrays::Many(tick_arrays) => tick_arrays,
         }
     }
 }
@@ -180,3 +164,24 @@ impl From<[TickArrayFacade; 6]> for TickArrays {
         TickArrays::Six(val[0], val[1], val[2], val[3], val[4], val[5])
     }
 }
+
+#[cfg(not(feature = "wasm"))]
+impl From<Vec<TickArrayFacade>> for TickArrays {
+    fn from(val: Vec<TickArrayFacade>) -> Self {
+        TickArrays::Many(val)
+    }
+}
+
+#[cfg(not(feature = "wasm"))]
+impl From<&[TickArrayFacade]> for TickArrays {
+    fn from(val: &[TickArrayFacade]) -> Self {
+        TickArrays::Many(val.to_vec())
+    }
+}
+
+#[cfg(not(feature = "wasm"))]
+impl FromIterator<TickArrayFacade> for TickArrays {
+    fn from_iter<I: IntoIterator<Item = TickArrayFacade>>(iter: I) -> Self {
+        TickArrays::Many(iter.into_iter().collect())
+    }
+}
@@ -22,3 +22,23 @@ impl TransferFee {
         Self { fee_bps, max_fee }
     }
 }
+
+/// The interest rate state of a Token-2022 mint with the `InterestBearingConfig` extension.
+/// Rates are in basis points per year.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub struct InterestBearingConfigFacade {
+    pub initialization_timestamp: u64,
+    pub pre_update_average_rate: i16,
+    pub last_update_timestamp: u64,
+    pub current_rate: i16,
+}
+
+/// The multiplier state of a Token-2022 mint with the `ScaledUiAmount` extension.
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "wasm", wasm_expose)]
+pub struct ScaledUiAmountConfigFacade {
+    pub multiplier: f64,
+    pub new_multiplier_effective_timestamp: u64,
+    pub new_multiplier: f64,
+}
@@ -0,0 +1,173 @@
+use std::error::Error;
+
+use orca_whirlpools_client::{
+    get_fee_tier_address, get_token_badge_address, get_whirlpools_config_extension_address,
+    InitializeAdaptiveFeeTier, InitializeAdaptiveFeeTierInstructionArgs, InitializeConfig,
+    InitializeConfigExtension, InitializeConfigInstructionArgs, InitializeFeeTier,
+    InitializeFeeTierInstructionArgs, InitializeTokenBadge,
+};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer, system_program};
+
+use crate::TestContext;
+
+/// The default protocol fee rate of configs created by `setup_config`.
+pub const DEFAULT_PROTOCOL_FEE_RATE: u16 = 300;
+
+/// The settings of an adaptive fee tier created by `setup_adaptive_fee_tier`.
+#[derive(Debug, Clone, Copy)]
+pub struct AdaptiveFeeTierConfig {
+    pub fee_tier_index: u16,
+    pub tick_spacing: u16,
+    pub initialize_pool_authority: Pubkey,
+    pub delegated_fee_authority: Pubkey,
+    pub default_base_fee_rate: u16,
+    pub filter_period: u16,
+    pub decay_period: u16,
+    pub reduction_factor: u16,
+    pub adaptive_fee_control_factor: u32,
+    pub max_volatility_accumulator: u32,
+    pub tick_group_size: u16,
+    pub major_swap_threshold_ticks: u16,
+}
+
+impl Default for AdaptiveFeeTierConfig {
+    fn default() -> Self {
+        Self {
+            fee_tier_index: 1024 + 64,
+            tick_spacing: 64,
+            initialize_pool_authority: Pubkey::default(),
+            delegated_fee_authority: Pubkey::default(),
+            default_base_fee_rate: 3000,
+            filter_period: 30,
+            decay_period: 600,
+            reduction_factor: 500,
+            adaptive_fee_control_factor: 4_000,
+            max_volatility_accumulator: 350_000,
+            tick_group_size: 64,
+            major_swap_threshold_ticks: 64,
+        }
+    }
+}
+
+/// Creates a whirlpools config and its config extension.
+///
+/// The signer is every authority of the config and the extension.
+pub async fn setup_config(ctx: &TestContext) -> Result<Pubkey, Box<dyn Error>> {
+    let config = Keypair::new();
+    let authority = ctx.signer.pubkey();
+    let config_extension = get_whirlpools_config_extension_address(&config.pubkey())?.0;
+
+    let instructions = vec![
+        InitializeConfig {
+            config: config.pubkey(),
+            funder: authority,
+            system_program: system_program::id(),
+        }
+        .instruction(InitializeConfigInstructionArgs {
+            fee_authority: authority,
+            collect_protocol_fees_authority: authority,
+            reward_emissions_super_authority: authority,
+            default_protocol_fee_rate: DEFAULT_PROTOCOL_FEE_RATE,
+        }),
+        InitializeConfigExtension {
+            config: config.pubkey(),
+            config_extension,
+            funder: authority,
+            fee_authority: authority,
+            system_program: system_program::id(),
+        }
+        .instruction(),
+    ];
+
+    ctx.send_transaction_with_signers(instructions, vec![&config])
+        .await?;
+
+    Ok(config.pubkey())
+}
+
+/// Creates a fee tier for a tick spacing under a config created by `setup_config`.
+pub async fn setup_fee_tier(
+    ctx: &TestContext,
+    config: Pubkey,
+    tick_spacing: u16,
+    default_fee_rate: u16,
+) -> Result<Pubkey, Box<dyn Error>> {
+    let fee_tier = get_fee_tier_address(&config, tick_spacing)?.0;
+
+    let instruction = InitializeFeeTier {
+        config,
+        fee_tier,
+        funder: ctx.signer.pubkey(),
+        fee_authority: ctx.signer.pubkey(),
+        system_program: system_program::id(),
+    }
+    .instruction(InitializeFeeTierInstructionArgs {
+        tick_spacing,
+        default_fee_rate,
+    });
+
+    ctx.send_transaction(vec![instruction]).await?;
+
+    Ok(fee_tier)
+}
+
+/// Creates an adaptive fee tier under a config created by `setup_config`.
+pub async fn setup_adaptive_fee_tier(
+    ctx: &TestContext,
+    config: Pubkey,
+    adaptive_fee_tier_config: AdaptiveFeeTierConfig,
+) -> Result<Pubkey, Box<dyn Error>> {
+    let adaptive_fee_tier =
+        get_fee_tier_address(&config, adaptive_fee_tier_config.fee_tier_index)?.0;
+
+    let instruction = InitializeAdaptiveFeeTier {
+        whirlpools_config: config,
+        adaptive_fee_tier,
+        funder: ctx.signer.pubkey(),
+        fee_authority: ctx.signer.pubkey(),
+        system_program: system_program::id(),
+    }
+    .instruction(InitializeAdaptiveFeeTierInstructionArgs {
+        fee_tier_index: adaptive_fee_tier_config.fee_tier_index,
+        tick_spacing: adaptive_fee_tier_config.tick_spacing,
+        initialize_pool_authority: adaptive_fee_tier_config.initialize_pool_authority,
+        delegated_fee_authority: adaptive_fee_tier_config.delegated_fee_authority,
+        default_base_fee_rate: adaptive_fee_tier_config.default_base_fee_rate,
+        filter_period: adaptive_fee_tier_config.filter_period,
+        decay_period: adaptive_fee_tier_config.decay_period,
+        reduction_factor: adaptive_fee_tier_config.reduction_factor,
+        adaptive_fee_control_factor: adaptive_fee_tier_config.adaptive_fee_control_factor,
+        max_volatility_accumulator: adaptive_fee_tier_config.max_volatility_accumulator,
+        tick_group_size: adaptive_fee_tier_config.tick_group_size,
+        major_swap_threshold_ticks: adaptive_fee_tier_config.major_swap_threshold_ticks,
+    });
+
+    ctx.send_transaction(vec![instruction]).await?;
+
+    Ok(adaptive_fee_tier)
+}
+
+/// Creates a token badge for a mint under a config created by `setup_config`.
+pub async fn setup_token_badge(
+    ctx: &TestContext,
+    config: Pubkey,
+    token_mint: Pubkey,
+) -> Result<Pubkey, Box<dyn Error>> {
+    let token_badge = get_token_badge_address(&config, &token_mint)?.0;
+
+    let instruction = InitializeTokenBadge {
+        whirlpools_config: config,
+        whirlpools_config_extension: get_whirlpools_config_extension_address(&config)?.0,
+        token_badge_authority: ctx.signer.pubkey(),
+        token_mint,
+        token_badge,
+        funder: ctx.signer.pubkey(),
+        system_program: system_program::id(),
+        token_badge_history: None,
+    }
+    .instruction();
+
+    ctx.send_transaction(vec![instruction]).await?;
+
+    Ok(token_badge)
+}
@@ -0,0 +1,152 @@
+use std::{
+    collections::BTreeSet,
+    error::Error,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use litesvm::LiteSVM;
+use solana_client::{nonblocking::rpc_client::RpcClient, rpc_client::RpcClientConfig};
+use solana_sdk::{
+    account::Account,
+    clock::Clock,
+    instruction::Instruction,
+    message::{v0::Message, VersionedMessage},
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    signer::Signer,
+    transaction::VersionedTransaction,
+};
+
+use crate::{programs::load_anchor_programs, rpc::LiteSvmRpcSender};
+
+/// The lamports the default signer starts with.
+pub const SIGNER_LAMPORTS: u64 = 1_000_000_000_000;
+
+/// An in-process test environment with the Whirlpool program loaded.
+///
+/// The environment runs on LiteSVM and exposes an `RpcClient` that the sdk instruction builders
+/// can use as if it was connected to a validator. Transactions are executed synchronously,
+/// so a sent transaction is immediately visible to the next request.
+pub struct TestContext {
+    /// An rpc client backed by the in-process runtime.
+    pub rpc: RpcClient,
+
+    /// A funded keypair that pays for and signs every transaction.
+    pub signer: Keypair,
+
+    svm: Arc<Mutex<LiteSVM>>,
+    addresses: Arc<Mutex<BTreeSet<Pubkey>>>,
+}
+
+impl TestContext {
+    /// Creates a test environment with the programs of the repository workspace.
+    ///
+    /// The programs must be built with `anchor build` before running the tests.
+    pub fn new() -> Result<Self, Box<dyn Error>> {
+        let workspace_root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../..");
+        Self::with_workspace(&workspace_root)
+    }
+
+    /// Creates a test environment with the programs listed in the `Anchor.toml` of a workspace.
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace_root` - The path of the directory that contains `Anchor.toml`.
+    pub fn with_workspace(workspace_root: &Path) -> Result<Self, Box<dyn Error>> {
+        let mut svm = LiteSVM::new();
+        load_anchor_programs(&mut svm, workspace_root)?;
+
+        let signer = Keypair::new();
+        svm.airdrop(&signer.pubkey(), SIGNER_LAMPORTS)
+            .map_err(|e| format!("Failed to fund signer: {}", e.err))?;
+
+        let svm = Arc::new(Mutex::new(svm));
+        let addresses = Arc::new(Mutex::new(BTreeSet::from([signer.pubkey()])));
+        let rpc = RpcClient::new_sender(
+            LiteSvmRpcSender {
+                svm: svm.clone(),
+                addresses: addresses.clone(),
+            },
+            RpcClientConfig::default(),
+        );
+
+        Ok(Self {
+            rpc,
+            signer,
+            svm,
+            addresses,
+        })
+    }
+
+    /// Gives direct access to the underlying runtime.
+    ///
+    /// Accounts written directly are not returned by `getProgramAccounts` and
+    /// `getTokenAccountsByOwner`. Use `set_account` to write accounts that should be.
+    pub fn svm(&self) -> MutexGuard<'_, LiteSVM> {
+        self.svm.lock().unwrap()
+    }
+
+    /// Writes an account directly, bypassing the programs.
+    pub fn set_account(&self, address: Pubkey, account: Account) -> Result<(), Box<dyn Error>> {
+        self.svm()
+            .set_account(address, account)
+            .map_err(|e| e.to_string())?;
+        self.addresses.lock().unwrap().insert(address);
+        Ok(())
+    }
+
+    /// Returns the current unix timestamp of the clock sysvar.
+    pub fn unix_timestamp(&self) -> i64 {
+        self.svm().get_sysvar::<Clock>().unix_timestamp
+    }
+
+    /// Moves the clock forward, which is required by oracle and reward tests.
+    ///
+    /// The slot advances by one so that the next transactions run in a new slot.
+    ///
+    /// # Arguments
+    ///
+    /// * `seconds` - The number of seconds to add to the unix timestamp.
+    pub fn warp_time(&self, seconds: i64) {
+        let mut svm = self.svm();
+        let mut clock = svm.get_sysvar::<Clock>();
+        clock.slot += 1;
+        clock.unix_timestamp += seconds;
+        svm.warp_to_slot(clock.slot);
+        svm.set_sysvar::<Clock>(&clock);
+        svm.expire_blockhash();
+    }
+
+    /// Sets the unix timestamp of the clock sysvar.
+    pub fn set_unix_timestamp(&self, unix_timestamp: i64) {
+        let seconds = unix_timestamp - self.unix_timestamp();
+        self.warp_time(seconds);
+    }
+
+    pub async fn send_transaction(
+        &self,
+        instructions: Vec<Instruction>,
+    ) -> Result<Signature, Box<dyn Error>> {
+        self.send_transaction_with_signers(instructions, vec![])
+            .await
+    }
+
+    pub async fn send_transaction_with_signers(
+        &self,
+        instructions: Vec<Instruction>,
+        signers: Vec<&Keypair>,
+    ) -> Result<Signature, Box<dyn Error>> {
+        let blockhash = self.rpc.get_latest_blockhash().await?;
+        let message = VersionedMessage::V0(Message::try_compile(
+            &self.signer.pubkey(),
+            &instructions,
+            &[],
+            blockhash,
+        )?);
+        let transaction =
+            VersionedTransaction::try_new(message, &[vec![&self.signer], signers].concat())?;
+        let signature = self.rpc.send_transaction(&transaction).await?;
+        Ok(signature)
+    }
+}
@@ -0,0 +1,17 @@
+mod config;
+mod context;
+mod pool;
+mod position;
+mod programs;
+mod rpc;
+mod snapshot;
+mod token;
+mod token_extensions;
+
+pub use config::*;
+pub use context::*;
+pub use pool::*;
+pub use position::*;
+pub use programs::*;
+pub use token::*;
+pub use token_extensions::*;
@@ -0,0 +1,209 @@
+use std::error::Error;
+
+use orca_whirlpools_client::{
+    get_fee_tier_address, get_oracle_address, get_tick_array_address, get_token_badge_address,
+    get_whirlpool_address, InitializeDynamicTickArray, InitializeDynamicTickArrayInstructionArgs,
+    InitializePoolV2, InitializePoolV2InstructionArgs, InitializePoolWithAdaptiveFee,
+    InitializePoolWithAdaptiveFeeInstructionArgs, InitializeTickArray,
+    InitializeTickArrayInstructionArgs,
+};
+use orca_whirlpools_core::{get_tick_array_start_tick_index, TICK_ARRAY_SIZE};
+use solana_program::sysvar::rent::ID as RENT_PROGRAM_ID;
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer, system_program,
+};
+
+use crate::TestContext;
+
+/// The kind of tick arrays created by `setup_tick_arrays`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TickArrayKind {
+    #[default]
+    Fixed,
+    Dynamic,
+}
+
+fn order_mints(token_a: Pubkey, token_b: Pubkey) -> (Pubkey, Pubkey) {
+    if token_a < token_b {
+        (token_a, token_b)
+    } else {
+        (token_b, token_a)
+    }
+}
+
+/// Creates a pool with a fee tier created by `setup_fee_tier`.
+///
+/// The mints are ordered as the program requires, so they can be passed in any order.
+///
+/// # Arguments
+///
+/// * `ctx` - The test environment.
+/// * `config` - The whirlpools config of the pool.
+/// * `token_a` - One of the mints of the pool.
+/// * `token_b` - The other mint of the pool.
+/// * `tick_spacing` - The tick spacing of the fee tier.
+/// * `initial_sqrt_price` - The initial sqrt price of the pool.
+pub async fn setup_whirlpool(
+    ctx: &TestContext,
+    config: Pubkey,
+    token_a: Pubkey,
+    token_b: Pubkey,
+    tick_spacing: u16,
+    initial_sqrt_price: u128,
+) -> Result<Pubkey, Box<dyn Error>> {
+    let (token_a, token_b) = order_mints(token_a, token_b);
+    let whirlpool = get_whirlpool_address(&config, &token_a, &token_b, tick_spacing)?.0;
+    let vault_a = Keypair::new();
+    let vault_b = Keypair::new();
+    let mint_a_info = ctx.rpc.get_account(&token_a).await?;
+    let mint_b_info = ctx.rpc.get_account(&token_b).await?;
+
+    let instruction = InitializePoolV2 {
+        whirlpool,
+        fee_tier: get_fee_tier_address(&config, tick_spacing)?.0,
+        token_mint_a: token_a,
+        token_mint_b: token_b,
+        whirlpools_config: config,
+        funder: ctx.signer.pubkey(),
+        token_vault_a: vault_a.pubkey(),
+        token_vault_b: vault_b.pubkey(),
+        token_badge_a: get_token_badge_address(&config, &token_a)?.0,
+        token_badge_b: get_token_badge_address(&config, &token_b)?.0,
+        token_program_a: mint_a_info.owner,
+        token_program_b: mint_b_info.owner,
+        system_program: system_program::id(),
+        rent: RENT_PROGRAM_ID,
+    }
+    .instruction(InitializePoolV2InstructionArgs {
+        tick_spacing,
+        initial_sqrt_price,
+    });
+
+    ctx.send_transaction_with_signers(vec![instruction], vec![&vault_a, &vault_b])
+        .await?;
+
+    Ok(whirlpool)
+}
+
+/// Creates a pool and its oracle with an adaptive fee tier created by `setup_adaptive_fee_tier`.
+///
+/// The signer is used as the initialize pool authority, so permissioned fee tiers work if the signer is their authority.
+pub async fn setup_adaptive_fee_whirlpool(
+    ctx: &TestContext,
+    config: Pubkey,
+    token_a: Pubkey,
+    token_b: Pubkey,
+    fee_tier_index: u16,
+    initial_sqrt_price: u128,
+) -> Result<Pubkey, Box<dyn Error>> {
+    let (token_a, token_b) = order_mints(token_a, token_b);
+    let whirlpool = get_whirlpool_address(&config, &token_a, &token_b, fee_tier_index)?.0;
+    let vault_a = Keypair::new();
+    let vault_b = Keypair::new();
+    let mint_a_info = ctx.rpc.get_account(&token_a).await?;
+    let mint_b_info = ctx.rpc.get_account(&token_b).await?;
+
+    let instruction = InitializePoolWithAdaptiveFee {
+        whirlpools_config: config,
+        token_mint_a: token_a,
+        token_mint_b: token_b,
+        token_badge_a: get_token_badge_address(&config, &token_a)?.0,
+        token_badge_b: get_token_badge_address(&config, &token_b)?.0,
+        funder: ctx.signer.pubkey(),
+        initialize_pool_authority: ctx.signer.pubkey(),
+        whirlpool,
+        oracle: get_oracle_address(&whirlpool)?.0,
+        token_vault_a: vault_a.pubkey(),
+        token_vault_b: vault_b.pubkey(),
+        adaptive_fee_tier: get_fee_tier_address(&config, fee_tier_index)?.0,
+        token_program_a: mint_a_info.owner,
+        token_program_b: mint_b_info.owner,
+        system_program: system_program::id(),
+        rent: RENT_PROGRAM_ID,
+    }
+    .instruction(InitializePoolWithAdaptiveFeeInstructionArgs {
+        initial_sqrt_price,
+        trade_enable_timestamp: None,
+    });
+
+    ctx.send_transaction_with_signers(vec![instruction], vec![&vault_a, &vault_b])
+        .await?;
+
+    Ok(whirlpool)
+}
+
+/// Creates the missing tick arrays that cover a tick range of a pool.
+///
+/// # Arguments
+///
+/// * `ctx` - The test environment.
+/// * `whirlpool` - The pool of the tick arrays.
+/// * `tick_spacing` - The tick spacing of the pool.
+/// * `lower_tick_index` - The lowest tick index to cover.
+/// * `upper_tick_index` - The highest tick index to cover.
+/// * `kind` - Whether to create fixed or dynamic tick arrays.
+///
+/// # Returns
+///
+/// The addresses of all tick arrays in the range, including those that already existed.
+pub async fn setup_tick_arrays(
+    ctx: &TestContext,
+    whirlpool: Pubkey,
+    tick_spacing: u16,
+    lower_tick_index: i32,
+    upper_tick_index: i32,
+    kind: TickArrayKind,
+) -> Result<Vec<Pubkey>, Box<dyn Error>> {
+    let ticks_in_array = TICK_ARRAY_SIZE as i32 * tick_spacing as i32;
+    let start_low =
+        get_tick_array_start_tick_index(lower_tick_index.min(upper_tick_index), tick_spacing);
+    let start_high =
+        get_tick_array_start_tick_index(lower_tick_index.max(upper_tick_index), tick_spacing);
+
+    let start_tick_indexes: Vec<i32> = (start_low..=start_high)
+        .step_by(ticks_in_array as usize)
+        .collect();
+    let tick_array_addresses = start_tick_indexes
+        .iter()
+        .map(|x| get_tick_array_address(&whirlpool, *x).map(|x| x.0))
+        .collect::<Result<Vec<Pubkey>, _>>()?;
+    let tick_array_infos = ctx.rpc.get_multiple_accounts(&tick_array_addresses).await?;
+
+    let mut instructions: Vec<Instruction> = Vec::new();
+    for ((start_tick_index, tick_array), tick_array_info) in start_tick_indexes
+        .iter()
+        .zip(tick_array_addresses.iter())
+        .zip(tick_array_infos.iter())
+    {
+        if tick_array_info.is_some() {
+            continue;
+        }
+        instructions.push(match kind {
+            TickArrayKind::Fixed => InitializeTickArray {
+                whirlpool,
+                funder: ctx.signer.pubkey(),
+                tick_array: *tick_array,
+                system_program: system_program::id(),
+            }
+            .instruction(InitializeTickArrayInstructionArgs {
+                start_tick_index: *start_tick_index,
+            }),
+            TickArrayKind::Dynamic => InitializeDynamicTickArray {
+                whirlpool,
+                funder: ctx.signer.pubkey(),
+                tick_array: *tick_array,
+                system_program: system_program::id(),
+            }
+            .instruction(InitializeDynamicTickArrayInstructionArgs {
+                start_tick_index: *start_tick_index,
+                idempotent: true,
+            }),
+        });
+    }
+
+    for chunk in instructions.chunks(8) {
+        ctx.send_transaction(chunk.to_vec()).await?;
+    }
+
+    Ok(tick_array_addresses)
+}
@@ -0,0 +1,77 @@
+use std::error::Error;
+
+use orca_whirlpools_client::{
+    get_position_address, OpenPositionWithTokenExtensions,
+    OpenPositionWithTokenExtensionsInstructionArgs, Whirlpool,
+};
+use orca_whirlpools_core::get_initializable_tick_index;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer, system_program};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_token_2022::ID as TOKEN_2022_PROGRAM_ID;
+
+use crate::{setup_tick_arrays, TestContext, TickArrayKind};
+
+/// Opens an empty position and creates the tick arrays it needs.
+///
+/// The position is a Token-2022 position NFT held by the owner. Liquidity can be added with the sdk instruction builders.
+///
+/// # Arguments
+///
+/// * `ctx` - The test environment.
+/// * `whirlpool` - The pool of the position.
+/// * `tick_range` - The tick range of the position, rounded to initializable tick indexes.
+/// * `owner` - The owner of the position. Defaults to the signer if not provided.
+///
+/// # Returns
+///
+/// The mint address of the position NFT.
+pub async fn setup_position(
+    ctx: &TestContext,
+    whirlpool: Pubkey,
+    tick_range: (i32, i32),
+    owner: Option<Pubkey>,
+) -> Result<Pubkey, Box<dyn Error>> {
+    let owner = owner.unwrap_or(ctx.signer.pubkey());
+    let whirlpool_info = ctx.rpc.get_account(&whirlpool).await?;
+    let tick_spacing = Whirlpool::from_bytes(&whirlpool_info.data)?.tick_spacing;
+
+    let tick_lower_index = get_initializable_tick_index(tick_range.0, tick_spacing, Some(false));
+    let tick_upper_index = get_initializable_tick_index(tick_range.1, tick_spacing, Some(true));
+    setup_tick_arrays(
+        ctx,
+        whirlpool,
+        tick_spacing,
+        tick_lower_index,
+        tick_upper_index,
+        TickArrayKind::Fixed,
+    )
+    .await?;
+
+    let position_mint = Keypair::new();
+    let instruction = OpenPositionWithTokenExtensions {
+        funder: ctx.signer.pubkey(),
+        owner,
+        position: get_position_address(&position_mint.pubkey())?.0,
+        position_mint: position_mint.pubkey(),
+        position_token_account: get_associated_token_address_with_program_id(
+            &owner,
+            &position_mint.pubkey(),
+            &TOKEN_2022_PROGRAM_ID,
+        ),
+        whirlpool,
+        token2022_program: TOKEN_2022_PROGRAM_ID,
+        system_program: system_program::id(),
+        associated_token_program: spl_associated_token_account::id(),
+        metadata_update_auth: Pubkey::try_from("3axbTs2z5GBy6usVbNVoqEgZMng3vZvMnAoX29BFfwhr")?,
+    }
+    .instruction(OpenPositionWithTokenExtensionsInstructionArgs {
+        tick_lower_index,
+        tick_upper_index,
+        with_token_metadata_extension: true,
+    });
+
+    ctx.send_transaction_with_signers(vec![instruction], vec![&position_mint])
+        .await?;
+
+    Ok(position_mint.pubkey())
+}
@@ -0,0 +1,72 @@
+use std::{error::Error, fs::read_to_string, path::Path, str::FromStr};
+
+use litesvm::LiteSVM;
+use solana_sdk::pubkey::Pubkey;
+use toml::Table;
+
+/// Reads the programs to load from the `Anchor.toml` of a workspace.
+///
+/// This includes the programs in `programs.localnet`, which are loaded from `target/deploy`,
+/// and the external programs in `test.genesis`, which are loaded from their configured path.
+///
+/// # Arguments
+///
+/// * `workspace_root` - The path of the directory that contains `Anchor.toml`.
+///
+/// # Returns
+///
+/// A vector of program ids and the paths of their compiled `.so` files.
+pub fn anchor_programs(workspace_root: &Path) -> Result<Vec<(Pubkey, String)>, Box<dyn Error>> {
+    let toml_str = read_to_string(workspace_root.join("Anchor.toml"))?;
+    let parsed_toml = Table::from_str(&toml_str)?;
+    let mut programs: Vec<(Pubkey, String)> = Vec::new();
+
+    let localnet = parsed_toml
+        .get("programs")
+        .and_then(|x| x.get("localnet"))
+        .and_then(|x| x.as_table())
+        .ok_or("`programs.localnet` not found in Anchor.toml")?;
+    for (name, address) in localnet {
+        let address = address
+            .as_str()
+            .ok_or_else(|| format!("Invalid address for program {}", name))?;
+        let path = workspace_root
+            .join("target/deploy")
+            .join(format!("{}.so", name));
+        programs.push((Pubkey::from_str(address)?, path.to_string_lossy().into()));
+    }
+
+    let genesis = parsed_toml
+        .get("test")
+        .and_then(|x| x.get("genesis"))
+        .and_then(|x| x.as_array())
+        .cloned()
+        .unwrap_or_default();
+    for program in genesis {
+        let address = program
+            .get("address")
+            .and_then(|x| x.as_str())
+            .ok_or("`address` not found in `test.genesis` entry")?;
+        let path = program
+            .get("program")
+            .and_then(|x| x.as_str())
+            .ok_or("`program` not found in `test.genesis` entry")?;
+        programs.push((
+            Pubkey::from_str(address)?,
+            workspace_root.join(path).to_string_lossy().into(),
+        ));
+    }
+
+    Ok(programs)
+}
+
+pub(crate) fn load_anchor_programs(
+    svm: &mut LiteSVM,
+    workspace_root: &Path,
+) -> Result<(), Box<dyn Error>> {
+    for (program_id, path) in anchor_programs(workspace_root)? {
+        svm.add_program_from_file(program_id, &path)
+            .map_err(|e| format!("Failed to load program {} from {}: {}", program_id, path, e))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,388 @@
+use std::{
+    collections::BTreeSet,
+    error::Error,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use litesvm::LiteSVM;
+use serde_json::{from_value, to_value, Value};
+use solana_account_decoder::{
+    encode_ui_account,
+    parse_account_data::{AccountAdditionalDataV3, SplTokenAdditionalDataV2},
+    parse_token::{real_number_string_trimmed, UiTokenAmount},
+    UiAccount, UiAccountEncoding, UiDataSliceConfig,
+};
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind, Result as ClientResult},
+    rpc_client::SerializableTransaction,
+    rpc_filter::RpcFilterType,
+    rpc_request::RpcRequest,
+    rpc_response::{
+        Response, RpcBlockhash, RpcKeyedAccount, RpcResponseContext, RpcTokenAccountBalance,
+        RpcVersionInfo,
+    },
+    rpc_sender::{RpcSender, RpcTransportStats},
+};
+use solana_sdk::{
+    account::Account, bs58, clock::Clock, epoch_info::EpochInfo, pubkey::Pubkey,
+    transaction::VersionedTransaction,
+};
+use spl_token_2022::{
+    extension::StateWithExtensions,
+    state::{Account as TokenAccount, Mint},
+};
+
+// Version reported by `getVersion`. The rpc client only uses it for feature detection.
+const SOLANA_CORE_VERSION: &str = "2.2.0";
+
+fn get_encoding(config: &Value) -> UiAccountEncoding {
+    config
+        .as_object()
+        .and_then(|x| x.get("encoding"))
+        .and_then(|x| x.as_str())
+        .and_then(|x| from_value::<UiAccountEncoding>(x.into()).ok())
+        .unwrap_or(UiAccountEncoding::Base64)
+}
+
+fn get_data_slice(config: &Value) -> Option<UiDataSliceConfig> {
+    config
+        .as_object()
+        .and_then(|x| x.get("dataSlice"))
+        .and_then(|x| from_value::<UiDataSliceConfig>(x.clone()).ok())
+}
+
+fn get_filters(config: &Value) -> Result<Vec<RpcFilterType>, Box<dyn Error>> {
+    let filters = config
+        .as_object()
+        .and_then(|x| x.get("filters"))
+        .and_then(|x| x.as_array())
+        .cloned()
+        .unwrap_or_default();
+    let mut parsed_filters: Vec<RpcFilterType> = Vec::new();
+    for filter in filters {
+        parsed_filters.push(from_value::<RpcFilterType>(filter)?);
+    }
+    Ok(parsed_filters)
+}
+
+fn is_token_account(account: &Account) -> bool {
+    (account.owner == spl_token::ID || account.owner == spl_token_2022::ID)
+        && StateWithExtensions::<TokenAccount>::unpack(&account.data).is_ok()
+}
+
+fn filter_allows(filter: &RpcFilterType, account: &Account) -> bool {
+    match filter {
+        RpcFilterType::DataSize(size) => account.data.len() as u64 == *size,
+        RpcFilterType::Memcmp(memcmp) => memcmp.bytes_match(&account.data),
+        RpcFilterType::TokenAccountState => is_token_account(account),
+    }
+}
+
+// The `jsonParsed` encoding of token accounts needs the decimals of their mint.
+fn get_additional_data(svm: &LiteSVM, account: &Account) -> Option<AccountAdditionalDataV3> {
+    if !is_token_account(account) {
+        return None;
+    }
+    let token_account = StateWithExtensions::<TokenAccount>::unpack(&account.data).ok()?;
+    let mint_account = get_account(svm, &token_account.base.mint)?;
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_account.data).ok()?;
+    Some(AccountAdditionalDataV3 {
+        spl_token_additional_data: Some(SplTokenAdditionalDataV2::with_decimals(
+            mint.base.decimals,
+        )),
+    })
+}
+
+fn encode_account(
+    svm: &LiteSVM,
+    address: &Pubkey,
+    account: &Account,
+    encoding: UiAccountEncoding,
+    data_slice: Option<UiDataSliceConfig>,
+) -> UiAccount {
+    let additional_data = if encoding == UiAccountEncoding::JsonParsed {
+        get_additional_data(svm, account)
+    } else {
+        None
+    };
+    encode_ui_account(address, account, encoding, additional_data, data_slice)
+}
+
+fn to_wire_account(
+    svm: &LiteSVM,
+    address: &Pubkey,
+    account: Option<Account>,
+    encoding: UiAccountEncoding,
+    data_slice: Option<UiDataSliceConfig>,
+) -> Result<Value, Box<dyn Error>> {
+    if let Some(account) = account {
+        let value = to_value(encode_account(svm, address, &account, encoding, data_slice))?;
+        Ok(value)
+    } else {
+        Ok(Value::Null)
+    }
+}
+
+fn to_keyed_accounts(
+    svm: &LiteSVM,
+    accounts: Vec<(Pubkey, Account)>,
+    encoding: UiAccountEncoding,
+    data_slice: Option<UiDataSliceConfig>,
+) -> Vec<RpcKeyedAccount> {
+    accounts
+        .iter()
+        .map(|(address, account)| RpcKeyedAccount {
+            pubkey: address.to_string(),
+            account: encode_account(svm, address, account, encoding, data_slice),
+        })
+        .collect()
+}
+
+fn with_context<T: serde::Serialize>(slot: u64, value: T) -> Result<Value, Box<dyn Error>> {
+    Ok(to_value(Response {
+        context: RpcResponseContext {
+            slot,
+            api_version: None,
+        },
+        value,
+    })?)
+}
+
+fn get_account(svm: &LiteSVM, address: &Pubkey) -> Option<Account> {
+    // LiteSVM returns an empty system account for addresses that were never written.
+    svm.get_account(address)
+        .filter(|account| account.lamports > 0)
+}
+
+// LiteSVM cannot list its accounts, so the queries over all accounts scan the addresses
+// that have been written through the test environment.
+fn get_program_accounts(
+    svm: &LiteSVM,
+    addresses: &BTreeSet<Pubkey>,
+    program_id: &Pubkey,
+    filters: &[RpcFilterType],
+) -> Vec<(Pubkey, Account)> {
+    addresses
+        .iter()
+        .filter_map(|address| get_account(svm, address).map(|account| (*address, account)))
+        .filter(|(_, account)| &account.owner == program_id)
+        .filter(|(_, account)| filters.iter().all(|x| filter_allows(x, account)))
+        .collect()
+}
+
+fn send(
+    svm: &mut LiteSVM,
+    addresses: &mut BTreeSet<Pubkey>,
+    method: &str,
+    params: &[Value],
+) -> Result<Value, Box<dyn Error>> {
+    let slot = svm.get_sysvar::<Clock>().slot;
+
+    let response = match method {
+        "getAccountInfo" => {
+            let address = Pubkey::from_str(params[0].as_str().unwrap_or_default())?;
+            let config = params.get(1).unwrap_or(&Value::Null);
+            let account = get_account(svm, &address);
+            let account = to_wire_account(
+                svm,
+                &address,
+                account,
+                get_encoding(config),
+                get_data_slice(config),
+            )?;
+            with_context(slot, account)?
+        }
+        "getMultipleAccounts" => {
+            let default_addresses = Vec::new();
+            let addresses = params[0].as_array().unwrap_or(&default_addresses);
+            let config = params.get(1).unwrap_or(&Value::Null);
+            let mut accounts: Vec<Value> = Vec::new();
+            for address_str in addresses {
+                let address = Pubkey::from_str(address_str.as_str().unwrap_or_default())?;
+                let account = get_account(svm, &address);
+                accounts.push(to_wire_account(
+                    svm,
+                    &address,
+                    account,
+                    get_encoding(config),
+                    get_data_slice(config),
+                )?);
+            }
+            with_context(slot, accounts)?
+        }
+        "getProgramAccounts" => {
+            let program_id = Pubkey::from_str(params[0].as_str().unwrap_or_default())?;
+            let config = params.get(1).unwrap_or(&Value::Null);
+            let filters = get_filters(config)?;
+            let accounts = get_program_accounts(svm, addresses, &program_id, &filters);
+            let accounts =
+                to_keyed_accounts(svm, accounts, get_encoding(config), get_data_slice(config));
+            let with_context_flag = config
+                .as_object()
+                .and_then(|x| x.get("withContext"))
+                .and_then(|x| x.as_bool())
+                .unwrap_or(false);
+            if with_context_flag {
+                with_context(slot, accounts)?
+            } else {
+                to_value(accounts)?
+            }
+        }
+        "getTokenAccountsByOwner" => {
+            let owner = Pubkey::from_str(params[0].as_str().unwrap_or_default())?;
+            let filter = params.get(1).unwrap_or(&Value::Null);
+            let config = params.get(2).unwrap_or(&Value::Null);
+            let mint = filter
+                .get("mint")
+                .and_then(|x| x.as_str())
+                .map(Pubkey::from_str)
+                .transpose()?;
+            let program_id = filter.get("programId").and_then(|x| x.as_str());
+            let token_programs = match (mint, program_id) {
+                (Some(_), None) => vec![spl_token::ID, spl_token_2022::ID],
+                (None, Some(program_id)) => vec![Pubkey::from_str(program_id)?],
+                _ => return Err("Either mint or programId must be provided".into()),
+            };
+            let mut accounts: Vec<(Pubkey, Account)> = Vec::new();
+            for token_program in token_programs {
+                accounts.extend(get_program_accounts(
+                    svm,
+                    addresses,
+                    &token_program,
+                    &[RpcFilterType::TokenAccountState],
+                ));
+            }
+            let accounts = accounts
+                .into_iter()
+                .filter(|(_, account)| {
+                    StateWithExtensions::<TokenAccount>::unpack(&account.data).is_ok_and(|x| {
+                        x.base.owner == owner && mint.unwrap_or(x.base.mint) == x.base.mint
+                    })
+                })
+                .collect();
+            let accounts =
+                to_keyed_accounts(svm, accounts, get_encoding(config), get_data_slice(config));
+            with_context(slot, accounts)?
+        }
+        "getTokenLargestAccounts" => {
+            let mint = Pubkey::from_str(params[0].as_str().unwrap_or_default())?;
+            let mint_account = get_account(svm, &mint).ok_or("Mint not found")?;
+            let decimals = StateWithExtensions::<Mint>::unpack(&mint_account.data)?
+                .base
+                .decimals;
+            let mut balances: Vec<(Pubkey, u64)> = get_program_accounts(
+                svm,
+                addresses,
+                &mint_account.owner,
+                &[RpcFilterType::TokenAccountState],
+            )
+            .into_iter()
+            .filter_map(|(address, account)| {
+                let token_account =
+                    StateWithExtensions::<TokenAccount>::unpack(&account.data).ok()?;
+                (token_account.base.mint == mint).then_some((address, token_account.base.amount))
+            })
+            .collect();
+            balances.sort_by(|a, b| b.1.cmp(&a.1));
+            let balances: Vec<RpcTokenAccountBalance> = balances
+                .into_iter()
+                .take(20)
+                .map(|(address, amount)| RpcTokenAccountBalance {
+                    address: address.to_string(),
+                    amount: UiTokenAmount {
+                        ui_amount: Some(amount as f64 / 10f64.powi(decimals as i32)),
+                        decimals,
+                        amount: amount.to_string(),
+                        ui_amount_string: real_number_string_trimmed(amount, decimals),
+                    },
+                })
+                .collect();
+            with_context(slot, balances)?
+        }
+        "getBalance" => {
+            let address = Pubkey::from_str(params[0].as_str().unwrap_or_default())?;
+            with_context(slot, svm.get_balance(&address).unwrap_or(0))?
+        }
+        "getMinimumBalanceForRentExemption" => {
+            let data_len = params[0].as_u64().unwrap_or(0) as usize;
+            to_value(svm.minimum_balance_for_rent_exemption(data_len))?
+        }
+        "getLatestBlockhash" => with_context(
+            slot,
+            RpcBlockhash {
+                blockhash: svm.latest_blockhash().to_string(),
+                last_valid_block_height: slot + 150,
+            },
+        )?,
+        "sendTransaction" => {
+            let transaction_base64 = params[0].as_str().unwrap_or_default();
+            let transaction_bytes = base64::decode(transaction_base64)?;
+            let transaction = bincode::deserialize::<VersionedTransaction>(&transaction_bytes)?;
+            let signature = *transaction.get_signature();
+            let account_keys = transaction.message.static_account_keys().to_vec();
+            if let Err(failed) = svm.send_transaction(transaction) {
+                return Err(format!("{}\n{}", failed.err, failed.meta.logs.join("\n")).into());
+            }
+            addresses.extend(account_keys);
+            // A new blockhash allows sending the same instructions again in a later transaction.
+            svm.expire_blockhash();
+            to_value(bs58::encode(signature).into_string())?
+        }
+        "getSlot" | "getBlockHeight" => to_value(slot)?,
+        "getEpochInfo" => to_value(EpochInfo {
+            epoch: slot / 32,
+            slot_index: slot % 32,
+            slots_in_epoch: 32,
+            absolute_slot: slot,
+            block_height: slot,
+            transaction_count: Some(0),
+        })?,
+        "getVersion" => to_value(RpcVersionInfo {
+            solana_core: SOLANA_CORE_VERSION.to_string(),
+            feature_set: None,
+        })?,
+        _ => return Err(format!("Method not implemented: {}", method).into()),
+    };
+
+    Ok(response)
+}
+
+/// An `RpcSender` that serves requests from an in-process LiteSVM instance.
+pub(crate) struct LiteSvmRpcSender {
+    pub(crate) svm: Arc<Mutex<LiteSVM>>,
+
+    /// The addresses written by transactions or `TestContext::set_account`, which are the
+    /// candidates of `getProgramAccounts`, `getTokenAccountsByOwner` and `getTokenLargestAccounts`.
+    pub(crate) addresses: Arc<Mutex<BTreeSet<Pubkey>>>,
+}
+
+#[async_trait]
+impl RpcSender for LiteSvmRpcSender {
+    async fn send(&self, request: RpcRequest, params: Value) -> ClientResult<Value> {
+        let request_json = request.build_request_json(42, params.clone());
+        let method = request_json["method"].as_str().unwrap_or_default();
+        let default_params = Vec::new();
+        let params = request_json["params"].as_array().unwrap_or(&default_params);
+        let mut svm = self.svm.lock().map_err(|e| {
+            ClientError::new_with_request(ClientErrorKind::Custom(e.to_string()), request)
+        })?;
+        let mut addresses = self.addresses.lock().map_err(|e| {
+            ClientError::new_with_request(ClientErrorKind::Custom(e.to_string()), request)
+        })?;
+        let response = send(&mut svm, &mut addresses, method, params).map_err(|e| {
+            ClientError::new_with_request(ClientErrorKind::Custom(e.to_string()), request)
+        })?;
+
+        Ok(response)
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        RpcTransportStats::default()
+    }
+
+    fn url(&self) -> String {
+        "LiteSvmRpcSender".to_string()
+    }
+}
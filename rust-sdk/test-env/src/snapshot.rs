@@ -0,0 +1,29 @@
+use std::error::Error;
+
+use orca_whirlpools_client::PoolSnapshot;
+use solana_sdk::clock::Clock;
+
+use crate::TestContext;
+
+impl TestContext {
+    /// Loads a pool snapshot, e.g. one taken from mainnet to reproduce a failed swap.
+    ///
+    /// Every account of the snapshot is written as is and the clock is moved to the slot, epoch
+    /// and unix timestamp of the snapshot. The token accounts of the signer are not part of the
+    /// snapshot and have to be set up separately.
+    pub fn load_snapshot(&self, snapshot: &PoolSnapshot) -> Result<(), Box<dyn Error>> {
+        for (address, account) in snapshot.to_accounts() {
+            self.set_account(address, account)?;
+        }
+
+        let mut svm = self.svm();
+        let mut clock = svm.get_sysvar::<Clock>();
+        clock.slot = snapshot.slot;
+        clock.epoch = snapshot.epoch;
+        clock.unix_timestamp = snapshot.unix_timestamp;
+        svm.warp_to_slot(clock.slot);
+        svm.set_sysvar::<Clock>(&clock);
+        svm.expire_blockhash();
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+use std::error::Error;
+
+use solana_sdk::{
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_instruction::{create_account, transfer},
+};
+use spl_associated_token_account::{
+    get_associated_token_address_with_program_id,
+    instruction::create_associated_token_account_idempotent,
+};
+use spl_token::{native_mint, state::Mint, ID as TOKEN_PROGRAM_ID};
+
+use crate::TestContext;
+
+/// Creates a token program mint with the signer as mint authority.
+pub async fn setup_mint(ctx: &TestContext, decimals: u8) -> Result<Pubkey, Box<dyn Error>> {
+    let mint = Keypair::new();
+    let rent = ctx
+        .rpc
+        .get_minimum_balance_for_rent_exemption(Mint::LEN)
+        .await?;
+
+    let instructions = vec![
+        create_account(
+            &ctx.signer.pubkey(),
+            &mint.pubkey(),
+            rent,
+            Mint::LEN as u64,
+            &TOKEN_PROGRAM_ID,
+        ),
+        spl_token::instruction::initialize_mint2(
+            &TOKEN_PROGRAM_ID,
+            &mint.pubkey(),
+            &ctx.signer.pubkey(),
+            None,
+            decimals,
+        )?,
+    ];
+
+    ctx.send_transaction_with_signers(instructions, vec![&mint])
+        .await?;
+
+    Ok(mint.pubkey())
+}
+
+/// Creates the associated token account of an owner and mints tokens to it.
+///
+/// Works for both token programs. Native mint balances are funded by the signer and synced.
+///
+/// # Arguments
+///
+/// * `ctx` - The test environment.
+/// * `mint` - The mint of the token account. The signer must be its mint authority if `amount` is not zero.
+/// * `owner` - The owner of the token account. Defaults to the signer if not provided.
+/// * `amount` - The amount to mint to the token account.
+pub async fn setup_ata(
+    ctx: &TestContext,
+    mint: Pubkey,
+    owner: Option<Pubkey>,
+    amount: u64,
+) -> Result<Pubkey, Box<dyn Error>> {
+    let owner = owner.unwrap_or(ctx.signer.pubkey());
+    let token_program = ctx.rpc.get_account(&mint).await?.owner;
+    let ata = get_associated_token_address_with_program_id(&owner, &mint, &token_program);
+
+    let mut instructions = vec![create_associated_token_account_idempotent(
+        &ctx.signer.pubkey(),
+        &owner,
+        &mint,
+        &token_program,
+    )];
+
+    if amount > 0 {
+        if mint == native_mint::ID {
+            instructions.push(transfer(&ctx.signer.pubkey(), &ata, amount));
+            instructions.push(spl_token::instruction::sync_native(
+                &TOKEN_PROGRAM_ID,
+                &ata,
+            )?);
+        } else {
+            instructions.push(spl_token_2022::instruction::mint_to(
+                &token_program,
+                &mint,
+                &ata,
+                &ctx.signer.pubkey(),
+                &[],
+                amount,
+            )?);
+        }
+    }
+
+    ctx.send_transaction(instructions).await?;
+
+    Ok(ata)
+}
@@ -0,0 +1,195 @@
+use std::error::Error;
+
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    system_instruction::create_account,
+};
+use spl_token_2022::{
+    extension::{
+        confidential_transfer, default_account_state, group_member_pointer, group_pointer,
+        interest_bearing_mint, metadata_pointer, pausable, scaled_ui_amount,
+        transfer_fee::instruction::initialize_transfer_fee_config, transfer_hook, ExtensionType,
+    },
+    instruction::{
+        initialize_mint2, initialize_mint_close_authority, initialize_non_transferable_mint,
+        initialize_permanent_delegate,
+    },
+    state::{AccountState, Mint},
+    ID as TOKEN_2022_PROGRAM_ID,
+};
+
+use crate::TestContext;
+
+/// The settings of the extensions created by `setup_mint_with_extensions`.
+///
+/// Extensions that are not requested ignore their settings. Every authority of the mint is the signer.
+#[derive(Debug, Clone)]
+pub struct MintExtensionsConfig {
+    pub decimals: u8,
+    pub transfer_fee_basis_points: u16,
+    pub maximum_transfer_fee: u64,
+    pub interest_rate_basis_points: i16,
+    pub ui_amount_multiplier: f64,
+    pub transfer_hook_program_id: Option<Pubkey>,
+    pub default_account_state: AccountState,
+}
+
+impl Default for MintExtensionsConfig {
+    fn default() -> Self {
+        Self {
+            decimals: 6,
+            transfer_fee_basis_points: 100,
+            maximum_transfer_fee: 1_000_000_000,
+            interest_rate_basis_points: 500,
+            ui_amount_multiplier: 1.0,
+            transfer_hook_program_id: None,
+            default_account_state: AccountState::Initialized,
+        }
+    }
+}
+
+fn initialize_extension_instruction(
+    ctx: &TestContext,
+    mint: &Pubkey,
+    extension: ExtensionType,
+    config: &MintExtensionsConfig,
+) -> Result<Instruction, Box<dyn Error>> {
+    let authority = ctx.signer.pubkey();
+    let instruction = match extension {
+        ExtensionType::TransferFeeConfig => initialize_transfer_fee_config(
+            &TOKEN_2022_PROGRAM_ID,
+            mint,
+            Some(&authority),
+            Some(&authority),
+            config.transfer_fee_basis_points,
+            config.maximum_transfer_fee,
+        )?,
+        ExtensionType::InterestBearingConfig => interest_bearing_mint::instruction::initialize(
+            &TOKEN_2022_PROGRAM_ID,
+            mint,
+            Some(authority),
+            config.interest_rate_basis_points,
+        )?,
+        ExtensionType::ScaledUiAmount => scaled_ui_amount::instruction::initialize(
+            &TOKEN_2022_PROGRAM_ID,
+            mint,
+            Some(authority),
+            config.ui_amount_multiplier,
+        )?,
+        ExtensionType::MintCloseAuthority => {
+            initialize_mint_close_authority(&TOKEN_2022_PROGRAM_ID, mint, Some(&authority))?
+        }
+        ExtensionType::PermanentDelegate => {
+            initialize_permanent_delegate(&TOKEN_2022_PROGRAM_ID, mint, &authority)?
+        }
+        ExtensionType::NonTransferable => {
+            initialize_non_transferable_mint(&TOKEN_2022_PROGRAM_ID, mint)?
+        }
+        ExtensionType::DefaultAccountState => {
+            default_account_state::instruction::initialize_default_account_state(
+                &TOKEN_2022_PROGRAM_ID,
+                mint,
+                &config.default_account_state,
+            )?
+        }
+        ExtensionType::TransferHook => transfer_hook::instruction::initialize(
+            &TOKEN_2022_PROGRAM_ID,
+            mint,
+            Some(authority),
+            config.transfer_hook_program_id,
+        )?,
+        ExtensionType::MetadataPointer => metadata_pointer::instruction::initialize(
+            &TOKEN_2022_PROGRAM_ID,
+            mint,
+            Some(authority),
+            Some(*mint),
+        )?,
+        ExtensionType::GroupPointer => group_pointer::instruction::initialize(
+            &TOKEN_2022_PROGRAM_ID,
+            mint,
+            Some(authority),
+            Some(*mint),
+        )?,
+        ExtensionType::GroupMemberPointer => group_member_pointer::instruction::initialize(
+            &TOKEN_2022_PROGRAM_ID,
+            mint,
+            Some(authority),
+            Some(*mint),
+        )?,
+        ExtensionType::ConfidentialTransferMint => {
+            confidential_transfer::instruction::initialize_mint(
+                &TOKEN_2022_PROGRAM_ID,
+                mint,
+                Some(authority),
+                true,
+                None,
+            )?
+        }
+        ExtensionType::Pausable => {
+            pausable::instruction::initialize(&TOKEN_2022_PROGRAM_ID, mint, &authority)?
+        }
+        _ => return Err(format!("Unsupported mint extension: {:?}", extension).into()),
+    };
+    Ok(instruction)
+}
+
+/// Creates a Token-2022 mint with the given extensions and the signer as mint authority.
+///
+/// The extensions are initialized before the mint, so any combination that Token-2022 accepts can be created.
+/// Mints with the default account state extension get the signer as freeze authority.
+///
+/// # Arguments
+///
+/// * `ctx` - The test environment.
+/// * `extensions` - The mint extensions to initialize.
+/// * `config` - The settings of the extensions. Defaults to `MintExtensionsConfig::default()` if not provided.
+///
+/// # Errors
+///
+/// This function will return an error if an extension is not supported or the transaction fails.
+pub async fn setup_mint_with_extensions(
+    ctx: &TestContext,
+    extensions: &[ExtensionType],
+    config: Option<MintExtensionsConfig>,
+) -> Result<Pubkey, Box<dyn Error>> {
+    let config = config.unwrap_or_default();
+    let mint = Keypair::new();
+
+    let space = ExtensionType::try_calculate_account_len::<Mint>(extensions)?;
+    let rent = ctx
+        .rpc
+        .get_minimum_balance_for_rent_exemption(space)
+        .await?;
+
+    let mut instructions = vec![create_account(
+        &ctx.signer.pubkey(),
+        &mint.pubkey(),
+        rent,
+        space as u64,
+        &TOKEN_2022_PROGRAM_ID,
+    )];
+    for extension in extensions {
+        instructions.push(initialize_extension_instruction(
+            ctx,
+            &mint.pubkey(),
+            *extension,
+            &config,
+        )?);
+    }
+
+    let freeze_authority = ctx.signer.pubkey();
+    instructions.push(initialize_mint2(
+        &TOKEN_2022_PROGRAM_ID,
+        &mint.pubkey(),
+        &ctx.signer.pubkey(),
+        extensions
+            .contains(&ExtensionType::DefaultAccountState)
+            .then_some(&freeze_authority),
+        config.decimals,
+    )?);
+
+    ctx.send_transaction_with_signers(instructions, vec![&mint])
+        .await?;
+
+    Ok(mint.pubkey())
+}
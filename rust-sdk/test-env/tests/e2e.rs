@@ -0,0 +1,110 @@
+use std::error::Error;
+
+use orca_whirlpools::{
+    create_concentrated_liquidity_pool_instructions, fetch_positions_for_owner,
+    open_position_instructions, set_whirlpools_config_address, swap_instructions,
+    IncreaseLiquidityParam, PositionOrBundle, SwapQuote, SwapType, WhirlpoolsConfigInput,
+};
+use orca_whirlpools_client::{fetch_all_position_with_filter, PositionFilter};
+use orca_whirlpools_test_env::{setup_ata, setup_config, setup_fee_tier, setup_mint, TestContext};
+use solana_sdk::{program_pack::Pack, pubkey::Pubkey, signer::Signer};
+use spl_token::state::Account;
+
+async fn get_token_balance(ctx: &TestContext, address: Pubkey) -> Result<u64, Box<dyn Error>> {
+    let account_info = ctx.rpc.get_account(&address).await?;
+    Ok(Account::unpack(&account_info.data)?.amount)
+}
+
+#[tokio::test]
+async fn test_open_position_and_swap_with_sdk() -> Result<(), Box<dyn Error>> {
+    let ctx = TestContext::new()?;
+    let config = setup_config(&ctx).await?;
+    setup_fee_tier(&ctx, config, 64, 3000).await?;
+    set_whirlpools_config_address(WhirlpoolsConfigInput::Address(config))?;
+
+    let mint_1 = setup_mint(&ctx, 6).await?;
+    let mint_2 = setup_mint(&ctx, 6).await?;
+    let (mint_a, mint_b) = if mint_1 < mint_2 {
+        (mint_1, mint_2)
+    } else {
+        (mint_2, mint_1)
+    };
+    let ata_a = setup_ata(&ctx, mint_a, None, 1_000_000_000_000).await?;
+    let ata_b = setup_ata(&ctx, mint_b, None, 1_000_000_000_000).await?;
+
+    let pool = create_concentrated_liquidity_pool_instructions(
+        &ctx.rpc,
+        mint_a,
+        mint_b,
+        64,
+        Some(1.0),
+        Some(ctx.signer.pubkey()),
+    )
+    .await?;
+    ctx.send_transaction_with_signers(pool.instructions, pool.additional_signers.iter().collect())
+        .await?;
+
+    let position = open_position_instructions(
+        &ctx.rpc,
+        pool.pool_address,
+        0.5,
+        2.0,
+        IncreaseLiquidityParam::TokenA(1_000_000_000),
+        None,
+        Some(ctx.signer.pubkey()),
+    )
+    .await?;
+    ctx.send_transaction_with_signers(
+        position.instructions,
+        position.additional_signers.iter().collect(),
+    )
+    .await?;
+
+    // getTokenAccountsByOwner
+    let positions = fetch_positions_for_owner(&ctx.rpc, ctx.signer.pubkey()).await?;
+    assert_eq!(positions.len(), 1);
+    let PositionOrBundle::Position(owner_position) = &positions[0] else {
+        return Err("Expected a position".into());
+    };
+    assert_eq!(owner_position.data.position_mint, position.position_mint);
+
+    // getProgramAccounts
+    let pool_positions = fetch_all_position_with_filter(
+        &ctx.rpc,
+        vec![PositionFilter::Whirlpool(pool.pool_address)],
+    )
+    .await?;
+    assert_eq!(pool_positions.len(), 1);
+    assert_eq!(pool_positions[0].address, owner_position.address);
+    assert_eq!(
+        pool_positions[0].data.liquidity,
+        position.quote.liquidity_delta
+    );
+
+    let token_a_before = get_token_balance(&ctx, ata_a).await?;
+    let token_b_before = get_token_balance(&ctx, ata_b).await?;
+
+    let swap = swap_instructions(
+        &ctx.rpc,
+        pool.pool_address,
+        1_000_000,
+        mint_a,
+        SwapType::ExactIn,
+        None,
+        None,
+        Some(ctx.signer.pubkey()),
+    )
+    .await?;
+    ctx.send_transaction_with_signers(swap.instructions, swap.additional_signers.iter().collect())
+        .await?;
+
+    let token_a_after = get_token_balance(&ctx, ata_a).await?;
+    let token_b_after = get_token_balance(&ctx, ata_b).await?;
+    let SwapQuote::ExactIn(quote) = swap.quote else {
+        return Err("Swap quote is not ExactIn".into());
+    };
+    assert_eq!(token_a_before - token_a_after, quote.token_in);
+    assert_eq!(token_b_after - token_b_before, quote.token_est_out);
+
+    Ok(())
+}
@@ -0,0 +1,1213 @@
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use orca_whirlpools_client::{
+    get_bundled_position_address, get_tick_array_address, CloseBundledPosition,
+    CloseBundledPositionInstructionArgs, ClosePosition, ClosePositionWithTokenExtensions,
+    CollectFeesV2, CollectFeesV2InstructionArgs, CollectRewardV2, CollectRewardV2InstructionArgs,
+    DecreaseLiquidityV2, DecreaseLiquidityV2InstructionArgs, Position, UpdateFeesAndRewards,
+    Whirlpool,
+};
+use orca_whirlpools_core::{
+    collect_fees_quote, collect_rewards_quote, decrease_liquidity_quote,
+    get_tick_array_start_tick_index, get_tick_index_in_array, CollectFeesQuote,
+    CollectRewardsQuote, DecreaseLiquidityQuote, TickArrayFacade, TickFacade, TransferFee,
+    BPS_DENOMINATOR, POSITION_BUNDLE_SIZE,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    account::Account, instruction::Instruction, message::Message, packet::PACKET_DATA_SIZE,
+    pubkey::Pubkey, signature::Keypair,
+};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+
+use crate::{
+    tick_array::decode_tick_array,
+    token::{
+        get_current_transfer_fee, prepare_token_accounts_instructions, TokenAccountInstructions,
+        TokenAccountStrategy,
+    },
+    utils::batch_get_multiple_accounts,
+    PositionOrBundle, FUNDER, SLIPPAGE_TOLERANCE_BPS,
+};
+
+// TODO: support transfer hooks
+
+/// The number of bytes of every transaction that are kept free for compute budget instructions.
+const COMPUTE_BUDGET_RESERVED_SIZE: usize = 64;
+
+/// A group of instructions that fits in a single transaction.
+#[derive(Debug)]
+pub struct BatchTransaction {
+    /// A vector of `Instruction` objects to send in this transaction.
+    pub instructions: Vec<Instruction>,
+
+    /// A vector of `Keypair` objects representing additional signers required for this transaction.
+    pub additional_signers: Vec<Keypair>,
+
+    /// The addresses of the positions that this transaction acts on.
+    pub positions: Vec<Pubkey>,
+}
+
+/// The amounts a batch operation moves for a single position.
+#[derive(Debug, Clone, Copy)]
+pub struct BatchPositionQuote {
+    /// The address of the position.
+    pub position: Pubkey,
+
+    /// The quote for the liquidity removed from the position, if any is removed.
+    pub decrease_quote: Option<DecreaseLiquidityQuote>,
+
+    /// The fees collected from the position.
+    pub fees_quote: CollectFeesQuote,
+
+    /// The rewards collected from the position.
+    pub rewards_quote: CollectRewardsQuote,
+}
+
+/// Represents the transactions and quotes of an operation on many positions.
+///
+/// The transactions must be sent in order. The first transaction creates the token accounts
+/// shared by all positions (including a temporary wSOL account, if needed), and the last
+/// transaction closes the temporary accounts again.
+#[derive(Debug)]
+pub struct BatchPositionsInstruction {
+    /// The transactions of the batch, each sized to fit in a single legacy transaction.
+    pub transactions: Vec<BatchTransaction>,
+
+    /// The quotes of the positions included in the batch. Positions without anything to do are
+    /// left out.
+    pub quotes: Vec<BatchPositionQuote>,
+}
+
+/// Generates instructions to harvest the fees and rewards of many positions at once.
+///
+/// Unlike calling `harvest_position_instructions` for every position, the pools, mints and tick
+/// arrays of all positions are fetched once, token accounts are created once, and the
+/// instructions are packed into as few transactions as possible.
+///
+/// # Arguments
+///
+/// * `rpc` - A reference to a Solana RPC client for fetching accounts and pool data.
+/// * `positions` - The positions to harvest, as returned by `fetch_positions_for_owner`. Positions in bundles are included.
+/// * `authority` - An optional public key of the owner of the positions. Defaults to the global funder if not provided.
+///
+/// # Returns
+///
+/// A `Result` containing `BatchPositionsInstruction` on success:
+///
+/// * `transactions` - The transactions to send, in order.
+/// * `quotes` - The fees and rewards collected from every position that has anything to harvest.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The `authority` account is invalid or missing.
+/// - A pool, mint or tick array of a position is not found or has invalid data.
+/// - The instructions of a single position do not fit in a transaction.
+/// - Any RPC request to the blockchain fails.
+///
+/// # Example
+///
+/// ```rust
+/// use orca_whirlpools::{
+///     fetch_positions_for_owner, harvest_positions_instructions, set_whirlpools_config_address,
+///     WhirlpoolsConfigInput,
+/// };
+/// use solana_client::nonblocking::rpc_client::RpcClient;
+/// use crate::utils::load_wallet;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     set_whirlpools_config_address(WhirlpoolsConfigInput::SolanaDevnet).unwrap();
+///     let rpc = RpcClient::new("https://api.devnet.solana.com".to_string());
+///     let wallet = load_wallet();
+///
+///     let positions = fetch_positions_for_owner(&rpc, wallet.pubkey()).await.unwrap();
+///     let result = harvest_positions_instructions(&rpc, &positions, Some(wallet.pubkey()))
+///         .await
+///         .unwrap();
+///
+///     println!("Positions to harvest: {}", result.quotes.len());
+///     println!("Number of Transactions: {}", result.transactions.len());
+/// }
+/// ```
+pub async fn harvest_positions_instructions(
+    rpc: &RpcClient,
+    positions: &[PositionOrBundle],
+    authority: Option<Pubkey>,
+) -> Result<BatchPositionsInstruction, Box<dyn Error>> {
+    let (authority, positions, context) =
+        prepare_batch(rpc, positions, authority, |_| true).await?;
+
+    let mut plans: Vec<PositionPlan> = Vec::new();
+    for position in &positions {
+        let (fees_quote, rewards_quote) = context.collect_quotes(position)?;
+        let plan = PositionPlan {
+            position,
+            update_fees_and_rewards: position.data.liquidity > 0,
+            decrease_quote: None,
+            fees_quote,
+            rewards_quote,
+            close: false,
+        };
+        if plan.collects_anything() {
+            plans.push(plan);
+        }
+    }
+
+    build_batch(rpc, authority, &context, plans).await
+}
+
+/// Generates instructions to close every position without liquidity.
+///
+/// Fees and rewards that are still owed to an empty position are collected before it is
+/// closed. Positions in bundles are closed as well, the bundles themselves are kept.
+///
+/// # Arguments
+///
+/// * `rpc` - A reference to a Solana RPC client for fetching accounts and pool data.
+/// * `positions` - The positions to consider, as returned by `fetch_positions_for_owner`. Positions with liquidity are skipped.
+/// * `authority` - An optional public key of the owner of the positions. Defaults to the global funder if not provided.
+///
+/// # Returns
+///
+/// A `Result` containing `BatchPositionsInstruction` on success:
+///
+/// * `transactions` - The transactions to send, in order.
+/// * `quotes` - The fees and rewards collected from every position that is closed.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The `authority` account is invalid or missing.
+/// - A pool, mint or tick array of a position is not found or has invalid data.
+/// - A position NFT is owned by an unsupported token program.
+/// - Any RPC request to the blockchain fails.
+///
+/// # Example
+///
+/// ```rust
+/// use orca_whirlpools::{
+///     close_empty_positions_instructions, fetch_positions_for_owner,
+///     set_whirlpools_config_address, WhirlpoolsConfigInput,
+/// };
+/// use solana_client::nonblocking::rpc_client::RpcClient;
+/// use crate::utils::load_wallet;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     set_whirlpools_config_address(WhirlpoolsConfigInput::SolanaDevnet).unwrap();
+///     let rpc = RpcClient::new("https://api.devnet.solana.com".to_string());
+///     let wallet = load_wallet();
+///
+///     let positions = fetch_positions_for_owner(&rpc, wallet.pubkey()).await.unwrap();
+///     let result = close_empty_positions_instructions(&rpc, &positions, Some(wallet.pubkey()))
+///         .await
+///         .unwrap();
+///
+///     println!("Positions to close: {}", result.quotes.len());
+/// }
+/// ```
+pub async fn close_empty_positions_instructions(
+    rpc: &RpcClient,
+    positions: &[PositionOrBundle],
+    authority: Option<Pubkey>,
+) -> Result<BatchPositionsInstruction, Box<dyn Error>> {
+    let (authority, positions, context) =
+        prepare_batch(rpc, positions, authority, |x| x.liquidity == 0).await?;
+
+    let mut plans: Vec<PositionPlan> = Vec::new();
+    for position in &positions {
+        let (fees_quote, rewards_quote) = context.collect_quotes(position)?;
+        plans.push(PositionPlan {
+            position,
+            update_fees_and_rewards: false,
+            decrease_quote: None,
+            fees_quote,
+            rewards_quote,
+            close: true,
+        });
+    }
+
+    build_batch(rpc, authority, &context, plans).await
+}
+
+/// Generates instructions to collect a single reward token from many positions.
+///
+/// Only the reward with the given mint is collected. Positions in pools that do not emit the
+/// reward, or that have nothing owed, are skipped.
+///
+/// # Arguments
+///
+/// * `rpc` - A reference to a Solana RPC client for fetching accounts and pool data.
+/// * `positions` - The positions to consider, as returned by `fetch_positions_for_owner`.
+/// * `reward_mint` - The mint of the reward token to collect.
+/// * `authority` - An optional public key of the owner of the positions. Defaults to the global funder if not provided.
+///
+/// # Returns
+///
+/// A `Result` containing `BatchPositionsInstruction` on success:
+///
+/// * `transactions` - The transactions to send, in order.
+/// * `quotes` - The rewards collected from every position. Only the entry of the collected reward is non-zero.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The `authority` account is invalid or missing.
+/// - A pool, mint or tick array of a position is not found or has invalid data.
+/// - Any RPC request to the blockchain fails.
+///
+/// # Example
+///
+/// ```rust
+/// use orca_whirlpools::{
+///     collect_reward_instructions, fetch_positions_for_owner, set_whirlpools_config_address,
+///     WhirlpoolsConfigInput,
+/// };
+/// use solana_client::nonblocking::rpc_client::RpcClient;
+/// use solana_sdk::pubkey::Pubkey;
+/// use std::str::FromStr;
+/// use crate::utils::load_wallet;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     set_whirlpools_config_address(WhirlpoolsConfigInput::SolanaDevnet).unwrap();
+///     let rpc = RpcClient::new("https://api.devnet.solana.com".to_string());
+///     let wallet = load_wallet();
+///     let reward_mint = Pubkey::from_str("orcaEKTdK7LKz57vaAYr9QeNsVEPfiu6QeMU1kektZE").unwrap();
+///
+///     let positions = fetch_positions_for_owner(&rpc, wallet.pubkey()).await.unwrap();
+///     let result =
+///         collect_reward_instructions(&rpc, &positions, reward_mint, Some(wallet.pubkey()))
+///             .await
+///             .unwrap();
+///
+///     println!("Number of Transactions: {}", result.transactions.len());
+/// }
+/// ```
+pub async fn collect_reward_instructions(
+    rpc: &RpcClient,
+    positions: &[PositionOrBundle],
+    reward_mint: Pubkey,
+    authority: Option<Pubkey>,
+) -> Result<BatchPositionsInstruction, Box<dyn Error>> {
+    if reward_mint == Pubkey::default() {
+        return Err("Reward mint must be provided".into());
+    }
+    let (authority, positions, context) =
+        prepare_batch(rpc, positions, authority, |_| true).await?;
+
+    let mut plans: Vec<PositionPlan> = Vec::new();
+    for position in &positions {
+        let pool = context.pool(&position.data.whirlpool)?;
+        let Some(reward_index) = pool.reward_infos.iter().position(|x| x.mint == reward_mint)
+        else {
+            continue;
+        };
+        let (_, all_rewards_quote) = context.collect_quotes(position)?;
+        let mut rewards_quote = CollectRewardsQuote::default();
+        rewards_quote.rewards[reward_index] = all_rewards_quote.rewards[reward_index];
+        let plan = PositionPlan {
+            position,
+            update_fees_and_rewards: position.data.liquidity > 0,
+            decrease_quote: None,
+            fees_quote: CollectFeesQuote::default(),
+            rewards_quote,
+            close: false,
+        };
+        if plan.collects_anything() {
+            plans.push(plan);
+        }
+    }
+
+    build_batch(rpc, authority, &context, plans).await
+}
+
+/// Generates instructions to remove a share of the liquidity of every position in a pool.
+///
+/// Fees and rewards are not collected; use `harvest_positions_instructions` for that.
+///
+/// # Arguments
+///
+/// * `rpc` - A reference to a Solana RPC client for fetching accounts and pool data.
+/// * `whirlpool` - The address of the pool. Positions in other pools are skipped.
+/// * `positions` - The positions to consider, as returned by `fetch_positions_for_owner`.
+/// * `percentage_bps` - The share of the liquidity of every position to remove, in basis points. `10_000` removes all liquidity.
+/// * `slippage_tolerance_bps` - An optional slippage tolerance in basis points. Defaults to the global slippage tolerance if not provided.
+/// * `authority` - An optional public key of the owner of the positions. Defaults to the global funder if not provided.
+///
+/// # Returns
+///
+/// A `Result` containing `BatchPositionsInstruction` on success:
+///
+/// * `transactions` - The transactions to send, in order.
+/// * `quotes` - The liquidity removed from every position, in `decrease_quote`.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The `authority` account is invalid or missing.
+/// - `percentage_bps` is zero or more than `10_000`.
+/// - The pool, its mints or a tick array of a position are not found or have invalid data.
+/// - Any RPC request to the blockchain fails.
+///
+/// # Example
+///
+/// ```rust
+/// use orca_whirlpools::{
+///     decrease_liquidity_in_whirlpool_instructions, fetch_positions_for_owner,
+///     set_whirlpools_config_address, WhirlpoolsConfigInput,
+/// };
+/// use solana_client::nonblocking::rpc_client::RpcClient;
+/// use solana_sdk::pubkey::Pubkey;
+/// use std::str::FromStr;
+/// use crate::utils::load_wallet;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     set_whirlpools_config_address(WhirlpoolsConfigInput::SolanaDevnet).unwrap();
+///     let rpc = RpcClient::new("https://api.devnet.solana.com".to_string());
+///     let wallet = load_wallet();
+///     let whirlpool = Pubkey::from_str("3KBZiL2g8C7tiJ32hTv5v3KM7aK9htpqTw4cTXz1HvPt").unwrap();
+///
+///     let positions = fetch_positions_for_owner(&rpc, wallet.pubkey()).await.unwrap();
+///     let result = decrease_liquidity_in_whirlpool_instructions(
+///         &rpc,
+///         whirlpool,
+///         &positions,
+///         2_500,
+///         Some(100),
+///         Some(wallet.pubkey()),
+///     )
+///     .await
+///     .unwrap();
+///
+///     println!("Number of Transactions: {}", result.transactions.len());
+/// }
+/// ```
+pub async fn decrease_liquidity_in_whirlpool_instructions(
+    rpc: &RpcClient,
+    whirlpool: Pubkey,
+    positions: &[PositionOrBundle],
+    percentage_bps: u16,
+    slippage_tolerance_bps: Option<u16>,
+    authority: Option<Pubkey>,
+) -> Result<BatchPositionsInstruction, Box<dyn Error>> {
+    if percentage_bps == 0 || percentage_bps > BPS_DENOMINATOR {
+        return Err("Percentage must be between 1 and 10000 basis points".into());
+    }
+    let slippage_tolerance_bps =
+        slippage_tolerance_bps.unwrap_or(*SLIPPAGE_TOLERANCE_BPS.try_lock()?);
+    let (authority, positions, context) = prepare_batch(rpc, positions, authority, |x| {
+        x.whirlpool == whirlpool && x.liquidity > 0
+    })
+    .await?;
+
+    let mut plans: Vec<PositionPlan> = Vec::new();
+    for position in &positions {
+        let pool = context.pool(&position.data.whirlpool)?;
+        let liquidity_delta = share_of_liquidity(position.data.liquidity, percentage_bps);
+        if liquidity_delta == 0 {
+            continue;
+        }
+        let decrease_quote = decrease_liquidity_quote(
+            liquidity_delta.into(),
+            slippage_tolerance_bps,
+            pool.sqrt_price.into(),
+            position.data.tick_lower_index,
+            position.data.tick_upper_index,
+            context.transfer_fee(&pool.token_mint_a),
+            context.transfer_fee(&pool.token_mint_b),
+        )?;
+        plans.push(PositionPlan {
+            position,
+            update_fees_and_rewards: false,
+            decrease_quote: Some(decrease_quote),
+            fees_quote: CollectFeesQuote::default(),
+            rewards_quote: CollectRewardsQuote::default(),
+            close: false,
+        });
+    }
+
+    build_batch(rpc, authority, &context, plans).await
+}
+
+/// How the position NFT of a position is held, which decides how the position is closed.
+#[derive(Debug, Clone, Copy)]
+enum PositionHolding {
+    Nft {
+        position_mint: Pubkey,
+        token_program: Pubkey,
+    },
+    Bundle {
+        position_bundle: Pubkey,
+        bundle_index: u16,
+    },
+}
+
+#[derive(Debug)]
+struct BatchPosition {
+    address: Pubkey,
+    data: Position,
+    position_token_account: Pubkey,
+    holding: PositionHolding,
+}
+
+/// The accounts shared by the positions of a batch, fetched once for all of them.
+struct BatchContext {
+    pools: HashMap<Pubkey, Whirlpool>,
+    mints: HashMap<Pubkey, Account>,
+    tick_arrays: HashMap<Pubkey, TickArrayFacade>,
+    current_epoch: u64,
+    unix_timestamp: u64,
+}
+
+/// What a batch does with a single position.
+struct PositionPlan<'a> {
+    position: &'a BatchPosition,
+    update_fees_and_rewards: bool,
+    decrease_quote: Option<DecreaseLiquidityQuote>,
+    fees_quote: CollectFeesQuote,
+    rewards_quote: CollectRewardsQuote,
+    close: bool,
+}
+
+impl PositionPlan<'_> {
+    fn collects_fees(&self) -> bool {
+        self.fees_quote.fee_owed_a > 0 || self.fees_quote.fee_owed_b > 0
+    }
+
+    fn collects_anything(&self) -> bool {
+        self.collects_fees()
+            || self
+                .rewards_quote
+                .rewards
+                .iter()
+                .any(|x| x.rewards_owed > 0)
+    }
+}
+
+async fn prepare_batch(
+    rpc: &RpcClient,
+    positions: &[PositionOrBundle],
+    authority: Option<Pubkey>,
+    filter: impl Fn(&Position) -> bool,
+) -> Result<(Pubkey, Vec<BatchPosition>, BatchContext), Box<dyn Error>> {
+    let authority = authority.unwrap_or(*FUNDER.try_lock()?);
+    if authority == Pubkey::default() {
+        return Err("Authority must be provided".into());
+    }
+
+    let positions: Vec<BatchPosition> = flatten_positions(positions, authority)?
+        .into_iter()
+        .filter(|x| filter(&x.data))
+        .collect();
+    let context = BatchContext::fetch(rpc, &positions).await?;
+    Ok((authority, positions, context))
+}
+
+fn flatten_positions(
+    positions: &[PositionOrBundle],
+    authority: Pubkey,
+) -> Result<Vec<BatchPosition>, Box<dyn Error>> {
+    let mut result: Vec<BatchPosition> = Vec::new();
+    for position_or_bundle in positions {
+        match position_or_bundle {
+            PositionOrBundle::Position(position) => {
+                result.push(BatchPosition {
+                    address: position.address,
+                    data: position.data.clone(),
+                    position_token_account: get_associated_token_address_with_program_id(
+                        &authority,
+                        &position.data.position_mint,
+                        &position.token_program,
+                    ),
+                    holding: PositionHolding::Nft {
+                        position_mint: position.data.position_mint,
+                        token_program: position.token_program,
+                    },
+                });
+            }
+            PositionOrBundle::PositionBundle(bundle) => {
+                let bundle_mint = bundle.data.position_bundle_mint;
+                let mut bundle_indexes: HashMap<Pubkey, u16> = HashMap::new();
+                for i in 0..POSITION_BUNDLE_SIZE {
+                    if bundle.data.position_bitmap[i / 8] & (1 << (i % 8)) != 0 {
+                        let address = get_bundled_position_address(&bundle_mint, i as u8)?.0;
+                        bundle_indexes.insert(address, i as u16);
+                    }
+                }
+                let position_token_account = get_associated_token_address_with_program_id(
+                    &authority,
+                    &bundle_mint,
+                    &bundle.token_program,
+                );
+                for position in &bundle.positions {
+                    let bundle_index = *bundle_indexes
+                        .get(&position.address)
+                        .ok_or("Bundled position not found in position bundle")?;
+                    result.push(BatchPosition {
+                        address: position.address,
+                        data: position.data.clone(),
+                        position_token_account,
+                        holding: PositionHolding::Bundle {
+                            position_bundle: bundle.address,
+                            bundle_index,
+                        },
+                    });
+                }
+            }
+        }
+    }
+    Ok(result)
+}
+
+fn dedupe(addresses: impl IntoIterator<Item = Pubkey>) -> Vec<Pubkey> {
+    let mut seen: HashSet<Pubkey> = HashSet::new();
+    addresses.into_iter().filter(|x| seen.insert(*x)).collect()
+}
+
+fn tick_array_addresses(
+    position: &Position,
+    pool: &Whirlpool,
+) -> Result<[(Pubkey, i32); 2], Box<dyn Error>> {
+    let lower_start_index =
+        get_tick_array_start_tick_index(position.tick_lower_index, pool.tick_spacing);
+    let upper_start_index =
+        get_tick_array_start_tick_index(position.tick_upper_index, pool.tick_spacing);
+    Ok([
+        (
+            get_tick_array_address(&position.whirlpool, lower_start_index)?.0,
+            lower_start_index,
+        ),
+        (
+            get_tick_array_address(&position.whirlpool, upper_start_index)?.0,
+            upper_start_index,
+        ),
+    ])
+}
+
+impl BatchContext {
+    async fn fetch(rpc: &RpcClient, positions: &[BatchPosition]) -> Result<Self, Box<dyn Error>> {
+        let pool_addresses = dedupe(positions.iter().map(|x| x.data.whirlpool));
+        let pool_infos = batch_get_multiple_accounts(rpc, &pool_addresses, None).await?;
+        let mut pools: HashMap<Pubkey, Whirlpool> = HashMap::new();
+        for (address, info) in pool_addresses.iter().zip(pool_infos) {
+            let info = info.ok_or(format!("Whirlpool {} not found", address))?;
+            pools.insert(*address, Whirlpool::from_bytes(&info.data)?);
+        }
+
+        let mint_addresses = dedupe(pools.values().flat_map(|pool| {
+            [pool.token_mint_a, pool.token_mint_b]
+                .into_iter()
+                .chain(pool.reward_infos.iter().map(|x| x.mint))
+                .filter(|x| *x != Pubkey::default())
+        }));
+        let mint_infos = batch_get_multiple_accounts(rpc, &mint_addresses, None).await?;
+        let mut mints: HashMap<Pubkey, Account> = HashMap::new();
+        for (address, info) in mint_addresses.iter().zip(mint_infos) {
+            let info = info.ok_or(format!("Mint {} not found", address))?;
+            mints.insert(*address, info);
+        }
+
+        let mut tick_array_keys: Vec<Pubkey> = Vec::new();
+        for position in positions {
+            let pool = &pools[&position.data.whirlpool];
+            for (address, _) in tick_array_addresses(&position.data, pool)? {
+                tick_array_keys.push(address);
+            }
+        }
+        let tick_array_keys = dedupe(tick_array_keys);
+        let tick_array_infos = batch_get_multiple_accounts(rpc, &tick_array_keys, None).await?;
+        let mut tick_arrays: HashMap<Pubkey, TickArrayFacade> = HashMap::new();
+        for (address, info) in tick_array_keys.iter().zip(tick_array_infos) {
+            let info = info.ok_or(format!("Tick array {} not found", address))?;
+            tick_arrays.insert(*address, decode_tick_array(&info.data)?);
+        }
+
+        let current_epoch = if positions.is_empty() {
+            0
+        } else {
+            rpc.get_epoch_info().await?.epoch
+        };
+        let unix_timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+        Ok(Self {
+            pools,
+            mints,
+            tick_arrays,
+            current_epoch,
+            unix_timestamp,
+        })
+    }
+
+    fn pool(&self, address: &Pubkey) -> Result<&Whirlpool, Box<dyn Error>> {
+        self.pools
+            .get(address)
+            .ok_or_else(|| format!("Whirlpool {} not found", address).into())
+    }
+
+    fn mint(&self, address: &Pubkey) -> Result<&Account, Box<dyn Error>> {
+        self.mints
+            .get(address)
+            .ok_or_else(|| format!("Mint {} not found", address).into())
+    }
+
+    fn transfer_fee(&self, mint: &Pubkey) -> Option<TransferFee> {
+        get_current_transfer_fee(self.mints.get(mint), self.current_epoch)
+    }
+
+    fn ticks(&self, position: &BatchPosition) -> Result<(TickFacade, TickFacade), Box<dyn Error>> {
+        let pool = self.pool(&position.data.whirlpool)?;
+        let [lower, upper] = tick_array_addresses(&position.data, pool)?;
+        let tick = |(address, start_index): (Pubkey, i32), tick_index: i32| {
+            let tick_array = self
+                .tick_arrays
+                .get(&address)
+                .ok_or(format!("Tick array {} not found", address))?;
+            let index = get_tick_index_in_array(tick_index, start_index, pool.tick_spacing)?;
+            Ok::<TickFacade, Box<dyn Error>>(tick_array.ticks[index as usize])
+        };
+        Ok((
+            tick(lower, position.data.tick_lower_index)?,
+            tick(upper, position.data.tick_upper_index)?,
+        ))
+    }
+
+    fn collect_quotes(
+        &self,
+        position: &BatchPosition,
+    ) -> Result<(CollectFeesQuote, CollectRewardsQuote), Box<dyn Error>> {
+        let pool = self.pool(&position.data.whirlpool)?;
+        let (lower_tick, upper_tick) = self.ticks(position)?;
+
+        let fees_quote = collect_fees_quote(
+            pool.clone().into(),
+            position.data.clone().into(),
+            lower_tick,
+            upper_tick,
+            self.transfer_fee(&pool.token_mint_a),
+            self.transfer_fee(&pool.token_mint_b),
+        )?;
+
+        let rewards_quote = collect_rewards_quote(
+            pool.clone().into(),
+            position.data.clone().into(),
+            lower_tick,
+            upper_tick,
+            self.unix_timestamp,
+            self.transfer_fee(&pool.reward_infos[0].mint),
+            self.transfer_fee(&pool.reward_infos[1].mint),
+            self.transfer_fee(&pool.reward_infos[2].mint),
+        )?;
+
+        Ok((fees_quote, rewards_quote))
+    }
+}
+
+/// Floors `liquidity * percentage_bps / 10_000` without overflowing.
+fn share_of_liquidity(liquidity: u128, percentage_bps: u16) -> u128 {
+    let denominator = BPS_DENOMINATOR as u128;
+    let percentage_bps = percentage_bps as u128;
+    liquidity / denominator * percentage_bps
+        + liquidity % denominator * percentage_bps / denominator
+}
+
+async fn build_batch(
+    rpc: &RpcClient,
+    authority: Pubkey,
+    context: &BatchContext,
+    plans: Vec<PositionPlan<'_>>,
+) -> Result<BatchPositionsInstruction, Box<dyn Error>> {
+    if plans.is_empty() {
+        return Ok(BatchPositionsInstruction {
+            transactions: Vec::new(),
+            quotes: Vec::new(),
+        });
+    }
+
+    let mut required_mints: HashSet<TokenAccountStrategy> = HashSet::new();
+    for plan in &plans {
+        let pool = context.pool(&plan.position.data.whirlpool)?;
+        if plan.decrease_quote.is_some() || plan.collects_fees() {
+            required_mints.insert(TokenAccountStrategy::WithoutBalance(pool.token_mint_a));
+            required_mints.insert(TokenAccountStrategy::WithoutBalance(pool.token_mint_b));
+        }
+        for i in 0..3 {
+            if plan.rewards_quote.rewards[i].rewards_owed > 0 {
+                required_mints.insert(TokenAccountStrategy::WithoutBalance(
+                    pool.reward_infos[i].mint,
+                ));
+            }
+        }
+    }
+
+    let token_accounts =
+        prepare_token_accounts_instructions(rpc, authority, required_mints.into_iter().collect())
+            .await?;
+
+    let mut units: Vec<(Pubkey, Vec<Instruction>)> = Vec::new();
+    for plan in &plans {
+        let instructions = position_instructions(authority, context, &token_accounts, plan)?;
+        units.push((plan.position.address, instructions));
+    }
+
+    let quotes = plans
+        .iter()
+        .map(|plan| BatchPositionQuote {
+            position: plan.position.address,
+            decrease_quote: plan.decrease_quote,
+            fees_quote: plan.fees_quote,
+            rewards_quote: plan.rewards_quote,
+        })
+        .collect();
+
+    Ok(BatchPositionsInstruction {
+        transactions: pack_transactions(authority, token_accounts, units)?,
+        quotes,
+    })
+}
+
+fn position_instructions(
+    authority: Pubkey,
+    context: &BatchContext,
+    token_accounts: &TokenAccountInstructions,
+    plan: &PositionPlan,
+) -> Result<Vec<Instruction>, Box<dyn Error>> {
+    let position = plan.position;
+    let pool = context.pool(&position.data.whirlpool)?;
+    let [(tick_array_lower, _), (tick_array_upper, _)] =
+        tick_array_addresses(&position.data, pool)?;
+    let token_account = |mint: &Pubkey| {
+        token_accounts
+            .token_account_addresses
+            .get(mint)
+            .copied()
+            .ok_or_else(|| format!("Token account for mint {} not found", mint))
+    };
+
+    let mut instructions: Vec<Instruction> = Vec::new();
+
+    if plan.update_fees_and_rewards {
+        instructions.push(
+            UpdateFeesAndRewards {
+                whirlpool: position.data.whirlpool,
+                position: position.address,
+                tick_array_lower,
+                tick_array_upper,
+            }
+            .instruction(),
+        );
+    }
+
+    if let Some(quote) = plan.decrease_quote {
+        instructions.push(
+            DecreaseLiquidityV2 {
+                whirlpool: position.data.whirlpool,
+                token_program_a: context.mint(&pool.token_mint_a)?.owner,
+                token_program_b: context.mint(&pool.token_mint_b)?.owner,
+                memo_program: spl_memo::ID,
+                position_authority: authority,
+                position: position.address,
+                position_token_account: position.position_token_account,
+                token_mint_a: pool.token_mint_a,
+                token_mint_b: pool.token_mint_b,
+                token_owner_account_a: token_account(&pool.token_mint_a)?,
+                token_owner_account_b: token_account(&pool.token_mint_b)?,
+                token_vault_a: pool.token_vault_a,
+                token_vault_b: pool.token_vault_b,
+                tick_array_lower,
+                tick_array_upper,
+            }
+            .instruction(DecreaseLiquidityV2InstructionArgs {
+                liquidity_amount: quote.liquidity_delta,
+                token_min_a: quote.token_min_a,
+                token_min_b: quote.token_min_b,
+                remaining_accounts_info: None,
+            }),
+        );
+    }
+
+    if plan.collects_fees() {
+        instructions.push(
+            CollectFeesV2 {
+                whirlpool: position.data.whirlpool,
+                position_authority: authority,
+                position: position.address,
+                position_token_account: position.position_token_account,
+                token_owner_account_a: token_account(&pool.token_mint_a)?,
+                token_owner_account_b: token_account(&pool.token_mint_b)?,
+                token_vault_a: pool.token_vault_a,
+                token_vault_b: pool.token_vault_b,
+                token_mint_a: pool.token_mint_a,
+                token_mint_b: pool.token_mint_b,
+                token_program_a: context.mint(&pool.token_mint_a)?.owner,
+                token_program_b: context.mint(&pool.token_mint_b)?.owner,
+                memo_program: spl_memo::ID,
+            }
+            .instruction(CollectFeesV2InstructionArgs {
+                remaining_accounts_info: None,
+            }),
+        );
+    }
+
+    for i in 0..3 {
+        if plan.rewards_quote.rewards[i].rewards_owed == 0 {
+            continue;
+        }
+        let reward_mint = pool.reward_infos[i].mint;
+        instructions.push(
+            CollectRewardV2 {
+                whirlpool: position.data.whirlpool,
+                position_authority: authority,
+                position: position.address,
+                position_token_account: position.position_token_account,
+                reward_owner_account: token_account(&reward_mint)?,
+                reward_vault: pool.reward_infos[i].vault,
+                reward_mint,
+                reward_token_program: context.mint(&reward_mint)?.owner,
+                memo_program: spl_memo::ID,
+            }
+            .instruction(CollectRewardV2InstructionArgs {
+                reward_index: i as u8,
+                remaining_accounts_info: None,
+            }),
+        );
+    }
+
+    if plan.close {
+        instructions.push(close_position_instruction(authority, position)?);
+    }
+
+    Ok(instructions)
+}
+
+fn close_position_instruction(
+    authority: Pubkey,
+    position: &BatchPosition,
+) -> Result<Instruction, Box<dyn Error>> {
+    match position.holding {
+        PositionHolding::Nft {
+            position_mint,
+            token_program: spl_token::ID,
+        } => Ok(ClosePosition {
+            position_authority: authority,
+            position: position.address,
+            position_token_account: position.position_token_account,
+            position_mint,
+            receiver: authority,
+            token_program: spl_token::ID,
+        }
+        .instruction()),
+        PositionHolding::Nft {
+            position_mint,
+            token_program: spl_token_2022::ID,
+        } => Ok(ClosePositionWithTokenExtensions {
+            position_authority: authority,
+            position: position.address,
+            position_token_account: position.position_token_account,
+            position_mint,
+            receiver: authority,
+            token2022_program: spl_token_2022::ID,
+        }
+        .instruction()),
+        PositionHolding::Nft { .. } => Err("Unsupported token program".into()),
+        PositionHolding::Bundle {
+            position_bundle,
+            bundle_index,
+        } => Ok(CloseBundledPosition {
+            bundled_position: position.address,
+            position_bundle,
+            position_bundle_token_account: position.position_token_account,
+            position_bundle_authority: authority,
+            receiver: authority,
+        }
+        .instruction(CloseBundledPositionInstructionArgs { bundle_index })),
+    }
+}
+
+/// Whether the instructions fit in a legacy transaction paid by `payer`, leaving room for
+/// compute budget instructions.
+fn fits_in_transaction(instructions: &[Instruction], payer: &Pubkey) -> bool {
+    let message = Message::new(instructions, Some(payer));
+    let signatures = message.header.num_required_signatures as usize;
+    let size = 1 + signatures * 64 + message.serialize().len();
+    size + COMPUTE_BUDGET_RESERVED_SIZE <= PACKET_DATA_SIZE
+}
+
+/// Packs the instructions of the positions into transactions. The instructions of a position
+/// are never split. Token account creation goes into the first transaction and cleanup into the
+/// last one, so that the token accounts are shared by all transactions.
+fn pack_transactions(
+    authority: Pubkey,
+    token_accounts: TokenAccountInstructions,
+    units: Vec<(Pubkey, Vec<Instruction>)>,
+) -> Result<Vec<BatchTransaction>, Box<dyn Error>> {
+    let mut transactions: Vec<BatchTransaction> = Vec::new();
+    let mut current = BatchTransaction {
+        instructions: token_accounts.create_instructions,
+        additional_signers: token_accounts.additional_signers,
+        positions: Vec::new(),
+    };
+
+    for (position, instructions) in units {
+        let mut candidate = current.instructions.clone();
+        candidate.extend(instructions.iter().cloned());
+        if fits_in_transaction(&candidate, &authority) {
+            current.instructions = candidate;
+            current.positions.push(position);
+            continue;
+        }
+        if !fits_in_transaction(&instructions, &authority) {
+            return Err(format!(
+                "Instructions for position {} do not fit in a single transaction",
+                position
+            )
+            .into());
+        }
+        let next = BatchTransaction {
+            instructions,
+            additional_signers: Vec::new(),
+            positions: vec![position],
+        };
+        transactions.push(std::mem::replace(&mut current, next));
+    }
+
+    let cleanup_instructions = token_accounts.cleanup_instructions;
+    if !cleanup_instructions.is_empty() {
+        let mut candidate = current.instructions.clone();
+        candidate.extend(cleanup_instructions.iter().cloned());
+        if fits_in_transaction(&candidate, &authority) {
+            current.instructions = candidate;
+        } else {
+            let next = BatchTransaction {
+                instructions: cleanup_instructions,
+                additional_signers: Vec::new(),
+                positions: Vec::new(),
+            };
+            transactions.push(std::mem::replace(&mut current, next));
+        }
+    }
+
+    transactions.push(current);
+    Ok(transactions)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use orca_whirlpools_client::{get_position_address, Position};
+    use serial_test::serial;
+    use solana_program_test::tokio;
+    use solana_sdk::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        signer::{keypair::Keypair, Signer},
+    };
+
+    use super::{pack_transactions, share_of_liquidity, PACKET_DATA_SIZE};
+    use crate::{
+        close_empty_positions_instructions, decrease_liquidity_in_whirlpool_instructions,
+        fetch_positions_for_owner, harvest_positions_instructions, increase_liquidity_instructions,
+        swap_instructions,
+        tests::{setup_ata_with_amount, setup_mint, setup_position, setup_whirlpool, RpcContext},
+        token::TokenAccountInstructions,
+        BatchPositionsInstruction, IncreaseLiquidityParam, SwapType,
+    };
+
+    fn dummy_instruction(accounts: usize) -> Instruction {
+        Instruction {
+            program_id: Pubkey::new_unique(),
+            accounts: (0..accounts)
+                .map(|_| AccountMeta::new(Pubkey::new_unique(), false))
+                .collect(),
+            data: vec![0; 16],
+        }
+    }
+
+    #[test]
+    fn test_share_of_liquidity() {
+        assert_eq!(share_of_liquidity(1_000_000, 10_000), 1_000_000);
+        assert_eq!(share_of_liquidity(1_000_000, 2_500), 250_000);
+        assert_eq!(share_of_liquidity(9_999, 1), 0);
+        assert_eq!(share_of_liquidity(u128::MAX, 10_000), u128::MAX);
+        assert_eq!(share_of_liquidity(u128::MAX, 5_000), u128::MAX / 2);
+    }
+
+    #[test]
+    fn test_pack_transactions() {
+        let authority = Pubkey::new_unique();
+        let setup = dummy_instruction(2);
+        let cleanup = dummy_instruction(2);
+        let token_accounts = TokenAccountInstructions {
+            create_instructions: vec![setup.clone()],
+            cleanup_instructions: vec![cleanup.clone()],
+            token_account_addresses: HashMap::new(),
+            additional_signers: vec![Keypair::new()],
+        };
+        let units: Vec<(Pubkey, Vec<Instruction>)> = (0..20)
+            .map(|_| (Pubkey::new_unique(), vec![dummy_instruction(4)]))
+            .collect();
+        let positions: Vec<Pubkey> = units.iter().map(|x| x.0).collect();
+
+        let transactions = pack_transactions(authority, token_accounts, units).unwrap();
+
+        assert!(transactions.len() > 1);
+        assert_eq!(transactions[0].instructions[0], setup);
+        assert_eq!(transactions[0].additional_signers.len(), 1);
+        assert!(transactions[1..]
+            .iter()
+            .all(|x| x.additional_signers.is_empty()));
+        assert_eq!(
+            transactions.last().unwrap().instructions.last().unwrap(),
+            &cleanup
+        );
+        let packed: Vec<Pubkey> = transactions
+            .iter()
+            .flat_map(|x| x.positions.clone())
+            .collect();
+        assert_eq!(packed, positions);
+        for transaction in &transactions {
+            let message =
+                solana_sdk::message::Message::new(&transaction.instructions, Some(&authority));
+            assert!(message.serialize().len() < PACKET_DATA_SIZE);
+        }
+    }
+
+    #[test]
+    fn test_pack_transactions_rejects_oversized_position() {
+        let token_accounts = TokenAccountInstructions {
+            create_instructions: Vec::new(),
+            cleanup_instructions: Vec::new(),
+            token_account_addresses: HashMap::new(),
+            additional_signers: Vec::new(),
+        };
+        let units = vec![(Pubkey::new_unique(), vec![dummy_instruction(40)])];
+        assert!(pack_transactions(Pubkey::new_unique(), token_accounts, units).is_err());
+    }
+
+    async fn send_batch(ctx: &RpcContext, batch: BatchPositionsInstruction) {
+        for transaction in batch.transactions {
+            ctx.send_transaction_with_signers(
+                transaction.instructions,
+                transaction.additional_signers.iter().collect(),
+            )
+            .await
+            .unwrap();
+        }
+    }
+
+    async fn fetch_position(ctx: &RpcContext, position_mint: Pubkey) -> Option<Position> {
+        let address = get_position_address(&position_mint).unwrap().0;
+        let account = ctx.rpc.get_account(&address).await.ok()?;
+        Some(Position::from_bytes(&account.data).unwrap())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_batch_position_operations() {
+        let ctx = RpcContext::new().await;
+        let mint_1 = setup_mint(&ctx).await.unwrap();
+        let mint_2 = setup_mint(&ctx).await.unwrap();
+        let (mint_a, mint_b) = if mint_1 < mint_2 {
+            (mint_1, mint_2)
+        } else {
+            (mint_2, mint_1)
+        };
+        setup_ata_with_amount(&ctx, mint_a, 1_000_000_000)
+            .await
+            .unwrap();
+        setup_ata_with_amount(&ctx, mint_b, 1_000_000_000)
+            .await
+            .unwrap();
+        let pool = setup_whirlpool(&ctx, mint_a, mint_b, 64).await.unwrap();
+
+        let mut funded_positions: Vec<Pubkey> = Vec::new();
+        for _ in 0..4 {
+            let position_mint = setup_position(&ctx, pool, Some((-640, 640)), None)
+                .await
+                .unwrap();
+            let increase = increase_liquidity_instructions(
+                &ctx.rpc,
+                position_mint,
+                IncreaseLiquidityParam::Liquidity(1_000_000),
+                Some(100),
+                Some(ctx.signer.pubkey()),
+            )
+            .await
+            .unwrap();
+            ctx.send_transaction(increase.instructions).await.unwrap();
+            funded_positions.push(position_mint);
+        }
+        let empty_position = setup_position(&ctx, pool, None, None).await.unwrap();
+
+        let swap = swap_instructions(
+            &ctx.rpc,
+            pool,
+            10_000,
+            mint_a,
+            SwapType::ExactIn,
+            Some(100),
+            None,
+            Some(ctx.signer.pubkey()),
+        )
+        .await
+        .unwrap();
+        ctx.send_transaction_with_signers(
+            swap.instructions,
+            swap.additional_signers.iter().collect(),
+        )
+        .await
+        .unwrap();
+
+        let positions = fetch_positions_for_owner(&ctx.rpc, ctx.signer.pubkey())
+            .await
+            .unwrap();
+
+        let harvest =
+            harvest_positions_instructions(&ctx.rpc, &positions, Some(ctx.signer.pubkey()))
+                .await
+                .unwrap();
+        assert_eq!(harvest.quotes.len(), funded_positions.len());
+        send_batch(&ctx, harvest).await;
+        for position_mint in &funded_positions {
+            let position = fetch_position(&ctx, *position_mint).await.unwrap();
+            assert_eq!(position.fee_owed_a, 0);
+            assert_eq!(position.fee_owed_b, 0);
+        }
+
+        let decrease = decrease_liquidity_in_whirlpool_instructions(
+            &ctx.rpc,
+            pool,
+            &positions,
+            5_000,
+            Some(100),
+            Some(ctx.signer.pubkey()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(decrease.quotes.len(), funded_positions.len());
+        send_batch(&ctx, decrease).await;
+        for position_mint in &funded_positions {
+            let position = fetch_position(&ctx, *position_mint).await.unwrap();
+            assert_eq!(position.liquidity, 500_000);
+        }
+
+        let close =
+            close_empty_positions_instructions(&ctx.rpc, &positions, Some(ctx.signer.pubkey()))
+                .await
+                .unwrap();
+        assert_eq!(close.quotes.len(), 1);
+        send_batch(&ctx, close).await;
+        assert!(fetch_position(&ctx, empty_position).await.is_none());
+        for position_mint in &funded_positions {
+            assert!(fetch_position(&ctx, *position_mint).await.is_some());
+        }
+    }
+}
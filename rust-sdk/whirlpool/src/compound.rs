@@ -0,0 +1,271 @@
+use std::{
+    error::Error,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use orca_whirlpools_client::{
+    get_oracle_address, get_position_address, get_tick_array_address, AccountsType,
+    CompoundFeesAndRewards, CompoundFeesAndRewardsInstructionArgs, Position, RemainingAccountsInfo,
+    RemainingAccountsSlice, Whirlpool,
+};
+use orca_whirlpools_core::{
+    collect_fees_quote, collect_rewards_quote, compound_fees_quote,
+    get_tick_array_start_tick_index, get_tick_index_in_array, CompoundFeesQuote,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{instruction::AccountMeta, instruction::Instruction, pubkey::Pubkey};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+
+use crate::{
+    pool::fetch_pool_paused, tick_array::decode_tick_array, token::get_current_transfer_fee, FUNDER,
+};
+
+// TODO: support transfer hooks
+
+/// Represents the instructions and quote for compounding the fees and rewards of a position.
+#[derive(Debug)]
+pub struct CompoundFeesInstruction {
+    /// The computed quote for compounding, including:
+    /// - `liquidity_delta` - The liquidity added to the position.
+    /// - `token_est_a` and `token_est_b` - The token amounts added to the position.
+    /// - `fee_owed_a` and `fee_owed_b` - The fees that remain owed to the position.
+    /// - `reward_owed_a` and `reward_owed_b` - The rewards in token A or B that remain owed to the position.
+    pub quote: CompoundFeesQuote,
+
+    /// A vector of `Instruction` objects required to compound the fees and rewards.
+    pub instructions: Vec<Instruction>,
+}
+
+/// Generates instructions to compound the fees and rewards of a position into its liquidity.
+///
+/// The fees owed to the position are added to its liquidity at the current price without leaving
+/// the pool vaults, so no token accounts are needed and no transfer fee is charged on them. Rewards
+/// denominated in token A or B of the pool are compounded as well, after deducting the transfer fee
+/// for moving them from the reward vault. Whatever cannot be matched at the current price remains
+/// owed to the position and can be collected or compounded later.
+///
+/// # Arguments
+///
+/// * `rpc` - A reference to the Solana RPC client.
+/// * `position_mint_address` - The public key of the NFT mint address representing the pool position.
+/// * `authority` - An optional public key of the position owner. Defaults to the global funder if not provided.
+///
+/// # Returns
+///
+/// Returns a `Result` containing a `CompoundFeesInstruction` on success, which includes:
+/// * `quote` - The computed compounding quote.
+/// * `instructions` - A vector of `Instruction` objects required to compound the fees and rewards.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The authority account is invalid.
+/// - The fees and rewards owed are not enough to add any liquidity.
+/// - The pool is paused.
+/// - The position, pool, or token mint accounts are not found or invalid.
+/// - Any RPC request fails.
+///
+/// # Example
+///
+/// ```rust
+/// use orca_whirlpools::{
+///     compound_fees_and_rewards_instructions, set_whirlpools_config_address,
+///     WhirlpoolsConfigInput,
+/// };
+/// use solana_client::nonblocking::rpc_client::RpcClient;
+/// use solana_sdk::pubkey::Pubkey;
+/// use std::str::FromStr;
+/// use crate::utils::load_wallet;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     set_whirlpools_config_address(WhirlpoolsConfigInput::SolanaDevnet).unwrap();
+///     let rpc = RpcClient::new("https://api.devnet.solana.com".to_string());
+///     let wallet = load_wallet();
+///
+///     let position_mint_address =
+///         Pubkey::from_str("HqoV7Qv27REUtmd9UKSJGGmCRNx3531t33bDG1BUfo9K").unwrap();
+///
+///     let result =
+///         compound_fees_and_rewards_instructions(&rpc, position_mint_address, Some(wallet.pubkey()))
+///             .await
+///             .unwrap();
+///
+///     println!("Liquidity Delta: {}", result.quote.liquidity_delta);
+/// }
+/// ```
+pub async fn compound_fees_and_rewards_instructions(
+    rpc: &RpcClient,
+    position_mint_address: Pubkey,
+    authority: Option<Pubkey>,
+) -> Result<CompoundFeesInstruction, Box<dyn Error>> {
+    let authority = authority.unwrap_or(*FUNDER.try_lock()?);
+    if authority == Pubkey::default() {
+        return Err("Authority must be provided".into());
+    }
+
+    let position_address = get_position_address(&position_mint_address)?.0;
+    let position_info = rpc.get_account(&position_address).await?;
+    let position = Position::from_bytes(&position_info.data)?;
+
+    let pool_info = rpc.get_account(&position.whirlpool).await?;
+    let pool = Whirlpool::from_bytes(&pool_info.data)?;
+    if fetch_pool_paused(rpc, position.whirlpool, &pool).await? {
+        return Err("Pool is paused".into());
+    }
+
+    let mint_infos = rpc
+        .get_multiple_accounts(&[pool.token_mint_a, pool.token_mint_b, position_mint_address])
+        .await?;
+    let mint_a_info = mint_infos[0]
+        .as_ref()
+        .ok_or("Token A mint info not found")?;
+    let mint_b_info = mint_infos[1]
+        .as_ref()
+        .ok_or("Token B mint info not found")?;
+    let position_mint_info = mint_infos[2]
+        .as_ref()
+        .ok_or("Position mint info not found")?;
+
+    let current_epoch = rpc.get_epoch_info().await?.epoch;
+    let transfer_fee_a = get_current_transfer_fee(Some(mint_a_info), current_epoch);
+    let transfer_fee_b = get_current_transfer_fee(Some(mint_b_info), current_epoch);
+
+    let lower_tick_array_start_index =
+        get_tick_array_start_tick_index(position.tick_lower_index, pool.tick_spacing);
+    let upper_tick_array_start_index =
+        get_tick_array_start_tick_index(position.tick_upper_index, pool.tick_spacing);
+
+    let position_token_account_address = get_associated_token_address_with_program_id(
+        &authority,
+        &position_mint_address,
+        &position_mint_info.owner,
+    );
+    let lower_tick_array_address =
+        get_tick_array_address(&position.whirlpool, lower_tick_array_start_index)?.0;
+    let upper_tick_array_address =
+        get_tick_array_address(&position.whirlpool, upper_tick_array_start_index)?.0;
+
+    let tick_array_infos = rpc
+        .get_multiple_accounts(&[lower_tick_array_address, upper_tick_array_address])
+        .await?;
+
+    let lower_tick_array_info = tick_array_infos[0]
+        .as_ref()
+        .ok_or("Lower tick array info not found")?;
+    let lower_tick_array = decode_tick_array(&lower_tick_array_info.data)?;
+    let lower_tick = lower_tick_array.ticks[get_tick_index_in_array(
+        position.tick_lower_index,
+        lower_tick_array_start_index,
+        pool.tick_spacing,
+    )? as usize];
+
+    let upper_tick_array_info = tick_array_infos[1]
+        .as_ref()
+        .ok_or("Upper tick array info not found")?;
+    let upper_tick_array = decode_tick_array(&upper_tick_array_info.data)?;
+    let upper_tick = upper_tick_array.ticks[get_tick_index_in_array(
+        position.tick_upper_index,
+        upper_tick_array_start_index,
+        pool.tick_spacing,
+    )? as usize];
+
+    // Fees stay in the pool vaults, so the transfer fee is not applied to them.
+    let fees_quote = collect_fees_quote(
+        pool.clone().into(),
+        position.clone().into(),
+        lower_tick,
+        upper_tick,
+        None,
+        None,
+    )?;
+
+    // The transfer fee for rewards is applied by the compound quote.
+    let unix_timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let rewards_quote = collect_rewards_quote(
+        pool.clone().into(),
+        position.clone().into(),
+        lower_tick,
+        upper_tick,
+        unix_timestamp,
+        None,
+        None,
+        None,
+    )?;
+
+    let mut reward_owed_a = 0u64;
+    let mut reward_owed_b = 0u64;
+    let mut reward_vaults: Vec<Pubkey> = Vec::new();
+    for (i, reward_info) in pool.reward_infos.iter().enumerate() {
+        let rewards_owed = rewards_quote.rewards[i].rewards_owed;
+        if rewards_owed == 0 {
+            continue;
+        }
+        if reward_info.mint == pool.token_mint_a {
+            reward_owed_a = reward_owed_a.saturating_add(rewards_owed);
+        } else if reward_info.mint == pool.token_mint_b {
+            reward_owed_b = reward_owed_b.saturating_add(rewards_owed);
+        } else {
+            continue;
+        }
+        reward_vaults.push(reward_info.vault);
+    }
+
+    let quote = compound_fees_quote(
+        fees_quote.fee_owed_a,
+        fees_quote.fee_owed_b,
+        reward_owed_a,
+        reward_owed_b,
+        pool.sqrt_price.into(),
+        position.tick_lower_index,
+        position.tick_upper_index,
+        transfer_fee_a,
+        transfer_fee_b,
+    )?;
+
+    if quote.liquidity_delta == 0 {
+        return Err("Fees and rewards owed are not enough to add liquidity".into());
+    }
+
+    let remaining_accounts_info = if reward_vaults.is_empty() {
+        None
+    } else {
+        Some(RemainingAccountsInfo {
+            slices: vec![RemainingAccountsSlice {
+                accounts_type: AccountsType::RewardVaults,
+                length: reward_vaults.len() as u8,
+            }],
+        })
+    };
+
+    let instructions = vec![CompoundFeesAndRewards {
+        whirlpool: position.whirlpool,
+        token_program_a: mint_a_info.owner,
+        token_program_b: mint_b_info.owner,
+        memo_program: spl_memo::ID,
+        position_authority: authority,
+        position: position_address,
+        position_token_account: position_token_account_address,
+        token_mint_a: pool.token_mint_a,
+        token_mint_b: pool.token_mint_b,
+        token_vault_a: pool.token_vault_a,
+        token_vault_b: pool.token_vault_b,
+        tick_array_lower: lower_tick_array_address,
+        tick_array_upper: upper_tick_array_address,
+        oracle: get_oracle_address(&position.whirlpool)?.0,
+    }
+    .instruction_with_remaining_accounts(
+        CompoundFeesAndRewardsInstructionArgs {
+            remaining_accounts_info,
+        },
+        &reward_vaults
+            .iter()
+            .map(|&x| AccountMeta::new(x, false))
+            .collect::<Vec<AccountMeta>>(),
+    )];
+
+    Ok(CompoundFeesInstruction {
+        quote,
+        instructions,
+    })
+}
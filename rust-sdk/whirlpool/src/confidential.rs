@@ -0,0 +1,765 @@
+use std::{error::Error, mem::size_of, num::NonZeroI8};
+
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    account::Account as SolanaAccount, instruction::Instruction, pubkey::Pubkey,
+    signature::Keypair, signer::Signer, system_instruction::create_account,
+};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_token_2022::{
+    extension::{
+        confidential_transfer::{
+            account_info::WithdrawAccountInfo,
+            instruction::{apply_pending_balance, deposit, withdraw},
+            ConfidentialTransferAccount, ConfidentialTransferMint,
+        },
+        BaseStateWithExtensions, StateWithExtensions,
+    },
+    solana_zk_sdk::{
+        encryption::{
+            auth_encryption::{AeCiphertext, AeKey},
+            elgamal::{ElGamalCiphertext, ElGamalKeypair},
+            pod::elgamal::PodElGamalCiphertext,
+        },
+        zk_elgamal_proof_program::{
+            self,
+            instruction::{close_context_state, ContextStateInfo, ProofInstruction},
+            proof_data::BatchedRangeProofContext,
+            state::ProofContextState,
+        },
+    },
+    state::{Account, Mint},
+    ID as TOKEN_2022_PROGRAM_ID,
+};
+use spl_token_confidential_transfer_ciphertext_arithmetic::add_with_lo_hi;
+use spl_token_confidential_transfer_proof_extraction::instruction::{ProofData, ProofLocation};
+
+use crate::FUNDER;
+
+/// The number of bits of the pending balance that are encrypted in `pending_balance_lo`.
+const PENDING_BALANCE_LO_BIT_LENGTH: u32 = 16;
+
+/// The encryption keys of a token account that is configured for confidential transfers.
+///
+/// The keys never leave the client. They are used to decrypt balances and to generate the
+/// zero-knowledge proofs of withdrawals locally.
+pub struct ConfidentialTransferKeys {
+    /// The ElGamal keypair that encrypts the balances of the token account.
+    pub elgamal_keypair: ElGamalKeypair,
+
+    /// The authenticated encryption key of the decryptable available balance.
+    pub aes_key: AeKey,
+}
+
+impl ConfidentialTransferKeys {
+    /// Derives the keys of a token account from the signature of its owner, the same way the
+    /// `spl-token` CLI does when configuring the account.
+    pub fn new_from_signer(
+        signer: &dyn Signer,
+        token_account: &Pubkey,
+    ) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            elgamal_keypair: ElGamalKeypair::new_from_signer(signer, &token_account.to_bytes())?,
+            aes_key: AeKey::new_from_signer(signer, &token_account.to_bytes())?,
+        })
+    }
+}
+
+/// The public and confidential balances of a token account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfidentialBalance {
+    /// The address of the token account.
+    pub token_account: Pubkey,
+
+    /// The public balance, which is the only balance the Whirlpool program can transfer from.
+    pub public_amount: u64,
+
+    /// The decrypted available confidential balance, if keys were provided.
+    pub available_amount: Option<u64>,
+
+    /// The decrypted pending confidential balance, if keys were provided.
+    pub pending_amount: Option<u64>,
+
+    /// The number of deposits and transfers into the pending balance since it was last applied.
+    pub pending_balance_credit_counter: u64,
+
+    /// Whether the confidential balances might hold tokens. Without keys this cannot be known
+    /// for sure, so it is only false if the account never held a confidential balance.
+    pub may_have_confidential_funds: bool,
+
+    /// Whether the account accepts non-confidential transfers, such as the output of a swap.
+    pub allow_non_confidential_credits: bool,
+}
+
+/// The tokens that are missing from the public balance of a token account while its
+/// confidential balance might cover them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfidentialBalanceShortfall {
+    /// The address of the token mint.
+    pub mint: Pubkey,
+
+    /// The address of the token account.
+    pub token_account: Pubkey,
+
+    /// The amount that has to be in the public balance.
+    pub required_amount: u64,
+
+    /// The amount that is missing from the public balance.
+    pub shortfall: u64,
+
+    /// The balances of the token account.
+    pub balance: ConfidentialBalance,
+}
+
+/// A group of instructions that has to be sent as a single transaction.
+#[derive(Debug)]
+pub struct ConfidentialTransaction {
+    /// A vector of `Instruction` objects to send in this transaction.
+    pub instructions: Vec<Instruction>,
+
+    /// A vector of `Keypair` objects representing additional signers required for this transaction.
+    pub additional_signers: Vec<Keypair>,
+}
+
+/// Represents the transactions that move tokens between the public and confidential balances.
+#[derive(Debug)]
+pub struct ConfidentialBalanceInstruction {
+    /// The transactions to send, in order. Later transactions rely on the state created by
+    /// earlier ones, so each has to be confirmed before the next is sent.
+    pub transactions: Vec<ConfidentialTransaction>,
+
+    /// The amount of tokens that is moved.
+    pub amount: u64,
+}
+
+/// Fetches the public and confidential balances of the owner's associated token account.
+///
+/// # Arguments
+///
+/// * `rpc` - A reference to a Solana RPC client for fetching accounts.
+/// * `mint` - The address of the Token-2022 mint.
+/// * `keys` - The optional encryption keys of the token account. Without them only the public balance is known.
+/// * `owner` - An optional public key of the owner of the token account. Defaults to the global funder if not provided.
+///
+/// # Returns
+///
+/// A `Result` containing the `ConfidentialBalance`, or `None` if the token account does not
+/// exist or is not configured for confidential transfers.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The `owner` account is invalid or missing.
+/// - The token account has invalid data.
+/// - Any RPC request to the blockchain fails.
+pub async fn fetch_confidential_balance(
+    rpc: &RpcClient,
+    mint: Pubkey,
+    keys: Option<&ConfidentialTransferKeys>,
+    owner: Option<Pubkey>,
+) -> Result<Option<ConfidentialBalance>, Box<dyn Error>> {
+    let owner = owner.unwrap_or(*FUNDER.try_lock()?);
+    if owner == Pubkey::default() {
+        return Err("Owner must be provided".into());
+    }
+    let token_account =
+        get_associated_token_address_with_program_id(&owner, &mint, &TOKEN_2022_PROGRAM_ID);
+    let account_info = rpc.get_multiple_accounts(&[token_account]).await?.remove(0);
+    match account_info {
+        Some(account_info) => decode_confidential_balance(token_account, &account_info, keys),
+        None => Ok(None),
+    }
+}
+
+/// Checks whether tokens for a swap or deposit are stuck in the confidential balance.
+///
+/// The Whirlpool program only transfers from the public balance of a token account. If the
+/// public balance does not cover `required_amount` but the confidential balance might, the
+/// shortfall is returned so it can be shown to the user, or moved to the public balance with
+/// `withdraw_confidential_balance_instructions`.
+///
+/// # Arguments
+///
+/// * `rpc` - A reference to a Solana RPC client for fetching accounts.
+/// * `mint` - The address of the Token-2022 mint.
+/// * `required_amount` - The amount that has to be in the public balance.
+/// * `keys` - The optional encryption keys of the token account, to include the decrypted confidential balances.
+/// * `owner` - An optional public key of the owner of the token account. Defaults to the global funder if not provided.
+///
+/// # Returns
+///
+/// A `Result` containing the `ConfidentialBalanceShortfall`, or `None` if the public balance is
+/// sufficient or there is no confidential balance that could cover it.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The `owner` account is invalid or missing.
+/// - The token account has invalid data.
+/// - Any RPC request to the blockchain fails.
+pub async fn check_confidential_balance_shortfall(
+    rpc: &RpcClient,
+    mint: Pubkey,
+    required_amount: u64,
+    keys: Option<&ConfidentialTransferKeys>,
+    owner: Option<Pubkey>,
+) -> Result<Option<ConfidentialBalanceShortfall>, Box<dyn Error>> {
+    let balance = fetch_confidential_balance(rpc, mint, keys, owner).await?;
+    Ok(balance.and_then(|balance| get_shortfall(mint, required_amount, balance)))
+}
+
+/// Generates instructions to move tokens from the confidential balance to the public balance.
+///
+/// The pending balance is applied first, so deposits and incoming transfers can be withdrawn as
+/// well. The proofs of the withdrawal are generated locally. The range proof is too large to be
+/// sent with the withdrawal, so it is verified into a temporary context state account that is
+/// closed again by the withdrawal transaction.
+///
+/// # Arguments
+///
+/// * `rpc` - A reference to a Solana RPC client for fetching accounts.
+/// * `mint` - The address of the Token-2022 mint.
+/// * `amount` - The amount to withdraw. Withdraws the whole confidential balance if not provided.
+/// * `keys` - The encryption keys of the token account.
+/// * `authority` - An optional public key of the owner of the token account. Defaults to the global funder if not provided.
+///
+/// # Returns
+///
+/// A `Result` containing `ConfidentialBalanceInstruction` on success:
+///
+/// * `transactions` - The transactions to send, in order. Empty if there is nothing to withdraw.
+/// * `amount` - The amount of tokens that is moved to the public balance.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The `authority` account is invalid or missing.
+/// - The mint or the token account is not configured for confidential transfers.
+/// - The keys do not belong to the token account.
+/// - The confidential balance is lower than `amount`.
+/// - Any RPC request to the blockchain fails.
+///
+/// # Example
+///
+/// ```rust
+/// use orca_whirlpools::{ConfidentialTransferKeys, withdraw_confidential_balance_instructions};
+/// use solana_client::nonblocking::rpc_client::RpcClient;
+/// use solana_sdk::pubkey::Pubkey;
+/// use spl_associated_token_account::get_associated_token_address_with_program_id;
+/// use std::str::FromStr;
+/// use crate::utils::load_wallet;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let rpc = RpcClient::new("https://api.devnet.solana.com".to_string());
+///     let wallet = load_wallet();
+///     let mint = Pubkey::from_str("MINT_ADDRESS").unwrap();
+///     let token_account = get_associated_token_address_with_program_id(
+///         &wallet.pubkey(),
+///         &mint,
+///         &spl_token_2022::ID,
+///     );
+///     let keys = ConfidentialTransferKeys::new_from_signer(&wallet, &token_account).unwrap();
+///
+///     let result = withdraw_confidential_balance_instructions(
+///         &rpc,
+///         mint,
+///         None,
+///         &keys,
+///         Some(wallet.pubkey()),
+///     )
+///     .await
+///     .unwrap();
+///
+///     println!("Amount to withdraw: {}", result.amount);
+///     println!("Number of Transactions: {}", result.transactions.len());
+/// }
+/// ```
+pub async fn withdraw_confidential_balance_instructions(
+    rpc: &RpcClient,
+    mint: Pubkey,
+    amount: Option<u64>,
+    keys: &ConfidentialTransferKeys,
+    authority: Option<Pubkey>,
+) -> Result<ConfidentialBalanceInstruction, Box<dyn Error>> {
+    let authority = authority.unwrap_or(*FUNDER.try_lock()?);
+    if authority == Pubkey::default() {
+        return Err("Authority must be provided".into());
+    }
+
+    let state = fetch_confidential_state(rpc, mint, authority).await?;
+    let account = &state.account;
+    let available_amount = decrypt_available_balance(account, keys).ok_or(
+        "Failed to decrypt the confidential balance, the keys do not match the token account",
+    )?;
+    let pending_balance_credit_counter = u64::from(account.pending_balance_credit_counter);
+    let pending_amount = if pending_balance_credit_counter > 0 {
+        decrypt_pending_balance(account, keys).ok_or(
+            "Failed to decrypt the pending balance, the keys do not match the token account",
+        )?
+    } else {
+        0
+    };
+    let total_amount = available_amount + pending_amount;
+    let amount = amount.unwrap_or(total_amount);
+    if amount > total_amount {
+        return Err(format!("Insufficient confidential balance for mint {}", mint).into());
+    }
+    if amount == 0 {
+        return Ok(ConfidentialBalanceInstruction {
+            transactions: Vec::new(),
+            amount,
+        });
+    }
+
+    let mut setup_instructions: Vec<Instruction> = Vec::new();
+    let mut available_balance = account.available_balance;
+    if pending_balance_credit_counter > 0 {
+        setup_instructions.push(apply_pending_balance(
+            &TOKEN_2022_PROGRAM_ID,
+            &state.token_account,
+            pending_balance_credit_counter,
+            &keys.aes_key.encrypt(total_amount).into(),
+            &authority,
+            &[],
+        )?);
+        available_balance = add_with_lo_hi(
+            &available_balance,
+            &account.pending_balance_lo,
+            &account.pending_balance_hi,
+        )
+        .ok_or("Failed to apply the pending balance")?;
+    }
+
+    let withdraw_account_info = WithdrawAccountInfo {
+        available_balance,
+        decryptable_available_balance: keys.aes_key.encrypt(total_amount).into(),
+    };
+    let proof_data =
+        withdraw_account_info.generate_proof_data(amount, &keys.elgamal_keypair, &keys.aes_key)?;
+    let new_decryptable_available_balance =
+        withdraw_account_info.new_decryptable_available_balance(amount, &keys.aes_key)?;
+
+    let range_proof_account = Keypair::new();
+    let range_proof_address = range_proof_account.pubkey();
+    let range_proof_space = size_of::<ProofContextState<BatchedRangeProofContext>>();
+    let range_proof_lamports = rpc
+        .get_minimum_balance_for_rent_exemption(range_proof_space)
+        .await?;
+    let range_proof_context = ContextStateInfo {
+        context_state_account: &range_proof_address,
+        context_state_authority: &authority,
+    };
+
+    setup_instructions.push(create_account(
+        &authority,
+        &range_proof_address,
+        range_proof_lamports,
+        range_proof_space as u64,
+        &zk_elgamal_proof_program::id(),
+    ));
+
+    let verify_instructions = vec![ProofInstruction::VerifyBatchedRangeProofU64
+        .encode_verify_proof(Some(range_proof_context), &proof_data.range_proof_data)];
+
+    let mut withdraw_instructions = withdraw(
+        &TOKEN_2022_PROGRAM_ID,
+        &state.token_account,
+        &mint,
+        amount,
+        state.decimals,
+        &new_decryptable_available_balance.into(),
+        &authority,
+        &[],
+        ProofLocation::InstructionOffset(
+            NonZeroI8::try_from(1)?,
+            ProofData::InstructionData(&proof_data.equality_proof_data),
+        ),
+        ProofLocation::ContextStateAccount(&range_proof_address),
+    )?;
+    withdraw_instructions.push(close_context_state(range_proof_context, &authority));
+
+    Ok(ConfidentialBalanceInstruction {
+        transactions: vec![
+            ConfidentialTransaction {
+                instructions: setup_instructions,
+                additional_signers: vec![range_proof_account],
+            },
+            ConfidentialTransaction {
+                instructions: verify_instructions,
+                additional_signers: Vec::new(),
+            },
+            ConfidentialTransaction {
+                instructions: withdraw_instructions,
+                additional_signers: Vec::new(),
+            },
+        ],
+        amount,
+    })
+}
+
+/// Generates instructions to move tokens from the public balance to the confidential balance.
+///
+/// The output of swaps and withdrawals from pools always lands in the public balance. This can
+/// be used as a follow-up to keep it confidential. Deposited tokens arrive in the pending
+/// balance. If keys are provided, the pending balance is applied in the same transaction so the
+/// tokens are immediately available.
+///
+/// # Arguments
+///
+/// * `rpc` - A reference to a Solana RPC client for fetching accounts.
+/// * `mint` - The address of the Token-2022 mint.
+/// * `amount` - The amount to deposit. Deposits the whole public balance if not provided.
+/// * `keys` - The optional encryption keys of the token account, to apply the pending balance.
+/// * `authority` - An optional public key of the owner of the token account. Defaults to the global funder if not provided.
+///
+/// # Returns
+///
+/// A `Result` containing `ConfidentialBalanceInstruction` on success:
+///
+/// * `transactions` - The transactions to send, in order. Empty if there is nothing to deposit.
+/// * `amount` - The amount of tokens that is moved to the confidential balance.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The `authority` account is invalid or missing.
+/// - The mint or the token account is not configured for confidential transfers.
+/// - The keys do not belong to the token account.
+/// - The public balance is lower than `amount`.
+/// - Any RPC request to the blockchain fails.
+pub async fn deposit_confidential_balance_instructions(
+    rpc: &RpcClient,
+    mint: Pubkey,
+    amount: Option<u64>,
+    keys: Option<&ConfidentialTransferKeys>,
+    authority: Option<Pubkey>,
+) -> Result<ConfidentialBalanceInstruction, Box<dyn Error>> {
+    let authority = authority.unwrap_or(*FUNDER.try_lock()?);
+    if authority == Pubkey::default() {
+        return Err("Authority must be provided".into());
+    }
+
+    let state = fetch_confidential_state(rpc, mint, authority).await?;
+    let account = &state.account;
+    let amount = amount.unwrap_or(state.public_amount);
+    if amount > state.public_amount {
+        return Err(format!("Insufficient balance for mint {}", mint).into());
+    }
+    if amount == 0 {
+        return Ok(ConfidentialBalanceInstruction {
+            transactions: Vec::new(),
+            amount,
+        });
+    }
+
+    let mut instructions = vec![deposit(
+        &TOKEN_2022_PROGRAM_ID,
+        &state.token_account,
+        &mint,
+        amount,
+        state.decimals,
+        &authority,
+        &[],
+    )?];
+
+    if let Some(keys) = keys {
+        let available_amount = decrypt_available_balance(account, keys).ok_or(
+            "Failed to decrypt the confidential balance, the keys do not match the token account",
+        )?;
+        let pending_balance_credit_counter = u64::from(account.pending_balance_credit_counter);
+        let pending_amount = if pending_balance_credit_counter > 0 {
+            decrypt_pending_balance(account, keys).ok_or(
+                "Failed to decrypt the pending balance, the keys do not match the token account",
+            )?
+        } else {
+            0
+        };
+        let new_available_amount = available_amount + pending_amount + amount;
+        instructions.push(apply_pending_balance(
+            &TOKEN_2022_PROGRAM_ID,
+            &state.token_account,
+            pending_balance_credit_counter + 1,
+            &keys.aes_key.encrypt(new_available_amount).into(),
+            &authority,
+            &[],
+        )?);
+    }
+
+    Ok(ConfidentialBalanceInstruction {
+        transactions: vec![ConfidentialTransaction {
+            instructions,
+            additional_signers: Vec::new(),
+        }],
+        amount,
+    })
+}
+
+/// Whether a token account might hold tokens in its confidential balances.
+pub(crate) fn has_confidential_funds(account_info: &SolanaAccount) -> bool {
+    decode_confidential_balance(Pubkey::default(), account_info, None)
+        .ok()
+        .flatten()
+        .is_some_and(|x| x.may_have_confidential_funds)
+}
+
+fn decode_confidential_balance(
+    token_account: Pubkey,
+    account_info: &SolanaAccount,
+    keys: Option<&ConfidentialTransferKeys>,
+) -> Result<Option<ConfidentialBalance>, Box<dyn Error>> {
+    if account_info.owner != TOKEN_2022_PROGRAM_ID {
+        return Ok(None);
+    }
+    let state = StateWithExtensions::<Account>::unpack(&account_info.data)?;
+    let Ok(account) = state.get_extension::<ConfidentialTransferAccount>() else {
+        return Ok(None);
+    };
+
+    let pending_balance_credit_counter = u64::from(account.pending_balance_credit_counter);
+    let available_amount = keys.and_then(|keys| decrypt_available_balance(account, keys));
+    let pending_amount = keys.and_then(|keys| {
+        if pending_balance_credit_counter > 0 {
+            decrypt_pending_balance(account, keys)
+        } else {
+            Some(0)
+        }
+    });
+    let may_have_confidential_funds = match (available_amount, pending_amount) {
+        (Some(available_amount), Some(pending_amount)) => available_amount + pending_amount > 0,
+        _ => {
+            pending_balance_credit_counter > 0
+                || account.available_balance != PodElGamalCiphertext::default()
+        }
+    };
+
+    Ok(Some(ConfidentialBalance {
+        token_account,
+        public_amount: state.base.amount,
+        available_amount,
+        pending_amount,
+        pending_balance_credit_counter,
+        may_have_confidential_funds,
+        allow_non_confidential_credits: bool::from(account.allow_non_confidential_credits),
+    }))
+}
+
+fn get_shortfall(
+    mint: Pubkey,
+    required_amount: u64,
+    balance: ConfidentialBalance,
+) -> Option<ConfidentialBalanceShortfall> {
+    if balance.public_amount >= required_amount || !balance.may_have_confidential_funds {
+        return None;
+    }
+    Some(ConfidentialBalanceShortfall {
+        mint,
+        token_account: balance.token_account,
+        required_amount,
+        shortfall: required_amount - balance.public_amount,
+        balance,
+    })
+}
+
+fn decrypt_available_balance(
+    account: &ConfidentialTransferAccount,
+    keys: &ConfidentialTransferKeys,
+) -> Option<u64> {
+    let ciphertext = AeCiphertext::try_from(account.decryptable_available_balance).ok()?;
+    keys.aes_key.decrypt(&ciphertext)
+}
+
+fn decrypt_pending_balance(
+    account: &ConfidentialTransferAccount,
+    keys: &ConfidentialTransferKeys,
+) -> Option<u64> {
+    let secret = keys.elgamal_keypair.secret();
+    let lo = secret.decrypt_u32(&ElGamalCiphertext::try_from(account.pending_balance_lo).ok()?)?;
+    let hi = secret.decrypt_u32(&ElGamalCiphertext::try_from(account.pending_balance_hi).ok()?)?;
+    hi.checked_shl(PENDING_BALANCE_LO_BIT_LENGTH)?
+        .checked_add(lo)
+}
+
+struct ConfidentialState {
+    token_account: Pubkey,
+    decimals: u8,
+    public_amount: u64,
+    account: ConfidentialTransferAccount,
+}
+
+async fn fetch_confidential_state(
+    rpc: &RpcClient,
+    mint: Pubkey,
+    owner: Pubkey,
+) -> Result<ConfidentialState, Box<dyn Error>> {
+    let token_account =
+        get_associated_token_address_with_program_id(&owner, &mint, &TOKEN_2022_PROGRAM_ID);
+    let account_infos = rpc.get_multiple_accounts(&[mint, token_account]).await?;
+    let mint_info = account_infos[0]
+        .as_ref()
+        .ok_or(format!("Mint {} not found", mint))?;
+    let account_info = account_infos[1]
+        .as_ref()
+        .ok_or(format!("Token account {} not found", token_account))?;
+    if mint_info.owner != TOKEN_2022_PROGRAM_ID {
+        return Err(format!("Mint {} does not support confidential transfers", mint).into());
+    }
+
+    let mint_state = StateWithExtensions::<Mint>::unpack(&mint_info.data)?;
+    if mint_state
+        .get_extension::<ConfidentialTransferMint>()
+        .is_err()
+    {
+        return Err(format!("Mint {} does not support confidential transfers", mint).into());
+    }
+    let account_state = StateWithExtensions::<Account>::unpack(&account_info.data)?;
+    let account = *account_state
+        .get_extension::<ConfidentialTransferAccount>()
+        .map_err(|_| "Token account is not configured for confidential transfers")?;
+
+    Ok(ConfidentialState {
+        token_account,
+        decimals: mint_state.base.decimals,
+        public_amount: account_state.base.amount,
+        account,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spl_token::solana_program::program_pack::Pack;
+    use spl_token_2022::{
+        extension::{ExtensionType, StateWithExtensionsMut},
+        state::AccountState,
+    };
+
+    fn new_keys() -> ConfidentialTransferKeys {
+        ConfidentialTransferKeys {
+            elgamal_keypair: ElGamalKeypair::new_rand(),
+            aes_key: AeKey::new_rand(),
+        }
+    }
+
+    fn token_account(
+        keys: &ConfidentialTransferKeys,
+        public_amount: u64,
+        available_amount: Option<u64>,
+        pending_amount: Option<u64>,
+    ) -> SolanaAccount {
+        let space = ExtensionType::try_calculate_account_len::<Account>(&[
+            ExtensionType::ConfidentialTransferAccount,
+        ])
+        .unwrap();
+        let mut data = vec![0u8; space];
+        let mut state = StateWithExtensionsMut::<Account>::unpack_uninitialized(&mut data).unwrap();
+        let extension = state
+            .init_extension::<ConfidentialTransferAccount>(true)
+            .unwrap();
+        let elgamal_pubkey = keys.elgamal_keypair.pubkey();
+        extension.approved = true.into();
+        extension.elgamal_pubkey = (*elgamal_pubkey).into();
+        extension.allow_confidential_credits = true.into();
+        extension.allow_non_confidential_credits = true.into();
+        if let Some(available_amount) = available_amount {
+            extension.available_balance = elgamal_pubkey.encrypt(available_amount).into();
+        }
+        extension.decryptable_available_balance =
+            keys.aes_key.encrypt(available_amount.unwrap_or(0)).into();
+        if let Some(pending_amount) = pending_amount {
+            extension.pending_balance_lo = elgamal_pubkey.encrypt(pending_amount).into();
+            extension.pending_balance_hi = elgamal_pubkey.encrypt(0_u64).into();
+            extension.pending_balance_credit_counter = 1.into();
+        }
+        state.base = Account {
+            amount: public_amount,
+            state: AccountState::Initialized,
+            ..Default::default()
+        };
+        state.pack_base();
+        state.init_account_type().unwrap();
+
+        SolanaAccount {
+            lamports: 0,
+            data,
+            owner: TOKEN_2022_PROGRAM_ID,
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    #[test]
+    fn test_decode_without_confidential_funds() {
+        let keys = new_keys();
+        let account_info = token_account(&keys, 500, None, None);
+        let balance = decode_confidential_balance(Pubkey::default(), &account_info, None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(balance.public_amount, 500);
+        assert_eq!(balance.available_amount, None);
+        assert_eq!(balance.pending_amount, None);
+        assert!(!balance.may_have_confidential_funds);
+        assert!(balance.allow_non_confidential_credits);
+        assert!(!has_confidential_funds(&account_info));
+        assert_eq!(get_shortfall(Pubkey::default(), 1_000, balance), None);
+    }
+
+    #[test]
+    fn test_decode_with_confidential_funds_without_keys() {
+        let keys = new_keys();
+        let account_info = token_account(&keys, 500, Some(300), Some(400));
+        let balance = decode_confidential_balance(Pubkey::default(), &account_info, None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(balance.available_amount, None);
+        assert_eq!(balance.pending_amount, None);
+        assert_eq!(balance.pending_balance_credit_counter, 1);
+        assert!(balance.may_have_confidential_funds);
+        assert!(has_confidential_funds(&account_info));
+
+        let shortfall = get_shortfall(Pubkey::default(), 1_000, balance).unwrap();
+        assert_eq!(shortfall.required_amount, 1_000);
+        assert_eq!(shortfall.shortfall, 500);
+        assert_eq!(get_shortfall(Pubkey::default(), 500, balance), None);
+    }
+
+    #[test]
+    fn test_decode_with_keys() {
+        let keys = new_keys();
+        let account_info = token_account(&keys, 500, Some(300), Some(400));
+        let balance = decode_confidential_balance(Pubkey::default(), &account_info, Some(&keys))
+            .unwrap()
+            .unwrap();
+        assert_eq!(balance.available_amount, Some(300));
+        assert_eq!(balance.pending_amount, Some(400));
+        assert!(balance.may_have_confidential_funds);
+    }
+
+    #[test]
+    fn test_decode_with_wrong_keys() {
+        let keys = new_keys();
+        let account_info = token_account(&keys, 500, Some(300), None);
+        let balance =
+            decode_confidential_balance(Pubkey::default(), &account_info, Some(&new_keys()))
+                .unwrap()
+                .unwrap();
+        assert_eq!(balance.available_amount, None);
+        assert!(balance.may_have_confidential_funds);
+    }
+
+    #[test]
+    fn test_decode_without_extension() {
+        let account_info = SolanaAccount {
+            lamports: 0,
+            data: vec![0u8; Account::LEN],
+            owner: spl_token::ID,
+            executable: false,
+            rent_epoch: 0,
+        };
+        assert_eq!(
+            decode_confidential_balance(Pubkey::default(), &account_info, None).unwrap(),
+            None
+        );
+        assert!(!has_confidential_funds(&account_info));
+    }
+}
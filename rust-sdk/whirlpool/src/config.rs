@@ -157,6 +157,29 @@ pub fn set_enforce_token_balance_check(enforce_balance_check: bool) -> Result<()
     Ok(())
 }
 
+/// Defines which kind of tick array is created when a tick array needs to be initialized.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TickArrayKind {
+    /// Allocates all ticks up front. Costs more rent but never grows.
+    Fixed,
+    /// Only allocates initialized ticks. The account grows as ticks are initialized.
+    Dynamic,
+    /// Uses whichever kind requires the least rent to initialize.
+    MinimumRent,
+}
+
+/// The default tick array kind.
+pub const DEFAULT_TICK_ARRAY_KIND: TickArrayKind = TickArrayKind::MinimumRent;
+
+/// The currently selected tick array kind.
+pub static TICK_ARRAY_KIND: Mutex<TickArrayKind> = Mutex::new(DEFAULT_TICK_ARRAY_KIND);
+
+/// Sets the currently selected tick array kind.
+pub fn set_tick_array_kind(kind: TickArrayKind) -> Result<(), Box<dyn Error>> {
+    *TICK_ARRAY_KIND.try_lock()? = kind;
+    Ok(())
+}
+
 /// Resets the configuration to its default values.
 pub fn reset_configuration() -> Result<(), Box<dyn Error>> {
     *WHIRLPOOLS_CONFIG_ADDRESS.try_lock()? = SOLANA_MAINNET_WHIRLPOOLS_CONFIG_ADDRESS;
@@ -166,6 +189,7 @@ pub fn reset_configuration() -> Result<(), Box<dyn Error>> {
     *NATIVE_MINT_WRAPPING_STRATEGY.try_lock()? = DEFAULT_NATIVE_MINT_WRAPPING_STRATEGY;
     *SLIPPAGE_TOLERANCE_BPS.try_lock()? = DEFAULT_SLIPPAGE_TOLERANCE_BPS;
     *ENFORCE_TOKEN_BALANCE_CHECK.try_lock()? = DEFAULT_ENFORCE_TOKEN_BALANCE_CHECK;
+    *TICK_ARRAY_KIND.try_lock()? = DEFAULT_TICK_ARRAY_KIND;
     Ok(())
 }
 
@@ -246,6 +270,14 @@ mod tests {
         reset_configuration().unwrap();
     }
 
+    #[test]
+    #[serial]
+    fn test_set_tick_array_kind() {
+        set_tick_array_kind(TickArrayKind::Fixed).unwrap();
+        assert_eq!(*TICK_ARRAY_KIND.lock().unwrap(), TickArrayKind::Fixed);
+        reset_configuration().unwrap();
+    }
+
     #[test]
     #[serial]
     fn test_reset_configuration() {
@@ -267,5 +299,6 @@ mod tests {
             *ENFORCE_TOKEN_BALANCE_CHECK.lock().unwrap(),
             DEFAULT_ENFORCE_TOKEN_BALANCE_CHECK
         );
+        assert_eq!(*TICK_ARRAY_KIND.lock().unwrap(), DEFAULT_TICK_ARRAY_KIND);
     }
 }
@@ -1,18 +1,21 @@
 use std::collections::HashSet;
 use std::error::Error;
 
+use std::time::{SystemTime, UNIX_EPOCH};
+
 use orca_whirlpools_client::Whirlpool;
 use orca_whirlpools_client::{
-    get_fee_tier_address, get_tick_array_address, get_token_badge_address, get_whirlpool_address,
-    DynamicTickArray,
+    get_fee_tier_address, get_oracle_address, get_token_badge_address, get_whirlpool_address,
+    AdaptiveFeeTier, Oracle,
 };
 use orca_whirlpools_client::{
-    InitializeDynamicTickArray, InitializeDynamicTickArrayInstructionArgs, InitializePoolV2,
-    InitializePoolV2InstructionArgs,
+    InitializePoolV2, InitializePoolV2InstructionArgs, InitializePoolWithAdaptiveFee,
+    InitializePoolWithAdaptiveFeeInstructionArgs,
 };
 use orca_whirlpools_core::{
-    get_full_range_tick_indexes, get_tick_array_start_tick_index, price_to_sqrt_price,
-    sqrt_price_to_tick_index,
+    get_full_range_tick_indexes, get_initializable_tick_index, get_tick_array_start_tick_index,
+    order_tick_indexes, price_to_sqrt_price, price_to_tick_index, sqrt_price_to_tick_index,
+    IncreaseLiquidityQuote, MAX_TRADE_ENABLE_TIMESTAMP_DELTA,
 };
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_program::rent::Rent;
@@ -24,9 +27,12 @@ use solana_sdk::signer::Signer;
 use spl_token_2022::extension::StateWithExtensions;
 use spl_token_2022::state::Mint;
 
-use crate::token::order_mints;
+use crate::increase_liquidity::{internal_open_position, OpenPositionPool};
+use crate::tick_array::initialize_tick_array_instruction;
+use crate::token::{order_mints, verify_token_mint_compatibility};
 use crate::{
-    get_account_data_size, get_rent, FUNDER, SPLASH_POOL_TICK_SPACING, WHIRLPOOLS_CONFIG_ADDRESS,
+    get_account_data_size, get_rent, IncreaseLiquidityParam, FUNDER, SPLASH_POOL_TICK_SPACING,
+    WHIRLPOOLS_CONFIG_ADDRESS,
 };
 
 /// Represents the instructions and metadata for creating a pool.
@@ -68,6 +74,7 @@ pub struct CreatePoolInstructions {
 /// - The funder account is invalid.
 /// - Token mints are not found or have invalid data.
 /// - The token mint order does not match the canonical byte order.
+/// - A token mint is not supported by the Whirlpool program, e.g. it has a transfer hook but no token badge.
 /// - Any RPC request to the blockchain fails.
 ///
 /// # Example
@@ -145,6 +152,7 @@ pub async fn create_splash_pool_instructions(
 /// - The funder account is invalid.
 /// - Token mints are not found or have invalid data.
 /// - The token mint order does not match the canonical byte order.
+/// - A token mint is not supported by the Whirlpool program, e.g. it has a transfer hook but no token badge.
 /// - Any RPC request to the blockchain fails.
 ///
 /// # Example
@@ -206,7 +214,13 @@ pub async fn create_concentrated_liquidity_pool_instructions(
 
     let rent = get_rent(rpc).await?;
 
-    let account_infos = rpc.get_multiple_accounts(&[token_a, token_b]).await?;
+    let whirlpools_config = *WHIRLPOOLS_CONFIG_ADDRESS.try_lock()?;
+    let token_badge_a = get_token_badge_address(&whirlpools_config, &token_a)?.0;
+    let token_badge_b = get_token_badge_address(&whirlpools_config, &token_b)?.0;
+
+    let account_infos = rpc
+        .get_multiple_accounts(&[token_a, token_b, token_badge_a, token_badge_b])
+        .await?;
     let mint_a_info = account_infos[0]
         .as_ref()
         .ok_or(format!("Mint {} not found", token_a))?;
@@ -220,23 +234,25 @@ pub async fn create_concentrated_liquidity_pool_instructions(
     let decimals_b = mint_b.base.decimals;
     let token_program_b = mint_b_info.owner;
 
-    let initial_sqrt_price: u128 = price_to_sqrt_price(initial_price, decimals_a, decimals_b);
-
-    let pool_address = get_whirlpool_address(
-        &*WHIRLPOOLS_CONFIG_ADDRESS.try_lock()?,
+    verify_token_mint_compatibility(
+        &whirlpools_config,
         &token_a,
+        mint_a_info,
+        account_infos[2].as_ref(),
+    )?;
+    verify_token_mint_compatibility(
+        &whirlpools_config,
         &token_b,
-        tick_spacing,
-    )?
-    .0;
+        mint_b_info,
+        account_infos[3].as_ref(),
+    )?;
 
-    let fee_tier = get_fee_tier_address(&*WHIRLPOOLS_CONFIG_ADDRESS.try_lock()?, tick_spacing)?.0;
+    let initial_sqrt_price: u128 = price_to_sqrt_price(initial_price, decimals_a, decimals_b);
 
-    let token_badge_a =
-        get_token_badge_address(&*WHIRLPOOLS_CONFIG_ADDRESS.try_lock()?, &token_a)?.0;
+    let pool_address =
+        get_whirlpool_address(&whirlpools_config, &token_a, &token_b, tick_spacing)?.0;
 
-    let token_badge_b =
-        get_token_badge_address(&*WHIRLPOOLS_CONFIG_ADDRESS.try_lock()?, &token_b)?.0;
+    let fee_tier = get_fee_tier_address(&whirlpools_config, tick_spacing)?.0;
 
     let token_vault_a = Keypair::new();
     let token_vault_b = Keypair::new();
@@ -246,7 +262,7 @@ pub async fn create_concentrated_liquidity_pool_instructions(
 
     instructions.push(
         InitializePoolV2 {
-            whirlpools_config: *WHIRLPOOLS_CONFIG_ADDRESS.try_lock()?,
+            whirlpools_config,
             token_mint_a: token_a,
             token_mint_b: token_b,
             token_badge_a,
@@ -284,20 +300,10 @@ pub async fn create_concentrated_liquidity_pool_instructions(
     let tick_array_indexes =
         HashSet::from([lower_tick_index, upper_tick_index, current_tick_index]);
     for start_tick_index in tick_array_indexes {
-        let tick_array_address = get_tick_array_address(&pool_address, start_tick_index)?;
-        instructions.push(
-            InitializeDynamicTickArray {
-                whirlpool: pool_address,
-                tick_array: tick_array_address.0,
-                funder,
-                system_program: system_program::id(),
-            }
-            .instruction(InitializeDynamicTickArrayInstructionArgs {
-                start_tick_index,
-                idempotent: false,
-            }),
-        );
-        initialization_cost += rent.minimum_balance(DynamicTickArray::MIN_LEN);
+        let (instruction, cost) =
+            initialize_tick_array_instruction(pool_address, start_tick_index, funder, &rent)?;
+        instructions.push(instruction);
+        initialization_cost += cost;
     }
 
     Ok(CreatePoolInstructions {
@@ -308,10 +314,352 @@ pub async fn create_concentrated_liquidity_pool_instructions(
     })
 }
 
+/// Represents the position opened when a pool is created.
+#[derive(Debug, Clone)]
+pub struct InitialPosition {
+    /// The lower and upper price of the position. Opens a full-range position if not provided.
+    pub price_range: Option<(f64, f64)>,
+
+    /// The liquidity to deposit into the position, specified as `IncreaseLiquidityParam`.
+    pub param: IncreaseLiquidityParam,
+
+    /// An optional slippage tolerance in basis points. Defaults to the global slippage tolerance if not provided.
+    pub slippage_tolerance_bps: Option<u16>,
+}
+
+/// Represents the instructions and metadata for creating a pool with an adaptive fee tier.
+pub struct CreateAdaptiveFeePoolInstructions {
+    /// The list of instructions needed to create the pool and open the initial position.
+    pub instructions: Vec<Instruction>,
+
+    /// The estimated rent exemption cost for initializing the pool and the initial position, in lamports.
+    pub initialization_cost: u64,
+
+    /// The address of the newly created pool.
+    pub pool_address: Pubkey,
+
+    /// The address of the oracle that stores the adaptive fee state of the pool.
+    pub oracle_address: Pubkey,
+
+    /// The mint of the initial position NFT, if an initial position is opened.
+    pub position_mint: Option<Pubkey>,
+
+    /// The liquidity quote of the initial position, if an initial position is opened.
+    pub quote: Option<IncreaseLiquidityQuote>,
+
+    /// The list of signers for the instructions.
+    pub additional_signers: Vec<Keypair>,
+}
+
+/// Creates the necessary instructions to initialize a pool with an adaptive fee tier.
+///
+/// Adaptive fee pools charge a fee that increases with volatility on top of the base fee of the
+/// adaptive fee tier. Permissioned adaptive fee tiers only allow their `initialize_pool_authority`
+/// to create pools and may delay trading until `trade_enable_timestamp`.
+///
+/// # Arguments
+///
+/// * `rpc` - A reference to a Solana RPC client for communicating with the blockchain.
+/// * `token_a` - The public key of the first token mint address to include in the pool.
+/// * `token_b` - The public key of the second token mint address to include in the pool.
+/// * `fee_tier_index` - The index of the adaptive fee tier.
+/// * `initial_price` - An optional initial price of token A in terms of token B. Defaults to 1.0 if not provided.
+/// * `trade_enable_timestamp` - An optional timestamp before which swaps are rejected. Only allowed for permissioned adaptive fee tiers and at most `MAX_TRADE_ENABLE_TIMESTAMP_DELTA` seconds in the future.
+/// * `initial_position` - An optional position to open and fund in the new pool.
+/// * `funder` - An optional public key of the account funding the initialization process. Defaults to the global funder if not provided. Also signs as the initialize pool authority.
+///
+/// # Returns
+///
+/// A `Result` containing `CreateAdaptiveFeePoolInstructions` on success:
+/// * `instructions` - A vector of Solana instructions needed to initialize the pool and the initial position.
+/// * `initialization_cost` - The estimated rent exemption cost, in lamports.
+/// * `pool_address` - The public key of the newly created pool.
+/// * `oracle_address` - The public key of the oracle of the pool.
+/// * `position_mint` - The mint of the initial position NFT, if any.
+/// * `quote` - The liquidity quote of the initial position, if any.
+/// * `additional_signers` - A vector of `Keypair` objects representing additional signers required for the instructions.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The funder account is invalid or is not the initialize pool authority of a permissioned adaptive fee tier.
+/// - The adaptive fee tier or token mints are not found or have invalid data.
+/// - The token mint order does not match the canonical byte order.
+/// - A token mint is not supported by the Whirlpool program, e.g. it has a transfer hook but no token badge.
+/// - The trade enable timestamp is not allowed for the adaptive fee tier or out of range.
+/// - The funder holds too few tokens for the initial position.
+/// - Any RPC request to the blockchain fails.
+///
+/// # Example
+///
+/// ```rust
+/// use orca_whirlpools::{
+///     create_adaptive_fee_pool_instructions, set_whirlpools_config_address, InitialPosition,
+///     IncreaseLiquidityParam, WhirlpoolsConfigInput,
+/// };
+/// use solana_client::nonblocking::rpc_client::RpcClient;
+/// use solana_sdk::{pubkey::Pubkey, signature::Signer, signer::keypair::Keypair};
+/// use std::str::FromStr;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     set_whirlpools_config_address(WhirlpoolsConfigInput::SolanaDevnet).unwrap();
+///     let rpc = RpcClient::new("https://api.devnet.solana.com".to_string());
+///     let token_a = Pubkey::from_str("So11111111111111111111111111111111111111112").unwrap();
+///     let token_b = Pubkey::from_str("BRjpCHtyQLNCo8gqRUr8jtdAj5AjPYQaoqbvcZiHok1k").unwrap(); // devUSDC
+///     let fee_tier_index = 1024 + 64;
+///     let wallet = Keypair::new(); // CAUTION: This wallet is not persistent.
+///
+///     let create_pool_instructions = create_adaptive_fee_pool_instructions(
+///         &rpc,
+///         token_a,
+///         token_b,
+///         fee_tier_index,
+///         Some(0.01),
+///         None,
+///         Some(InitialPosition {
+///             price_range: None,
+///             param: IncreaseLiquidityParam::TokenA(1_000_000),
+///             slippage_tolerance_bps: None,
+///         }),
+///         Some(wallet.pubkey()),
+///     )
+///     .await
+///     .unwrap();
+///
+///     println!("Pool Address: {:?}", create_pool_instructions.pool_address);
+///     println!("Position Mint: {:?}", create_pool_instructions.position_mint);
+/// }
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub async fn create_adaptive_fee_pool_instructions(
+    rpc: &RpcClient,
+    token_a: Pubkey,
+    token_b: Pubkey,
+    fee_tier_index: u16,
+    initial_price: Option<f64>,
+    trade_enable_timestamp: Option<u64>,
+    initial_position: Option<InitialPosition>,
+    funder: Option<Pubkey>,
+) -> Result<CreateAdaptiveFeePoolInstructions, Box<dyn Error>> {
+    let initial_price = initial_price.unwrap_or(1.0);
+    let funder = funder.unwrap_or(*FUNDER.try_lock()?);
+    if funder == Pubkey::default() {
+        return Err("Funder must be provided".into());
+    }
+    if order_mints(token_a, token_b)[0] != token_a {
+        return Err("Token order needs to be flipped to match the canonical ordering (i.e. sorted on the byte repr. of the mint pubkeys)".into());
+    }
+
+    let whirlpools_config = *WHIRLPOOLS_CONFIG_ADDRESS.try_lock()?;
+    let adaptive_fee_tier_address = get_fee_tier_address(&whirlpools_config, fee_tier_index)?.0;
+
+    let rent = get_rent(rpc).await?;
+
+    let token_badge_a = get_token_badge_address(&whirlpools_config, &token_a)?.0;
+    let token_badge_b = get_token_badge_address(&whirlpools_config, &token_b)?.0;
+
+    let account_infos = rpc
+        .get_multiple_accounts(&[
+            token_a,
+            token_b,
+            adaptive_fee_tier_address,
+            token_badge_a,
+            token_badge_b,
+        ])
+        .await?;
+    let mint_a_info = account_infos[0]
+        .as_ref()
+        .ok_or(format!("Mint {} not found", token_a))?;
+    let mint_a = StateWithExtensions::<Mint>::unpack(&mint_a_info.data)?;
+    let decimals_a = mint_a.base.decimals;
+    let token_program_a = mint_a_info.owner;
+    let mint_b_info = account_infos[1]
+        .as_ref()
+        .ok_or(format!("Mint {} not found", token_b))?;
+    let mint_b = StateWithExtensions::<Mint>::unpack(&mint_b_info.data)?;
+    let decimals_b = mint_b.base.decimals;
+    let token_program_b = mint_b_info.owner;
+    let adaptive_fee_tier_info = account_infos[2]
+        .as_ref()
+        .ok_or(format!("Adaptive fee tier {} not found", fee_tier_index))?;
+    let adaptive_fee_tier = AdaptiveFeeTier::from_bytes(&adaptive_fee_tier_info.data)?;
+
+    verify_token_mint_compatibility(
+        &whirlpools_config,
+        &token_a,
+        mint_a_info,
+        account_infos[3].as_ref(),
+    )?;
+    verify_token_mint_compatibility(
+        &whirlpools_config,
+        &token_b,
+        mint_b_info,
+        account_infos[4].as_ref(),
+    )?;
+
+    let is_permissioned = adaptive_fee_tier.initialize_pool_authority != Pubkey::default();
+    if is_permissioned && adaptive_fee_tier.initialize_pool_authority != funder {
+        return Err(format!(
+            "Adaptive fee tier {} only allows {} to initialize pools",
+            fee_tier_index, adaptive_fee_tier.initialize_pool_authority
+        )
+        .into());
+    }
+
+    if let Some(trade_enable_timestamp) = trade_enable_timestamp {
+        if !is_permissioned {
+            return Err("Trade enable timestamp requires a permissioned adaptive fee tier".into());
+        }
+        let current_timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        if trade_enable_timestamp > current_timestamp + MAX_TRADE_ENABLE_TIMESTAMP_DELTA {
+            return Err(format!(
+                "Trade enable timestamp must be at most {} seconds in the future",
+                MAX_TRADE_ENABLE_TIMESTAMP_DELTA
+            )
+            .into());
+        }
+        if trade_enable_timestamp < current_timestamp {
+            return Err("Trade enable timestamp must not be in the past".into());
+        }
+    }
+
+    let tick_spacing = adaptive_fee_tier.tick_spacing;
+    let initial_sqrt_price: u128 = price_to_sqrt_price(initial_price, decimals_a, decimals_b);
+
+    let pool_address =
+        get_whirlpool_address(&whirlpools_config, &token_a, &token_b, fee_tier_index)?.0;
+    let oracle_address = get_oracle_address(&pool_address)?.0;
+
+    let token_vault_a = Keypair::new();
+    let token_vault_b = Keypair::new();
+
+    let mut initialization_cost: u64 = 0;
+    let mut instructions = vec![];
+
+    instructions.push(
+        InitializePoolWithAdaptiveFee {
+            whirlpools_config,
+            token_mint_a: token_a,
+            token_mint_b: token_b,
+            token_badge_a,
+            token_badge_b,
+            funder,
+            initialize_pool_authority: funder,
+            whirlpool: pool_address,
+            oracle: oracle_address,
+            token_vault_a: token_vault_a.pubkey(),
+            token_vault_b: token_vault_b.pubkey(),
+            adaptive_fee_tier: adaptive_fee_tier_address,
+            token_program_a,
+            token_program_b,
+            system_program: system_program::id(),
+            rent: Rent::id(),
+        }
+        .instruction(InitializePoolWithAdaptiveFeeInstructionArgs {
+            initial_sqrt_price,
+            trade_enable_timestamp,
+        }),
+    );
+
+    initialization_cost += rent.minimum_balance(Whirlpool::LEN);
+    initialization_cost += rent.minimum_balance(Oracle::LEN);
+    let token_a_space = get_account_data_size(token_program_a, mint_a_info)?;
+    initialization_cost += rent.minimum_balance(token_a_space);
+    let token_b_space = get_account_data_size(token_program_b, mint_b_info)?;
+    initialization_cost += rent.minimum_balance(token_b_space);
+
+    // The tick arrays of the initial position are initialized when the position is opened.
+    let position_tick_range = initial_position.as_ref().map(|position| {
+        let tick_range = match position.price_range {
+            Some((lower_price, upper_price)) => order_tick_indexes(
+                price_to_tick_index(lower_price, decimals_a, decimals_b),
+                price_to_tick_index(upper_price, decimals_a, decimals_b),
+            ),
+            None => get_full_range_tick_indexes(tick_spacing),
+        };
+        (
+            get_initializable_tick_index(tick_range.tick_lower_index, tick_spacing, Some(false)),
+            get_initializable_tick_index(tick_range.tick_upper_index, tick_spacing, Some(true)),
+        )
+    });
+    let position_tick_array_indexes: HashSet<i32> = position_tick_range
+        .map(|(lower, upper)| {
+            HashSet::from([
+                get_tick_array_start_tick_index(lower, tick_spacing),
+                get_tick_array_start_tick_index(upper, tick_spacing),
+            ])
+        })
+        .unwrap_or_default();
+
+    let full_range = get_full_range_tick_indexes(tick_spacing);
+    let lower_tick_index =
+        get_tick_array_start_tick_index(full_range.tick_lower_index, tick_spacing);
+    let upper_tick_index =
+        get_tick_array_start_tick_index(full_range.tick_upper_index, tick_spacing);
+    let initial_tick_index = sqrt_price_to_tick_index(initial_sqrt_price);
+    let current_tick_index = get_tick_array_start_tick_index(initial_tick_index, tick_spacing);
+
+    let tick_array_indexes =
+        HashSet::from([lower_tick_index, upper_tick_index, current_tick_index]);
+    for start_tick_index in tick_array_indexes.difference(&position_tick_array_indexes) {
+        let (instruction, cost) =
+            initialize_tick_array_instruction(pool_address, *start_tick_index, funder, &rent)?;
+        instructions.push(instruction);
+        initialization_cost += cost;
+    }
+
+    let mut additional_signers = vec![token_vault_a, token_vault_b];
+    let mut position_mint = None;
+    let mut quote = None;
+
+    if let (Some(initial_position), Some((tick_lower_index, tick_upper_index))) =
+        (initial_position, position_tick_range)
+    {
+        let pool = OpenPositionPool {
+            tick_spacing,
+            sqrt_price: initial_sqrt_price,
+            token_mint_a: token_a,
+            token_mint_b: token_b,
+            token_vault_a: additional_signers[0].pubkey(),
+            token_vault_b: additional_signers[1].pubkey(),
+        };
+        let open_position = internal_open_position(
+            rpc,
+            pool_address,
+            pool,
+            initial_position.param,
+            tick_lower_index,
+            tick_upper_index,
+            mint_a_info,
+            mint_b_info,
+            initial_position.slippage_tolerance_bps,
+            Some(funder),
+        )
+        .await?;
+        instructions.extend(open_position.instructions);
+        additional_signers.extend(open_position.additional_signers);
+        initialization_cost += open_position.initialization_cost;
+        position_mint = Some(open_position.position_mint);
+        quote = Some(open_position.quote);
+    }
+
+    Ok(CreateAdaptiveFeePoolInstructions {
+        instructions,
+        initialization_cost,
+        pool_address,
+        oracle_address,
+        position_mint,
+        quote,
+        additional_signers,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use crate::tests::{
-        setup_mint, setup_mint_te, setup_mint_te_fee, setup_mint_te_sua, RpcContext,
+        setup_ata_with_amount, setup_mint, setup_mint_te, setup_mint_te_fee, setup_mint_te_sua,
+        RpcContext, ADAPTIVE_FEE_TIER_INDEX, PERMISSIONED_ADAPTIVE_FEE_TIER_INDEX,
     };
 
     use super::*;
@@ -821,4 +1169,155 @@ mod tests {
         assert_eq!(mint_te_sua, pool_after.token_mint_b);
         assert_eq!(64, pool_after.tick_spacing);
     }
+
+    async fn setup_ordered_mints(ctx: &RpcContext) -> (Pubkey, Pubkey) {
+        let mint_a = setup_mint(ctx).await.unwrap();
+        let mint_b = setup_mint(ctx).await.unwrap();
+        let [mint_a, mint_b] = order_mints(mint_a, mint_b);
+        setup_ata_with_amount(ctx, mint_a, 1_000_000_000_000)
+            .await
+            .unwrap();
+        setup_ata_with_amount(ctx, mint_b, 1_000_000_000_000)
+            .await
+            .unwrap();
+        (mint_a, mint_b)
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_create_adaptive_fee_pool_with_initial_position() {
+        let ctx = RpcContext::new().await;
+        let (mint_a, mint_b) = setup_ordered_mints(&ctx).await;
+
+        let result = create_adaptive_fee_pool_instructions(
+            &ctx.rpc,
+            mint_a,
+            mint_b,
+            ADAPTIVE_FEE_TIER_INDEX,
+            Some(1.0),
+            None,
+            Some(InitialPosition {
+                price_range: Some((0.5, 2.0)),
+                param: IncreaseLiquidityParam::TokenA(1_000_000),
+                slippage_tolerance_bps: None,
+            }),
+            Some(ctx.signer.pubkey()),
+        )
+        .await
+        .unwrap();
+
+        ctx.send_transaction_with_signers(
+            result.instructions,
+            result.additional_signers.iter().collect(),
+        )
+        .await
+        .unwrap();
+
+        let pool = fetch_pool(&ctx.rpc, result.pool_address).await.unwrap();
+        assert_eq!(pool.tick_spacing, 64);
+        assert_eq!(pool.fee_rate, 3000);
+        assert_eq!(
+            u16::from_le_bytes(pool.fee_tier_index_seed),
+            ADAPTIVE_FEE_TIER_INDEX
+        );
+        assert!(pool.liquidity > 0);
+        assert!(ctx.rpc.get_account(&result.oracle_address).await.is_ok());
+        assert!(result.position_mint.is_some());
+        assert_eq!(result.quote.unwrap().liquidity_delta, pool.liquidity);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_create_permissioned_adaptive_fee_pool() {
+        let ctx = RpcContext::new().await;
+        let (mint_a, mint_b) = setup_ordered_mints(&ctx).await;
+        let trade_enable_timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            + 60;
+
+        let result = create_adaptive_fee_pool_instructions(
+            &ctx.rpc,
+            mint_a,
+            mint_b,
+            PERMISSIONED_ADAPTIVE_FEE_TIER_INDEX,
+            Some(1.0),
+            Some(trade_enable_timestamp),
+            None,
+            Some(ctx.signer.pubkey()),
+        )
+        .await
+        .unwrap();
+
+        ctx.send_transaction_with_signers(
+            result.instructions,
+            result.additional_signers.iter().collect(),
+        )
+        .await
+        .unwrap();
+
+        let pool = fetch_pool(&ctx.rpc, result.pool_address).await.unwrap();
+        assert_eq!(pool.liquidity, 0);
+        assert!(result.position_mint.is_none());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_error_if_not_initialize_pool_authority() {
+        let ctx = RpcContext::new().await;
+        let (mint_a, mint_b) = setup_ordered_mints(&ctx).await;
+
+        let result = create_adaptive_fee_pool_instructions(
+            &ctx.rpc,
+            mint_a,
+            mint_b,
+            PERMISSIONED_ADAPTIVE_FEE_TIER_INDEX,
+            Some(1.0),
+            None,
+            None,
+            Some(Pubkey::new_unique()),
+        )
+        .await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_error_if_invalid_trade_enable_timestamp() {
+        let ctx = RpcContext::new().await;
+        let (mint_a, mint_b) = setup_ordered_mints(&ctx).await;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        // permission-less adaptive fee tiers do not allow a trade enable timestamp
+        let result = create_adaptive_fee_pool_instructions(
+            &ctx.rpc,
+            mint_a,
+            mint_b,
+            ADAPTIVE_FEE_TIER_INDEX,
+            Some(1.0),
+            Some(now + 60),
+            None,
+            Some(ctx.signer.pubkey()),
+        )
+        .await;
+        assert!(result.is_err());
+
+        let result = create_adaptive_fee_pool_instructions(
+            &ctx.rpc,
+            mint_a,
+            mint_b,
+            PERMISSIONED_ADAPTIVE_FEE_TIER_INDEX,
+            Some(1.0),
+            Some(now + MAX_TRADE_ENABLE_TIMESTAMP_DELTA + 60),
+            None,
+            Some(ctx.signer.pubkey()),
+        )
+        .await;
+        assert!(result.is_err());
+    }
 }
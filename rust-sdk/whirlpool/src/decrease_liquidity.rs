@@ -1,6 +1,4 @@
-use orca_whirlpools_client::{
-    get_position_address, get_tick_array_address, FixedTickArray, Position, TickArray, Whirlpool,
-};
+use orca_whirlpools_client::{get_position_address, get_tick_array_address, Position, Whirlpool};
 use orca_whirlpools_client::{
     ClosePosition, ClosePositionWithTokenExtensions, CollectFeesV2, CollectFeesV2InstructionArgs,
     CollectRewardV2, CollectRewardV2InstructionArgs, DecreaseLiquidityV2,
@@ -21,6 +19,7 @@ use std::{
 };
 
 use crate::{
+    tick_array::decode_tick_array,
     token::{get_current_transfer_fee, prepare_token_accounts_instructions, TokenAccountStrategy},
     FUNDER, SLIPPAGE_TOLERANCE_BPS,
 };
@@ -442,9 +441,8 @@ pub async fn close_position_instructions(
     let lower_tick_array_info = tick_array_infos[0]
         .as_ref()
         .ok_or("Lower tick array info not found")?;
-    let lower_tick_array: FixedTickArray =
-        TickArray::from_bytes(&lower_tick_array_info.data)?.into();
-    let lower_tick = &lower_tick_array.ticks[get_tick_index_in_array(
+    let lower_tick_array = decode_tick_array(&lower_tick_array_info.data)?;
+    let lower_tick = lower_tick_array.ticks[get_tick_index_in_array(
         position.tick_lower_index,
         lower_tick_array_start_index,
         pool.tick_spacing,
@@ -453,9 +451,8 @@ pub async fn close_position_instructions(
     let upper_tick_array_info = tick_array_infos[1]
         .as_ref()
         .ok_or("Upper tick array info not found")?;
-    let upper_tick_array: FixedTickArray =
-        TickArray::from_bytes(&upper_tick_array_info.data)?.into();
-    let upper_tick = &upper_tick_array.ticks[get_tick_index_in_array(
+    let upper_tick_array = decode_tick_array(&upper_tick_array_info.data)?;
+    let upper_tick = upper_tick_array.ticks[get_tick_index_in_array(
         position.tick_upper_index,
         upper_tick_array_start_index,
         pool.tick_spacing,
@@ -464,8 +461,8 @@ pub async fn close_position_instructions(
     let fees_quote = collect_fees_quote(
         pool.clone().into(),
         position.clone().into(),
-        lower_tick.clone().into(),
-        upper_tick.clone().into(),
+        lower_tick,
+        upper_tick,
         transfer_fee_a,
         transfer_fee_b,
     )?;
@@ -474,8 +471,8 @@ pub async fn close_position_instructions(
     let rewards_quote = collect_rewards_quote(
         pool.clone().into(),
         position.clone().into(),
-        lower_tick.clone().into(),
-        upper_tick.clone().into(),
+        lower_tick,
+        upper_tick,
         unix_timestamp,
         get_current_transfer_fee(reward_infos[0].as_ref(), current_epoch),
         get_current_transfer_fee(reward_infos[1].as_ref(), current_epoch),
@@ -968,6 +965,7 @@ mod tests {
             final_a,
             SwapType::ExactIn,
             Some(100),
+            None,
             Some(ctx.signer.pubkey()),
         )
         .await?;
@@ -1,20 +1,32 @@
 use std::error::Error;
 
-use orca_whirlpools_client::{get_position_address, Position, Whirlpool};
+use orca_whirlpools_client::{
+    get_oracle_address, get_position_address, get_range_order_address,
+    get_whirlpools_config_extension_address, InitializeConfigExtension, Position, SetConfigPause,
+    SetConfigPauseInstructionArgs, SetWhirlpoolPause, SetWhirlpoolPauseInstructionArgs,
+    SyncWhirlpoolPause, Whirlpool,
+};
 use serial_test::serial;
 use solana_program_test::tokio::{self};
-use solana_sdk::{program_pack::Pack, pubkey::Pubkey, signer::Signer};
+use solana_sdk::{
+    instruction::Instruction, program_pack::Pack, pubkey::Pubkey, signer::Signer, system_program,
+};
 use spl_token_2022::state::Account;
 
 use crate::{
-    close_position_instructions, create_concentrated_liquidity_pool_instructions,
-    create_splash_pool_instructions, decrease_liquidity_instructions,
-    harvest_position_instructions, increase_liquidity_instructions,
-    open_full_range_position_instructions, swap_instructions,
+    close_position_instructions, compound_fees_and_rewards_instructions,
+    create_concentrated_liquidity_pool_instructions, create_splash_pool_instructions,
+    decrease_liquidity_instructions, flash_loan_instructions, harvest_position_instructions,
+    increase_liquidity_instructions, open_full_range_position_instructions,
+    open_range_order_instructions, settle_range_order_instructions, swap_instructions,
     tests::{setup_ata_with_amount, setup_mint_with_decimals, RpcContext},
     DecreaseLiquidityParam, IncreaseLiquidityParam, SwapQuote, SwapType, SPLASH_POOL_TICK_SPACING,
+    WHIRLPOOLS_CONFIG_ADDRESS,
 };
 
+// The custom program error of WhirlpoolPaused
+const WHIRLPOOL_PAUSED_ERROR: &str = "0x17c5";
+
 struct TestContext {
     ctx: RpcContext,
     mint_a: Pubkey,
@@ -339,6 +351,7 @@ impl TestContext {
             self.mint_a,
             SwapType::ExactIn,
             None,
+            None,
             Some(self.ctx.signer.pubkey()),
         )
         .await?;
@@ -386,6 +399,7 @@ impl TestContext {
             self.mint_a,
             SwapType::ExactOut,
             None,
+            None,
             Some(self.ctx.signer.pubkey()),
         )
         .await?;
@@ -436,6 +450,7 @@ impl TestContext {
             self.mint_b,
             SwapType::ExactIn,
             None,
+            None,
             Some(self.ctx.signer.pubkey()),
         )
         .await?;
@@ -483,6 +498,7 @@ impl TestContext {
             self.mint_b,
             SwapType::ExactOut,
             None,
+            None,
             Some(self.ctx.signer.pubkey()),
         )
         .await?;
@@ -515,6 +531,76 @@ impl TestContext {
         }
         Ok(())
     }
+
+    pub async fn init_config_extension(&self) -> Result<(), Box<dyn Error>> {
+        let config = *WHIRLPOOLS_CONFIG_ADDRESS.try_lock()?;
+        let instruction = InitializeConfigExtension {
+            config,
+            config_extension: get_whirlpools_config_extension_address(&config)?.0,
+            funder: self.ctx.signer.pubkey(),
+            fee_authority: self.ctx.signer.pubkey(),
+            system_program: system_program::id(),
+        }
+        .instruction();
+        self.ctx.send_transaction(vec![instruction]).await?;
+        Ok(())
+    }
+
+    pub async fn set_pool_pause(&self, pool: Pubkey, paused: bool) -> Result<(), Box<dyn Error>> {
+        let config = *WHIRLPOOLS_CONFIG_ADDRESS.try_lock()?;
+        let instruction = SetWhirlpoolPause {
+            whirlpools_config: config,
+            whirlpools_config_extension: get_whirlpools_config_extension_address(&config)?.0,
+            emergency_authority: self.ctx.signer.pubkey(),
+            whirlpool: pool,
+            oracle: get_oracle_address(&pool)?.0,
+            funder: self.ctx.signer.pubkey(),
+            system_program: system_program::id(),
+        }
+        .instruction(SetWhirlpoolPauseInstructionArgs { paused });
+        self.ctx.send_transaction(vec![instruction]).await?;
+        Ok(())
+    }
+
+    pub async fn set_config_pause(&self, paused: bool) -> Result<(), Box<dyn Error>> {
+        let config = *WHIRLPOOLS_CONFIG_ADDRESS.try_lock()?;
+        let instruction = SetConfigPause {
+            whirlpools_config: config,
+            whirlpools_config_extension: get_whirlpools_config_extension_address(&config)?.0,
+            emergency_authority: self.ctx.signer.pubkey(),
+        }
+        .instruction(SetConfigPauseInstructionArgs { paused });
+        self.ctx.send_transaction(vec![instruction]).await?;
+        Ok(())
+    }
+
+    pub async fn sync_pool_pause(&self, pool: Pubkey) -> Result<(), Box<dyn Error>> {
+        let config = *WHIRLPOOLS_CONFIG_ADDRESS.try_lock()?;
+        let instruction = SyncWhirlpoolPause {
+            whirlpools_config_extension: get_whirlpools_config_extension_address(&config)?.0,
+            whirlpool: pool,
+            oracle: get_oracle_address(&pool)?.0,
+            funder: self.ctx.signer.pubkey(),
+            system_program: system_program::id(),
+        }
+        .instruction();
+        self.ctx.send_transaction(vec![instruction]).await?;
+        Ok(())
+    }
+
+    // The instructions are built before pausing because the builders refuse paused pools,
+    // so this asserts that the program itself rejects them.
+    pub async fn assert_paused(
+        &self,
+        instructions: Vec<Instruction>,
+    ) -> Result<(), Box<dyn Error>> {
+        let result = self.ctx.send_transaction(instructions).await;
+        let error = result
+            .err()
+            .ok_or("Transaction succeeded on a paused pool")?;
+        assert!(error.to_string().contains(WHIRLPOOL_PAUSED_ERROR));
+        Ok(())
+    }
 }
 
 #[tokio::test]
@@ -556,3 +642,250 @@ async fn test_concentrated_liquidity_pool() {
     Box::pin(ctx.swap_b_exact_out(pool)).await.unwrap();
     Box::pin(ctx.close_position(position_mint)).await.unwrap();
 }
+
+#[tokio::test]
+#[serial]
+async fn test_paused_pool_rejects_swap() {
+    let ctx = TestContext::new().await.unwrap();
+    ctx.init_config_extension().await.unwrap();
+    let pool = ctx.init_concentrated_liquidity_pool().await.unwrap();
+    ctx.open_position(pool).await.unwrap();
+
+    let swap = swap_instructions(
+        &ctx.ctx.rpc,
+        pool,
+        100000,
+        ctx.mint_a,
+        SwapType::ExactIn,
+        None,
+        None,
+        Some(ctx.ctx.signer.pubkey()),
+    )
+    .await
+    .unwrap();
+
+    ctx.set_pool_pause(pool, true).await.unwrap();
+    ctx.assert_paused(swap.instructions).await.unwrap();
+
+    ctx.set_pool_pause(pool, false).await.unwrap();
+    Box::pin(ctx.swap_a_exact_in(pool)).await.unwrap();
+}
+
+#[tokio::test]
+#[serial]
+async fn test_paused_pool_rejects_increase_liquidity() {
+    let ctx = TestContext::new().await.unwrap();
+    ctx.init_config_extension().await.unwrap();
+    let pool = ctx.init_concentrated_liquidity_pool().await.unwrap();
+    let position_mint = ctx.open_position(pool).await.unwrap();
+
+    let increase_liquidity = increase_liquidity_instructions(
+        &ctx.ctx.rpc,
+        position_mint,
+        IncreaseLiquidityParam::Liquidity(1000000000),
+        None,
+        Some(ctx.ctx.signer.pubkey()),
+    )
+    .await
+    .unwrap();
+
+    ctx.set_pool_pause(pool, true).await.unwrap();
+    ctx.assert_paused(increase_liquidity.instructions)
+        .await
+        .unwrap();
+
+    // Decreases are never blocked by a pause
+    Box::pin(ctx.decrease_liquidity(position_mint))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[serial]
+async fn test_paused_config_rejects_increase_liquidity_once_synced() {
+    let ctx = TestContext::new().await.unwrap();
+    ctx.init_config_extension().await.unwrap();
+    let pool = ctx.init_concentrated_liquidity_pool().await.unwrap();
+    let position_mint = ctx.open_position(pool).await.unwrap();
+
+    let increase_liquidity = increase_liquidity_instructions(
+        &ctx.ctx.rpc,
+        position_mint,
+        IncreaseLiquidityParam::Liquidity(1000000000),
+        None,
+        Some(ctx.ctx.signer.pubkey()),
+    )
+    .await
+    .unwrap();
+
+    ctx.set_config_pause(true).await.unwrap();
+    ctx.sync_pool_pause(pool).await.unwrap();
+    ctx.assert_paused(increase_liquidity.instructions)
+        .await
+        .unwrap();
+
+    ctx.set_config_pause(false).await.unwrap();
+    ctx.sync_pool_pause(pool).await.unwrap();
+    Box::pin(ctx.increase_liquidity(position_mint))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[serial]
+async fn test_paused_pool_rejects_open_range_order() {
+    let ctx = TestContext::new().await.unwrap();
+    ctx.init_config_extension().await.unwrap();
+    let pool = ctx.init_concentrated_liquidity_pool().await.unwrap();
+
+    let mut range_order = open_range_order_instructions(
+        &ctx.ctx.rpc,
+        pool,
+        2.0,
+        true,
+        1_000_000,
+        Some(ctx.ctx.signer.pubkey()),
+    )
+    .await
+    .unwrap();
+
+    // Open the position first so that only the open_range_order instruction runs while paused
+    let open_range_order = range_order.instructions.pop().unwrap();
+    ctx.ctx
+        .send_transaction_with_signers(
+            range_order.instructions,
+            range_order.additional_signers.iter().collect(),
+        )
+        .await
+        .unwrap();
+
+    ctx.set_pool_pause(pool, true).await.unwrap();
+    ctx.assert_paused(vec![open_range_order.clone()])
+        .await
+        .unwrap();
+
+    ctx.set_pool_pause(pool, false).await.unwrap();
+    ctx.ctx
+        .send_transaction(vec![open_range_order])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[serial]
+async fn test_paused_pool_rejects_compound_fees_and_rewards() {
+    let ctx = TestContext::new().await.unwrap();
+    ctx.init_config_extension().await.unwrap();
+    let pool = ctx.init_concentrated_liquidity_pool().await.unwrap();
+    let position_mint = ctx.open_position(pool).await.unwrap();
+    Box::pin(ctx.swap_a_exact_in(pool)).await.unwrap();
+    Box::pin(ctx.swap_b_exact_in(pool)).await.unwrap();
+
+    let compound = compound_fees_and_rewards_instructions(
+        &ctx.ctx.rpc,
+        position_mint,
+        Some(ctx.ctx.signer.pubkey()),
+    )
+    .await
+    .unwrap();
+
+    ctx.set_pool_pause(pool, true).await.unwrap();
+    ctx.assert_paused(compound.instructions).await.unwrap();
+}
+
+#[tokio::test]
+#[serial]
+async fn test_paused_pool_rejects_flash_borrow() {
+    let ctx = TestContext::new().await.unwrap();
+    ctx.init_config_extension().await.unwrap();
+    let pool = ctx.init_concentrated_liquidity_pool().await.unwrap();
+    ctx.open_position(pool).await.unwrap();
+
+    let flash_loan = flash_loan_instructions(
+        &ctx.ctx.rpc,
+        pool,
+        100000,
+        0,
+        vec![],
+        Some(ctx.ctx.signer.pubkey()),
+    )
+    .await
+    .unwrap();
+
+    ctx.set_pool_pause(pool, true).await.unwrap();
+    ctx.assert_paused(flash_loan.instructions).await.unwrap();
+}
+
+#[tokio::test]
+#[serial]
+async fn test_range_order_not_filled_after_price_reverts() {
+    let ctx = TestContext::new().await.unwrap();
+    let pool = ctx.init_concentrated_liquidity_pool().await.unwrap();
+    ctx.open_position(pool).await.unwrap();
+
+    let range_order = open_range_order_instructions(
+        &ctx.ctx.rpc,
+        pool,
+        2.0,
+        true,
+        1_000_000,
+        Some(ctx.ctx.signer.pubkey()),
+    )
+    .await
+    .unwrap();
+    ctx.ctx
+        .send_transaction_with_signers(
+            range_order.instructions,
+            range_order.additional_signers.iter().collect(),
+        )
+        .await
+        .unwrap();
+
+    // Push the price through the order range and back below it
+    for (specified_mint, amount) in [(ctx.mint_b, 1_000_000_000), (ctx.mint_a, 2_000_000_000)] {
+        let swap = swap_instructions(
+            &ctx.ctx.rpc,
+            pool,
+            amount,
+            specified_mint,
+            SwapType::ExactIn,
+            None,
+            None,
+            Some(ctx.ctx.signer.pubkey()),
+        )
+        .await
+        .unwrap();
+        ctx.ctx
+            .send_transaction_with_signers(
+                swap.instructions,
+                swap.additional_signers.iter().collect(),
+            )
+            .await
+            .unwrap();
+    }
+
+    let whirlpool_info = ctx.ctx.rpc.get_account(&pool).await.unwrap();
+    let whirlpool = Whirlpool::from_bytes(&whirlpool_info.data).unwrap();
+    assert!(whirlpool.tick_current_index < range_order.quote.tick_lower_index);
+
+    // The swap back converted the order into token A again, so it is not filled anymore
+    let result = settle_range_order_instructions(
+        &ctx.ctx.rpc,
+        range_order.position_mint,
+        Some(ctx.ctx.signer.pubkey()),
+    )
+    .await;
+    assert!(result.is_err());
+
+    let position_address = get_position_address(&range_order.position_mint).unwrap().0;
+    let range_order_address = get_range_order_address(&position_address).unwrap().0;
+    let infos = ctx
+        .ctx
+        .rpc
+        .get_multiple_accounts(&[position_address, range_order_address])
+        .await
+        .unwrap();
+    let position = Position::from_bytes(&infos[0].as_ref().unwrap().data).unwrap();
+    assert!(position.liquidity > 0);
+    assert!(infos[1].is_some());
+}
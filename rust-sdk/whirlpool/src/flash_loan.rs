@@ -0,0 +1,218 @@
+use std::error::Error;
+
+use orca_whirlpools_client::{
+    get_oracle_address, FlashBorrow, FlashBorrowInstructionArgs, FlashRepay,
+    FlashRepayInstructionArgs, Whirlpool,
+};
+use orca_whirlpools_core::{flash_loan_quote, FlashLoanQuote};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Keypair};
+
+use crate::{
+    pool::fetch_pool_paused,
+    token::{get_current_transfer_fee, prepare_token_accounts_instructions, TokenAccountStrategy},
+    FUNDER,
+};
+
+// TODO: support transfer hooks
+
+/// Represents the instructions and quote for a flash loan.
+#[derive(Debug)]
+pub struct FlashLoanInstruction {
+    /// The computed quote for the flash loan, including:
+    /// - `fee_a` and `fee_b` - The fees paid to the pool.
+    /// - `token_received_a` and `token_received_b` - The amounts received after transfer fees.
+    /// - `token_repay_a` and `token_repay_b` - The amounts withdrawn from the borrower when repaying.
+    pub quote: FlashLoanQuote,
+
+    /// A vector of `Instruction` objects: the borrow, the provided instructions, and the repayment.
+    pub instructions: Vec<Instruction>,
+
+    /// A vector of `Keypair` objects representing additional signers required for the instructions.
+    pub additional_signers: Vec<Keypair>,
+}
+
+/// Generates instructions to borrow tokens from the vaults of a pool and repay them in the same transaction.
+///
+/// The provided instructions are placed between the borrow and the repayment, so they can use the
+/// borrowed tokens as long as the borrower's token accounts hold the loan plus the fee when the
+/// repayment executes. The fee is charged at the pool's fee rate and is paid to liquidity providers.
+///
+/// # Arguments
+///
+/// * `rpc` - A reference to the Solana RPC client.
+/// * `pool_address` - The public key of the liquidity pool.
+/// * `amount_a` - The amount of token A to borrow.
+/// * `amount_b` - The amount of token B to borrow.
+/// * `instructions` - The instructions that use the borrowed tokens.
+/// * `authority` - An optional public key of the borrower. Defaults to the global funder if not provided.
+///
+/// # Returns
+///
+/// Returns a `Result` containing a `FlashLoanInstruction` on success, which includes:
+/// * `quote` - The computed flash loan quote.
+/// * `instructions` - A vector of `Instruction` objects required for the flash loan.
+/// * `additional_signers` - A vector of `Keypair` objects for additional transaction signers.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The authority account is invalid.
+/// - Both amounts are zero.
+/// - The pool is paused.
+/// - The pool or token mint accounts are not found or invalid.
+/// - Any RPC request fails.
+///
+/// # Example
+///
+/// ```rust
+/// use orca_whirlpools::{
+///     flash_loan_instructions, set_whirlpools_config_address, WhirlpoolsConfigInput,
+/// };
+/// use solana_client::nonblocking::rpc_client::RpcClient;
+/// use solana_sdk::pubkey::Pubkey;
+/// use std::str::FromStr;
+/// use crate::utils::load_wallet;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     set_whirlpools_config_address(WhirlpoolsConfigInput::SolanaDevnet).unwrap();
+///     let rpc = RpcClient::new("https://api.devnet.solana.com".to_string());
+///     let wallet = load_wallet();
+///
+///     let whirlpool_address =
+///         Pubkey::from_str("3KBZiL2g8C7tiJ32hTv5v3KM7aK9htpqTw4cTXz1HvPt").unwrap();
+///     let arbitrage_instructions = vec![];
+///
+///     let result = flash_loan_instructions(
+///         &rpc,
+///         whirlpool_address,
+///         1_000_000,
+///         0,
+///         arbitrage_instructions,
+///         Some(wallet.pubkey()),
+///     )
+///     .await
+///     .unwrap();
+///
+///     println!("Fee A: {}", result.quote.fee_a);
+/// }
+/// ```
+pub async fn flash_loan_instructions(
+    rpc: &RpcClient,
+    pool_address: Pubkey,
+    amount_a: u64,
+    amount_b: u64,
+    instructions: Vec<Instruction>,
+    authority: Option<Pubkey>,
+) -> Result<FlashLoanInstruction, Box<dyn Error>> {
+    let authority = authority.unwrap_or(*FUNDER.try_lock()?);
+    if authority == Pubkey::default() {
+        return Err("Authority must be provided".into());
+    }
+    if amount_a == 0 && amount_b == 0 {
+        return Err("Flash loan amount must be greater than zero".into());
+    }
+
+    let pool_info = rpc.get_account(&pool_address).await?;
+    let pool = Whirlpool::from_bytes(&pool_info.data)?;
+    if fetch_pool_paused(rpc, pool_address, &pool).await? {
+        return Err("Pool is paused".into());
+    }
+
+    let mint_infos = rpc
+        .get_multiple_accounts(&[pool.token_mint_a, pool.token_mint_b])
+        .await?;
+    let mint_a_info = mint_infos[0]
+        .as_ref()
+        .ok_or("Token A mint info not found")?;
+    let mint_b_info = mint_infos[1]
+        .as_ref()
+        .ok_or("Token B mint info not found")?;
+
+    let current_epoch = rpc.get_epoch_info().await?.epoch;
+    let quote = flash_loan_quote(
+        amount_a,
+        amount_b,
+        pool.fee_rate,
+        get_current_transfer_fee(Some(mint_a_info), current_epoch),
+        get_current_transfer_fee(Some(mint_b_info), current_epoch),
+    )?;
+
+    let token_accounts = prepare_token_accounts_instructions(
+        rpc,
+        authority,
+        vec![
+            TokenAccountStrategy::WithoutBalance(pool.token_mint_a),
+            TokenAccountStrategy::WithoutBalance(pool.token_mint_b),
+        ],
+    )
+    .await?;
+
+    let token_owner_account_a = token_accounts
+        .token_account_addresses
+        .get(&pool.token_mint_a)
+        .ok_or("Token A owner account not found")?;
+    let token_owner_account_b = token_accounts
+        .token_account_addresses
+        .get(&pool.token_mint_b)
+        .ok_or("Token B owner account not found")?;
+
+    let mut flash_loan_instructions: Vec<Instruction> = Vec::new();
+    flash_loan_instructions.extend(token_accounts.create_instructions);
+
+    flash_loan_instructions.push(
+        FlashBorrow {
+            whirlpool: pool_address,
+            token_program_a: mint_a_info.owner,
+            token_program_b: mint_b_info.owner,
+            memo_program: spl_memo::ID,
+            borrower: authority,
+            token_mint_a: pool.token_mint_a,
+            token_mint_b: pool.token_mint_b,
+            token_owner_account_a: *token_owner_account_a,
+            token_owner_account_b: *token_owner_account_b,
+            token_vault_a: pool.token_vault_a,
+            token_vault_b: pool.token_vault_b,
+            instructions_sysvar: solana_sdk::sysvar::instructions::ID,
+            oracle: get_oracle_address(&pool_address)?.0,
+        }
+        .instruction(FlashBorrowInstructionArgs {
+            amount_a,
+            amount_b,
+            remaining_accounts_info: None,
+        }),
+    );
+
+    flash_loan_instructions.extend(instructions);
+
+    flash_loan_instructions.push(
+        FlashRepay {
+            whirlpool: pool_address,
+            token_program_a: mint_a_info.owner,
+            token_program_b: mint_b_info.owner,
+            memo_program: spl_memo::ID,
+            borrower: authority,
+            token_mint_a: pool.token_mint_a,
+            token_mint_b: pool.token_mint_b,
+            token_owner_account_a: *token_owner_account_a,
+            token_owner_account_b: *token_owner_account_b,
+            token_vault_a: pool.token_vault_a,
+            token_vault_b: pool.token_vault_b,
+            instructions_sysvar: solana_sdk::sysvar::instructions::ID,
+        }
+        .instruction(FlashRepayInstructionArgs {
+            amount_a,
+            amount_b,
+            remaining_accounts_info: None,
+        }),
+    );
+
+    flash_loan_instructions.extend(token_accounts.cleanup_instructions);
+
+    Ok(FlashLoanInstruction {
+        quote,
+        instructions: flash_loan_instructions,
+        additional_signers: token_accounts.additional_signers,
+    })
+}
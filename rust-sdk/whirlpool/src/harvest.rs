@@ -4,9 +4,7 @@ use std::{
     time::{SystemTime, UNIX_EPOCH},
 };
 
-use orca_whirlpools_client::{
-    get_position_address, get_tick_array_address, FixedTickArray, Position, TickArray, Whirlpool,
-};
+use orca_whirlpools_client::{get_position_address, get_tick_array_address, Position, Whirlpool};
 use orca_whirlpools_client::{
     CollectFeesV2, CollectFeesV2InstructionArgs, CollectRewardV2, CollectRewardV2InstructionArgs,
     UpdateFeesAndRewards,
@@ -20,6 +18,7 @@ use solana_sdk::{account::Account, instruction::Instruction, pubkey::Pubkey, sig
 use spl_associated_token_account::get_associated_token_address_with_program_id;
 
 use crate::{
+    tick_array::decode_tick_array,
     token::{get_current_transfer_fee, prepare_token_accounts_instructions, TokenAccountStrategy},
     FUNDER,
 };
@@ -182,9 +181,8 @@ pub async fn harvest_position_instructions(
     let lower_tick_array_info = tick_array_infos[0]
         .as_ref()
         .ok_or("Lower tick array info not found")?;
-    let lower_tick_array: FixedTickArray =
-        TickArray::from_bytes(&lower_tick_array_info.data)?.into();
-    let lower_tick = &lower_tick_array.ticks[get_tick_index_in_array(
+    let lower_tick_array = decode_tick_array(&lower_tick_array_info.data)?;
+    let lower_tick = lower_tick_array.ticks[get_tick_index_in_array(
         position.tick_lower_index,
         lower_tick_array_start_index,
         pool.tick_spacing,
@@ -193,9 +191,8 @@ pub async fn harvest_position_instructions(
     let upper_tick_array_info = tick_array_infos[1]
         .as_ref()
         .ok_or("Upper tick array info not found")?;
-    let upper_tick_array: FixedTickArray =
-        TickArray::from_bytes(&upper_tick_array_info.data)?.into();
-    let upper_tick = &upper_tick_array.ticks[get_tick_index_in_array(
+    let upper_tick_array = decode_tick_array(&upper_tick_array_info.data)?;
+    let upper_tick = upper_tick_array.ticks[get_tick_index_in_array(
         position.tick_upper_index,
         upper_tick_array_start_index,
         pool.tick_spacing,
@@ -204,8 +201,8 @@ pub async fn harvest_position_instructions(
     let fees_quote = collect_fees_quote(
         pool.clone().into(),
         position.clone().into(),
-        lower_tick.clone().into(),
-        upper_tick.clone().into(),
+        lower_tick,
+        upper_tick,
         transfer_fee_a,
         transfer_fee_b,
     )?;
@@ -214,8 +211,8 @@ pub async fn harvest_position_instructions(
     let rewards_quote = collect_rewards_quote(
         pool.clone().into(),
         position.clone().into(),
-        lower_tick.clone().into(),
-        upper_tick.clone().into(),
+        lower_tick,
+        upper_tick,
         unix_timestamp,
         get_current_transfer_fee(reward_infos[0].as_ref(), current_epoch),
         get_current_transfer_fee(reward_infos[1].as_ref(), current_epoch),
@@ -566,6 +563,7 @@ mod tests {
                 swap_input_mint,
                 SwapType::ExactIn,
                 Some(100), // 1% slippage
+                None,
                 Some(ctx.signer.pubkey()),
             )
             .await
@@ -2,9 +2,9 @@ use std::error::Error;
 use std::str::FromStr;
 
 use orca_whirlpools_client::{
-    get_position_address, get_tick_array_address, DynamicTickArray, InitializeDynamicTickArray,
-    InitializeDynamicTickArrayInstructionArgs, OpenPositionWithTokenExtensions,
-    OpenPositionWithTokenExtensionsInstructionArgs, Position, Whirlpool,
+    get_oracle_address, get_position_address, get_tick_array_address, AccountsType,
+    OpenPositionWithTokenExtensions, OpenPositionWithTokenExtensionsInstructionArgs, Position,
+    RemainingAccountsInfo, RemainingAccountsSlice, Whirlpool,
 };
 use orca_whirlpools_client::{IncreaseLiquidityV2, IncreaseLiquidityV2InstructionArgs};
 use orca_whirlpools_core::{
@@ -16,10 +16,16 @@ use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::account::Account;
 use solana_sdk::program_pack::Pack;
 use solana_sdk::signer::Signer;
-use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Keypair};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+};
 use spl_associated_token_account::get_associated_token_address_with_program_id;
 use spl_token_2022::state::Mint;
 
+use crate::pool::fetch_pool_paused;
+use crate::tick_array::initialize_tick_array_instruction;
 use crate::{get_rent, SPLASH_POOL_TICK_SPACING};
 use crate::{
     token::{get_current_transfer_fee, prepare_token_accounts_instructions, TokenAccountStrategy},
@@ -28,10 +34,20 @@ use crate::{
 
 // TODO: support transfer hooks
 
+// The oracle of the pool is passed so that the program can enforce the pause flags of the pool.
+fn oracle_remaining_accounts_info() -> RemainingAccountsInfo {
+    RemainingAccountsInfo {
+        slices: vec![RemainingAccountsSlice {
+            accounts_type: AccountsType::Oracle,
+            length: 1,
+        }],
+    }
+}
+
 fn get_increase_liquidity_quote(
     param: IncreaseLiquidityParam,
     slippage_tolerance_bps: u16,
-    pool: &Whirlpool,
+    sqrt_price: u128,
     tick_lower_index: i32,
     tick_upper_index: i32,
     transfer_fee_a: Option<TransferFee>,
@@ -41,7 +57,7 @@ fn get_increase_liquidity_quote(
         IncreaseLiquidityParam::TokenA(amount) => increase_liquidity_quote_a(
             amount,
             slippage_tolerance_bps,
-            pool.sqrt_price,
+            sqrt_price,
             tick_lower_index,
             tick_upper_index,
             transfer_fee_a,
@@ -50,7 +66,7 @@ fn get_increase_liquidity_quote(
         IncreaseLiquidityParam::TokenB(amount) => increase_liquidity_quote_b(
             amount,
             slippage_tolerance_bps,
-            pool.sqrt_price,
+            sqrt_price,
             tick_lower_index,
             tick_upper_index,
             transfer_fee_a,
@@ -59,7 +75,7 @@ fn get_increase_liquidity_quote(
         IncreaseLiquidityParam::Liquidity(amount) => increase_liquidity_quote(
             amount,
             slippage_tolerance_bps,
-            pool.sqrt_price,
+            sqrt_price,
             tick_lower_index,
             tick_upper_index,
             transfer_fee_a,
@@ -132,6 +148,7 @@ pub struct IncreaseLiquidityInstruction {
 /// This function will return an error if:
 /// - The `authority` account is invalid or missing.
 /// - The position or token mint accounts are not found or have invalid data.
+/// - The pool is paused.
 /// - Any RPC request to the blockchain fails.
 ///
 /// # Example
@@ -187,6 +204,9 @@ pub async fn increase_liquidity_instructions(
 
     let pool_info = rpc.get_account(&position.whirlpool).await?;
     let pool = Whirlpool::from_bytes(&pool_info.data)?;
+    if fetch_pool_paused(rpc, position.whirlpool, &pool).await? {
+        return Err("Pool is paused".into());
+    }
 
     let mint_infos = rpc
         .get_multiple_accounts(&[pool.token_mint_a, pool.token_mint_b, position_mint_address])
@@ -209,7 +229,7 @@ pub async fn increase_liquidity_instructions(
     let quote = get_increase_liquidity_quote(
         param,
         slippage_tolerance_bps,
-        &pool,
+        pool.sqrt_price,
         position.tick_lower_index,
         position.tick_upper_index,
         transfer_fee_a,
@@ -272,12 +292,18 @@ pub async fn increase_liquidity_instructions(
             tick_array_lower: lower_tick_array_address,
             tick_array_upper: upper_tick_array_address,
         }
-        .instruction(IncreaseLiquidityV2InstructionArgs {
-            liquidity_amount: quote.liquidity_delta,
-            token_max_a: quote.token_max_a,
-            token_max_b: quote.token_max_b,
-            remaining_accounts_info: None,
-        }),
+        .instruction_with_remaining_accounts(
+            IncreaseLiquidityV2InstructionArgs {
+                liquidity_amount: quote.liquidity_delta,
+                token_max_a: quote.token_max_a,
+                token_max_b: quote.token_max_b,
+                remaining_accounts_info: Some(oracle_remaining_accounts_info()),
+            },
+            &[AccountMeta::new_readonly(
+                get_oracle_address(&position.whirlpool)?.0,
+                false,
+            )],
+        ),
     );
 
     instructions.extend(token_accounts.cleanup_instructions);
@@ -313,10 +339,34 @@ pub struct OpenPositionInstruction {
     pub initialization_cost: u64,
 }
 
-async fn internal_open_position(
+/// The pool state needed to open a position. The pool does not have to exist on chain yet, which
+/// allows opening a position in the same transaction that creates the pool.
+pub(crate) struct OpenPositionPool {
+    pub tick_spacing: u16,
+    pub sqrt_price: u128,
+    pub token_mint_a: Pubkey,
+    pub token_mint_b: Pubkey,
+    pub token_vault_a: Pubkey,
+    pub token_vault_b: Pubkey,
+}
+
+impl From<&Whirlpool> for OpenPositionPool {
+    fn from(whirlpool: &Whirlpool) -> Self {
+        Self {
+            tick_spacing: whirlpool.tick_spacing,
+            sqrt_price: whirlpool.sqrt_price,
+            token_mint_a: whirlpool.token_mint_a,
+            token_mint_b: whirlpool.token_mint_b,
+            token_vault_a: whirlpool.token_vault_a,
+            token_vault_b: whirlpool.token_vault_b,
+        }
+    }
+}
+
+pub(crate) async fn internal_open_position(
     rpc: &RpcClient,
     pool_address: Pubkey,
-    whirlpool: Whirlpool,
+    whirlpool: OpenPositionPool,
     param: IncreaseLiquidityParam,
     lower_tick_index: i32,
     upper_tick_index: i32,
@@ -358,7 +408,7 @@ async fn internal_open_position(
     let quote = get_increase_liquidity_quote(
         param,
         slippage_tolerance_bps,
-        &whirlpool,
+        whirlpool.sqrt_price,
         lower_initializable_tick_index,
         upper_initializable_tick_index,
         transfer_fee_a,
@@ -397,35 +447,17 @@ async fn internal_open_position(
         .await?;
 
     if tick_array_infos[0].is_none() {
-        instructions.push(
-            InitializeDynamicTickArray {
-                whirlpool: pool_address,
-                funder,
-                tick_array: lower_tick_array_address,
-                system_program: solana_sdk::system_program::id(),
-            }
-            .instruction(InitializeDynamicTickArrayInstructionArgs {
-                start_tick_index: lower_tick_start_index,
-                idempotent: false,
-            }),
-        );
-        non_refundable_rent += rent.minimum_balance(DynamicTickArray::MIN_LEN);
+        let (instruction, cost) =
+            initialize_tick_array_instruction(pool_address, lower_tick_start_index, funder, &rent)?;
+        instructions.push(instruction);
+        non_refundable_rent += cost;
     }
 
     if tick_array_infos[1].is_none() && lower_tick_start_index != upper_tick_start_index {
-        instructions.push(
-            InitializeDynamicTickArray {
-                whirlpool: pool_address,
-                funder,
-                tick_array: upper_tick_array_address,
-                system_program: solana_sdk::system_program::id(),
-            }
-            .instruction(InitializeDynamicTickArrayInstructionArgs {
-                start_tick_index: upper_tick_start_index,
-                idempotent: false,
-            }),
-        );
-        non_refundable_rent += rent.minimum_balance(DynamicTickArray::MIN_LEN);
+        let (instruction, cost) =
+            initialize_tick_array_instruction(pool_address, upper_tick_start_index, funder, &rent)?;
+        instructions.push(instruction);
+        non_refundable_rent += cost;
     }
 
     let token_owner_account_a = token_accounts
@@ -475,12 +507,18 @@ async fn internal_open_position(
             tick_array_lower: lower_tick_array_address,
             tick_array_upper: upper_tick_array_address,
         }
-        .instruction(IncreaseLiquidityV2InstructionArgs {
-            liquidity_amount: quote.liquidity_delta,
-            token_max_a: quote.token_max_a,
-            token_max_b: quote.token_max_b,
-            remaining_accounts_info: None,
-        }),
+        .instruction_with_remaining_accounts(
+            IncreaseLiquidityV2InstructionArgs {
+                liquidity_amount: quote.liquidity_delta,
+                token_max_a: quote.token_max_a,
+                token_max_b: quote.token_max_b,
+                remaining_accounts_info: Some(oracle_remaining_accounts_info()),
+            },
+            &[AccountMeta::new_readonly(
+                get_oracle_address(&pool_address)?.0,
+                false,
+            )],
+        ),
     );
 
     instructions.extend(token_accounts.cleanup_instructions);
@@ -521,6 +559,7 @@ async fn internal_open_position(
 /// Returns an error if:
 /// - The funder account is invalid.
 /// - The pool or token mint accounts are not found or invalid.
+/// - The pool is paused.
 /// - Any RPC request fails.
 ///
 /// # Example
@@ -561,6 +600,9 @@ pub async fn open_full_range_position_instructions(
 ) -> Result<OpenPositionInstruction, Box<dyn Error>> {
     let whirlpool_info = rpc.get_account(&pool_address).await?;
     let whirlpool = Whirlpool::from_bytes(&whirlpool_info.data)?;
+    if fetch_pool_paused(rpc, pool_address, &whirlpool).await? {
+        return Err("Pool is paused".into());
+    }
     let tick_range = get_full_range_tick_indexes(whirlpool.tick_spacing);
     let mint_infos = rpc
         .get_multiple_accounts(&[whirlpool.token_mint_a, whirlpool.token_mint_b])
@@ -574,7 +616,7 @@ pub async fn open_full_range_position_instructions(
     internal_open_position(
         rpc,
         pool_address,
-        whirlpool,
+        OpenPositionPool::from(&whirlpool),
         param,
         tick_range.tick_lower_index,
         tick_range.tick_upper_index,
@@ -615,6 +657,7 @@ pub async fn open_full_range_position_instructions(
 /// Returns an error if:
 /// - The funder account is invalid.
 /// - The pool or token mint accounts are not found or invalid.
+/// - The pool is paused.
 /// - Any RPC request fails.
 /// - The pool is a Splash Pool, as they only support full-range positions.
 /// - The lower price is less or equal to 0.0.
@@ -670,6 +713,9 @@ pub async fn open_position_instructions(
     if whirlpool.tick_spacing == SPLASH_POOL_TICK_SPACING {
         return Err("Splash pools only support full range positions".into());
     }
+    if fetch_pool_paused(rpc, pool_address, &whirlpool).await? {
+        return Err("Pool is paused".into());
+    }
     let mint_infos = rpc
         .get_multiple_accounts(&[whirlpool.token_mint_a, whirlpool.token_mint_b])
         .await?;
@@ -691,7 +737,7 @@ pub async fn open_position_instructions(
     internal_open_position(
         rpc,
         pool_address,
-        whirlpool,
+        OpenPositionPool::from(&whirlpool),
         param,
         lower_tick_index,
         upper_tick_index,
@@ -1,12 +1,21 @@
 mod account;
+mod batch;
+mod compound;
+mod confidential;
 mod config;
 mod create_pool;
 mod decrease_liquidity;
+mod flash_loan;
 mod harvest;
 mod increase_liquidity;
 mod pool;
 mod position;
+mod position_delegate;
+mod protocol_fees;
+mod range_order;
+mod reward;
 mod swap;
+mod tick_array;
 mod token;
 mod utils;
 
@@ -17,12 +26,20 @@ mod e2e;
 mod tests;
 
 pub use account::*;
+pub use batch::*;
+pub use compound::*;
+pub use confidential::*;
 pub use config::*;
 pub use create_pool::*;
 pub use decrease_liquidity::*;
+pub use flash_loan::*;
 pub use harvest::*;
 pub use increase_liquidity::*;
 pub use pool::*;
 pub use position::*;
+pub use position_delegate::*;
+pub use protocol_fees::*;
+pub use range_order::*;
+pub use reward::*;
 pub use swap::*;
 pub use token::*;
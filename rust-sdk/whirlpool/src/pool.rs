@@ -1,13 +1,15 @@
 use std::error::Error;
 
 use orca_whirlpools_client::{
-    fetch_all_fee_tier_with_filter, get_fee_tier_address, get_whirlpool_address, FeeTier,
-    FeeTierFilter, Whirlpool, WhirlpoolsConfig,
+    fetch_all_fee_tier_with_filter, get_fee_tier_address, get_oracle_address,
+    get_whirlpool_address, get_whirlpools_config_extension_address, FeeRateSchedule, FeeTier,
+    FeeTierFilter, Oracle, Whirlpool, WhirlpoolsConfig, WhirlpoolsConfigExtension,
 };
 
 use orca_whirlpools_core::sqrt_price_to_price;
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_program::pubkey::Pubkey;
+use solana_sdk::account::Account;
 use solana_sdk::{program_error::ProgramError, program_pack::Pack};
 use spl_token::state::Mint;
 
@@ -53,6 +55,14 @@ pub struct InitializedPool {
 
     /// The current price in the pool.
     pub price: f64,
+
+    /// Whether the pool is paused by the emergency authority, either directly or through its
+    /// whirlpools_config. Swaps and liquidity increases are not possible while the pool is paused.
+    pub paused: bool,
+
+    /// The fee rate change scheduled by the fee authority, if any. Swaps apply it lazily, so
+    /// `data.fee_rate` is only updated by the first swap after the change starts.
+    pub fee_rate_schedule: Option<FeeRateSchedule>,
 }
 
 impl InitializedPool {
@@ -61,17 +71,63 @@ impl InitializedPool {
         whirlpool_address: Pubkey,
         mint_a: Mint,
         mint_b: Mint,
+        oracle_info: Option<&Account>,
+        whirlpools_config_extension_info: Option<&Account>,
     ) -> Result<Self, Box<dyn Error>> {
         let whirlpool = Whirlpool::from_bytes(bytes)?;
         let price = sqrt_price_to_price(whirlpool.sqrt_price, mint_a.decimals, mint_b.decimals);
+        let oracle = oracle_info
+            .map(|x| Oracle::from_bytes(&x.data))
+            .transpose()?;
+        let paused = is_pool_paused(oracle.as_ref(), whirlpools_config_extension_info)?;
+        let fee_rate_schedule = oracle
+            .map(|x| x.fee_rate_schedule)
+            .filter(|x| x.effective_timestamp != 0);
         Ok(InitializedPool {
             address: whirlpool_address,
             data: whirlpool,
             price,
+            paused,
+            fee_rate_schedule,
         })
     }
 }
 
+// A pool is paused if a pause flag is set in its oracle or if its whirlpools_config is paused.
+// The config pause is reported even if it has not been synced to the oracle of the pool yet.
+fn is_pool_paused(
+    oracle: Option<&Oracle>,
+    whirlpools_config_extension_info: Option<&Account>,
+) -> Result<bool, Box<dyn Error>> {
+    if let Some(whirlpools_config_extension_info) = whirlpools_config_extension_info {
+        let whirlpools_config_extension =
+            WhirlpoolsConfigExtension::from_bytes(&whirlpools_config_extension_info.data)?;
+        if whirlpools_config_extension.paused {
+            return Ok(true);
+        }
+    }
+    Ok(oracle.is_some_and(|x| x.pause_flags != 0))
+}
+
+// Fetches the oracle and whirlpools_config extension of a pool to check whether it is paused.
+pub(crate) async fn fetch_pool_paused(
+    rpc: &RpcClient,
+    whirlpool_address: Pubkey,
+    whirlpool: &Whirlpool,
+) -> Result<bool, Box<dyn Error>> {
+    let oracle_address = get_oracle_address(&whirlpool_address)?.0;
+    let whirlpools_config_extension_address =
+        get_whirlpools_config_extension_address(&whirlpool.whirlpools_config)?.0;
+    let account_infos = rpc
+        .get_multiple_accounts(&[oracle_address, whirlpools_config_extension_address])
+        .await?;
+    let oracle = account_infos[0]
+        .as_ref()
+        .map(|x| Oracle::from_bytes(&x.data))
+        .transpose()?;
+    is_pool_paused(oracle.as_ref(), account_infos[1].as_ref())
+}
+
 /// Represents information about a pool, either initialized or uninitialized.
 ///
 /// This enum provides a unified way to describe both initialized and uninitialized pools,
@@ -216,6 +272,8 @@ pub async fn fetch_concentrated_liquidity_pool(
             fee_tier_address.0,
             token_a,
             token_b,
+            get_oracle_address(&whirlpool_address)?.0,
+            get_whirlpools_config_extension_address(whirlpools_config_address)?.0,
         ])
         .await?;
 
@@ -241,8 +299,14 @@ pub async fn fetch_concentrated_liquidity_pool(
     let mint_b = Mint::unpack(&mint_b_info.data)?;
 
     if let Some(whirlpool_info) = &account_infos[0] {
-        let initialized_pool =
-            InitializedPool::from_bytes(&whirlpool_info.data, whirlpool_address, mint_a, mint_b)?;
+        let initialized_pool = InitializedPool::from_bytes(
+            &whirlpool_info.data,
+            whirlpool_address,
+            mint_a,
+            mint_b,
+            account_infos[5].as_ref(),
+            account_infos[6].as_ref(),
+        )?;
         Ok(PoolInfo::Initialized(initialized_pool))
     } else {
         Ok(PoolInfo::Uninitialized(UninitializedPool {
@@ -325,7 +389,12 @@ pub async fn fetch_whirlpools_by_token_pair(
     .await?;
 
     let account_infos = rpc
-        .get_multiple_accounts(&[*whirlpools_config_address, token_a, token_b])
+        .get_multiple_accounts(&[
+            *whirlpools_config_address,
+            token_a,
+            token_b,
+            get_whirlpools_config_extension_address(whirlpools_config_address)?.0,
+        ])
         .await?;
 
     let whirlpools_config_info = account_infos[0].as_ref().ok_or(format!(
@@ -353,7 +422,13 @@ pub async fn fetch_whirlpools_by_token_pair(
         .map(|x| x.map(|y| y.0))
         .collect::<Result<Vec<Pubkey>, ProgramError>>()?;
 
+    let oracle_addresses = whirlpool_addresses
+        .iter()
+        .map(|x| get_oracle_address(x).map(|y| y.0))
+        .collect::<Result<Vec<Pubkey>, ProgramError>>()?;
+
     let whirlpool_infos = rpc.get_multiple_accounts(&whirlpool_addresses).await?;
+    let oracle_infos = rpc.get_multiple_accounts(&oracle_addresses).await?;
 
     let mut whirlpools: Vec<PoolInfo> = Vec::new();
     for i in 0..whirlpool_addresses.len() {
@@ -362,8 +437,15 @@ pub async fn fetch_whirlpools_by_token_pair(
         let fee_tier = &fee_tiers[i];
 
         if let Some(pool_info) = pool_info {
-            let initialized_pool =
-                InitializedPool::from_bytes(&pool_info.data, pool_address, mint_a, mint_b)?;
+            let oracle_info = oracle_infos.get(i).and_then(|x| x.as_ref());
+            let initialized_pool = InitializedPool::from_bytes(
+                &pool_info.data,
+                pool_address,
+                mint_a,
+                mint_b,
+                oracle_info,
+                account_infos[3].as_ref(),
+            )?;
             whirlpools.push(PoolInfo::Initialized(initialized_pool));
         } else {
             whirlpools.push(PoolInfo::Uninitialized(UninitializedPool {
@@ -439,6 +521,8 @@ mod tests {
             assert_eq!(pool.data.token_mint_b, test_ctx.mint_b);
             assert_eq!(pool.data.fee_rate, 1000);
             assert_eq!(pool.data.protocol_fee_rate, 0);
+            assert!(!pool.paused);
+            assert!(pool.fee_rate_schedule.is_none());
         } else {
             panic!("Expected initialized pool");
         }
@@ -465,6 +549,7 @@ mod tests {
             assert_eq!(pool.data.token_mint_b, test_ctx.mint_b);
             assert_eq!(pool.data.fee_rate, 300);
             assert_eq!(pool.data.protocol_fee_rate, 0);
+            assert!(!pool.paused);
         } else {
             panic!("Expected initialized pool");
         }
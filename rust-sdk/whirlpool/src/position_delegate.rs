@@ -0,0 +1,242 @@
+use std::error::Error;
+
+use orca_whirlpools_client::{
+    get_position_address, get_position_delegate_address, RevokePositionDelegate,
+    SetPositionDelegate, SetPositionDelegateInstructionArgs,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+
+use crate::FUNDER;
+
+/// Allows the delegate to collect the fees of the position.
+pub const POSITION_DELEGATE_PERMISSION_COLLECT_FEES: u16 = 1 << 0;
+
+/// Allows the delegate to collect the rewards of the position.
+pub const POSITION_DELEGATE_PERMISSION_COLLECT_REWARD: u16 = 1 << 1;
+
+/// Allows the delegate to withdraw liquidity from the position.
+pub const POSITION_DELEGATE_PERMISSION_DECREASE_LIQUIDITY: u16 = 1 << 2;
+
+/// Allows the delegate to add liquidity to the position.
+pub const POSITION_DELEGATE_PERMISSION_INCREASE_LIQUIDITY: u16 = 1 << 3;
+
+/// Allows the delegate to reset the range of an empty position.
+pub const POSITION_DELEGATE_PERMISSION_RESET_POSITION_RANGE: u16 = 1 << 4;
+
+/// Allows the delegate to compound the fees and rewards of the position into its liquidity.
+pub const POSITION_DELEGATE_PERMISSION_COMPOUND_FEES: u16 = 1 << 5;
+
+/// Represents the instructions for delegating a position.
+#[derive(Debug)]
+pub struct SetPositionDelegateInstruction {
+    /// The address of the position delegate account.
+    pub position_delegate_address: Pubkey,
+
+    /// A vector of `Instruction` objects required to delegate the position.
+    pub instructions: Vec<Instruction>,
+}
+
+/// Represents the instructions for revoking a position delegation.
+#[derive(Debug)]
+pub struct RevokePositionDelegateInstruction {
+    /// A vector of `Instruction` objects required to revoke the delegation.
+    pub instructions: Vec<Instruction>,
+}
+
+/// Generates instructions to delegate a subset of the operations on a position to another key.
+///
+/// The delegate can then pass the position delegate account as a remaining account to the
+/// instructions covered by its permissions. Withdrawn tokens can only be sent to token accounts
+/// owned by the position owner, and the delegation becomes invalid once the position NFT changes
+/// owner.
+///
+/// # Arguments
+///
+/// * `rpc` - A reference to the Solana RPC client.
+/// * `position_mint_address` - The public key of the position NFT mint.
+/// * `delegate` - The public key that is allowed to manage the position.
+/// * `permissions` - A bitmask of `POSITION_DELEGATE_PERMISSION_*` values.
+/// * `authority` - An optional public key of the position owner. Defaults to the global funder if not provided.
+///
+/// # Returns
+///
+/// Returns a `Result` containing a `SetPositionDelegateInstruction` on success, which includes:
+/// * `position_delegate_address` - The address of the position delegate account.
+/// * `instructions` - A vector of `Instruction` objects required to delegate the position.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The authority account is invalid.
+/// - No permissions are provided.
+/// - The position mint account is not found.
+/// - Any RPC request fails.
+///
+/// # Example
+///
+/// ```rust
+/// use orca_whirlpools::{
+///     set_position_delegate_instructions, set_whirlpools_config_address,
+///     WhirlpoolsConfigInput, POSITION_DELEGATE_PERMISSION_COLLECT_FEES,
+///     POSITION_DELEGATE_PERMISSION_COLLECT_REWARD,
+/// };
+/// use solana_client::nonblocking::rpc_client::RpcClient;
+/// use solana_sdk::pubkey::Pubkey;
+/// use std::str::FromStr;
+/// use crate::utils::load_wallet;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     set_whirlpools_config_address(WhirlpoolsConfigInput::SolanaDevnet).unwrap();
+///     let rpc = RpcClient::new("https://api.devnet.solana.com".to_string());
+///     let wallet = load_wallet();
+///
+///     let position_mint_address =
+///         Pubkey::from_str("HqoV7Qv27REUtmd9UKSJGGmCRNx3531t33bDG1BUfo9K").unwrap();
+///     let delegate = Pubkey::from_str("2EtH4ZZStW8Ffh2CbbW4baekdtWgPLcBXfYQ6FRmMVsq").unwrap();
+///
+///     let result = set_position_delegate_instructions(
+///         &rpc,
+///         position_mint_address,
+///         delegate,
+///         POSITION_DELEGATE_PERMISSION_COLLECT_FEES | POSITION_DELEGATE_PERMISSION_COLLECT_REWARD,
+///         Some(wallet.pubkey()),
+///     )
+///     .await
+///     .unwrap();
+///
+///     println!("Position Delegate: {:?}", result.position_delegate_address);
+/// }
+/// ```
+pub async fn set_position_delegate_instructions(
+    rpc: &RpcClient,
+    position_mint_address: Pubkey,
+    delegate: Pubkey,
+    permissions: u16,
+    authority: Option<Pubkey>,
+) -> Result<SetPositionDelegateInstruction, Box<dyn Error>> {
+    let authority = authority.unwrap_or(*FUNDER.try_lock()?);
+    if authority == Pubkey::default() {
+        return Err("Authority must be provided".into());
+    }
+    if permissions == 0 {
+        return Err("At least one permission must be provided".into());
+    }
+
+    let position_address = get_position_address(&position_mint_address)?.0;
+    let position_delegate_address = get_position_delegate_address(&position_address)?.0;
+
+    let position_mint_info = rpc.get_account(&position_mint_address).await?;
+    let position_token_account_address = get_associated_token_address_with_program_id(
+        &authority,
+        &position_mint_address,
+        &position_mint_info.owner,
+    );
+
+    let instructions = vec![SetPositionDelegate {
+        funder: authority,
+        position_authority: authority,
+        position: position_address,
+        position_token_account: position_token_account_address,
+        position_delegate: position_delegate_address,
+        system_program: solana_sdk::system_program::id(),
+    }
+    .instruction(SetPositionDelegateInstructionArgs {
+        delegate,
+        permissions,
+    })];
+
+    Ok(SetPositionDelegateInstruction {
+        position_delegate_address,
+        instructions,
+    })
+}
+
+/// Generates instructions to revoke the delegation of a position.
+///
+/// The delegation can be revoked by the position owner or by the delegate itself. The rent of
+/// the position delegate account is returned to the authority.
+///
+/// # Arguments
+///
+/// * `rpc` - A reference to the Solana RPC client.
+/// * `position_mint_address` - The public key of the position NFT mint.
+/// * `authority` - An optional public key of the position owner or delegate. Defaults to the global funder if not provided.
+///
+/// # Returns
+///
+/// Returns a `Result` containing a `RevokePositionDelegateInstruction` on success, which includes:
+/// * `instructions` - A vector of `Instruction` objects required to revoke the delegation.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The authority account is invalid.
+/// - The position token account is not found.
+/// - Any RPC request fails.
+///
+/// # Example
+///
+/// ```rust
+/// use orca_whirlpools::{
+///     revoke_position_delegate_instructions, set_whirlpools_config_address,
+///     WhirlpoolsConfigInput,
+/// };
+/// use solana_client::nonblocking::rpc_client::RpcClient;
+/// use solana_sdk::pubkey::Pubkey;
+/// use std::str::FromStr;
+/// use crate::utils::load_wallet;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     set_whirlpools_config_address(WhirlpoolsConfigInput::SolanaDevnet).unwrap();
+///     let rpc = RpcClient::new("https://api.devnet.solana.com".to_string());
+///     let wallet = load_wallet();
+///
+///     let position_mint_address =
+///         Pubkey::from_str("HqoV7Qv27REUtmd9UKSJGGmCRNx3531t33bDG1BUfo9K").unwrap();
+///
+///     let result =
+///         revoke_position_delegate_instructions(&rpc, position_mint_address, Some(wallet.pubkey()))
+///             .await
+///             .unwrap();
+///
+///     println!("Instructions: {:?}", result.instructions);
+/// }
+/// ```
+pub async fn revoke_position_delegate_instructions(
+    rpc: &RpcClient,
+    position_mint_address: Pubkey,
+    authority: Option<Pubkey>,
+) -> Result<RevokePositionDelegateInstruction, Box<dyn Error>> {
+    let authority = authority.unwrap_or(*FUNDER.try_lock()?);
+    if authority == Pubkey::default() {
+        return Err("Authority must be provided".into());
+    }
+
+    let position_address = get_position_address(&position_mint_address)?.0;
+    let position_delegate_address = get_position_delegate_address(&position_address)?.0;
+
+    // The authority might be the delegate, so the position token account is looked up by mint.
+    let position_token_account_address = rpc
+        .get_token_largest_accounts(&position_mint_address)
+        .await?
+        .into_iter()
+        .find(|x| x.amount.amount == "1")
+        .ok_or("Position token account not found")?
+        .address
+        .parse::<Pubkey>()?;
+
+    let instructions = vec![RevokePositionDelegate {
+        position_authority: authority,
+        receiver: authority,
+        position: position_address,
+        position_token_account: position_token_account_address,
+        position_delegate: position_delegate_address,
+    }
+    .instruction()];
+
+    Ok(RevokePositionDelegateInstruction { instructions })
+}
@@ -0,0 +1,194 @@
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+};
+
+use orca_whirlpools_client::{
+    fetch_all_whirlpool_with_filter, get_whirlpools_config_extension_address, AccountsType,
+    RemainingAccountsInfo, RemainingAccountsSlice, SweepProtocolFees,
+    SweepProtocolFeesInstructionArgs, WhirlpoolFilter, WhirlpoolsConfigExtension,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use spl_associated_token_account::{
+    get_associated_token_address_with_program_id,
+    instruction::create_associated_token_account_idempotent,
+};
+
+use crate::{FUNDER, WHIRLPOOLS_CONFIG_ADDRESS};
+
+// TODO: support transfer hooks
+
+/// Represents the instructions for sweeping the protocol fees of all pools under a config.
+#[derive(Debug)]
+pub struct SweepProtocolFeesInstructions {
+    /// A vector of `Instruction` objects required to sweep the protocol fees.
+    ///
+    /// The recipient token accounts are created idempotently before the sweep instructions.
+    pub instructions: Vec<Instruction>,
+
+    /// The addresses of the pools that have protocol fees to sweep, in instruction order.
+    pub whirlpools: Vec<Pubkey>,
+}
+
+/// Generates instructions to sweep the protocol fees of every pool under a whirlpools config.
+///
+/// The protocol fees owed by each pool are distributed to the recipients configured on the
+/// config extension, weighted by their basis points. Sweeping is permissionless, the funder
+/// only pays for the recipient token accounts. Pools without any protocol fees owed are skipped.
+///
+/// # Arguments
+///
+/// * `rpc` - A reference to a Solana RPC client for fetching accounts.
+/// * `whirlpools_config` - An optional public key of the whirlpools config. Defaults to the global whirlpools config if not provided.
+/// * `funder` - An optional public key of the account paying for the recipient token accounts. Defaults to the global funder if not provided.
+///
+/// # Returns
+///
+/// A `Result` containing `SweepProtocolFeesInstructions` on success:
+/// * `instructions` - A vector of `Instruction` objects to create the recipient token accounts and sweep the protocol fees.
+/// * `whirlpools` - The addresses of the pools whose protocol fees are swept.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The funder account is invalid.
+/// - The config extension does not exist or has no protocol fee recipients.
+/// - Any RPC request fails.
+///
+/// # Example
+///
+/// ```rust
+/// use orca_whirlpools::{
+///     set_whirlpools_config_address, sweep_protocol_fees_instructions, WhirlpoolsConfigInput,
+/// };
+/// use solana_client::nonblocking::rpc_client::RpcClient;
+/// use solana_sdk::signer::{keypair::Keypair, Signer};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     set_whirlpools_config_address(WhirlpoolsConfigInput::SolanaDevnet).unwrap();
+///     let rpc = RpcClient::new("https://api.devnet.solana.com".to_string());
+///     let wallet = Keypair::new(); // CAUTION: This wallet is not persistent.
+///
+///     let result = sweep_protocol_fees_instructions(&rpc, None, Some(wallet.pubkey()))
+///         .await
+///         .unwrap();
+///
+///     println!("Pools to sweep: {:?}", result.whirlpools);
+///     println!("Number of Instructions: {}", result.instructions.len());
+/// }
+/// ```
+pub async fn sweep_protocol_fees_instructions(
+    rpc: &RpcClient,
+    whirlpools_config: Option<Pubkey>,
+    funder: Option<Pubkey>,
+) -> Result<SweepProtocolFeesInstructions, Box<dyn Error>> {
+    let whirlpools_config = whirlpools_config.unwrap_or(*WHIRLPOOLS_CONFIG_ADDRESS.try_lock()?);
+    let funder = funder.unwrap_or(*FUNDER.try_lock()?);
+    if funder == Pubkey::default() {
+        return Err("Funder must be provided".into());
+    }
+
+    let whirlpools_config_extension_address =
+        get_whirlpools_config_extension_address(&whirlpools_config)?.0;
+    let whirlpools_config_extension_info = rpc
+        .get_account(&whirlpools_config_extension_address)
+        .await?;
+    let whirlpools_config_extension =
+        WhirlpoolsConfigExtension::from_bytes(&whirlpools_config_extension_info.data)?;
+    let recipients: Vec<Pubkey> = whirlpools_config_extension
+        .protocol_fee_recipients
+        .iter()
+        .take_while(|x| x.weight_bps > 0)
+        .map(|x| x.owner)
+        .collect();
+    if recipients.is_empty() {
+        return Err("Config extension has no protocol fee recipients".into());
+    }
+
+    let whirlpools = fetch_all_whirlpool_with_filter(
+        rpc,
+        vec![WhirlpoolFilter::WhirlpoolConfig(whirlpools_config)],
+    )
+    .await?
+    .into_iter()
+    .filter(|x| x.data.protocol_fee_owed_a > 0 || x.data.protocol_fee_owed_b > 0)
+    .collect::<Vec<_>>();
+
+    let mut mints: Vec<Pubkey> = whirlpools
+        .iter()
+        .flat_map(|x| [x.data.token_mint_a, x.data.token_mint_b])
+        .collect();
+    mints.sort();
+    mints.dedup();
+    let mut token_programs: HashMap<Pubkey, Pubkey> = HashMap::new();
+    for chunk in mints.chunks(100) {
+        let mint_infos = rpc.get_multiple_accounts(chunk).await?;
+        for (mint, mint_info) in chunk.iter().zip(mint_infos) {
+            let mint_info = mint_info.ok_or(format!("Mint {} not found", mint))?;
+            token_programs.insert(*mint, mint_info.owner);
+        }
+    }
+
+    let mut instructions: Vec<Instruction> = Vec::new();
+    let mut created_token_accounts: HashSet<Pubkey> = HashSet::new();
+    for whirlpool in whirlpools.iter() {
+        let pool = &whirlpool.data;
+        let token_program_a = token_programs[&pool.token_mint_a];
+        let token_program_b = token_programs[&pool.token_mint_b];
+
+        let mut recipient_accounts: Vec<AccountMeta> = Vec::with_capacity(recipients.len() * 2);
+        for recipient in recipients.iter() {
+            for (mint, token_program) in [
+                (pool.token_mint_a, token_program_a),
+                (pool.token_mint_b, token_program_b),
+            ] {
+                let token_account =
+                    get_associated_token_address_with_program_id(recipient, &mint, &token_program);
+                if created_token_accounts.insert(token_account) {
+                    instructions.push(create_associated_token_account_idempotent(
+                        &funder,
+                        recipient,
+                        &mint,
+                        &token_program,
+                    ));
+                }
+                recipient_accounts.push(AccountMeta::new(token_account, false));
+            }
+        }
+
+        instructions.push(
+            SweepProtocolFees {
+                whirlpools_config_extension: whirlpools_config_extension_address,
+                whirlpool: whirlpool.address,
+                token_mint_a: pool.token_mint_a,
+                token_mint_b: pool.token_mint_b,
+                token_vault_a: pool.token_vault_a,
+                token_vault_b: pool.token_vault_b,
+                token_program_a,
+                token_program_b,
+                memo_program: spl_memo::ID,
+            }
+            .instruction_with_remaining_accounts(
+                SweepProtocolFeesInstructionArgs {
+                    remaining_accounts_info: Some(RemainingAccountsInfo {
+                        slices: vec![RemainingAccountsSlice {
+                            accounts_type: AccountsType::ProtocolFeeRecipients,
+                            length: recipient_accounts.len() as u8,
+                        }],
+                    }),
+                },
+                &recipient_accounts,
+            ),
+        );
+    }
+
+    Ok(SweepProtocolFeesInstructions {
+        instructions,
+        whirlpools: whirlpools.iter().map(|x| x.address).collect(),
+    })
+}
@@ -0,0 +1,457 @@
+use std::error::Error;
+
+use orca_whirlpools_client::{
+    get_oracle_address, get_position_address, get_range_order_address, get_tick_array_address,
+    OpenRangeOrder, Position, RangeOrder, SettleRangeOrder, SettleRangeOrderInstructionArgs,
+    Whirlpool,
+};
+use orca_whirlpools_core::{
+    decrease_liquidity_quote, get_tick_array_start_tick_index, price_to_tick_index,
+    range_order_quote, DecreaseLiquidityQuote, RangeOrderQuote,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Keypair};
+use spl_associated_token_account::{
+    get_associated_token_address_with_program_id,
+    instruction::create_associated_token_account_idempotent,
+};
+use spl_token_2022::extension::StateWithExtensions;
+use spl_token_2022::state::{Account as TokenAccount, Mint};
+
+use crate::increase_liquidity::{internal_open_position, OpenPositionPool};
+use crate::pool::fetch_pool_paused;
+use crate::token::get_current_transfer_fee;
+use crate::{IncreaseLiquidityParam, FUNDER, SPLASH_POOL_TICK_SPACING};
+
+// TODO: support transfer hooks
+
+/// Represents the instructions and quote for opening a range order.
+#[derive(Debug)]
+pub struct OpenRangeOrderInstruction {
+    /// The public key of the position NFT that represents ownership of the range order.
+    pub position_mint: Pubkey,
+
+    /// The computed quote for the range order, including:
+    /// - `liquidity_delta` - The liquidity deposited into the order range.
+    /// - `tick_lower_index` and `tick_upper_index` - The one tick-spacing wide order range.
+    /// - `token_max_in` - The maximum amount of the input token deposited.
+    /// - `token_est_out` - The estimated amount of the output token once the order is filled.
+    pub quote: RangeOrderQuote,
+
+    /// A vector of `Instruction` objects required to open the range order.
+    pub instructions: Vec<Instruction>,
+
+    /// A vector of `Keypair` objects representing additional signers required for the instructions.
+    pub additional_signers: Vec<Keypair>,
+
+    /// The cost of initializing the position, measured in lamports.
+    pub initialization_cost: u64,
+}
+
+/// Represents the instructions and quote for settling a filled range order.
+#[derive(Debug)]
+pub struct SettleRangeOrderInstruction {
+    /// The owner of the position token, who receives the withdrawn tokens.
+    pub position_owner: Pubkey,
+
+    /// The estimated token amounts withdrawn from the position.
+    pub quote: DecreaseLiquidityQuote,
+
+    /// A vector of `Instruction` objects required to settle the range order.
+    pub instructions: Vec<Instruction>,
+
+    /// A vector of `Keypair` objects representing additional signers required for the instructions.
+    pub additional_signers: Vec<Keypair>,
+}
+
+fn is_range_order_filled(
+    tick_current_index: i32,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    a_to_b: bool,
+) -> bool {
+    if a_to_b {
+        tick_current_index >= tick_upper_index
+    } else {
+        tick_current_index < tick_lower_index
+    }
+}
+
+/// Opens a range order in a liquidity pool.
+///
+/// A range order is a position exactly one tick spacing wide that sits entirely on one side of
+/// the current price. Once the price has moved through the range, the deposited token has been
+/// converted into the other token and anyone can settle the order, which withdraws the liquidity
+/// to the position owner.
+///
+/// # Arguments
+///
+/// * `rpc` - A reference to the Solana RPC client.
+/// * `pool_address` - The public key of the liquidity pool.
+/// * `price` - The price at which the order should start filling.
+/// * `a_to_b` - Whether the order sells token A for token B (above the current price) or token B for token A (below it).
+/// * `token_amount_in` - The amount of the input token to deposit.
+/// * `funder` - An optional public key of the funder account. Defaults to the global funder if not provided.
+///
+/// # Returns
+///
+/// Returns a `Result` containing an `OpenRangeOrderInstruction` on success, which includes:
+/// * `position_mint` - The mint address of the position NFT.
+/// * `quote` - The computed range order quote.
+/// * `instructions` - A vector of `Instruction` objects required for opening the range order.
+/// * `additional_signers` - A vector of `Keypair` objects for additional transaction signers.
+/// * `initialization_cost` - The cost of initializing the position, in lamports.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The funder account is invalid.
+/// - The pool is a Splash Pool, which only supports full range positions.
+/// - The pool is paused.
+/// - The order range is not entirely on the unfilled side of the current price.
+/// - The pool or token mint accounts are not found or invalid.
+/// - Any RPC request fails.
+///
+/// # Example
+///
+/// ```rust
+/// use orca_whirlpools::{
+///     open_range_order_instructions, set_whirlpools_config_address, WhirlpoolsConfigInput,
+/// };
+/// use solana_client::nonblocking::rpc_client::RpcClient;
+/// use solana_sdk::pubkey::Pubkey;
+/// use std::str::FromStr;
+/// use crate::utils::load_wallet;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     set_whirlpools_config_address(WhirlpoolsConfigInput::SolanaDevnet).unwrap();
+///     let rpc = RpcClient::new("https://api.devnet.solana.com".to_string());
+///     let wallet = load_wallet();
+///
+///     let whirlpool_address =
+///         Pubkey::from_str("3KBZiL2g8C7tiJ32hTv5v3KM7aK9htpqTw4cTXz1HvPt").unwrap();
+///
+///     let result = open_range_order_instructions(
+///         &rpc,
+///         whirlpool_address,
+///         0.015,
+///         true,
+///         1_000_000,
+///         Some(wallet.pubkey()),
+///     )
+///     .await
+///     .unwrap();
+///
+///     println!("Position Mint: {:?}", result.position_mint);
+///     println!("Estimated Output: {}", result.quote.token_est_out);
+/// }
+/// ```
+pub async fn open_range_order_instructions(
+    rpc: &RpcClient,
+    pool_address: Pubkey,
+    price: f64,
+    a_to_b: bool,
+    token_amount_in: u64,
+    funder: Option<Pubkey>,
+) -> Result<OpenRangeOrderInstruction, Box<dyn Error>> {
+    if price <= 0.0 {
+        return Err("Floating price must be greater than 0.0".into());
+    }
+    let funder = funder.unwrap_or(*FUNDER.try_lock()?);
+    if funder == Pubkey::default() {
+        return Err("Funder must be provided".into());
+    }
+
+    let whirlpool_info = rpc.get_account(&pool_address).await?;
+    let whirlpool = Whirlpool::from_bytes(&whirlpool_info.data)?;
+    if whirlpool.tick_spacing == SPLASH_POOL_TICK_SPACING {
+        return Err("Splash pools only support full range positions".into());
+    }
+    if fetch_pool_paused(rpc, pool_address, &whirlpool).await? {
+        return Err("Pool is paused".into());
+    }
+
+    let mint_infos = rpc
+        .get_multiple_accounts(&[whirlpool.token_mint_a, whirlpool.token_mint_b])
+        .await?;
+    let mint_a_info = mint_infos[0]
+        .as_ref()
+        .ok_or("Token A mint info not found")?;
+    let mint_a = Mint::unpack(&mint_a_info.data)?;
+    let mint_b_info = mint_infos[1]
+        .as_ref()
+        .ok_or("Token B mint info not found")?;
+    let mint_b = Mint::unpack(&mint_b_info.data)?;
+
+    let epoch = rpc.get_epoch_info().await?.epoch;
+    let transfer_fee_a = get_current_transfer_fee(Some(mint_a_info), epoch);
+    let transfer_fee_b = get_current_transfer_fee(Some(mint_b_info), epoch);
+
+    let tick_index = price_to_tick_index(price, mint_a.decimals, mint_b.decimals);
+    let quote = range_order_quote(
+        token_amount_in,
+        a_to_b,
+        tick_index,
+        whirlpool.tick_spacing,
+        whirlpool.sqrt_price.into(),
+        transfer_fee_a,
+        transfer_fee_b,
+    )?;
+
+    let open_position = internal_open_position(
+        rpc,
+        pool_address,
+        OpenPositionPool::from(&whirlpool),
+        IncreaseLiquidityParam::Liquidity(quote.liquidity_delta),
+        quote.tick_lower_index,
+        quote.tick_upper_index,
+        mint_a_info,
+        mint_b_info,
+        Some(0),
+        Some(funder),
+    )
+    .await?;
+
+    let position_mint = open_position.position_mint;
+    let position_address = get_position_address(&position_mint)?.0;
+    let position_token_account_address =
+        get_associated_token_address_with_program_id(&funder, &position_mint, &spl_token_2022::ID);
+
+    let mut instructions = open_position.instructions;
+    instructions.push(
+        OpenRangeOrder {
+            funder,
+            position_authority: funder,
+            position: position_address,
+            position_token_account: position_token_account_address,
+            whirlpool: pool_address,
+            oracle: get_oracle_address(&pool_address)?.0,
+            range_order: get_range_order_address(&position_address)?.0,
+            system_program: solana_sdk::system_program::id(),
+        }
+        .instruction(),
+    );
+
+    Ok(OpenRangeOrderInstruction {
+        position_mint,
+        quote,
+        instructions,
+        additional_signers: open_position.additional_signers,
+        initialization_cost: open_position.initialization_cost,
+    })
+}
+
+/// Settles a filled range order.
+///
+/// Settling withdraws all liquidity of the range order position to the owner of the position
+/// token and closes the range order account. Anyone can settle a filled range order, so this can
+/// be used by keepers as well as by the position owner. Fees and rewards stay in the position.
+///
+/// # Arguments
+///
+/// * `rpc` - A reference to the Solana RPC client.
+/// * `position_mint_address` - The public key of the NFT mint address representing the range order position.
+/// * `payer` - An optional public key of the account paying for the owner's token accounts. Defaults to the global funder if not provided.
+///
+/// # Returns
+///
+/// Returns a `Result` containing a `SettleRangeOrderInstruction` on success, which includes:
+/// * `position_owner` - The owner of the position token, who receives the withdrawn tokens.
+/// * `quote` - The estimated token amounts withdrawn from the position.
+/// * `instructions` - A vector of `Instruction` objects required for settling the range order.
+/// * `additional_signers` - A vector of `Keypair` objects for additional transaction signers.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The payer account is invalid.
+/// - The position has no range order or the range order is not filled yet.
+/// - The position, pool, or token mint accounts are not found or invalid.
+/// - Any RPC request fails.
+///
+/// # Example
+///
+/// ```rust
+/// use orca_whirlpools::{
+///     settle_range_order_instructions, set_whirlpools_config_address, WhirlpoolsConfigInput,
+/// };
+/// use solana_client::nonblocking::rpc_client::RpcClient;
+/// use solana_sdk::pubkey::Pubkey;
+/// use std::str::FromStr;
+/// use crate::utils::load_wallet;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     set_whirlpools_config_address(WhirlpoolsConfigInput::SolanaDevnet).unwrap();
+///     let rpc = RpcClient::new("https://api.devnet.solana.com".to_string());
+///     let wallet = load_wallet();
+///
+///     let position_mint_address =
+///         Pubkey::from_str("HqoV7Qv27REUtmd9UKSJGGmCRNx3531t33bDG1BUfo9K").unwrap();
+///
+///     let result =
+///         settle_range_order_instructions(&rpc, position_mint_address, Some(wallet.pubkey()))
+///             .await
+///             .unwrap();
+///
+///     println!("Position Owner: {:?}", result.position_owner);
+///     println!("Quote: {:?}", result.quote);
+/// }
+/// ```
+pub async fn settle_range_order_instructions(
+    rpc: &RpcClient,
+    position_mint_address: Pubkey,
+    payer: Option<Pubkey>,
+) -> Result<SettleRangeOrderInstruction, Box<dyn Error>> {
+    let payer = payer.unwrap_or(*FUNDER.try_lock()?);
+    if payer == Pubkey::default() {
+        return Err("Payer must be provided".into());
+    }
+
+    let position_address = get_position_address(&position_mint_address)?.0;
+    let range_order_address = get_range_order_address(&position_address)?.0;
+    let infos = rpc
+        .get_multiple_accounts(&[position_address, range_order_address])
+        .await?;
+    let position_info = infos[0].as_ref().ok_or("Position info not found")?;
+    let position = Position::from_bytes(&position_info.data)?;
+    let range_order_info = infos[1].as_ref().ok_or("Range order info not found")?;
+    let range_order = RangeOrder::from_bytes(&range_order_info.data)?;
+
+    let pool_info = rpc.get_account(&position.whirlpool).await?;
+    let pool = Whirlpool::from_bytes(&pool_info.data)?;
+
+    if !is_range_order_filled(
+        pool.tick_current_index,
+        range_order.tick_lower_index,
+        range_order.tick_upper_index,
+        range_order.a_to_b,
+    ) {
+        return Err("Range order is not filled".into());
+    }
+
+    let position_token_account_address = rpc
+        .get_token_largest_accounts(&position_mint_address)
+        .await?
+        .into_iter()
+        .find(|x| x.amount.amount == "1")
+        .ok_or("Position token account not found")?
+        .address
+        .parse::<Pubkey>()?;
+    let position_token_account_info = rpc.get_account(&position_token_account_address).await?;
+    let position_owner =
+        StateWithExtensions::<TokenAccount>::unpack(&position_token_account_info.data)?
+            .base
+            .owner;
+
+    let mint_infos = rpc
+        .get_multiple_accounts(&[pool.token_mint_a, pool.token_mint_b])
+        .await?;
+    let mint_a_info = mint_infos[0]
+        .as_ref()
+        .ok_or("Token A mint info not found")?;
+    let mint_b_info = mint_infos[1]
+        .as_ref()
+        .ok_or("Token B mint info not found")?;
+
+    let current_epoch = rpc.get_epoch_info().await?.epoch;
+    let transfer_fee_a = get_current_transfer_fee(Some(mint_a_info), current_epoch);
+    let transfer_fee_b = get_current_transfer_fee(Some(mint_b_info), current_epoch);
+
+    let quote = decrease_liquidity_quote(
+        position.liquidity.into(),
+        0,
+        pool.sqrt_price.into(),
+        position.tick_lower_index,
+        position.tick_upper_index,
+        transfer_fee_a,
+        transfer_fee_b,
+    )?;
+
+    let lower_tick_array_start_index =
+        get_tick_array_start_tick_index(position.tick_lower_index, pool.tick_spacing);
+    let upper_tick_array_start_index =
+        get_tick_array_start_tick_index(position.tick_upper_index, pool.tick_spacing);
+
+    let token_owner_account_a = get_associated_token_address_with_program_id(
+        &position_owner,
+        &pool.token_mint_a,
+        &mint_a_info.owner,
+    );
+    let token_owner_account_b = get_associated_token_address_with_program_id(
+        &position_owner,
+        &pool.token_mint_b,
+        &mint_b_info.owner,
+    );
+
+    let instructions = vec![
+        create_associated_token_account_idempotent(
+            &payer,
+            &position_owner,
+            &pool.token_mint_a,
+            &mint_a_info.owner,
+        ),
+        create_associated_token_account_idempotent(
+            &payer,
+            &position_owner,
+            &pool.token_mint_b,
+            &mint_b_info.owner,
+        ),
+        SettleRangeOrder {
+            whirlpool: position.whirlpool,
+            token_program_a: mint_a_info.owner,
+            token_program_b: mint_b_info.owner,
+            memo_program: spl_memo::ID,
+            position: position_address,
+            position_token_account: position_token_account_address,
+            position_owner,
+            range_order: range_order_address,
+            token_mint_a: pool.token_mint_a,
+            token_mint_b: pool.token_mint_b,
+            token_owner_account_a,
+            token_owner_account_b,
+            token_vault_a: pool.token_vault_a,
+            token_vault_b: pool.token_vault_b,
+            tick_array_lower: get_tick_array_address(
+                &position.whirlpool,
+                lower_tick_array_start_index,
+            )?
+            .0,
+            tick_array_upper: get_tick_array_address(
+                &position.whirlpool,
+                upper_tick_array_start_index,
+            )?
+            .0,
+        }
+        .instruction(SettleRangeOrderInstructionArgs {
+            remaining_accounts_info: None,
+        }),
+    ];
+
+    Ok(SettleRangeOrderInstruction {
+        position_owner,
+        quote,
+        instructions,
+        additional_signers: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_range_order_filled;
+
+    #[test]
+    fn test_is_range_order_filled() {
+        // Selling token A fills once the price reaches the upper tick
+        assert!(!is_range_order_filled(63, 64, 128, true));
+        assert!(!is_range_order_filled(127, 64, 128, true));
+        assert!(is_range_order_filled(128, 64, 128, true));
+
+        // Selling token B fills once the price drops below the lower tick
+        assert!(!is_range_order_filled(128, 64, 128, false));
+        assert!(!is_range_order_filled(64, 64, 128, false));
+        assert!(is_range_order_filled(63, 64, 128, false));
+    }
+}
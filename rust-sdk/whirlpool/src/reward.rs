@@ -0,0 +1,836 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use orca_whirlpools_client::{
+    get_tick_array_address, get_token_badge_address, InitializeRewardV2,
+    InitializeRewardV2InstructionArgs, SetRewardEmissionsV2, SetRewardEmissionsV2InstructionArgs,
+    Whirlpool, WhirlpoolRewardInfo,
+};
+use orca_whirlpools_core::{
+    collect_rewards_quote, get_tick_array_start_tick_index, get_tick_index_in_array,
+    reward_vault_runway_quote, try_get_emissions_amount, try_get_emissions_per_second_x64,
+    try_reverse_apply_transfer_fee, RewardVaultRunwayQuote, TickArrayFacade, TickFacade,
+    NUM_REWARDS, REWARD_VAULT_MIN_EMISSIONS_DURATION,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_program::rent::Rent;
+use solana_program::system_program;
+use solana_program::sysvar::SysvarId;
+use solana_sdk::{
+    account::Account, instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer,
+};
+use spl_token_2022::extension::StateWithExtensions;
+use spl_token_2022::instruction::transfer_checked;
+use spl_token_2022::state::{Account as TokenAccount, Mint};
+
+use crate::{
+    fetch_positions_in_whirlpool,
+    tick_array::decode_tick_array,
+    token::{get_current_transfer_fee, prepare_token_accounts_instructions, TokenAccountStrategy},
+    FUNDER,
+};
+
+// TODO: support transfer hooks
+
+/// Represents the instructions for initializing a reward on a pool.
+#[derive(Debug)]
+pub struct InitializeRewardInstructions {
+    /// A vector of `Instruction` objects required to initialize the reward.
+    pub instructions: Vec<Instruction>,
+
+    /// A vector of `Keypair` objects representing additional signers required for the instructions.
+    pub additional_signers: Vec<Keypair>,
+
+    /// The address of the newly created reward vault.
+    pub reward_vault: Pubkey,
+}
+
+/// Represents the instructions for depositing reward tokens into a reward vault.
+#[derive(Debug)]
+pub struct FundRewardInstructions {
+    /// A vector of `Instruction` objects required to fund the reward vault.
+    pub instructions: Vec<Instruction>,
+
+    /// A vector of `Keypair` objects representing additional signers required for the instructions.
+    pub additional_signers: Vec<Keypair>,
+
+    /// The amount transferred out of the funder's token account. This is higher than the amount
+    /// deposited into the vault if the reward token has a transfer fee.
+    pub transfer_amount: u64,
+}
+
+/// Represents the instructions for setting the emissions rate of a reward.
+#[derive(Debug)]
+pub struct SetRewardEmissionsInstructions {
+    /// A vector of `Instruction` objects required to set the emissions rate.
+    pub instructions: Vec<Instruction>,
+
+    /// A vector of `Keypair` objects representing additional signers required for the instructions.
+    pub additional_signers: Vec<Keypair>,
+}
+
+/// Represents the instructions and schedule for a reward emissions campaign.
+#[derive(Debug)]
+pub struct ScheduleRewardEmissionsInstructions {
+    /// A vector of `Instruction` objects required to initialize (if needed), fund and start the reward.
+    pub instructions: Vec<Instruction>,
+
+    /// A vector of `Keypair` objects representing additional signers required for the instructions.
+    pub additional_signers: Vec<Keypair>,
+
+    /// The address of the reward vault.
+    pub reward_vault: Pubkey,
+
+    /// The emissions per second as a x64 fixed-point number.
+    pub emissions_per_second_x64: u128,
+
+    /// The amount transferred out of the authority's token account, including transfer fees.
+    pub transfer_amount: u64,
+}
+
+/// Generates instructions to initialize a reward on a pool.
+///
+/// Rewards have to be initialized in order: the reward at `reward_index` can only be initialized
+/// once all rewards before it are initialized.
+///
+/// # Arguments
+///
+/// * `rpc` - A reference to a Solana RPC client for fetching accounts and pool data.
+/// * `whirlpool_address` - The public key of the pool.
+/// * `reward_index` - The index of the reward to initialize (0, 1 or 2).
+/// * `reward_mint` - The public key of the reward token mint.
+/// * `authority` - An optional public key of the reward authority. Defaults to the global funder if not provided.
+///
+/// # Returns
+///
+/// A `Result` containing `InitializeRewardInstructions` on success:
+///
+/// * `instructions` - A vector of `Instruction` objects required to initialize the reward.
+/// * `additional_signers` - A vector of `Keypair` objects representing additional signers required for the instructions.
+/// * `reward_vault` - The address of the newly created reward vault.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The `authority` is missing or is not the reward authority of the pool.
+/// - The reward index is invalid or the reward is already initialized.
+/// - The pool or reward mint accounts are not found or have invalid data.
+/// - Any RPC request to the blockchain fails.
+pub async fn initialize_reward_instructions(
+    rpc: &RpcClient,
+    whirlpool_address: Pubkey,
+    reward_index: u8,
+    reward_mint: Pubkey,
+    authority: Option<Pubkey>,
+) -> Result<InitializeRewardInstructions, Box<dyn Error>> {
+    let authority = get_reward_authority(authority)?;
+    let whirlpool_info = rpc.get_account(&whirlpool_address).await?;
+    let whirlpool = Whirlpool::from_bytes(&whirlpool_info.data)?;
+    let reward_mint_info = rpc.get_account(&reward_mint).await?;
+
+    let reward_vault = Keypair::new();
+    let instruction = initialize_reward_instruction(
+        whirlpool_address,
+        &whirlpool,
+        reward_index,
+        reward_mint,
+        &reward_mint_info,
+        reward_vault.pubkey(),
+        authority,
+    )?;
+
+    Ok(InitializeRewardInstructions {
+        instructions: vec![instruction],
+        reward_vault: reward_vault.pubkey(),
+        additional_signers: vec![reward_vault],
+    })
+}
+
+/// Generates instructions to deposit reward tokens into the vault of a reward.
+///
+/// If the reward token has a transfer fee, the transferred amount is increased so that the
+/// vault receives exactly `amount`.
+///
+/// # Arguments
+///
+/// * `rpc` - A reference to a Solana RPC client for fetching accounts and pool data.
+/// * `whirlpool_address` - The public key of the pool.
+/// * `reward_index` - The index of the reward to fund (0, 1 or 2).
+/// * `amount` - The amount of reward tokens the vault should receive.
+/// * `funder` - An optional public key of the account funding the vault. Defaults to the global funder if not provided.
+///
+/// # Returns
+///
+/// A `Result` containing `FundRewardInstructions` on success:
+///
+/// * `instructions` - A vector of `Instruction` objects required to fund the reward vault.
+/// * `additional_signers` - A vector of `Keypair` objects representing additional signers required for the instructions.
+/// * `transfer_amount` - The amount transferred out of the funder's token account, including transfer fees.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The `funder` is missing or holds fewer tokens than `transfer_amount`.
+/// - The reward is not initialized.
+/// - Any RPC request to the blockchain fails.
+pub async fn fund_reward_instructions(
+    rpc: &RpcClient,
+    whirlpool_address: Pubkey,
+    reward_index: u8,
+    amount: u64,
+    funder: Option<Pubkey>,
+) -> Result<FundRewardInstructions, Box<dyn Error>> {
+    let funder = funder.unwrap_or(*FUNDER.try_lock()?);
+    if funder == Pubkey::default() {
+        return Err("Funder must be provided".into());
+    }
+
+    let whirlpool_info = rpc.get_account(&whirlpool_address).await?;
+    let whirlpool = Whirlpool::from_bytes(&whirlpool_info.data)?;
+    let reward_info = get_initialized_reward_info(&whirlpool, reward_index)?;
+    let reward_mint_info = rpc.get_account(&reward_info.mint).await?;
+
+    fund_reward_vault_instructions(
+        rpc,
+        reward_info.mint,
+        &reward_mint_info,
+        reward_info.vault,
+        amount,
+        funder,
+    )
+    .await
+}
+
+/// Generates instructions to set the emissions rate of a reward.
+///
+/// The program requires the reward vault to hold at least one day of emissions when the
+/// emissions rate is set.
+///
+/// # Arguments
+///
+/// * `rpc` - A reference to a Solana RPC client for fetching accounts and pool data.
+/// * `whirlpool_address` - The public key of the pool.
+/// * `reward_index` - The index of the reward (0, 1 or 2).
+/// * `emissions_per_second_x64` - The emissions per second as a x64 fixed-point number.
+/// * `authority` - An optional public key of the reward authority. Defaults to the global funder if not provided.
+///
+/// # Returns
+///
+/// A `Result` containing `SetRewardEmissionsInstructions` on success.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The `authority` is missing or is not the reward authority of the pool.
+/// - The reward is not initialized.
+/// - The reward vault holds less than one day of emissions.
+/// - Any RPC request to the blockchain fails.
+pub async fn set_reward_emissions_instructions(
+    rpc: &RpcClient,
+    whirlpool_address: Pubkey,
+    reward_index: u8,
+    emissions_per_second_x64: u128,
+    authority: Option<Pubkey>,
+) -> Result<SetRewardEmissionsInstructions, Box<dyn Error>> {
+    let authority = get_reward_authority(authority)?;
+    let whirlpool_info = rpc.get_account(&whirlpool_address).await?;
+    let whirlpool = Whirlpool::from_bytes(&whirlpool_info.data)?;
+    let reward_info = get_initialized_reward_info(&whirlpool, reward_index)?;
+
+    let vault_info = rpc.get_account(&reward_info.vault).await?;
+    let vault_balance = StateWithExtensions::<TokenAccount>::unpack(&vault_info.data)?
+        .base
+        .amount;
+    let emissions_per_day = try_get_emissions_amount(
+        emissions_per_second_x64,
+        REWARD_VAULT_MIN_EMISSIONS_DURATION,
+    )?;
+    if vault_balance < emissions_per_day {
+        return Err(format!(
+            "Reward vault holds {} tokens but needs at least {} (one day of emissions)",
+            vault_balance, emissions_per_day
+        )
+        .into());
+    }
+
+    let instruction = set_reward_emissions_instruction(
+        whirlpool_address,
+        &whirlpool,
+        reward_index,
+        reward_info.vault,
+        emissions_per_second_x64,
+        authority,
+    )?;
+
+    Ok(SetRewardEmissionsInstructions {
+        instructions: vec![instruction],
+        additional_signers: Vec::new(),
+    })
+}
+
+/// Generates instructions to distribute an amount of reward tokens over a duration.
+///
+/// The reward is initialized if needed, the vault is funded with `amount` (plus any transfer fee)
+/// and the emissions rate is set so that `amount` is emitted over `duration` seconds. Tokens
+/// already in the vault are not taken into account and are emitted after the campaign ends.
+///
+/// # Arguments
+///
+/// * `rpc` - A reference to a Solana RPC client for fetching accounts and pool data.
+/// * `whirlpool_address` - The public key of the pool.
+/// * `reward_index` - The index of the reward (0, 1 or 2).
+/// * `reward_mint` - The public key of the reward token mint.
+/// * `amount` - The amount of reward tokens to distribute.
+/// * `duration` - The duration of the campaign in seconds. Must be at least one day.
+/// * `authority` - An optional public key of the reward authority, which also funds the vault. Defaults to the global funder if not provided.
+///
+/// # Returns
+///
+/// A `Result` containing `ScheduleRewardEmissionsInstructions` on success:
+///
+/// * `instructions` - A vector of `Instruction` objects required to start the campaign.
+/// * `additional_signers` - A vector of `Keypair` objects representing additional signers required for the instructions.
+/// * `reward_vault` - The address of the reward vault.
+/// * `emissions_per_second_x64` - The emissions rate of the campaign.
+/// * `transfer_amount` - The amount transferred out of the authority's token account, including transfer fees.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The `authority` is missing, is not the reward authority of the pool, or holds too few tokens.
+/// - The reward is initialized with a different mint.
+/// - The duration is shorter than one day.
+/// - Any RPC request to the blockchain fails.
+///
+/// # Example
+///
+/// ```rust
+/// use orca_whirlpools::{
+///     schedule_reward_emissions_instructions, set_whirlpools_config_address,
+///     WhirlpoolsConfigInput,
+/// };
+/// use solana_client::nonblocking::rpc_client::RpcClient;
+/// use solana_sdk::pubkey::Pubkey;
+/// use std::str::FromStr;
+/// use crate::utils::load_wallet;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     set_whirlpools_config_address(WhirlpoolsConfigInput::SolanaDevnet).unwrap();
+///     let rpc = RpcClient::new("https://api.devnet.solana.com".to_string());
+///     let wallet = load_wallet();
+///     let whirlpool_address =
+///         Pubkey::from_str("3KBZiL2g8C7tiJ32hTv5v3KM7aK9htpqTw4cTXz1HvPt").unwrap();
+///     let reward_mint = Pubkey::from_str("BRjpCHtyQLNCo8gqRUr8jtdAj5AjPYQaoqbvcZiHok1k").unwrap();
+///
+///     // Distribute 1,000 tokens (6 decimals) over 30 days
+///     let result = schedule_reward_emissions_instructions(
+///         &rpc,
+///         whirlpool_address,
+///         0,
+///         reward_mint,
+///         1_000_000_000,
+///         30 * 24 * 60 * 60,
+///         Some(wallet.pubkey()),
+///     )
+///     .await
+///     .unwrap();
+///
+///     println!("Emissions per second (x64): {}", result.emissions_per_second_x64);
+///     println!("Number of Instructions: {}", result.instructions.len());
+/// }
+/// ```
+pub async fn schedule_reward_emissions_instructions(
+    rpc: &RpcClient,
+    whirlpool_address: Pubkey,
+    reward_index: u8,
+    reward_mint: Pubkey,
+    amount: u64,
+    duration: u64,
+    authority: Option<Pubkey>,
+) -> Result<ScheduleRewardEmissionsInstructions, Box<dyn Error>> {
+    let authority = get_reward_authority(authority)?;
+    if duration < REWARD_VAULT_MIN_EMISSIONS_DURATION {
+        return Err(format!(
+            "Duration must be at least {} seconds",
+            REWARD_VAULT_MIN_EMISSIONS_DURATION
+        )
+        .into());
+    }
+    let emissions_per_second_x64 = try_get_emissions_per_second_x64(amount, duration)?;
+
+    let whirlpool_info = rpc.get_account(&whirlpool_address).await?;
+    let whirlpool = Whirlpool::from_bytes(&whirlpool_info.data)?;
+    let reward_mint_info = rpc.get_account(&reward_mint).await?;
+
+    let mut instructions: Vec<Instruction> = Vec::new();
+    let mut additional_signers: Vec<Keypair> = Vec::new();
+
+    let current_reward_mint = whirlpool
+        .reward_infos
+        .get(reward_index as usize)
+        .ok_or("Invalid reward index")?
+        .mint;
+    let reward_vault = if current_reward_mint == Pubkey::default() {
+        let reward_vault = Keypair::new();
+        instructions.push(initialize_reward_instruction(
+            whirlpool_address,
+            &whirlpool,
+            reward_index,
+            reward_mint,
+            &reward_mint_info,
+            reward_vault.pubkey(),
+            authority,
+        )?);
+        let reward_vault_address = reward_vault.pubkey();
+        additional_signers.push(reward_vault);
+        reward_vault_address
+    } else if current_reward_mint == reward_mint {
+        whirlpool.reward_infos[reward_index as usize].vault
+    } else {
+        return Err(format!(
+            "Reward {} is already initialized with mint {}",
+            reward_index, current_reward_mint
+        )
+        .into());
+    };
+
+    let fund_instructions = fund_reward_vault_instructions(
+        rpc,
+        reward_mint,
+        &reward_mint_info,
+        reward_vault,
+        amount,
+        authority,
+    )
+    .await?;
+    instructions.extend(fund_instructions.instructions);
+    additional_signers.extend(fund_instructions.additional_signers);
+
+    instructions.push(set_reward_emissions_instruction(
+        whirlpool_address,
+        &whirlpool,
+        reward_index,
+        reward_vault,
+        emissions_per_second_x64,
+        authority,
+    )?);
+
+    Ok(ScheduleRewardEmissionsInstructions {
+        instructions,
+        additional_signers,
+        reward_vault,
+        emissions_per_second_x64,
+        transfer_amount: fund_instructions.transfer_amount,
+    })
+}
+
+/// Projects when the vault of a reward runs dry.
+///
+/// The vault has to cover the rewards owed to all positions in the pool as well as future
+/// emissions. This function fetches every position in the pool to sum up the rewards owed.
+///
+/// # Arguments
+///
+/// * `rpc` - A reference to a Solana RPC client for fetching accounts and pool data.
+/// * `whirlpool_address` - The public key of the pool.
+/// * `reward_index` - The index of the reward (0, 1 or 2).
+///
+/// # Returns
+///
+/// A `Result` containing `RewardVaultRunwayQuote` on success:
+///
+/// * `growth_global_x64` - The global reward growth at the current time.
+/// * `rewards_outstanding` - The rewards owed to positions that have not been collected yet.
+/// * `vault_surplus` - The vault balance left for future emissions.
+/// * `depletion_timestamp` - The earliest timestamp at which the vault runs dry, `u64::MAX` if the reward has no emissions.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The reward is not initialized.
+/// - Any RPC request to the blockchain fails.
+pub async fn fetch_reward_vault_runway(
+    rpc: &RpcClient,
+    whirlpool_address: Pubkey,
+    reward_index: u8,
+) -> Result<RewardVaultRunwayQuote, Box<dyn Error>> {
+    let whirlpool_info = rpc.get_account(&whirlpool_address).await?;
+    let whirlpool = Whirlpool::from_bytes(&whirlpool_info.data)?;
+    let reward_info = get_initialized_reward_info(&whirlpool, reward_index)?;
+
+    let vault_info = rpc.get_account(&reward_info.vault).await?;
+    let vault_balance = StateWithExtensions::<TokenAccount>::unpack(&vault_info.data)?
+        .base
+        .amount;
+
+    let positions = fetch_positions_in_whirlpool(rpc, whirlpool_address).await?;
+
+    let mut tick_array_addresses: Vec<Pubkey> = Vec::new();
+    for position in &positions {
+        for tick_index in [
+            position.data.tick_lower_index,
+            position.data.tick_upper_index,
+        ] {
+            let start_tick_index =
+                get_tick_array_start_tick_index(tick_index, whirlpool.tick_spacing);
+            let address = get_tick_array_address(&whirlpool_address, start_tick_index)?.0;
+            if !tick_array_addresses.contains(&address) {
+                tick_array_addresses.push(address);
+            }
+        }
+    }
+
+    let mut tick_arrays: HashMap<Pubkey, TickArrayFacade> = HashMap::new();
+    for addresses in tick_array_addresses.chunks(100) {
+        let infos = rpc.get_multiple_accounts(addresses).await?;
+        for (address, info) in addresses.iter().zip(infos) {
+            let info = info.ok_or(format!("Tick array {} not found", address))?;
+            tick_arrays.insert(*address, decode_tick_array(&info.data)?);
+        }
+    }
+
+    // Rewards owed at the last reward update, the core quote adds the emissions since then.
+    let mut rewards_owed: u64 = 0;
+    for position in &positions {
+        let lower_tick = get_tick(
+            &tick_arrays,
+            whirlpool_address,
+            &whirlpool,
+            position.data.tick_lower_index,
+        )?;
+        let upper_tick = get_tick(
+            &tick_arrays,
+            whirlpool_address,
+            &whirlpool,
+            position.data.tick_upper_index,
+        )?;
+
+        let quote = collect_rewards_quote(
+            whirlpool.clone().into(),
+            position.data.clone().into(),
+            lower_tick,
+            upper_tick,
+            whirlpool.reward_last_updated_timestamp,
+            None,
+            None,
+            None,
+        )?;
+        rewards_owed =
+            rewards_owed.saturating_add(quote.rewards[reward_index as usize].rewards_owed);
+    }
+
+    let current_timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let quote = reward_vault_runway_quote(
+        whirlpool.into(),
+        reward_index,
+        vault_balance,
+        rewards_owed,
+        current_timestamp,
+    )?;
+    Ok(quote)
+}
+
+fn get_tick(
+    tick_arrays: &HashMap<Pubkey, TickArrayFacade>,
+    whirlpool_address: Pubkey,
+    whirlpool: &Whirlpool,
+    tick_index: i32,
+) -> Result<TickFacade, Box<dyn Error>> {
+    let start_tick_index = get_tick_array_start_tick_index(tick_index, whirlpool.tick_spacing);
+    let address = get_tick_array_address(&whirlpool_address, start_tick_index)?.0;
+    let tick_array = tick_arrays
+        .get(&address)
+        .ok_or(format!("Tick array {} not found", address))?;
+    let index = get_tick_index_in_array(tick_index, start_tick_index, whirlpool.tick_spacing)?;
+    Ok(tick_array.ticks[index as usize])
+}
+
+fn get_reward_authority(authority: Option<Pubkey>) -> Result<Pubkey, Box<dyn Error>> {
+    let authority = authority.unwrap_or(*FUNDER.try_lock()?);
+    if authority == Pubkey::default() {
+        return Err("Authority must be provided".into());
+    }
+    Ok(authority)
+}
+
+fn get_initialized_reward_info(
+    whirlpool: &Whirlpool,
+    reward_index: u8,
+) -> Result<&WhirlpoolRewardInfo, Box<dyn Error>> {
+    let reward_info = whirlpool
+        .reward_infos
+        .get(reward_index as usize)
+        .ok_or("Invalid reward index")?;
+    if reward_info.mint == Pubkey::default() {
+        return Err(format!("Reward {} is not initialized", reward_index).into());
+    }
+    Ok(reward_info)
+}
+
+fn initialize_reward_instruction(
+    whirlpool_address: Pubkey,
+    whirlpool: &Whirlpool,
+    reward_index: u8,
+    reward_mint: Pubkey,
+    reward_mint_info: &Account,
+    reward_vault: Pubkey,
+    authority: Pubkey,
+) -> Result<Instruction, Box<dyn Error>> {
+    let next_reward_index = whirlpool
+        .reward_infos
+        .iter()
+        .position(|x| x.mint == Pubkey::default())
+        .unwrap_or(NUM_REWARDS);
+    if reward_index as usize != next_reward_index {
+        return Err(format!(
+            "Reward {} cannot be initialized, the next reward to initialize is {}",
+            reward_index, next_reward_index
+        )
+        .into());
+    }
+
+    let reward_authority = whirlpool.reward_infos[reward_index as usize].authority;
+    if reward_authority != authority {
+        return Err(format!("Reward authority is {}", reward_authority).into());
+    }
+
+    let reward_token_badge = get_token_badge_address(&whirlpool.whirlpools_config, &reward_mint)?.0;
+
+    Ok(InitializeRewardV2 {
+        reward_authority: authority,
+        funder: authority,
+        whirlpool: whirlpool_address,
+        reward_mint,
+        reward_token_badge,
+        reward_vault,
+        reward_token_program: reward_mint_info.owner,
+        system_program: system_program::id(),
+        rent: Rent::id(),
+    }
+    .instruction(InitializeRewardV2InstructionArgs { reward_index }))
+}
+
+fn set_reward_emissions_instruction(
+    whirlpool_address: Pubkey,
+    whirlpool: &Whirlpool,
+    reward_index: u8,
+    reward_vault: Pubkey,
+    emissions_per_second_x64: u128,
+    authority: Pubkey,
+) -> Result<Instruction, Box<dyn Error>> {
+    let reward_authority = whirlpool.reward_infos[reward_index as usize].authority;
+    if reward_authority != authority {
+        return Err(format!("Reward authority is {}", reward_authority).into());
+    }
+
+    Ok(SetRewardEmissionsV2 {
+        whirlpool: whirlpool_address,
+        reward_authority: authority,
+        reward_vault,
+    }
+    .instruction(SetRewardEmissionsV2InstructionArgs {
+        reward_index,
+        emissions_per_second_x64,
+    }))
+}
+
+async fn fund_reward_vault_instructions(
+    rpc: &RpcClient,
+    reward_mint: Pubkey,
+    reward_mint_info: &Account,
+    reward_vault: Pubkey,
+    amount: u64,
+    funder: Pubkey,
+) -> Result<FundRewardInstructions, Box<dyn Error>> {
+    let current_epoch = rpc.get_epoch_info().await?.epoch;
+    let transfer_fee = get_current_transfer_fee(Some(reward_mint_info), current_epoch);
+    let transfer_amount = try_reverse_apply_transfer_fee(amount, transfer_fee.unwrap_or_default())?;
+    let decimals = StateWithExtensions::<Mint>::unpack(&reward_mint_info.data)?
+        .base
+        .decimals;
+
+    let token_accounts = prepare_token_accounts_instructions(
+        rpc,
+        funder,
+        vec![TokenAccountStrategy::WithBalance(
+            reward_mint,
+            transfer_amount,
+        )],
+    )
+    .await?;
+    let source = token_accounts
+        .token_account_addresses
+        .get(&reward_mint)
+        .ok_or("Reward token account not found")?;
+
+    let mut instructions: Vec<Instruction> = Vec::new();
+    instructions.extend(token_accounts.create_instructions);
+    instructions.push(transfer_checked(
+        &reward_mint_info.owner,
+        source,
+        &reward_mint,
+        &reward_vault,
+        &funder,
+        &[],
+        transfer_amount,
+        decimals,
+    )?);
+    instructions.extend(token_accounts.cleanup_instructions);
+
+    Ok(FundRewardInstructions {
+        instructions,
+        additional_signers: token_accounts.additional_signers,
+        transfer_amount,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use orca_whirlpools_client::Whirlpool;
+    use serial_test::serial;
+    use solana_program_test::tokio;
+    use solana_sdk::{pubkey::Pubkey, signer::keypair::Keypair, signer::Signer};
+    use spl_token_2022::{extension::StateWithExtensions, state::Account as TokenAccount};
+
+    use crate::{
+        schedule_reward_emissions_instructions, set_reward_emissions_instructions,
+        tests::{
+            setup_ata_te, setup_ata_with_amount, setup_mint_te_fee, setup_mint_with_decimals,
+            setup_whirlpool, RpcContext, SetupAtaConfig,
+        },
+    };
+
+    const DAY: u64 = 24 * 60 * 60;
+
+    async fn get_token_balance(ctx: &RpcContext, address: Pubkey) -> Result<u64, Box<dyn Error>> {
+        let account = ctx.rpc.get_account(&address).await?;
+        Ok(StateWithExtensions::<TokenAccount>::unpack(&account.data)?
+            .base
+            .amount)
+    }
+
+    async fn setup_pool(ctx: &RpcContext) -> Result<Pubkey, Box<dyn Error>> {
+        let mint_a = setup_mint_with_decimals(ctx, 9).await?;
+        let mint_b = setup_mint_with_decimals(ctx, 9).await?;
+        setup_whirlpool(ctx, mint_a, mint_b, 64).await
+    }
+
+    async fn schedule_and_verify(
+        ctx: &RpcContext,
+        whirlpool_address: Pubkey,
+        reward_mint: Pubkey,
+        amount: u64,
+    ) -> Result<u64, Box<dyn Error>> {
+        let result = schedule_reward_emissions_instructions(
+            &ctx.rpc,
+            whirlpool_address,
+            0,
+            reward_mint,
+            amount,
+            10 * DAY,
+            Some(ctx.signer.pubkey()),
+        )
+        .await?;
+        let signers: Vec<&Keypair> = result.additional_signers.iter().collect();
+        ctx.send_transaction_with_signers(result.instructions, signers)
+            .await?;
+
+        let whirlpool_info = ctx.rpc.get_account(&whirlpool_address).await?;
+        let whirlpool = Whirlpool::from_bytes(&whirlpool_info.data)?;
+        assert_eq!(whirlpool.reward_infos[0].mint, reward_mint);
+        assert_eq!(whirlpool.reward_infos[0].vault, result.reward_vault);
+        assert_eq!(
+            whirlpool.reward_infos[0].emissions_per_second_x64,
+            result.emissions_per_second_x64
+        );
+        assert_eq!(
+            result.emissions_per_second_x64,
+            ((amount as u128) << 64) / (10 * DAY as u128)
+        );
+        assert_eq!(get_token_balance(ctx, result.reward_vault).await?, amount);
+        Ok(result.transfer_amount)
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_schedule_reward_emissions() -> Result<(), Box<dyn Error>> {
+        let ctx = RpcContext::new().await;
+        let whirlpool = setup_pool(&ctx).await?;
+        let reward_mint = setup_mint_with_decimals(&ctx, 6).await?;
+        setup_ata_with_amount(&ctx, reward_mint, 1_000_000_000).await?;
+
+        let transfer_amount =
+            schedule_and_verify(&ctx, whirlpool, reward_mint, 100_000_000).await?;
+        assert_eq!(transfer_amount, 100_000_000);
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_schedule_reward_emissions_with_transfer_fee() -> Result<(), Box<dyn Error>> {
+        let ctx = RpcContext::new().await;
+        let whirlpool = setup_pool(&ctx).await?;
+        let reward_mint = setup_mint_te_fee(&ctx).await?;
+        setup_ata_te(
+            &ctx,
+            reward_mint,
+            Some(SetupAtaConfig {
+                amount: Some(1_000_000_000),
+            }),
+        )
+        .await?;
+
+        let transfer_amount =
+            schedule_and_verify(&ctx, whirlpool, reward_mint, 100_000_000).await?;
+        assert!(transfer_amount > 100_000_000);
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_schedule_reward_emissions_too_short() -> Result<(), Box<dyn Error>> {
+        let ctx = RpcContext::new().await;
+        let whirlpool = setup_pool(&ctx).await?;
+        let reward_mint = setup_mint_with_decimals(&ctx, 6).await?;
+
+        let result = schedule_reward_emissions_instructions(
+            &ctx.rpc,
+            whirlpool,
+            0,
+            reward_mint,
+            100_000_000,
+            DAY - 1,
+            Some(ctx.signer.pubkey()),
+        )
+        .await;
+        assert!(result.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_set_reward_emissions_insufficient_vault() -> Result<(), Box<dyn Error>> {
+        let ctx = RpcContext::new().await;
+        let whirlpool = setup_pool(&ctx).await?;
+        let reward_mint = setup_mint_with_decimals(&ctx, 6).await?;
+        setup_ata_with_amount(&ctx, reward_mint, 1_000_000_000).await?;
+        schedule_and_verify(&ctx, whirlpool, reward_mint, 100_000_000).await?;
+
+        let result = set_reward_emissions_instructions(
+            &ctx.rpc,
+            whirlpool,
+            0,
+            (200_000_000u128 << 64) / DAY as u128,
+            Some(ctx.signer.pubkey()),
+        )
+        .await;
+        assert!(result.is_err());
+        Ok(())
+    }
+}
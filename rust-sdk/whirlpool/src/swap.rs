@@ -5,19 +5,27 @@ use std::{
 };
 
 use orca_whirlpools_client::{
-    get_oracle_address, get_tick_array_address, AccountsType, FixedTickArray, Oracle,
-    RemainingAccountsInfo, RemainingAccountsSlice, SwapV2, SwapV2InstructionArgs, Whirlpool,
+    get_oracle_address, get_referral_address, get_tick_array_address,
+    get_whirlpools_config_extension_address, AccountsType, Oracle, RemainingAccountsInfo,
+    RemainingAccountsSlice, SwapV2, SwapV2InstructionArgs, Whirlpool,
 };
 use orca_whirlpools_core::{
-    get_tick_array_start_tick_index, swap_quote_by_input_token, swap_quote_by_output_token,
-    ExactInSwapQuote, ExactOutSwapQuote, TickArrayFacade, TickFacade, TICK_ARRAY_SIZE,
+    get_swap_tick_array_start_tick_indexes, split_swap_by_tick_arrays, swap_quote_by_input_token,
+    swap_quote_by_output_token, try_apply_transfer_fee, try_reverse_apply_transfer_fee, CoreError,
+    ExactInSwapQuote, ExactOutSwapQuote, TickArrayFacade, TickArrays, TickFacade, TransferFee,
+    INVALID_TICK_ARRAY_SEQUENCE, MAX_SWAP_TICK_ARRAYS, TICK_ARRAY_SIZE,
 };
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::{
     instruction::AccountMeta, instruction::Instruction, pubkey::Pubkey, signature::Keypair,
 };
+use spl_associated_token_account::{
+    get_associated_token_address_with_program_id,
+    instruction::create_associated_token_account_idempotent,
+};
 
 use crate::{
+    tick_array::decode_tick_array,
     token::{get_current_transfer_fee, prepare_token_accounts_instructions, TokenAccountStrategy},
     FUNDER, SLIPPAGE_TOLERANCE_BPS,
 };
@@ -77,20 +85,8 @@ fn uninitialized_tick_array(start_tick_index: i32) -> TickArrayFacade {
 async fn fetch_tick_arrays_or_default(
     rpc: &RpcClient,
     whirlpool_address: Pubkey,
-    whirlpool: &Whirlpool,
-) -> Result<[(Pubkey, TickArrayFacade); 5], Box<dyn Error>> {
-    let tick_array_start_index =
-        get_tick_array_start_tick_index(whirlpool.tick_current_index, whirlpool.tick_spacing);
-    let offset = whirlpool.tick_spacing as i32 * TICK_ARRAY_SIZE as i32;
-
-    let tick_array_indexes = [
-        tick_array_start_index,
-        tick_array_start_index + offset,
-        tick_array_start_index + offset * 2,
-        tick_array_start_index - offset,
-        tick_array_start_index - offset * 2,
-    ];
-
+    tick_array_indexes: &[i32],
+) -> Result<Vec<(Pubkey, TickArrayFacade)>, Box<dyn Error>> {
     let tick_array_addresses: Vec<Pubkey> = tick_array_indexes
         .iter()
         .map(|&x| get_tick_array_address(&whirlpool_address, x).map(|y| y.0))
@@ -100,11 +96,7 @@ async fn fetch_tick_arrays_or_default(
 
     let maybe_tick_arrays: Vec<Option<TickArrayFacade>> = tick_array_infos
         .iter()
-        .map(|x| {
-            x.as_ref()
-                .and_then(|y| FixedTickArray::from_bytes(&y.data).ok())
-        })
-        .map(|x| x.map(|y| y.into()))
+        .map(|x| x.as_ref().and_then(|y| decode_tick_array(&y.data).ok()))
         .collect();
 
     let tick_arrays: Vec<TickArrayFacade> = maybe_tick_arrays
@@ -113,25 +105,286 @@ async fn fetch_tick_arrays_or_default(
         .map(|(i, x)| x.unwrap_or(uninitialized_tick_array(tick_array_indexes[i])))
         .collect::<Vec<TickArrayFacade>>();
 
-    let result: [(Pubkey, TickArrayFacade); 5] = zip(tick_array_addresses, tick_arrays)
-        .collect::<Vec<(Pubkey, TickArrayFacade)>>()
-        .try_into()
-        .map_err(|_| "Failed to convert tick arrays to array".to_string())?;
-
-    Ok(result)
+    Ok(zip(tick_array_addresses, tick_arrays).collect())
 }
 
 async fn fetch_oracle(
     rpc: &RpcClient,
     oracle_address: Pubkey,
-    whirlpool: &Whirlpool,
 ) -> Result<Option<Oracle>, Box<dyn Error>> {
-    // no need to fetch oracle for non-adaptive fee whirlpools
-    if whirlpool.tick_spacing == u16::from_le_bytes(whirlpool.fee_tier_index_seed) {
-        return Ok(None);
+    // whirlpools without adaptive fee only have an oracle if it holds a pause flag or a fee rate schedule
+    let oracle_infos = rpc.get_multiple_accounts(&[oracle_address]).await?;
+    let oracle = oracle_infos[0]
+        .as_ref()
+        .map(|x| Oracle::from_bytes(&x.data))
+        .transpose()?;
+    Ok(oracle)
+}
+
+struct SwapContext {
+    whirlpool: Whirlpool,
+    oracle_address: Pubkey,
+    oracle: Option<Oracle>,
+    token_program_a: Pubkey,
+    token_program_b: Pubkey,
+    transfer_fee_a: Option<TransferFee>,
+    transfer_fee_b: Option<TransferFee>,
+    timestamp: u64,
+}
+
+async fn fetch_swap_context(
+    rpc: &RpcClient,
+    whirlpool_address: Pubkey,
+) -> Result<SwapContext, Box<dyn Error>> {
+    let whirlpool_info = rpc.get_account(&whirlpool_address).await?;
+    let whirlpool = Whirlpool::from_bytes(&whirlpool_info.data)?;
+
+    let mint_infos = rpc
+        .get_multiple_accounts(&[whirlpool.token_mint_a, whirlpool.token_mint_b])
+        .await?;
+
+    let mint_a_info = mint_infos[0]
+        .as_ref()
+        .ok_or(format!("Mint a not found: {}", whirlpool.token_mint_a))?;
+
+    let mint_b_info = mint_infos[1]
+        .as_ref()
+        .ok_or(format!("Mint b not found: {}", whirlpool.token_mint_b))?;
+
+    let oracle_address = get_oracle_address(&whirlpool_address)?.0;
+    let oracle = fetch_oracle(rpc, oracle_address).await?;
+
+    let current_epoch = rpc.get_epoch_info().await?.epoch;
+    let transfer_fee_a = get_current_transfer_fee(Some(mint_a_info), current_epoch);
+    let transfer_fee_b = get_current_transfer_fee(Some(mint_b_info), current_epoch);
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    Ok(SwapContext {
+        whirlpool,
+        oracle_address,
+        oracle,
+        token_program_a: mint_a_info.owner,
+        token_program_b: mint_b_info.owner,
+        transfer_fee_a,
+        transfer_fee_b,
+        timestamp,
+    })
+}
+
+fn get_swap_quote(
+    context: &SwapContext,
+    tick_arrays: &[(Pubkey, TickArrayFacade)],
+    amount: u64,
+    specified_token_a: bool,
+    swap_type: &SwapType,
+    slippage_tolerance_bps: u16,
+) -> Result<SwapQuote, CoreError> {
+    let tick_arrays: TickArrays = tick_arrays.iter().map(|x| x.1).collect();
+    let oracle = context.oracle.clone().map(|oracle| oracle.into());
+    match swap_type {
+        SwapType::ExactIn => swap_quote_by_input_token(
+            amount,
+            specified_token_a,
+            slippage_tolerance_bps,
+            context.whirlpool.clone().into(),
+            oracle,
+            tick_arrays,
+            context.timestamp,
+            context.transfer_fee_a,
+            context.transfer_fee_b,
+        )
+        .map(SwapQuote::ExactIn),
+        SwapType::ExactOut => swap_quote_by_output_token(
+            amount,
+            specified_token_a,
+            slippage_tolerance_bps,
+            context.whirlpool.clone().into(),
+            oracle,
+            tick_arrays,
+            context.timestamp,
+            context.transfer_fee_a,
+            context.transfer_fee_b,
+        )
+        .map(SwapQuote::ExactOut),
+    }
+}
+
+/// Fetches the tick arrays a swap traverses, starting with the tick arrays a single swap
+/// instruction can use and fetching more on demand until the quote no longer runs out of them.
+async fn fetch_swap_tick_arrays(
+    rpc: &RpcClient,
+    whirlpool_address: Pubkey,
+    context: &SwapContext,
+    amount: u64,
+    specified_token_a: bool,
+    swap_type: &SwapType,
+    slippage_tolerance_bps: u16,
+) -> Result<(Vec<(Pubkey, TickArrayFacade)>, SwapQuote), Box<dyn Error>> {
+    let specified_input = *swap_type == SwapType::ExactIn;
+    let a_to_b = specified_token_a == specified_input;
+    let whirlpool = &context.whirlpool;
+
+    let mut tick_array_count = MAX_SWAP_TICK_ARRAYS;
+    let mut tick_array_indexes = get_swap_tick_array_start_tick_indexes(
+        whirlpool.tick_current_index,
+        whirlpool.tick_spacing,
+        a_to_b,
+        tick_array_count,
+    );
+    let mut tick_arrays =
+        fetch_tick_arrays_or_default(rpc, whirlpool_address, &tick_array_indexes).await?;
+
+    loop {
+        match get_swap_quote(
+            context,
+            &tick_arrays,
+            amount,
+            specified_token_a,
+            swap_type,
+            slippage_tolerance_bps,
+        ) {
+            Err(INVALID_TICK_ARRAY_SEQUENCE) => {
+                tick_array_count *= 2;
+                let next_tick_array_indexes: Vec<i32> = get_swap_tick_array_start_tick_indexes(
+                    whirlpool.tick_current_index,
+                    whirlpool.tick_spacing,
+                    a_to_b,
+                    tick_array_count,
+                )
+                .into_iter()
+                .filter(|x| !tick_array_indexes.contains(x))
+                .collect();
+                if next_tick_array_indexes.is_empty() {
+                    return Err(INVALID_TICK_ARRAY_SEQUENCE.into());
+                }
+                tick_arrays.extend(
+                    fetch_tick_arrays_or_default(rpc, whirlpool_address, &next_tick_array_indexes)
+                        .await?,
+                );
+                tick_array_indexes.extend(next_tick_array_indexes);
+            }
+            result => return Ok((tick_arrays, result?)),
+        }
+    }
+}
+
+fn swap_quote_tick_array_count(quote: &SwapQuote) -> u32 {
+    match quote {
+        SwapQuote::ExactIn(quote) => quote.tick_array_count,
+        SwapQuote::ExactOut(quote) => quote.tick_array_count,
+    }
+}
+
+fn split_swap_amounts_with_tick_arrays(
+    context: &SwapContext,
+    tick_arrays: &[(Pubkey, TickArrayFacade)],
+    amount: u64,
+    specified_token_a: bool,
+    swap_type: &SwapType,
+) -> Result<Vec<u64>, CoreError> {
+    let specified_input = *swap_type == SwapType::ExactIn;
+    let transfer_fee = if specified_token_a {
+        context.transfer_fee_a
+    } else {
+        context.transfer_fee_b
+    }
+    .unwrap_or_default();
+
+    // The split is computed on the amounts that reach the pool, the transfer fee of each
+    // swap is applied again to the individual amounts.
+    let amount = if specified_input {
+        try_apply_transfer_fee(amount, transfer_fee)?
+    } else {
+        try_reverse_apply_transfer_fee(amount, transfer_fee)?
+    };
+
+    let amounts = split_swap_by_tick_arrays(
+        amount,
+        specified_token_a,
+        specified_input,
+        context.whirlpool.clone().into(),
+        context.oracle.clone().map(|oracle| oracle.into()),
+        tick_arrays.iter().map(|x| x.1).collect(),
+        context.timestamp,
+        MAX_SWAP_TICK_ARRAYS,
+    )?;
+
+    amounts
+        .into_iter()
+        .map(|amount| {
+            if specified_input {
+                try_reverse_apply_transfer_fee(amount, transfer_fee)
+            } else {
+                try_apply_transfer_fee(amount, transfer_fee)
+            }
+        })
+        .collect()
+}
+
+/// Computes how to split a swap over multiple transactions.
+///
+/// A single swap instruction can traverse at most `MAX_SWAP_TICK_ARRAYS` tick arrays. Large swaps on
+/// pools with a narrow tick spacing can move the price further than that, in which case
+/// `swap_instructions` returns an error. This function fetches as many tick arrays as the swap
+/// needs and returns the amounts to pass to consecutive `swap_instructions` calls.
+///
+/// # Arguments
+///
+/// * `rpc` - A reference to the Solana RPC client for fetching accounts and interacting with the blockchain.
+/// * `whirlpool_address` - The public key of the Whirlpool against which the swap will be executed.
+/// * `amount` - The token amount specified for the whole swap.
+/// * `specified_mint` - The public key of the token mint being swapped.
+/// * `swap_type` - The type of swap (`SwapType::ExactIn` or `SwapType::ExactOut`).
+///
+/// # Returns
+///
+/// A `Result` containing the amount of `specified_mint` to specify in each consecutive swap. A swap
+/// that fits in a single transaction returns a single amount. Because the transfer fee is applied to
+/// each swap separately, the amounts may not add up exactly to `amount` for Token-2022 mints with a
+/// transfer fee.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The Whirlpool or token mint accounts are not found or have invalid data.
+/// - The pool does not hold enough liquidity for the swap.
+/// - Any RPC request to the blockchain fails.
+pub async fn split_swap_amounts(
+    rpc: &RpcClient,
+    whirlpool_address: Pubkey,
+    amount: u64,
+    specified_mint: Pubkey,
+    swap_type: SwapType,
+) -> Result<Vec<u64>, Box<dyn Error>> {
+    let context = fetch_swap_context(rpc, whirlpool_address).await?;
+    let specified_token_a = specified_mint == context.whirlpool.token_mint_a;
+
+    let (tick_arrays, quote) = fetch_swap_tick_arrays(
+        rpc,
+        whirlpool_address,
+        &context,
+        amount,
+        specified_token_a,
+        &swap_type,
+        0,
+    )
+    .await?;
+
+    if swap_quote_tick_array_count(&quote) <= MAX_SWAP_TICK_ARRAYS {
+        return Ok(vec![amount]);
     }
-    let oracle_info = rpc.get_account(&oracle_address).await?;
-    Ok(Some(Oracle::from_bytes(&oracle_info.data)?))
+
+    Ok(split_swap_amounts_with_tick_arrays(
+        &context,
+        &tick_arrays,
+        amount,
+        specified_token_a,
+        &swap_type,
+    )?)
 }
 
 /// Generates the instructions necessary to execute a token swap.
@@ -148,6 +401,9 @@ async fn fetch_oracle(
 /// * `specified_mint` - The public key of the token mint being swapped.
 /// * `swap_type` - The type of swap (`SwapType::ExactIn` or `SwapType::ExactOut`).
 /// * `slippage_tolerance_bps` - An optional slippage tolerance, in basis points (BPS). Defaults to the global setting if not provided.
+/// * `referrer` - An optional public key of a referrer with an initialized referral account for the
+///                Whirlpool's config. The referrer receives its share of the protocol fee in the input
+///                token, and its token account is created if needed.
 /// * `signer` - An optional public key of the wallet or account executing the swap. Defaults to the global funder if not provided.
 ///
 /// # Returns
@@ -162,6 +418,8 @@ async fn fetch_oracle(
 /// Returns an error if:
 /// - The signer is invalid or missing.
 /// - The Whirlpool or token mint accounts are not found or have invalid data.
+/// - The swap traverses more tick arrays than a single swap instruction supports. The error
+///   describes how to split the swap, see also `split_swap_amounts`.
 /// - Any RPC request to the blockchain fails.
 ///
 /// # Example
@@ -192,6 +450,7 @@ async fn fetch_oracle(
 ///         mint_address,
 ///         SwapType::ExactIn,
 ///         Some(100),
+///         None,
 ///         Some(wallet.pubkey()),
 ///     )
 ///     .await
@@ -208,6 +467,7 @@ pub async fn swap_instructions(
     specified_mint: Pubkey,
     swap_type: SwapType,
     slippage_tolerance_bps: Option<u16>,
+    referrer: Option<Pubkey>,
     signer: Option<Pubkey>,
 ) -> Result<SwapInstructions, Box<dyn Error>> {
     let slippage_tolerance_bps =
@@ -217,67 +477,45 @@ pub async fn swap_instructions(
         return Err("Signer must be provided".into());
     }
 
-    let whirlpool_info = rpc.get_account(&whirlpool_address).await?;
-    let whirlpool = Whirlpool::from_bytes(&whirlpool_info.data)?;
+    let context = fetch_swap_context(rpc, whirlpool_address).await?;
+    let whirlpool = &context.whirlpool;
     let specified_input = swap_type == SwapType::ExactIn;
     let specified_token_a = specified_mint == whirlpool.token_mint_a;
     let a_to_b = specified_token_a == specified_input;
 
-    let tick_arrays = fetch_tick_arrays_or_default(rpc, whirlpool_address, &whirlpool).await?;
-
-    let mint_infos = rpc
-        .get_multiple_accounts(&[whirlpool.token_mint_a, whirlpool.token_mint_b])
-        .await?;
-
-    let mint_a_info = mint_infos[0]
-        .as_ref()
-        .ok_or(format!("Mint a not found: {}", whirlpool.token_mint_a))?;
-
-    let mint_b_info = mint_infos[1]
-        .as_ref()
-        .ok_or(format!("Mint b not found: {}", whirlpool.token_mint_b))?;
-
-    let oracle_address = get_oracle_address(&whirlpool_address)?.0;
-    let oracle = fetch_oracle(rpc, oracle_address, &whirlpool).await?;
-
-    let current_epoch = rpc.get_epoch_info().await?.epoch;
-    let transfer_fee_a = get_current_transfer_fee(Some(mint_a_info), current_epoch);
-    let transfer_fee_b = get_current_transfer_fee(Some(mint_b_info), current_epoch);
+    let (tick_arrays, quote) = fetch_swap_tick_arrays(
+        rpc,
+        whirlpool_address,
+        &context,
+        amount,
+        specified_token_a,
+        &swap_type,
+        slippage_tolerance_bps,
+    )
+    .await?;
+
+    let tick_array_count = swap_quote_tick_array_count(&quote);
+    if tick_array_count > MAX_SWAP_TICK_ARRAYS {
+        let amounts = split_swap_amounts_with_tick_arrays(
+            &context,
+            &tick_arrays,
+            amount,
+            specified_token_a,
+            &swap_type,
+        )?;
+        return Err(format!(
+            "Swap traverses {} tick arrays but a single swap can traverse at most {}. Split it into swaps of {:?} (see split_swap_amounts)",
+            tick_array_count, MAX_SWAP_TICK_ARRAYS, amounts
+        )
+        .into());
+    }
 
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_secs();
-    let trade_enable_timestamp = oracle
+    let trade_enable_timestamp = context
+        .oracle
         .as_ref()
         .map(|x| x.trade_enable_timestamp)
         .unwrap_or(0);
 
-    let quote = match swap_type {
-        SwapType::ExactIn => SwapQuote::ExactIn(swap_quote_by_input_token(
-            amount,
-            specified_token_a,
-            slippage_tolerance_bps,
-            whirlpool.clone().into(),
-            oracle.map(|oracle| oracle.into()),
-            tick_arrays.map(|x| x.1).into(),
-            timestamp,
-            transfer_fee_a,
-            transfer_fee_b,
-        )?),
-        SwapType::ExactOut => SwapQuote::ExactOut(swap_quote_by_output_token(
-            amount,
-            specified_token_a,
-            slippage_tolerance_bps,
-            whirlpool.clone().into(),
-            oracle.map(|oracle| oracle.into()),
-            tick_arrays.map(|x| x.1).into(),
-            timestamp,
-            transfer_fee_a,
-            transfer_fee_b,
-        )?),
-    };
-
     let max_in_amount = match quote {
         SwapQuote::ExactIn(quote) => quote.token_in,
         SwapQuote::ExactOut(quote) => quote.token_max_in,
@@ -314,9 +552,91 @@ pub async fn swap_instructions(
         .get(&whirlpool.token_mint_b)
         .ok_or("Token B owner account not found")?;
 
+    // The swap instruction gets the first three tick arrays in the swap direction, the rest of
+    // them are passed as supplemental tick arrays. The remaining supplemental slots hold up to two
+    // tick arrays in the opposite direction as a fallback in case the price moves before the swap
+    // lands.
+    let swap_tick_arrays: Vec<Pubkey> = get_swap_tick_array_start_tick_indexes(
+        whirlpool.tick_current_index,
+        whirlpool.tick_spacing,
+        a_to_b,
+        MAX_SWAP_TICK_ARRAYS,
+    )
+    .iter()
+    .map(|&x| get_tick_array_address(&whirlpool_address, x).map(|y| y.0))
+    .collect::<Result<Vec<Pubkey>, _>>()?;
+    let (static_tick_arrays, extra_tick_arrays) =
+        swap_tick_arrays.split_at(swap_tick_arrays.len().min(3));
+    let fallback_tick_arrays: Vec<Pubkey> = get_swap_tick_array_start_tick_indexes(
+        whirlpool.tick_current_index,
+        whirlpool.tick_spacing,
+        !a_to_b,
+        MAX_SWAP_TICK_ARRAYS,
+    )
+    .iter()
+    .map(|&x| get_tick_array_address(&whirlpool_address, x).map(|y| y.0))
+    .collect::<Result<Vec<Pubkey>, _>>()?
+    .into_iter()
+    .filter(|x| !swap_tick_arrays.contains(x))
+    .take(2.min(3 - extra_tick_arrays.len()))
+    .collect();
+    let supplemental_tick_arrays: Vec<Pubkey> = extra_tick_arrays
+        .iter()
+        .copied()
+        .chain(fallback_tick_arrays)
+        .collect();
+    // Near the edge of the tick range there can be fewer tick arrays than the swap instruction
+    // expects. Duplicate accounts are ignored by the program.
+    let tick_array_at = |i: usize| static_tick_arrays[i.min(static_tick_arrays.len() - 1)];
+
+    let mut remaining_accounts_slices = vec![RemainingAccountsSlice {
+        accounts_type: AccountsType::SupplementalTickArrays,
+        length: supplemental_tick_arrays.len() as u8,
+    }];
+    let mut remaining_accounts: Vec<AccountMeta> = supplemental_tick_arrays
+        .iter()
+        .map(|&x| AccountMeta::new(x, false))
+        .collect();
+
+    // The referrer receives its share of the protocol fee in the input token.
+    if let Some(referrer) = referrer {
+        let (input_mint, input_token_program) = if a_to_b {
+            (whirlpool.token_mint_a, context.token_program_a)
+        } else {
+            (whirlpool.token_mint_b, context.token_program_b)
+        };
+        let referral_token_account = get_associated_token_address_with_program_id(
+            &referrer,
+            &input_mint,
+            &input_token_program,
+        );
+        instructions.push(create_associated_token_account_idempotent(
+            &signer,
+            &referrer,
+            &input_mint,
+            &input_token_program,
+        ));
+
+        remaining_accounts_slices.push(RemainingAccountsSlice {
+            accounts_type: AccountsType::Referral,
+            length: 3,
+        });
+        remaining_accounts.extend([
+            AccountMeta::new_readonly(
+                get_whirlpools_config_extension_address(&whirlpool.whirlpools_config)?.0,
+                false,
+            ),
+            AccountMeta::new_readonly(
+                get_referral_address(&whirlpool.whirlpools_config, &referrer)?.0,
+                false,
+            ),
+            AccountMeta::new(referral_token_account, false),
+        ]);
+    }
+
     let swap_instruction = SwapV2 {
-        token_program_a: mint_a_info.owner,
-        token_program_b: mint_b_info.owner,
+        token_program_a: context.token_program_a,
+        token_program_b: context.token_program_b,
         memo_program: spl_memo::ID,
         token_authority: signer,
         whirlpool: whirlpool_address,
@@ -326,10 +646,10 @@ pub async fn swap_instructions(
         token_vault_a: whirlpool.token_vault_a,
         token_owner_account_b: *token_owner_account_b,
         token_vault_b: whirlpool.token_vault_b,
-        tick_array0: tick_arrays[0].0,
-        tick_array1: tick_arrays[1].0,
-        tick_array2: tick_arrays[2].0,
-        oracle: oracle_address,
+        tick_array0: tick_array_at(0),
+        tick_array1: tick_array_at(1),
+        tick_array2: tick_array_at(2),
+        oracle: context.oracle_address,
     }
     .instruction_with_remaining_accounts(
         SwapV2InstructionArgs {
@@ -339,16 +659,10 @@ pub async fn swap_instructions(
             amount_specified_is_input: specified_input,
             a_to_b,
             remaining_accounts_info: Some(RemainingAccountsInfo {
-                slices: vec![RemainingAccountsSlice {
-                    accounts_type: AccountsType::SupplementalTickArrays,
-                    length: 2,
-                }],
+                slices: remaining_accounts_slices,
             }),
         },
-        &[
-            AccountMeta::new(tick_arrays[3].0, false),
-            AccountMeta::new(tick_arrays[4].0, false),
-        ],
+        &remaining_accounts,
     );
 
     instructions.push(swap_instruction);
@@ -625,6 +939,7 @@ mod tests {
                 token_for_this_call,
                 swap_type.clone(),
                 Some(100), // slippage
+                None,
                 Some(ctx.signer.pubkey()),
             )
             .await
@@ -3,7 +3,8 @@ use std::{error::Error, str::FromStr};
 
 use async_trait::async_trait;
 use orca_whirlpools_client::{
-    get_fee_tier_address, FEE_TIER_DISCRIMINATOR, WHIRLPOOLS_CONFIG_DISCRIMINATOR, WHIRLPOOL_ID,
+    get_fee_tier_address, ADAPTIVE_FEE_TIER_DISCRIMINATOR, FEE_TIER_DISCRIMINATOR,
+    WHIRLPOOLS_CONFIG_DISCRIMINATOR, WHIRLPOOL_ID,
 };
 use serde_json::{from_value, to_value, Value};
 use solana_account_decoder::{encode_ui_account, UiAccountEncoding};
@@ -37,6 +38,37 @@ use spl_memo::build_memo;
 use crate::tests::anchor_programs;
 use crate::{SPLASH_POOL_TICK_SPACING, WHIRLPOOLS_CONFIG_ADDRESS};
 
+/// Fee tier index of a permission-less adaptive fee tier with tick spacing 64.
+pub const ADAPTIVE_FEE_TIER_INDEX: u16 = 1024 + 64;
+
+/// Fee tier index of an adaptive fee tier with tick spacing 64 that only the test signer can use.
+pub const PERMISSIONED_ADAPTIVE_FEE_TIER_INDEX: u16 = 2048 + 64;
+
+fn adaptive_fee_tier_data(
+    config: Pubkey,
+    fee_tier_index: u16,
+    initialize_pool_authority: Pubkey,
+) -> Vec<u8> {
+    [
+        ADAPTIVE_FEE_TIER_DISCRIMINATOR,
+        &config.to_bytes(),
+        &fee_tier_index.to_le_bytes(),
+        &64u16.to_le_bytes(), // tick_spacing
+        &initialize_pool_authority.to_bytes(),
+        &Pubkey::default().to_bytes(), // delegated_fee_authority
+        &3000u16.to_le_bytes(),        // default_base_fee_rate
+        &30u16.to_le_bytes(),          // filter_period
+        &600u16.to_le_bytes(),         // decay_period
+        &500u16.to_le_bytes(),         // reduction_factor
+        &4_000u32.to_le_bytes(),       // adaptive_fee_control_factor
+        &350_000u32.to_le_bytes(),     // max_volatility_accumulator
+        &64u16.to_le_bytes(),          // tick_group_size
+        &64u16.to_le_bytes(),          // major_swap_threshold_ticks
+        &[0; 128],
+    ]
+    .concat()
+}
+
 lazy_static::lazy_static! {
     static ref PROGRAMS: Vec<(String, Pubkey)> = anchor_programs("../..").unwrap();
 }
@@ -140,6 +172,22 @@ impl RpcContext {
             },
         );
 
+        for (fee_tier_index, initialize_pool_authority) in [
+            (ADAPTIVE_FEE_TIER_INDEX, Pubkey::default()),
+            (PERMISSIONED_ADAPTIVE_FEE_TIER_INDEX, signer.pubkey()),
+        ] {
+            test.add_account(
+                get_fee_tier_address(&config, fee_tier_index).unwrap().0,
+                Account {
+                    lamports: 100_000_000_000,
+                    data: adaptive_fee_tier_data(config, fee_tier_index, initialize_pool_authority),
+                    owner: WHIRLPOOL_ID,
+                    executable: false,
+                    rent_epoch: 0,
+                },
+            );
+        }
+
         for (name, pubkey) in PROGRAMS.iter() {
             test.add_program(name, *pubkey, None);
         }
@@ -0,0 +1,101 @@
+use std::error::Error;
+
+use orca_whirlpools_client::{
+    get_tick_array_address, DynamicTickArray, FixedTickArray, InitializeDynamicTickArray,
+    InitializeDynamicTickArrayInstructionArgs, InitializeTickArray,
+    InitializeTickArrayInstructionArgs, TickArray,
+};
+use orca_whirlpools_core::TickArrayFacade;
+use solana_program::rent::Rent;
+use solana_program::system_program;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+use crate::{TickArrayKind, TICK_ARRAY_KIND};
+
+/// Decodes a fixed or dynamic tick array account based on its discriminator.
+pub(crate) fn decode_tick_array(data: &[u8]) -> Result<TickArrayFacade, Box<dyn Error>> {
+    Ok(TickArray::from_bytes(data)?.into())
+}
+
+/// Builds the instruction to initialize a tick array of the currently selected kind.
+///
+/// Returns the instruction together with the rent exemption cost of the new account.
+pub(crate) fn initialize_tick_array_instruction(
+    whirlpool: Pubkey,
+    start_tick_index: i32,
+    funder: Pubkey,
+    rent: &Rent,
+) -> Result<(Instruction, u64), Box<dyn Error>> {
+    let tick_array = get_tick_array_address(&whirlpool, start_tick_index)?.0;
+    let kind = resolve_tick_array_kind(*TICK_ARRAY_KIND.try_lock()?, rent);
+
+    let result = match kind {
+        TickArrayKind::Fixed => (
+            InitializeTickArray {
+                whirlpool,
+                funder,
+                tick_array,
+                system_program: system_program::id(),
+            }
+            .instruction(InitializeTickArrayInstructionArgs { start_tick_index }),
+            rent.minimum_balance(FixedTickArray::LEN),
+        ),
+        _ => (
+            InitializeDynamicTickArray {
+                whirlpool,
+                funder,
+                tick_array,
+                system_program: system_program::id(),
+            }
+            .instruction(InitializeDynamicTickArrayInstructionArgs {
+                start_tick_index,
+                idempotent: false,
+            }),
+            rent.minimum_balance(DynamicTickArray::MIN_LEN),
+        ),
+    };
+    Ok(result)
+}
+
+fn resolve_tick_array_kind(kind: TickArrayKind, rent: &Rent) -> TickArrayKind {
+    match kind {
+        TickArrayKind::MinimumRent => {
+            if rent.minimum_balance(DynamicTickArray::MIN_LEN)
+                <= rent.minimum_balance(FixedTickArray::LEN)
+            {
+                TickArrayKind::Dynamic
+            } else {
+                TickArrayKind::Fixed
+            }
+        }
+        kind => kind,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_tick_array_kind() {
+        let rent = Rent::default();
+        assert_eq!(
+            resolve_tick_array_kind(TickArrayKind::Fixed, &rent),
+            TickArrayKind::Fixed
+        );
+        assert_eq!(
+            resolve_tick_array_kind(TickArrayKind::Dynamic, &rent),
+            TickArrayKind::Dynamic
+        );
+        assert_eq!(
+            resolve_tick_array_kind(TickArrayKind::MinimumRent, &rent),
+            TickArrayKind::Dynamic
+        );
+    }
+
+    #[test]
+    fn test_decode_invalid_tick_array() {
+        assert!(decode_tick_array(&[0; 8]).is_err());
+        assert!(decode_tick_array(&[]).is_err());
+    }
+}
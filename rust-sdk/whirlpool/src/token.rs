@@ -1,3 +1,6 @@
+use orca_whirlpools_client::{
+    check_token_mint_compatibility, is_token_badge_initialized, TokenMintCheck, TokenMintSupport,
+};
 use orca_whirlpools_core::TransferFee;
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::account::Account as SolanaAccount;
@@ -21,6 +24,7 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use std::{collections::HashMap, error::Error};
 
 use crate::{
+    confidential::has_confidential_funds, utils::batch_get_multiple_accounts,
     NativeMintWrappingStrategy, ENFORCE_TOKEN_BALANCE_CHECK, NATIVE_MINT_WRAPPING_STRATEGY,
 };
 
@@ -56,7 +60,7 @@ pub(crate) async fn prepare_token_accounts_instructions(
         .position(|&x| x == spl_token::native_mint::ID);
     let has_native_mint = native_mint_index.is_some();
 
-    let maybe_mint_account_infos = rpc.get_multiple_accounts(&mint_addresses).await?;
+    let maybe_mint_account_infos = batch_get_multiple_accounts(rpc, &mint_addresses, None).await?;
     let mint_account_infos: Vec<&SolanaAccount> = maybe_mint_account_infos
         .iter()
         .map(|x| x.as_ref().ok_or(ProgramError::UninitializedAccount))
@@ -70,7 +74,7 @@ pub(crate) async fn prepare_token_accounts_instructions(
         })
         .collect();
 
-    let ata_account_infos = rpc.get_multiple_accounts(&ata_addresses).await?;
+    let ata_account_infos = batch_get_multiple_accounts(rpc, &ata_addresses, None).await?;
 
     let mut token_account_addresses: HashMap<Pubkey, Pubkey> = HashMap::new();
     let mut create_instructions: Vec<Instruction> = Vec::new();
@@ -129,6 +133,16 @@ pub(crate) async fn prepare_token_accounts_instructions(
 
         let enforce_balance_check = *ENFORCE_TOKEN_BALANCE_CHECK.try_lock()?;
         if enforce_balance_check && existing_balance < required_balance {
+            if ata_account_infos[i]
+                .as_ref()
+                .is_some_and(has_confidential_funds)
+            {
+                return Err(format!(
+                    "Insufficient balance for mint {}, the tokens may be in the confidential balance (see `withdraw_confidential_balance_instructions`)",
+                    mint_addresses[i]
+                )
+                .into());
+            }
             return Err(format!("Insufficient balance for mint {}", mint_addresses[i]).into());
         }
     }
@@ -293,6 +307,46 @@ pub(crate) fn get_current_transfer_fee(
     None
 }
 
+/// Fails with the reasons of the Whirlpool program if it would reject the mint in a pool.
+pub(crate) fn verify_token_mint_compatibility(
+    whirlpools_config: &Pubkey,
+    token_mint: &Pubkey,
+    mint_account_info: &SolanaAccount,
+    token_badge_account_info: Option<&SolanaAccount>,
+) -> Result<(), Box<dyn Error>> {
+    let compatibility = check_token_mint_compatibility(
+        token_mint,
+        &mint_account_info.owner,
+        &mint_account_info.data,
+    )?;
+    let token_badge_initialized = token_badge_account_info.is_some_and(|x| {
+        is_token_badge_initialized(whirlpools_config, token_mint, &x.owner, &x.data)
+    });
+    let blocking_checks: Vec<&TokenMintCheck> = compatibility
+        .blocking_checks(token_badge_initialized)
+        .collect();
+    if blocking_checks.is_empty() {
+        return Ok(());
+    }
+
+    let reasons: Vec<&str> = blocking_checks.iter().map(|x| x.reason).collect();
+    let hint = if blocking_checks
+        .iter()
+        .all(|x| x.support == TokenMintSupport::RequiresTokenBadge)
+    {
+        " (supported once a token badge is initialized for the mint)"
+    } else {
+        ""
+    };
+    Err(format!(
+        "Token mint {} is not supported: {}{}",
+        token_mint,
+        reasons.join("; "),
+        hint
+    )
+    .into())
+}
+
 /// Orders two mint addresses by their canonical byte order.
 ///
 /// This function compares two Solana `Pubkey` values and returns an array where the first element
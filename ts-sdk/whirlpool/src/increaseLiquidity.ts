@@ -1,11 +1,16 @@
-import type { Whirlpool } from "@orca-so/whirlpools-client";
+import type {
+  IncreaseLiquidityV2Input,
+  Whirlpool,
+} from "@orca-so/whirlpools-client";
 import {
+  AccountsType,
   fetchAllMaybeTickArray,
   fetchPosition,
   fetchWhirlpool,
   getIncreaseLiquidityV2Instruction,
   getInitializeDynamicTickArrayInstruction,
   getOpenPositionWithTokenExtensionsInstruction,
+  getOracleAddress,
   getPositionAddress,
   getTickArrayAddress,
   getDynamicTickArrayMinSize,
@@ -38,7 +43,12 @@ import type {
   Rpc,
   TransactionSigner,
 } from "@solana/kit";
-import { address, generateKeyPairSigner, lamports } from "@solana/kit";
+import {
+  AccountRole,
+  address,
+  generateKeyPairSigner,
+  lamports,
+} from "@solana/kit";
 import { fetchSysvarRent } from "@solana/sysvars";
 import {
   DEFAULT_ADDRESS,
@@ -139,6 +149,26 @@ function getIncreaseLiquidityQuote(
   }
 }
 
+// The oracle of the pool is passed so that the program can enforce the pause flags of the pool.
+async function getIncreaseLiquidityV2InstructionWithOracle(
+  input: Omit<IncreaseLiquidityV2Input, "remainingAccountsInfo">,
+): Promise<IInstruction> {
+  const oracleAddress = await getOracleAddress(input.whirlpool).then(
+    (x) => x[0],
+  );
+  const instruction = getIncreaseLiquidityV2Instruction({
+    ...input,
+    remainingAccountsInfo: {
+      slices: [{ accountsType: AccountsType.Oracle, length: 1 }],
+    },
+  });
+  instruction.accounts.push({
+    address: oracleAddress,
+    role: AccountRole.READONLY,
+  });
+  return instruction;
+}
+
 /**
  * Generates instructions to increase liquidity for an existing position.
  *
@@ -245,7 +275,7 @@ export async function increaseLiquidityInstructions(
   // Since position exists tick arrays must also already exist
 
   instructions.push(
-    getIncreaseLiquidityV2Instruction({
+    await getIncreaseLiquidityV2InstructionWithOracle({
       whirlpool: whirlpool.address,
       positionAuthority: authority,
       position: position.address,
@@ -264,7 +294,6 @@ export async function increaseLiquidityInstructions(
       tokenMaxA: quote.tokenMaxA,
       tokenMaxB: quote.tokenMaxB,
       memoProgram: MEMO_PROGRAM_ADDRESS,
-      remainingAccountsInfo: null,
     }),
   );
 
@@ -436,7 +465,7 @@ async function internalOpenPositionInstructions(
   );
 
   instructions.push(
-    getIncreaseLiquidityV2Instruction({
+    await getIncreaseLiquidityV2InstructionWithOracle({
       whirlpool: whirlpool.address,
       positionAuthority: funder,
       position: positionAddress[0],
@@ -455,7 +484,6 @@ async function internalOpenPositionInstructions(
       tokenMaxA: quote.tokenMaxA,
       tokenMaxB: quote.tokenMaxB,
       memoProgram: MEMO_PROGRAM_ADDRESS,
-      remainingAccountsInfo: null,
     }),
   );
 